@@ -2,7 +2,7 @@ use crate::agent::dispatcher::{
     NativeToolDispatcher, ParsedToolCall, ToolDispatcher, ToolExecutionResult, XmlToolDispatcher,
 };
 use crate::agent::memory_loader::{DefaultMemoryLoader, MemoryLoader};
-use crate::agent::prompt::{PromptContext, SystemPromptBuilder};
+use crate::agent::prompt::{PromptContext, ReadOnlySection, SystemPromptBuilder};
 use crate::config::Config;
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::observability::{self, Observer, ObserverEvent};
@@ -302,6 +302,11 @@ impl Agent {
         let available_hints: Vec<String> =
             config.model_routes.iter().map(|r| r.hint.clone()).collect();
 
+        let mut prompt_builder = SystemPromptBuilder::with_defaults();
+        if config.autonomy.level == crate::security::AutonomyLevel::ReadOnly {
+            prompt_builder = prompt_builder.add_section(Box::new(ReadOnlySection));
+        }
+
         Agent::builder()
             .provider(provider)
             .tools(tools)
@@ -312,7 +317,7 @@ impl Agent {
                 5,
                 config.memory.min_relevance_score,
             )))
-            .prompt_builder(SystemPromptBuilder::with_defaults())
+            .prompt_builder(prompt_builder)
             .config(config.agent.clone())
             .model_name(model_name)
             .temperature(config.default_temperature)
@@ -369,6 +369,13 @@ fn tool_call_signature(name: &str, arguments: &serde_json::Value) -> (String, St
     (name.trim().to_ascii_lowercase(), args_json)
 }
 
+/// SHA-256 of a call's canonical JSON arguments, for tool provenance.
+fn tool_args_hash(arguments: &serde_json::Value) -> String {
+    use sha2::{Digest, Sha256};
+    let (_, args_json) = tool_call_signature("", arguments);
+    hex::encode(Sha256::digest(args_json.as_bytes()))
+}
+
 fn parse_tool_call_value(value: &serde_json::Value) -> Option<ParsedToolCall> {
     if let Some(function) = value.get("function") {
         let tool_call_id = parse_tool_call_id(value, Some(function));
@@ -1782,6 +1789,8 @@ pub(crate) async fn agent_turn(
     silent: bool,
     multimodal_config: &crate::config::MultimodalConfig,
     max_tool_iterations: usize,
+    on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+    cancellation_token: Option<CancellationToken>,
 ) -> Result<String> {
     run_tool_call_loop(
         provider,
@@ -1796,8 +1805,8 @@ pub(crate) async fn agent_turn(
         "channel",
         multimodal_config,
         max_tool_iterations,
-        None,
-        None,
+        cancellation_token,
+        on_delta,
         None,
         &[],
     )
@@ -1892,8 +1901,46 @@ struct ToolExecutionOutcome {
     duration: Duration,
 }
 
+/// Classify why a tool result is missing or partial, for
+/// [`ObserverEvent::ToolDegraded`].
+pub(crate) fn degradation_kind(reason: &str) -> &'static str {
+    let lower = reason.to_ascii_lowercase();
+    if lower.contains("rate limit") || lower.contains("budget exhausted") {
+        "rate_limited"
+    } else if lower.contains("timed out") || lower.contains("timeout") {
+        "timeout"
+    } else if lower.contains("circuit") {
+        "circuit_open"
+    } else if lower.starts_with("denied") || lower.contains("blocked") {
+        "denied"
+    } else if lower.starts_with("skipped") || lower.starts_with("cancelled") {
+        "skipped"
+    } else if lower.starts_with("unknown tool") {
+        "unknown_tool"
+    } else {
+        "failed"
+    }
+}
+
+/// Whether calling `name` with `args` needs a prompt, counting the tools it
+/// would run on the caller's behalf (plan and composite steps).
+fn call_needs_approval(
+    mgr: &ApprovalManager,
+    tools_registry: &[Box<dyn Tool>],
+    name: &str,
+    args: &serde_json::Value,
+) -> bool {
+    mgr.needs_approval(name)
+        || find_tool(tools_registry, name).is_some_and(|tool| {
+            tool.nested_tools(args)
+                .iter()
+                .any(|nested| mgr.needs_approval(nested))
+        })
+}
+
 fn should_execute_tools_in_parallel(
     tool_calls: &[ParsedToolCall],
+    tools_registry: &[Box<dyn Tool>],
     approval: Option<&ApprovalManager>,
 ) -> bool {
     if tool_calls.len() <= 1 {
@@ -1901,7 +1948,10 @@ fn should_execute_tools_in_parallel(
     }
 
     if let Some(mgr) = approval {
-        if tool_calls.iter().any(|call| mgr.needs_approval(&call.name)) {
+        if tool_calls
+            .iter()
+            .any(|call| call_needs_approval(mgr, tools_registry, &call.name, &call.arguments))
+        {
             // Approval-gated calls must keep sequential handling so the caller can
             // enforce CLI prompt/deny policy consistently.
             return false;
@@ -2006,6 +2056,21 @@ pub(crate) async fn run_tool_call_loop(
     let turn_id = Uuid::new_v4().to_string();
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
 
+    if let Some(message) = history.iter().rev().find(|m| m.role == "user") {
+        runtime_trace::record_event(
+            "turn_user_message",
+            Some(channel_name),
+            Some(provider_name),
+            Some(model),
+            Some(&turn_id),
+            None,
+            None,
+            serde_json::json!({
+                "text": truncate_with_ellipsis(&scrub_credentials(&message.content), 2000),
+            }),
+        );
+    }
+
     for iteration in 0..max_iterations {
         if cancellation_token
             .as_ref()
@@ -2294,7 +2359,8 @@ pub(crate) async fn run_tool_call_loop(
         let mut individual_results: Vec<(Option<String>, String)> = Vec::new();
         let mut ordered_results: Vec<Option<(String, Option<String>, ToolExecutionOutcome)>> =
             (0..tool_calls.len()).map(|_| None).collect();
-        let allow_parallel_execution = should_execute_tools_in_parallel(&tool_calls, approval);
+        let allow_parallel_execution =
+            should_execute_tools_in_parallel(&tool_calls, tools_registry, approval);
         let mut executable_indices: Vec<usize> = Vec::new();
         let mut executable_calls: Vec<ParsedToolCall> = Vec::new();
 
@@ -2345,7 +2411,7 @@ pub(crate) async fn run_tool_call_loop(
 
             // ── Approval hook ────────────────────────────────
             if let Some(mgr) = approval {
-                if mgr.needs_approval(&tool_name) {
+                if call_needs_approval(mgr, tools_registry, &tool_name, &tool_args) {
                     let request = ApprovalRequest {
                         tool_name: tool_name.clone(),
                         arguments: tool_args.clone(),
@@ -2497,6 +2563,13 @@ pub(crate) async fn run_tool_call_loop(
                     "output": scrub_credentials(&outcome.output),
                 }),
             );
+            if outcome.success {
+                observer.record_event(&ObserverEvent::ToolEvidence {
+                    tool: call.name.clone(),
+                    args_hash: tool_args_hash(&call.arguments),
+                    output: outcome.output.clone(),
+                });
+            }
 
             // ── Hook: after_tool_call (void) ─────────────────
             if let Some(hooks) = hooks {
@@ -2525,15 +2598,24 @@ pub(crate) async fn run_tool_call_loop(
             ordered_results[*idx] = Some((call.name.clone(), call.tool_call_id.clone(), outcome));
         }
 
-        for entry in ordered_results {
-            if let Some((tool_name, tool_call_id, outcome)) = entry {
-                individual_results.push((tool_call_id, outcome.output.clone()));
-                let _ = writeln!(
-                    tool_results,
-                    "<tool_result name=\"{}\">\n{}\n</tool_result>",
-                    tool_name, outcome.output
-                );
+        for (tool_name, tool_call_id, outcome) in ordered_results.into_iter().flatten() {
+            if !outcome.success {
+                let reason = outcome
+                    .error_reason
+                    .clone()
+                    .unwrap_or_else(|| outcome.output.clone());
+                observer.record_event(&ObserverEvent::ToolDegraded {
+                    tool: tool_name.clone(),
+                    kind: degradation_kind(&reason).to_string(),
+                    reason: truncate_with_ellipsis(&reason, 200),
+                });
             }
+            individual_results.push((tool_call_id, outcome.output.clone()));
+            let _ = writeln!(
+                tool_results,
+                "<tool_result name=\"{}\">\n{}\n</tool_result>",
+                tool_name, outcome.output
+            );
         }
 
         // Add assistant message with tool calls + tool results to history.
@@ -2616,13 +2698,27 @@ pub(crate) fn build_tool_instructions(tools_registry: &[Box<dyn Tool>]) -> Strin
     instructions
 }
 
+/// Keep only the tools named in `allowed`, warning about names that match
+/// nothing so a typo in a job's allowlist does not go unnoticed.
+pub(crate) fn scope_tools(tools: Vec<Box<dyn Tool>>, allowed: &[String]) -> Vec<Box<dyn Tool>> {
+    for name in allowed {
+        if !tools.iter().any(|tool| tool.name() == name) {
+            tracing::warn!(tool = %name, "Allowlisted tool is not available");
+        }
+    }
+    tools
+        .into_iter()
+        .filter(|tool| allowed.iter().any(|name| name == tool.name()))
+        .collect()
+}
+
 // ── CLI Entrypoint ───────────────────────────────────────────────────────
 // Wires up all subsystems (observer, runtime, security, memory, tools,
 // provider, hardware RAG, peripherals) and enters either single-shot or
 // interactive REPL mode. The interactive loop manages history compaction
 // and hard trimming to keep the context window bounded.
 
-#[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 pub async fn run(
     config: Config,
     message: Option<String>,
@@ -2631,10 +2727,11 @@ pub async fn run(
     temperature: f64,
     peripheral_overrides: Vec<String>,
     interactive: bool,
+    allowed_tools: Option<Vec<String>>,
 ) -> Result<String> {
     // ── Wire up agnostic subsystems ──────────────────────────────
-    let base_observer = observability::create_observer(&config.observability);
-    let observer: Arc<dyn Observer> = Arc::from(base_observer);
+    let cost_tracker = create_cost_tracker(&config);
+    let observer = create_cost_aware_observer(&config, cost_tracker.as_ref(), None);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -2668,7 +2765,9 @@ pub async fn run(
     } else {
         (None, None)
     };
-    let mut tools_registry = tools::all_tools_with_runtime(
+    // Scheduled runs may be limited to an explicit tool allowlist; the
+    // registry applies it before plan and composites capture their tools.
+    let mut tools_registry = tools::tool_registry_with_runtime(
         Arc::new(config.clone()),
         &security,
         runtime,
@@ -2681,7 +2780,9 @@ pub async fn run(
         &config.agents,
         config.api_key.as_deref(),
         &config,
-    );
+        allowed_tools.as_deref(),
+    )
+    .tools();
 
     let peripheral_tools: Vec<Box<dyn Tool>> =
         crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
@@ -2690,14 +2791,26 @@ pub async fn run(
         tools_registry.extend(peripheral_tools);
     }
 
+    if let Some(allowed) = allowed_tools.as_deref() {
+        tools_registry = scope_tools(tools_registry, allowed);
+    }
+
     // ── Resolve provider ─────────────────────────────────────────
-    let provider_name = provider_override
-        .as_deref()
+    let cap_fallback = if provider_override.is_none() {
+        daily_cap_fallback(&config, cost_tracker.as_deref())
+    } else {
+        None
+    };
+
+    let provider_name = cap_fallback
+        .map(|(provider, _)| provider)
+        .or(provider_override.as_deref())
         .or(config.default_provider.as_deref())
         .unwrap_or("openrouter");
 
-    let model_name = model_override
-        .as_deref()
+    let model_name = cap_fallback
+        .and_then(|(_, model)| model)
+        .or(model_override.as_deref())
         .or(config.default_model.as_deref())
         .unwrap_or("anthropic/claude-sonnet-4");
 
@@ -2770,10 +2883,14 @@ pub async fn run(
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "preference_set",
+            "Record a standing user preference (added to every system prompt). Use when: the user states how you should behave from now on (units, tone, approval rules). Don't use when: it is a one-off request or a fact about the world.",
+        ),
     ];
     tool_descs.push((
         "cron_add",
-        "Create a cron job. Supports schedule kinds: cron, at, every; and job types: shell or agent.",
+        "Create a cron job. Supports schedule kinds: cron, at, every; and job types: shell or agent. Chain jobs with after (+ after_condition success/failure/always).",
     ));
     tool_descs.push((
         "cron_list",
@@ -2782,11 +2899,11 @@ pub async fn run(
     tool_descs.push(("cron_remove", "Remove a cron job by job_id."));
     tool_descs.push((
         "cron_update",
-        "Patch a cron job (schedule, enabled, command/prompt, model, delivery, session_target).",
+        "Patch a cron job (schedule, enabled, command/prompt, model, delivery, session_target, allowed_tools, after, after_condition).",
     ));
     tool_descs.push((
         "cron_run",
-        "Force-run a cron job immediately and record a run history entry.",
+        "Force-run a cron job immediately (optional date/delivery/model overrides) and record it as a manual run.",
     ));
     tool_descs.push(("cron_runs", "Show recent run history for a cron job."));
     tool_descs.push((
@@ -2853,6 +2970,9 @@ pub async fn run(
             "Query connected hardware for reported GPIO pins and LED pin. Use when: user asks what pins are available.",
         ));
     }
+    if allowed_tools.is_some() {
+        tool_descs.retain(|(name, _)| tools_registry.iter().any(|tool| tool.name() == *name));
+    }
     let bootstrap_max_chars = if config.agent.compact_context {
         Some(6000)
     } else {
@@ -2869,6 +2989,10 @@ pub async fn run(
         native_tools,
         config.skills.prompt_injection_mode,
     );
+    if let Some(notice) = tools::read_only_notice(config.autonomy.level, &tools_registry) {
+        system_prompt.push_str(&notice);
+    }
+    system_prompt.push_str(&crate::locale::prompt_section(&config.locale));
 
     // Append structured tool-use instructions with schemas (only for non-native providers)
     if !native_tools {
@@ -2882,6 +3006,7 @@ pub async fn run(
         None
     };
     let channel_name = if interactive { "cli" } else { "daemon" };
+    let conf_d = crate::config::confd::ConfDWatcher::shared(&config);
 
     // ── Execute ──────────────────────────────────────────────────
     let start = Instant::now();
@@ -2905,7 +3030,13 @@ pub async fn run(
             .as_ref()
             .map(|r| build_hardware_context(r, &msg, &board_names, rag_limit))
             .unwrap_or_default();
-        let context = format!("{mem_context}{hw_context}");
+        let fact_context = crate::facts::prompt_context(
+            &config.workspace_dir,
+            &config.facts,
+            &config.locale,
+            &msg,
+        );
+        let context = format!("{mem_context}{fact_context}{hw_context}");
         let enriched = if context.is_empty() {
             msg.clone()
         } else {
@@ -2913,7 +3044,13 @@ pub async fn run(
         };
 
         let mut history = vec![
-            ChatMessage::system(&system_prompt),
+            ChatMessage::system(crate::preferences::apply_to_prompt(
+                &config.workspace_dir,
+                &conf_d.apply_to_prompt(&crate::locale::with_current_datetime(
+                    &config.locale,
+                    &system_prompt,
+                )),
+            )),
             ChatMessage::user(&enriched),
         ];
 
@@ -2945,7 +3082,10 @@ pub async fn run(
         let cli = crate::channels::CliChannel::new();
 
         // Persistent conversation history across turns
-        let mut history = vec![ChatMessage::system(&system_prompt)];
+        let mut history = vec![ChatMessage::system(crate::preferences::apply_to_prompt(
+            &config.workspace_dir,
+            &conf_d.apply_to_prompt(&system_prompt),
+        ))];
 
         loop {
             print!("> ");
@@ -2992,7 +3132,10 @@ pub async fn run(
                     }
 
                     history.clear();
-                    history.push(ChatMessage::system(&system_prompt));
+                    history.push(ChatMessage::system(crate::preferences::apply_to_prompt(
+                        &config.workspace_dir,
+                        &conf_d.apply_to_prompt(&system_prompt),
+                    )));
                     // Clear conversation and daily memory
                     let mut cleared = 0;
                     for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
@@ -3029,13 +3172,30 @@ pub async fn run(
                 .as_ref()
                 .map(|r| build_hardware_context(r, &user_input, &board_names, rag_limit))
                 .unwrap_or_default();
-            let context = format!("{mem_context}{hw_context}");
+            let fact_context = crate::facts::prompt_context(
+                &config.workspace_dir,
+                &config.facts,
+                &config.locale,
+                &user_input,
+            );
+            let context = format!("{mem_context}{fact_context}{hw_context}");
             let enriched = if context.is_empty() {
                 user_input.clone()
             } else {
                 format!("{context}{user_input}")
             };
 
+            // Pick up conf.d persona/prompt edits and preferences recorded
+            // since the last turn, and move the clock forward
+            if let Some(system) = history.first_mut().filter(|m| m.role == "system") {
+                system.content = crate::preferences::apply_to_prompt(
+                    &config.workspace_dir,
+                    &conf_d.apply_to_prompt(&crate::locale::with_current_datetime(
+                        &config.locale,
+                        &system_prompt,
+                    )),
+                );
+            }
             history.push(ChatMessage::user(&enriched));
 
             let response = match run_tool_call_loop(
@@ -3106,11 +3266,127 @@ pub async fn run(
     Ok(final_output)
 }
 
+/// The run's cost ledger when `[cost]` tracking is enabled. One tracker per
+/// run feeds both the cost observer and the daily-cap check, so the ledger is
+/// read once instead of on every check.
+fn create_cost_tracker(config: &Config) -> Option<Arc<crate::cost::CostTracker>> {
+    if !config.cost.enabled {
+        return None;
+    }
+    match crate::cost::CostTracker::new(config.cost.clone(), &config.workspace_dir) {
+        Ok(tracker) => Some(Arc::new(tracker)),
+        Err(e) => {
+            tracing::warn!("Cost tracking disabled for this run: {e}");
+            None
+        }
+    }
+}
+
+/// Build the configured observer, fanning out to a cost-ledger observer when
+/// `[cost]` tracking is enabled so provider-reported usage counts toward limits.
+fn create_cost_aware_observer(
+    config: &Config,
+    cost_tracker: Option<&Arc<crate::cost::CostTracker>>,
+    extra: Option<Box<dyn Observer>>,
+) -> Arc<dyn Observer> {
+    let mut observers = vec![observability::create_observer(&config.observability)];
+    if let Some(tracker) = cost_tracker {
+        observers.push(Box::new(crate::cost::CostObserver::new(
+            Arc::clone(tracker),
+            config.cost.prices.clone(),
+        )));
+    }
+    observers.extend(extra);
+
+    if observers.len() == 1 {
+        Arc::from(observers.remove(0))
+    } else {
+        Arc::new(observability::MultiObserver::new(observers))
+    }
+}
+
+/// Cheapest `provider/model` in the `[cost]` price table by combined input and
+/// output price per 1M tokens; ties go to the alphabetically first entry.
+fn cheapest_priced_model(
+    prices: &std::collections::HashMap<String, crate::config::schema::ModelPricing>,
+) -> Option<(&str, &str)> {
+    prices
+        .iter()
+        .filter_map(|(key, pricing)| {
+            let (provider, model) = key.split_once('/')?;
+            let total = pricing.input + pricing.output;
+            (total.is_finite() && !provider.is_empty() && !model.is_empty()).then_some((
+                total,
+                key.as_str(),
+                provider,
+                model,
+            ))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(b.1)))
+        .map(|(_, _, provider, model)| (provider, model))
+}
+
+/// Return the provider/model to use once today's spend cap is reached:
+/// `cap_fallback_provider` when set, otherwise the cheapest entry in the
+/// `[cost]` price table.
+fn daily_cap_fallback<'a>(
+    config: &'a Config,
+    cost_tracker: Option<&crate::cost::CostTracker>,
+) -> Option<(&'a str, Option<&'a str>)> {
+    let capped = match cost_tracker?.daily_cap_exceeded() {
+        Ok(capped) => capped,
+        Err(e) => {
+            tracing::warn!("Daily spend cap check failed: {e}");
+            false
+        }
+    };
+    if !capped {
+        return None;
+    }
+
+    let configured = config
+        .cost
+        .cap_fallback_provider
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty());
+    let fallback = match configured {
+        Some(provider) => (provider, config.cost.cap_fallback_model.as_deref()),
+        None => {
+            let (provider, model) = cheapest_priced_model(&config.cost.prices)?;
+            (provider, Some(model))
+        }
+    };
+
+    tracing::warn!(
+        provider = fallback.0,
+        model = fallback.1,
+        "Daily spend cap reached; switching to the fallback provider"
+    );
+    Some(fallback)
+}
+
 /// Process a single message through the full agent (with tools, peripherals, memory).
 /// Used by channels (Telegram, Discord, etc.) to enable hardware and tool use.
 pub async fn process_message(config: Config, message: &str) -> Result<String> {
-    let observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
+    Box::pin(process_message_streaming(config, message, None, None, None)).await
+}
+
+/// Like [`process_message`], but relays progress lines and the final reply
+/// through `on_delta` (see [`DRAFT_CLEAR_SENTINEL`]) and reports lifecycle
+/// events to `extra_observer` alongside the configured observers.
+///
+/// Cancelling `cancellation_token` stops the turn at the next provider call
+/// or tool execution and returns a [`ToolLoopCancelled`] error.
+pub async fn process_message_streaming(
+    config: Config,
+    message: &str,
+    on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+    extra_observer: Option<Box<dyn Observer>>,
+    cancellation_token: Option<CancellationToken>,
+) -> Result<String> {
+    let cost_tracker = create_cost_tracker(&config);
+    let observer = create_cost_aware_observer(&config, cost_tracker.as_ref(), extra_observer);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -3150,11 +3426,18 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
     tools_registry.extend(peripheral_tools);
 
-    let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
-    let model_name = config
-        .default_model
-        .clone()
-        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+    let cap_fallback = daily_cap_fallback(&config, cost_tracker.as_deref());
+    let provider_name = cap_fallback
+        .map(|(provider, _)| provider)
+        .or(config.default_provider.as_deref())
+        .unwrap_or("openrouter");
+    let model_name = cap_fallback
+        .and_then(|(_, model)| model)
+        .or(config.default_model.as_deref())
+        .map_or_else(
+            || "anthropic/claude-sonnet-4-20250514".to_string(),
+            ToString::to_string,
+        );
     let provider_runtime_options = providers::ProviderRuntimeOptions {
         auth_profile_override: None,
         zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
@@ -3194,6 +3477,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         ("memory_store", "Save to memory."),
         ("memory_recall", "Search memory."),
         ("memory_forget", "Delete a memory entry."),
+        ("preference_set", "Record a standing user preference."),
         (
             "model_routing_config",
             "Configure default model, scenario routing, and delegate agents.",
@@ -3250,9 +3534,14 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         native_tools,
         config.skills.prompt_injection_mode,
     );
+    if let Some(notice) = tools::read_only_notice(config.autonomy.level, &tools_registry) {
+        system_prompt.push_str(&notice);
+    }
+    system_prompt.push_str(&crate::locale::prompt_section(&config.locale));
     if !native_tools {
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
+    let system_prompt = crate::locale::with_current_datetime(&config.locale, &system_prompt);
 
     let mem_context = build_context(mem.as_ref(), message, config.memory.min_relevance_score).await;
     let rag_limit = if config.agent.compact_context { 2 } else { 5 };
@@ -3260,31 +3549,91 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         .as_ref()
         .map(|r| build_hardware_context(r, message, &board_names, rag_limit))
         .unwrap_or_default();
-    let context = format!("{mem_context}{hw_context}");
+    let fact_context = crate::facts::prompt_context(
+        &config.workspace_dir,
+        &config.facts,
+        &config.locale,
+        message,
+    );
+    let context = format!("{mem_context}{fact_context}{hw_context}");
     let enriched = if context.is_empty() {
         message.to_string()
     } else {
         format!("{context}{message}")
     };
 
+    let system_prompt = crate::preferences::apply_to_prompt(
+        &config.workspace_dir,
+        &crate::config::confd::ConfDWatcher::shared(&config).apply_to_prompt(&system_prompt),
+    );
     let mut history = vec![
         ChatMessage::system(&system_prompt),
         ChatMessage::user(&enriched),
     ];
 
-    agent_turn(
+    let caller_token = cancellation_token.unwrap_or_default();
+    let turn_token = caller_token.child_token();
+    // Cancelling on return also stops the timeout task.
+    let _turn_guard = turn_token.clone().drop_guard();
+    spawn_turn_timeout(&turn_token, config.agent.turn_timeout_secs);
+
+    let turn_recorder = crate::analytics::TurnRecorder::new(observer);
+    let turn_started_at = chrono::Utc::now();
+    let started_at = Instant::now();
+    let result = agent_turn(
         provider.as_ref(),
         &mut history,
         &tools_registry,
-        observer.as_ref(),
+        &turn_recorder,
         provider_name,
         &model_name,
         config.default_temperature,
         true,
         &config.multimodal,
         config.agent.max_tool_iterations,
+        on_delta,
+        Some(turn_token),
     )
-    .await
+    .await;
+    if !caller_token.is_cancelled() {
+        let turn = turn_recorder.finish(
+            "gateway",
+            turn_started_at,
+            started_at.elapsed(),
+            result.is_ok(),
+        );
+        crate::analytics::record_in_background(
+            config.workspace_dir.clone(),
+            &config.analytics,
+            turn,
+        );
+    }
+    match result {
+        Err(e) if is_tool_loop_cancelled(&e) && !caller_token.is_cancelled() => {
+            Err(e.context(format!(
+                "Agent turn timed out after {}s (agent.turn_timeout_secs)",
+                config.agent.turn_timeout_secs
+            )))
+        }
+        other => other,
+    }
+}
+
+/// Cancel `token` once `timeout_secs` have passed; `0` disables the limit.
+fn spawn_turn_timeout(token: &CancellationToken, timeout_secs: u64) {
+    if timeout_secs == 0 {
+        return;
+    }
+    let token = token.clone();
+    tokio::spawn(async move {
+        tokio::select! {
+            () = token.cancelled() => {}
+            () = tokio::time::sleep(Duration::from_secs(timeout_secs)) => {
+                tracing::warn!("Agent turn exceeded {timeout_secs}s; cancelling");
+                token.cancel();
+            }
+        }
+    });
 }
 
 #[cfg(test)]
@@ -3297,6 +3646,22 @@ mod tests {
     use std::sync::{Arc, Mutex};
     use std::time::Duration;
 
+    #[test]
+    fn degradation_kind_classifies_common_reasons() {
+        assert_eq!(
+            degradation_kind("Rate limit exceeded: too many actions in the last hour"),
+            "rate_limited"
+        );
+        assert_eq!(degradation_kind("request timed out after 30s"), "timeout");
+        assert_eq!(degradation_kind("Denied by user."), "denied");
+        assert_eq!(
+            degradation_kind("Skipped duplicate tool call 'shell' with identical arguments"),
+            "skipped"
+        );
+        assert_eq!(degradation_kind("Unknown tool: nope"), "unknown_tool");
+        assert_eq!(degradation_kind("exit status 1"), "failed");
+    }
+
     #[test]
     fn test_scrub_credentials() {
         let input = "API_KEY=sk-1234567890abcdef; token: 1234567890; password=\"secret123456\"";
@@ -3564,6 +3929,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn scope_tools_keeps_only_allowlisted_tools() {
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let tools: Vec<Box<dyn Tool>> = ["shell", "rss_feed", "ntfy"]
+            .into_iter()
+            .map(|name| {
+                Box::new(CountingTool::new(name, Arc::clone(&invocations))) as Box<dyn Tool>
+            })
+            .collect();
+
+        let scoped = scope_tools(tools, &["ntfy".to_string(), "rss_feed".to_string()]);
+        let names: Vec<&str> = scoped.iter().map(|tool| tool.name()).collect();
+        assert_eq!(names, vec!["rss_feed", "ntfy"]);
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_returns_structured_error_for_non_vision_provider() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -3696,7 +4076,7 @@ mod tests {
             tool_call_id: None,
         }];
 
-        assert!(!should_execute_tools_in_parallel(&calls, None));
+        assert!(!should_execute_tools_in_parallel(&calls, &[], None));
     }
 
     #[test]
@@ -3718,6 +4098,7 @@ mod tests {
 
         assert!(!should_execute_tools_in_parallel(
             &calls,
+            &[],
             Some(&approval_mgr)
         ));
     }
@@ -3744,10 +4125,55 @@ mod tests {
 
         assert!(should_execute_tools_in_parallel(
             &calls,
+            &[],
             Some(&approval_mgr)
         ));
     }
 
+    #[test]
+    fn plan_steps_with_always_ask_tools_need_approval() {
+        let approval_cfg = crate::config::AutonomyConfig {
+            level: crate::security::AutonomyLevel::Supervised,
+            auto_approve: vec!["plan".into(), "file_read".into()],
+            always_ask: vec!["shell".into()],
+            ..crate::config::AutonomyConfig::default()
+        };
+        let approval_mgr = ApprovalManager::from_config(&approval_cfg);
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(crate::tools::PlanTool::new(
+            Arc::new(SecurityPolicy::default()),
+            Arc::new(Vec::new()),
+        ))];
+
+        let reads = serde_json::json!({ "steps": [{ "tool": "file_read" }] });
+        assert!(!call_needs_approval(
+            &approval_mgr,
+            &tools_registry,
+            "plan",
+            &reads
+        ));
+
+        let shell_step = serde_json::json!({ "steps": [
+            { "tool": "file_read" },
+            { "tool": "shell", "args": { "command": "rm -rf /tmp/x" } }
+        ]});
+        assert!(call_needs_approval(
+            &approval_mgr,
+            &tools_registry,
+            "plan",
+            &shell_step
+        ));
+
+        let shell_undo = serde_json::json!({ "steps": [
+            { "tool": "file_read", "compensate": { "tool": "shell" } }
+        ]});
+        assert!(call_needs_approval(
+            &approval_mgr,
+            &tools_registry,
+            "plan",
+            &shell_undo
+        ));
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_executes_multiple_tools_with_ordered_results() {
         let provider = ScriptedProvider::from_text_responses(vec![
@@ -5276,4 +5702,34 @@ Let me check the result."#;
         assert!(parse_glm_shortened_body("not-a-tool>value").is_none());
         assert!(parse_glm_shortened_body("tool name>value").is_none());
     }
+
+    #[test]
+    fn daily_cap_fallback_prefers_configured_then_cheapest_provider() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.cost.enabled = true;
+        config.cost.daily_limit_usd = 0.0;
+        let tracker =
+            crate::cost::CostTracker::new(config.cost.clone(), &config.workspace_dir).unwrap();
+
+        assert_eq!(
+            daily_cap_fallback(&config, Some(&tracker)),
+            Some(("google", Some("gemini-2.0-flash")))
+        );
+        assert_eq!(daily_cap_fallback(&config, None), None);
+
+        config.cost.cap_fallback_provider = Some("ollama".into());
+        assert_eq!(
+            daily_cap_fallback(&config, Some(&tracker)),
+            Some(("ollama", None))
+        );
+
+        config.cost.daily_limit_usd = 10.0;
+        let tracker =
+            crate::cost::CostTracker::new(config.cost.clone(), &config.workspace_dir).unwrap();
+        assert_eq!(daily_cap_fallback(&config, Some(&tracker)), None);
+    }
 }
@@ -3,7 +3,6 @@ use crate::identity;
 use crate::skills::Skill;
 use crate::tools::Tool;
 use anyhow::Result;
-use chrono::Local;
 use std::fmt::Write;
 use std::path::Path;
 
@@ -70,6 +69,8 @@ pub struct SkillsSection;
 pub struct WorkspaceSection;
 pub struct RuntimeSection;
 pub struct DateTimeSection;
+/// Added by callers when autonomy is read-only.
+pub struct ReadOnlySection;
 
 impl PromptSection for IdentitySection {
     fn name(&self) -> &str {
@@ -148,6 +149,19 @@ impl PromptSection for SafetySection {
     }
 }
 
+impl PromptSection for ReadOnlySection {
+    fn name(&self) -> &str {
+        "read_only"
+    }
+
+    fn build(&self, ctx: &PromptContext<'_>) -> Result<String> {
+        Ok(
+            crate::tools::read_only_notice(crate::security::AutonomyLevel::ReadOnly, ctx.tools)
+                .unwrap_or_default(),
+        )
+    }
+}
+
 impl PromptSection for SkillsSection {
     fn name(&self) -> &str {
         "skills"
@@ -197,12 +211,11 @@ impl PromptSection for DateTimeSection {
     }
 
     fn build(&self, _ctx: &PromptContext<'_>) -> Result<String> {
-        let now = Local::now();
-        Ok(format!(
-            "## Current Date & Time\n\n{} ({})",
-            now.format("%Y-%m-%d %H:%M:%S"),
-            now.format("%Z")
-        ))
+        let section = crate::locale::datetime_section(
+            &crate::config::LocaleConfig::default(),
+            chrono::Utc::now(),
+        );
+        Ok(section.trim_end().to_string())
     }
 }
 
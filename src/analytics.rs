@@ -0,0 +1,491 @@
+//! Conversation analytics.
+//!
+//! Every channel and gateway turn is recorded in
+//! `workspace/analytics/analytics.db`: one row per turn (channel, latency,
+//! tokens, outcome) and one per tool call. [`TurnRecorder`] wraps the
+//! runtime observer for the duration of a turn to collect them without
+//! blocking the agent loop. `GET /api/analytics` aggregates the rows, and
+//! cron run history, over a time window for the dashboard charts. Turns
+//! older than `[analytics] retention_days` are pruned on write.
+
+use crate::config::AnalyticsConfig;
+use crate::observability::traits::ObserverMetric;
+use crate::observability::{Observer, ObserverEvent};
+use crate::schema_migrations::{migrate, Migration};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub(crate) const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "baseline",
+    up: "CREATE TABLE turns (
+            id            INTEGER PRIMARY KEY AUTOINCREMENT,
+            channel       TEXT NOT NULL,
+            started_at    TEXT NOT NULL,
+            duration_ms   INTEGER NOT NULL,
+            input_tokens  INTEGER NOT NULL,
+            output_tokens INTEGER NOT NULL,
+            success       INTEGER NOT NULL
+        );
+        CREATE INDEX idx_turns_started_at ON turns(started_at);
+        CREATE TABLE tool_calls (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            turn_id     INTEGER NOT NULL REFERENCES turns(id) ON DELETE CASCADE,
+            tool        TEXT NOT NULL,
+            at          TEXT NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            success     INTEGER NOT NULL
+        );
+        CREATE INDEX idx_tool_calls_turn_id ON tool_calls(turn_id);",
+    down: "DROP TABLE tool_calls;
+        DROP TABLE turns;",
+}];
+
+/// Longest window `/api/analytics` accepts.
+const MAX_WINDOW_DAYS: i64 = 365;
+/// Windows up to this long are bucketed by hour, longer ones by day.
+const HOURLY_BUCKETS_UP_TO_HOURS: i64 = 48;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolCallRecord {
+    pub tool: String,
+    pub at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub success: bool,
+}
+
+/// One finished turn, ready to be stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnRecord {
+    pub channel: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub success: bool,
+    pub tool_calls: Vec<ToolCallRecord>,
+}
+
+#[derive(Debug, Default)]
+struct TurnTotals {
+    input_tokens: u64,
+    output_tokens: u64,
+    tool_calls: Vec<ToolCallRecord>,
+}
+
+/// Forwards everything to the wrapped observer and keeps the token counts
+/// and tool calls of one turn in memory until [`TurnRecorder::finish`].
+pub struct TurnRecorder {
+    inner: Arc<dyn Observer>,
+    totals: Mutex<TurnTotals>,
+}
+
+impl TurnRecorder {
+    pub fn new(inner: Arc<dyn Observer>) -> Self {
+        Self {
+            inner,
+            totals: Mutex::new(TurnTotals::default()),
+        }
+    }
+
+    /// Take the collected totals as the record of a turn on `channel`.
+    pub fn finish(
+        &self,
+        channel: &str,
+        started_at: DateTime<Utc>,
+        elapsed: std::time::Duration,
+        success: bool,
+    ) -> TurnRecord {
+        let totals = std::mem::take(&mut *self.totals.lock());
+        TurnRecord {
+            channel: channel.to_string(),
+            started_at,
+            duration_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+            input_tokens: totals.input_tokens,
+            output_tokens: totals.output_tokens,
+            success,
+            tool_calls: totals.tool_calls,
+        }
+    }
+}
+
+impl Observer for TurnRecorder {
+    fn record_event(&self, event: &ObserverEvent) {
+        self.inner.record_event(event);
+        match event {
+            ObserverEvent::LlmResponse {
+                input_tokens,
+                output_tokens,
+                ..
+            } => {
+                let mut totals = self.totals.lock();
+                totals.input_tokens += input_tokens.unwrap_or(0);
+                totals.output_tokens += output_tokens.unwrap_or(0);
+            }
+            ObserverEvent::ToolCall {
+                tool,
+                duration,
+                success,
+            } => self.totals.lock().tool_calls.push(ToolCallRecord {
+                tool: tool.clone(),
+                at: Utc::now(),
+                duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+                success: *success,
+            }),
+            _ => {}
+        }
+    }
+
+    fn record_metric(&self, metric: &ObserverMetric) {
+        self.inner.record_metric(metric);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+pub fn db_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("analytics").join("analytics.db")
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let path = db_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let conn = Connection::open(&path)
+        .with_context(|| format!("Failed to open analytics DB: {}", path.display()))?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    migrate(&conn, "analytics", MIGRATIONS)?;
+    f(&conn)
+}
+
+fn to_sql_int(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+/// Store a turn and its tool calls, pruning turns older than `retention_days`.
+pub fn record(workspace_dir: &Path, turn: &TurnRecord, retention_days: u32) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO turns (channel, started_at, duration_ms, input_tokens, output_tokens, success)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                turn.channel,
+                turn.started_at.to_rfc3339(),
+                to_sql_int(turn.duration_ms),
+                to_sql_int(turn.input_tokens),
+                to_sql_int(turn.output_tokens),
+                turn.success,
+            ],
+        )?;
+        let turn_id = tx.last_insert_rowid();
+        for call in &turn.tool_calls {
+            tx.execute(
+                "INSERT INTO tool_calls (turn_id, tool, at, duration_ms, success)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    turn_id,
+                    call.tool,
+                    call.at.to_rfc3339(),
+                    to_sql_int(call.duration_ms),
+                    call.success,
+                ],
+            )?;
+        }
+        let cutoff = Utc::now() - Duration::days(i64::from(retention_days));
+        tx.execute(
+            "DELETE FROM turns WHERE started_at < ?1",
+            params![cutoff.to_rfc3339()],
+        )?;
+        tx.commit()?;
+        Ok(())
+    })
+}
+
+/// [`record`] on the blocking pool; failures are logged, never surfaced to
+/// the turn that produced the record.
+pub fn record_in_background(workspace_dir: PathBuf, config: &AnalyticsConfig, turn: TurnRecord) {
+    if !config.enabled {
+        return;
+    }
+    let retention_days = config.retention_days;
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = record(&workspace_dir, &turn, retention_days) {
+            tracing::warn!("Failed to record turn analytics: {e}");
+        }
+    });
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ToolStats {
+    pub tool: String,
+    pub calls: u64,
+    pub failures: u64,
+    pub failure_rate: f64,
+    pub avg_duration_ms: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChannelStats {
+    pub channel: String,
+    pub turns: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub avg_latency_ms: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Bucket {
+    /// Start of the hour or day, UTC, e.g. `2026-10-16T04` or `2026-10-16`.
+    pub start: String,
+    pub turns: u64,
+    pub tool_calls: u64,
+    pub tokens: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    pub since: DateTime<Utc>,
+    /// `hour` or `day`.
+    pub bucket: &'static str,
+    pub turns: u64,
+    pub failed_turns: u64,
+    pub avg_turn_latency_ms: Option<f64>,
+    /// Most used first.
+    pub tools: Vec<ToolStats>,
+    /// Most tokens first.
+    pub channels: Vec<ChannelStats>,
+    pub timeline: Vec<Bucket>,
+}
+
+/// Parse a window such as `24h` or `7d`.
+pub fn parse_window(raw: &str) -> Result<Duration> {
+    let raw = raw.trim();
+    let unit = raw.chars().last().unwrap_or_default();
+    let count: i64 = raw[..raw.len() - unit.len_utf8()]
+        .parse()
+        .ok()
+        .filter(|n| *n > 0)
+        .with_context(|| format!("Invalid window '{raw}': use e.g. 24h or 7d"))?;
+    let window = match unit {
+        'h' => Duration::hours(count),
+        'd' => Duration::days(count),
+        _ => bail!("Invalid window '{raw}': use e.g. 24h or 7d"),
+    };
+    if window > Duration::days(MAX_WINDOW_DAYS) {
+        bail!("Window '{raw}' is longer than {MAX_WINDOW_DAYS} days");
+    }
+    Ok(window)
+}
+
+fn count(value: i64) -> u64 {
+    value.unsigned_abs()
+}
+
+/// Aggregate turns and tool calls from `now - window` on.
+pub fn summary(workspace_dir: &Path, window: Duration) -> Result<Summary> {
+    let since = Utc::now() - window;
+    let since_raw = since.to_rfc3339();
+    let (bucket, prefix_len) = if window <= Duration::hours(HOURLY_BUCKETS_UP_TO_HOURS) {
+        ("hour", 13)
+    } else {
+        ("day", 10)
+    };
+    with_connection(workspace_dir, |conn| {
+        let (turns, failed_turns, avg_turn_latency_ms) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(1 - success), 0), AVG(duration_ms)
+             FROM turns WHERE started_at >= ?1",
+            params![since_raw],
+            |row| {
+                Ok((
+                    count(row.get(0)?),
+                    count(row.get(1)?),
+                    row.get::<_, Option<f64>>(2)?,
+                ))
+            },
+        )?;
+
+        let mut stmt = conn.prepare(
+            "SELECT tool, COUNT(*), SUM(1 - success), AVG(duration_ms)
+             FROM tool_calls
+             WHERE turn_id IN (SELECT id FROM turns WHERE started_at >= ?1)
+             GROUP BY tool ORDER BY COUNT(*) DESC, tool",
+        )?;
+        let tools = stmt
+            .query_map(params![since_raw], |row| {
+                let calls = count(row.get(1)?);
+                let failures = count(row.get(2)?);
+                #[allow(clippy::cast_precision_loss)]
+                let failure_rate = failures as f64 / calls.max(1) as f64;
+                Ok(ToolStats {
+                    tool: row.get(0)?,
+                    calls,
+                    failures,
+                    failure_rate,
+                    avg_duration_ms: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT channel, COUNT(*), SUM(input_tokens), SUM(output_tokens), AVG(duration_ms)
+             FROM turns WHERE started_at >= ?1
+             GROUP BY channel ORDER BY SUM(input_tokens + output_tokens) DESC, channel",
+        )?;
+        let channels = stmt
+            .query_map(params![since_raw], |row| {
+                Ok(ChannelStats {
+                    channel: row.get(0)?,
+                    turns: count(row.get(1)?),
+                    input_tokens: count(row.get(2)?),
+                    output_tokens: count(row.get(3)?),
+                    avg_latency_ms: row.get(4)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT substr(t.started_at, 1, ?2) AS bucket, COUNT(*),
+                    SUM(t.input_tokens + t.output_tokens),
+                    SUM((SELECT COUNT(*) FROM tool_calls c WHERE c.turn_id = t.id))
+             FROM turns t WHERE t.started_at >= ?1
+             GROUP BY bucket ORDER BY bucket",
+        )?;
+        let timeline = stmt
+            .query_map(params![since_raw, prefix_len], |row| {
+                Ok(Bucket {
+                    start: row.get(0)?,
+                    turns: count(row.get(1)?),
+                    tokens: count(row.get(2)?),
+                    tool_calls: count(row.get(3)?),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(Summary {
+            since,
+            bucket,
+            turns,
+            failed_turns,
+            avg_turn_latency_ms,
+            tools,
+            channels,
+            timeline,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::NoopObserver;
+    use tempfile::TempDir;
+
+    #[test]
+    fn recorder_collects_tokens_and_tool_calls() {
+        let recorder = TurnRecorder::new(Arc::new(NoopObserver));
+        for tokens in [(Some(100), Some(20)), (Some(50), None)] {
+            recorder.record_event(&ObserverEvent::LlmResponse {
+                provider: "p".into(),
+                model: "m".into(),
+                duration: std::time::Duration::from_millis(5),
+                success: true,
+                error_message: None,
+                input_tokens: tokens.0,
+                output_tokens: tokens.1,
+            });
+        }
+        recorder.record_event(&ObserverEvent::ToolCall {
+            tool: "shell".into(),
+            duration: std::time::Duration::from_millis(40),
+            success: false,
+        });
+
+        let turn = recorder.finish(
+            "telegram",
+            Utc::now(),
+            std::time::Duration::from_secs(2),
+            true,
+        );
+        assert_eq!((turn.input_tokens, turn.output_tokens), (150, 20));
+        assert_eq!(turn.duration_ms, 2000);
+        assert_eq!(turn.tool_calls.len(), 1);
+        assert!(!turn.tool_calls[0].success);
+        assert!(recorder
+            .finish("telegram", Utc::now(), std::time::Duration::ZERO, true)
+            .tool_calls
+            .is_empty());
+    }
+
+    #[test]
+    fn summarizes_tools_channels_and_timeline() {
+        let tmp = TempDir::new().unwrap();
+        let now = Utc::now();
+        let call = |tool: &str, success| ToolCallRecord {
+            tool: tool.into(),
+            at: now,
+            duration_ms: 100,
+            success,
+        };
+        let turn = |channel: &str, tokens, tool_calls| TurnRecord {
+            channel: channel.into(),
+            started_at: now,
+            duration_ms: 1000,
+            input_tokens: tokens,
+            output_tokens: 10,
+            success: true,
+            tool_calls,
+        };
+        record(
+            tmp.path(),
+            &turn(
+                "telegram",
+                500,
+                vec![call("shell", true), call("shell", false)],
+            ),
+            90,
+        )
+        .unwrap();
+        record(
+            tmp.path(),
+            &turn("gateway", 90, vec![call("web_search", true)]),
+            90,
+        )
+        .unwrap();
+        let mut old = turn("telegram", 1000, vec![call("shell", true)]);
+        old.started_at = now - Duration::days(3);
+        record(tmp.path(), &old, 90).unwrap();
+
+        let summary = summary(tmp.path(), parse_window("24h").unwrap()).unwrap();
+        assert_eq!(summary.bucket, "hour");
+        assert_eq!((summary.turns, summary.failed_turns), (2, 0));
+        assert_eq!(summary.tools[0].tool, "shell");
+        assert_eq!((summary.tools[0].calls, summary.tools[0].failures), (2, 1));
+        assert!((summary.tools[0].failure_rate - 0.5).abs() < f64::EPSILON);
+        assert_eq!(summary.channels[0].channel, "telegram");
+        assert_eq!(summary.channels[0].input_tokens, 500);
+        assert_eq!(summary.timeline.len(), 1);
+        assert_eq!(summary.timeline[0].tool_calls, 3);
+
+        assert!(parse_window("0d").is_err());
+        assert!(parse_window("2w").is_err());
+        assert!(parse_window("400d").is_err());
+    }
+}
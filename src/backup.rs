@@ -0,0 +1,582 @@
+//! Scheduled config and state backups (`[backup]`) and `zeroclaw restore`.
+//!
+//! An archive is a gzipped ustar file with two roots: `config/` mirrors the
+//! config directory (`config.toml`, `conf.d/`, `.secret_key`) and
+//! `workspace/` the workspace. SQLite databases anywhere in the workspace
+//! are captured with `VACUUM INTO`, so a backup taken while the daemon is
+//! writing is still consistent.
+
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+const COMPONENT: &str = "backup";
+const ARCHIVE_PREFIX: &str = "zeroclaw-backup-";
+const ARCHIVE_SUFFIX: &str = ".tar.gz";
+const SQLITE_MAGIC: &[u8; 16] = b"SQLite format 3\0";
+const BLOCK: usize = 512;
+
+/// Files of the config directory worth restoring.
+const CONFIG_ENTRIES: [&str; 3] = ["config.toml", "conf.d", ".secret_key"];
+
+enum Source {
+    File(PathBuf),
+    /// Snapshot with `VACUUM INTO` instead of copying the live file.
+    Sqlite(PathBuf),
+}
+
+fn config_dir(config: &Config) -> PathBuf {
+    config
+        .config_path
+        .parent()
+        .map_or_else(|| PathBuf::from("."), PathBuf::from)
+}
+
+pub fn backup_dir(config: &Config) -> PathBuf {
+    let dir = PathBuf::from(shellexpand::tilde(config.backup.dir.trim()).as_ref());
+    if dir.is_absolute() {
+        dir
+    } else {
+        config_dir(config).join(dir)
+    }
+}
+
+/// Regular files under `path` (or `path` itself); symlinks are not followed.
+fn walk_files(path: &Path, skip: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        if current == skip {
+            continue;
+        }
+        let Ok(meta) = current.symlink_metadata() else {
+            continue;
+        };
+        if meta.is_file() {
+            files.push(current);
+        } else if meta.is_dir() {
+            if let Ok(entries) = std::fs::read_dir(&current) {
+                pending.extend(entries.filter_map(Result::ok).map(|e| e.path()));
+            }
+        }
+    }
+    files
+}
+
+fn is_sqlite(path: &Path) -> bool {
+    let mut magic = [0u8; 16];
+    File::open(path)
+        .and_then(|mut f| f.read_exact(&mut magic))
+        .is_ok_and(|()| &magic == SQLITE_MAGIC)
+}
+
+/// Journal files belong to their database and are folded into its snapshot.
+fn is_sqlite_sidecar(path: &Path) -> bool {
+    path.to_str()
+        .is_some_and(|p| p.ends_with("-wal") || p.ends_with("-shm") || p.ends_with("-journal"))
+}
+
+fn archive_name(root: &str, base: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(base).ok()?;
+    let parts: Vec<&str> = relative
+        .components()
+        .map(|c| match c {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    Some(format!("{root}/{}", parts.join("/")))
+}
+
+/// Everything a backup of `config` contains, keyed by archive path.
+fn collect_sources(config: &Config, skip: &Path) -> BTreeMap<String, Source> {
+    let mut sources = BTreeMap::new();
+    let config_dir = config_dir(config);
+    for entry in CONFIG_ENTRIES {
+        for file in walk_files(&config_dir.join(entry), skip) {
+            if let Some(name) = archive_name("config", &config_dir, &file) {
+                sources.insert(name, Source::File(file));
+            }
+        }
+    }
+
+    let workspace = &config.workspace_dir;
+    for file in walk_files(workspace, skip) {
+        if !is_sqlite_sidecar(&file) && is_sqlite(&file) {
+            if let Some(name) = archive_name("workspace", workspace, &file) {
+                sources.insert(name, Source::Sqlite(file));
+            }
+        }
+    }
+    for include in &config.backup.include {
+        let include = include.trim().trim_start_matches('/');
+        if include.is_empty() || include.split('/').any(|part| part == "..") {
+            tracing::warn!("Ignoring backup.include entry '{include}'");
+            continue;
+        }
+        for file in walk_files(&workspace.join(include), skip) {
+            if is_sqlite_sidecar(&file) {
+                continue;
+            }
+            if let Some(name) = archive_name("workspace", workspace, &file) {
+                sources.entry(name).or_insert(Source::File(file));
+            }
+        }
+    }
+    sources
+}
+
+// ── ustar ───────────────────────────────────────────────────────
+
+fn write_octal(field: &mut [u8], value: u64) -> Result<()> {
+    let digits = format!("{value:0width$o}", width = field.len() - 1);
+    if digits.len() >= field.len() {
+        bail!("value {value} does not fit in a tar header field");
+    }
+    field[..digits.len()].copy_from_slice(digits.as_bytes());
+    Ok(())
+}
+
+/// Split `path` into ustar `prefix` and `name` fields.
+fn split_path(path: &str) -> Result<(&str, &str)> {
+    if path.len() <= 100 {
+        return Ok(("", path));
+    }
+    path.match_indices('/')
+        .map(|(i, _)| (&path[..i], &path[i + 1..]))
+        .find(|(prefix, name)| prefix.len() <= 155 && !name.is_empty() && name.len() <= 100)
+        .with_context(|| format!("Path too long for a backup archive: {path}"))
+}
+
+fn tar_header(path: &str, size: u64, mtime: u64) -> Result<[u8; BLOCK]> {
+    let (prefix, name) = split_path(path)?;
+    let mut header = [0u8; BLOCK];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    write_octal(&mut header[100..108], 0o600)?;
+    write_octal(&mut header[108..116], 0)?;
+    write_octal(&mut header[116..124], 0)?;
+    write_octal(&mut header[124..136], size)?;
+    write_octal(&mut header[136..148], mtime)?;
+    header[148..156].fill(b' ');
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+    let checksum: u32 = header.iter().map(|b| u32::from(*b)).sum();
+    header[148..156].copy_from_slice(format!("{checksum:06o}\0 ").as_bytes());
+    Ok(header)
+}
+
+fn padding(size: u64) -> usize {
+    let rem = usize::try_from(size % BLOCK as u64).unwrap_or(0);
+    if rem == 0 {
+        0
+    } else {
+        BLOCK - rem
+    }
+}
+
+fn append_file(out: &mut impl Write, name: &str, path: &Path, mtime: u64) -> Result<()> {
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let size = file.metadata()?.len();
+    out.write_all(&tar_header(name, size, mtime)?)?;
+    let copied = io::copy(&mut (&mut file).take(size), out)?;
+    if copied != size {
+        bail!("{} changed size while being backed up", path.display());
+    }
+    out.write_all(&[0u8; BLOCK][..padding(size)])?;
+    Ok(())
+}
+
+fn header_str(field: &[u8]) -> Result<&str> {
+    let end = field.iter().position(|b| *b == 0).unwrap_or(field.len());
+    std::str::from_utf8(&field[..end]).context("Backup archive has a non-UTF-8 path")
+}
+
+fn parse_octal(field: &[u8]) -> Result<u64> {
+    let text = header_str(field)?.trim();
+    u64::from_str_radix(text, 8).with_context(|| format!("Invalid tar header number '{text}'"))
+}
+
+/// Call `f` with the path and contents of each regular file in a tar stream.
+fn for_each_entry(
+    mut reader: impl Read,
+    mut f: impl FnMut(&str, &mut dyn Read) -> Result<()>,
+) -> Result<()> {
+    let mut header = [0u8; BLOCK];
+    loop {
+        reader.read_exact(&mut header)?;
+        if header.iter().all(|b| *b == 0) {
+            return Ok(());
+        }
+        let stored: u32 = header[148..156].iter().map(|b| u32::from(*b)).sum();
+        let expected = parse_octal(&header[148..156])?;
+        let actual = u64::from(
+            header.iter().map(|b| u32::from(*b)).sum::<u32>() - stored + 8 * u32::from(b' '),
+        );
+        if expected != actual {
+            bail!("Backup archive is corrupt (bad header checksum)");
+        }
+
+        let name = header_str(&header[..100])?;
+        let prefix = header_str(&header[345..500])?;
+        let path = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}/{name}")
+        };
+        let size = parse_octal(&header[124..136])?;
+
+        let mut entry = (&mut reader).take(size);
+        if matches!(header[156], b'0' | 0) {
+            f(&path, &mut entry)?;
+        }
+        io::copy(&mut entry, &mut io::sink())?;
+        io::copy(
+            &mut (&mut reader).take(padding(size) as u64),
+            &mut io::sink(),
+        )?;
+    }
+}
+
+// ── Backup ──────────────────────────────────────────────────────
+
+/// Write a new archive into [`backup_dir`] and return its path.
+pub fn create_backup(config: &Config) -> Result<PathBuf> {
+    let dir = backup_dir(config);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create backup directory {}", dir.display()))?;
+
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+    let target = dir.join(format!("{ARCHIVE_PREFIX}{stamp}{ARCHIVE_SUFFIX}"));
+    let partial = target.with_extension("partial");
+    let staging = dir.join(format!(".staging-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&staging)?;
+
+    let result = write_archive(config, &dir, &partial, &staging);
+    let _ = std::fs::remove_dir_all(&staging);
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&partial);
+        return Err(e);
+    }
+    std::fs::rename(&partial, &target)?;
+    Ok(target)
+}
+
+fn write_archive(config: &Config, dir: &Path, partial: &Path, staging: &Path) -> Result<()> {
+    let mtime = u64::try_from(Utc::now().timestamp()).unwrap_or(0);
+    let file = create_private(partial)?;
+    let mut out = GzEncoder::new(io::BufWriter::new(file), Compression::default());
+
+    for (index, (name, source)) in collect_sources(config, dir).into_iter().enumerate() {
+        match source {
+            Source::File(path) => append_file(&mut out, &name, &path, mtime)?,
+            Source::Sqlite(path) => {
+                let snapshot = staging.join(format!("{index}.db"));
+                let conn = rusqlite::Connection::open(&path)
+                    .with_context(|| format!("Failed to open {}", path.display()))?;
+                conn.execute("VACUUM INTO ?1", [snapshot.to_string_lossy().as_ref()])
+                    .with_context(|| format!("Failed to snapshot {}", path.display()))?;
+                append_file(&mut out, &name, &snapshot, mtime)?;
+                std::fs::remove_file(&snapshot)?;
+            }
+        }
+    }
+
+    out.write_all(&[0u8; 2 * BLOCK])?;
+    out.finish()?.flush()?;
+    Ok(())
+}
+
+/// Archives hold the secret key; keep them owner-only.
+fn create_private(path: &Path) -> Result<File> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .with_context(|| format!("Failed to create {}", path.display()))
+}
+
+/// Delete all but the newest `keep` archives in `dir`; returns what was removed.
+pub fn rotate(dir: &Path, keep: usize) -> Result<Vec<PathBuf>> {
+    let mut archives: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(ARCHIVE_PREFIX) && n.ends_with(ARCHIVE_SUFFIX))
+        })
+        .collect();
+    // Timestamped names sort chronologically.
+    archives.sort();
+    let excess = archives.len().saturating_sub(keep.max(1));
+    let removed: Vec<PathBuf> = archives.drain(..excess).collect();
+    for path in &removed {
+        std::fs::remove_file(path)?;
+    }
+    Ok(removed)
+}
+
+async fn upload(config: &Config, archive: &Path, location: &str) -> Result<()> {
+    let location = location.trim().trim_matches('/');
+    let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+    let file_name = archive
+        .file_name()
+        .and_then(|n| n.to_str())
+        .context("Archive has no file name")?;
+    let key = if prefix.is_empty() {
+        file_name.to_string()
+    } else {
+        format!("{prefix}/{file_name}")
+    };
+    if !crate::tools::object_store::is_location_allowed(&config.object_store.allowed, bucket, &key)
+    {
+        bail!("backup.upload '{bucket}/{key}' is not in object_store.allowed");
+    }
+
+    let data = tokio::fs::read(archive).await?;
+    let limit = config
+        .object_store
+        .max_object_mb
+        .saturating_mul(1024 * 1024);
+    if data.len() as u64 > limit {
+        bail!(
+            "Archive is {} bytes, above object_store.max_object_mb",
+            data.len()
+        );
+    }
+    crate::tools::object_store::S3Client::from_config(&config.object_store)?
+        .put(bucket, &key, data, "application/gzip")
+        .await
+}
+
+/// Create an archive, rotate old ones and upload it when configured.
+pub async fn run_once(config: &Config) -> Result<PathBuf> {
+    let blocking_cfg = config.clone();
+    let archive = tokio::task::spawn_blocking(move || {
+        let archive = create_backup(&blocking_cfg)?;
+        for removed in rotate(&backup_dir(&blocking_cfg), blocking_cfg.backup.keep)? {
+            tracing::info!("Removed old backup {}", removed.display());
+        }
+        Ok::<_, anyhow::Error>(archive)
+    })
+    .await??;
+
+    if let Some(location) = config.backup.upload.as_deref() {
+        upload(config, &archive, location).await?;
+    }
+    Ok(archive)
+}
+
+/// Daemon loop running backups on `[backup] schedule`.
+pub async fn run(config: Config) -> Result<()> {
+    let schedule = crate::cron::Schedule::Cron {
+        expr: config.backup.schedule.clone(),
+        tz: None,
+    };
+    loop {
+        let next = crate::cron::next_run_for_schedule(&schedule, Utc::now())?;
+        let wait = (next - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+
+        match run_once(&config).await {
+            Ok(archive) => {
+                crate::health::mark_component_ok(COMPONENT);
+                tracing::info!("Backup written to {}", archive.display());
+            }
+            Err(e) => {
+                crate::health::mark_component_error(COMPONENT, e.to_string());
+                tracing::warn!("Backup failed: {e}");
+            }
+        }
+    }
+}
+
+// ── Restore ─────────────────────────────────────────────────────
+
+/// Where `entry` restores to, or `None` for paths outside the two roots.
+fn restore_target(config: &Config, entry: &str) -> Option<PathBuf> {
+    let (root, rest) = entry.split_once('/')?;
+    let base = match root {
+        "config" => config_dir(config),
+        "workspace" => config.workspace_dir.clone(),
+        _ => return None,
+    };
+    let relative = Path::new(rest);
+    let safe = !rest.is_empty()
+        && relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+    safe.then(|| base.join(relative))
+}
+
+/// List the archive paths in `archive`.
+pub fn list_archive(archive: &Path) -> Result<Vec<String>> {
+    let file =
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    let mut entries = Vec::new();
+    for_each_entry(GzDecoder::new(io::BufReader::new(file)), |path, _| {
+        entries.push(path.to_string());
+        Ok(())
+    })?;
+    Ok(entries)
+}
+
+/// Restore `archive` over the config directory and workspace; returns the
+/// restored archive paths. Files not in the archive are left alone.
+pub fn restore(config: &Config, archive: &Path) -> Result<Vec<String>> {
+    let file =
+        File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    let mut restored = Vec::new();
+    for_each_entry(
+        GzDecoder::new(io::BufReader::new(file)),
+        |path, contents| {
+            let Some(target) = restore_target(config, path) else {
+                tracing::warn!("Skipping unexpected archive entry {path}");
+                return Ok(());
+            };
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let mut partial = target.clone().into_os_string();
+            partial.push(".restoring");
+            let mut out = create_private(Path::new(&partial))?;
+            io::copy(contents, &mut out)?;
+            out.sync_all()?;
+            std::fs::rename(&partial, &target)?;
+            // A leftover journal would be replayed into the restored database.
+            for suffix in ["-wal", "-shm", "-journal"] {
+                let mut sidecar = target.clone().into_os_string();
+                sidecar.push(suffix);
+                let _ = std::fs::remove_file(sidecar);
+            }
+            restored.push(path.to_string());
+            Ok(())
+        },
+    )?;
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(tmp: &Path) -> Config {
+        Config {
+            workspace_dir: tmp.join("workspace"),
+            config_path: tmp.join("config.toml"),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn backup_round_trips_config_databases_and_includes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = test_config(tmp.path());
+        config.backup.include = vec!["notes".into()];
+        std::fs::create_dir_all(config.workspace_dir.join("cron")).unwrap();
+        std::fs::create_dir_all(config.workspace_dir.join("notes")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("conf.d/prompts")).unwrap();
+        std::fs::write(&config.config_path, "default_temperature = 0.5\n").unwrap();
+        std::fs::write(tmp.path().join("conf.d/prompts/style.md"), "Be brief.").unwrap();
+        std::fs::write(config.workspace_dir.join("notes/todo.md"), "- ship").unwrap();
+        std::fs::write(config.workspace_dir.join("scratch.txt"), "skip me").unwrap();
+        let db = config.workspace_dir.join("cron/jobs.db");
+        let conn = rusqlite::Connection::open(&db).unwrap();
+        conn.execute_batch("CREATE TABLE t (v TEXT); INSERT INTO t VALUES ('kept');")
+            .unwrap();
+        drop(conn);
+
+        let archive = create_backup(&config).unwrap();
+        assert!(archive.starts_with(tmp.path().join("backups")));
+        assert_eq!(
+            list_archive(&archive).unwrap(),
+            [
+                "config/conf.d/prompts/style.md",
+                "config/config.toml",
+                "workspace/cron/jobs.db",
+                "workspace/notes/todo.md",
+            ]
+        );
+
+        std::fs::write(&config.config_path, "broken").unwrap();
+        std::fs::remove_file(&db).unwrap();
+        std::fs::write(config.workspace_dir.join("notes/todo.md"), "lost").unwrap();
+
+        let restored = restore(&config, &archive).unwrap();
+        assert_eq!(restored.len(), 4);
+        assert_eq!(
+            std::fs::read_to_string(&config.config_path).unwrap(),
+            "default_temperature = 0.5\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(config.workspace_dir.join("notes/todo.md")).unwrap(),
+            "- ship"
+        );
+        let conn = rusqlite::Connection::open(&db).unwrap();
+        let value: String = conn
+            .query_row("SELECT v FROM t", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(value, "kept");
+    }
+
+    #[test]
+    fn rotate_keeps_newest_archives() {
+        let tmp = tempfile::tempdir().unwrap();
+        for stamp in ["20260101T030000Z", "20260102T030000Z", "20260103T030000Z"] {
+            std::fs::write(
+                tmp.path()
+                    .join(format!("{ARCHIVE_PREFIX}{stamp}{ARCHIVE_SUFFIX}")),
+                "x",
+            )
+            .unwrap();
+        }
+        std::fs::write(tmp.path().join("unrelated.tar.gz"), "x").unwrap();
+
+        let removed = rotate(tmp.path(), 2).unwrap();
+        assert_eq!(removed.len(), 1);
+        assert!(removed[0].ends_with("zeroclaw-backup-20260101T030000Z.tar.gz"));
+        assert!(tmp.path().join("unrelated.tar.gz").exists());
+    }
+
+    #[test]
+    fn restore_rejects_paths_outside_the_roots() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = test_config(tmp.path());
+        assert!(restore_target(&config, "workspace/notes/a.md").is_some());
+        assert!(restore_target(&config, "workspace/../etc/passwd").is_none());
+        assert!(restore_target(&config, "other/file").is_none());
+        assert!(restore_target(&config, "config/").is_none());
+    }
+
+    #[test]
+    fn long_paths_use_the_ustar_prefix() {
+        let path = format!("workspace/{}/{}", "d".repeat(120), "f".repeat(90));
+        let header = tar_header(&path, 3, 0).unwrap();
+        let mut archive = header.to_vec();
+        archive.extend_from_slice(b"abc");
+        archive.resize(archive.len() + padding(3) + 2 * BLOCK, 0);
+
+        let mut seen = Vec::new();
+        for_each_entry(archive.as_slice(), |name, contents| {
+            let mut body = String::new();
+            contents.read_to_string(&mut body)?;
+            seen.push((name.to_string(), body));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(seen, [(path, "abc".to_string())]);
+    }
+}
@@ -0,0 +1,53 @@
+//! Sender allowlist matching shared by channel implementations.
+//!
+//! Every channel gates inbound messages on an `allowed_*` list with the same
+//! semantics: an empty list denies everyone, `"*"` allows everyone, and
+//! otherwise the sender identity must match one of the entries.
+
+/// Exact (case-sensitive) match, for platform user IDs and phone numbers.
+pub fn allows(list: &[String], identity: &str) -> bool {
+    list.iter().any(|entry| entry == "*" || entry == identity)
+}
+
+/// ASCII case-insensitive match, for nicknames and handles.
+pub fn allows_ignore_case(list: &[String], identity: &str) -> bool {
+    list.iter()
+        .any(|entry| entry == "*" || entry.eq_ignore_ascii_case(identity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|e| (*e).to_string()).collect()
+    }
+
+    #[test]
+    fn empty_list_denies_everyone() {
+        assert!(!allows(&[], "U123"));
+        assert!(!allows_ignore_case(&[], "alice"));
+    }
+
+    #[test]
+    fn wildcard_allows_everyone() {
+        assert!(allows(&list(&["*"]), "anyone"));
+        assert!(allows_ignore_case(&list(&["bob", "*"]), "anyone"));
+    }
+
+    #[test]
+    fn exact_match_is_case_sensitive() {
+        let users = list(&["U123"]);
+        assert!(allows(&users, "U123"));
+        assert!(!allows(&users, "u123"));
+        assert!(!allows(&users, "U1234"));
+    }
+
+    #[test]
+    fn ignore_case_match() {
+        let users = list(&["Alice"]);
+        assert!(allows_ignore_case(&users, "alice"));
+        assert!(allows_ignore_case(&users, "ALICE"));
+        assert!(!allows_ignore_case(&users, "alicia"));
+    }
+}
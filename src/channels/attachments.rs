@@ -0,0 +1,270 @@
+//! Files moving between channels, the workspace, and tools.
+//!
+//! Inbound files are saved under `{workspace}/attachments/<origin>/` and
+//! handed to the agent as a marker (`[IMAGE:<path>]` or `[DOCUMENT:<path>]`),
+//! so workspace tools such as `file_read` can open them by path. Outbound, the
+//! agent references workspace files with the same markers; channels that
+//! implement [`Channel::send_attachment`](super::Channel::send_attachment)
+//! upload them instead of showing the marker text.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Largest file accepted from a channel or sent back to one.
+pub const MAX_ATTACHMENT_BYTES: u64 = 20 * 1024 * 1024;
+
+/// A file stored in (or referenced from) the workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    /// Short unique id; also the file-name prefix for stored inbound files.
+    pub id: String,
+    pub mime: String,
+    pub path: PathBuf,
+    /// Where the file came from: a channel name, or `"agent"` for outbound files.
+    pub origin: String,
+}
+
+fn new_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..8].to_string()
+}
+
+impl Attachment {
+    /// Describe an existing file.
+    pub fn from_path(path: impl Into<PathBuf>, origin: &str) -> Self {
+        let path = path.into();
+        Self {
+            id: new_id(),
+            mime: mime_for(&path).to_string(),
+            path,
+            origin: origin.to_string(),
+        }
+    }
+
+    /// File name shown to recipients.
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map_or_else(|| "file".to_string(), |n| n.to_string_lossy().into_owned())
+    }
+
+    pub fn is_image(&self) -> bool {
+        self.mime.starts_with("image/")
+    }
+
+    /// Marker used to reference this file in message text.
+    pub fn marker(&self) -> String {
+        let kind = if self.is_image() { "IMAGE" } else { "DOCUMENT" };
+        format!("[{kind}:{}]", self.path.display())
+    }
+}
+
+/// Best-effort MIME type from the file extension.
+pub fn mime_for(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "pdf" => "application/pdf",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "json" => "application/json",
+        "html" | "htm" => "text/html",
+        "zip" => "application/zip",
+        "gz" | "tgz" => "application/gzip",
+        "tar" => "application/x-tar",
+        "mp3" => "audio/mpeg",
+        "ogg" | "oga" | "opus" => "audio/ogg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Reduce a sender-supplied file name to a safe single path component.
+fn sanitize_file_name(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    let cleaned: String = base
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        "file".to_string()
+    } else {
+        cleaned.chars().take(120).collect()
+    }
+}
+
+/// Save an inbound file under `{workspace}/attachments/<origin>/`.
+pub async fn store(
+    workspace_dir: &Path,
+    origin: &str,
+    file_name: &str,
+    bytes: &[u8],
+) -> Result<Attachment> {
+    if u64::try_from(bytes.len()).unwrap_or(u64::MAX) > MAX_ATTACHMENT_BYTES {
+        anyhow::bail!(
+            "attachment exceeds {} MB limit",
+            MAX_ATTACHMENT_BYTES / (1024 * 1024)
+        );
+    }
+    let dir = workspace_dir
+        .join("attachments")
+        .join(sanitize_file_name(origin));
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .with_context(|| format!("failed to create {}", dir.display()))?;
+
+    let id = new_id();
+    let path = dir.join(format!("{id}_{}", sanitize_file_name(file_name)));
+    tokio::fs::write(&path, bytes)
+        .await
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(Attachment {
+        id,
+        mime: mime_for(&path).to_string(),
+        path,
+        origin: origin.to_string(),
+    })
+}
+
+/// Resolve an outbound marker target to a file inside the workspace.
+/// URLs, missing files, oversized files and paths escaping the workspace
+/// are rejected.
+fn resolve_outbound(workspace_dir: &Path, target: &str) -> Option<PathBuf> {
+    let target = target
+        .trim()
+        .strip_prefix("file://")
+        .unwrap_or(target.trim());
+    if target.starts_with("http://") || target.starts_with("https://") {
+        return None;
+    }
+    let candidate = Path::new(target);
+    let candidate = if candidate.is_absolute() {
+        candidate.to_path_buf()
+    } else {
+        workspace_dir.join(candidate)
+    };
+    let resolved = candidate.canonicalize().ok()?;
+    let workspace = workspace_dir.canonicalize().ok()?;
+    let metadata = std::fs::metadata(&resolved).ok()?;
+    (resolved.starts_with(&workspace)
+        && metadata.is_file()
+        && metadata.len() <= MAX_ATTACHMENT_BYTES)
+        .then_some(resolved)
+}
+
+/// Pull workspace-file markers (`[IMAGE:…]`, `[DOCUMENT:…]`, `[FILE:…]`,
+/// `[VIDEO:…]`, `[AUDIO:…]`, `[VOICE:…]`) out of `text`.
+///
+/// Returns the text without the extracted markers and the attachments they
+/// referenced. Markers that don't resolve to a workspace file stay in the text.
+pub fn extract_outbound(text: &str, workspace_dir: &Path) -> (String, Vec<Attachment>) {
+    const KINDS: &[&str] = &[
+        "IMAGE", "PHOTO", "DOCUMENT", "FILE", "VIDEO", "AUDIO", "VOICE",
+    ];
+    let mut cleaned = String::with_capacity(text.len());
+    let mut attachments = Vec::new();
+    let mut rest = text;
+
+    while let Some(open) = rest.find('[') {
+        cleaned.push_str(&rest[..open]);
+        let after = &rest[open..];
+        let Some(close) = after.find(']') else {
+            cleaned.push_str(after);
+            rest = "";
+            break;
+        };
+        let marker = &after[1..close];
+        let resolved = marker
+            .split_once(':')
+            .filter(|(kind, _)| KINDS.contains(&kind.trim().to_ascii_uppercase().as_str()))
+            .and_then(|(_, target)| resolve_outbound(workspace_dir, target));
+        match resolved {
+            Some(path) => attachments.push(Attachment::from_path(path, "agent")),
+            None => cleaned.push_str(&after[..=close]),
+        }
+        rest = &after[close + 1..];
+    }
+    cleaned.push_str(rest);
+
+    let cleaned = cleaned
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+    (cleaned, attachments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mime_and_marker_follow_extension() {
+        let image = Attachment::from_path("/ws/a/photo.JPG", "telegram");
+        assert_eq!(image.mime, "image/jpeg");
+        assert!(image.marker().starts_with("[IMAGE:"));
+        let doc = Attachment::from_path("/ws/a/report.pdf", "discord");
+        assert_eq!(doc.mime, "application/pdf");
+        assert_eq!(doc.marker(), "[DOCUMENT:/ws/a/report.pdf]");
+        assert_eq!(doc.file_name(), "report.pdf");
+    }
+
+    #[test]
+    fn sanitize_strips_paths_and_odd_characters() {
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_file_name("C:\\tmp\\my file?.txt"), "my_file_.txt");
+        assert_eq!(sanitize_file_name(".hidden"), "hidden");
+        assert_eq!(sanitize_file_name(""), "file");
+    }
+
+    #[tokio::test]
+    async fn store_saves_under_origin_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let att = store(dir.path(), "discord", "notes.txt", b"hello")
+            .await
+            .unwrap();
+        assert!(att.path.starts_with(dir.path().join("attachments/discord")));
+        assert!(att.path.to_string_lossy().ends_with("_notes.txt"));
+        assert_eq!(att.mime, "text/plain");
+        assert_eq!(std::fs::read(&att.path).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn extract_outbound_only_takes_workspace_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("chart.png"), b"png").unwrap();
+        let outside = tempfile::NamedTempFile::new().unwrap();
+
+        let text = format!(
+            "Here you go [IMAGE:chart.png]\n[DOCUMENT:{}] [IMAGE:https://x.io/a.png] [note]",
+            outside.path().display()
+        );
+        let (cleaned, attachments) = extract_outbound(&text, dir.path());
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].file_name(), "chart.png");
+        assert_eq!(attachments[0].origin, "agent");
+        assert!(cleaned.starts_with("Here you go\n[DOCUMENT:"));
+        assert!(cleaned.contains("[IMAGE:https://x.io/a.png] [note]"));
+    }
+}
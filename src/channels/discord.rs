@@ -1,9 +1,11 @@
+use super::attachments::{self, Attachment};
 use super::traits::{Channel, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
 use serde_json::json;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tokio_tungstenite::tungstenite::Message;
 use uuid::Uuid;
 
@@ -15,6 +17,7 @@ pub struct DiscordChannel {
     listen_to_bots: bool,
     mention_only: bool,
     typing_handles: Mutex<HashMap<String, tokio::task::JoinHandle<()>>>,
+    workspace_dir: Option<PathBuf>,
 }
 
 impl DiscordChannel {
@@ -32,9 +35,16 @@ impl DiscordChannel {
             listen_to_bots,
             mention_only,
             typing_handles: Mutex::new(HashMap::new()),
+            workspace_dir: None,
         }
     }
 
+    /// Save non-text attachments under the workspace so tools can open them.
+    pub fn with_workspace_dir(mut self, dir: PathBuf) -> Self {
+        self.workspace_dir = Some(dir);
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.discord")
     }
@@ -43,7 +53,7 @@ impl DiscordChannel {
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
     fn is_user_allowed(&self, user_id: &str) -> bool {
-        self.allowed_users.iter().any(|u| u == "*" || u == user_id)
+        super::allowlist::allows(&self.allowed_users, user_id)
     }
 
     fn bot_user_id_from_token(token: &str) -> Option<String> {
@@ -56,11 +66,13 @@ impl DiscordChannel {
 /// Process Discord message attachments and return a string to append to the
 /// agent message context.
 ///
-/// Only `text/*` MIME types are fetched and inlined. All other types are
-/// silently skipped. Fetch errors are logged as warnings.
+/// `text/*` attachments are fetched and inlined. Other types are saved to the
+/// workspace and referenced by marker when `workspace` is set, and skipped
+/// otherwise. Fetch errors are logged as warnings.
 async fn process_attachments(
     attachments: &[serde_json::Value],
     client: &reqwest::Client,
+    workspace: Option<&Path>,
 ) -> String {
     let mut parts: Vec<String> = Vec::new();
     for att in attachments {
@@ -90,6 +102,33 @@ async fn process_attachments(
                     tracing::warn!(name, error = %e, "discord attachment fetch error");
                 }
             }
+        } else if let Some(workspace) = workspace {
+            let size = att.get("size").and_then(serde_json::Value::as_u64);
+            if size.is_some_and(|size| size > attachments::MAX_ATTACHMENT_BYTES) {
+                tracing::warn!(name, "discord: attachment too large, skipping");
+                continue;
+            }
+            let bytes = match client.get(url).send().await {
+                Ok(resp) if resp.status().is_success() => match resp.bytes().await {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        tracing::warn!(name, error = %e, "discord attachment read error");
+                        continue;
+                    }
+                },
+                Ok(resp) => {
+                    tracing::warn!(name, status = %resp.status(), "discord attachment fetch failed");
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!(name, error = %e, "discord attachment fetch error");
+                    continue;
+                }
+            };
+            match attachments::store(workspace, "discord", name, &bytes).await {
+                Ok(stored) => parts.push(stored.marker()),
+                Err(e) => tracing::warn!(name, error = %e, "discord attachment save failed"),
+            }
         } else {
             tracing::debug!(
                 name,
@@ -318,6 +357,47 @@ impl Channel for DiscordChannel {
         Ok(())
     }
 
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
+    async fn send_attachment(
+        &self,
+        recipient: &str,
+        attachment: &Attachment,
+        _thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let bytes = tokio::fs::read(&attachment.path).await?;
+        let file_name = attachment.file_name();
+        let part = reqwest::multipart::Part::bytes(bytes)
+            .file_name(file_name.clone())
+            .mime_str(&attachment.mime)?;
+        let payload = json!({ "attachments": [{ "id": 0, "filename": file_name }] });
+        let form = reqwest::multipart::Form::new()
+            .text("payload_json", payload.to_string())
+            .part("files[0]", part);
+
+        let resp = self
+            .http_client()
+            .post(format!(
+                "https://discord.com/api/v10/channels/{recipient}/messages"
+            ))
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Discord file upload failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
     #[allow(clippy::too_many_lines)]
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         let bot_user_id = Self::bot_user_id_from_token(&self.bot_token).unwrap_or_default();
@@ -493,7 +573,12 @@ impl Channel for DiscordChannel {
                             .and_then(|a| a.as_array())
                             .cloned()
                             .unwrap_or_default();
-                        process_attachments(&atts, &self.http_client()).await
+                        process_attachments(
+                            &atts,
+                            &self.http_client(),
+                            self.workspace_dir.as_deref(),
+                        )
+                        .await
                     };
                     let final_content = if attachment_text.is_empty() {
                         clean_content
@@ -1203,7 +1288,7 @@ mod tests {
     #[tokio::test]
     async fn process_attachments_empty_list_returns_empty() {
         let client = reqwest::Client::new();
-        let result = process_attachments(&[], &client).await;
+        let result = process_attachments(&[], &client, None).await;
         assert!(result.is_empty());
     }
 
@@ -1215,7 +1300,13 @@ mod tests {
             "filename": "doc.pdf",
             "content_type": "application/pdf"
         })];
-        let result = process_attachments(&attachments, &client).await;
+        let result = process_attachments(&attachments, &client, None).await;
         assert!(result.is_empty());
     }
+
+    #[test]
+    fn discord_supports_attachments() {
+        let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
+        assert!(ch.supports_attachments());
+    }
 }
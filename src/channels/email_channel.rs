@@ -14,7 +14,7 @@ use async_imap::types::Fetch;
 use async_imap::Session;
 use async_trait::async_trait;
 use futures_util::TryStreamExt;
-use lettre::message::SinglePart;
+use lettre::message::MultiPart;
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use mail_parser::{MessageParser, MimeHeaders};
@@ -33,6 +33,7 @@ use tokio_rustls::TlsConnector;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use super::format::FormatProfile;
 use super::traits::{Channel, ChannelMessage, SendMessage};
 
 /// Email channel configuration
@@ -528,7 +529,10 @@ impl Channel for EmailChannel {
             .from(self.config.from_address.parse()?)
             .to(message.recipient.parse()?)
             .subject(subject)
-            .singlepart(SinglePart::plain(body.to_string()))?;
+            .multipart(MultiPart::alternative_plain_html(
+                FormatProfile::PlainText.render(body),
+                FormatProfile::Html.render(body),
+            ))?;
 
         let transport = self.create_smtp_transport()?;
         transport.send(&email)?;
@@ -0,0 +1,428 @@
+//! Per-destination rendering of agent output.
+//!
+//! The agent writes Markdown. Before a reply leaves the channel layer it is
+//! adapted to what the destination can display: Markdown-native channels get
+//! it unchanged, Slack gets `mrkdwn`, and SMS-like channels get plain text.
+//! Email is sent as multipart plain text + HTML by the email channel itself,
+//! using [`FormatProfile::PlainText`] and [`FormatProfile::Html`].
+
+use regex::{Captures, Regex};
+use std::fmt::Write;
+use std::sync::LazyLock;
+
+static LINK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"!?\[([^\]]+)\]\(([^)\s]+)\)").unwrap());
+static BOLD_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\*\*([^*]+?)\*\*|__([^_]+?)__").unwrap());
+static ITALIC_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\*([^*\s](?:[^*]*[^*\s])?)\*").unwrap());
+static STRIKE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"~~([^~]+?)~~").unwrap());
+static HEADING_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(#{1,6})\s+(.+?)\s*#*\s*$").unwrap());
+static BULLET_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(\s*)[*+-]\s+(.*)$").unwrap());
+static URL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"https?://[^\s<>()\]|]+").unwrap());
+
+/// How a destination displays text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatProfile {
+    /// Telegram, Matrix, Discord, Mattermost, … — rendered natively or
+    /// converted by the channel itself.
+    Markdown,
+    /// Slack `mrkdwn`.
+    Slack,
+    /// IRC, SMS, Signal, WhatsApp, iMessage, ntfy, … — no markup at all.
+    PlainText,
+    /// HTML fragment, for the email body.
+    Html,
+}
+
+impl FormatProfile {
+    /// Profile applied to replies on the channel named `channel`.
+    pub fn for_channel(channel: &str) -> Self {
+        match channel {
+            "slack" => Self::Slack,
+            "irc" | "signal" | "whatsapp" | "imessage" | "linq" | "nostr" | "clawdtalk" | "sms"
+            | "ntfy" => Self::PlainText,
+            _ => Self::Markdown,
+        }
+    }
+
+    /// Render Markdown `text` for this profile.
+    pub fn render(self, text: &str) -> String {
+        match self {
+            Self::Markdown => text.to_string(),
+            Self::Slack => render_lines(text, true, slack_line, slack_escape),
+            Self::PlainText => render_lines(text, false, plain_line, str::to_string),
+            Self::Html => render_html(text),
+        }
+    }
+}
+
+/// Render `text` for the channel named `channel`.
+pub fn render_for_channel(channel: &str, text: &str) -> String {
+    FormatProfile::for_channel(channel).render(text)
+}
+
+/// Link-unfurl hint: previews are only worth showing when a message points at
+/// a single link; several previews bury the actual answer.
+pub fn should_unfurl(text: &str) -> bool {
+    let mut urls: Vec<&str> = URL_RE.find_iter(text).map(|m| m.as_str()).collect();
+    urls.sort_unstable();
+    urls.dedup();
+    urls.len() == 1
+}
+
+/// Split `text` into chunks of at most `max_chars` characters on line
+/// boundaries, hard-splitting lines that are longer than a chunk. A fenced
+/// code block cut across chunks is closed at the end of one chunk and
+/// reopened at the start of the next.
+pub fn split(text: &str, max_chars: usize) -> Vec<String> {
+    const FENCE: &str = "```";
+    let max_chars = max_chars.max(1);
+    // Closing "\n```" plus reopening "```\n" must fit alongside content.
+    let fence_aware = max_chars >= 24;
+    let line_budget = if fence_aware {
+        max_chars - 8
+    } else {
+        max_chars
+    };
+
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+    let mut in_fence = false;
+
+    for line in text.lines() {
+        let is_fence = line.trim_start().starts_with(FENCE);
+        for piece in hard_split(line, line_budget) {
+            let piece_len = piece.chars().count();
+            let separator = usize::from(!current.is_empty());
+            let reserve = if fence_aware && in_fence && !is_fence {
+                FENCE.len() + 1
+            } else {
+                0
+            };
+            if !current.is_empty() && current_len + separator + piece_len + reserve > max_chars {
+                if fence_aware && in_fence {
+                    current.push('\n');
+                    current.push_str(FENCE);
+                }
+                chunks.push(std::mem::take(&mut current));
+                current_len = 0;
+                if fence_aware && in_fence {
+                    current.push_str(FENCE);
+                    current_len = FENCE.len();
+                }
+            }
+            if !current.is_empty() {
+                current.push('\n');
+                current_len += 1;
+            }
+            current.push_str(&piece);
+            current_len += piece_len;
+        }
+        if is_fence {
+            in_fence = !in_fence;
+        }
+    }
+
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+fn hard_split(line: &str, max_chars: usize) -> Vec<String> {
+    if line.is_empty() {
+        return vec![String::new()];
+    }
+    let chars: Vec<char> = line.chars().collect();
+    chars
+        .chunks(max_chars)
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+/// Apply `prose` to every line outside fenced code blocks and `code` to the
+/// lines inside them. Fence lines are kept without their language tag when
+/// `keep_fences` is set, and dropped otherwise.
+fn render_lines(
+    text: &str,
+    keep_fences: bool,
+    prose: impl Fn(&str) -> String,
+    code: impl Fn(&str) -> String,
+) -> String {
+    let mut out = Vec::new();
+    let mut in_fence = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            if keep_fences {
+                out.push("```".to_string());
+            }
+            continue;
+        }
+        out.push(if in_fence { code(line) } else { prose(line) });
+    }
+    out.join("\n")
+}
+
+/// Apply `prose` to the parts of a line outside inline `code` spans.
+fn map_inline(line: &str, prose: impl Fn(&str) -> String, code: impl Fn(&str) -> String) -> String {
+    let parts: Vec<&str> = line.split('`').collect();
+    if parts.len().is_multiple_of(2) {
+        // Unbalanced backticks: treat the whole line as prose.
+        return prose(line);
+    }
+    parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| if i % 2 == 1 { code(part) } else { prose(part) })
+        .collect()
+}
+
+fn plain_line(line: &str) -> String {
+    if let Some(caps) = HEADING_RE.captures(line) {
+        return plain_inline(&caps[2]);
+    }
+    if let Some(caps) = BULLET_RE.captures(line) {
+        return format!("{}- {}", &caps[1], plain_inline(&caps[2]));
+    }
+    plain_inline(line)
+}
+
+fn plain_inline(line: &str) -> String {
+    map_inline(
+        line,
+        |prose| {
+            let text = LINK_RE.replace_all(prose, |caps: &Captures| {
+                if caps[1] == caps[2] {
+                    caps[2].to_string()
+                } else {
+                    format!("{} ({})", &caps[1], &caps[2])
+                }
+            });
+            let text = BOLD_RE.replace_all(&text, |caps: &Captures| {
+                caps.get(1)
+                    .or_else(|| caps.get(2))
+                    .unwrap()
+                    .as_str()
+                    .to_string()
+            });
+            let text = ITALIC_RE.replace_all(&text, "$1");
+            STRIKE_RE.replace_all(&text, "$1").into_owned()
+        },
+        str::to_string,
+    )
+}
+
+fn slack_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn slack_line(line: &str) -> String {
+    if let Some(caps) = HEADING_RE.captures(line) {
+        return format!("*{}*", slack_inline(&caps[2]));
+    }
+    if let Some(caps) = BULLET_RE.captures(line) {
+        return format!("{}• {}", &caps[1], slack_inline(&caps[2]));
+    }
+    // Markdown and Slack share the `>` quote prefix; keep it unescaped.
+    if let Some(quoted) = line.strip_prefix("> ") {
+        return format!("> {}", slack_inline(quoted));
+    }
+    slack_inline(line)
+}
+
+fn slack_inline(line: &str) -> String {
+    map_inline(
+        line,
+        |prose| {
+            let text = slack_escape(prose);
+            let text = LINK_RE.replace_all(&text, |caps: &Captures| {
+                format!("<{}|{}>", &caps[2], &caps[1])
+            });
+            // Bold goes through a placeholder so the italic pass can't see it.
+            let text = BOLD_RE.replace_all(&text, |caps: &Captures| {
+                let inner = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+                format!("\u{1}{inner}\u{1}")
+            });
+            let text = ITALIC_RE.replace_all(&text, "_${1}_");
+            let text = STRIKE_RE.replace_all(&text, "~${1}~");
+            text.replace('\u{1}', "*")
+        },
+        |code| format!("`{}`", slack_escape(code)),
+    )
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html_inline(line: &str) -> String {
+    map_inline(
+        line,
+        |prose| {
+            let text = html_escape(prose);
+            let text = LINK_RE.replace_all(&text, r#"<a href="$2">$1</a>"#);
+            let text = BOLD_RE.replace_all(&text, |caps: &Captures| {
+                let inner = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+                format!("<strong>{inner}</strong>")
+            });
+            let text = ITALIC_RE.replace_all(&text, "<em>$1</em>");
+            STRIKE_RE.replace_all(&text, "<del>$1</del>").into_owned()
+        },
+        |code| format!("<code>{}</code>", html_escape(code)),
+    )
+}
+
+fn render_html(text: &str) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut list_open = false;
+    let mut code: Option<Vec<String>> = None;
+
+    let flush_paragraph = |out: &mut String, paragraph: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            let _ = writeln!(out, "<p>{}</p>", paragraph.join("<br>\n"));
+            paragraph.clear();
+        }
+    };
+    let close_list = |out: &mut String, list_open: &mut bool| {
+        if *list_open {
+            out.push_str("</ul>\n");
+            *list_open = false;
+        }
+    };
+
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            if let Some(lines) = code.take() {
+                let _ = writeln!(out, "<pre><code>{}</code></pre>", lines.join("\n"));
+            } else {
+                flush_paragraph(&mut out, &mut paragraph);
+                close_list(&mut out, &mut list_open);
+                code = Some(Vec::new());
+            }
+            continue;
+        }
+        if let Some(lines) = code.as_mut() {
+            lines.push(html_escape(line));
+            continue;
+        }
+        if line.trim().is_empty() {
+            flush_paragraph(&mut out, &mut paragraph);
+            close_list(&mut out, &mut list_open);
+        } else if let Some(caps) = HEADING_RE.captures(line) {
+            flush_paragraph(&mut out, &mut paragraph);
+            close_list(&mut out, &mut list_open);
+            let level = caps[1].len();
+            let _ = writeln!(out, "<h{level}>{}</h{level}>", html_inline(&caps[2]));
+        } else if let Some(caps) = BULLET_RE.captures(line) {
+            flush_paragraph(&mut out, &mut paragraph);
+            if !list_open {
+                out.push_str("<ul>\n");
+                list_open = true;
+            }
+            let _ = writeln!(out, "<li>{}</li>", html_inline(&caps[2]));
+        } else {
+            close_list(&mut out, &mut list_open);
+            paragraph.push(html_inline(line));
+        }
+    }
+
+    // An unterminated fence still renders as code.
+    if let Some(lines) = code {
+        let _ = writeln!(out, "<pre><code>{}</code></pre>", lines.join("\n"));
+    }
+    flush_paragraph(&mut out, &mut paragraph);
+    close_list(&mut out, &mut list_open);
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_by_channel() {
+        assert_eq!(
+            FormatProfile::for_channel("telegram"),
+            FormatProfile::Markdown
+        );
+        assert_eq!(
+            FormatProfile::for_channel("matrix"),
+            FormatProfile::Markdown
+        );
+        assert_eq!(FormatProfile::for_channel("slack"), FormatProfile::Slack);
+        assert_eq!(FormatProfile::for_channel("irc"), FormatProfile::PlainText);
+        assert_eq!(FormatProfile::for_channel("email"), FormatProfile::Markdown);
+    }
+
+    #[test]
+    fn plain_text_strips_markup_but_keeps_code() {
+        let markdown = "## Status\n**Disk** is *fine*, see [dashboard](https://nas.lan).\n* one\n```sh\ndf -h **\n```\nRun `ls **`.";
+        assert_eq!(
+            FormatProfile::PlainText.render(markdown),
+            "Status\nDisk is fine, see dashboard (https://nas.lan).\n- one\ndf -h **\nRun ls **."
+        );
+    }
+
+    #[test]
+    fn slack_converts_to_mrkdwn() {
+        let markdown = "# Title\n**bold** and *italic* and ~~gone~~ <tag>\n- [docs](https://x.io)\n```rust\nlet a = 1 < 2;\n```";
+        assert_eq!(
+            FormatProfile::Slack.render(markdown),
+            "*Title*\n*bold* and _italic_ and ~gone~ &lt;tag&gt;\n• <https://x.io|docs>\n```\nlet a = 1 &lt; 2;\n```"
+        );
+    }
+
+    #[test]
+    fn html_renders_blocks() {
+        let markdown = "# Hi\nline one\nline **two**\n\n- a\n- b\n\n```\n<x>\n```";
+        assert_eq!(
+            FormatProfile::Html.render(markdown),
+            "<h1>Hi</h1>\n<p>line one<br>\nline <strong>two</strong></p>\n<ul>\n<li>a</li>\n<li>b</li>\n</ul>\n<pre><code>&lt;x&gt;</code></pre>"
+        );
+    }
+
+    #[test]
+    fn arithmetic_asterisks_are_not_italic() {
+        assert_eq!(FormatProfile::PlainText.render("2 * 3 * 4"), "2 * 3 * 4");
+    }
+
+    #[test]
+    fn split_respects_limit_and_reopens_fences() {
+        let text = format!(
+            "intro\n```\n{}\n```\noutro",
+            vec!["code line"; 10].join("\n")
+        );
+        let chunks = split(&text, 40);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= 40, "{chunk:?}");
+            assert_eq!(chunk.matches("```").count() % 2, 0, "{chunk:?}");
+        }
+    }
+
+    #[test]
+    fn split_hard_splits_long_lines() {
+        let chunks = split(&"x".repeat(50), 20);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|c| c.len() <= 20));
+        assert!(split("", 10).is_empty());
+    }
+
+    #[test]
+    fn unfurl_only_for_a_single_link() {
+        assert!(should_unfurl("see https://a.io/x"));
+        assert!(should_unfurl("https://a.io/x and again https://a.io/x"));
+        assert!(!should_unfurl("no links"));
+        assert!(!should_unfurl("https://a.io and https://b.io"));
+    }
+}
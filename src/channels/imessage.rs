@@ -22,12 +22,7 @@ impl IMessageChannel {
     }
 
     fn is_contact_allowed(&self, sender: &str) -> bool {
-        if self.allowed_contacts.iter().any(|u| u == "*") {
-            return true;
-        }
-        self.allowed_contacts
-            .iter()
-            .any(|u| u.eq_ignore_ascii_case(sender))
+        super::allowlist::allows_ignore_case(&self.allowed_contacts, sender)
     }
 }
 
@@ -2,6 +2,7 @@ use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::sync::{mpsc, Mutex};
 
@@ -20,6 +21,8 @@ static MSG_SEQ: AtomicU64 = AtomicU64::new(0);
 /// Connects to an IRC server using TLS, joins configured channels,
 /// and forwards PRIVMSG messages to the `ZeroClaw` message bus.
 /// Supports both channel messages and private messages (DMs).
+/// Outgoing lines are paced by [`FloodControl`] so long replies don't get
+/// the bot kicked for excess flood.
 pub struct IrcChannel {
     server: String,
     port: u16,
@@ -31,8 +34,11 @@ pub struct IrcChannel {
     nickserv_password: Option<String>,
     sasl_password: Option<String>,
     verify_tls: bool,
+    mention_only: bool,
     /// Shared write half of the TLS stream for sending messages.
     writer: Arc<Mutex<Option<WriteHalf>>>,
+    /// Send pacing; only touched while `writer` is locked.
+    flood: parking_lot::Mutex<FloodControl>,
 }
 
 type WriteHalf = tokio::io::WriteHalf<tokio_rustls::client::TlsStream<tokio::net::TcpStream>>;
@@ -49,6 +55,77 @@ Use short lines. Avoid walls of text.]\n";
 /// Reserved bytes for the server-prepended sender prefix (`:nick!user@host `).
 const SENDER_PREFIX_RESERVE: usize = 64;
 
+/// Client-side token bucket for outgoing lines.
+///
+/// Up to `burst` lines go out back-to-back; after that one line is released
+/// per `interval` as the bucket refills. A zero interval disables pacing.
+#[derive(Debug)]
+struct FloodControl {
+    burst: f64,
+    interval: Duration,
+    available: f64,
+    last_refill: Instant,
+}
+
+impl FloodControl {
+    fn new(burst: u32, interval: Duration) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            burst,
+            interval,
+            available: burst,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take one token and return how long the caller must wait before sending.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        if self.interval.is_zero() {
+            return Duration::ZERO;
+        }
+        let interval = self.interval.as_secs_f64();
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.available = (self.available + elapsed / interval).min(self.burst);
+        self.last_refill = now;
+        self.available -= 1.0;
+        if self.available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.available * interval)
+        }
+    }
+}
+
+/// Check whether a channel message is addressed to `nick`.
+///
+/// `nick: text`, `nick, text` and `@nick text` are stripped down to `text`;
+/// a bare mention elsewhere in the line keeps the full text. Returns `None`
+/// when the bot is not addressed at all.
+fn addressed_text(text: &str, nick: &str) -> Option<String> {
+    let trimmed = text.trim_start();
+    let body = trimmed.strip_prefix('@').unwrap_or(trimmed);
+    if body.len() >= nick.len()
+        && body.is_char_boundary(nick.len())
+        && body[..nick.len()].eq_ignore_ascii_case(nick)
+    {
+        let rest = &body[nick.len()..];
+        match rest.chars().next() {
+            None => return Some(String::new()),
+            Some(':' | ',') => return Some(rest[1..].trim().to_string()),
+            Some(c) if c.is_whitespace() => return Some(rest.trim().to_string()),
+            Some(_) => {}
+        }
+    }
+
+    let is_nick_char = |c: char| c.is_ascii_alphanumeric() || "-_[]\\`^{}|".contains(c);
+    let mentioned = text
+        .split(|c: char| !is_nick_char(c))
+        .any(|word| word.eq_ignore_ascii_case(nick));
+    mentioned.then(|| text.trim().to_string())
+}
+
 /// A parsed IRC message.
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct IrcMessage {
@@ -237,6 +314,9 @@ pub struct IrcChannelConfig {
     pub nickserv_password: Option<String>,
     pub sasl_password: Option<String>,
     pub verify_tls: bool,
+    pub mention_only: bool,
+    pub flood_burst: u32,
+    pub flood_interval_ms: u64,
 }
 
 impl IrcChannel {
@@ -253,17 +333,17 @@ impl IrcChannel {
             nickserv_password: cfg.nickserv_password,
             sasl_password: cfg.sasl_password,
             verify_tls: cfg.verify_tls,
+            mention_only: cfg.mention_only,
             writer: Arc::new(Mutex::new(None)),
+            flood: parking_lot::Mutex::new(FloodControl::new(
+                cfg.flood_burst,
+                Duration::from_millis(cfg.flood_interval_ms),
+            )),
         }
     }
 
     fn is_user_allowed(&self, nick: &str) -> bool {
-        if self.allowed_users.iter().any(|u| u == "*") {
-            return true;
-        }
-        self.allowed_users
-            .iter()
-            .any(|u| u.eq_ignore_ascii_case(nick))
+        super::allowlist::allows_ignore_case(&self.allowed_users, nick)
     }
 
     /// Create a TLS connection to the IRC server.
@@ -363,6 +443,10 @@ impl Channel for IrcChannel {
         let chunks = split_message(&message.content, max_payload);
 
         for chunk in chunks {
+            let wait = self.flood.lock().reserve(Instant::now());
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
             Self::send_raw(writer, &format!("PRIVMSG {} :{chunk}", message.recipient)).await?;
         }
 
@@ -562,6 +646,14 @@ impl Channel for IrcChannel {
                     } else {
                         sender_nick.to_string()
                     };
+                    let text = if is_channel && self.mention_only {
+                        match addressed_text(text, &current_nick) {
+                            Some(text) if !text.is_empty() => text,
+                            _ => continue,
+                        }
+                    } else {
+                        text.to_string()
+                    };
                     let content = if is_channel {
                         format!("{IRC_STYLE_PREFIX}<{sender_nick}> {text}")
                     } else {
@@ -828,6 +920,9 @@ mod tests {
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            mention_only: false,
+            flood_burst: 4,
+            flood_interval_ms: 2000,
         });
         assert!(ch.is_user_allowed("alice"));
         assert!(ch.is_user_allowed("bob"));
@@ -847,6 +942,9 @@ mod tests {
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            mention_only: false,
+            flood_burst: 4,
+            flood_interval_ms: 2000,
         });
         assert!(ch.is_user_allowed("alice"));
         assert!(ch.is_user_allowed("ALICE"));
@@ -866,6 +964,9 @@ mod tests {
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            mention_only: false,
+            flood_burst: 4,
+            flood_interval_ms: 2000,
         });
         assert!(!ch.is_user_allowed("anyone"));
     }
@@ -885,6 +986,9 @@ mod tests {
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            mention_only: false,
+            flood_burst: 4,
+            flood_interval_ms: 2000,
         });
         assert_eq!(ch.username, "mybot");
     }
@@ -902,6 +1006,9 @@ mod tests {
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            mention_only: false,
+            flood_burst: 4,
+            flood_interval_ms: 2000,
         });
         assert_eq!(ch.username, "customuser");
         assert_eq!(ch.nickname, "mybot");
@@ -926,6 +1033,9 @@ mod tests {
             nickserv_password: Some("nspass".into()),
             sasl_password: Some("saslpass".into()),
             verify_tls: false,
+            mention_only: false,
+            flood_burst: 4,
+            flood_interval_ms: 2000,
         });
         assert_eq!(ch.server, "irc.example.com");
         assert_eq!(ch.port, 6697);
@@ -939,6 +1049,73 @@ mod tests {
         assert!(!ch.verify_tls);
     }
 
+    // ── Addressing ──────────────────────────────────────────
+
+    #[test]
+    fn addressed_prefix_is_stripped() {
+        assert_eq!(
+            addressed_text("zcbot: status?", "zcbot").as_deref(),
+            Some("status?")
+        );
+        assert_eq!(
+            addressed_text("ZCBot, uptime", "zcbot").as_deref(),
+            Some("uptime")
+        );
+        assert_eq!(
+            addressed_text("@zcbot ping", "zcbot").as_deref(),
+            Some("ping")
+        );
+    }
+
+    #[test]
+    fn addressed_mention_keeps_full_text() {
+        assert_eq!(
+            addressed_text("ask zcbot about it", "zcbot").as_deref(),
+            Some("ask zcbot about it")
+        );
+    }
+
+    #[test]
+    fn not_addressed_returns_none() {
+        assert!(addressed_text("hello everyone", "zcbot").is_none());
+        assert!(addressed_text("zcbot2: not you", "zcbot").is_none());
+        assert!(addressed_text("zcbotty is here", "zcbot").is_none());
+    }
+
+    // ── Flood control ───────────────────────────────────────
+
+    #[test]
+    fn flood_burst_then_paced() {
+        let start = Instant::now();
+        let mut flood = FloodControl::new(2, Duration::from_secs(1));
+        flood.last_refill = start;
+        assert_eq!(flood.reserve(start), Duration::ZERO);
+        assert_eq!(flood.reserve(start), Duration::ZERO);
+        assert_eq!(flood.reserve(start), Duration::from_secs(1));
+        assert_eq!(flood.reserve(start), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn flood_refills_over_time() {
+        let start = Instant::now();
+        let mut flood = FloodControl::new(1, Duration::from_secs(1));
+        flood.last_refill = start;
+        assert_eq!(flood.reserve(start), Duration::ZERO);
+        assert_eq!(
+            flood.reserve(start + Duration::from_secs(5)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn flood_zero_interval_disables_pacing() {
+        let mut flood = FloodControl::new(1, Duration::ZERO);
+        let now = Instant::now();
+        for _ in 0..10 {
+            assert_eq!(flood.reserve(now), Duration::ZERO);
+        }
+    }
+
     // ── Config serde ────────────────────────────────────────
 
     #[test]
@@ -956,6 +1133,9 @@ mod tests {
             nickserv_password: Some("secret".into()),
             sasl_password: None,
             verify_tls: Some(true),
+            mention_only: true,
+            flood_burst: 2,
+            flood_interval_ms: 500,
         };
 
         let toml_str = toml::to_string(&config).unwrap();
@@ -970,6 +1150,9 @@ mod tests {
         assert_eq!(parsed.nickserv_password.as_deref(), Some("secret"));
         assert!(parsed.sasl_password.is_none());
         assert_eq!(parsed.verify_tls, Some(true));
+        assert!(parsed.mention_only);
+        assert_eq!(parsed.flood_burst, 2);
+        assert_eq!(parsed.flood_interval_ms, 500);
     }
 
     #[test]
@@ -991,6 +1174,9 @@ nickname = "bot"
         assert!(parsed.nickserv_password.is_none());
         assert!(parsed.sasl_password.is_none());
         assert!(parsed.verify_tls.is_none());
+        assert!(!parsed.mention_only);
+        assert_eq!(parsed.flood_burst, 4);
+        assert_eq!(parsed.flood_interval_ms, 2000);
     }
 
     #[test]
@@ -1016,6 +1202,9 @@ nickname = "bot"
             nickserv_password: None,
             sasl_password: None,
             verify_tls: true,
+            mention_only: false,
+            flood_burst: 4,
+            flood_interval_ms: 2000,
         })
     }
 }
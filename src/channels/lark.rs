@@ -705,7 +705,7 @@ impl LarkChannel {
 
     /// Check if a user open_id is allowed
     fn is_user_allowed(&self, open_id: &str) -> bool {
-        self.allowed_users.iter().any(|u| u == "*" || u == open_id)
+        super::allowlist::allows(&self.allowed_users, open_id)
     }
 
     /// Get or refresh tenant access token
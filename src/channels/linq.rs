@@ -29,7 +29,7 @@ impl LinqChannel {
 
     /// Check if a sender phone number is allowed (E.164 format: +1234567890)
     fn is_sender_allowed(&self, phone: &str) -> bool {
-        self.allowed_senders.iter().any(|n| n == "*" || n == phone)
+        super::allowlist::allows(&self.allowed_senders, phone)
     }
 
     /// Get the bot's phone number
@@ -48,7 +48,7 @@ impl MattermostChannel {
     /// Check if a user ID is in the allowlist.
     /// Empty list means deny everyone. "*" means allow everyone.
     fn is_user_allowed(&self, user_id: &str) -> bool {
-        self.allowed_users.iter().any(|u| u == "*" || u == user_id)
+        super::allowlist::allows(&self.allowed_users, user_id)
     }
 
     /// Get the bot's own user ID and username so we can ignore our own messages
@@ -12,13 +12,18 @@
 //! # Extension
 //!
 //! To add a new channel, implement [`Channel`] in a new submodule and wire it into
-//! [`start_channels`]. See `AGENTS.md` §7.2 for the full change playbook.
+//! [`start_channels`]. Gate inbound senders with [`allowlist::allows`] (or
+//! [`allowlist::allows_ignore_case`]) so every channel shares the same empty/`"*"`
+//! semantics. See `AGENTS.md` §7.2 for the full change playbook.
 
+pub mod allowlist;
+pub mod attachments;
 pub mod clawdtalk;
 pub mod cli;
 pub mod dingtalk;
 pub mod discord;
 pub mod email_channel;
+pub mod format;
 pub mod imessage;
 pub mod irc;
 #[cfg(feature = "channel-lark")]
@@ -200,7 +205,7 @@ struct ChannelRuntimeContext {
     provider: Arc<dyn Provider>,
     default_provider: Arc<String>,
     memory: Arc<dyn Memory>,
-    tools_registry: Arc<Vec<Box<dyn Tool>>>,
+    tools_registry: Arc<tools::ToolRegistry>,
     observer: Arc<dyn Observer>,
     system_prompt: Arc<String>,
     model: Arc<String>,
@@ -221,6 +226,10 @@ struct ChannelRuntimeContext {
     multimodal: crate::config::MultimodalConfig,
     hooks: Option<Arc<crate::hooks::HookRunner>>,
     non_cli_excluded_tools: Arc<Vec<String>>,
+    conf_d: Arc<crate::config::confd::ConfDWatcher>,
+    facts: crate::config::FactsConfig,
+    analytics: crate::config::AnalyticsConfig,
+    locale: crate::config::LocaleConfig,
 }
 
 #[derive(Clone)]
@@ -1561,6 +1570,7 @@ async fn process_channel_message(
 
     println!("  ⏳ Processing message...");
     let started_at = Instant::now();
+    let turn_started_at = chrono::Utc::now();
 
     let had_prior_history = ctx
         .conversation_histories
@@ -1587,6 +1597,10 @@ async fn process_channel_message(
     if !had_prior_history {
         let memory_context =
             build_memory_context(ctx.memory.as_ref(), &msg.content, ctx.min_relevance_score).await;
+        let memory_context = format!(
+            "{memory_context}{}",
+            crate::facts::prompt_context(&ctx.workspace_dir, &ctx.facts, &ctx.locale, &msg.content)
+        );
         if let Some(last_turn) = prior_turns.last_mut() {
             if last_turn.role == "user" && !memory_context.is_empty() {
                 last_turn.content = format!("{memory_context}{}", msg.content);
@@ -1594,7 +1608,13 @@ async fn process_channel_message(
         }
     }
 
-    let system_prompt = build_channel_system_prompt(ctx.system_prompt.as_str(), &msg.channel);
+    let system_prompt = crate::preferences::apply_to_prompt(
+        &ctx.workspace_dir,
+        &ctx.conf_d.apply_to_prompt(&build_channel_system_prompt(
+            &crate::locale::with_current_datetime(&ctx.locale, ctx.system_prompt.as_str()),
+            &msg.channel,
+        )),
+    );
     let mut history = vec![ChatMessage::system(system_prompt)];
     history.extend(prior_turns);
     let use_streaming = target_channel
@@ -1695,15 +1715,18 @@ async fn process_channel_message(
 
     let timeout_budget_secs =
         channel_message_timeout_budget_secs(ctx.message_timeout_secs, ctx.max_tool_iterations);
+    let turn_recorder = crate::analytics::TurnRecorder::new(Arc::clone(&ctx.observer));
+    // Taken per turn so conf.d composite edits apply to the next message.
+    let turn_tools = ctx.tools_registry.tools();
     let llm_result = tokio::select! {
         () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
         result = tokio::time::timeout(
             Duration::from_secs(timeout_budget_secs),
-            run_tool_call_loop(
+            crate::tools::CONVERSATION_ID.scope(history_key.clone(), run_tool_call_loop(
                 active_provider.as_ref(),
                 &mut history,
-                ctx.tools_registry.as_ref(),
-                ctx.observer.as_ref(),
+                &turn_tools,
+                &turn_recorder,
                 route.provider.as_str(),
                 route.model.as_str(),
                 runtime_defaults.temperature,
@@ -1720,7 +1743,7 @@ async fn process_channel_message(
                 } else {
                     ctx.non_cli_excluded_tools.as_ref()
                 },
-            ),
+            )),
         ) => LlmExecutionResult::Completed(result),
     };
 
@@ -1735,6 +1758,20 @@ async fn process_channel_message(
         log_worker_join_result(handle.await);
     }
 
+    if let LlmExecutionResult::Completed(outcome) = &llm_result {
+        let turn = turn_recorder.finish(
+            &msg.channel,
+            turn_started_at,
+            started_at.elapsed(),
+            matches!(outcome, Ok(Ok(_))),
+        );
+        crate::analytics::record_in_background(
+            ctx.workspace_dir.as_ref().clone(),
+            &ctx.analytics,
+            turn,
+        );
+    }
+
     let reaction_done_emoji = match &llm_result {
         LlmExecutionResult::Completed(Ok(Ok(_))) => "\u{2705}", // ✅
         _ => "\u{26A0}\u{FE0F}",                                // ⚠️
@@ -1827,8 +1864,7 @@ async fn process_channel_message(
                 }
             }
 
-            let sanitized_response =
-                sanitize_channel_response(&outbound_response, ctx.tools_registry.as_ref());
+            let sanitized_response = sanitize_channel_response(&outbound_response, &turn_tools);
             let delivered_response = if sanitized_response.is_empty()
                 && !outbound_response.trim().is_empty()
             {
@@ -1871,29 +1907,70 @@ async fn process_channel_message(
                 started_at.elapsed().as_millis(),
                 truncate_with_ellipsis(&delivered_response, 80)
             );
+            // History keeps the agent's Markdown; the wire gets the channel's format.
+            let rendered_response = format::render_for_channel(&msg.channel, &delivered_response);
+            let (rendered_response, outbound_files) = match target_channel.as_ref() {
+                Some(channel) if channel.supports_attachments() => {
+                    let (text, files) = attachments::extract_outbound(
+                        &rendered_response,
+                        ctx.workspace_dir.as_path(),
+                    );
+                    if text.is_empty() && !files.is_empty() {
+                        let names: Vec<String> = files
+                            .iter()
+                            .map(attachments::Attachment::file_name)
+                            .collect();
+                        (format!("📎 {}", names.join(", ")), files)
+                    } else {
+                        (text, files)
+                    }
+                }
+                _ => (rendered_response, Vec::new()),
+            };
             if let Some(channel) = target_channel.as_ref() {
                 if let Some(ref draft_id) = draft_message_id {
                     if let Err(e) = channel
-                        .finalize_draft(&msg.reply_target, draft_id, &delivered_response)
+                        .finalize_draft(&msg.reply_target, draft_id, &rendered_response)
                         .await
                     {
                         tracing::warn!("Failed to finalize draft: {e}; sending as new message");
                         let _ = channel
                             .send(
-                                &SendMessage::new(&delivered_response, &msg.reply_target)
+                                &SendMessage::new(&rendered_response, &msg.reply_target)
                                     .in_thread(msg.thread_ts.clone()),
                             )
                             .await;
                     }
                 } else if let Err(e) = channel
                     .send(
-                        &SendMessage::new(delivered_response, &msg.reply_target)
+                        &SendMessage::new(rendered_response, &msg.reply_target)
                             .in_thread(msg.thread_ts.clone()),
                     )
                     .await
                 {
                     eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
                 }
+                for attachment in &outbound_files {
+                    if let Err(e) = channel
+                        .send_attachment(&msg.reply_target, attachment, msg.thread_ts.as_deref())
+                        .await
+                    {
+                        tracing::warn!(
+                            "Failed to send attachment {} on {}: {e}",
+                            attachment.path.display(),
+                            channel.name()
+                        );
+                        let _ = channel
+                            .send(
+                                &SendMessage::new(
+                                    format!("⚠️ Could not send {}", attachment.file_name()),
+                                    &msg.reply_target,
+                                )
+                                .in_thread(msg.thread_ts.clone()),
+                            )
+                            .await;
+                    }
+                }
             }
         }
         LlmExecutionResult::Completed(Ok(Err(e))) => {
@@ -2346,13 +2423,12 @@ pub fn build_system_prompt_with_mode(
     }
 
     // ── 6. Date & Time ──────────────────────────────────────────
-    let now = chrono::Local::now();
-    let _ = writeln!(
-        prompt,
-        "## Current Date & Time\n\n{} ({})\n",
-        now.format("%Y-%m-%d %H:%M:%S"),
-        now.format("%Z")
-    );
+    // Rendered in host time here; callers re-render it in the configured
+    // timezone before each turn via `locale::with_current_datetime`.
+    prompt.push_str(&crate::locale::datetime_section(
+        &crate::config::LocaleConfig::default(),
+        chrono::Utc::now(),
+    ));
 
     // ── 7. Runtime ──────────────────────────────────────────────
     let host =
@@ -2671,24 +2747,30 @@ fn collect_configured_channels(
     if let Some(ref dc) = config.channels_config.discord {
         channels.push(ConfiguredChannel {
             display_name: "Discord",
-            channel: Arc::new(DiscordChannel::new(
-                dc.bot_token.clone(),
-                dc.guild_id.clone(),
-                dc.allowed_users.clone(),
-                dc.listen_to_bots,
-                dc.mention_only,
-            )),
+            channel: Arc::new(
+                DiscordChannel::new(
+                    dc.bot_token.clone(),
+                    dc.guild_id.clone(),
+                    dc.allowed_users.clone(),
+                    dc.listen_to_bots,
+                    dc.mention_only,
+                )
+                .with_workspace_dir(config.workspace_dir.clone()),
+            ),
         });
     }
 
     if let Some(ref sl) = config.channels_config.slack {
         channels.push(ConfiguredChannel {
             display_name: "Slack",
-            channel: Arc::new(SlackChannel::new(
-                sl.bot_token.clone(),
-                sl.channel_id.clone(),
-                sl.allowed_users.clone(),
-            )),
+            channel: Arc::new(
+                SlackChannel::new(
+                    sl.bot_token.clone(),
+                    sl.channel_id.clone(),
+                    sl.allowed_users.clone(),
+                )
+                .with_app_token(sl.app_token.clone()),
+            ),
         });
     }
 
@@ -2845,6 +2927,9 @@ fn collect_configured_channels(
                 nickserv_password: irc.nickserv_password.clone(),
                 sasl_password: irc.sasl_password.clone(),
                 verify_tls: irc.verify_tls.unwrap_or(true),
+                mention_only: irc.mention_only,
+                flood_burst: irc.flood_burst,
+                flood_interval_ms: irc.flood_interval_ms,
             })),
         });
     }
@@ -3021,7 +3106,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     };
     // Build system prompt from workspace identity files + skills
     let workspace = config.workspace_dir.clone();
-    let tools_registry = Arc::new(tools::all_tools_with_runtime(
+    let tools_registry = Arc::new(tools::tool_registry_with_runtime(
         Arc::new(config.clone()),
         &security,
         runtime,
@@ -3034,6 +3119,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         &config.agents,
         config.api_key.as_deref(),
         &config,
+        None,
     ));
 
     let skills = crate::skills::load_skills_with_config(&workspace, &config);
@@ -3064,6 +3150,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "preference_set",
+            "Record a standing user preference (added to every system prompt). Use when: the user states how you should behave from now on (units, tone, approval rules). Don't use when: it is a one-off request or a fact about the world.",
+        ),
     ];
 
     if config.browser.enabled {
@@ -3116,8 +3206,12 @@ pub async fn start_channels(config: Config) -> Result<()> {
         native_tools,
         config.skills.prompt_injection_mode,
     );
+    if let Some(notice) = tools::read_only_notice(config.autonomy.level, &tools_registry.tools()) {
+        system_prompt.push_str(&notice);
+    }
+    system_prompt.push_str(&crate::locale::prompt_section(&config.locale));
     if !native_tools {
-        system_prompt.push_str(&build_tool_instructions(tools_registry.as_ref()));
+        system_prompt.push_str(&build_tool_instructions(&tools_registry.tools()));
     }
 
     if !skills.is_empty() {
@@ -3246,11 +3340,16 @@ pub async fn start_channels(config: Config) -> Result<()> {
             if config.hooks.builtin.command_logger {
                 runner.register(Box::new(crate::hooks::builtin::CommandLoggerHook::new()));
             }
+            crate::hooks::builtin::register_script_hooks(&mut runner, &config);
             Some(Arc::new(runner))
         } else {
             None
         },
         non_cli_excluded_tools: Arc::new(config.autonomy.non_cli_excluded_tools.clone()),
+        conf_d: crate::config::confd::ConfDWatcher::shared(&config),
+        facts: config.facts.clone(),
+        analytics: config.analytics.clone(),
+        locale: config.locale.clone(),
     });
 
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
@@ -3443,7 +3542,7 @@ mod tests {
             provider: Arc::new(DummyProvider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -3464,6 +3563,13 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -3492,7 +3598,7 @@ mod tests {
             provider: Arc::new(DummyProvider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -3513,6 +3619,13 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -3544,7 +3657,7 @@ mod tests {
             provider: Arc::new(DummyProvider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -3565,6 +3678,13 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         };
 
         assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
@@ -4019,7 +4139,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: Arc::new(ToolCallingProvider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![Box::new(MockPriceTool)])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4038,6 +4158,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4078,7 +4205,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: Arc::new(ToolCallingProvider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![Box::new(MockPriceTool)])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4097,6 +4224,13 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4151,7 +4285,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: Arc::new(RawToolArtifactProvider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![Box::new(MockPriceTool)])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4172,6 +4306,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -4210,7 +4351,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: Arc::new(ToolCallingAliasProvider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![Box::new(MockPriceTool)])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4231,6 +4372,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -4278,7 +4426,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: Arc::clone(&default_provider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
@@ -4299,6 +4447,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -4367,7 +4522,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: Arc::clone(&default_provider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
@@ -4388,6 +4543,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -4438,7 +4600,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: Arc::clone(&startup_provider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
@@ -4459,6 +4621,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -4521,7 +4690,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: Arc::clone(&provider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("startup-model".to_string()),
@@ -4545,6 +4714,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -4595,7 +4771,7 @@ BTC is currently around $65,000 based on latest tool output."#
             }),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![Box::new(MockPriceTool)])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4616,6 +4792,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -4655,7 +4838,7 @@ BTC is currently around $65,000 based on latest tool output."#
             }),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![Box::new(MockPriceTool)])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4676,6 +4859,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -4826,7 +5016,7 @@ BTC is currently around $65,000 based on latest tool output."#
             }),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4847,6 +5037,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -4906,7 +5103,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: provider_impl.clone(),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -4927,6 +5124,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -4998,7 +5202,7 @@ BTC is currently around $65,000 based on latest tool output."#
             }),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5019,6 +5223,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5072,7 +5283,7 @@ BTC is currently around $65,000 based on latest tool output."#
             }),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5093,6 +5304,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -5131,7 +5349,7 @@ BTC is currently around $65,000 based on latest tool output."#
             }),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5152,6 +5370,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -5647,7 +5872,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: provider_impl.clone(),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5668,6 +5893,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -5732,7 +5964,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: provider_impl.clone(),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(RecallMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5753,6 +5985,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -5817,7 +6056,7 @@ BTC is currently around $65,000 based on latest tool output."#
             provider: provider_impl.clone(),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -5838,6 +6077,13 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
@@ -6366,7 +6612,7 @@ This is an example JSON object for profile settings."#;
             provider: Arc::new(DummyProvider),
             default_provider: Arc::new("dummy".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("You are a helpful assistant.".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -6387,6 +6633,13 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -6432,7 +6685,7 @@ This is an example JSON object for profile settings."#;
             provider: Arc::new(DummyProvider),
             default_provider: Arc::new("dummy".to_string()),
             memory: Arc::new(NoopMemory),
-            tools_registry: Arc::new(vec![]),
+            tools_registry: Arc::new(tools::ToolRegistry::fixed(vec![])),
             observer: Arc::new(NoopObserver),
             system_prompt: Arc::new("You are a helpful assistant.".to_string()),
             model: Arc::new("test-model".to_string()),
@@ -6453,6 +6706,13 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            conf_d: Arc::new(crate::config::confd::ConfDWatcher::default()),
+            facts: crate::config::FactsConfig::default(),
+            analytics: crate::config::AnalyticsConfig {
+                enabled: false,
+                ..crate::config::AnalyticsConfig::default()
+            },
+            locale: crate::config::LocaleConfig::default(),
         });
 
         process_channel_message(
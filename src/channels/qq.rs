@@ -121,7 +121,7 @@ impl QQChannel {
     }
 
     fn is_user_allowed(&self, user_id: &str) -> bool {
-        self.allowed_users.iter().any(|u| u == "*" || u == user_id)
+        super::allowlist::allows(&self.allowed_users, user_id)
     }
 
     /// Fetch an access token from QQ's OAuth2 endpoint.
@@ -1,24 +1,87 @@
+use super::attachments::Attachment;
 use super::traits::{Channel, ChannelMessage, SendMessage};
 use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
+use tokio_tungstenite::tungstenite::Message;
 
-/// Slack channel — polls conversations.history via Web API
+/// Minimum interval between `chat.update` calls for one draft (Slack tier-3 limits).
+const DRAFT_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+const SLASH_USAGE: &str =
+    "Usage: `/zeroclaw run <prompt>` or `/zeroclaw cron [list | <what to schedule>]`";
+
+/// Slack channel — polls conversations.history via Web API, or receives
+/// events over Socket Mode when an app-level token is configured.
+///
+/// In Socket Mode the bot also answers `/zeroclaw` slash commands and
+/// streams tool-call progress into the reply thread as an edited draft.
 pub struct SlackChannel {
     bot_token: String,
+    app_token: Option<String>,
     channel_id: Option<String>,
     allowed_users: Vec<String>,
+    last_draft_edit: Mutex<HashMap<String, Instant>>,
+}
+
+/// Parsed `/zeroclaw` slash command text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SlashCommand {
+    Run(String),
+    Cron(String),
+    Help,
+}
+
+impl SlashCommand {
+    fn parse(text: &str) -> Self {
+        let text = text.trim();
+        let (verb, rest) = text
+            .split_once(char::is_whitespace)
+            .map_or((text, ""), |(verb, rest)| (verb, rest.trim()));
+        match verb.to_ascii_lowercase().as_str() {
+            "run" if !rest.is_empty() => Self::Run(rest.to_string()),
+            "cron" => Self::Cron(rest.to_string()),
+            _ => Self::Help,
+        }
+    }
+
+    /// Prompt handed to the agent for this command.
+    fn prompt(&self) -> Option<String> {
+        match self {
+            Self::Run(prompt) => Some(prompt.clone()),
+            Self::Cron(rest) if rest.is_empty() || rest.eq_ignore_ascii_case("list") => {
+                Some("List my scheduled cron jobs with their next run times.".to_string())
+            }
+            Self::Cron(rest) => Some(format!(
+                "Using the cron tools, manage scheduled jobs as follows: {rest}"
+            )),
+            Self::Help => None,
+        }
+    }
 }
 
 impl SlackChannel {
     pub fn new(bot_token: String, channel_id: Option<String>, allowed_users: Vec<String>) -> Self {
         Self {
             bot_token,
+            app_token: None,
             channel_id,
             allowed_users,
+            last_draft_edit: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Enable Socket Mode with an app-level token (`xapp-...`).
+    /// Blank tokens leave the channel in polling mode.
+    pub fn with_app_token(mut self, app_token: Option<String>) -> Self {
+        self.app_token = app_token
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty());
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.slack")
     }
@@ -27,7 +90,7 @@ impl SlackChannel {
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
     fn is_user_allowed(&self, user_id: &str) -> bool {
-        self.allowed_users.iter().any(|u| u == "*" || u == user_id)
+        super::allowlist::allows(&self.allowed_users, user_id)
     }
 
     /// Get the bot's own user ID so we can ignore our own messages
@@ -158,29 +221,19 @@ impl SlackChannel {
         channels.dedup();
         Ok(channels)
     }
-}
-
-#[async_trait]
-impl Channel for SlackChannel {
-    fn name(&self) -> &str {
-        "slack"
-    }
-
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let mut body = serde_json::json!({
-            "channel": message.recipient,
-            "text": message.content
-        });
-
-        if let Some(ref ts) = message.thread_ts {
-            body["thread_ts"] = serde_json::json!(ts);
-        }
 
+    /// Call a Slack Web API method with a JSON body and return the parsed response.
+    async fn api_call(
+        &self,
+        method: &str,
+        token: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
         let resp = self
             .http_client()
-            .post("https://slack.com/api/chat.postMessage")
-            .bearer_auth(&self.bot_token)
-            .json(&body)
+            .post(format!("https://slack.com/api/{method}"))
+            .bearer_auth(token)
+            .json(body)
             .send()
             .await?;
 
@@ -191,7 +244,7 @@ impl Channel for SlackChannel {
             .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
 
         if !status.is_success() {
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {body}");
+            anyhow::bail!("Slack {method} failed ({status}): {body}");
         }
 
         // Slack returns 200 for most app-level errors; check JSON "ok" field
@@ -201,13 +254,380 @@ impl Channel for SlackChannel {
                 .get("error")
                 .and_then(|e| e.as_str())
                 .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
+            anyhow::bail!("Slack {method} failed: {err}");
+        }
+
+        Ok(parsed)
+    }
+
+    /// Post a message and return its `ts`.
+    async fn post_message(
+        &self,
+        channel: &str,
+        text: &str,
+        thread_ts: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let mut body = serde_json::json!({
+            "channel": channel,
+            "text": text
+        });
+        if let Some(ts) = thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+        if !super::format::should_unfurl(text) {
+            body["unfurl_links"] = serde_json::json!(false);
+            body["unfurl_media"] = serde_json::json!(false);
+        }
+
+        let resp = self
+            .api_call("chat.postMessage", &self.bot_token, &body)
+            .await?;
+        Ok(resp
+            .get("ts")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    async fn update_message(&self, channel: &str, ts: &str, text: &str) -> anyhow::Result<()> {
+        let body = serde_json::json!({
+            "channel": channel,
+            "ts": ts,
+            "text": text
+        });
+        self.api_call("chat.update", &self.bot_token, &body).await?;
+        Ok(())
+    }
+
+    /// Build a [`ChannelMessage`] from a Socket Mode `events_api` payload.
+    ///
+    /// Only plain user `message` events are forwarded; edits, bot posts and
+    /// `app_mention` (which duplicates the matching `message` event) are dropped.
+    fn socket_event_message(
+        payload: &serde_json::Value,
+        bot_user_id: &str,
+    ) -> Option<ChannelMessage> {
+        let event = payload.get("event")?;
+        if event.get("type").and_then(|t| t.as_str()) != Some("message")
+            || event.get("subtype").is_some()
+            || event.get("bot_id").is_some()
+        {
+            return None;
+        }
+
+        let user = event.get("user").and_then(|u| u.as_str())?;
+        let channel_id = event.get("channel").and_then(|c| c.as_str())?;
+        let ts = event.get("ts").and_then(|t| t.as_str()).unwrap_or("");
+        let text = event.get("text").and_then(|t| t.as_str()).unwrap_or("");
+        if user == bot_user_id || text.is_empty() {
+            return None;
+        }
+
+        Some(ChannelMessage {
+            id: format!("slack_{channel_id}_{ts}"),
+            sender: user.to_string(),
+            reply_target: channel_id.to_string(),
+            content: text.to_string(),
+            channel: "slack".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: Self::inbound_thread_ts(event, ts),
+        })
+    }
+
+    /// Handle a `/zeroclaw` slash command payload.
+    ///
+    /// Returns the ephemeral acknowledgement text and, for runnable commands,
+    /// the message to forward. The command is echoed into the channel so the
+    /// agent's progress and answer land in a thread under it.
+    async fn slash_command_message(
+        &self,
+        payload: &serde_json::Value,
+    ) -> (String, Option<ChannelMessage>) {
+        let user = payload
+            .get("user_id")
+            .and_then(|u| u.as_str())
+            .unwrap_or("");
+        let channel_id = payload
+            .get("channel_id")
+            .and_then(|c| c.as_str())
+            .unwrap_or("");
+        let command = payload
+            .get("command")
+            .and_then(|c| c.as_str())
+            .unwrap_or("/zeroclaw");
+        let text = payload.get("text").and_then(|t| t.as_str()).unwrap_or("");
+
+        if !self.is_user_allowed(user) {
+            tracing::warn!("Slack: ignoring message from unauthorized user: {user}");
+            return ("You are not allowed to use this command.".into(), None);
+        }
+        let Some(prompt) = SlashCommand::parse(text).prompt() else {
+            return (SLASH_USAGE.into(), None);
+        };
+
+        let anchor = format!("<@{user}> `{command} {}`", text.trim());
+        let thread_ts = match self.post_message(channel_id, &anchor, None).await {
+            Ok(ts) if !ts.is_empty() => ts,
+            Ok(_) => return ("Slack did not return a message timestamp.".into(), None),
+            Err(e) => {
+                tracing::warn!("Slack: failed to post slash command anchor: {e}");
+                return (
+                    "Could not post in this channel — is the bot a member?".into(),
+                    None,
+                );
+            }
+        };
+
+        let msg = ChannelMessage {
+            id: format!("slack_{channel_id}_{thread_ts}"),
+            sender: user.to_string(),
+            reply_target: channel_id.to_string(),
+            content: prompt,
+            channel: "slack".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: Some(thread_ts),
+        };
+        (
+            "Working on it — follow along in the thread.".into(),
+            Some(msg),
+        )
+    }
+
+    /// Receive events over a Socket Mode WebSocket until Slack asks us to
+    /// reconnect or the connection drops; the supervisor restarts us.
+    async fn listen_socket_mode(
+        &self,
+        app_token: &str,
+        tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+    ) -> anyhow::Result<()> {
+        let bot_user_id = self.get_bot_user_id().await.unwrap_or_default();
+        let scoped_channel = self.configured_channel_id();
+
+        let opened = self
+            .api_call("apps.connections.open", app_token, &serde_json::json!({}))
+            .await?;
+        let ws_url = opened
+            .get("url")
+            .and_then(|u| u.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Slack apps.connections.open returned no url"))?;
+
+        let (ws_stream, _) = tokio_tungstenite::connect_async(ws_url).await?;
+        let (mut write, mut read) = ws_stream.split();
+        tracing::info!("Slack: connected via Socket Mode");
+
+        while let Some(frame) = read.next().await {
+            let text = match frame? {
+                Message::Text(t) => t,
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            let Ok(envelope) = serde_json::from_str::<serde_json::Value>(text.as_ref()) else {
+                continue;
+            };
+            let kind = envelope.get("type").and_then(|t| t.as_str()).unwrap_or("");
+            let envelope_id = envelope.get("envelope_id").and_then(|e| e.as_str());
+            let payload = envelope
+                .get("payload")
+                .cloned()
+                .unwrap_or(serde_json::Value::Null);
+
+            // Every envelope must be acknowledged within 3 seconds.
+            let (ack_text, channel_msg) = match kind {
+                "events_api" => (None, Self::socket_event_message(&payload, &bot_user_id)),
+                "slash_commands" => {
+                    let (ack, msg) = self.slash_command_message(&payload).await;
+                    (Some(ack), msg)
+                }
+                "disconnect" => {
+                    tracing::info!("Slack: Socket Mode disconnect requested, reconnecting");
+                    break;
+                }
+                _ => (None, None),
+            };
+            if let Some(id) = envelope_id {
+                let mut ack = serde_json::json!({ "envelope_id": id });
+                if let Some(text) = ack_text {
+                    ack["payload"] = serde_json::json!({ "text": text });
+                }
+                write.send(Message::Text(ack.to_string().into())).await?;
+            }
+
+            let Some(channel_msg) = channel_msg else {
+                continue;
+            };
+            if scoped_channel
+                .as_deref()
+                .is_some_and(|scoped| scoped != channel_msg.reply_target)
+            {
+                continue;
+            }
+            if !self.is_user_allowed(&channel_msg.sender) {
+                tracing::warn!(
+                    "Slack: ignoring message from unauthorized user: {}",
+                    channel_msg.sender
+                );
+                continue;
+            }
+            if tx.send(channel_msg).await.is_err() {
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Channel for SlackChannel {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        self.post_message(
+            &message.recipient,
+            &message.content,
+            message.thread_ts.as_deref(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.app_token.is_some()
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        let ts = self
+            .post_message(
+                &message.recipient,
+                &message.content,
+                message.thread_ts.as_deref(),
+            )
+            .await?;
+        self.last_draft_edit
+            .lock()
+            .insert(format!("{}:{ts}", message.recipient), Instant::now());
+        Ok(Some(ts).filter(|ts| !ts.is_empty()))
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        let key = format!("{recipient}:{message_id}");
+        {
+            let mut last_edits = self.last_draft_edit.lock();
+            if last_edits
+                .get(&key)
+                .is_some_and(|last| last.elapsed() < DRAFT_UPDATE_INTERVAL)
+            {
+                return Ok(());
+            }
+            last_edits.insert(key, Instant::now());
+        }
+        self.update_message(recipient, message_id, text).await
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        self.last_draft_edit
+            .lock()
+            .remove(&format!("{recipient}:{message_id}"));
+        self.update_message(recipient, message_id, text).await
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        self.last_draft_edit
+            .lock()
+            .remove(&format!("{recipient}:{message_id}"));
+        let body = serde_json::json!({
+            "channel": recipient,
+            "ts": message_id
+        });
+        self.api_call("chat.delete", &self.bot_token, &body).await?;
+        Ok(())
+    }
+
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
+    async fn send_attachment(
+        &self,
+        recipient: &str,
+        attachment: &Attachment,
+        thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let bytes = tokio::fs::read(&attachment.path).await?;
+        let file_name = attachment.file_name();
+
+        // files.getUploadURLExternal only accepts form-encoded arguments.
+        let resp = self
+            .http_client()
+            .post("https://slack.com/api/files.getUploadURLExternal")
+            .bearer_auth(&self.bot_token)
+            .form(&[
+                ("filename", file_name.clone()),
+                ("length", bytes.len().to_string()),
+            ])
+            .send()
+            .await?;
+        let ticket: serde_json::Value = resp.json().await?;
+        if ticket.get("ok") != Some(&serde_json::Value::Bool(true)) {
+            let err = ticket
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack files.getUploadURLExternal failed: {err}");
+        }
+        let (Some(upload_url), Some(file_id)) = (
+            ticket.get("upload_url").and_then(|u| u.as_str()),
+            ticket.get("file_id").and_then(|f| f.as_str()),
+        ) else {
+            anyhow::bail!("Slack files.getUploadURLExternal returned no upload_url");
+        };
+
+        let upload = self
+            .http_client()
+            .post(upload_url)
+            .header("Content-Type", attachment.mime.as_str())
+            .body(bytes)
+            .send()
+            .await?;
+        if !upload.status().is_success() {
+            anyhow::bail!("Slack file upload failed ({})", upload.status());
         }
 
+        let mut body = serde_json::json!({
+            "files": [{ "id": file_id, "title": file_name }],
+            "channel_id": recipient
+        });
+        if let Some(ts) = thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+        self.api_call("files.completeUploadExternal", &self.bot_token, &body)
+            .await?;
         Ok(())
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        if let Some(app_token) = self.app_token.clone() {
+            return self.listen_socket_mode(&app_token, tx).await;
+        }
+
         let bot_user_id = self.get_bot_user_id().await.unwrap_or_default();
         let scoped_channel = self.configured_channel_id();
         let mut discovered_channels: Vec<String> = Vec::new();
@@ -532,4 +952,92 @@ mod tests {
         let thread_ts = SlackChannel::inbound_thread_ts(&msg, "");
         assert_eq!(thread_ts, None);
     }
+
+    #[test]
+    fn app_token_enables_socket_mode_drafts() {
+        let polling = SlackChannel::new("xoxb-fake".into(), None, vec![]);
+        assert!(!polling.supports_draft_updates());
+
+        let blank =
+            SlackChannel::new("xoxb-fake".into(), None, vec![]).with_app_token(Some("  ".into()));
+        assert!(!blank.supports_draft_updates());
+
+        let socket = SlackChannel::new("xoxb-fake".into(), None, vec![])
+            .with_app_token(Some("xapp-fake".into()));
+        assert!(socket.supports_draft_updates());
+    }
+
+    #[test]
+    fn socket_event_message_forwards_user_messages() {
+        let payload = serde_json::json!({
+            "event": {
+                "type": "message",
+                "user": "U111",
+                "channel": "C123",
+                "text": "deploy status?",
+                "ts": "1700000000.000100"
+            }
+        });
+        let msg = SlackChannel::socket_event_message(&payload, "UBOT").unwrap();
+        assert_eq!(msg.id, "slack_C123_1700000000.000100");
+        assert_eq!(msg.sender, "U111");
+        assert_eq!(msg.reply_target, "C123");
+        assert_eq!(msg.content, "deploy status?");
+        assert_eq!(msg.thread_ts.as_deref(), Some("1700000000.000100"));
+    }
+
+    #[test]
+    fn socket_event_message_skips_bots_edits_and_mentions() {
+        let own = serde_json::json!({
+            "event": {"type": "message", "user": "UBOT", "channel": "C1", "text": "hi", "ts": "1.0"}
+        });
+        let bot = serde_json::json!({
+            "event": {"type": "message", "user": "U2", "bot_id": "B1", "channel": "C1", "text": "hi", "ts": "1.0"}
+        });
+        let edit = serde_json::json!({
+            "event": {"type": "message", "subtype": "message_changed", "channel": "C1", "ts": "1.0"}
+        });
+        let mention = serde_json::json!({
+            "event": {"type": "app_mention", "user": "U2", "channel": "C1", "text": "<@UBOT> hi", "ts": "1.0"}
+        });
+        for payload in [own, bot, edit, mention] {
+            assert!(SlackChannel::socket_event_message(&payload, "UBOT").is_none());
+        }
+    }
+
+    #[test]
+    fn slash_command_parse() {
+        assert_eq!(
+            SlashCommand::parse("run check disk usage"),
+            SlashCommand::Run("check disk usage".into())
+        );
+        assert_eq!(SlashCommand::parse("RUN"), SlashCommand::Help);
+        assert_eq!(
+            SlashCommand::parse("cron"),
+            SlashCommand::Cron(String::new())
+        );
+        assert_eq!(
+            SlashCommand::parse("cron  every day at 9 remind me to stretch"),
+            SlashCommand::Cron("every day at 9 remind me to stretch".into())
+        );
+        assert_eq!(SlashCommand::parse(""), SlashCommand::Help);
+        assert_eq!(SlashCommand::parse("bogus"), SlashCommand::Help);
+    }
+
+    #[test]
+    fn slash_command_prompts() {
+        assert_eq!(
+            SlashCommand::Run("hello".into()).prompt().as_deref(),
+            Some("hello")
+        );
+        assert!(SlashCommand::Cron("list".into())
+            .prompt()
+            .unwrap()
+            .starts_with("List my scheduled cron jobs"));
+        assert!(SlashCommand::Cron("remove job 3".into())
+            .prompt()
+            .unwrap()
+            .ends_with("remove job 3"));
+        assert!(SlashCommand::Help.prompt().is_none());
+    }
 }
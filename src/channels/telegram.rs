@@ -870,7 +870,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
 
     /// Attempt to parse a Telegram update as a document/photo attachment.
     ///
-    /// Downloads the file to `{workspace_dir}/telegram_files/` and returns a
+    /// Downloads the file to `{workspace_dir}/attachments/telegram/` and returns a
     /// `ChannelMessage` with the local file path. Returns `None` if the message
     /// is not an attachment, workspace_dir is not configured, or the file exceeds
     /// size limits.
@@ -931,12 +931,6 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             None
         })?;
 
-        let save_dir = workspace.join("telegram_files");
-        if let Err(e) = tokio::fs::create_dir_all(&save_dir).await {
-            tracing::warn!("Failed to create telegram_files directory: {e}");
-            return None;
-        }
-
         // Download file from Telegram
         let tg_file_path = match self.get_file_path(&attachment.file_id).await {
             Ok(p) => p,
@@ -964,11 +958,17 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             }
         };
 
-        let local_path = save_dir.join(&local_filename);
-        if let Err(e) = tokio::fs::write(&local_path, &file_data).await {
-            tracing::warn!("Failed to save attachment to {}: {e}", local_path.display());
-            return None;
-        }
+        let stored =
+            match super::attachments::store(workspace, "telegram", &local_filename, &file_data)
+                .await
+            {
+                Ok(stored) => stored,
+                Err(e) => {
+                    tracing::warn!("Failed to save attachment: {e}");
+                    return None;
+                }
+            };
+        let local_path = &stored.path;
 
         // Build message content.
         // Photos use [IMAGE:] marker so the multimodal pipeline validates
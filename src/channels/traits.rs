@@ -1,3 +1,4 @@
+use super::attachments::Attachment;
 use async_trait::async_trait;
 
 /// A message received from or sent to a channel
@@ -142,6 +143,23 @@ pub trait Channel: Send + Sync {
     ) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Whether this channel uploads files via [`Channel::send_attachment`].
+    /// When true, the channel layer strips workspace-file markers from replies
+    /// and sends each referenced file after the text.
+    fn supports_attachments(&self) -> bool {
+        false
+    }
+
+    /// Upload a workspace file to `recipient`, in `thread_ts` when given.
+    async fn send_attachment(
+        &self,
+        _recipient: &str,
+        _attachment: &Attachment,
+        _thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("{} does not support attachments", self.name())
+    }
 }
 
 #[cfg(test)]
@@ -244,6 +262,18 @@ mod tests {
         assert!(channel.cancel_draft("bob", "msg_1").await.is_ok());
     }
 
+    #[tokio::test]
+    async fn default_attachment_methods_decline() {
+        let channel = DummyChannel;
+        let attachment = Attachment::from_path("/tmp/report.pdf", "agent");
+
+        assert!(!channel.supports_attachments());
+        assert!(channel
+            .send_attachment("bob", &attachment, None)
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn listen_sends_message_to_channel() {
         let channel = DummyChannel;
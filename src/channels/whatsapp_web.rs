@@ -96,7 +96,7 @@ impl WhatsAppWebChannel {
     /// Check if a phone number is allowed (E.164 format: +1234567890)
     #[cfg(feature = "whatsapp-web")]
     fn is_number_allowed(&self, phone: &str) -> bool {
-        self.allowed_numbers.iter().any(|n| n == "*" || n == phone)
+        super::allowlist::allows(&self.allowed_numbers, phone)
     }
 
     /// Normalize phone number to E.164 format
@@ -0,0 +1,318 @@
+//! `conf.d` overlay directory next to `config.toml`.
+//!
+//! ```text
+//! conf.d/
+//!   persona.md            # injected as "## Persona"
+//!   prompts/*.md          # injected as "## Custom Instructions", by file name
+//!   tools/<name>.toml     # one composite tool each (same keys as [composite_tools.<name>])
+//!   automations/<name>.toml  # one cron job each, synced by the scheduler
+//! ```
+//!
+//! The directory is re-read whenever a file under it is added, removed or
+//! modified, so prompt and automation edits apply without a restart.
+
+use super::schema::{CompositeToolConfig, Config};
+use crate::cron::{DeliveryConfig, Schedule};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::SystemTime;
+
+pub const CONF_D_DIR: &str = "conf.d";
+
+/// A cron job declared in `conf.d/automations/<name>.toml`. Exactly one of
+/// `command` (shell job) or `prompt` (agent job) is set.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AutomationConfig {
+    pub schedule: Schedule,
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub prompt: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub delivery: Option<DeliveryConfig>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Parsed contents of a `conf.d` directory.
+#[derive(Debug, Clone, Default)]
+pub struct ConfD {
+    pub persona: Option<String>,
+    /// `(file stem, content)`, sorted by file name.
+    pub prompts: Vec<(String, String)>,
+    pub composite_tools: HashMap<String, CompositeToolConfig>,
+    /// `(file stem, job)`, sorted by file name.
+    pub automations: Vec<(String, AutomationConfig)>,
+}
+
+impl ConfD {
+    /// Read `dir`. Unreadable or invalid files are skipped with a warning.
+    pub fn load(dir: &Path) -> Self {
+        let persona = std::fs::read_to_string(dir.join("persona.md"))
+            .ok()
+            .map(|text| text.trim().to_string())
+            .filter(|text| !text.is_empty());
+        let prompts = files_with_extension(&dir.join("prompts"), "md")
+            .into_iter()
+            .filter_map(|(stem, path)| {
+                let text = std::fs::read_to_string(&path).ok()?;
+                let text = text.trim();
+                (!text.is_empty()).then(|| (stem, text.to_string()))
+            })
+            .collect();
+        let composite_tools = parse_toml_files(&dir.join("tools")).into_iter().collect();
+        let automations = parse_toml_files(&dir.join("automations"))
+            .into_iter()
+            .filter(|(stem, job): &(String, AutomationConfig)| {
+                let valid = job.command.is_some() != job.prompt.is_some();
+                if !valid {
+                    tracing::warn!(
+                        "Skipping conf.d automation '{stem}': set exactly one of command or prompt"
+                    );
+                }
+                valid
+            })
+            .collect();
+
+        Self {
+            persona,
+            prompts,
+            composite_tools,
+            automations,
+        }
+    }
+
+    /// System prompt sections for the persona and prompt files.
+    pub fn prompt_section(&self) -> String {
+        let mut section = String::new();
+        if let Some(persona) = &self.persona {
+            section.push_str("## Persona\n\n");
+            section.push_str(persona);
+            section.push_str("\n\n");
+        }
+        if !self.prompts.is_empty() {
+            section.push_str("## Custom Instructions\n\n");
+            for (name, text) in &self.prompts {
+                let _ = write!(section, "### {name}\n\n{text}\n\n");
+            }
+        }
+        section
+    }
+}
+
+/// `(stem, path)` of files in `dir` with `extension`, sorted by file name.
+fn files_with_extension(dir: &Path, extension: &str) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(String, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == extension))
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?.to_string();
+            Some((stem, path))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn parse_toml_files<T: serde::de::DeserializeOwned>(dir: &Path) -> Vec<(String, T)> {
+    files_with_extension(dir, "toml")
+        .into_iter()
+        .filter_map(|(stem, path)| {
+            let text = std::fs::read_to_string(&path).ok()?;
+            match toml::from_str(&text) {
+                Ok(value) => Some((stem, value)),
+                Err(e) => {
+                    tracing::warn!("Skipping {}: {e}", path.display());
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// `(path, modified, len)` for every file under the directory.
+type Fingerprint = Vec<(PathBuf, Option<SystemTime>, u64)>;
+
+fn fingerprint(dir: &Path) -> Fingerprint {
+    let mut stamps = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                pending.push(path);
+            } else {
+                stamps.push((path, meta.modified().ok(), meta.len()));
+            }
+        }
+    }
+    stamps.sort();
+    stamps
+}
+
+/// Watchers by directory, shared by the scheduler, channel workers and tool
+/// registries so they all see the same reload.
+static SHARED: OnceLock<Mutex<HashMap<PathBuf, Arc<ConfDWatcher>>>> = OnceLock::new();
+
+/// Cached [`ConfD`] that reloads when the directory changes. Checking is a
+/// directory walk of `stat` calls, cheap enough to run once per turn.
+#[derive(Default)]
+pub struct ConfDWatcher {
+    dir: Option<PathBuf>,
+    state: Mutex<Option<(Fingerprint, Arc<ConfD>)>>,
+}
+
+impl ConfDWatcher {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir: Some(dir),
+            state: Mutex::new(None),
+        }
+    }
+
+    /// The process-wide watcher for the `conf.d` directory next to
+    /// `config.toml`.
+    pub fn shared(config: &Config) -> Arc<Self> {
+        let Some(parent) = config.config_path.parent() else {
+            return Arc::new(Self::default());
+        };
+        let dir = parent.join(CONF_D_DIR);
+        SHARED
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .entry(dir.clone())
+            .or_insert_with(|| Arc::new(Self::new(dir)))
+            .clone()
+    }
+
+    /// Current contents. The same `Arc` is returned until the directory
+    /// changes, so callers detect a reload with [`Arc::ptr_eq`].
+    pub fn current(&self) -> Arc<ConfD> {
+        let Some(dir) = &self.dir else {
+            return Arc::new(ConfD::default());
+        };
+        let stamps = fingerprint(dir);
+        let mut state = self.state.lock();
+        if let Some((previous, conf_d)) = state.as_ref() {
+            if *previous == stamps {
+                return conf_d.clone();
+            }
+        }
+        let conf_d = Arc::new(ConfD::load(dir));
+        if state.is_some() {
+            tracing::info!("Reloaded {}", dir.display());
+        }
+        *state = Some((stamps, conf_d.clone()));
+        conf_d
+    }
+
+    /// `base_prompt` followed by the current `conf.d` prompt sections.
+    pub fn apply_to_prompt(&self, base_prompt: &str) -> String {
+        let section = self.current().prompt_section();
+        if section.is_empty() {
+            base_prompt.to_string()
+        } else {
+            format!("{base_prompt}\n\n{}", section.trim_end())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_prompts_tools_and_automations() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::create_dir_all(dir.join("prompts")).unwrap();
+        std::fs::create_dir_all(dir.join("tools")).unwrap();
+        std::fs::create_dir_all(dir.join("automations")).unwrap();
+        std::fs::write(dir.join("persona.md"), "Dry wit.\n").unwrap();
+        std::fs::write(dir.join("prompts/20-home.md"), "Lights are Hue.").unwrap();
+        std::fs::write(dir.join("prompts/10-style.md"), "Be brief.").unwrap();
+        std::fs::write(dir.join("prompts/notes.txt"), "ignored").unwrap();
+        std::fs::write(
+            dir.join("tools/disk.toml"),
+            "description = \"Disk usage\"\nsteps = [{ tool = \"shell\", args = { command = \"df -h\" } }]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("automations/morning.toml"),
+            "schedule = { kind = \"cron\", expr = \"0 7 * * *\" }\nprompt = \"Brief me\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("automations/broken.toml"),
+            "schedule = { kind = \"cron\", expr = \"0 7 * * *\" }\n",
+        )
+        .unwrap();
+
+        let conf_d = ConfD::load(dir);
+        assert_eq!(conf_d.persona.as_deref(), Some("Dry wit."));
+        let names: Vec<&str> = conf_d.prompts.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, ["10-style", "20-home"]);
+        assert!(conf_d.composite_tools.contains_key("disk"));
+        assert_eq!(conf_d.automations.len(), 1);
+        assert_eq!(conf_d.automations[0].0, "morning");
+
+        let section = conf_d.prompt_section();
+        assert!(section.find("Be brief.").unwrap() < section.find("Lights are Hue.").unwrap());
+        assert!(section.starts_with("## Persona"));
+    }
+
+    #[test]
+    fn watcher_reloads_on_change() {
+        let tmp = tempfile::tempdir().unwrap();
+        let watcher = ConfDWatcher::new(tmp.path().to_path_buf());
+        assert_eq!(watcher.apply_to_prompt("base"), "base");
+        let before = watcher.current();
+        assert!(Arc::ptr_eq(&before, &watcher.current()));
+
+        std::fs::create_dir_all(tmp.path().join("prompts")).unwrap();
+        std::fs::write(tmp.path().join("prompts/a.md"), "Say hi.").unwrap();
+        let conf_d = watcher.current();
+        assert!(!Arc::ptr_eq(&before, &conf_d));
+        assert_eq!(conf_d.prompts.len(), 1);
+        assert!(watcher.apply_to_prompt("base").ends_with("Say hi."));
+    }
+
+    #[test]
+    fn shared_watcher_is_reused_per_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = Config {
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        assert!(Arc::ptr_eq(
+            &ConfDWatcher::shared(&config),
+            &ConfDWatcher::shared(&config)
+        ));
+    }
+
+    #[test]
+    fn default_watcher_is_empty() {
+        let watcher = ConfDWatcher::default();
+        assert_eq!(watcher.apply_to_prompt("base"), "base");
+    }
+}
@@ -1,22 +1,34 @@
+pub mod confd;
 pub mod schema;
+pub mod sync;
 pub mod traits;
 
 #[allow(unused_imports)]
 pub use schema::{
     apply_runtime_proxy_to_builder, build_runtime_proxy_client,
     build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
-    AgentConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig, BrowserConfig,
-    BuiltinHooksConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config, CostConfig,
-    CronConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, EmbeddingRouteConfig,
-    EstopConfig, GatewayConfig, HardwareConfig, HardwareTransport, HeartbeatConfig, HooksConfig,
-    HttpRequestConfig, IMessageConfig, IdentityConfig, LarkConfig, MatrixConfig, MemoryConfig,
-    ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig, ObservabilityConfig, OtpConfig,
-    OtpMethod, PeripheralBoardConfig, PeripheralsConfig, ProxyConfig, ProxyScope,
-    QueryClassificationConfig, ReliabilityConfig, ResourceLimitsConfig, RuntimeConfig,
-    SandboxBackend, SandboxConfig, SchedulerConfig, SecretsConfig, SecurityConfig, SkillsConfig,
-    SkillsPromptInjectionMode, SlackConfig, StorageConfig, StorageProviderConfig,
-    StorageProviderSection, StreamMode, TelegramConfig, TranscriptionConfig, TunnelConfig,
-    WebSearchConfig, WebhookConfig,
+    AccessLogBodies, AdvisoriesConfig, AgentConfig, AnalyticsConfig, AuditConfig, AutonomyConfig,
+    BackupConfig, BrowserComputerUseConfig, BrowserConfig, BuiltinHooksConfig, CertCheckConfig,
+    ChannelsConfig, ClassificationRule, ClockFormat, CodeExecConfig, ComposioConfig,
+    CompositeStepConfig, CompositeToolConfig, Config, CostConfig, CronConfig, DelegateAgentConfig,
+    DiscordConfig, DnsConfig, DockerRuntimeConfig, DomainInfoConfig, DownloadClientConfig,
+    DownloadsConfig, EmbeddingRouteConfig, EnergyConfig, EnergyInverterConfig, EnergyMeterConfig,
+    EstopConfig, FactsConfig, GatewayAccessLogConfig, GatewayConfig, GatewayFeedbackConfig,
+    GeofenceConfig, HardwareConfig, HardwareTransport, HeartbeatConfig, HooksConfig,
+    HttpRequestConfig, IMessageConfig, IdentityConfig, InventoryConfig, LarkConfig, LocaleConfig,
+    LocationConfig, MatrixConfig, MeasurementSystem, MediaServerConfig, MemoryConfig,
+    MetricsConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig, NotesConfig,
+    NotifyConfig, NotifyTargetConfig, ObjectStoreConfig, ObservabilityConfig, OtpConfig, OtpMethod,
+    PeopleCardDavConfig, PeopleConfig, PeripheralBoardConfig, PeripheralsConfig, PhoneAlertConfig,
+    PhoneAlertModemConfig, PhoneAlertTwilioConfig, PhoneAlertVonageConfig, PrinterConfig,
+    ProxyConfig, ProxyScope, PublishConfig, PythonReplConfig, QueryClassificationConfig,
+    ReliabilityConfig, ResearchConfig, ResourceLimitsConfig, RuntimeConfig, SafeModeConfig,
+    SandboxBackend, SandboxConfig, SchedulerConfig, ScriptHookConfig, SecretsConfig,
+    SecurityConfig, SkillsConfig, SkillsPromptInjectionMode, SlackConfig, StorageConfig,
+    StorageProviderConfig, StorageProviderSection, StreamMode, SyncConfig, TasksCalDavConfig,
+    TasksConfig, TasksVikunjaConfig, TelegramConfig, TemperatureUnit, TranscriptionConfig,
+    TunnelConfig, UpdatesConfig, UptimeKumaConfig, UptimeKumaPushJobConfig, WebDavConfig,
+    WebDavTarget, WebSearchConfig, WebhookConfig,
 };
 
 pub fn name_and_presence<T: traits::ChannelConfig>(channel: &Option<T>) -> (&'static str, bool) {
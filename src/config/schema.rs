@@ -33,10 +33,24 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "channel.slack",
     "channel.telegram",
     "channel.whatsapp",
+    "tool.advisories",
     "tool.browser",
     "tool.composio",
+    "tool.domain_info",
+    "tool.downloads",
+    "tool.energy",
     "tool.http_request",
+    "tool.media_server",
+    "tool.notify",
+    "tool.object_store",
+    "tool.people",
+    "tool.phone_alert",
+    "tool.printer",
     "tool.pushover",
+    "tool.research",
+    "tool.tasks",
+    "tool.updates",
+    "tool.webdav",
     "memory.embeddings",
     "tunnel.custom",
     "transcription.groq",
@@ -195,6 +209,10 @@ pub struct Config {
     #[serde(default)]
     pub agents: HashMap<String, DelegateAgentConfig>,
 
+    /// Composite tools built from existing tool calls (`[composite_tools.<name>]`).
+    #[serde(default)]
+    pub composite_tools: HashMap<String, CompositeToolConfig>,
+
     /// Hooks configuration (lifecycle hooks and built-in hook toggles).
     #[serde(default)]
     pub hooks: HooksConfig,
@@ -206,6 +224,154 @@ pub struct Config {
     /// Voice transcription configuration (Whisper API via Groq).
     #[serde(default)]
     pub transcription: TranscriptionConfig,
+
+    /// Sandboxed code execution tool configuration (`[code_exec]`).
+    #[serde(default)]
+    pub code_exec: CodeExecConfig,
+
+    /// Persistent Python interpreter tool configuration (`[python_repl]`).
+    #[serde(default)]
+    pub python_repl: PythonReplConfig,
+
+    /// Static dashboard publisher (`[publish]`).
+    #[serde(default)]
+    pub publish: PublishConfig,
+
+    /// S3-compatible object storage tool (`[object_store]`).
+    #[serde(default)]
+    pub object_store: ObjectStoreConfig,
+
+    /// WebDAV / Nextcloud file tool (`[webdav]`).
+    #[serde(default)]
+    pub webdav: WebDavConfig,
+
+    /// Named todo / shopping list tool (`[tasks]`).
+    #[serde(default)]
+    pub tasks: TasksConfig,
+
+    /// Quick-capture daily markdown notes (`[notes]`).
+    #[serde(default)]
+    pub notes: NotesConfig,
+
+    /// Birthday and anniversary reminders (`[people]`).
+    #[serde(default)]
+    pub people: PeopleConfig,
+
+    /// Location webhook ingestion and geofences (`[location]`).
+    #[serde(default)]
+    pub location: LocationConfig,
+
+    /// Solar / energy monitoring (`[energy]`).
+    #[serde(default)]
+    pub energy: EnergyConfig,
+
+    /// 3D printer tool and print notifications (`[printer]`).
+    #[serde(default)]
+    pub printer: PrinterConfig,
+
+    /// Jellyfin / Plex media server tool (`[media_server]`).
+    #[serde(default)]
+    pub media_server: MediaServerConfig,
+
+    /// Torrent / Usenet download clients (`[downloads]`).
+    #[serde(default)]
+    pub downloads: DownloadsConfig,
+
+    /// Push notification targets: ntfy, Gotify, Pushover (`[notify]`).
+    #[serde(default)]
+    pub notify: NotifyConfig,
+
+    /// SMS / voice-call escalation for critical alerts (`[phone_alert]`).
+    #[serde(default)]
+    pub phone_alert: PhoneAlertConfig,
+
+    /// Replicate `conf.d` sections from a primary instance (`[sync]`).
+    #[serde(default)]
+    pub sync: SyncConfig,
+
+    /// Crash-loop detection and safe mode (`[safe_mode]`).
+    #[serde(default)]
+    pub safe_mode: SafeModeConfig,
+
+    /// Scheduled config and state backups (`[backup]`).
+    #[serde(default)]
+    pub backup: BackupConfig,
+
+    /// TLS certificate expiry checks (`[cert_check]`).
+    #[serde(default)]
+    pub cert_check: CertCheckConfig,
+
+    /// DNS lookups and record change watches (`[dns]`).
+    #[serde(default)]
+    pub dns: DnsConfig,
+
+    /// RDAP domain registration lookups (`[domain_info]`).
+    #[serde(default)]
+    pub domain_info: DomainInfoConfig,
+
+    /// Pending package, container image and release updates (`[updates]`).
+    #[serde(default)]
+    pub updates: UpdatesConfig,
+
+    /// OSV security advisory matching (`[advisories]`).
+    #[serde(default)]
+    pub advisories: AdvisoriesConfig,
+
+    /// Device inventory of what has been seen on the network (`[inventory]`).
+    #[serde(default)]
+    pub inventory: InventoryConfig,
+
+    /// Uptime Kuma push heartbeats sent by the daemon (`[uptime_kuma]`).
+    #[serde(default)]
+    pub uptime_kuma: UptimeKumaConfig,
+
+    /// Local metric history and anomaly detection (`[metrics]`).
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+
+    /// Multi-step web research tool (`[research]`).
+    #[serde(default)]
+    pub research: ResearchConfig,
+
+    /// Structured fact store with expiry and provenance (`[facts]`).
+    #[serde(default)]
+    pub facts: FactsConfig,
+
+    /// Turn and tool-call analytics (`[analytics]`).
+    #[serde(default)]
+    pub analytics: AnalyticsConfig,
+
+    /// Time, unit and currency conventions for agent output (`[locale]`).
+    #[serde(default)]
+    pub locale: LocaleConfig,
+}
+
+// ── Composite tools ─────────────────────────────────────────────
+
+/// A named sequence of existing tool calls registered as a single tool.
+///
+/// Step arguments are templates: `{{param}}` inserts a call parameter,
+/// `{{prev}}` the previous step's output, and `{{steps.N}}` the output of
+/// step `N` (0-based). The last step's output is the tool's output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompositeToolConfig {
+    /// Description shown to the LLM
+    pub description: String,
+    /// Call parameters: name → description. All are required strings.
+    #[serde(default)]
+    pub parameters: HashMap<String, String>,
+    /// Tool calls to run in order
+    pub steps: Vec<CompositeStepConfig>,
+}
+
+/// One step of a composite tool.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CompositeStepConfig {
+    /// Name of an existing tool
+    pub tool: String,
+    /// Arguments; string values may contain `{{…}}` placeholders
+    #[serde(default)]
+    pub args: serde_json::Map<String, serde_json::Value>,
 }
 
 // ── Delegate Agents ──────────────────────────────────────────────
@@ -383,6 +549,16 @@ pub struct AgentConfig {
     /// Tool dispatch strategy (e.g. `"auto"`). Default: `"auto"`.
     #[serde(default = "default_agent_tool_dispatcher")]
     pub tool_dispatcher: String,
+    /// Wall-clock limit for one gateway agent turn, in seconds. When it
+    /// expires, pending provider calls and tool executions are cancelled.
+    /// `0` disables the limit. Default: `0`.
+    #[serde(default)]
+    pub turn_timeout_secs: u64,
+    /// Maximum concurrent executions per tool name, shared by every agent
+    /// turn and cron job in the process (e.g. `shell = 1`). Unlisted tools
+    /// and `0` are unlimited. Default: empty.
+    #[serde(default)]
+    pub tool_concurrency: HashMap<String, usize>,
 }
 
 fn default_agent_max_tool_iterations() -> usize {
@@ -405,6 +581,8 @@ impl Default for AgentConfig {
             max_history_messages: default_agent_max_history_messages(),
             parallel_tools: false,
             tool_dispatcher: default_agent_tool_dispatcher(),
+            turn_timeout_secs: 0,
+            tool_concurrency: HashMap::new(),
         }
     }
 }
@@ -553,6 +731,22 @@ pub struct CostConfig {
     #[serde(default)]
     pub allow_override: bool,
 
+    /// Hard daily token ceiling across all models; 0 disables the token cap (default: 0)
+    #[serde(default)]
+    pub daily_token_limit: u64,
+
+    /// Provider interactive chat switches to once the daily cap is reached (default: none)
+    #[serde(default)]
+    pub cap_fallback_provider: Option<String>,
+
+    /// Model used with `cap_fallback_provider` (default: the regular model selection)
+    #[serde(default)]
+    pub cap_fallback_model: Option<String>,
+
+    /// Defer scheduled agent jobs to the next UTC day once the daily cap is reached (default: true)
+    #[serde(default = "default_true")]
+    pub defer_jobs_at_cap: bool,
+
     /// Per-model pricing (USD per 1M tokens)
     #[serde(default)]
     pub prices: std::collections::HashMap<String, ModelPricing>,
@@ -590,6 +784,10 @@ impl Default for CostConfig {
             monthly_limit_usd: default_monthly_limit(),
             warn_at_percent: default_warn_percent(),
             allow_override: false,
+            daily_token_limit: 0,
+            cap_fallback_provider: None,
+            cap_fallback_model: None,
+            defer_jobs_at_cap: true,
             prices: get_default_pricing(),
         }
     }
@@ -731,6 +929,7 @@ impl Default for PeripheralBoardConfig {
 ///
 /// Controls the HTTP gateway for webhook and pairing endpoints.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct GatewayConfig {
     /// Gateway port (default: 42617)
     #[serde(default = "default_gateway_port")]
@@ -772,6 +971,91 @@ pub struct GatewayConfig {
     /// Maximum distinct idempotency keys retained in memory.
     #[serde(default = "default_gateway_idempotency_max_keys")]
     pub idempotency_max_keys: usize,
+
+    /// Serve `GET /status` without a bearer token (default: false).
+    /// The page shows component health and cron results, never secrets.
+    #[serde(default)]
+    pub public_status_page: bool,
+
+    /// Request/response access log (`[gateway.access_log]`).
+    #[serde(default)]
+    pub access_log: GatewayAccessLogConfig,
+
+    /// Ratings and comments on `/api/ws` turns (`[gateway.feedback]`).
+    #[serde(default)]
+    pub feedback: GatewayFeedbackConfig,
+}
+
+/// How much of a request body the gateway access log keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessLogBodies {
+    /// Record only the body size (default).
+    #[default]
+    Omit,
+    /// Keep JSON structure with every string value masked.
+    Redacted,
+    /// Keep the body as sent, with credentials scrubbed.
+    Full,
+}
+
+/// Gateway access log (`[gateway.access_log]`).
+///
+/// Entries (method, route, status, latency, token scope) are stored in
+/// `{workspace}/gateway/access_log.db`, separate from the security audit log.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayAccessLogConfig {
+    /// Record gateway requests (default: true).
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Request body handling: `omit`, `redacted` or `full` (default: omit).
+    #[serde(default)]
+    pub bodies: AccessLogBodies,
+    /// Oldest entries beyond this count are pruned (default: 10000).
+    #[serde(default = "default_access_log_max_entries")]
+    pub max_entries: usize,
+}
+
+fn default_access_log_max_entries() -> usize {
+    10_000
+}
+
+impl Default for GatewayAccessLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            bodies: AccessLogBodies::Omit,
+            max_entries: default_access_log_max_entries(),
+        }
+    }
+}
+
+/// Response feedback (`[gateway.feedback]`).
+///
+/// Completed `/api/ws` turns are stored with their tool trace in
+/// `{workspace}/gateway/feedback.db` so clients can rate them and the rated
+/// turns can be exported as a dataset.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayFeedbackConfig {
+    /// Store turns and accept feedback (default: false).
+    #[serde(default)]
+    pub enabled: bool,
+    /// Days an unrated turn stays rateable; rated turns are kept (default: 30).
+    #[serde(default = "default_feedback_retention_days")]
+    pub retention_days: u32,
+}
+
+fn default_feedback_retention_days() -> u32 {
+    30
+}
+
+impl Default for GatewayFeedbackConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_days: default_feedback_retention_days(),
+        }
+    }
 }
 
 fn default_gateway_port() -> u16 {
@@ -820,6 +1104,9 @@ impl Default for GatewayConfig {
             rate_limit_max_keys: default_gateway_rate_limit_max_keys(),
             idempotency_ttl_secs: default_idempotency_ttl_secs(),
             idempotency_max_keys: default_gateway_idempotency_max_keys(),
+            public_status_page: false,
+            access_log: GatewayAccessLogConfig::default(),
+            feedback: GatewayFeedbackConfig::default(),
         }
     }
 }
@@ -955,113 +1242,1709 @@ pub struct BrowserConfig {
     pub computer_use: BrowserComputerUseConfig,
 }
 
-fn default_browser_backend() -> String {
-    "agent_browser".into()
+fn default_browser_backend() -> String {
+    "agent_browser".into()
+}
+
+fn default_browser_webdriver_url() -> String {
+    "http://127.0.0.1:9515".into()
+}
+
+impl Default for BrowserConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed_domains: Vec::new(),
+            session_name: None,
+            backend: default_browser_backend(),
+            native_headless: default_true(),
+            native_webdriver_url: default_browser_webdriver_url(),
+            native_chrome_path: None,
+            computer_use: BrowserComputerUseConfig::default(),
+        }
+    }
+}
+
+// ── HTTP request tool ───────────────────────────────────────────
+
+/// HTTP request tool configuration (`[http_request]` section).
+///
+/// Deny-by-default: if `allowed_domains` is empty, all HTTP requests are rejected.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HttpRequestConfig {
+    /// Enable `http_request` tool for API interactions
+    #[serde(default)]
+    pub enabled: bool,
+    /// Allowed domains for HTTP requests (exact or subdomain match)
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    /// Maximum response size in bytes (default: 1MB)
+    #[serde(default = "default_http_max_response_size")]
+    pub max_response_size: usize,
+    /// Request timeout in seconds (default: 30)
+    #[serde(default = "default_http_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for HttpRequestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed_domains: vec![],
+            max_response_size: default_http_max_response_size(),
+            timeout_secs: default_http_timeout_secs(),
+        }
+    }
+}
+
+fn default_http_max_response_size() -> usize {
+    1_000_000 // 1MB
+}
+
+fn default_http_timeout_secs() -> u64 {
+    30
+}
+
+// ── Web search ───────────────────────────────────────────────────
+
+/// Web search tool configuration (`[web_search]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebSearchConfig {
+    /// Enable `web_search_tool` for web searches
+    #[serde(default)]
+    pub enabled: bool,
+    /// Search provider: "duckduckgo" (free, no API key) or "brave" (requires API key)
+    #[serde(default = "default_web_search_provider")]
+    pub provider: String,
+    /// Brave Search API key (required if provider is "brave")
+    #[serde(default)]
+    pub brave_api_key: Option<String>,
+    /// Maximum results per search (1-10)
+    #[serde(default = "default_web_search_max_results")]
+    pub max_results: usize,
+    /// Request timeout in seconds
+    #[serde(default = "default_web_search_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_web_search_provider() -> String {
+    "duckduckgo".into()
+}
+
+fn default_web_search_max_results() -> usize {
+    5
+}
+
+fn default_web_search_timeout_secs() -> u64 {
+    15
+}
+
+impl Default for WebSearchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: default_web_search_provider(),
+            brave_api_key: None,
+            max_results: default_web_search_max_results(),
+            timeout_secs: default_web_search_timeout_secs(),
+        }
+    }
+}
+
+// ── Code execution sandbox ──────────────────────────────────────
+
+/// Sandboxed code execution tool configuration (`[code_exec]` section).
+///
+/// Snippets run in a throwaway container with networking disabled and the
+/// workspace mounted at `/workspace`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CodeExecConfig {
+    /// Enable the `code_exec` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Container CLI used to launch the sandbox: "podman" or "docker"
+    #[serde(default = "default_code_exec_runtime")]
+    pub container_runtime: String,
+    /// Image used for Python snippets
+    #[serde(default = "default_code_exec_python_image")]
+    pub python_image: String,
+    /// Image used for JavaScript snippets
+    #[serde(default = "default_code_exec_node_image")]
+    pub node_image: String,
+    /// Wall-clock limit per snippet in seconds
+    #[serde(default = "default_code_exec_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Memory limit per snippet in MB
+    #[serde(default = "default_code_exec_memory_limit_mb")]
+    pub memory_limit_mb: u64,
+    /// CPU limit per snippet (fractional CPUs)
+    #[serde(default = "default_code_exec_cpu_limit")]
+    pub cpu_limit: f64,
+    /// Mount the workspace read-only inside the sandbox
+    #[serde(default)]
+    pub read_only_workspace: bool,
+}
+
+fn default_code_exec_runtime() -> String {
+    "podman".into()
+}
+
+fn default_code_exec_python_image() -> String {
+    "python:3.12-alpine".into()
+}
+
+fn default_code_exec_node_image() -> String {
+    "node:22-alpine".into()
+}
+
+fn default_code_exec_timeout_secs() -> u64 {
+    30
+}
+
+fn default_code_exec_memory_limit_mb() -> u64 {
+    256
+}
+
+fn default_code_exec_cpu_limit() -> f64 {
+    1.0
+}
+
+impl Default for CodeExecConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            container_runtime: default_code_exec_runtime(),
+            python_image: default_code_exec_python_image(),
+            node_image: default_code_exec_node_image(),
+            timeout_secs: default_code_exec_timeout_secs(),
+            memory_limit_mb: default_code_exec_memory_limit_mb(),
+            cpu_limit: default_code_exec_cpu_limit(),
+            read_only_workspace: false,
+        }
+    }
+}
+
+// ── Persistent Python REPL ──────────────────────────────────────
+
+/// Persistent Python interpreter tool configuration (`[python_repl]` section).
+///
+/// Sessions run in the `[code_exec]` sandbox (runtime, `python_image` and
+/// limits), one per conversation.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PythonReplConfig {
+    /// Enable the `python_repl` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Per-call execution timeout in seconds; a timed-out session is discarded
+    #[serde(default = "default_python_repl_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Maximum concurrently open sessions; the least recently used is evicted
+    #[serde(default = "default_python_repl_max_sessions")]
+    pub max_sessions: usize,
+}
+
+fn default_python_repl_timeout_secs() -> u64 {
+    60
+}
+
+fn default_python_repl_max_sessions() -> usize {
+    4
+}
+
+impl Default for PythonReplConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout_secs: default_python_repl_timeout_secs(),
+            max_sessions: default_python_repl_max_sessions(),
+        }
+    }
+}
+
+// ── Dashboard publisher ─────────────────────────────────────────
+
+/// Static dashboard publisher configuration (`[publish]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PublishConfig {
+    /// Regenerate the static dashboard from the daemon
+    #[serde(default)]
+    pub enabled: bool,
+    /// Output directory, relative to the workspace
+    #[serde(default = "default_publish_output_dir")]
+    pub output_dir: String,
+    /// Workspace-relative glob patterns selecting reports to publish
+    #[serde(default = "default_publish_sources")]
+    pub sources: Vec<String>,
+    /// Minutes between regenerations
+    #[serde(default = "default_publish_interval_minutes")]
+    pub interval_minutes: u32,
+    /// Commit and push the output directory when it is a git checkout
+    #[serde(default)]
+    pub git_push: bool,
+    /// Remote used by `git_push`
+    #[serde(default = "default_publish_git_remote")]
+    pub git_remote: String,
+    /// Also upload the site to `bucket/prefix` via `[object_store]`
+    #[serde(default)]
+    pub s3_target: Option<String>,
+}
+
+fn default_publish_output_dir() -> String {
+    "site".into()
+}
+
+fn default_publish_sources() -> Vec<String> {
+    vec!["reports/out/**/*.md".into(), "reports/out/**/*.html".into()]
+}
+
+fn default_publish_interval_minutes() -> u32 {
+    60
+}
+
+fn default_publish_git_remote() -> String {
+    "origin".into()
+}
+
+impl Default for PublishConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            output_dir: default_publish_output_dir(),
+            sources: default_publish_sources(),
+            interval_minutes: default_publish_interval_minutes(),
+            git_push: false,
+            git_remote: default_publish_git_remote(),
+            s3_target: None,
+        }
+    }
+}
+
+// ── Object storage ──────────────────────────────────────────────
+
+/// S3-compatible object storage configuration (`[object_store]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ObjectStoreConfig {
+    /// Enable the `object_store` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Service endpoint, e.g. `https://s3.amazonaws.com` or `http://minio.lan:9000`
+    #[serde(default = "default_object_store_endpoint")]
+    pub endpoint: String,
+    /// Signing region
+    #[serde(default = "default_object_store_region")]
+    pub region: String,
+    /// Address buckets as `endpoint/bucket` instead of `bucket.endpoint` (MinIO and most self-hosted stores)
+    #[serde(default)]
+    pub path_style: bool,
+    /// Access key ID; falls back to `AWS_ACCESS_KEY_ID`
+    #[serde(default)]
+    pub access_key_id: Option<String>,
+    /// Secret access key (encrypted at rest); falls back to `AWS_SECRET_ACCESS_KEY`
+    #[serde(default)]
+    pub secret_access_key: Option<String>,
+    /// Reachable locations as `bucket` or `bucket/prefix`; empty denies everything
+    #[serde(default)]
+    pub allowed: Vec<String>,
+    /// Maximum object size transferred in either direction, in MiB
+    #[serde(default = "default_object_store_max_object_mb")]
+    pub max_object_mb: u64,
+    /// HTTP request timeout in seconds
+    #[serde(default = "default_object_store_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_object_store_endpoint() -> String {
+    "https://s3.amazonaws.com".into()
+}
+
+fn default_object_store_region() -> String {
+    "us-east-1".into()
+}
+
+fn default_object_store_max_object_mb() -> u64 {
+    100
+}
+
+fn default_object_store_timeout_secs() -> u64 {
+    120
+}
+
+impl Default for ObjectStoreConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: default_object_store_endpoint(),
+            region: default_object_store_region(),
+            path_style: false,
+            access_key_id: None,
+            secret_access_key: None,
+            allowed: Vec::new(),
+            max_object_mb: default_object_store_max_object_mb(),
+            timeout_secs: default_object_store_timeout_secs(),
+        }
+    }
+}
+
+// ── WebDAV ──────────────────────────────────────────────────────
+
+/// WebDAV / Nextcloud file tool configuration (`[webdav]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebDavConfig {
+    /// Enable the `webdav` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Named WebDAV bases, e.g. `[webdav.targets.nextcloud]`
+    #[serde(default)]
+    pub targets: HashMap<String, WebDavTarget>,
+    /// Maximum file size transferred in either direction, in MiB
+    #[serde(default = "default_webdav_max_file_mb")]
+    pub max_file_mb: u64,
+    /// HTTP request timeout in seconds
+    #[serde(default = "default_webdav_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+/// A single WebDAV base the `webdav` tool may reach.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WebDavTarget {
+    /// Base collection URL, e.g. `https://cloud.example.com/remote.php/dav/files/alice/`
+    pub url: String,
+    /// Basic auth user
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Basic auth password or app password (encrypted at rest)
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Path prefixes under `url` the tool may touch
+    #[serde(default = "default_webdav_allowed_paths")]
+    pub allowed_paths: Vec<String>,
+}
+
+fn default_webdav_max_file_mb() -> u64 {
+    100
+}
+
+fn default_webdav_timeout_secs() -> u64 {
+    60
+}
+
+fn default_webdav_allowed_paths() -> Vec<String> {
+    vec!["/".into()]
+}
+
+impl Default for WebDavConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            targets: HashMap::new(),
+            max_file_mb: default_webdav_max_file_mb(),
+            timeout_secs: default_webdav_timeout_secs(),
+        }
+    }
+}
+
+// ── Tasks / shopping lists ──────────────────────────────────────
+
+/// Named todo / shopping list tool configuration (`[tasks]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TasksConfig {
+    /// Enable the `tasks` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Storage backend: `"sqlite"` (workspace-local), `"caldav"`, or `"vikunja"`
+    #[serde(default = "default_tasks_backend")]
+    pub backend: String,
+    /// List used when a request does not name one
+    #[serde(default = "default_tasks_default_list")]
+    pub default_list: String,
+    /// CalDAV VTODO backend settings (`[tasks.caldav]`)
+    #[serde(default)]
+    pub caldav: Option<TasksCalDavConfig>,
+    /// Vikunja backend settings (`[tasks.vikunja]`)
+    #[serde(default)]
+    pub vikunja: Option<TasksVikunjaConfig>,
+}
+
+/// CalDAV backend for the `tasks` tool. Each list is a calendar collection.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TasksCalDavConfig {
+    /// Calendar home URL, e.g. `https://cloud.example.com/remote.php/dav/calendars/alice/`
+    pub url: String,
+    /// Basic auth user
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Basic auth password or app password (encrypted at rest)
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Optional list name → calendar collection name overrides
+    #[serde(default)]
+    pub calendars: HashMap<String, String>,
+}
+
+/// Vikunja backend for the `tasks` tool. Each list is a Vikunja project.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TasksVikunjaConfig {
+    /// Vikunja base URL, e.g. `https://vikunja.example.com`
+    pub url: String,
+    /// API token (encrypted at rest)
+    #[serde(default)]
+    pub api_token: Option<String>,
+}
+
+fn default_tasks_backend() -> String {
+    "sqlite".into()
+}
+
+fn default_tasks_default_list() -> String {
+    "shopping".into()
+}
+
+impl Default for TasksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_tasks_backend(),
+            default_list: default_tasks_default_list(),
+            caldav: None,
+            vikunja: None,
+        }
+    }
+}
+
+// ── Notes ───────────────────────────────────────────────────────
+
+/// Quick-capture notes tool configuration (`[notes]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NotesConfig {
+    /// Enable the `note` tool
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Notes directory. Relative paths resolve under the workspace; an
+    /// absolute path (e.g. an Obsidian vault) must be listed in
+    /// `[autonomy].allowed_roots`.
+    #[serde(default = "default_notes_dir")]
+    pub dir: String,
+    /// chrono format for daily file names (without the `.md` extension)
+    #[serde(default = "default_notes_daily_format")]
+    pub daily_format: String,
+}
+
+fn default_notes_dir() -> String {
+    "notes".into()
+}
+
+fn default_notes_daily_format() -> String {
+    "%Y-%m-%d".into()
+}
+
+impl Default for NotesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: default_notes_dir(),
+            daily_format: default_notes_daily_format(),
+        }
+    }
+}
+
+// ── People (birthdays / anniversaries) ──────────────────────────
+
+/// Birthday and anniversary reminders (`[people]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PeopleConfig {
+    /// Enable the daily reminder rule and the `people` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Workspace-relative TOML dataset of people and dates
+    #[serde(default = "default_people_file")]
+    pub file: String,
+    /// Optional CardDAV address book merged with the TOML file (`[people.carddav]`)
+    #[serde(default)]
+    pub carddav: Option<PeopleCardDavConfig>,
+    /// Local time (`HH:MM`) at which the daily reminder is sent
+    #[serde(default = "default_people_remind_at")]
+    pub remind_at: String,
+    /// How many days ahead the daily summary looks
+    #[serde(default = "default_people_lookahead_days")]
+    pub lookahead_days: u32,
+    /// Delivery channel for reminders (`telegram`, `discord`, `slack`, `mattermost`)
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Recipient / chat id on `channel`
+    #[serde(default)]
+    pub to: Option<String>,
+}
+
+/// CardDAV address book read for `BDAY` / `ANNIVERSARY` fields.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PeopleCardDavConfig {
+    /// Address book collection URL
+    pub url: String,
+    /// Basic auth user
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Basic auth password or app password (encrypted at rest)
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+fn default_people_file() -> String {
+    "people.toml".into()
+}
+
+fn default_people_remind_at() -> String {
+    "08:00".into()
+}
+
+fn default_people_lookahead_days() -> u32 {
+    14
+}
+
+impl Default for PeopleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            file: default_people_file(),
+            carddav: None,
+            remind_at: default_people_remind_at(),
+            lookahead_days: default_people_lookahead_days(),
+            channel: None,
+            to: None,
+        }
+    }
+}
+
+// ── Location ────────────────────────────────────────────────────
+
+/// Location webhook ingestion (`[location]` section).
+///
+/// Enables `POST /hooks/location` (OwnTracks HTTP mode or a plain JSON
+/// payload from Home Assistant) and the read-only `where_is` tool.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct LocationConfig {
+    /// Enable the location webhook and `where_is` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Shared secret accepted as the HTTP basic auth password (OwnTracks),
+    /// `Authorization: Bearer <secret>`, or `X-Location-Secret`. When unset,
+    /// the gateway's pairing token is required instead. Encrypted at rest.
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Named circular regions; enter/leave transitions are emitted on the
+    /// gateway event stream.
+    #[serde(default)]
+    pub geofences: Vec<GeofenceConfig>,
+}
+
+/// A circular geofence.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GeofenceConfig {
+    /// Region name, e.g. `"home"`
+    pub name: String,
+    /// Centre latitude in degrees
+    pub lat: f64,
+    /// Centre longitude in degrees
+    pub lon: f64,
+    /// Radius in metres
+    #[serde(default = "default_geofence_radius_m")]
+    pub radius_m: f64,
+}
+
+fn default_geofence_radius_m() -> f64 {
+    150.0
+}
+
+// ── Energy ──────────────────────────────────────────────────────
+
+/// Solar / energy monitoring tool configuration (`[energy]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EnergyConfig {
+    /// Enable the `energy` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Solar inverter backend (`[energy.inverter]`)
+    #[serde(default)]
+    pub inverter: Option<EnergyInverterConfig>,
+    /// Smart meter read over MQTT (`[energy.meter]`)
+    #[serde(default)]
+    pub meter: Option<EnergyMeterConfig>,
+    /// Per-backend request timeout in seconds
+    #[serde(default = "default_energy_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+/// Solar inverter connection.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EnergyInverterConfig {
+    /// `"fronius"` (Solar API), `"solaredge"` (monitoring API), or `"sunspec"` (Modbus TCP)
+    pub backend: String,
+    /// Fronius base URL (`http://192.168.1.50`) or SunSpec `host:port` (port defaults to 502)
+    #[serde(default)]
+    pub url: Option<String>,
+    /// SolarEdge site id
+    #[serde(default)]
+    pub site_id: Option<String>,
+    /// SolarEdge API key (encrypted at rest)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// SunSpec Modbus unit id
+    #[serde(default = "default_sunspec_unit_id")]
+    pub unit_id: u8,
+}
+
+/// Smart meter values published to an MQTT broker (Tasmota SML, Shelly, HA, …).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EnergyMeterConfig {
+    /// Broker `host:port` (plain TCP; port defaults to 1883)
+    pub broker: String,
+    /// MQTT username
+    #[serde(default)]
+    pub username: Option<String>,
+    /// MQTT password (encrypted at rest)
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Topic carrying grid power in watts (positive = import, negative = export)
+    pub power_topic: String,
+    /// Dot path into a JSON payload on `power_topic`, e.g. `SML.Power_curr`
+    #[serde(default)]
+    pub power_field: Option<String>,
+    /// Optional topic carrying the cumulative grid import counter in kWh
+    #[serde(default)]
+    pub import_energy_topic: Option<String>,
+    /// Dot path into a JSON payload on `import_energy_topic`
+    #[serde(default)]
+    pub import_energy_field: Option<String>,
+}
+
+fn default_energy_timeout_secs() -> u64 {
+    10
+}
+
+fn default_sunspec_unit_id() -> u8 {
+    1
+}
+
+impl Default for EnergyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            inverter: None,
+            meter: None,
+            timeout_secs: default_energy_timeout_secs(),
+        }
+    }
+}
+
+// ── Printer ─────────────────────────────────────────────────────
+
+/// 3D printer tool and print-finished notifications (`[printer]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PrinterConfig {
+    /// Enable the `printer` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// `"octoprint"` or `"moonraker"` (Klipper)
+    #[serde(default = "default_printer_backend")]
+    pub backend: String,
+    /// Base URL, e.g. `http://octopi.local` or `http://mainsail.local:7125`
+    #[serde(default)]
+    pub url: String,
+    /// OctoPrint API key / Moonraker API key (encrypted at rest)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Webcam snapshot URL; defaults to `<url>/webcam/?action=snapshot`
+    #[serde(default)]
+    pub snapshot_url: Option<String>,
+    /// HTTP request timeout in seconds
+    #[serde(default = "default_printer_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Delivery channel for print finished / failed notifications
+    /// (`telegram`, `discord`, `slack`, `mattermost`); unset disables the watcher
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Recipient / chat id on `channel`
+    #[serde(default)]
+    pub to: Option<String>,
+    /// How often the daemon polls the printer for job state changes
+    #[serde(default = "default_printer_poll_secs")]
+    pub poll_secs: u64,
+}
+
+fn default_printer_backend() -> String {
+    "octoprint".into()
+}
+
+fn default_printer_timeout_secs() -> u64 {
+    10
+}
+
+fn default_printer_poll_secs() -> u64 {
+    60
+}
+
+impl Default for PrinterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_printer_backend(),
+            url: String::new(),
+            api_key: None,
+            snapshot_url: None,
+            timeout_secs: default_printer_timeout_secs(),
+            channel: None,
+            to: None,
+            poll_secs: default_printer_poll_secs(),
+        }
+    }
+}
+
+// ── Media server ────────────────────────────────────────────────
+
+/// Jellyfin / Plex media server tool (`[media_server]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MediaServerConfig {
+    /// Enable the `media_server` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// `"jellyfin"` (also works for Emby) or `"plex"`
+    #[serde(default = "default_media_server_backend")]
+    pub backend: String,
+    /// Server base URL, e.g. `http://jellyfin.lan:8096` or `http://plex.lan:32400`
+    #[serde(default)]
+    pub url: String,
+    /// Jellyfin API key or Plex token (encrypted at rest)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// HTTP request timeout in seconds
+    #[serde(default = "default_media_server_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_media_server_backend() -> String {
+    "jellyfin".into()
+}
+
+fn default_media_server_timeout_secs() -> u64 {
+    15
+}
+
+impl Default for MediaServerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_media_server_backend(),
+            url: String::new(),
+            api_key: None,
+            timeout_secs: default_media_server_timeout_secs(),
+        }
+    }
+}
+
+// ── Downloads ───────────────────────────────────────────────────
+
+/// Torrent / Usenet download client tool (`[downloads]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DownloadsConfig {
+    /// Enable the `downloads` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Download clients (`[[downloads.clients]]`)
+    #[serde(default)]
+    pub clients: Vec<DownloadClientConfig>,
+    /// Tracker / indexer hostnames that `add` accepts. A magnet must list at
+    /// least one allowlisted tracker; an NZB URL must be on an allowlisted host.
+    /// Empty disables `add`.
+    #[serde(default)]
+    pub allowed_sources: Vec<String>,
+    /// HTTP request timeout in seconds
+    #[serde(default = "default_downloads_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+/// One download client instance.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DownloadClientConfig {
+    /// Name used to pick this client in tool calls
+    pub name: String,
+    /// `"qbittorrent"`, `"transmission"`, or `"sabnzbd"`
+    pub kind: String,
+    /// Web UI / API base URL, e.g. `http://nas.lan:8080`
+    pub url: String,
+    /// Username (qBittorrent, Transmission)
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Password (qBittorrent, Transmission), encrypted at rest
+    #[serde(default)]
+    pub password: Option<String>,
+    /// API key (SABnzbd), encrypted at rest
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+fn default_downloads_timeout_secs() -> u64 {
+    15
+}
+
+impl Default for DownloadsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            clients: Vec::new(),
+            allowed_sources: Vec::new(),
+            timeout_secs: default_downloads_timeout_secs(),
+        }
+    }
+}
+
+// ── Notify ──────────────────────────────────────────────────────
+
+/// Push notification targets for the `notify` tool (`[notify]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NotifyConfig {
+    /// Enable the `notify` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Named delivery targets (`[[notify.targets]]`); the first is the default
+    #[serde(default)]
+    pub targets: Vec<NotifyTargetConfig>,
+    /// HTTP request timeout in seconds
+    #[serde(default = "default_notify_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+/// One push endpoint. Which fields are required depends on `kind`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct NotifyTargetConfig {
+    /// Name used to pick this target in tool calls
+    pub name: String,
+    /// `"ntfy"`, `"gotify"`, or `"pushover"`
+    #[serde(default = "default_notify_kind")]
+    pub kind: String,
+    /// Server base URL (ntfy defaults to `https://ntfy.sh`; required for gotify)
+    #[serde(default)]
+    pub url: Option<String>,
+    /// ntfy topic
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// ntfy access token, Gotify application token, or Pushover API token (encrypted at rest)
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Pushover user or group key (encrypted at rest)
+    #[serde(default)]
+    pub user_key: Option<String>,
+    /// Apprise-style URL (`tgram://`, `discord://`, `mailto://`, …). When set,
+    /// it replaces `kind` and the fields above (encrypted at rest)
+    #[serde(default)]
+    pub uri: Option<String>,
+}
+
+fn default_notify_timeout_secs() -> u64 {
+    15
+}
+
+fn default_notify_kind() -> String {
+    "ntfy".into()
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            targets: Vec::new(),
+            timeout_secs: default_notify_timeout_secs(),
+        }
+    }
+}
+
+// ── Phone alert ─────────────────────────────────────────────────
+
+/// SMS / voice-call escalation for critical alerts (`[phone_alert]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PhoneAlertConfig {
+    /// Enable the `phone_alert` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// `"twilio"`, `"vonage"`, or `"modem"` (AT commands; needs the `hardware` feature)
+    #[serde(default = "default_phone_alert_backend")]
+    pub backend: String,
+    /// E.164 numbers that may be texted or called; nothing else is reachable
+    #[serde(default)]
+    pub recipients: Vec<String>,
+    /// Sender number / caller id (Twilio, Vonage)
+    #[serde(default)]
+    pub from: Option<String>,
+    /// Twilio credentials (`[phone_alert.twilio]`)
+    #[serde(default)]
+    pub twilio: Option<PhoneAlertTwilioConfig>,
+    /// Vonage credentials (`[phone_alert.vonage]`)
+    #[serde(default)]
+    pub vonage: Option<PhoneAlertVonageConfig>,
+    /// GSM modem (`[phone_alert.modem]`)
+    #[serde(default)]
+    pub modem: Option<PhoneAlertModemConfig>,
+    /// Request timeout in seconds
+    #[serde(default = "default_phone_alert_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+/// Twilio account used for SMS and TTS calls.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PhoneAlertTwilioConfig {
+    /// Account SID (`AC…`)
+    pub account_sid: String,
+    /// Auth token (encrypted at rest)
+    pub auth_token: String,
+}
+
+/// Vonage account. SMS uses the API key/secret; calls need a Voice application.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PhoneAlertVonageConfig {
+    /// API key
+    pub api_key: String,
+    /// API secret (encrypted at rest)
+    pub api_secret: String,
+    /// Voice application id (required for calls)
+    #[serde(default)]
+    pub application_id: Option<String>,
+    /// Path to the Voice application's PEM private key (required for calls)
+    #[serde(default)]
+    pub private_key_path: Option<String>,
+}
+
+/// Serial GSM modem driven with AT commands.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PhoneAlertModemConfig {
+    /// Serial device, e.g. `/dev/ttyUSB2`
+    pub device: String,
+    /// Baud rate
+    #[serde(default = "default_phone_alert_modem_baud")]
+    pub baud: u32,
+    /// How long an alert call rings before hanging up, in seconds
+    #[serde(default = "default_phone_alert_ring_secs")]
+    pub ring_secs: u64,
+}
+
+fn default_phone_alert_backend() -> String {
+    "twilio".into()
+}
+
+fn default_phone_alert_timeout_secs() -> u64 {
+    20
+}
+
+fn default_phone_alert_modem_baud() -> u32 {
+    115_200
+}
+
+fn default_phone_alert_ring_secs() -> u64 {
+    30
+}
+
+impl Default for PhoneAlertConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_phone_alert_backend(),
+            recipients: Vec::new(),
+            from: None,
+            twilio: None,
+            vonage: None,
+            modem: None,
+            timeout_secs: default_phone_alert_timeout_secs(),
+        }
+    }
+}
+
+// ── Config sync ─────────────────────────────────────────────────
+
+/// Replicate `conf.d` sections between instances (`[sync]` section).
+///
+/// The primary sets `serve = true`; peers set `primary_url` and pull the
+/// selected sections over the primary's bearer-authenticated gateway API.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SyncConfig {
+    /// Serve this instance's `conf.d` sections at `GET /api/sync/bundle`
+    #[serde(default)]
+    pub serve: bool,
+    /// Gateway URL of the primary to pull from; unset disables pulling
+    #[serde(default)]
+    pub primary_url: Option<String>,
+    /// Bearer token paired with the primary (encrypted at rest)
+    #[serde(default)]
+    pub token: Option<String>,
+    /// `conf.d` sections to replicate: `prompts`, `tools`, `automations`
+    #[serde(default = "default_sync_sections")]
+    pub sections: Vec<String>,
+    /// Seconds between pulls
+    #[serde(default = "default_sync_interval_secs")]
+    pub interval_secs: u64,
+}
+
+fn default_sync_sections() -> Vec<String> {
+    vec!["prompts".into(), "tools".into(), "automations".into()]
+}
+
+fn default_sync_interval_secs() -> u64 {
+    300
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            serve: false,
+            primary_url: None,
+            token: None,
+            sections: default_sync_sections(),
+            interval_secs: default_sync_interval_secs(),
+        }
+    }
+}
+
+// ── Safe mode ───────────────────────────────────────────────────
+
+/// Crash-loop detection (`[safe_mode]` section).
+///
+/// Every daemon start that neither stays up for `stable_secs` nor shuts
+/// down cleanly counts as a crash. After `crash_threshold` consecutive
+/// crashes the daemon boots with only the gateway and read-only tools.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SafeModeConfig {
+    /// Enter safe mode after repeated crashes
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Consecutive crashed starts that trigger safe mode
+    #[serde(default = "default_safe_mode_crash_threshold")]
+    pub crash_threshold: u32,
+    /// Seconds a start must stay up to reset the crash counter
+    #[serde(default = "default_safe_mode_stable_secs")]
+    pub stable_secs: u64,
+    /// Where to announce safe mode, tried in order until one succeeds:
+    /// `"<channel>:<recipient>"` (e.g. `"telegram:123456"`) or
+    /// `"notify:<target>"`. Empty tries every `[[notify.targets]]` entry.
+    #[serde(default)]
+    pub notify: Vec<String>,
+}
+
+fn default_safe_mode_crash_threshold() -> u32 {
+    3
+}
+
+fn default_safe_mode_stable_secs() -> u64 {
+    300
+}
+
+impl Default for SafeModeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            crash_threshold: default_safe_mode_crash_threshold(),
+            stable_secs: default_safe_mode_stable_secs(),
+            notify: Vec::new(),
+        }
+    }
+}
+
+// ── Backup ──────────────────────────────────────────────────────
+
+/// Scheduled backups (`[backup]` section).
+///
+/// Each run writes `zeroclaw-backup-<timestamp>.tar.gz` holding the config
+/// directory (`config.toml`, `conf.d/`, the secret key), consistent
+/// snapshots of every SQLite database in the workspace, and the `include`
+/// paths. `zeroclaw restore <archive>` puts them back.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BackupConfig {
+    /// Run the backup job in the daemon
+    #[serde(default)]
+    pub enabled: bool,
+    /// Cron expression for the backup job
+    #[serde(default = "default_backup_schedule")]
+    pub schedule: String,
+    /// Directory for archives; relative paths resolve against the config directory
+    #[serde(default = "default_backup_dir")]
+    pub dir: String,
+    /// Extra workspace-relative files or directories to include
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Archives to keep; older ones are deleted after each run
+    #[serde(default = "default_backup_keep")]
+    pub keep: usize,
+    /// Also upload each archive to `bucket/prefix` through `[object_store]`
+    #[serde(default)]
+    pub upload: Option<String>,
+}
+
+fn default_backup_schedule() -> String {
+    "0 3 * * *".into()
+}
+
+fn default_backup_dir() -> String {
+    "backups".into()
+}
+
+fn default_backup_keep() -> usize {
+    7
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            schedule: default_backup_schedule(),
+            dir: default_backup_dir(),
+            include: Vec::new(),
+            keep: default_backup_keep(),
+            upload: None,
+        }
+    }
+}
+
+// ── Certificate checks ──────────────────────────────────────────
+
+/// TLS certificate expiry checks (`[cert_check]` section).
+///
+/// Controls the `cert_check` tool. Only the listed targets can be checked.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CertCheckConfig {
+    /// Enable the `cert_check` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Endpoints to check, as `host` or `host:port` (port defaults to 443)
+    #[serde(default)]
+    pub targets: Vec<String>,
+    /// Report `warning` when a certificate in the chain expires within this many days
+    #[serde(default = "default_cert_check_warn_days")]
+    pub warn_days: i64,
+    /// Report `critical` when a certificate in the chain expires within this many days
+    #[serde(default = "default_cert_check_critical_days")]
+    pub critical_days: i64,
+    /// Connect and handshake timeout per target, in seconds
+    #[serde(default = "default_cert_check_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_cert_check_warn_days() -> i64 {
+    21
+}
+
+fn default_cert_check_critical_days() -> i64 {
+    7
+}
+
+fn default_cert_check_timeout_secs() -> u64 {
+    10
+}
+
+impl Default for CertCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            targets: Vec::new(),
+            warn_days: default_cert_check_warn_days(),
+            critical_days: default_cert_check_critical_days(),
+            timeout_secs: default_cert_check_timeout_secs(),
+        }
+    }
+}
+
+// ── Domain info ─────────────────────────────────────────────────
+
+/// RDAP registration lookups (`[domain_info]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DomainInfoConfig {
+    /// Enable the `domain_info` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Registered domains checked when no domain is given (e.g. `"example.com"`)
+    #[serde(default)]
+    pub domains: Vec<String>,
+    /// Report `warning` when a registration expires within this many days
+    #[serde(default = "default_domain_info_warn_days")]
+    pub warn_days: i64,
+    /// Report `critical` when a registration expires within this many days
+    #[serde(default = "default_domain_info_critical_days")]
+    pub critical_days: i64,
+    /// Per-request timeout in seconds
+    #[serde(default = "default_domain_info_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_domain_info_warn_days() -> i64 {
+    30
+}
+
+fn default_domain_info_critical_days() -> i64 {
+    7
+}
+
+fn default_domain_info_timeout_secs() -> u64 {
+    15
+}
+
+impl Default for DomainInfoConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            domains: Vec::new(),
+            warn_days: default_domain_info_warn_days(),
+            critical_days: default_domain_info_critical_days(),
+            timeout_secs: default_domain_info_timeout_secs(),
+        }
+    }
+}
+
+// ── DNS ─────────────────────────────────────────────────────────
+
+/// DNS lookups and record watches (`[dns]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DnsConfig {
+    /// Enable the `dns` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Default resolver as `ip` or `ip:port`; the first `/etc/resolv.conf`
+    /// nameserver when unset
+    #[serde(default)]
+    pub resolver: Option<String>,
+    /// Records checked by `action = "watch"`, as `"name TYPE"` (e.g. `"example.com MX"`)
+    #[serde(default)]
+    pub watch: Vec<String>,
+    /// Per-query timeout in seconds
+    #[serde(default = "default_dns_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_dns_timeout_secs() -> u64 {
+    5
+}
+
+impl Default for DnsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            resolver: None,
+            watch: Vec::new(),
+            timeout_secs: default_dns_timeout_secs(),
+        }
+    }
+}
+
+// ── Updates ─────────────────────────────────────────────────────
+
+/// Pending package, image and release updates (`[updates]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UpdatesConfig {
+    /// Enable the `updates` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// `"auto"`, `"apt"`, `"dnf"`, `"pacman"`, or `"none"` to skip package checks
+    #[serde(default = "default_updates_package_manager")]
+    pub package_manager: String,
+    /// Additional hosts checked over SSH (`user@host`); needs key-based auth
+    #[serde(default)]
+    pub ssh_hosts: Vec<String>,
+    /// Container images compared with their registry (`nginx:1.27`, `ghcr.io/org/app`)
+    #[serde(default)]
+    pub images: Vec<String>,
+    /// Container CLI used to read local image digests
+    #[serde(default = "default_updates_container_runtime")]
+    pub container_runtime: String,
+    /// GitHub releases to follow, as `owner/repo` or `owner/repo@installed_version`
+    #[serde(default)]
+    pub github_releases: Vec<String>,
+    /// Timeout per command or request, in seconds
+    #[serde(default = "default_updates_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_updates_package_manager() -> String {
+    "auto".into()
+}
+
+fn default_updates_container_runtime() -> String {
+    "docker".into()
+}
+
+fn default_updates_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for UpdatesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            package_manager: default_updates_package_manager(),
+            ssh_hosts: Vec::new(),
+            images: Vec::new(),
+            container_runtime: default_updates_container_runtime(),
+            github_releases: Vec::new(),
+            timeout_secs: default_updates_timeout_secs(),
+        }
+    }
+}
+
+// ── Advisories ──────────────────────────────────────────────────
+
+/// Security advisory matching against installed software (`[advisories]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AdvisoriesConfig {
+    /// Enable the `advisories` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Match the OS packages installed on this host and `[updates] ssh_hosts`
+    #[serde(default = "default_true")]
+    pub os_packages: bool,
+    /// Extra software to match, as `ecosystem/name@version` (`PyPI/django@4.2.1`, `npm/lodash@4.17.20`)
+    #[serde(default)]
+    pub packages: Vec<String>,
+    /// Alert on newly seen advisories at or above this severity: `low`, `medium`, `high`, `critical`
+    #[serde(default = "default_advisories_alert_severity")]
+    pub alert_severity: String,
+    /// `[[notify.targets]]` name for alerts; unset only pushes them to `/api/ws` clients
+    #[serde(default)]
+    pub notify_target: Option<String>,
+    /// Per-request timeout in seconds
+    #[serde(default = "default_advisories_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_advisories_alert_severity() -> String {
+    "high".into()
+}
+
+fn default_advisories_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for AdvisoriesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            os_packages: true,
+            packages: Vec::new(),
+            alert_severity: default_advisories_alert_severity(),
+            notify_target: None,
+            timeout_secs: default_advisories_timeout_secs(),
+        }
+    }
+}
+
+// ── Inventory ───────────────────────────────────────────────────
+
+/// Device inventory store and `device_inventory` tool (`[inventory]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct InventoryConfig {
+    /// Enable the `device_inventory` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// Devices first seen within this many hours are marked `NEW` in listings
+    #[serde(default = "default_inventory_new_window_hours")]
+    pub new_window_hours: u32,
+}
+
+fn default_inventory_new_window_hours() -> u32 {
+    24
+}
+
+impl Default for InventoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            new_window_hours: default_inventory_new_window_hours(),
+        }
+    }
+}
+
+// ── Uptime Kuma ─────────────────────────────────────────────────
+
+/// Uptime Kuma push monitors heartbeated by the daemon (`[uptime_kuma]` section).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct UptimeKumaConfig {
+    /// Push monitors to heartbeat while the daemon runs
+    #[serde(default)]
+    pub push_jobs: Vec<UptimeKumaPushJobConfig>,
+}
+
+/// One Uptime Kuma "Push" monitor (`[[uptime_kuma.push_jobs]]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct UptimeKumaPushJobConfig {
+    /// Uptime Kuma base URL, e.g. `https://status.example.com`
+    pub url: String,
+    /// Token from the monitor's push URL (`/api/push/<token>`); stored encrypted
+    pub token: String,
+    /// Seconds between heartbeats; keep below the monitor's heartbeat interval
+    #[serde(default = "default_uptime_kuma_interval_secs")]
+    pub interval_secs: u64,
+    /// Message sent with each heartbeat
+    #[serde(default = "default_uptime_kuma_msg")]
+    pub msg: String,
+}
+
+fn default_uptime_kuma_interval_secs() -> u64 {
+    60
+}
+
+fn default_uptime_kuma_msg() -> String {
+    "OK".into()
+}
+
+// ── Metrics ─────────────────────────────────────────────────────
+
+/// Local metric history and anomaly detection (`[metrics]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MetricsConfig {
+    /// Enable the `metrics` tool
+    #[serde(default)]
+    pub enabled: bool,
+    /// EWMA smoothing factor; higher adapts faster to new levels
+    #[serde(default = "default_metrics_alpha")]
+    pub alpha: f64,
+    /// Deviation, in standard deviations, that counts as an anomaly
+    #[serde(default = "default_metrics_threshold")]
+    pub threshold: f64,
+    /// Samples a baseline needs before it can flag anomalies
+    #[serde(default = "default_metrics_min_samples")]
+    pub min_samples: u32,
+    /// Compare against the metric's usual level for the local hour of day
+    #[serde(default = "default_true")]
+    pub seasonal: bool,
+    /// Further anomalies of a metric within this window do not alert again
+    #[serde(default = "default_metrics_cooldown_minutes")]
+    pub cooldown_minutes: u32,
+    /// Samples and anomalies older than this are deleted
+    #[serde(default = "default_metrics_retention_days")]
+    pub retention_days: u32,
+    /// `[[notify.targets]]` name for anomaly alerts; unset only pushes them to `/api/ws` clients
+    #[serde(default)]
+    pub notify_target: Option<String>,
+    /// `conf.d/automations` name to run when an anomaly is detected
+    #[serde(default)]
+    pub on_anomaly: Option<String>,
+}
+
+fn default_metrics_alpha() -> f64 {
+    0.1
+}
+
+fn default_metrics_threshold() -> f64 {
+    3.0
+}
+
+fn default_metrics_min_samples() -> u32 {
+    20
+}
+
+fn default_metrics_cooldown_minutes() -> u32 {
+    60
+}
+
+fn default_metrics_retention_days() -> u32 {
+    90
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            alpha: default_metrics_alpha(),
+            threshold: default_metrics_threshold(),
+            min_samples: default_metrics_min_samples(),
+            seasonal: true,
+            cooldown_minutes: default_metrics_cooldown_minutes(),
+            retention_days: default_metrics_retention_days(),
+            notify_target: None,
+            on_anomaly: None,
+        }
+    }
+}
+
+// ── Research ────────────────────────────────────────────────────
+
+/// Multi-step web research tool (`[research]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResearchConfig {
+    /// Enable the `research` tool (also requires `[web_search]`)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Search rounds per question, counting follow-up searches
+    #[serde(default = "default_research_max_depth")]
+    pub max_depth: usize,
+    /// Search queries per round
+    #[serde(default = "default_research_max_queries")]
+    pub max_queries: usize,
+    /// Sources read and cited per report
+    #[serde(default = "default_research_max_sources")]
+    pub max_sources: usize,
+    /// Page text passed to the model per source, in characters
+    #[serde(default = "default_research_max_chars_per_source")]
+    pub max_chars_per_source: usize,
+    /// Model for notes and the report; unset uses `default_model`
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Workspace-relative directory reports are saved to
+    #[serde(default = "default_research_output_dir")]
+    pub output_dir: String,
+    /// Page fetch timeout in seconds
+    #[serde(default = "default_research_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_research_max_depth() -> usize {
+    2
+}
+
+fn default_research_max_queries() -> usize {
+    3
+}
+
+fn default_research_max_sources() -> usize {
+    8
 }
 
-fn default_browser_webdriver_url() -> String {
-    "http://127.0.0.1:9515".into()
+fn default_research_max_chars_per_source() -> usize {
+    6000
 }
 
-impl Default for BrowserConfig {
+fn default_research_output_dir() -> String {
+    "research".into()
+}
+
+fn default_research_timeout_secs() -> u64 {
+    20
+}
+
+impl Default for ResearchConfig {
     fn default() -> Self {
         Self {
             enabled: false,
-            allowed_domains: Vec::new(),
-            session_name: None,
-            backend: default_browser_backend(),
-            native_headless: default_true(),
-            native_webdriver_url: default_browser_webdriver_url(),
-            native_chrome_path: None,
-            computer_use: BrowserComputerUseConfig::default(),
+            max_depth: default_research_max_depth(),
+            max_queries: default_research_max_queries(),
+            max_sources: default_research_max_sources(),
+            max_chars_per_source: default_research_max_chars_per_source(),
+            model: None,
+            output_dir: default_research_output_dir(),
+            timeout_secs: default_research_timeout_secs(),
         }
     }
 }
 
-// ── HTTP request tool ───────────────────────────────────────────
+// ── Facts ───────────────────────────────────────────────────────
 
-/// HTTP request tool configuration (`[http_request]` section).
-///
-/// Deny-by-default: if `allowed_domains` is empty, all HTTP requests are rejected.
+/// Structured fact store with expiry and provenance (`[facts]` section).
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct HttpRequestConfig {
-    /// Enable `http_request` tool for API interactions
+pub struct FactsConfig {
+    /// Enable the `facts` tool and inject relevant facts into prompts
     #[serde(default)]
     pub enabled: bool,
-    /// Allowed domains for HTTP requests (exact or subdomain match)
-    #[serde(default)]
-    pub allowed_domains: Vec<String>,
-    /// Maximum response size in bytes (default: 1MB)
-    #[serde(default = "default_http_max_response_size")]
-    pub max_response_size: usize,
-    /// Request timeout in seconds (default: 30)
-    #[serde(default = "default_http_timeout_secs")]
-    pub timeout_secs: u64,
+    /// Lifetime of facts recorded without an explicit TTL; 0 keeps them until replaced
+    #[serde(default = "default_facts_ttl_hours")]
+    pub default_ttl_hours: u64,
+    /// Facts injected into one prompt at most
+    #[serde(default = "default_facts_max_injected")]
+    pub max_injected: usize,
+    /// Facts below this confidence are stored but never injected
+    #[serde(default = "default_facts_min_confidence")]
+    pub min_confidence: f64,
 }
 
-impl Default for HttpRequestConfig {
+fn default_facts_ttl_hours() -> u64 {
+    168
+}
+
+fn default_facts_max_injected() -> usize {
+    8
+}
+
+fn default_facts_min_confidence() -> f64 {
+    0.5
+}
+
+impl Default for FactsConfig {
     fn default() -> Self {
         Self {
             enabled: false,
-            allowed_domains: vec![],
-            max_response_size: default_http_max_response_size(),
-            timeout_secs: default_http_timeout_secs(),
+            default_ttl_hours: default_facts_ttl_hours(),
+            max_injected: default_facts_max_injected(),
+            min_confidence: default_facts_min_confidence(),
         }
     }
 }
 
-fn default_http_max_response_size() -> usize {
-    1_000_000 // 1MB
-}
+// ── Analytics ───────────────────────────────────────────────────
 
-fn default_http_timeout_secs() -> u64 {
-    30
+/// Turn and tool-call analytics behind `/api/analytics` (`[analytics]` section).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AnalyticsConfig {
+    /// Record channel and gateway turns in `workspace/analytics/analytics.db`
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Turns older than this many days are pruned
+    #[serde(default = "default_analytics_retention_days")]
+    pub retention_days: u32,
 }
 
-// ── Web search ───────────────────────────────────────────────────
+fn default_analytics_retention_days() -> u32 {
+    90
+}
 
-/// Web search tool configuration (`[web_search]` section).
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct WebSearchConfig {
-    /// Enable `web_search_tool` for web searches
-    #[serde(default)]
-    pub enabled: bool,
-    /// Search provider: "duckduckgo" (free, no API key) or "brave" (requires API key)
-    #[serde(default = "default_web_search_provider")]
-    pub provider: String,
-    /// Brave Search API key (required if provider is "brave")
-    #[serde(default)]
-    pub brave_api_key: Option<String>,
-    /// Maximum results per search (1-10)
-    #[serde(default = "default_web_search_max_results")]
-    pub max_results: usize,
-    /// Request timeout in seconds
-    #[serde(default = "default_web_search_timeout_secs")]
-    pub timeout_secs: u64,
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            retention_days: default_analytics_retention_days(),
+        }
+    }
 }
 
-fn default_web_search_provider() -> String {
-    "duckduckgo".into()
+// ── Locale ──────────────────────────────────────────────────────
+
+/// Clock style for displayed times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+pub enum ClockFormat {
+    /// `14:30` (default).
+    #[default]
+    #[serde(rename = "24h")]
+    H24,
+    /// `2:30 PM`.
+    #[serde(rename = "12h")]
+    H12,
 }
 
-fn default_web_search_max_results() -> usize {
-    5
+/// Temperature unit for displayed readings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
 }
 
-fn default_web_search_timeout_secs() -> u64 {
-    15
+/// Measurement system for distances, speeds and similar quantities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MeasurementSystem {
+    #[default]
+    Metric,
+    Imperial,
 }
 
-impl Default for WebSearchConfig {
-    fn default() -> Self {
-        Self {
-            enabled: false,
-            provider: default_web_search_provider(),
-            brave_api_key: None,
-            max_results: default_web_search_max_results(),
-            timeout_secs: default_web_search_timeout_secs(),
-        }
-    }
+/// How the agent and its tools present times, units and money (`[locale]` section).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct LocaleConfig {
+    /// IANA timezone for displayed times (e.g. `"Europe/Berlin"`). Unset uses the host timezone.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// `"24h"` or `"12h"`
+    #[serde(default)]
+    pub clock: ClockFormat,
+    /// `"celsius"` or `"fahrenheit"`
+    #[serde(default)]
+    pub temperature: TemperatureUnit,
+    /// `"metric"` or `"imperial"`
+    #[serde(default)]
+    pub units: MeasurementSystem,
+    /// ISO 4217 currency code for prices and amounts (e.g. `"EUR"`)
+    #[serde(default)]
+    pub currency: Option<String>,
 }
 
 // ── Proxy ───────────────────────────────────────────────────────
@@ -1814,6 +3697,9 @@ pub struct HooksConfig {
     pub enabled: bool,
     #[serde(default)]
     pub builtin: BuiltinHooksConfig,
+    /// Rhai script hooks (`[[hooks.scripts]]`, needs the `scripting` feature).
+    #[serde(default)]
+    pub scripts: Vec<ScriptHookConfig>,
 }
 
 impl Default for HooksConfig {
@@ -1821,10 +3707,21 @@ impl Default for HooksConfig {
         Self {
             enabled: true,
             builtin: BuiltinHooksConfig::default(),
+            scripts: Vec::new(),
         }
     }
 }
 
+/// A Rhai script registered as a lifecycle hook.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScriptHookConfig {
+    /// Script file, relative to the workspace unless absolute
+    pub path: String,
+    /// Hook priority; higher runs first among modifying hooks
+    #[serde(default)]
+    pub priority: i32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BuiltinHooksConfig {
     /// Enable the command-logger hook (logs tool calls for auditing).
@@ -2963,6 +4860,17 @@ pub struct IrcConfig {
     pub sasl_password: Option<String>,
     /// Verify TLS certificate (default: true)
     pub verify_tls: Option<bool>,
+    /// When true, only respond in channels when addressed (`nick: …`, `nick, …`)
+    /// or mentioned by nickname. Private messages are always processed.
+    #[serde(default)]
+    pub mention_only: bool,
+    /// Lines that may be sent back-to-back before pacing kicks in (default: 4)
+    #[serde(default = "default_irc_flood_burst")]
+    pub flood_burst: u32,
+    /// Minimum interval (ms) between lines once the burst is spent (default: 2000).
+    /// Keeps multi-line replies under typical server flood limits; 0 disables pacing.
+    #[serde(default = "default_irc_flood_interval_ms")]
+    pub flood_interval_ms: u64,
 }
 
 impl ChannelConfig for IrcConfig {
@@ -2978,6 +4886,14 @@ fn default_irc_port() -> u16 {
     6697
 }
 
+fn default_irc_flood_burst() -> u32 {
+    4
+}
+
+fn default_irc_flood_interval_ms() -> u64 {
+    2000
+}
+
 /// How ZeroClaw receives events from Feishu / Lark.
 ///
 /// - `websocket` (default) — persistent WSS long-connection; no public URL required.
@@ -3415,10 +5331,41 @@ impl Default for Config {
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
+            composite_tools: HashMap::new(),
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             query_classification: QueryClassificationConfig::default(),
             transcription: TranscriptionConfig::default(),
+            phone_alert: PhoneAlertConfig::default(),
+            sync: SyncConfig::default(),
+            safe_mode: SafeModeConfig::default(),
+            backup: BackupConfig::default(),
+            cert_check: CertCheckConfig::default(),
+            dns: DnsConfig::default(),
+            domain_info: DomainInfoConfig::default(),
+            updates: UpdatesConfig::default(),
+            advisories: AdvisoriesConfig::default(),
+            inventory: InventoryConfig::default(),
+            uptime_kuma: UptimeKumaConfig::default(),
+            metrics: MetricsConfig::default(),
+            research: ResearchConfig::default(),
+            facts: FactsConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            locale: LocaleConfig::default(),
+            notify: NotifyConfig::default(),
+            downloads: DownloadsConfig::default(),
+            media_server: MediaServerConfig::default(),
+            printer: PrinterConfig::default(),
+            energy: EnergyConfig::default(),
+            location: LocationConfig::default(),
+            people: PeopleConfig::default(),
+            notes: NotesConfig::default(),
+            tasks: TasksConfig::default(),
+            webdav: WebDavConfig::default(),
+            object_store: ObjectStoreConfig::default(),
+            publish: PublishConfig::default(),
+            python_repl: PythonReplConfig::default(),
+            code_exec: CodeExecConfig::default(),
         }
     }
 }
@@ -3818,6 +5765,131 @@ impl Config {
                 "config.storage.provider.config.db_url",
             )?;
 
+            decrypt_optional_secret(
+                &store,
+                &mut config.object_store.secret_access_key,
+                "config.object_store.secret_access_key",
+            )?;
+
+            for target in config.webdav.targets.values_mut() {
+                decrypt_optional_secret(
+                    &store,
+                    &mut target.password,
+                    "config.webdav.targets.*.password",
+                )?;
+            }
+
+            if let Some(ref mut caldav) = config.tasks.caldav {
+                decrypt_optional_secret(
+                    &store,
+                    &mut caldav.password,
+                    "config.tasks.caldav.password",
+                )?;
+            }
+
+            if let Some(ref mut vikunja) = config.tasks.vikunja {
+                decrypt_optional_secret(
+                    &store,
+                    &mut vikunja.api_token,
+                    "config.tasks.vikunja.api_token",
+                )?;
+            }
+
+            if let Some(ref mut carddav) = config.people.carddav {
+                decrypt_optional_secret(
+                    &store,
+                    &mut carddav.password,
+                    "config.people.carddav.password",
+                )?;
+            }
+
+            decrypt_optional_secret(
+                &store,
+                &mut config.location.secret,
+                "config.location.secret",
+            )?;
+
+            if let Some(ref mut inverter) = config.energy.inverter {
+                decrypt_optional_secret(
+                    &store,
+                    &mut inverter.api_key,
+                    "config.energy.inverter.api_key",
+                )?;
+            }
+
+            if let Some(ref mut meter) = config.energy.meter {
+                decrypt_optional_secret(
+                    &store,
+                    &mut meter.password,
+                    "config.energy.meter.password",
+                )?;
+            }
+
+            for job in &mut config.uptime_kuma.push_jobs {
+                decrypt_secret(
+                    &store,
+                    &mut job.token,
+                    "config.uptime_kuma.push_jobs.*.token",
+                )?;
+            }
+
+            decrypt_optional_secret(
+                &store,
+                &mut config.printer.api_key,
+                "config.printer.api_key",
+            )?;
+
+            decrypt_optional_secret(
+                &store,
+                &mut config.media_server.api_key,
+                "config.media_server.api_key",
+            )?;
+
+            for client in &mut config.downloads.clients {
+                decrypt_optional_secret(
+                    &store,
+                    &mut client.password,
+                    "config.downloads.clients.*.password",
+                )?;
+                decrypt_optional_secret(
+                    &store,
+                    &mut client.api_key,
+                    "config.downloads.clients.*.api_key",
+                )?;
+            }
+
+            for target in &mut config.notify.targets {
+                decrypt_optional_secret(
+                    &store,
+                    &mut target.token,
+                    "config.notify.targets.*.token",
+                )?;
+                decrypt_optional_secret(
+                    &store,
+                    &mut target.user_key,
+                    "config.notify.targets.*.user_key",
+                )?;
+                decrypt_optional_secret(&store, &mut target.uri, "config.notify.targets.*.uri")?;
+            }
+
+            if let Some(ref mut twilio) = config.phone_alert.twilio {
+                decrypt_secret(
+                    &store,
+                    &mut twilio.auth_token,
+                    "config.phone_alert.twilio.auth_token",
+                )?;
+            }
+
+            if let Some(ref mut vonage) = config.phone_alert.vonage {
+                decrypt_secret(
+                    &store,
+                    &mut vonage.api_secret,
+                    "config.phone_alert.vonage.api_secret",
+                )?;
+            }
+
+            decrypt_optional_secret(&store, &mut config.sync.token, "config.sync.token")?;
+
             for agent in config.agents.values_mut() {
                 decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
             }
@@ -3982,6 +6054,18 @@ impl Config {
             }
         }
 
+        // Locale
+        if let Some(timezone) = self.locale.timezone.as_deref() {
+            if timezone.parse::<chrono_tz::Tz>().is_err() {
+                anyhow::bail!("locale.timezone '{timezone}' is not a valid IANA timezone");
+            }
+        }
+        if let Some(currency) = self.locale.currency.as_deref() {
+            if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_uppercase()) {
+                anyhow::bail!("locale.currency must be a 3-letter ISO 4217 code such as \"EUR\"");
+            }
+        }
+
         // Proxy (delegate to existing validation)
         self.proxy.validate()?;
 
@@ -4303,6 +6387,119 @@ impl Config {
             "config.storage.provider.config.db_url",
         )?;
 
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.object_store.secret_access_key,
+            "config.object_store.secret_access_key",
+        )?;
+
+        for target in config_to_save.webdav.targets.values_mut() {
+            encrypt_optional_secret(
+                &store,
+                &mut target.password,
+                "config.webdav.targets.*.password",
+            )?;
+        }
+
+        if let Some(ref mut caldav) = config_to_save.tasks.caldav {
+            encrypt_optional_secret(&store, &mut caldav.password, "config.tasks.caldav.password")?;
+        }
+
+        if let Some(ref mut vikunja) = config_to_save.tasks.vikunja {
+            encrypt_optional_secret(
+                &store,
+                &mut vikunja.api_token,
+                "config.tasks.vikunja.api_token",
+            )?;
+        }
+
+        if let Some(ref mut carddav) = config_to_save.people.carddav {
+            encrypt_optional_secret(
+                &store,
+                &mut carddav.password,
+                "config.people.carddav.password",
+            )?;
+        }
+
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.location.secret,
+            "config.location.secret",
+        )?;
+
+        if let Some(ref mut inverter) = config_to_save.energy.inverter {
+            encrypt_optional_secret(
+                &store,
+                &mut inverter.api_key,
+                "config.energy.inverter.api_key",
+            )?;
+        }
+
+        if let Some(ref mut meter) = config_to_save.energy.meter {
+            encrypt_optional_secret(&store, &mut meter.password, "config.energy.meter.password")?;
+        }
+
+        for job in &mut config_to_save.uptime_kuma.push_jobs {
+            encrypt_secret(
+                &store,
+                &mut job.token,
+                "config.uptime_kuma.push_jobs.*.token",
+            )?;
+        }
+
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.printer.api_key,
+            "config.printer.api_key",
+        )?;
+
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.media_server.api_key,
+            "config.media_server.api_key",
+        )?;
+
+        for client in &mut config_to_save.downloads.clients {
+            encrypt_optional_secret(
+                &store,
+                &mut client.password,
+                "config.downloads.clients.*.password",
+            )?;
+            encrypt_optional_secret(
+                &store,
+                &mut client.api_key,
+                "config.downloads.clients.*.api_key",
+            )?;
+        }
+
+        for target in &mut config_to_save.notify.targets {
+            encrypt_optional_secret(&store, &mut target.token, "config.notify.targets.*.token")?;
+            encrypt_optional_secret(
+                &store,
+                &mut target.user_key,
+                "config.notify.targets.*.user_key",
+            )?;
+            encrypt_optional_secret(&store, &mut target.uri, "config.notify.targets.*.uri")?;
+        }
+
+        if let Some(ref mut twilio) = config_to_save.phone_alert.twilio {
+            encrypt_secret(
+                &store,
+                &mut twilio.auth_token,
+                "config.phone_alert.twilio.auth_token",
+            )?;
+        }
+
+        if let Some(ref mut vonage) = config_to_save.phone_alert.vonage {
+            encrypt_secret(
+                &store,
+                &mut vonage.api_secret,
+                "config.phone_alert.vonage.api_secret",
+            )?;
+        }
+
+        encrypt_optional_secret(&store, &mut config_to_save.sync.token, "config.sync.token")?;
+
         for agent in config_to_save.agents.values_mut() {
             encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
         }
@@ -4686,9 +6883,40 @@ default_temperature = 0.7
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
+            composite_tools: HashMap::new(),
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
+            phone_alert: PhoneAlertConfig::default(),
+            sync: SyncConfig::default(),
+            safe_mode: SafeModeConfig::default(),
+            backup: BackupConfig::default(),
+            cert_check: CertCheckConfig::default(),
+            dns: DnsConfig::default(),
+            domain_info: DomainInfoConfig::default(),
+            updates: UpdatesConfig::default(),
+            advisories: AdvisoriesConfig::default(),
+            inventory: InventoryConfig::default(),
+            uptime_kuma: UptimeKumaConfig::default(),
+            metrics: MetricsConfig::default(),
+            research: ResearchConfig::default(),
+            facts: FactsConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            locale: LocaleConfig::default(),
+            notify: NotifyConfig::default(),
+            downloads: DownloadsConfig::default(),
+            media_server: MediaServerConfig::default(),
+            printer: PrinterConfig::default(),
+            energy: EnergyConfig::default(),
+            location: LocationConfig::default(),
+            people: PeopleConfig::default(),
+            notes: NotesConfig::default(),
+            tasks: TasksConfig::default(),
+            webdav: WebDavConfig::default(),
+            object_store: ObjectStoreConfig::default(),
+            publish: PublishConfig::default(),
+            python_repl: PythonReplConfig::default(),
+            code_exec: CodeExecConfig::default(),
         };
 
         let toml_str = toml::to_string_pretty(&config).unwrap();
@@ -4860,9 +7088,40 @@ tool_dispatcher = "xml"
             cost: CostConfig::default(),
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
+            composite_tools: HashMap::new(),
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
+            phone_alert: PhoneAlertConfig::default(),
+            sync: SyncConfig::default(),
+            safe_mode: SafeModeConfig::default(),
+            backup: BackupConfig::default(),
+            cert_check: CertCheckConfig::default(),
+            dns: DnsConfig::default(),
+            domain_info: DomainInfoConfig::default(),
+            updates: UpdatesConfig::default(),
+            advisories: AdvisoriesConfig::default(),
+            inventory: InventoryConfig::default(),
+            uptime_kuma: UptimeKumaConfig::default(),
+            metrics: MetricsConfig::default(),
+            research: ResearchConfig::default(),
+            facts: FactsConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            locale: LocaleConfig::default(),
+            notify: NotifyConfig::default(),
+            downloads: DownloadsConfig::default(),
+            media_server: MediaServerConfig::default(),
+            printer: PrinterConfig::default(),
+            energy: EnergyConfig::default(),
+            location: LocationConfig::default(),
+            people: PeopleConfig::default(),
+            notes: NotesConfig::default(),
+            tasks: TasksConfig::default(),
+            webdav: WebDavConfig::default(),
+            object_store: ObjectStoreConfig::default(),
+            publish: PublishConfig::default(),
+            python_repl: PythonReplConfig::default(),
+            code_exec: CodeExecConfig::default(),
         };
 
         config.save().await.unwrap();
@@ -5518,6 +7777,16 @@ channel_id = "C123"
             rate_limit_max_keys: 2048,
             idempotency_ttl_secs: 600,
             idempotency_max_keys: 4096,
+            public_status_page: true,
+            access_log: GatewayAccessLogConfig {
+                enabled: false,
+                bodies: AccessLogBodies::Redacted,
+                max_entries: 500,
+            },
+            feedback: GatewayFeedbackConfig {
+                enabled: true,
+                retention_days: 7,
+            },
         };
         let toml_str = toml::to_string(&g).unwrap();
         let parsed: GatewayConfig = toml::from_str(&toml_str).unwrap();
@@ -5530,6 +7799,12 @@ channel_id = "C123"
         assert_eq!(parsed.rate_limit_max_keys, 2048);
         assert_eq!(parsed.idempotency_ttl_secs, 600);
         assert_eq!(parsed.idempotency_max_keys, 4096);
+        assert!(parsed.public_status_page);
+        assert!(!parsed.access_log.enabled);
+        assert_eq!(parsed.access_log.bodies, AccessLogBodies::Redacted);
+        assert_eq!(parsed.access_log.max_entries, 500);
+        assert!(parsed.feedback.enabled);
+        assert_eq!(parsed.feedback.retention_days, 7);
     }
 
     #[test]
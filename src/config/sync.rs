@@ -0,0 +1,359 @@
+//! Pull-based replication of `conf.d` sections from a primary instance.
+//!
+//! The primary serves its `prompts/`, `tools/` and `automations/` files at
+//! `GET /api/sync/bundle`; peers fetch the bundle every
+//! `[sync] interval_secs` and write it into their own `conf.d`, where the
+//! usual hot reload picks it up. A manifest of what was last synced protects
+//! local edits: a file whose content no longer matches the manifest (or that
+//! was never synced) is left alone and logged instead of overwritten.
+
+use super::confd::CONF_D_DIR;
+use super::Config;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::time::Duration;
+
+const COMPONENT: &str = "config_sync";
+const MANIFEST_FILE: &str = ".sync-manifest.json";
+const MIN_INTERVAL_SECS: u64 = 30;
+const MAX_FILE_BYTES: usize = 256 * 1024;
+
+/// Sections that may be replicated, with the file extension each holds.
+const SECTIONS: [(&str, &str); 3] = [
+    ("prompts", "md"),
+    ("tools", "toml"),
+    ("automations", "toml"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncFile {
+    /// Path relative to `conf.d`, e.g. `automations/backup.toml`.
+    pub path: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncBundle {
+    pub files: Vec<SyncFile>,
+}
+
+/// What [`apply_bundle`] changed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncOutcome {
+    pub written: Vec<String>,
+    pub removed: Vec<String>,
+    /// Files left untouched because they were edited or created locally.
+    pub kept_local: Vec<String>,
+}
+
+fn sha256_hex(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+pub fn conf_d_dir(config: &Config) -> PathBuf {
+    config
+        .config_path
+        .parent()
+        .map_or_else(|| PathBuf::from(CONF_D_DIR), |dir| dir.join(CONF_D_DIR))
+}
+
+/// Whether `path` is `<section>/<name>.<ext>` for one of `sections`.
+fn is_syncable(path: &str, sections: &[String]) -> bool {
+    let Some((section, file)) = path.split_once('/') else {
+        return false;
+    };
+    let Some((_, extension)) = SECTIONS.iter().find(|(name, _)| *name == section) else {
+        return false;
+    };
+    sections.iter().any(|s| s == section)
+        && !file.contains(['/', '\\'])
+        && !file.starts_with('.')
+        && file
+            .strip_suffix(extension)
+            .and_then(|stem| stem.strip_suffix('.'))
+            .is_some_and(|stem| !stem.is_empty())
+}
+
+/// Primary side: the files of `sections` under `conf_d`.
+pub fn build_bundle(conf_d: &Path, sections: &[String]) -> SyncBundle {
+    let mut files = Vec::new();
+    for (section, _) in SECTIONS {
+        if !sections.iter().any(|s| s == section) {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(conf_d.join(section)) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            let path = format!("{section}/{name}");
+            if !is_syncable(&path, sections) || !entry.path().is_file() {
+                continue;
+            }
+            match std::fs::read_to_string(entry.path()) {
+                Ok(content) if content.len() <= MAX_FILE_BYTES => {
+                    files.push(SyncFile { path, content });
+                }
+                Ok(_) => tracing::warn!("Not syncing {path}: larger than {MAX_FILE_BYTES} bytes"),
+                Err(e) => tracing::warn!("Not syncing {path}: {e}"),
+            }
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    SyncBundle { files }
+}
+
+fn load_manifest(conf_d: &Path) -> BTreeMap<String, String> {
+    std::fs::read_to_string(conf_d.join(MANIFEST_FILE))
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+/// Peer side: write `bundle` into `conf_d`, keeping local edits.
+///
+/// Files removed on the primary are deleted here only if they still hold the
+/// synced content; sections not listed in `sections` are never touched.
+pub fn apply_bundle(
+    conf_d: &Path,
+    bundle: &SyncBundle,
+    sections: &[String],
+) -> Result<SyncOutcome> {
+    let previous = load_manifest(conf_d);
+    let mut manifest = BTreeMap::new();
+    let mut outcome = SyncOutcome::default();
+
+    for file in &bundle.files {
+        if !is_syncable(&file.path, sections) || file.content.len() > MAX_FILE_BYTES {
+            tracing::warn!("Ignoring synced file {}", file.path);
+            continue;
+        }
+        let target = conf_d.join(&file.path);
+        let remote_hash = sha256_hex(&file.content);
+        match std::fs::read_to_string(&target)
+            .ok()
+            .map(|local| sha256_hex(&local))
+        {
+            Some(local_hash) if local_hash == remote_hash => {}
+            Some(local_hash) if previous.get(&file.path) != Some(&local_hash) => {
+                outcome.kept_local.push(file.path.clone());
+                continue;
+            }
+            _ => {
+                if let Some(dir) = target.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(&target, &file.content)
+                    .with_context(|| format!("Failed to write {}", target.display()))?;
+                outcome.written.push(file.path.clone());
+            }
+        }
+        manifest.insert(file.path.clone(), remote_hash);
+    }
+
+    for (path, hash) in &previous {
+        if manifest.contains_key(path) {
+            continue;
+        }
+        if !is_syncable(path, sections) {
+            // Section no longer synced: keep tracking it so re-enabling it
+            // does not mistake the old copy for a local edit.
+            manifest.insert(path.clone(), hash.clone());
+            continue;
+        }
+        let target = conf_d.join(path);
+        let unchanged =
+            std::fs::read_to_string(&target).is_ok_and(|local| sha256_hex(&local) == *hash);
+        if unchanged {
+            std::fs::remove_file(&target)?;
+            outcome.removed.push(path.clone());
+        }
+    }
+
+    // Rewriting an unchanged manifest would bump the directory fingerprint
+    // and trigger a needless conf.d reload.
+    if manifest != previous {
+        std::fs::create_dir_all(conf_d)?;
+        std::fs::write(
+            conf_d.join(MANIFEST_FILE),
+            serde_json::to_string_pretty(&manifest)?,
+        )?;
+    }
+    Ok(outcome)
+}
+
+/// Refuse to send the sync token in the clear: `primary_url` must be https
+/// unless it points at this machine.
+fn ensure_https(url: &str) -> Result<()> {
+    let parsed = reqwest::Url::parse(url).context("[sync] primary_url is not a valid URL")?;
+    if parsed.scheme() == "https" {
+        return Ok(());
+    }
+    let host = parsed
+        .host_str()
+        .unwrap_or_default()
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let loopback = host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback());
+    if parsed.scheme() != "http" || !loopback {
+        bail!("[sync] primary_url must use https (plain http is only allowed for loopback hosts)");
+    }
+    Ok(())
+}
+
+/// Fetch the primary's bundle once and apply it.
+pub async fn pull_once(config: &Config) -> Result<SyncOutcome> {
+    let Some(primary) = config.sync.primary_url.as_deref() else {
+        bail!("[sync] primary_url is not set");
+    };
+    let primary = primary.trim();
+    ensure_https(primary)?;
+    let url = format!("{}/api/sync/bundle", primary.trim_end_matches('/'));
+    let mut request =
+        crate::config::build_runtime_proxy_client_with_timeouts("config.sync", 30, 10)
+            .get(url)
+            .query(&[("sections", config.sync.sections.join(","))]);
+    if let Some(token) = config.sync.token.as_deref() {
+        request = request.bearer_auth(token);
+    }
+    let bundle: SyncBundle = request
+        .send()
+        .await?
+        .error_for_status()
+        .context("Primary rejected the sync request")?
+        .json()
+        .await?;
+    apply_bundle(&conf_d_dir(config), &bundle, &config.sync.sections)
+}
+
+/// Daemon loop pulling from `[sync] primary_url`.
+pub async fn run(config: Config) -> Result<()> {
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        config.sync.interval_secs.max(MIN_INTERVAL_SECS),
+    ));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        interval.tick().await;
+        match pull_once(&config).await {
+            Ok(outcome) => {
+                crate::health::mark_component_ok(COMPONENT);
+                if !outcome.written.is_empty() || !outcome.removed.is_empty() {
+                    tracing::info!(
+                        written = ?outcome.written,
+                        removed = ?outcome.removed,
+                        "Synced conf.d from primary"
+                    );
+                }
+                for path in outcome.kept_local {
+                    tracing::debug!("Keeping local override of conf.d/{path}");
+                }
+            }
+            Err(e) => {
+                crate::health::mark_component_error(COMPONENT, e.to_string());
+                tracing::warn!("conf.d sync failed: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sections() -> Vec<String> {
+        vec!["prompts".into(), "automations".into()]
+    }
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let target = dir.join(path);
+        std::fs::create_dir_all(target.parent().unwrap()).unwrap();
+        std::fs::write(target, content).unwrap();
+    }
+
+    #[test]
+    fn primary_url_requires_https_except_loopback() {
+        assert!(ensure_https("https://primary.example.com").is_ok());
+        assert!(ensure_https("http://localhost:42617").is_ok());
+        assert!(ensure_https("http://127.0.0.1:42617").is_ok());
+        assert!(ensure_https("http://[::1]:42617").is_ok());
+        assert!(ensure_https("http://primary.example.com").is_err());
+        assert!(ensure_https("http://10.0.0.5:42617").is_err());
+        assert!(ensure_https("ftp://localhost").is_err());
+        assert!(ensure_https("not a url").is_err());
+    }
+
+    #[test]
+    fn bundle_only_contains_selected_sections() {
+        let tmp = tempfile::tempdir().unwrap();
+        write(tmp.path(), "prompts/style.md", "Be brief.");
+        write(tmp.path(), "prompts/.hidden.md", "x");
+        write(tmp.path(), "tools/disk.toml", "description = \"d\"");
+        write(tmp.path(), "automations/backup.txt", "x");
+        write(tmp.path(), "persona.md", "Local persona");
+
+        let bundle = build_bundle(tmp.path(), &sections());
+        let paths: Vec<&str> = bundle.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["prompts/style.md"]);
+    }
+
+    #[test]
+    fn apply_protects_local_edits_and_removes_stale_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let bundle = |files: &[(&str, &str)]| SyncBundle {
+            files: files
+                .iter()
+                .map(|(path, content)| SyncFile {
+                    path: (*path).into(),
+                    content: (*content).into(),
+                })
+                .collect(),
+        };
+        write(dir, "prompts/mine.md", "Local only.");
+
+        let first = apply_bundle(
+            dir,
+            &bundle(&[
+                ("prompts/style.md", "v1"),
+                ("prompts/mine.md", "remote"),
+                ("automations/backup.toml", "a"),
+                ("../escape.md", "x"),
+            ]),
+            &sections(),
+        )
+        .unwrap();
+        assert_eq!(
+            first.written,
+            ["prompts/style.md", "automations/backup.toml"]
+        );
+        assert_eq!(first.kept_local, ["prompts/mine.md"]);
+        assert!(!dir.parent().unwrap().join("escape.md").exists());
+
+        write(dir, "automations/backup.toml", "edited here");
+        let second = apply_bundle(
+            dir,
+            &bundle(&[("prompts/style.md", "v2"), ("automations/backup.toml", "b")]),
+            &sections(),
+        )
+        .unwrap();
+        assert_eq!(second.written, ["prompts/style.md"]);
+        assert_eq!(second.kept_local, ["automations/backup.toml"]);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("prompts/mine.md")).unwrap(),
+            "Local only."
+        );
+
+        let third = apply_bundle(dir, &bundle(&[]), &sections()).unwrap();
+        assert_eq!(third.removed, ["prompts/style.md"]);
+        assert!(dir.join("automations/backup.toml").exists());
+    }
+}
@@ -1,8 +1,11 @@
+pub mod observer;
 pub mod tracker;
 pub mod types;
 
 // Re-exported for potential external use (public API)
 #[allow(unused_imports)]
-pub use tracker::CostTracker;
+pub use observer::CostObserver;
+#[allow(unused_imports)]
+pub use tracker::{daily_cap_reached, CostTracker};
 #[allow(unused_imports)]
 pub use types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
@@ -0,0 +1,112 @@
+use super::tracker::CostTracker;
+use super::types::TokenUsage;
+use crate::config::schema::ModelPricing;
+use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Observer that feeds provider-reported token usage into a [`CostTracker`].
+///
+/// Composed with the configured observability backend via `MultiObserver`,
+/// so the agent loop records spend without knowing about the cost subsystem.
+pub struct CostObserver {
+    tracker: Arc<CostTracker>,
+    prices: HashMap<String, ModelPricing>,
+}
+
+impl CostObserver {
+    pub fn new(tracker: Arc<CostTracker>, prices: HashMap<String, ModelPricing>) -> Self {
+        Self { tracker, prices }
+    }
+
+    /// Resolve pricing by exact model id first, then `provider/model`.
+    fn pricing_for(&self, provider: &str, model: &str) -> (f64, f64) {
+        self.prices
+            .get(model)
+            .or_else(|| self.prices.get(&format!("{provider}/{model}")))
+            .map_or((0.0, 0.0), |p| (p.input, p.output))
+    }
+}
+
+impl Observer for CostObserver {
+    fn record_event(&self, event: &ObserverEvent) {
+        let ObserverEvent::LlmResponse {
+            provider,
+            model,
+            success: true,
+            input_tokens,
+            output_tokens,
+            ..
+        } = event
+        else {
+            return;
+        };
+
+        let input = input_tokens.unwrap_or(0);
+        let output = output_tokens.unwrap_or(0);
+        if input == 0 && output == 0 {
+            return;
+        }
+
+        let (input_price, output_price) = self.pricing_for(provider, model);
+        let usage = TokenUsage::new(model.clone(), input, output, input_price, output_price);
+        if let Err(error) = self.tracker.record_usage(usage) {
+            tracing::warn!("Failed to record token usage: {error}");
+        }
+    }
+
+    fn record_metric(&self, _metric: &ObserverMetric) {}
+
+    fn name(&self) -> &str {
+        "cost"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::CostConfig;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn llm_response(model: &str, input: Option<u64>, output: Option<u64>) -> ObserverEvent {
+        ObserverEvent::LlmResponse {
+            provider: "openrouter".into(),
+            model: model.into(),
+            duration: Duration::from_millis(10),
+            success: true,
+            error_message: None,
+            input_tokens: input,
+            output_tokens: output,
+        }
+    }
+
+    #[test]
+    fn records_usage_with_configured_pricing() {
+        let tmp = TempDir::new().unwrap();
+        let config = CostConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let prices = config.prices.clone();
+        let tracker = Arc::new(CostTracker::new(config, tmp.path()).unwrap());
+        let observer = CostObserver::new(Arc::clone(&tracker), prices);
+
+        observer.record_event(&llm_response(
+            "anthropic/claude-sonnet-4-20250514",
+            Some(1000),
+            Some(500),
+        ));
+        observer.record_event(&llm_response("unknown/model", None, None));
+
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.request_count, 1);
+        assert_eq!(summary.total_tokens, 1500);
+        assert!(summary.session_cost_usd > 0.0);
+    }
+}
@@ -162,6 +162,25 @@ impl CostTracker {
         })
     }
 
+    /// Check whether today's usage has reached the hard daily cap.
+    ///
+    /// The cap trips when either `daily_limit_usd` or a non-zero
+    /// `daily_token_limit` is reached. Always `false` when tracking is disabled.
+    pub fn daily_cap_exceeded(&self) -> Result<bool> {
+        if !self.config.enabled {
+            return Ok(false);
+        }
+
+        let mut storage = self.lock_storage();
+        let (daily_cost, _) = storage.get_aggregated_costs()?;
+        if daily_cost >= self.config.daily_limit_usd {
+            return Ok(true);
+        }
+
+        let daily_tokens = storage.get_daily_tokens()?;
+        Ok(self.config.daily_token_limit > 0 && daily_tokens >= self.config.daily_token_limit)
+    }
+
     /// Get the daily cost for a specific date.
     pub fn get_daily_cost(&self, date: NaiveDate) -> Result<f64> {
         let storage = self.lock_storage();
@@ -204,6 +223,24 @@ fn resolve_storage_path(workspace_dir: &Path) -> Result<PathBuf> {
     Ok(storage_path)
 }
 
+/// Convenience check used by the agent loop and scheduler before starting work.
+///
+/// Storage errors are logged and treated as "not capped" so a broken cost
+/// ledger never blocks the runtime.
+pub fn daily_cap_reached(config: &CostConfig, workspace_dir: &Path) -> bool {
+    if !config.enabled {
+        return false;
+    }
+
+    match CostTracker::new(config.clone(), workspace_dir).and_then(|t| t.daily_cap_exceeded()) {
+        Ok(exceeded) => exceeded,
+        Err(error) => {
+            tracing::warn!("Daily spend cap check failed: {error}");
+            false
+        }
+    }
+}
+
 fn build_session_model_stats(session_costs: &[CostRecord]) -> HashMap<String, ModelStats> {
     let mut by_model: HashMap<String, ModelStats> = HashMap::new();
 
@@ -230,6 +267,7 @@ struct CostStorage {
     path: PathBuf,
     daily_cost_usd: f64,
     monthly_cost_usd: f64,
+    daily_tokens: u64,
     cached_day: NaiveDate,
     cached_year: i32,
     cached_month: u32,
@@ -248,6 +286,7 @@ impl CostStorage {
             path: path.to_path_buf(),
             daily_cost_usd: 0.0,
             monthly_cost_usd: 0.0,
+            daily_tokens: 0,
             cached_day: now.date_naive(),
             cached_year: now.year(),
             cached_month: now.month(),
@@ -306,12 +345,14 @@ impl CostStorage {
     fn rebuild_aggregates(&mut self, day: NaiveDate, year: i32, month: u32) -> Result<()> {
         let mut daily_cost = 0.0;
         let mut monthly_cost = 0.0;
+        let mut daily_tokens = 0u64;
 
         self.for_each_record(|record| {
             let timestamp = record.usage.timestamp.naive_utc();
 
             if timestamp.date() == day {
                 daily_cost += record.usage.cost_usd;
+                daily_tokens = daily_tokens.saturating_add(record.usage.total_tokens);
             }
 
             if timestamp.year() == year && timestamp.month() == month {
@@ -321,6 +362,7 @@ impl CostStorage {
 
         self.daily_cost_usd = daily_cost;
         self.monthly_cost_usd = monthly_cost;
+        self.daily_tokens = daily_tokens;
         self.cached_day = day;
         self.cached_year = year;
         self.cached_month = month;
@@ -359,6 +401,7 @@ impl CostStorage {
         let timestamp = record.usage.timestamp.naive_utc();
         if timestamp.date() == self.cached_day {
             self.daily_cost_usd += record.usage.cost_usd;
+            self.daily_tokens = self.daily_tokens.saturating_add(record.usage.total_tokens);
         }
         if timestamp.year() == self.cached_year && timestamp.month() == self.cached_month {
             self.monthly_cost_usd += record.usage.cost_usd;
@@ -373,6 +416,12 @@ impl CostStorage {
         Ok((self.daily_cost_usd, self.monthly_cost_usd))
     }
 
+    /// Get total tokens recorded for the current day.
+    fn get_daily_tokens(&mut self) -> Result<u64> {
+        self.ensure_period_cache_current()?;
+        Ok(self.daily_tokens)
+    }
+
     /// Get cost for a specific date.
     fn get_cost_for_date(&self, date: NaiveDate) -> Result<f64> {
         let mut cost = 0.0;
@@ -523,6 +572,47 @@ mod tests {
         assert!((today_cost - valid_usage.cost_usd).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn daily_cap_trips_on_token_limit() {
+        let tmp = TempDir::new().unwrap();
+        let config = CostConfig {
+            enabled: true,
+            daily_token_limit: 1_000,
+            ..Default::default()
+        };
+
+        let tracker = CostTracker::new(config.clone(), tmp.path()).unwrap();
+        assert!(!tracker.daily_cap_exceeded().unwrap());
+
+        tracker
+            .record_usage(TokenUsage::new("test/model", 800, 400, 0.0, 0.0))
+            .unwrap();
+        assert!(tracker.daily_cap_exceeded().unwrap());
+        assert!(daily_cap_reached(&config, tmp.path()));
+    }
+
+    #[test]
+    fn daily_cap_trips_on_cost_limit_and_ignores_disabled_tracking() {
+        let tmp = TempDir::new().unwrap();
+        let config = CostConfig {
+            enabled: true,
+            daily_limit_usd: 0.01,
+            ..Default::default()
+        };
+
+        let tracker = CostTracker::new(config.clone(), tmp.path()).unwrap();
+        tracker
+            .record_usage(TokenUsage::new("test/model", 10000, 5000, 1.0, 2.0))
+            .unwrap();
+        assert!(tracker.daily_cap_exceeded().unwrap());
+
+        let disabled = CostConfig {
+            enabled: false,
+            ..config
+        };
+        assert!(!daily_cap_reached(&disabled, tmp.path()));
+    }
+
     #[test]
     fn invalid_budget_estimate_is_rejected() {
         let tmp = TempDir::new().unwrap();
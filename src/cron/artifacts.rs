@@ -0,0 +1,294 @@
+use super::store::{parse_rfc3339, sql_conversion_error, with_connection};
+use crate::config::Config;
+use crate::cron::CronArtifact;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::params;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable pointing shell jobs at their artifact staging directory.
+pub const ARTIFACTS_DIR_ENV: &str = "ZEROCLAW_ARTIFACTS_DIR";
+
+const MAX_ARTIFACTS_PER_RUN: usize = 32;
+const MAX_ARTIFACT_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Per-job staging directory; files left here when a run finishes become its artifacts.
+pub fn artifact_staging_dir(config: &Config, job_id: &str) -> PathBuf {
+    config
+        .workspace_dir
+        .join("cron")
+        .join("staging")
+        .join(job_id)
+}
+
+fn artifact_store_dir(config: &Config) -> PathBuf {
+    config.workspace_dir.join("cron").join("artifacts")
+}
+
+fn blob_path(config: &Config, sha256: &str) -> PathBuf {
+    artifact_store_dir(config).join(sha256)
+}
+
+/// Reset the staging directory for a job before it runs.
+pub fn prepare_artifact_staging(config: &Config, job_id: &str) -> Result<PathBuf> {
+    let dir = artifact_staging_dir(config, job_id);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)
+            .with_context(|| format!("Failed to clear artifact staging: {}", dir.display()))?;
+    }
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create artifact staging: {}", dir.display()))?;
+    Ok(dir)
+}
+
+/// Move files staged by a job into the content-addressed store and link them to `run_id`.
+///
+/// Only top-level regular files are collected; symlinks, hidden files, and
+/// oversized files are skipped. The staging directory is removed afterwards.
+pub fn attach_staged_artifacts(
+    config: &Config,
+    job_id: &str,
+    run_id: i64,
+) -> Result<Vec<CronArtifact>> {
+    let staging = artifact_staging_dir(config, job_id);
+    if !staging.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut staged: Vec<(String, PathBuf, u64)> = Vec::new();
+    for entry in fs::read_dir(&staging)
+        .with_context(|| format!("Failed to read artifact staging: {}", staging.display()))?
+    {
+        let entry = entry?;
+        let meta = fs::symlink_metadata(entry.path())?;
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if !meta.is_file() || name.starts_with('.') {
+            continue;
+        }
+        if meta.len() > MAX_ARTIFACT_BYTES {
+            tracing::warn!(
+                "Skipping oversized cron artifact '{name}' ({} bytes)",
+                meta.len()
+            );
+            continue;
+        }
+        staged.push((name, entry.path(), meta.len()));
+    }
+    staged.sort_by(|a, b| a.0.cmp(&b.0));
+    staged.truncate(MAX_ARTIFACTS_PER_RUN);
+
+    let store_dir = artifact_store_dir(config);
+    fs::create_dir_all(&store_dir)
+        .with_context(|| format!("Failed to create artifact store: {}", store_dir.display()))?;
+
+    let now = Utc::now();
+    let mut artifacts = Vec::with_capacity(staged.len());
+    for (name, path, size_bytes) in staged {
+        let bytes = fs::read(&path)
+            .with_context(|| format!("Failed to read artifact: {}", path.display()))?;
+        let sha256 = hex::encode(Sha256::digest(&bytes));
+        let target = blob_path(config, &sha256);
+        if !target.exists() {
+            fs::write(&target, &bytes)
+                .with_context(|| format!("Failed to store artifact: {}", target.display()))?;
+        }
+        artifacts.push(CronArtifact {
+            run_id,
+            name,
+            sha256,
+            size_bytes,
+            created_at: now,
+        });
+    }
+
+    with_connection(config, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        for artifact in &artifacts {
+            tx.execute(
+                "INSERT OR REPLACE INTO cron_run_artifacts (run_id, name, sha256, size_bytes, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    artifact.run_id,
+                    artifact.name,
+                    artifact.sha256,
+                    i64::try_from(artifact.size_bytes).unwrap_or(i64::MAX),
+                    artifact.created_at.to_rfc3339(),
+                ],
+            )
+            .context("Failed to insert cron artifact")?;
+        }
+        tx.commit().context("Failed to commit cron artifacts")?;
+        Ok(())
+    })?;
+
+    if let Err(e) = fs::remove_dir_all(&staging) {
+        tracing::debug!(
+            "Failed to remove artifact staging {}: {e}",
+            staging.display()
+        );
+    }
+    if let Err(e) = prune_unreferenced_blobs(config) {
+        tracing::warn!("Failed to prune cron artifact store: {e}");
+    }
+
+    Ok(artifacts)
+}
+
+pub fn list_run_artifacts(config: &Config, run_id: i64) -> Result<Vec<CronArtifact>> {
+    with_connection(config, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT run_id, name, sha256, size_bytes, created_at
+             FROM cron_run_artifacts
+             WHERE run_id = ?1
+             ORDER BY name",
+        )?;
+        let rows = stmt.query_map(params![run_id], map_artifact_row)?;
+
+        let mut artifacts = Vec::new();
+        for row in rows {
+            artifacts.push(row?);
+        }
+        Ok(artifacts)
+    })
+}
+
+/// Look up a run artifact and the path of its stored blob.
+pub fn get_run_artifact(
+    config: &Config,
+    run_id: i64,
+    name: &str,
+) -> Result<Option<(CronArtifact, PathBuf)>> {
+    let artifact = with_connection(config, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT run_id, name, sha256, size_bytes, created_at
+             FROM cron_run_artifacts
+             WHERE run_id = ?1 AND name = ?2",
+        )?;
+        let mut rows = stmt.query(params![run_id, name])?;
+        match rows.next()? {
+            Some(row) => Ok(Some(map_artifact_row(row)?)),
+            None => Ok(None),
+        }
+    })?;
+
+    Ok(artifact.map(|artifact| {
+        let path = blob_path(config, &artifact.sha256);
+        (artifact, path)
+    }))
+}
+
+fn map_artifact_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronArtifact> {
+    let size_bytes: i64 = row.get(3)?;
+    Ok(CronArtifact {
+        run_id: row.get(0)?,
+        name: row.get(1)?,
+        sha256: row.get(2)?,
+        size_bytes: u64::try_from(size_bytes).unwrap_or(0),
+        created_at: parse_rfc3339(&row.get::<_, String>(4)?).map_err(sql_conversion_error)?,
+    })
+}
+
+/// Remove stored blobs whose runs have been pruned from history.
+fn prune_unreferenced_blobs(config: &Config) -> Result<()> {
+    let referenced: HashSet<String> = with_connection(config, |conn| {
+        let mut stmt = conn.prepare("SELECT DISTINCT sha256 FROM cron_run_artifacts")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut set = HashSet::new();
+        for row in rows {
+            set.insert(row?);
+        }
+        Ok(set)
+    })?;
+
+    let store_dir = artifact_store_dir(config);
+    for entry in fs::read_dir(&store_dir)? {
+        let path = entry?.path();
+        let is_orphan = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| !referenced.contains(n));
+        if is_orphan && path.is_file() {
+            remove_blob(&path);
+        }
+    }
+    Ok(())
+}
+
+fn remove_blob(path: &Path) {
+    if let Err(e) = fs::remove_file(path) {
+        tracing::debug!(
+            "Failed to remove cron artifact blob {}: {e}",
+            path.display()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cron;
+    use chrono::Duration as ChronoDuration;
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        fs::create_dir_all(&config.workspace_dir).unwrap();
+        config
+    }
+
+    #[test]
+    fn staged_files_are_attached_and_retrievable() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = cron::add_job(&config, "*/5 * * * *", "echo ok").unwrap();
+
+        let staging = prepare_artifact_staging(&config, &job.id).unwrap();
+        fs::write(staging.join("report.md"), "# digest").unwrap();
+        fs::write(staging.join(".hidden"), "skip").unwrap();
+
+        let now = Utc::now();
+        let run_id = cron::record_run(
+            &config,
+            &job.id,
+            now,
+            now + ChronoDuration::milliseconds(5),
+            "ok",
+            Some("done"),
+            5,
+        )
+        .unwrap();
+
+        let attached = attach_staged_artifacts(&config, &job.id, run_id).unwrap();
+        assert_eq!(attached.len(), 1);
+        assert_eq!(attached[0].name, "report.md");
+        assert!(!staging.exists());
+
+        let (artifact, path) = get_run_artifact(&config, run_id, "report.md")
+            .unwrap()
+            .unwrap();
+        assert_eq!(artifact.size_bytes, 8);
+        assert_eq!(fs::read_to_string(path).unwrap(), "# digest");
+        assert!(get_run_artifact(&config, run_id, ".hidden")
+            .unwrap()
+            .is_none());
+        assert_eq!(list_run_artifacts(&config, run_id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn missing_staging_dir_attaches_nothing() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        assert!(attach_staged_artifacts(&config, "nope", 1)
+            .unwrap()
+            .is_empty());
+    }
+}
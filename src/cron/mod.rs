@@ -2,25 +2,36 @@ use crate::config::Config;
 use crate::security::SecurityPolicy;
 use anyhow::{bail, Result};
 
+mod artifacts;
 mod schedule;
 mod store;
 mod types;
 
 pub mod scheduler;
 
+#[allow(unused_imports)]
+pub use artifacts::{
+    artifact_staging_dir, attach_staged_artifacts, get_run_artifact, list_run_artifacts,
+    prepare_artifact_staging, ARTIFACTS_DIR_ENV,
+};
 #[allow(unused_imports)]
 pub use schedule::{
     next_run_for_schedule, normalize_expression, schedule_cron_expression, validate_schedule,
 };
+pub(crate) use store::MIGRATIONS as SCHEMA_MIGRATIONS;
 #[allow(unused_imports)]
 pub use store::{
-    add_agent_job, add_job, add_shell_job, due_jobs, get_job, list_jobs, list_runs,
-    record_last_run, record_run, remove_job, reschedule_after_run, update_job,
+    add_agent_job, add_job, add_shell_job, busiest_jobs, defer_job, due_jobs, get_job, list_jobs,
+    list_runs, record_last_run, record_run, remove_job, reschedule_after_run, resolve_upstream,
+    set_run_invocation, skip_chained_run, update_job,
+};
+pub use types::{
+    ChainCondition, CronArtifact, CronJob, CronJobPatch, CronJobStats, CronRun, DeliveryConfig,
+    JobType, RunOverrides, Schedule, SessionTarget,
 };
-pub use types::{CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget};
 
 #[allow(clippy::needless_pass_by_value)]
-pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<()> {
+pub async fn handle_command(command: crate::CronCommands, config: &Config) -> Result<()> {
     match command {
         crate::CronCommands::List => {
             let jobs = list_jobs(config)?;
@@ -51,6 +62,9 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                 if let Some(prompt) = &job.prompt {
                     println!("    prompt: {prompt}");
                 }
+                if let Some(after) = &job.after {
+                    println!("    after: {after} (on {})", job.after_condition.as_str());
+                }
             }
             Ok(())
         }
@@ -149,6 +163,90 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             println!("  Cmd : {}", job.command);
             Ok(())
         }
+        crate::CronCommands::Run {
+            id,
+            date,
+            channel,
+            to,
+            model,
+        } => {
+            let job = get_job(config, &id)?;
+            let date = date
+                .as_deref()
+                .map(|raw| parse_date("--date", raw))
+                .transpose()?;
+            let delivery = channel.zip(to).map(|(channel, to)| DeliveryConfig {
+                mode: "announce".into(),
+                channel: Some(channel),
+                to: Some(to),
+                best_effort: false,
+            });
+            let overrides = RunOverrides {
+                date,
+                delivery,
+                model,
+            };
+            let run = Box::pin(scheduler::run_job_manually(config, &job, &overrides)).await;
+            println!(
+                "{} Ran cron job {} manually ({} ms)",
+                if run.success { "✅" } else { "❌" },
+                job.id,
+                run.duration_ms
+            );
+            if !run.output.trim().is_empty() {
+                println!("{}", run.output.trim_end());
+            }
+            if !run.success {
+                bail!("Cron job {} failed", job.id);
+            }
+            Ok(())
+        }
+        crate::CronCommands::Backfill {
+            id,
+            from,
+            to,
+            concurrency,
+            deliver,
+        } => {
+            let job = get_job(config, &id)?;
+            let from = parse_date("--from", &from)?;
+            let to = parse_date("--to", &to)?;
+            let concurrency = concurrency.unwrap_or(config.scheduler.max_concurrent);
+            println!("⏪ Backfilling cron job {} from {from} to {to}", job.id);
+            let results = scheduler::backfill_job(
+                config,
+                &job,
+                from,
+                to,
+                concurrency,
+                deliver,
+                |done, total, result| {
+                    println!(
+                        "  [{done}/{total}] {} {} ({} ms)",
+                        result.date,
+                        if result.run.success { "ok" } else { "error" },
+                        result.run.duration_ms
+                    );
+                },
+            )
+            .await?;
+            let failed: Vec<String> = results
+                .iter()
+                .filter(|result| !result.run.success)
+                .map(|result| result.date.to_string())
+                .collect();
+            if failed.is_empty() {
+                println!("✅ Backfilled {} day(s)", results.len());
+                Ok(())
+            } else {
+                bail!(
+                    "{} of {} day(s) failed: {}",
+                    failed.len(),
+                    results.len(),
+                    failed.join(", ")
+                )
+            }
+        }
         crate::CronCommands::Remove { id } => remove_job(config, &id),
         crate::CronCommands::Pause { id } => {
             pause_job(config, &id)?;
@@ -200,6 +298,11 @@ pub fn resume_job(config: &Config, id: &str) -> Result<CronJob> {
     )
 }
 
+fn parse_date(flag: &str, raw: &str) -> Result<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+        .map_err(|e| anyhow::anyhow!("Invalid {flag} (expected YYYY-MM-DD): {e}"))
+}
+
 fn parse_delay(input: &str) -> Result<chrono::Duration> {
     let input = input.trim();
     if input.is_empty() {
@@ -249,7 +352,7 @@ mod tests {
         .unwrap()
     }
 
-    fn run_update(
+    async fn run_update(
         config: &Config,
         id: &str,
         expression: Option<&str>,
@@ -267,47 +370,54 @@ mod tests {
             },
             config,
         )
+        .await
     }
 
-    #[test]
-    fn update_changes_command_via_handler() {
+    #[tokio::test]
+    async fn update_changes_command_via_handler() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(&config, "*/5 * * * *", None, "echo original");
 
-        run_update(&config, &job.id, None, None, Some("echo updated"), None).unwrap();
+        run_update(&config, &job.id, None, None, Some("echo updated"), None)
+            .await
+            .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
         assert_eq!(updated.command, "echo updated");
         assert_eq!(updated.id, job.id);
     }
 
-    #[test]
-    fn update_changes_expression_via_handler() {
+    #[tokio::test]
+    async fn update_changes_expression_via_handler() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(&config, "*/5 * * * *", None, "echo test");
 
-        run_update(&config, &job.id, Some("0 9 * * *"), None, None, None).unwrap();
+        run_update(&config, &job.id, Some("0 9 * * *"), None, None, None)
+            .await
+            .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
         assert_eq!(updated.expression, "0 9 * * *");
     }
 
-    #[test]
-    fn update_changes_name_via_handler() {
+    #[tokio::test]
+    async fn update_changes_name_via_handler() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(&config, "*/5 * * * *", None, "echo test");
 
-        run_update(&config, &job.id, None, None, None, Some("new-name")).unwrap();
+        run_update(&config, &job.id, None, None, None, Some("new-name"))
+            .await
+            .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
         assert_eq!(updated.name.as_deref(), Some("new-name"));
     }
 
-    #[test]
-    fn update_tz_alone_sets_timezone() {
+    #[tokio::test]
+    async fn update_tz_alone_sets_timezone() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(&config, "*/5 * * * *", None, "echo test");
@@ -320,6 +430,7 @@ mod tests {
             None,
             None,
         )
+        .await
         .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
@@ -332,8 +443,8 @@ mod tests {
         );
     }
 
-    #[test]
-    fn update_expression_preserves_existing_tz() {
+    #[tokio::test]
+    async fn update_expression_preserves_existing_tz() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(
@@ -343,7 +454,9 @@ mod tests {
             "echo test",
         );
 
-        run_update(&config, &job.id, Some("0 9 * * *"), None, None, None).unwrap();
+        run_update(&config, &job.id, Some("0 9 * * *"), None, None, None)
+            .await
+            .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
         assert_eq!(
@@ -355,8 +468,8 @@ mod tests {
         );
     }
 
-    #[test]
-    fn update_preserves_unchanged_fields() {
+    #[tokio::test]
+    async fn update_preserves_unchanged_fields() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = add_shell_job(
@@ -370,7 +483,9 @@ mod tests {
         )
         .unwrap();
 
-        run_update(&config, &job.id, None, None, Some("echo changed"), None).unwrap();
+        run_update(&config, &job.id, None, None, Some("echo changed"), None)
+            .await
+            .unwrap();
 
         let updated = get_job(&config, &job.id).unwrap();
         assert_eq!(updated.command, "echo changed");
@@ -378,19 +493,19 @@ mod tests {
         assert_eq!(updated.expression, "*/5 * * * *");
     }
 
-    #[test]
-    fn update_no_flags_fails() {
+    #[tokio::test]
+    async fn update_no_flags_fails() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
         let job = make_job(&config, "*/5 * * * *", None, "echo test");
 
-        let result = run_update(&config, &job.id, None, None, None, None);
+        let result = run_update(&config, &job.id, None, None, None, None).await;
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("At least one of"));
     }
 
-    #[test]
-    fn update_nonexistent_job_fails() {
+    #[tokio::test]
+    async fn update_nonexistent_job_fails() {
         let tmp = TempDir::new().unwrap();
         let config = test_config(&tmp);
 
@@ -401,7 +516,8 @@ mod tests {
             None,
             Some("echo test"),
             None,
-        );
+        )
+        .await;
         assert!(result.is_err());
     }
 
@@ -1,15 +1,21 @@
 use crate::channels::{
     Channel, DiscordChannel, MattermostChannel, SendMessage, SlackChannel, TelegramChannel,
 };
+use crate::config::confd::{AutomationConfig, ConfD, ConfDWatcher};
 use crate::config::Config;
 use crate::cron::{
-    due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
-    update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
+    add_agent_job, add_shell_job, artifact_staging_dir, attach_staged_artifacts, defer_job,
+    due_jobs, list_jobs, next_run_for_schedule, prepare_artifact_staging, record_last_run,
+    record_run, remove_job, reschedule_after_run, resolve_upstream, set_run_invocation,
+    skip_chained_run, update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, RunOverrides,
+    Schedule, SessionTarget,
 };
 use crate::security::SecurityPolicy;
+use crate::tools::template::{self, Escape, Unknown};
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use futures_util::{stream, StreamExt};
+use std::collections::HashMap;
 use std::process::Stdio;
 use std::sync::Arc;
 use tokio::process::Command;
@@ -18,6 +24,9 @@ use tokio::time::{self, Duration};
 const MIN_POLL_SECONDS: u64 = 5;
 const SHELL_JOB_TIMEOUT_SECS: u64 = 120;
 const SCHEDULER_COMPONENT: &str = "scheduler";
+const MAX_BACKFILL_DAYS: i64 = 366;
+/// Name prefix of cron jobs owned by `conf.d/automations`.
+const CONF_D_JOB_PREFIX: &str = "conf.d:";
 
 pub async fn run(config: Config) -> Result<()> {
     let poll_secs = config.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
@@ -28,6 +37,9 @@ pub async fn run(config: Config) -> Result<()> {
         &config.workspace_dir,
     ));
 
+    let conf_d = ConfDWatcher::shared(&config);
+    let mut synced: Option<Arc<ConfD>> = None;
+
     crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
     loop {
@@ -35,6 +47,17 @@ pub async fn run(config: Config) -> Result<()> {
         // Keep scheduler liveness fresh even when there are no due jobs.
         crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
+        let current = conf_d.current();
+        if !synced
+            .as_ref()
+            .is_some_and(|seen| Arc::ptr_eq(seen, &current))
+        {
+            if let Err(e) = sync_conf_d_automations(&config, &current) {
+                tracing::warn!("conf.d automation sync failed: {e}");
+            }
+            synced = Some(current);
+        }
+
         let jobs = match due_jobs(&config, Utc::now()) {
             Ok(jobs) => jobs,
             Err(e) => {
@@ -48,9 +71,260 @@ pub async fn run(config: Config) -> Result<()> {
     }
 }
 
-pub async fn execute_job_now(config: &Config, job: &CronJob) -> (bool, String) {
+/// Make the `conf.d:<name>` cron jobs match `conf.d/automations`: create
+/// new ones, patch changed ones, and remove jobs whose file is gone. A job
+/// that switches between `command` and `prompt` is recreated.
+pub fn sync_conf_d_automations(config: &Config, conf_d: &ConfD) -> Result<()> {
+    let mut existing: HashMap<String, CronJob> = list_jobs(config)?
+        .into_iter()
+        .filter_map(|job| {
+            let name = job.name.as_deref()?.strip_prefix(CONF_D_JOB_PREFIX)?;
+            Some((name.to_string(), job))
+        })
+        .collect();
+
+    for (name, automation) in &conf_d.automations {
+        let job_name = format!("{CONF_D_JOB_PREFIX}{name}");
+        let wanted_type = if automation.prompt.is_some() {
+            JobType::Agent
+        } else {
+            JobType::Shell
+        };
+        let result = match existing.remove(name) {
+            Some(job) if job.job_type == wanted_type => {
+                update_job(config, &job.id, automation_patch(&job, automation)).map(|_| ())
+            }
+            Some(job) => remove_job(config, &job.id)
+                .and_then(|()| add_automation_job(config, job_name, automation)),
+            None => add_automation_job(config, job_name, automation),
+        };
+        if let Err(e) = result {
+            tracing::warn!("conf.d automation '{name}' not applied: {e}");
+        }
+    }
+
+    for job in existing.into_values() {
+        remove_job(config, &job.id)?;
+    }
+    Ok(())
+}
+
+fn add_automation_job(config: &Config, name: String, automation: &AutomationConfig) -> Result<()> {
+    let job = match (&automation.command, &automation.prompt) {
+        (Some(command), _) => {
+            add_shell_job(config, Some(name), automation.schedule.clone(), command)?
+        }
+        (None, Some(prompt)) => add_agent_job(
+            config,
+            Some(name),
+            automation.schedule.clone(),
+            prompt,
+            SessionTarget::Isolated,
+            automation.model.clone(),
+            automation.delivery.clone(),
+            false,
+            None,
+        )?,
+        (None, None) => anyhow::bail!("automation sets neither command nor prompt"),
+    };
+    if !automation.enabled || automation.delivery.is_some() {
+        update_job(config, &job.id, automation_patch(&job, automation))?;
+    }
+    Ok(())
+}
+
+/// Patch for the fields of `job` that differ from `automation`. The schedule
+/// is only patched when it changed, so an unchanged file keeps `next_run`.
+fn automation_patch(job: &CronJob, automation: &AutomationConfig) -> CronJobPatch {
+    CronJobPatch {
+        schedule: (job.schedule != automation.schedule).then(|| automation.schedule.clone()),
+        command: automation.command.clone(),
+        prompt: automation.prompt.clone(),
+        enabled: Some(automation.enabled),
+        delivery: Some(automation.delivery.clone().unwrap_or_default()),
+        model: automation.model.clone(),
+        ..CronJobPatch::default()
+    }
+}
+
+/// Result of [`run_job_manually`].
+#[derive(Debug, Clone)]
+pub struct ManualRun {
+    pub run_id: Option<i64>,
+    pub success: bool,
+    pub output: String,
+    pub duration_ms: i64,
+}
+
+/// Run `job` immediately with one-off `overrides`, deliver the output, and
+/// record the run as a manual invocation. The job's schedule is untouched.
+pub async fn run_job_manually(
+    config: &Config,
+    job: &CronJob,
+    overrides: &RunOverrides,
+) -> ManualRun {
+    Box::pin(run_job_with_overrides(config, job, overrides, "manual")).await
+}
+
+/// Run the `conf.d/automations/<name>` job now on behalf of an event source
+/// (e.g. a metric anomaly), recorded as an `event` invocation. Disabled
+/// automations still run, so an automation can be event-only.
+pub async fn run_automation_for_event(config: &Config, name: &str) -> Result<ManualRun> {
+    let job_name = format!("{CONF_D_JOB_PREFIX}{name}");
+    let Some(job) = list_jobs(config)?
+        .into_iter()
+        .find(|job| job.name.as_deref() == Some(job_name.as_str()))
+    else {
+        anyhow::bail!("No conf.d automation named '{name}'");
+    };
+    Ok(Box::pin(run_job_with_overrides(
+        config,
+        &job,
+        &RunOverrides::default(),
+        "event",
+    ))
+    .await)
+}
+
+async fn run_job_with_overrides(
+    config: &Config,
+    job: &CronJob,
+    overrides: &RunOverrides,
+    invocation: &str,
+) -> ManualRun {
     let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
-    execute_job_with_retry(config, &security, job).await
+    let mut job = with_run_date(
+        job,
+        overrides.date.unwrap_or_else(|| Utc::now().date_naive()),
+    );
+    if let Some(delivery) = &overrides.delivery {
+        job.delivery = delivery.clone();
+    }
+    if let Some(model) = &overrides.model {
+        job.model = Some(model.clone());
+    }
+
+    let started_at = Utc::now();
+    let (mut success, output) = Box::pin(execute_job_with_retry(config, &security, &job)).await;
+    if let Err(e) = deliver_if_configured(config, &job, &output).await {
+        if job.delivery.best_effort {
+            tracing::warn!("Cron delivery failed (best_effort): {e}");
+        } else {
+            success = false;
+            tracing::warn!("Cron delivery failed: {e}");
+        }
+    }
+    let finished_at = Utc::now();
+    let duration_ms = (finished_at - started_at).num_milliseconds();
+
+    let run_id = record_run(
+        config,
+        &job.id,
+        started_at,
+        finished_at,
+        if success { "ok" } else { "error" },
+        Some(&output),
+        duration_ms,
+    )
+    .inspect_err(|e| tracing::warn!("Failed to record manual cron run: {e}"))
+    .ok();
+    if let Some(run_id) = run_id {
+        if let Err(e) = set_run_invocation(config, run_id, invocation) {
+            tracing::warn!("{e}");
+        }
+        if let Err(e) = attach_staged_artifacts(config, &job.id, run_id) {
+            tracing::warn!("Failed to attach cron run artifacts: {e}");
+        }
+    }
+    let _ = record_last_run(config, &job.id, finished_at, success, &output);
+
+    ManualRun {
+        run_id,
+        success,
+        output,
+        duration_ms,
+    }
+}
+
+/// One date of a backfill.
+#[derive(Debug, Clone)]
+pub struct BackfillRun {
+    pub date: NaiveDate,
+    pub run: ManualRun,
+}
+
+/// Run a date-parameterized job once per day in `from..=to`, at most
+/// `concurrency` at a time, recording each as a `backfill` invocation.
+/// `on_progress(done, total, run)` is called as each date finishes; results
+/// are returned oldest first. Delivery is skipped unless `deliver` is set.
+pub async fn backfill_job(
+    config: &Config,
+    job: &CronJob,
+    from: NaiveDate,
+    to: NaiveDate,
+    concurrency: usize,
+    deliver: bool,
+    mut on_progress: impl FnMut(usize, usize, &BackfillRun),
+) -> Result<Vec<BackfillRun>> {
+    if to < from {
+        anyhow::bail!("Backfill range is empty: {from} is after {to}");
+    }
+    let days = (to - from).num_days() + 1;
+    if days > MAX_BACKFILL_DAYS {
+        anyhow::bail!("Backfill range of {days} days exceeds the {MAX_BACKFILL_DAYS}-day limit");
+    }
+
+    let dates: Vec<NaiveDate> = from.iter_days().take_while(|date| *date <= to).collect();
+    let total = dates.len();
+    let mut pending = stream::iter(dates.into_iter().map(|date| async move {
+        let overrides = RunOverrides {
+            date: Some(date),
+            delivery: (!deliver).then(DeliveryConfig::default),
+            model: None,
+        };
+        let run = Box::pin(run_job_with_overrides(config, job, &overrides, "backfill")).await;
+        BackfillRun { date, run }
+    }))
+    .buffer_unordered(concurrency.max(1));
+
+    let mut results = Vec::with_capacity(total);
+    while let Some(result) = pending.next().await {
+        on_progress(results.len() + 1, total, &result);
+        results.push(result);
+    }
+    results.sort_by_key(|result| result.date);
+    Ok(results)
+}
+
+/// Expand `{{date}}` (the run date, YYYY-MM-DD), `{{now}}` and
+/// `{{last_result.output}}` / `{{last_result.status}}` (the previous run) in the
+/// job's command and prompt. The previous output is shell-quoted in commands;
+/// any other `{{…}}` is left untouched.
+fn with_run_date(job: &CronJob, date: NaiveDate) -> CronJob {
+    let ctx = serde_json::json!({
+        "date": date.format("%Y-%m-%d").to_string(),
+        "now": template::now(),
+        "last_result": {
+            "output": job.last_output.clone().unwrap_or_default(),
+            "status": job.last_status.clone().unwrap_or_default(),
+        },
+    });
+    let command_escape = |key: &str| {
+        if key.starts_with("last_result.") {
+            Escape::Shell
+        } else {
+            Escape::None
+        }
+    };
+
+    let mut job = job.clone();
+    if let Ok(command) = template::render_with(&job.command, &ctx, command_escape, Unknown::Keep) {
+        job.command = command;
+    }
+    job.prompt = job.prompt.map(|prompt| {
+        template::render(&prompt, &ctx, Escape::None, Unknown::Keep).unwrap_or(prompt)
+    });
+    job
 }
 
 async fn execute_job_with_retry(
@@ -60,6 +334,10 @@ async fn execute_job_with_retry(
 ) -> (bool, String) {
     let mut last_output = String::new();
     let retries = config.reliability.scheduler_retries;
+
+    if let Err(e) = prepare_artifact_staging(config, &job.id) {
+        tracing::warn!("Failed to prepare artifact staging for '{}': {e}", job.id);
+    }
     let mut backoff_ms = config.reliability.provider_backoff_ms.max(200);
 
     for attempt in 0..=retries {
@@ -98,23 +376,117 @@ async fn process_due_jobs(
     crate::health::mark_component_ok(component);
 
     let max_concurrent = config.scheduler.max_concurrent.max(1);
-    let mut in_flight =
-        stream::iter(
-            jobs.into_iter().map(|job| {
+    let all_jobs = if jobs.iter().any(|job| job.after.is_some()) {
+        list_jobs(config).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    let batch: Vec<CronJob> = jobs.clone();
+    let mut outcomes: HashMap<String, bool> = HashMap::new();
+
+    for wave in chain_waves(jobs) {
+        let mut runnable = Vec::with_capacity(wave.len());
+        for job in wave {
+            match chain_skip_reason(&job, &batch, &all_jobs, &outcomes) {
+                Some(reason) => {
+                    tracing::info!("Cron job '{}' {reason}", job.id);
+                    if let Err(e) = skip_chained_run(config, &job, &reason) {
+                        tracing::warn!("Failed to record skipped cron job '{}': {e}", job.id);
+                    }
+                }
+                None => runnable.push(job),
+            }
+        }
+
+        let mut in_flight =
+            stream::iter(runnable.into_iter().map(|job| {
                 let config = config.clone();
                 let security = Arc::clone(security);
                 let component = component.to_owned();
                 async move {
                     execute_and_persist_job(&config, security.as_ref(), &job, &component).await
                 }
-            }),
-        )
-        .buffer_unordered(max_concurrent);
+            }))
+            .buffer_unordered(max_concurrent);
 
-    while let Some((job_id, success)) = in_flight.next().await {
-        if !success {
-            tracing::warn!("Scheduler job '{job_id}' failed");
+        while let Some((job_id, success)) = in_flight.next().await {
+            if !success {
+                tracing::warn!("Scheduler job '{job_id}' failed");
+            }
+            outcomes.insert(job_id, success);
+        }
+    }
+}
+
+/// Split due jobs into waves so a chained job runs only after its upstream
+/// job, when both are due in the same tick. Jobs caught in a cycle land in the
+/// first wave, where their unmet upstream makes them skip.
+fn chain_waves(jobs: Vec<CronJob>) -> Vec<Vec<CronJob>> {
+    let depths: Vec<usize> = jobs
+        .iter()
+        .map(|job| {
+            let mut depth = 0;
+            let mut current = job;
+            while let Some(upstream) = current
+                .after
+                .as_deref()
+                .and_then(|after| resolve_upstream(&jobs, after))
+            {
+                depth += 1;
+                if depth > jobs.len() {
+                    return 0;
+                }
+                current = upstream;
+            }
+            depth
+        })
+        .collect();
+
+    let mut waves: Vec<Vec<CronJob>> = Vec::new();
+    for (job, depth) in jobs.into_iter().zip(depths) {
+        if waves.len() <= depth {
+            waves.resize_with(depth + 1, Vec::new);
         }
+        waves[depth].push(job);
+    }
+    waves.retain(|wave| !wave.is_empty());
+    waves
+}
+
+/// Why a chained job should not run this tick, or `None` to run it. The
+/// upstream outcome comes from this tick when it ran alongside, otherwise
+/// from its most recent recorded result.
+fn chain_skip_reason(
+    job: &CronJob,
+    batch: &[CronJob],
+    all_jobs: &[CronJob],
+    outcomes: &HashMap<String, bool>,
+) -> Option<String> {
+    let after = job.after.as_deref()?;
+    let upstream_outcome = if let Some(upstream) = resolve_upstream(batch, after) {
+        outcomes.get(&upstream.id).copied()
+    } else if let Some(upstream) = resolve_upstream(all_jobs, after) {
+        match upstream.last_status.as_deref() {
+            Some("ok") => Some(true),
+            Some("error") => Some(false),
+            _ => None,
+        }
+    } else {
+        return Some(format!("skipped: upstream job '{after}' not found"));
+    };
+
+    if job.after_condition.is_met(upstream_outcome) {
+        None
+    } else {
+        let outcome = match upstream_outcome {
+            Some(true) => "succeeded",
+            Some(false) => "failed",
+            None => "did not run",
+        };
+        Some(format!(
+            "skipped: upstream job '{after}' {outcome} (runs on {})",
+            job.after_condition.as_str()
+        ))
     }
 }
 
@@ -127,14 +499,48 @@ async fn execute_and_persist_job(
     crate::health::mark_component_ok(component);
     warn_if_high_frequency_agent_job(job);
 
+    if matches!(job.job_type, JobType::Agent)
+        && config.cost.defer_jobs_at_cap
+        && crate::cost::daily_cap_reached(&config.cost, &config.workspace_dir)
+    {
+        defer_job_for_daily_cap(config, job).await;
+        return (job.id.clone(), true);
+    }
+
     let started_at = Utc::now();
-    let (success, output) = execute_job_with_retry(config, security, job).await;
+    let run_job = with_run_date(job, started_at.date_naive());
+    let (success, output) = Box::pin(execute_job_with_retry(config, security, &run_job)).await;
     let finished_at = Utc::now();
     let success = persist_job_result(config, job, success, &output, started_at, finished_at).await;
 
     (job.id.clone(), success)
 }
 
+/// Skip an agent job while the daily spend cap is active and retry it once the
+/// UTC day rolls over, announcing the deferral on the job's delivery channel.
+async fn defer_job_for_daily_cap(config: &Config, job: &CronJob) {
+    let until = next_utc_day_start(Utc::now());
+    let reason = format!(
+        "deferred: daily spend cap reached; next attempt at {}",
+        until.to_rfc3339()
+    );
+    tracing::warn!("Cron agent job '{}' {reason}", job.id);
+
+    if let Err(e) = deliver_if_configured(config, job, &reason).await {
+        tracing::warn!("Cron deferral notification failed: {e}");
+    }
+    if let Err(e) = defer_job(config, &job.id, until, &reason) {
+        tracing::warn!("Failed to defer cron job '{}': {e}", job.id);
+    }
+}
+
+fn next_utc_day_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.date_naive()
+        .succ_opt()
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .map_or_else(|| now + chrono::Duration::days(1), |dt| dt.and_utc())
+}
+
 async fn run_agent_job(
     config: &Config,
     security: &SecurityPolicy,
@@ -162,12 +568,20 @@ async fn run_agent_job(
     }
     let name = job.name.clone().unwrap_or_else(|| "cron-job".to_string());
     let prompt = job.prompt.clone().unwrap_or_default();
-    let prefixed_prompt = format!("[cron:{} {name}] {prompt}", job.id);
+    let staging = artifact_staging_dir(config, &job.id);
+    let staging_rel = staging
+        .strip_prefix(&config.workspace_dir)
+        .unwrap_or(&staging)
+        .display();
+    let prefixed_prompt = format!(
+        "[cron:{} {name}] {prompt}\n\n[artifacts] Files saved to {staging_rel}/ are attached to this run.",
+        job.id
+    );
     let model_override = job.model.clone();
 
     let run_result = match job.session_target {
         SessionTarget::Main | SessionTarget::Isolated => {
-            crate::agent::run(
+            Box::pin(crate::agent::run(
                 config.clone(),
                 Some(prefixed_prompt),
                 None,
@@ -175,7 +589,8 @@ async fn run_agent_job(
                 config.default_temperature,
                 vec![],
                 false,
-            )
+                job.allowed_tools.clone(),
+            ))
             .await
         }
     };
@@ -212,7 +627,7 @@ async fn persist_job_result(
         }
     }
 
-    let _ = record_run(
+    if let Ok(run_id) = record_run(
         config,
         &job.id,
         started_at,
@@ -220,6 +635,21 @@ async fn persist_job_result(
         if success { "ok" } else { "error" },
         Some(output),
         duration_ms,
+    ) {
+        if let Err(e) = attach_staged_artifacts(config, &job.id, run_id) {
+            tracing::warn!("Failed to attach cron run artifacts: {e}");
+        }
+    }
+    crate::gateway::ws::publish_notification(
+        "cron_result",
+        serde_json::json!({
+            "job_id": job.id,
+            "name": job.name,
+            "status": if success { "ok" } else { "error" },
+            "output": crate::util::truncate_with_ellipsis(output, 500),
+            "duration_ms": duration_ms,
+            "finished_at": finished_at.to_rfc3339(),
+        }),
     );
 
     if is_one_shot_auto_delete(job) {
@@ -296,7 +726,20 @@ async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) ->
         .as_deref()
         .ok_or_else(|| anyhow::anyhow!("delivery.to is required for announce mode"))?;
 
-    match channel.to_ascii_lowercase().as_str() {
+    deliver_announcement(config, channel, target, output).await
+}
+
+/// Send `output` to `target` on one of the configured outbound channels.
+pub(crate) async fn deliver_announcement(
+    config: &Config,
+    channel: &str,
+    target: &str,
+    output: &str,
+) -> Result<()> {
+    let channel = channel.to_ascii_lowercase();
+    let rendered = crate::channels::format::render_for_channel(&channel, output);
+    let output = rendered.as_str();
+    match channel.as_str() {
         "telegram" => {
             let tg = config
                 .channels_config
@@ -422,6 +865,10 @@ async fn run_job_command_with_timeout(
         .arg("-lc")
         .arg(&job.command)
         .current_dir(&config.workspace_dir)
+        .env(
+            crate::cron::ARTIFACTS_DIR_ENV,
+            artifact_staging_dir(config, &job.id),
+        )
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -490,6 +937,9 @@ mod tests {
             enabled: true,
             delivery: DeliveryConfig::default(),
             delete_after_run: false,
+            allowed_tools: None,
+            after: None,
+            after_condition: crate::cron::ChainCondition::Success,
             created_at: Utc::now(),
             next_run: Utc::now(),
             last_run: None,
@@ -502,6 +952,157 @@ mod tests {
         format!("{prefix}-{}", uuid::Uuid::new_v4())
     }
 
+    #[tokio::test]
+    async fn run_templates_expand_previous_result_shell_quoted() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let mut job = cron::add_job(
+            &config,
+            "0 6 * * *",
+            "echo {{date}} {{last_result.output}} '{{.Names}}'",
+        )
+        .unwrap();
+        job.last_output = Some("it's $(done)".into());
+        job.prompt = Some("Yesterday said: {{last_result.output}}".into());
+
+        let run_job = with_run_date(&job, NaiveDate::from_ymd_opt(2026, 3, 14).unwrap());
+        assert_eq!(
+            run_job.command,
+            r"echo 2026-03-14 'it'\''s $(done)' '{{.Names}}'"
+        );
+        assert_eq!(
+            run_job.prompt.as_deref(),
+            Some("Yesterday said: it's $(done)")
+        );
+    }
+
+    #[tokio::test]
+    async fn manual_run_applies_date_override_and_is_recorded_as_manual() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let job = cron::add_job(&config, "0 6 * * *", "echo report-{{date}}").unwrap();
+        let overrides = RunOverrides {
+            date: NaiveDate::from_ymd_opt(2026, 3, 14),
+            ..RunOverrides::default()
+        };
+
+        let run = run_job_manually(&config, &job, &overrides).await;
+        assert!(run.success, "{}", run.output);
+        assert!(run.output.contains("report-2026-03-14"));
+
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].invocation, "manual");
+        assert_eq!(
+            cron::get_job(&config, &job.id).unwrap().next_run,
+            job.next_run
+        );
+    }
+
+    #[tokio::test]
+    async fn backfill_runs_each_date_and_reports_progress() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let job = cron::add_job(&config, "0 6 * * *", "echo day-{{date}}").unwrap();
+        let from = NaiveDate::from_ymd_opt(2026, 2, 27).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+
+        let mut progress = Vec::new();
+        let results = backfill_job(&config, &job, from, to, 2, false, |done, total, _| {
+            progress.push((done, total));
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(progress, vec![(1, 3), (2, 3), (3, 3)]);
+        let outputs: Vec<bool> = results
+            .iter()
+            .map(|r| r.run.success && r.run.output.contains(&format!("day-{}", r.date)))
+            .collect();
+        assert_eq!(outputs, vec![true, true, true]);
+        assert_eq!(results[0].date, from);
+
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 3);
+        assert!(runs.iter().all(|run| run.invocation == "backfill"));
+
+        assert!(
+            backfill_job(&config, &job, to, from, 1, false, |_, _, _| {})
+                .await
+                .is_err()
+        );
+    }
+
+    fn chained_job(id: &str, after: Option<&str>) -> CronJob {
+        let mut job = test_job("echo chained");
+        job.id = id.into();
+        job.name = Some(format!("{id}-name"));
+        job.after = after.map(str::to_string);
+        job
+    }
+
+    #[test]
+    fn chain_waves_order_pipeline_by_dependency() {
+        let jobs = vec![
+            chained_job("publish", Some("report-name")),
+            chained_job("report", Some("fetch")),
+            chained_job("fetch", None),
+            chained_job("unrelated", None),
+        ];
+
+        let waves: Vec<Vec<String>> = chain_waves(jobs)
+            .into_iter()
+            .map(|wave| wave.into_iter().map(|job| job.id).collect())
+            .collect();
+        assert_eq!(
+            waves,
+            vec![
+                vec!["fetch".to_string(), "unrelated".to_string()],
+                vec!["report".to_string()],
+                vec!["publish".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn chain_waves_do_not_starve_cycles() {
+        let jobs = vec![chained_job("a", Some("b")), chained_job("b", Some("a"))];
+        let waves = chain_waves(jobs);
+        assert_eq!(waves.len(), 1);
+        assert_eq!(waves[0].len(), 2);
+    }
+
+    #[test]
+    fn chain_skip_reason_follows_condition() {
+        let upstream = chained_job("fetch", None);
+        let mut on_failure = chained_job("alert", Some("fetch"));
+        on_failure.after_condition = crate::cron::ChainCondition::Failure;
+        let on_success = chained_job("report", Some("fetch"));
+        let batch = vec![upstream, on_failure.clone(), on_success.clone()];
+
+        let mut outcomes = HashMap::new();
+        outcomes.insert("fetch".to_string(), true);
+        assert!(chain_skip_reason(&on_success, &batch, &[], &outcomes).is_none());
+        let reason = chain_skip_reason(&on_failure, &batch, &[], &outcomes).unwrap();
+        assert!(reason.contains("succeeded"));
+
+        outcomes.clear();
+        let reason = chain_skip_reason(&on_success, &batch, &[], &outcomes).unwrap();
+        assert!(reason.contains("did not run"));
+    }
+
+    #[test]
+    fn chain_skip_reason_uses_last_status_outside_batch() {
+        let mut upstream = chained_job("fetch", None);
+        upstream.last_status = Some("error".into());
+        let mut job = chained_job("alert", Some("fetch-name"));
+        job.after_condition = crate::cron::ChainCondition::Failure;
+
+        assert!(chain_skip_reason(&job, &[], &[upstream], &HashMap::new()).is_none());
+        let missing = chain_skip_reason(&job, &[], &[], &HashMap::new()).unwrap();
+        assert!(missing.contains("not found"));
+    }
+
     #[tokio::test]
     async fn run_job_command_success() {
         let tmp = TempDir::new().unwrap();
@@ -811,6 +1412,7 @@ mod tests {
             None,
             None,
             true,
+            None,
         )
         .unwrap();
         let started = Utc::now();
@@ -836,6 +1438,7 @@ mod tests {
             None,
             None,
             true,
+            None,
         )
         .unwrap();
         let started = Utc::now();
@@ -848,6 +1451,57 @@ mod tests {
         assert_eq!(updated.last_status.as_deref(), Some("error"));
     }
 
+    #[tokio::test]
+    async fn agent_job_is_deferred_when_daily_cap_reached() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.cost.enabled = true;
+        config.cost.daily_token_limit = 10;
+        let tracker =
+            crate::cost::CostTracker::new(config.cost.clone(), &config.workspace_dir).unwrap();
+        tracker
+            .record_usage(crate::cost::TokenUsage::new("test/model", 20, 20, 0.0, 0.0))
+            .unwrap();
+
+        let job = cron::add_agent_job(
+            &config,
+            None,
+            Schedule::Every { every_ms: 60_000 },
+            "summarize",
+            SessionTarget::Isolated,
+            None,
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+
+        let (_, success) = execute_and_persist_job(
+            &config,
+            &SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir),
+            &job,
+            &unique_component("cap"),
+        )
+        .await;
+        assert!(success);
+
+        let updated = cron::get_job(&config, &job.id).unwrap();
+        assert_eq!(updated.last_status.as_deref(), Some("deferred"));
+        assert_eq!(updated.next_run, next_utc_day_start(Utc::now()));
+        assert!(cron::list_runs(&config, &job.id, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn next_utc_day_start_rolls_to_midnight() {
+        let now = DateTime::parse_from_rfc3339("2026-03-04T17:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            next_utc_day_start(now).to_rfc3339(),
+            "2026-03-05T00:00:00+00:00"
+        );
+    }
+
     #[tokio::test]
     async fn deliver_if_configured_handles_none_and_invalid_channel() {
         let tmp = TempDir::new().unwrap();
@@ -865,4 +1519,51 @@ mod tests {
         let err = deliver_if_configured(&config, &job, "x").await.unwrap_err();
         assert!(err.to_string().contains("unsupported delivery channel"));
     }
+
+    #[tokio::test]
+    async fn sync_conf_d_automations_creates_updates_and_removes_jobs() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let daily = Schedule::Cron {
+            expr: "0 7 * * *".into(),
+            tz: None,
+        };
+        let automation = |command: Option<&str>, prompt: Option<&str>| AutomationConfig {
+            schedule: daily.clone(),
+            command: command.map(str::to_string),
+            prompt: prompt.map(str::to_string),
+            model: None,
+            delivery: None,
+            enabled: true,
+        };
+        cron::add_shell_job(&config, Some("manual".into()), daily.clone(), "echo hi").unwrap();
+
+        let mut conf_d = ConfD {
+            automations: vec![
+                ("backup".into(), automation(Some("echo backup"), None)),
+                ("brief".into(), automation(None, Some("Brief me"))),
+            ],
+            ..ConfD::default()
+        };
+        sync_conf_d_automations(&config, &conf_d).unwrap();
+        let jobs = cron::list_jobs(&config).unwrap();
+        assert_eq!(jobs.len(), 3);
+        let backup = jobs
+            .iter()
+            .find(|j| j.name.as_deref() == Some("conf.d:backup"))
+            .unwrap();
+        assert_eq!(backup.job_type, JobType::Shell);
+
+        conf_d.automations[0].1.command = Some("echo v2".into());
+        conf_d.automations[0].1.enabled = false;
+        conf_d.automations.pop();
+        sync_conf_d_automations(&config, &conf_d).unwrap();
+        let jobs = cron::list_jobs(&config).unwrap();
+        assert_eq!(jobs.len(), 2);
+        let updated = cron::get_job(&config, &backup.id).unwrap();
+        assert_eq!(updated.command, "echo v2");
+        assert!(!updated.enabled);
+        assert_eq!(updated.next_run, backup.next_run);
+        assert!(jobs.iter().any(|j| j.name.as_deref() == Some("manual")));
+    }
 }
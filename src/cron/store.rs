@@ -1,8 +1,9 @@
 use crate::config::Config;
 use crate::cron::{
-    next_run_for_schedule, schedule_cron_expression, validate_schedule, CronJob, CronJobPatch,
-    CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
+    next_run_for_schedule, schedule_cron_expression, validate_schedule, ChainCondition, CronJob,
+    CronJobPatch, CronJobStats, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
 };
+use crate::schema_migrations::Migration;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::types::{FromSqlResult, ValueRef};
@@ -74,6 +75,7 @@ pub fn add_agent_job(
     model: Option<String>,
     delivery: Option<DeliveryConfig>,
     delete_after_run: bool,
+    allowed_tools: Option<Vec<String>>,
 ) -> Result<CronJob> {
     let now = Utc::now();
     validate_schedule(&schedule, now)?;
@@ -87,8 +89,8 @@ pub fn add_agent_job(
         conn.execute(
             "INSERT INTO cron_jobs (
                 id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                enabled, delivery, delete_after_run, created_at, next_run
-             ) VALUES (?1, ?2, '', ?3, 'agent', ?4, ?5, ?6, ?7, 1, ?8, ?9, ?10, ?11)",
+                enabled, delivery, delete_after_run, created_at, next_run, allowed_tools
+             ) VALUES (?1, ?2, '', ?3, 'agent', ?4, ?5, ?6, ?7, 1, ?8, ?9, ?10, ?11, ?12)",
             params![
                 id,
                 expression,
@@ -101,6 +103,7 @@ pub fn add_agent_job(
                 if delete_after_run { 1 } else { 0 },
                 now.to_rfc3339(),
                 next_run.to_rfc3339(),
+                encode_allowed_tools(allowed_tools.as_deref())?,
             ],
         )
         .context("Failed to insert cron agent job")?;
@@ -114,7 +117,8 @@ pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    allowed_tools, after_job, after_condition
              FROM cron_jobs ORDER BY next_run ASC",
         )?;
 
@@ -132,7 +136,8 @@ pub fn get_job(config: &Config, job_id: &str) -> Result<CronJob> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    allowed_tools, after_job, after_condition
              FROM cron_jobs WHERE id = ?1",
         )?;
 
@@ -165,7 +170,8 @@ pub fn due_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    allowed_tools, after_job, after_condition
              FROM cron_jobs
              WHERE enabled = 1 AND next_run <= ?1
              ORDER BY next_run ASC
@@ -216,6 +222,21 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
     if let Some(delete_after_run) = patch.delete_after_run {
         job.delete_after_run = delete_after_run;
     }
+    if let Some(allowed_tools) = patch.allowed_tools {
+        job.allowed_tools = (!allowed_tools.is_empty()).then_some(allowed_tools);
+    }
+    if let Some(after) = patch.after {
+        let after = after.trim().to_string();
+        if after.is_empty() {
+            job.after = None;
+        } else {
+            validate_chain(&list_jobs(config)?, &job.id, &after)?;
+            job.after = Some(after);
+        }
+    }
+    if let Some(condition) = patch.after_condition {
+        job.after_condition = condition;
+    }
 
     if schedule_changed {
         job.next_run = next_run_for_schedule(&job.schedule, Utc::now())?;
@@ -226,8 +247,8 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
             "UPDATE cron_jobs
              SET expression = ?1, command = ?2, schedule = ?3, job_type = ?4, prompt = ?5, name = ?6,
                  session_target = ?7, model = ?8, enabled = ?9, delivery = ?10, delete_after_run = ?11,
-                 next_run = ?12
-             WHERE id = ?13",
+                 next_run = ?12, allowed_tools = ?13, after_job = ?14, after_condition = ?15
+             WHERE id = ?16",
             params![
                 job.expression,
                 job.command,
@@ -241,6 +262,9 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
                 serde_json::to_string(&job.delivery)?,
                 if job.delete_after_run { 1 } else { 0 },
                 job.next_run.to_rfc3339(),
+                encode_allowed_tools(job.allowed_tools.as_deref())?,
+                job.after,
+                job.after_condition.as_str(),
                 job.id,
             ],
         )
@@ -301,6 +325,89 @@ pub fn reschedule_after_run(
     })
 }
 
+/// Push a job's next run to `until` without executing it, marking it `deferred`.
+pub fn defer_job(config: &Config, job_id: &str, until: DateTime<Utc>, reason: &str) -> Result<()> {
+    let bounded_output = truncate_cron_output(reason);
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE cron_jobs
+             SET next_run = ?1, last_status = 'deferred', last_output = ?2
+             WHERE id = ?3",
+            params![until.to_rfc3339(), bounded_output, job_id],
+        )
+        .context("Failed to defer cron job")?;
+        Ok(())
+    })
+}
+
+/// Record that a chained job's condition was not met: log a `skipped` run and
+/// move on to the next occurrence (one-shot jobs are disabled instead).
+pub fn skip_chained_run(config: &Config, job: &CronJob, reason: &str) -> Result<()> {
+    let now = Utc::now();
+    record_run(config, &job.id, now, now, "skipped", Some(reason), 0)?;
+    let enabled = !matches!(job.schedule, Schedule::At { .. });
+    let next_run = next_run_for_schedule(&job.schedule, now)?;
+    let bounded_output = truncate_cron_output(reason);
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE cron_jobs
+             SET next_run = ?1, last_run = ?2, last_status = 'skipped', last_output = ?3,
+                 enabled = ?4
+             WHERE id = ?5",
+            params![
+                next_run.to_rfc3339(),
+                now.to_rfc3339(),
+                bounded_output,
+                if enabled { 1 } else { 0 },
+                job.id
+            ],
+        )
+        .context("Failed to record skipped cron run")?;
+        Ok(())
+    })
+}
+
+/// Tag a recorded run with how it was started (`manual`, `backfill`); runs
+/// default to `schedule`.
+pub fn set_run_invocation(config: &Config, run_id: i64, invocation: &str) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE cron_runs SET invocation = ?1 WHERE id = ?2",
+            params![invocation, run_id],
+        )
+        .context("Failed to tag cron run invocation")?;
+        Ok(())
+    })
+}
+
+/// Find the job referenced by an `after` value, matching id first, then name.
+pub fn resolve_upstream<'a>(jobs: &'a [CronJob], after: &str) -> Option<&'a CronJob> {
+    jobs.iter()
+        .find(|job| job.id == after)
+        .or_else(|| jobs.iter().find(|job| job.name.as_deref() == Some(after)))
+}
+
+/// Reject chains that point at a missing job, at the job itself, or that
+/// would close a cycle.
+fn validate_chain(jobs: &[CronJob], job_id: &str, after: &str) -> Result<()> {
+    let mut current = resolve_upstream(jobs, after)
+        .ok_or_else(|| anyhow::anyhow!("Cron job '{after}' (after) not found"))?;
+    for _ in 0..=jobs.len() {
+        if current.id == job_id {
+            anyhow::bail!("Chaining after '{after}' would create a cycle");
+        }
+        match current
+            .after
+            .as_deref()
+            .and_then(|next| resolve_upstream(jobs, next))
+        {
+            Some(next) => current = next,
+            None => return Ok(()),
+        }
+    }
+    anyhow::bail!("Chaining after '{after}' would create a cycle")
+}
+
 pub fn record_run(
     config: &Config,
     job_id: &str,
@@ -309,7 +416,7 @@ pub fn record_run(
     status: &str,
     output: Option<&str>,
     duration_ms: i64,
-) -> Result<()> {
+) -> Result<i64> {
     let bounded_output = output.map(truncate_cron_output);
     with_connection(config, |conn| {
         // Wrap INSERT + pruning DELETE in an explicit transaction so that
@@ -330,6 +437,7 @@ pub fn record_run(
             ],
         )
         .context("Failed to insert cron run")?;
+        let run_id = tx.last_insert_rowid();
 
         let keep = i64::from(config.cron.max_run_history.max(1));
         tx.execute(
@@ -347,7 +455,7 @@ pub fn record_run(
 
         tx.commit()
             .context("Failed to commit cron run transaction")?;
-        Ok(())
+        Ok(run_id)
     })
 }
 
@@ -374,7 +482,7 @@ pub fn list_runs(config: &Config, job_id: &str, limit: usize) -> Result<Vec<Cron
     with_connection(config, |conn| {
         let lim = i64::try_from(limit.max(1)).context("Run history limit overflow")?;
         let mut stmt = conn.prepare(
-            "SELECT id, job_id, started_at, finished_at, status, output, duration_ms
+            "SELECT id, job_id, started_at, finished_at, status, output, duration_ms, invocation
              FROM cron_runs
              WHERE job_id = ?1
              ORDER BY started_at DESC, id DESC
@@ -392,6 +500,7 @@ pub fn list_runs(config: &Config, job_id: &str, limit: usize) -> Result<Vec<Cron
                 status: row.get(4)?,
                 output: row.get(5)?,
                 duration_ms: row.get(6)?,
+                invocation: row.get(7)?,
             })
         })?;
 
@@ -403,13 +512,46 @@ pub fn list_runs(config: &Config, job_id: &str, limit: usize) -> Result<Vec<Cron
     })
 }
 
-fn parse_rfc3339(raw: &str) -> Result<DateTime<Utc>> {
+/// Jobs with the most runs started at or after `since`, busiest first.
+pub fn busiest_jobs(
+    config: &Config,
+    since: DateTime<Utc>,
+    limit: usize,
+) -> Result<Vec<CronJobStats>> {
+    with_connection(config, |conn| {
+        let lim = i64::try_from(limit.max(1)).context("Job stats limit overflow")?;
+        let mut stmt = conn.prepare(
+            "SELECT r.job_id, j.name, COUNT(*),
+                    SUM(CASE WHEN r.status = 'error' THEN 1 ELSE 0 END),
+                    AVG(r.duration_ms)
+             FROM cron_runs r LEFT JOIN cron_jobs j ON j.id = r.job_id
+             WHERE r.started_at >= ?1
+             GROUP BY r.job_id
+             ORDER BY COUNT(*) DESC, r.job_id
+             LIMIT ?2",
+        )?;
+
+        let rows = stmt.query_map(params![since.to_rfc3339(), lim], |row| {
+            Ok(CronJobStats {
+                job_id: row.get(0)?,
+                name: row.get(1)?,
+                runs: row.get::<_, i64>(2)?.unsigned_abs(),
+                failures: row.get::<_, i64>(3)?.unsigned_abs(),
+                avg_duration_ms: row.get(4)?,
+            })
+        })?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(Into::into)
+    })
+}
+
+pub(super) fn parse_rfc3339(raw: &str) -> Result<DateTime<Utc>> {
     let parsed = DateTime::parse_from_rfc3339(raw)
         .with_context(|| format!("Invalid RFC3339 timestamp in cron DB: {raw}"))?;
     Ok(parsed.with_timezone(&Utc))
 }
 
-fn sql_conversion_error(err: anyhow::Error) -> rusqlite::Error {
+pub(super) fn sql_conversion_error(err: anyhow::Error) -> rusqlite::Error {
     rusqlite::Error::ToSqlConversionFailure(err.into())
 }
 
@@ -425,6 +567,7 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
     let next_run_raw: String = row.get(13)?;
     let last_run_raw: Option<String> = row.get(14)?;
     let created_at_raw: String = row.get(12)?;
+    let allowed_tools_raw: Option<String> = row.get(17)?;
 
     Ok(CronJob {
         id: row.get(0)?,
@@ -439,6 +582,10 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
         enabled: row.get::<_, i64>(9)? != 0,
         delivery,
         delete_after_run: row.get::<_, i64>(11)? != 0,
+        allowed_tools: decode_allowed_tools(allowed_tools_raw.as_deref())
+            .map_err(sql_conversion_error)?,
+        after: row.get(18)?,
+        after_condition: ChainCondition::parse(&row.get::<_, String>(19)?),
         created_at: parse_rfc3339(&created_at_raw).map_err(sql_conversion_error)?,
         next_run: parse_rfc3339(&next_run_raw).map_err(sql_conversion_error)?,
         last_run: match last_run_raw {
@@ -450,6 +597,21 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
     })
 }
 
+fn encode_allowed_tools(allowed_tools: Option<&[String]>) -> Result<Option<String>> {
+    allowed_tools
+        .map(|tools| serde_json::to_string(tools).map_err(Into::into))
+        .transpose()
+}
+
+fn decode_allowed_tools(raw: Option<&str>) -> Result<Option<Vec<String>>> {
+    match raw.map(str::trim) {
+        Some(raw) if !raw.is_empty() => serde_json::from_str(raw)
+            .map(Some)
+            .with_context(|| format!("Failed to parse cron allowed_tools JSON: {raw}")),
+        _ => Ok(None),
+    }
+}
+
 fn decode_schedule(schedule_raw: Option<&str>, expression: &str) -> Result<Schedule> {
     if let Some(raw) = schedule_raw {
         let trimmed = raw.trim();
@@ -480,15 +642,87 @@ fn decode_delivery(delivery_raw: Option<&str>) -> Result<DeliveryConfig> {
     Ok(DeliveryConfig::default())
 }
 
+/// Cron store schema history; append new versions, never edit applied ones.
+pub(crate) const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "baseline",
+    up: "CREATE TABLE IF NOT EXISTS cron_jobs (
+            id               TEXT PRIMARY KEY,
+            expression       TEXT NOT NULL,
+            command          TEXT NOT NULL,
+            schedule         TEXT,
+            job_type         TEXT NOT NULL DEFAULT 'shell',
+            prompt           TEXT,
+            name             TEXT,
+            session_target   TEXT NOT NULL DEFAULT 'isolated',
+            model            TEXT,
+            enabled          INTEGER NOT NULL DEFAULT 1,
+            delivery         TEXT,
+            delete_after_run INTEGER NOT NULL DEFAULT 0,
+            created_at       TEXT NOT NULL,
+            next_run         TEXT NOT NULL,
+            last_run         TEXT,
+            last_status      TEXT,
+            last_output      TEXT,
+            allowed_tools    TEXT,
+            after_job        TEXT,
+            after_condition  TEXT NOT NULL DEFAULT 'success'
+        );
+        CREATE INDEX IF NOT EXISTS idx_cron_jobs_next_run ON cron_jobs(next_run);
+
+        CREATE TABLE IF NOT EXISTS cron_runs (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            job_id      TEXT NOT NULL,
+            started_at  TEXT NOT NULL,
+            finished_at TEXT NOT NULL,
+            status      TEXT NOT NULL,
+            output      TEXT,
+            duration_ms INTEGER,
+            invocation  TEXT NOT NULL DEFAULT 'schedule',
+            FOREIGN KEY (job_id) REFERENCES cron_jobs(id) ON DELETE CASCADE
+        );
+        CREATE INDEX IF NOT EXISTS idx_cron_runs_job_id ON cron_runs(job_id);
+        CREATE INDEX IF NOT EXISTS idx_cron_runs_started_at ON cron_runs(started_at);
+        CREATE INDEX IF NOT EXISTS idx_cron_runs_job_started ON cron_runs(job_id, started_at);
+
+        CREATE TABLE IF NOT EXISTS cron_run_artifacts (
+            run_id     INTEGER NOT NULL,
+            name       TEXT NOT NULL,
+            sha256     TEXT NOT NULL,
+            size_bytes INTEGER NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (run_id, name),
+            FOREIGN KEY (run_id) REFERENCES cron_runs(id) ON DELETE CASCADE
+        );",
+    down: "DROP TABLE IF EXISTS cron_run_artifacts;
+           DROP TABLE IF EXISTS cron_runs;
+           DROP TABLE IF EXISTS cron_jobs;",
+}];
+
 fn add_column_if_missing(conn: &Connection, name: &str, sql_type: &str) -> Result<()> {
-    let mut stmt = conn.prepare("PRAGMA table_info(cron_jobs)")?;
+    add_table_column_if_missing(conn, "cron_jobs", name, sql_type)
+}
+
+fn add_table_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    name: &str,
+    sql_type: &str,
+) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
     let mut rows = stmt.query([])?;
+    let mut table_exists = false;
     while let Some(row) = rows.next()? {
+        table_exists = true;
         let col_name: String = row.get(1)?;
         if col_name == name {
             return Ok(());
         }
     }
+    // Fresh databases get the column from the baseline migration.
+    if !table_exists {
+        return Ok(());
+    }
     // Drop the statement/rows before executing ALTER to release any locks
     drop(rows);
     drop(stmt);
@@ -496,21 +730,24 @@ fn add_column_if_missing(conn: &Connection, name: &str, sql_type: &str) -> Resul
     // Tolerate "duplicate column name" errors to handle the race where
     // another process adds the column between our PRAGMA check and ALTER.
     match conn.execute(
-        &format!("ALTER TABLE cron_jobs ADD COLUMN {name} {sql_type}"),
+        &format!("ALTER TABLE {table} ADD COLUMN {name} {sql_type}"),
         [],
     ) {
         Ok(_) => Ok(()),
         Err(rusqlite::Error::SqliteFailure(err, Some(ref msg)))
             if msg.contains("duplicate column name") =>
         {
-            tracing::debug!("Column cron_jobs.{name} already exists (concurrent migration): {err}");
+            tracing::debug!("Column {table}.{name} already exists (concurrent migration): {err}");
             Ok(())
         }
-        Err(e) => Err(e).with_context(|| format!("Failed to add cron_jobs.{name}")),
+        Err(e) => Err(e).with_context(|| format!("Failed to add {table}.{name}")),
     }
 }
 
-fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+pub(super) fn with_connection<T>(
+    config: &Config,
+    f: impl FnOnce(&Connection) -> Result<T>,
+) -> Result<T> {
     let db_path = config.workspace_dir.join("cron").join("jobs.db");
     if let Some(parent) = db_path.parent() {
         std::fs::create_dir_all(parent)
@@ -520,45 +757,10 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
     let conn = Connection::open(&db_path)
         .with_context(|| format!("Failed to open cron DB: {}", db_path.display()))?;
 
-    conn.execute_batch(
-        "PRAGMA foreign_keys = ON;
-         CREATE TABLE IF NOT EXISTS cron_jobs (
-            id               TEXT PRIMARY KEY,
-            expression       TEXT NOT NULL,
-            command          TEXT NOT NULL,
-            schedule         TEXT,
-            job_type         TEXT NOT NULL DEFAULT 'shell',
-            prompt           TEXT,
-            name             TEXT,
-            session_target   TEXT NOT NULL DEFAULT 'isolated',
-            model            TEXT,
-            enabled          INTEGER NOT NULL DEFAULT 1,
-            delivery         TEXT,
-            delete_after_run INTEGER NOT NULL DEFAULT 0,
-            created_at       TEXT NOT NULL,
-            next_run         TEXT NOT NULL,
-            last_run         TEXT,
-            last_status      TEXT,
-            last_output      TEXT
-        );
-        CREATE INDEX IF NOT EXISTS idx_cron_jobs_next_run ON cron_jobs(next_run);
-
-        CREATE TABLE IF NOT EXISTS cron_runs (
-            id          INTEGER PRIMARY KEY AUTOINCREMENT,
-            job_id      TEXT NOT NULL,
-            started_at  TEXT NOT NULL,
-            finished_at TEXT NOT NULL,
-            status      TEXT NOT NULL,
-            output      TEXT,
-            duration_ms INTEGER,
-            FOREIGN KEY (job_id) REFERENCES cron_jobs(id) ON DELETE CASCADE
-        );
-        CREATE INDEX IF NOT EXISTS idx_cron_runs_job_id ON cron_runs(job_id);
-        CREATE INDEX IF NOT EXISTS idx_cron_runs_started_at ON cron_runs(started_at);
-        CREATE INDEX IF NOT EXISTS idx_cron_runs_job_started ON cron_runs(job_id, started_at);",
-    )
-    .context("Failed to initialize cron schema")?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
 
+    // Databases created before versioned migrations may predate some
+    // columns; patch them up so the baseline below matches.
     add_column_if_missing(&conn, "schedule", "TEXT")?;
     add_column_if_missing(&conn, "job_type", "TEXT NOT NULL DEFAULT 'shell'")?;
     add_column_if_missing(&conn, "prompt", "TEXT")?;
@@ -568,6 +770,18 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
     add_column_if_missing(&conn, "enabled", "INTEGER NOT NULL DEFAULT 1")?;
     add_column_if_missing(&conn, "delivery", "TEXT")?;
     add_column_if_missing(&conn, "delete_after_run", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(&conn, "allowed_tools", "TEXT")?;
+    add_column_if_missing(&conn, "after_job", "TEXT")?;
+    add_column_if_missing(&conn, "after_condition", "TEXT NOT NULL DEFAULT 'success'")?;
+    add_table_column_if_missing(
+        &conn,
+        "cron_runs",
+        "invocation",
+        "TEXT NOT NULL DEFAULT 'schedule'",
+    )?;
+
+    crate::schema_migrations::migrate(&conn, "cron", MIGRATIONS)
+        .context("Failed to migrate cron schema")?;
 
     f(&conn)
 }
@@ -600,6 +814,32 @@ mod tests {
         assert!(matches!(job.schedule, Schedule::Cron { .. }));
     }
 
+    #[test]
+    fn legacy_database_is_patched_and_adopted_by_baseline_migration() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let db_path = config.workspace_dir.join("cron").join("jobs.db");
+        std::fs::create_dir_all(db_path.parent().unwrap()).unwrap();
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE cron_jobs (
+                    id TEXT PRIMARY KEY, expression TEXT NOT NULL, command TEXT NOT NULL,
+                    created_at TEXT NOT NULL, next_run TEXT NOT NULL,
+                    last_run TEXT, last_status TEXT, last_output TEXT
+                );",
+            )
+            .unwrap();
+
+        let job = add_job(&config, "*/5 * * * *", "echo legacy").unwrap();
+        assert_eq!(list_jobs(&config).unwrap()[0].id, job.id);
+
+        let applied =
+            with_connection(&config, |conn| crate::schema_migrations::applied(conn)).unwrap();
+        assert_eq!(applied.len(), MIGRATIONS.len());
+        assert_eq!(applied[0].name, "baseline");
+    }
+
     #[test]
     fn add_list_remove_roundtrip() {
         let tmp = TempDir::new().unwrap();
@@ -614,6 +854,97 @@ mod tests {
         assert!(list_jobs(&config).unwrap().is_empty());
     }
 
+    #[test]
+    fn agent_job_allowed_tools_roundtrip_and_clear() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+
+        let job = add_agent_job(
+            &config,
+            Some("digest".into()),
+            Schedule::Cron {
+                expr: "0 8 * * *".into(),
+                tz: None,
+            },
+            "summarize feeds",
+            SessionTarget::Isolated,
+            None,
+            None,
+            false,
+            Some(vec!["rss_feed".into(), "ntfy".into()]),
+        )
+        .unwrap();
+        assert_eq!(
+            get_job(&config, &job.id).unwrap().allowed_tools,
+            Some(vec!["rss_feed".to_string(), "ntfy".to_string()])
+        );
+
+        let cleared = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                allowed_tools: Some(vec![]),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert!(cleared.allowed_tools.is_none());
+    }
+
+    #[test]
+    fn update_job_chains_by_name_and_rejects_cycles() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let every = || Schedule::Every { every_ms: 60_000 };
+
+        let fetch = add_shell_job(&config, Some("fetch".into()), every(), "echo fetch").unwrap();
+        let report = add_shell_job(&config, Some("report".into()), every(), "echo report").unwrap();
+
+        let chained = update_job(
+            &config,
+            &report.id,
+            CronJobPatch {
+                after: Some("fetch".into()),
+                after_condition: Some(ChainCondition::Always),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(chained.after.as_deref(), Some("fetch"));
+        assert_eq!(chained.after_condition, ChainCondition::Always);
+
+        let cycle = update_job(
+            &config,
+            &fetch.id,
+            CronJobPatch {
+                after: Some("report".into()),
+                ..CronJobPatch::default()
+            },
+        );
+        assert!(cycle.unwrap_err().to_string().contains("cycle"));
+
+        let missing = update_job(
+            &config,
+            &fetch.id,
+            CronJobPatch {
+                after: Some("nope".into()),
+                ..CronJobPatch::default()
+            },
+        );
+        assert!(missing.is_err());
+
+        let unchained = update_job(
+            &config,
+            &report.id,
+            CronJobPatch {
+                after: Some(String::new()),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert!(unchained.after.is_none());
+    }
+
     #[test]
     fn due_jobs_filters_by_timestamp_and_enabled() {
         let tmp = TempDir::new().unwrap();
@@ -819,6 +1150,27 @@ mod tests {
         assert!(stored.len() <= MAX_CRON_OUTPUT_BYTES);
     }
 
+    #[test]
+    fn busiest_jobs_counts_runs_and_failures_in_window() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let quiet = add_job(&config, "0 * * * *", "echo quiet").unwrap();
+        let busy = add_job(&config, "*/5 * * * *", "echo busy").unwrap();
+        let now = Utc::now();
+        let old = now - ChronoDuration::days(10);
+        record_run(&config, &quiet.id, old, old, "ok", None, 5).unwrap();
+        record_run(&config, &quiet.id, now, now, "ok", None, 5).unwrap();
+        record_run(&config, &busy.id, now, now, "ok", None, 10).unwrap();
+        record_run(&config, &busy.id, now, now, "error", None, 30).unwrap();
+
+        let stats = busiest_jobs(&config, now - ChronoDuration::days(1), 10).unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].job_id, busy.id);
+        assert_eq!((stats[0].runs, stats[0].failures), (2, 1));
+        assert!((stats[0].avg_duration_ms.unwrap() - 20.0).abs() < f64::EPSILON);
+        assert_eq!(stats[1].runs, 1);
+    }
+
     #[test]
     fn reschedule_after_run_truncates_last_output() {
         let tmp = TempDir::new().unwrap();
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -58,6 +58,43 @@ impl SessionTarget {
     }
 }
 
+/// Upstream outcome that lets a chained job (one with `after`) run.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ChainCondition {
+    #[default]
+    Success,
+    Failure,
+    Always,
+}
+
+impl ChainCondition {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failure => "failure",
+            Self::Always => "always",
+        }
+    }
+
+    pub(crate) fn parse(raw: &str) -> Self {
+        match raw.to_ascii_lowercase().as_str() {
+            "failure" => Self::Failure,
+            "always" => Self::Always,
+            _ => Self::Success,
+        }
+    }
+
+    /// Whether a finished upstream run (`Some(success)`) satisfies this
+    /// condition. Upstream jobs that never ran or were skipped never do.
+    pub(crate) fn is_met(self, upstream: Option<bool>) -> bool {
+        matches!(
+            (self, upstream),
+            (Self::Success, Some(true)) | (Self::Failure, Some(false)) | (Self::Always, Some(_))
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "kind", rename_all = "lowercase")]
 pub enum Schedule {
@@ -115,6 +152,11 @@ pub struct CronJob {
     pub enabled: bool,
     pub delivery: DeliveryConfig,
     pub delete_after_run: bool,
+    /// Tools an agent job may use; `None` leaves the full registry available.
+    pub allowed_tools: Option<Vec<String>>,
+    /// Name (or id) of the job this one is chained after.
+    pub after: Option<String>,
+    pub after_condition: ChainCondition,
     pub created_at: DateTime<Utc>,
     pub next_run: DateTime<Utc>,
     pub last_run: Option<DateTime<Utc>>,
@@ -131,6 +173,29 @@ pub struct CronRun {
     pub status: String,
     pub output: Option<String>,
     pub duration_ms: Option<i64>,
+    /// `schedule` or `manual`.
+    pub invocation: String,
+}
+
+/// Run counts of one job over a time window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CronJobStats {
+    pub job_id: String,
+    pub name: Option<String>,
+    pub runs: u64,
+    /// Runs with status `error`.
+    pub failures: u64,
+    pub avg_duration_ms: Option<f64>,
+}
+
+/// File attached to a cron run, stored content-addressed under the workspace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CronArtifact {
+    pub run_id: i64,
+    pub name: String,
+    pub sha256: String,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -144,11 +209,28 @@ pub struct CronJobPatch {
     pub model: Option<String>,
     pub session_target: Option<SessionTarget>,
     pub delete_after_run: Option<bool>,
+    /// Replace the tool allowlist; an empty list removes the restriction.
+    pub allowed_tools: Option<Vec<String>>,
+    /// Chain after another job by name or id; an empty string unchains.
+    pub after: Option<String>,
+    pub after_condition: Option<ChainCondition>,
+}
+
+/// One-off settings for a manual run; the stored job is left unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunOverrides {
+    /// Run date substituted for `{{date}}` instead of today (UTC).
+    #[serde(default)]
+    pub date: Option<NaiveDate>,
+    #[serde(default)]
+    pub delivery: Option<DeliveryConfig>,
+    #[serde(default)]
+    pub model: Option<String>,
 }
 
 #[cfg(test)]
 mod tests {
-    use super::JobType;
+    use super::{ChainCondition, JobType};
 
     #[test]
     fn job_type_try_from_accepts_known_values_case_insensitive() {
@@ -163,4 +245,15 @@ mod tests {
         assert!(JobType::try_from("").is_err());
         assert!(JobType::try_from("unknown").is_err());
     }
+
+    #[test]
+    fn chain_condition_matches_upstream_outcome() {
+        assert!(ChainCondition::Success.is_met(Some(true)));
+        assert!(!ChainCondition::Success.is_met(Some(false)));
+        assert!(ChainCondition::Failure.is_met(Some(false)));
+        assert!(ChainCondition::Always.is_met(Some(false)));
+        assert!(!ChainCondition::Always.is_met(None));
+        assert_eq!(ChainCondition::parse("FAILURE"), ChainCondition::Failure);
+        assert_eq!(ChainCondition::parse("bogus"), ChainCondition::Success);
+    }
 }
@@ -6,9 +6,17 @@ use std::path::PathBuf;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
 
+mod safe_mode;
+
 const STATUS_FLUSH_SECONDS: u64 = 5;
 
-pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
+pub async fn run(mut config: Config, host: String, port: u16) -> Result<()> {
+    let crashes = safe_mode::record_start(&config);
+    let in_safe_mode = safe_mode::should_enter(&config, crashes);
+    if in_safe_mode {
+        safe_mode::restrict(&mut config);
+    }
+
     let initial_backoff = config.reliability.channel_initial_backoff_secs.max(1);
     let max_backoff = config
         .reliability
@@ -40,7 +48,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         ));
     }
 
-    {
+    if !in_safe_mode {
         if has_supervised_channels(&config) {
             let channels_cfg = config.clone();
             handles.push(spawn_component_supervisor(
@@ -49,7 +57,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 max_backoff,
                 move || {
                     let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
+                    async move { Box::pin(crate::channels::start_channels(cfg)).await }
                 },
             ));
         } else {
@@ -87,13 +95,116 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         tracing::info!("Cron disabled; scheduler supervisor not started");
     }
 
+    if config.publish.enabled {
+        let publish_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "publisher",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = publish_cfg.clone();
+                async move { Box::pin(crate::publish::run(cfg)).await }
+            },
+        ));
+    }
+
+    if config.people.enabled {
+        let people_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "people",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = people_cfg.clone();
+                async move { crate::people::run(cfg).await }
+            },
+        ));
+    }
+
+    if config.printer.enabled && config.printer.channel.is_some() && config.printer.to.is_some() {
+        let printer_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "printer",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = printer_cfg.clone();
+                async move { Box::pin(crate::printer::run(cfg)).await }
+            },
+        ));
+    }
+
+    if config.backup.enabled {
+        let backup_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "backup",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = backup_cfg.clone();
+                async move { Box::pin(crate::backup::run(cfg)).await }
+            },
+        ));
+    }
+
+    if config.sync.primary_url.is_some() {
+        let sync_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "config_sync",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = sync_cfg.clone();
+                async move { crate::config::sync::run(cfg).await }
+            },
+        ));
+    }
+
+    if !config.uptime_kuma.push_jobs.is_empty() {
+        let kuma_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "uptime_kuma",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = kuma_cfg.clone();
+                async move { crate::uptime_kuma::run(cfg).await }
+            },
+        ));
+    }
+
+    {
+        let stable_cfg = config.clone();
+        handles.push(tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(stable_cfg.safe_mode.stable_secs)).await;
+            safe_mode::mark_stable(&stable_cfg);
+        }));
+    }
+
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
-    println!("   Components: gateway, channels, heartbeat, scheduler");
+    if in_safe_mode {
+        let message = format!(
+            "ZeroClaw started in safe mode after {crashes} consecutive crashed starts. \
+             Only the gateway and read-only tools are running; fix the configuration \
+             via the gateway and restart."
+        );
+        println!("   ⚠️  SAFE MODE: {crashes} consecutive crashed starts");
+        println!("   Components: gateway (read-only tools)");
+        tracing::error!("{message}");
+        crate::health::mark_component_error(safe_mode::COMPONENT, &message);
+        let notify_cfg = config.clone();
+        handles.push(tokio::spawn(async move {
+            safe_mode::notify(&notify_cfg, &message).await;
+        }));
+    } else {
+        println!("   Components: gateway, channels, heartbeat, scheduler");
+    }
     println!("   Ctrl+C to stop");
 
     tokio::signal::ctrl_c().await?;
     crate::health::mark_component_error("daemon", "shutdown requested");
+    safe_mode::mark_stable(&config);
 
     for handle in &handles {
         handle.abort();
@@ -196,7 +307,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
         for task in tasks {
             let prompt = format!("[Heartbeat Task] {task}");
             let temp = config.default_temperature;
-            if let Err(e) = crate::agent::run(
+            if let Err(e) = Box::pin(crate::agent::run(
                 config.clone(),
                 Some(prompt),
                 None,
@@ -204,7 +315,8 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
                 temp,
                 vec![],
                 false,
-            )
+                None,
+            ))
             .await
             {
                 crate::health::mark_component_error("heartbeat", e.to_string());
@@ -0,0 +1,168 @@
+//! Crash-loop detection for the daemon.
+//!
+//! `crash_state.json` (next to `daemon_state.json`) records whether the
+//! previous start is still "running". A start that never reached
+//! `[safe_mode] stable_secs` or a clean shutdown — startup error, panic,
+//! OOM kill — leaves the flag set, and the next start counts it as a crash.
+
+use crate::config::Config;
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const COMPONENT: &str = "safe_mode";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CrashState {
+    /// Set at start, cleared once the start is stable or shuts down cleanly.
+    running: bool,
+    consecutive_crashes: u32,
+    last_start: Option<String>,
+}
+
+fn state_path(config: &Config) -> PathBuf {
+    config
+        .config_path
+        .parent()
+        .map_or_else(|| PathBuf::from("."), PathBuf::from)
+        .join("crash_state.json")
+}
+
+fn load(config: &Config) -> CrashState {
+    std::fs::read_to_string(state_path(config))
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save(config: &Config, state: &CrashState) -> Result<()> {
+    let path = state_path(config);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_vec_pretty(state)?)?;
+    Ok(())
+}
+
+/// Record a daemon start and return the number of consecutive crashed
+/// starts before it.
+pub fn record_start(config: &Config) -> u32 {
+    let mut state = load(config);
+    if state.running {
+        state.consecutive_crashes = state.consecutive_crashes.saturating_add(1);
+    }
+    state.running = true;
+    state.last_start = Some(Utc::now().to_rfc3339());
+    if let Err(e) = save(config, &state) {
+        tracing::warn!("Failed to persist crash counter: {e}");
+    }
+    state.consecutive_crashes
+}
+
+/// The current start is healthy: reset the crash counter.
+pub fn mark_stable(config: &Config) {
+    let state = CrashState {
+        running: false,
+        consecutive_crashes: 0,
+        last_start: load(config).last_start,
+    };
+    if let Err(e) = save(config, &state) {
+        tracing::warn!("Failed to reset crash counter: {e}");
+    }
+}
+
+pub fn should_enter(config: &Config, crashes: u32) -> bool {
+    config.safe_mode.enabled && crashes >= config.safe_mode.crash_threshold.max(1)
+}
+
+/// Restrict `config` to the gateway and read-only tools.
+pub fn restrict(config: &mut Config) {
+    config.autonomy.level = crate::security::AutonomyLevel::ReadOnly;
+    config.heartbeat.enabled = false;
+    config.cron.enabled = false;
+    config.publish.enabled = false;
+    config.people.enabled = false;
+    config.printer.enabled = false;
+    config.sync.primary_url = None;
+}
+
+/// Announce safe mode on the first `[safe_mode] notify` destination that
+/// accepts the message.
+pub async fn notify(config: &Config, message: &str) {
+    let destinations: Vec<String> = if config.safe_mode.notify.is_empty() {
+        config
+            .notify
+            .targets
+            .iter()
+            .map(|t| format!("notify:{}", t.name))
+            .collect()
+    } else {
+        config.safe_mode.notify.clone()
+    };
+
+    for destination in destinations {
+        let Some((channel, target)) = destination.split_once(':') else {
+            tracing::warn!(
+                "Ignoring safe_mode.notify entry '{destination}': expected channel:target"
+            );
+            continue;
+        };
+        let result = if channel.eq_ignore_ascii_case("notify") {
+            match crate::notify::find_target(&config.notify, Some(target)) {
+                Ok(t) => {
+                    let mut notification = crate::notify::Notification::new(message);
+                    notification.title = Some("ZeroClaw safe mode".into());
+                    notification.priority = 5;
+                    crate::notify::send(&config.notify, t, &notification).await
+                }
+                Err(e) => Err(e),
+            }
+        } else {
+            crate::cron::scheduler::deliver_announcement(config, channel, target, message).await
+        };
+        match result {
+            Ok(()) => return,
+            Err(e) => tracing::warn!("Safe mode notification via {destination} failed: {e}"),
+        }
+    }
+    tracing::warn!("Safe mode notification was not delivered");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_unfinished_starts_until_stable() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+
+        assert_eq!(record_start(&config), 0);
+        assert_eq!(record_start(&config), 1);
+        assert_eq!(record_start(&config), 2);
+        assert!(!should_enter(&config, 2));
+        assert_eq!(record_start(&config), 3);
+        assert!(should_enter(&config, 3));
+
+        mark_stable(&config);
+        assert_eq!(record_start(&config), 0);
+    }
+
+    #[test]
+    fn restrict_leaves_only_read_only_gateway() {
+        let mut config = Config::default();
+        config.cron.enabled = true;
+        restrict(&mut config);
+        assert_eq!(
+            config.autonomy.level,
+            crate::security::AutonomyLevel::ReadOnly
+        );
+        assert!(!config.cron.enabled);
+        assert!(config.sync.primary_url.is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::config::Config;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use std::io::Write;
 use std::path::Path;
@@ -345,6 +345,28 @@ pub fn run_traces(
     Ok(())
 }
 
+pub fn run_share(
+    config: &Config,
+    options: &crate::observability::share::ShareOptions,
+    output: Option<&Path>,
+) -> Result<()> {
+    let path = crate::observability::runtime_trace::resolve_trace_path(
+        &config.observability,
+        &config.workspace_dir,
+    );
+    let markdown = crate::observability::share::share(&path, options)?;
+    match output {
+        Some(file) => {
+            std::fs::write(file, &markdown)
+                .with_context(|| format!("Failed to write {}", file.display()))?;
+            println!("✅ Wrote redacted transcript to {}", file.display());
+            println!("   Review it before sharing: redaction only catches known secret formats.");
+        }
+        None => print!("{markdown}"),
+    }
+    Ok(())
+}
+
 // ── Config semantic validation ───────────────────────────────────
 
 fn check_config_semantics(config: &Config, items: &mut Vec<DiagItem>) {
@@ -0,0 +1,394 @@
+//! Structured facts with provenance and expiry.
+//!
+//! Data that goes stale (addresses, prices, versions, …) is kept as
+//! `subject / predicate → value` rows in `workspace/facts/facts.db` instead
+//! of free-form memory strings. Each fact records which tool produced it,
+//! how confident that source was and when it stops being trustworthy. Only
+//! unexpired facts about subjects the user actually mentions are injected
+//! into the prompt, so the model sees current values without a growing
+//! memory dump.
+
+use crate::config::{FactsConfig, LocaleConfig};
+use crate::schema_migrations::{migrate, Migration};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+pub(crate) const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "baseline",
+    up: "CREATE TABLE facts (
+            subject     TEXT NOT NULL,
+            predicate   TEXT NOT NULL,
+            value       TEXT NOT NULL,
+            source      TEXT NOT NULL,
+            source_ref  TEXT,
+            confidence  REAL NOT NULL,
+            observed_at TEXT NOT NULL,
+            expires_at  TEXT,
+            PRIMARY KEY (subject, predicate)
+        );
+        CREATE INDEX idx_facts_expires_at ON facts(expires_at);",
+    down: "DROP TABLE facts;",
+}];
+
+/// Expired facts are kept this long so `get` can still show the stale value.
+const EXPIRED_RETENTION_DAYS: i64 = 30;
+/// About a century; longer TTLs are clamped.
+const MAX_TTL_HOURS: u64 = 876_000;
+/// Facts longer than this are cut in the prompt.
+const MAX_INJECTED_VALUE_CHARS: usize = 200;
+
+/// One `subject / predicate → value` fact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fact {
+    pub subject: String,
+    pub predicate: String,
+    pub value: String,
+    /// Tool or flow that produced the value, e.g. `research` or `device_inventory`.
+    pub source: String,
+    /// URL, file or record the value was taken from.
+    pub source_ref: Option<String>,
+    /// 0.0–1.0
+    pub confidence: f64,
+    pub observed_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl Fact {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+
+    /// One-line form with provenance, used by the tool and the prompt.
+    /// Times are shown in the `[locale]` timezone and clock style.
+    pub fn describe(&self, now: DateTime<Utc>, locale: &LocaleConfig) -> String {
+        let mut line = format!(
+            "{} {}: {} (source {}",
+            self.subject, self.predicate, self.value, self.source
+        );
+        if let Some(reference) = &self.source_ref {
+            let _ = write!(line, " {reference}");
+        }
+        let _ = write!(
+            line,
+            ", confidence {:.2}, observed {}",
+            self.confidence,
+            crate::locale::format_datetime(locale, self.observed_at)
+        );
+        match self.expires_at {
+            Some(at) if at <= now => {
+                let _ = write!(
+                    line,
+                    ", EXPIRED {}",
+                    crate::locale::format_datetime(locale, at)
+                );
+            }
+            Some(at) => {
+                let _ = write!(
+                    line,
+                    ", valid until {}",
+                    crate::locale::format_datetime(locale, at)
+                );
+            }
+            None => {}
+        }
+        line.push(')');
+        line
+    }
+}
+
+/// Subjects and predicates are compared case-insensitively with single spaces.
+pub fn normalize(raw: &str) -> String {
+    raw.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Expiry of a fact observed at `observed_at`: `ttl_hours`, else the
+/// configured default; 0 means it never expires.
+pub fn expiry(
+    config: &FactsConfig,
+    ttl_hours: Option<u64>,
+    observed_at: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let hours = ttl_hours
+        .unwrap_or(config.default_ttl_hours)
+        .min(MAX_TTL_HOURS);
+    (hours > 0).then(|| observed_at + Duration::hours(i64::try_from(hours).unwrap_or_default()))
+}
+
+pub fn db_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("facts").join("facts.db")
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let path = db_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let conn = Connection::open(&path)
+        .with_context(|| format!("Failed to open facts DB: {}", path.display()))?;
+    migrate(&conn, "facts", MIGRATIONS)?;
+    f(&conn)
+}
+
+fn parse_time(raw: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(raw).map_or_else(|_| Utc::now(), |t| t.with_timezone(&Utc))
+}
+
+fn row_to_fact(row: &rusqlite::Row<'_>) -> rusqlite::Result<Fact> {
+    Ok(Fact {
+        subject: row.get(0)?,
+        predicate: row.get(1)?,
+        value: row.get(2)?,
+        source: row.get(3)?,
+        source_ref: row.get(4)?,
+        confidence: row.get(5)?,
+        observed_at: parse_time(&row.get::<_, String>(6)?),
+        expires_at: row.get::<_, Option<String>>(7)?.as_deref().map(parse_time),
+    })
+}
+
+const COLUMNS: &str =
+    "subject, predicate, value, source, source_ref, confidence, observed_at, expires_at";
+
+/// Insert or replace the value of `fact.subject / fact.predicate` and
+/// return the value it replaced, if it differed.
+pub fn record(workspace_dir: &Path, fact: &Fact) -> Result<Option<String>> {
+    let subject = normalize(&fact.subject);
+    let predicate = normalize(&fact.predicate);
+    if subject.is_empty() || predicate.is_empty() {
+        bail!("Facts need a non-empty subject and predicate");
+    }
+    if fact.value.trim().is_empty() {
+        bail!("Fact value must not be empty");
+    }
+    if !(0.0..=1.0).contains(&fact.confidence) {
+        bail!("Confidence must be between 0 and 1");
+    }
+
+    with_connection(workspace_dir, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        let previous: Option<String> = tx
+            .query_row(
+                "SELECT value FROM facts WHERE subject = ?1 AND predicate = ?2",
+                params![subject, predicate],
+                |row| row.get(0),
+            )
+            .optional()?;
+        tx.execute(
+            "INSERT INTO facts (subject, predicate, value, source, source_ref, confidence, observed_at, expires_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(subject, predicate) DO UPDATE SET
+                value = excluded.value, source = excluded.source,
+                source_ref = excluded.source_ref, confidence = excluded.confidence,
+                observed_at = excluded.observed_at, expires_at = excluded.expires_at",
+            params![
+                subject,
+                predicate,
+                fact.value.trim(),
+                fact.source.trim(),
+                fact.source_ref.as_deref(),
+                fact.confidence,
+                fact.observed_at.to_rfc3339(),
+                fact.expires_at.map(|at| at.to_rfc3339()),
+            ],
+        )?;
+        let cutoff = fact.observed_at - Duration::days(EXPIRED_RETENTION_DAYS);
+        tx.execute(
+            "DELETE FROM facts WHERE expires_at IS NOT NULL AND expires_at < ?1",
+            params![cutoff.to_rfc3339()],
+        )?;
+        tx.commit()?;
+        Ok(previous.filter(|value| value != fact.value.trim()))
+    })
+}
+
+/// Facts whose subject contains `subject` (all facts when `None`), optionally
+/// narrowed to one predicate. Expired facts are included; callers decide
+/// how to present them.
+pub fn query(
+    workspace_dir: &Path,
+    subject: Option<&str>,
+    predicate: Option<&str>,
+) -> Result<Vec<Fact>> {
+    let pattern = format!("%{}%", subject.map(normalize).unwrap_or_default());
+    let predicate = predicate.map(normalize);
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {COLUMNS} FROM facts
+             WHERE subject LIKE ?1 AND (?2 IS NULL OR predicate = ?2)
+             ORDER BY subject, predicate"
+        ))?;
+        let facts = stmt
+            .query_map(params![pattern, predicate], row_to_fact)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(facts)
+    })
+}
+
+/// Delete the facts of `subject` (or just one predicate of it); returns
+/// how many were removed.
+pub fn forget(workspace_dir: &Path, subject: &str, predicate: Option<&str>) -> Result<usize> {
+    let subject = normalize(subject);
+    let predicate = predicate.map(normalize);
+    with_connection(workspace_dir, |conn| {
+        Ok(conn.execute(
+            "DELETE FROM facts WHERE subject = ?1 AND (?2 IS NULL OR predicate = ?2)",
+            params![subject, predicate],
+        )?)
+    })
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '.' && c != '-')
+        .map(|word| word.trim_matches(|c| c == '.' || c == '-').to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Unexpired facts about subjects mentioned in `message`, most confident
+/// and most recent first. A subject counts as mentioned when every word of
+/// it appears in the message.
+pub fn relevant(
+    facts: Vec<Fact>,
+    message: &str,
+    config: &FactsConfig,
+    now: DateTime<Utc>,
+) -> Vec<Fact> {
+    let mentioned = words(message);
+    let mut matches: Vec<Fact> = facts
+        .into_iter()
+        .filter(|fact| !fact.is_expired(now) && fact.confidence >= config.min_confidence)
+        .filter(|fact| {
+            let subject = words(&fact.subject);
+            !subject.is_empty() && subject.is_subset(&mentioned)
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then(b.observed_at.cmp(&a.observed_at))
+    });
+    matches.truncate(config.max_injected);
+    matches
+}
+
+/// `[Known facts]` block prepended to the user message, or an empty string
+/// when the store is disabled, not created yet or has nothing relevant.
+pub fn prompt_context(
+    workspace_dir: &Path,
+    config: &FactsConfig,
+    locale: &LocaleConfig,
+    message: &str,
+) -> String {
+    if !config.enabled || config.max_injected == 0 || !db_path(workspace_dir).exists() {
+        return String::new();
+    }
+    let facts = match query(workspace_dir, None, None) {
+        Ok(facts) => facts,
+        Err(e) => {
+            tracing::warn!("Failed to load facts: {e}");
+            return String::new();
+        }
+    };
+    let now = Utc::now();
+    let facts = relevant(facts, message, config, now);
+    if facts.is_empty() {
+        return String::new();
+    }
+    let mut context = String::from("[Known facts]\n");
+    for mut fact in facts {
+        if fact.value.chars().count() > MAX_INJECTED_VALUE_CHARS {
+            fact.value = crate::util::truncate_with_ellipsis(&fact.value, MAX_INJECTED_VALUE_CHARS);
+        }
+        let _ = writeln!(context, "- {}", fact.describe(now, locale));
+    }
+    context.push('\n');
+    context
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fact(subject: &str, predicate: &str, value: &str, confidence: f64) -> Fact {
+        Fact {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            value: value.into(),
+            source: "device_inventory".into(),
+            source_ref: None,
+            confidence,
+            observed_at: Utc::now(),
+            expires_at: Some(Utc::now() + Duration::hours(1)),
+        }
+    }
+
+    #[test]
+    fn records_replaces_and_forgets_facts() {
+        let tmp = TempDir::new().unwrap();
+        let ws = tmp.path();
+        assert_eq!(
+            record(ws, &fact("NAS", "ip address", "192.168.1.20", 0.9)).unwrap(),
+            None
+        );
+        assert_eq!(
+            record(ws, &fact("nas", "IP  address", "192.168.1.21", 0.9)).unwrap(),
+            Some("192.168.1.20".into())
+        );
+        record(ws, &fact("nas", "firmware", "7.2", 0.8)).unwrap();
+
+        let facts = query(ws, Some("nas"), Some("ip address")).unwrap();
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].value, "192.168.1.21");
+        assert!(facts[0]
+            .describe(Utc::now(), &LocaleConfig::default())
+            .starts_with("nas ip address: 192.168.1.21 (source device_inventory, confidence 0.90"));
+
+        assert!(record(ws, &fact("nas", "firmware", "7.3", 1.5)).is_err());
+        assert_eq!(forget(ws, "NAS", None).unwrap(), 2);
+        assert!(query(ws, None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn injects_only_current_facts_about_mentioned_subjects() {
+        let config = FactsConfig {
+            enabled: true,
+            ..FactsConfig::default()
+        };
+        let now = Utc::now();
+        let mut expired = fact("nas", "firmware", "7.2", 0.9);
+        expired.expires_at = Some(now - Duration::minutes(1));
+        let facts = vec![
+            fact("nas", "ip address", "192.168.1.20", 0.9),
+            expired,
+            fact("home assistant", "version", "2026.10.1", 0.9),
+            fact("nas", "guess", "maybe", 0.1),
+            fact("bitcoin", "price usd", "61000", 0.7),
+        ];
+
+        let picked = relevant(facts, "Is the NAS reachable?", &config, now);
+        assert_eq!(picked.len(), 1);
+        assert_eq!(picked[0].predicate, "ip address");
+
+        let tmp = TempDir::new().unwrap();
+        let locale = LocaleConfig::default();
+        assert!(prompt_context(tmp.path(), &config, &locale, "nas").is_empty());
+        record(
+            tmp.path(),
+            &fact("home assistant", "version", "2026.10.1", 0.9),
+        )
+        .unwrap();
+        let context = prompt_context(tmp.path(), &config, &locale, "update Home Assistant?");
+        assert!(context.starts_with("[Known facts]\n- home assistant version: 2026.10.1"));
+        assert!(prompt_context(tmp.path(), &config, &locale, "update home?").is_empty());
+    }
+}
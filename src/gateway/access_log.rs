@@ -0,0 +1,392 @@
+//! Gateway access log.
+//!
+//! A route-level middleware records method, matched route, status, latency
+//! and token scope for every gateway request into
+//! `{workspace}/gateway/access_log.db`. Query strings are never stored, and
+//! request bodies are kept only as configured by
+//! `[gateway.access_log] bodies`; bodies larger than 1 MiB, or streamed
+//! without a `Content-Length`, are recorded by size only. Writes share one
+//! connection per database, and old rows are pruned in batches rather than
+//! on every request. This is an operational log; security events still go
+//! to the audit log.
+
+use super::AppState;
+use crate::config::{AccessLogBodies, GatewayAccessLogConfig};
+use anyhow::{Context, Result};
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+
+/// Longest body excerpt stored per entry.
+const MAX_LOGGED_BODY_CHARS: usize = 2000;
+/// Largest body buffered for logging (matches the config PUT limit).
+const MAX_BUFFERED_BODY_BYTES: usize = 1_048_576;
+/// Upper bound for a single query.
+const MAX_QUERY_LIMIT: usize = 1000;
+/// Prune once per this fraction of `max_entries` inserts, so the table
+/// overshoots its cap by at most 10%.
+const PRUNE_BATCH_DIVISOR: usize = 10;
+
+/// Open connections by database path; the schema is created once per path.
+static CONNECTIONS: OnceLock<Mutex<HashMap<PathBuf, Arc<Mutex<Connection>>>>> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AccessLogEntry {
+    pub id: i64,
+    pub timestamp: String,
+    pub method: String,
+    pub route: String,
+    pub status: u16,
+    pub latency_ms: u64,
+    /// `paired`, `rejected`, `anonymous`, or `open` when pairing is disabled.
+    pub token_scope: String,
+    pub body_bytes: u64,
+    pub body: Option<String>,
+}
+
+/// Filters for [`query`].
+#[derive(Debug, Default, Clone)]
+pub struct AccessLogFilter {
+    pub limit: Option<usize>,
+    pub route: Option<String>,
+    pub status: Option<u16>,
+}
+
+fn open(db_path: &Path) -> Result<Connection> {
+    if let Some(dir) = db_path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create gateway directory: {}", dir.display()))?;
+    }
+    let conn = Connection::open(db_path)
+        .with_context(|| format!("Failed to open access log DB: {}", db_path.display()))?;
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+        PRAGMA synchronous = NORMAL;
+        CREATE TABLE IF NOT EXISTS access_log (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp   TEXT NOT NULL,
+            method      TEXT NOT NULL,
+            route       TEXT NOT NULL,
+            status      INTEGER NOT NULL,
+            latency_ms  INTEGER NOT NULL,
+            token_scope TEXT NOT NULL,
+            body_bytes  INTEGER NOT NULL DEFAULT 0,
+            body        TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_access_log_route ON access_log(route);",
+    )
+    .context("Failed to initialize access log schema")?;
+    Ok(conn)
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("gateway").join("access_log.db");
+    let shared = {
+        let mut connections = CONNECTIONS
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock();
+        match connections.get(&db_path) {
+            Some(conn) => Arc::clone(conn),
+            None => {
+                let conn = Arc::new(Mutex::new(open(&db_path)?));
+                connections.insert(db_path, Arc::clone(&conn));
+                conn
+            }
+        }
+    };
+    let conn = shared.lock();
+    f(&conn)
+}
+
+/// Store one entry. Rows beyond `max_entries` are pruned every
+/// `max_entries / 10` inserts instead of on each one.
+pub fn append(workspace_dir: &Path, entry: &AccessLogEntry, max_entries: usize) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO access_log
+                (timestamp, method, route, status, latency_ms, token_scope, body_bytes, body)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                entry.timestamp,
+                entry.method,
+                entry.route,
+                entry.status,
+                i64::try_from(entry.latency_ms).unwrap_or(i64::MAX),
+                entry.token_scope,
+                i64::try_from(entry.body_bytes).unwrap_or(i64::MAX),
+                entry.body,
+            ],
+        )?;
+        let max_entries = max_entries.max(1);
+        let batch = i64::try_from((max_entries / PRUNE_BATCH_DIVISOR).max(1)).unwrap_or(1);
+        if conn.last_insert_rowid() % batch == 0 {
+            let keep = i64::try_from(max_entries).unwrap_or(i64::MAX);
+            conn.execute(
+                "DELETE FROM access_log WHERE id <= (SELECT MAX(id) FROM access_log) - ?1",
+                params![keep],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+/// Most recent entries first.
+pub fn query(workspace_dir: &Path, filter: &AccessLogFilter) -> Result<Vec<AccessLogEntry>> {
+    let limit = filter.limit.unwrap_or(100).clamp(1, MAX_QUERY_LIMIT);
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, timestamp, method, route, status, latency_ms, token_scope, body_bytes, body
+             FROM access_log
+             WHERE (?1 IS NULL OR route = ?1) AND (?2 IS NULL OR status = ?2)
+             ORDER BY id DESC
+             LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                filter.route,
+                filter.status,
+                i64::try_from(limit).unwrap_or(100)
+            ],
+            |row| {
+                Ok(AccessLogEntry {
+                    id: row.get(0)?,
+                    timestamp: row.get(1)?,
+                    method: row.get(2)?,
+                    route: row.get(3)?,
+                    status: row.get(4)?,
+                    latency_ms: u64::try_from(row.get::<_, i64>(5)?).unwrap_or(0),
+                    token_scope: row.get(6)?,
+                    body_bytes: u64::try_from(row.get::<_, i64>(7)?).unwrap_or(0),
+                    body: row.get(8)?,
+                })
+            },
+        )?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    })
+}
+
+fn redact_value(value: &Value) -> Value {
+    match value {
+        Value::String(s) => Value::String(format!("[{} chars]", s.chars().count())),
+        Value::Array(items) => Value::Array(items.iter().map(redact_value).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), redact_value(v)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Body text to store for `mode`, or `None` when nothing should be kept.
+fn logged_body(mode: AccessLogBodies, bytes: &[u8]) -> Option<String> {
+    if bytes.is_empty() {
+        return None;
+    }
+    let text = match mode {
+        AccessLogBodies::Omit => return None,
+        AccessLogBodies::Redacted => match serde_json::from_slice::<Value>(bytes) {
+            Ok(value) => redact_value(&value).to_string(),
+            Err(_) => format!("[{} bytes]", bytes.len()),
+        },
+        AccessLogBodies::Full => {
+            crate::agent::loop_::scrub_credentials(&String::from_utf8_lossy(bytes))
+        }
+    };
+    Some(crate::util::truncate_with_ellipsis(
+        &text,
+        MAX_LOGGED_BODY_CHARS,
+    ))
+}
+
+fn token_scope(state: &AppState, headers: &HeaderMap, uri_query: Option<&str>) -> &'static str {
+    if !state.pairing.require_pairing() {
+        return "open";
+    }
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .or_else(|| {
+            // WebSocket clients authenticate with ?token=
+            uri_query?
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("token="))
+        });
+    match token {
+        Some(token) if state.pairing.is_authenticated(token) => "paired",
+        Some(_) => "rejected",
+        None => "anonymous",
+    }
+}
+
+/// Route-level middleware that records each request.
+pub async fn log_request(
+    State(state): State<AppState>,
+    matched: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (workspace_dir, settings): (_, GatewayAccessLogConfig) = {
+        let config = state.config.lock();
+        (
+            config.workspace_dir.clone(),
+            config.gateway.access_log.clone(),
+        )
+    };
+    let route = matched.map_or_else(
+        || request.uri().path().to_string(),
+        |m| m.as_str().to_string(),
+    );
+    if !settings.enabled || route.starts_with("/_app/") {
+        return next.run(request).await;
+    }
+
+    let method = request.method().to_string();
+    let scope = token_scope(&state, request.headers(), request.uri().query());
+    let declared_size = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    // Only bodies with a known, bounded size are buffered; anything else is
+    // passed through untouched and logged by size so the access log never
+    // changes how a request is handled.
+    let capture = settings.bodies != AccessLogBodies::Omit
+        && declared_size.is_some_and(|size| size <= MAX_BUFFERED_BODY_BYTES as u64);
+    let (request, body_bytes, body) = if capture {
+        let (parts, body) = request.into_parts();
+        let bytes = match axum::body::to_bytes(body, MAX_BUFFERED_BODY_BYTES).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::debug!("Failed to buffer request body for the access log: {e}");
+                return StatusCode::BAD_REQUEST.into_response();
+            }
+        };
+        let logged = logged_body(settings.bodies, &bytes);
+        let size = bytes.len() as u64;
+        (Request::from_parts(parts, Body::from(bytes)), size, logged)
+    } else {
+        (request, declared_size.unwrap_or(0), None)
+    };
+
+    let started = Instant::now();
+    let response = next.run(request).await;
+    let entry = AccessLogEntry {
+        id: 0,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        method,
+        route,
+        status: response.status().as_u16(),
+        latency_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        token_scope: scope.to_string(),
+        body_bytes,
+        body,
+    };
+
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = append(&workspace_dir, &entry, settings.max_entries) {
+            tracing::warn!("Failed to write gateway access log: {e}");
+        }
+    });
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(route: &str, status: u16) -> AccessLogEntry {
+        AccessLogEntry {
+            id: 0,
+            timestamp: "2026-01-01T00:00:00Z".into(),
+            method: "POST".into(),
+            route: route.into(),
+            status,
+            latency_ms: 12,
+            token_scope: "paired".into(),
+            body_bytes: 0,
+            body: None,
+        }
+    }
+
+    #[test]
+    fn append_query_and_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        for status in [200, 401, 200] {
+            append(dir.path(), &entry("/webhook", status), 2).unwrap();
+        }
+        append(dir.path(), &entry("/api/status", 200), 2).unwrap();
+
+        let all = query(dir.path(), &AccessLogFilter::default()).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].route, "/api/status");
+
+        let webhook = query(
+            dir.path(),
+            &AccessLogFilter {
+                route: Some("/webhook".into()),
+                ..AccessLogFilter::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(webhook.len(), 1);
+        assert_eq!(webhook[0].status, 200);
+        assert_eq!(webhook[0].latency_ms, 12);
+
+        let unauthorized = query(
+            dir.path(),
+            &AccessLogFilter {
+                status: Some(401),
+                ..AccessLogFilter::default()
+            },
+        )
+        .unwrap();
+        assert!(unauthorized.is_empty());
+    }
+
+    #[test]
+    fn prunes_in_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        for _ in 0..25 {
+            append(dir.path(), &entry("/webhook", 200), 20).unwrap();
+        }
+        let kept = query(dir.path(), &AccessLogFilter::default()).unwrap();
+        // Pruned to 20 at row 24; row 25 waits for the next batch.
+        assert_eq!(kept.len(), 21);
+    }
+
+    #[test]
+    fn redacted_bodies_keep_structure_only() {
+        let body = br#"{"message":"my door code is 4242","n":3,"tags":["a"]}"#;
+        let logged = logged_body(AccessLogBodies::Redacted, body).unwrap();
+        assert!(!logged.contains("4242"));
+        assert!(logged.contains(r#""message":"[20 chars]""#));
+        assert!(logged.contains(r#""n":3"#));
+
+        assert_eq!(
+            logged_body(AccessLogBodies::Redacted, b"not json").as_deref(),
+            Some("[8 bytes]")
+        );
+        assert!(logged_body(AccessLogBodies::Omit, body).is_none());
+        assert!(logged_body(AccessLogBodies::Full, b"").is_none());
+    }
+
+    #[test]
+    fn full_bodies_scrub_credentials() {
+        let logged = logged_body(AccessLogBodies::Full, b"api_key=sk-1234567890abcdef").unwrap();
+        assert!(!logged.contains("abcdef"));
+    }
+}
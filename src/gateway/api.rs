@@ -9,6 +9,7 @@ use axum::{
     response::{IntoResponse, Json},
 };
 use serde::Deserialize;
+use std::fmt::Write as _;
 
 // ── Bearer token auth extractor ─────────────────────────────────
 
@@ -21,7 +22,7 @@ fn extract_bearer_token(headers: &HeaderMap) -> Option<&str> {
 }
 
 /// Verify bearer token against PairingGuard. Returns error response if unauthorized.
-fn require_auth(
+pub(super) fn require_auth(
     state: &AppState,
     headers: &HeaderMap,
 ) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
@@ -57,6 +58,64 @@ pub struct MemoryStoreBody {
     pub category: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct AccessLogQuery {
+    pub limit: Option<usize>,
+    pub route: Option<String>,
+    pub status: Option<u16>,
+}
+
+#[derive(Deserialize)]
+pub struct CacheQuery {
+    pub name: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SyncBundleQuery {
+    /// Comma-separated `conf.d` sections; defaults to every served section.
+    pub sections: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct TraceShareQuery {
+    pub turns: Option<usize>,
+    /// Turn id (or prefix) the slice starts at; the latest turns when unset.
+    pub from: Option<String>,
+    pub channel: Option<String>,
+    pub max_output_chars: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct AnalyticsQuery {
+    /// `<n>h` or `<n>d`, up to 365 days (default: 7d).
+    pub window: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct FeedbackBody {
+    /// `request_id` from the turn's `started`/`done` frame.
+    pub request_id: String,
+    pub rating: Option<super::feedback::Rating>,
+    pub comment: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct FeedbackQuery {
+    pub rating: Option<super::feedback::Rating>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct CronRunsQuery {
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct FeedbackExportQuery {
+    /// RFC 3339 timestamp; only turns at or after it are exported.
+    pub since: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct CronAddBody {
     pub name: Option<String>,
@@ -286,6 +345,194 @@ pub async fn handle_api_cron_delete(
     }
 }
 
+/// POST /api/cron/:id/run — start a cron job now with optional overrides.
+///
+/// Jobs can outlive the gateway's request timeout, so the run happens in the
+/// background and the response is `202 Accepted`; poll `/api/cron/:id/runs`
+/// for the recorded result.
+pub async fn handle_api_cron_run(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    body: Option<Json<crate::cron::RunOverrides>>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let job = match crate::cron::get_job(&config, &id) {
+        Ok(job) => job,
+        Err(e) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+
+    let overrides = body.map(|Json(overrides)| overrides).unwrap_or_default();
+    let job_id = job.id.clone();
+    tokio::spawn(async move {
+        let run = Box::pin(crate::cron::scheduler::run_job_manually(
+            &config, &job, &overrides,
+        ))
+        .await;
+        if !run.success {
+            tracing::warn!("Manual cron run of {} failed: {}", job.id, run.output);
+        }
+    });
+    (
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "status": "accepted",
+            "job_id": job_id,
+            "invocation": "manual",
+            "runs": format!("/api/cron/{job_id}/runs"),
+        })),
+    )
+        .into_response()
+}
+
+/// GET /api/cron/:id/runs — recent runs of a cron job, newest first
+pub async fn handle_api_cron_runs(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(params): Query<CronRunsQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    if let Err(e) = crate::cron::get_job(&config, &id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response();
+    }
+    match crate::cron::list_runs(&config, &id, params.limit.unwrap_or(20)) {
+        Ok(runs) => Json(serde_json::json!({"runs": runs})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to list cron runs: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/runs/:id/artifacts — list artifacts attached to a cron run
+pub async fn handle_api_run_artifacts_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(run_id): Path<i64>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    match crate::cron::list_run_artifacts(&config, run_id) {
+        Ok(artifacts) => Json(serde_json::json!({"artifacts": artifacts})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to list run artifacts: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/runs/:id/artifacts/:name — download a cron run artifact
+pub async fn handle_api_run_artifact_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((run_id, name)): Path<(i64, String)>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let (artifact, path) = match crate::cron::get_run_artifact(&config, run_id, &name) {
+        Ok(Some(found)) => found,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({"error": "Artifact not found"})),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": format!("Failed to load artifact: {e}")})),
+            )
+                .into_response();
+        }
+    };
+
+    match tokio::fs::read(&path).await {
+        Ok(bytes) => (
+            [
+                (header::CONTENT_TYPE, artifact_content_type(&artifact.name)),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!(
+                        "attachment; filename=\"{}\"",
+                        attachment_filename(&artifact.name)
+                    ),
+                ),
+                (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+                (header::ETAG, format!("\"{}\"", artifact.sha256)),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to read artifact: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// Content types a browser would render as an active document on the
+/// dashboard origin; artifacts are job-controlled, so these are served as
+/// opaque bytes instead.
+const ACTIVE_CONTENT_TYPES: [&str; 5] = [
+    "text/html",
+    "image/svg+xml",
+    "application/xhtml+xml",
+    "text/xml",
+    "application/xml",
+];
+
+/// Content type for an artifact, guessed from its name.
+fn artifact_content_type(name: &str) -> String {
+    let mime = mime_guess::from_path(name).first_or_octet_stream();
+    if ACTIVE_CONTENT_TYPES.contains(&mime.essence_str()) {
+        mime_guess::mime::APPLICATION_OCTET_STREAM.to_string()
+    } else {
+        mime.to_string()
+    }
+}
+
+/// Artifact name made safe for a quoted `Content-Disposition` filename.
+fn attachment_filename(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c == ' ' || (c.is_ascii_graphic() && !matches!(c, '"' | '\\')) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
 /// GET /api/integrations — list all integrations with status
 pub async fn handle_api_integrations(
     State(state): State<AppState>,
@@ -447,6 +694,49 @@ pub async fn handle_api_memory_delete(
     }
 }
 
+/// GET /api/preferences — list recorded user preferences
+pub async fn handle_api_preferences_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    match crate::preferences::load(&workspace_dir) {
+        Ok(preferences) => Json(serde_json::json!({"preferences": preferences})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Preference list failed: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// DELETE /api/preferences/:key — delete a user preference
+pub async fn handle_api_preferences_delete(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(key): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    match crate::preferences::remove(&workspace_dir, &key) {
+        Ok(deleted) => {
+            Json(serde_json::json!({"status": "ok", "deleted": deleted})).into_response()
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("Preference delete failed: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
 /// GET /api/cost — cost summary
 pub async fn handle_api_cost(
     State(state): State<AppState>,
@@ -507,6 +797,355 @@ pub async fn handle_api_health(
     Json(serde_json::json!({"health": snapshot})).into_response()
 }
 
+/// GET /api/traces/share — agent turns from the runtime trace as redacted markdown
+pub async fn handle_api_trace_share(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<TraceShareQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let path = crate::observability::runtime_trace::resolve_trace_path(
+        &config.observability,
+        &config.workspace_dir,
+    );
+    let defaults = crate::observability::share::ShareOptions::default();
+    let options = crate::observability::share::ShareOptions {
+        from_turn: params.from.filter(|f| !f.is_empty()),
+        turns: params.turns.unwrap_or(defaults.turns),
+        channel: params.channel.filter(|c| !c.is_empty()),
+        max_output_chars: params
+            .max_output_chars
+            .unwrap_or(defaults.max_output_chars)
+            .max(200),
+    };
+    match crate::observability::share::share(&path, &options) {
+        Ok(markdown) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/markdown; charset=utf-8")],
+            markdown,
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+/// Cron jobs listed in `/api/analytics`.
+const ANALYTICS_CRON_JOBS: usize = 10;
+
+/// GET /api/analytics — tool, channel, latency and cron aggregates for a time window
+pub async fn handle_api_analytics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<AnalyticsQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let window = match crate::analytics::parse_window(params.window.as_deref().unwrap_or("7d")) {
+        Ok(window) => window,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response()
+        }
+    };
+    let config = state.config.lock().clone();
+    let summary = match crate::analytics::summary(&config.workspace_dir, window) {
+        Ok(summary) => summary,
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": format!("Analytics query failed: {e}")})),
+            )
+                .into_response()
+        }
+    };
+    let cron = crate::cron::busiest_jobs(&config, summary.since, ANALYTICS_CRON_JOBS)
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to aggregate cron runs: {e}");
+            Vec::new()
+        });
+
+    Json(serde_json::json!({
+        "enabled": config.analytics.enabled,
+        "window": params.window.as_deref().unwrap_or("7d"),
+        "summary": summary,
+        "cron": cron,
+    }))
+    .into_response()
+}
+
+/// GET /api/access-log — recent gateway requests
+pub async fn handle_api_access_log(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<AccessLogQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let filter = super::access_log::AccessLogFilter {
+        limit: params.limit,
+        route: params.route.filter(|r| !r.is_empty()),
+        status: params.status,
+    };
+    match super::access_log::query(&workspace_dir, &filter) {
+        Ok(entries) => Json(serde_json::json!({"entries": entries})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to read access log: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+fn feedback_disabled() -> axum::response::Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({"error": "Feedback is disabled ([gateway.feedback] enabled = false)"})),
+    )
+        .into_response()
+}
+
+/// POST /api/feedback — rate or comment on a finished `/api/ws` turn
+pub async fn handle_api_feedback_add(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<FeedbackBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    if !config.gateway.feedback.enabled {
+        return feedback_disabled();
+    }
+    match super::feedback::add(
+        &config.workspace_dir,
+        &body.request_id,
+        body.rating,
+        body.comment.as_deref(),
+    ) {
+        Ok(Some(id)) => (
+            StatusCode::CREATED,
+            Json(serde_json::json!({"status": "ok", "id": id})),
+        )
+            .into_response(),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("Unknown request_id '{}'", body.request_id)})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("Feedback rejected: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/feedback — recent feedback, optionally filtered by rating
+pub async fn handle_api_feedback_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<FeedbackQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    if !config.gateway.feedback.enabled {
+        return feedback_disabled();
+    }
+    match super::feedback::list(&config.workspace_dir, params.rating, params.limit) {
+        Ok(feedback) => Json(serde_json::json!({"feedback": feedback})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to read feedback: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/feedback/export — rated turns as JSONL, one record per turn
+pub async fn handle_api_feedback_export(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<FeedbackExportQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    if !config.gateway.feedback.enabled {
+        return feedback_disabled();
+    }
+    match super::feedback::export(&config.workspace_dir, params.since.as_deref()) {
+        Ok(records) => {
+            let body = records.iter().fold(String::new(), |mut out, r| {
+                let _ = writeln!(out, "{r}");
+                out
+            });
+            (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "application/x-ndjson")],
+                body,
+            )
+                .into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Feedback export failed: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// Caches the admin endpoints know about.
+const CACHE_NAMES: &[&str] = &["response_cache"];
+
+/// Open the LLM response cache only if it already exists on disk.
+fn open_response_cache(config: &crate::config::Config) -> Option<crate::memory::ResponseCache> {
+    let db_path = config
+        .workspace_dir
+        .join("memory")
+        .join("response_cache.db");
+    if !db_path.exists() {
+        return None;
+    }
+    crate::memory::ResponseCache::new(
+        &config.workspace_dir,
+        config.memory.response_cache_ttl_minutes,
+        config.memory.response_cache_max_entries,
+    )
+    .map_err(|e| tracing::warn!("Failed to open response cache: {e}"))
+    .ok()
+}
+
+/// GET /api/admin/caches — cache inventory
+pub async fn handle_api_caches_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let response_cache = match open_response_cache(&config).map(|cache| cache.stats()) {
+        Some(Ok((entries, hits, tokens_saved))) => serde_json::json!({
+            "name": "response_cache",
+            "enabled": config.memory.response_cache_enabled,
+            "entries": entries,
+            "hits": hits,
+            "tokens_saved": tokens_saved,
+        }),
+        Some(Err(e)) => serde_json::json!({
+            "name": "response_cache",
+            "enabled": config.memory.response_cache_enabled,
+            "error": e.to_string(),
+        }),
+        None => serde_json::json!({
+            "name": "response_cache",
+            "enabled": config.memory.response_cache_enabled,
+            "entries": 0,
+        }),
+    };
+
+    Json(serde_json::json!({"caches": [response_cache]})).into_response()
+}
+
+/// DELETE /api/admin/caches?name=<cache> — clear one cache, or all when no name is given
+pub async fn handle_api_caches_clear(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<CacheQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    if let Some(name) = params.name.as_deref() {
+        if !CACHE_NAMES.contains(&name) {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({
+                    "error": format!("Unknown cache '{name}'"),
+                    "caches": CACHE_NAMES,
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    let config = state.config.lock().clone();
+    let cleared = match open_response_cache(&config).map(|cache| cache.clear()) {
+        Some(Ok(count)) => count,
+        Some(Err(e)) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": format!("Failed to clear response_cache: {e}")})),
+            )
+                .into_response();
+        }
+        None => 0,
+    };
+
+    Json(serde_json::json!({
+        "status": "ok",
+        "cleared": {"response_cache": cleared},
+    }))
+    .into_response()
+}
+
+/// GET /api/sync/bundle — `conf.d` files for peers (`[sync] serve = true`)
+pub async fn handle_api_sync_bundle(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<SyncBundleQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    if !config.sync.serve {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "This instance does not serve config sync"})),
+        )
+            .into_response();
+    }
+
+    let sections: Vec<String> = match query.sections.as_deref() {
+        Some(requested) => requested
+            .split(',')
+            .map(str::trim)
+            .filter(|s| config.sync.sections.iter().any(|served| served == s))
+            .map(str::to_string)
+            .collect(),
+        None => config.sync.sections.clone(),
+    };
+    let conf_d = crate::config::sync::conf_d_dir(&config);
+    Json(crate::config::sync::build_bundle(&conf_d, &sections)).into_response()
+}
+
 // ── Helpers ─────────────────────────────────────────────────────
 
 fn mask_sensitive_fields(toml_str: &str) -> String {
@@ -533,3 +1172,28 @@ fn mask_sensitive_fields(toml_str: &str) -> String {
     }
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn active_artifact_types_are_served_as_bytes() {
+        assert_eq!(
+            artifact_content_type("report.html"),
+            "application/octet-stream"
+        );
+        assert_eq!(
+            artifact_content_type("chart.svg"),
+            "application/octet-stream"
+        );
+        assert_eq!(artifact_content_type("data.csv"), "text/csv");
+        assert_eq!(artifact_content_type("plot.png"), "image/png");
+    }
+
+    #[test]
+    fn attachment_filename_strips_quotes_and_non_ascii() {
+        assert_eq!(attachment_filename("a \"b\".txt"), "a _b_.txt");
+        assert_eq!(attachment_filename("résumé\r\n.pdf"), "r_sum___.pdf");
+    }
+}
@@ -0,0 +1,210 @@
+//! Capability report (`GET /api/capabilities`, also logged at startup).
+//!
+//! A machine-readable summary of what this instance exposes: tools with a
+//! hash of each parameter schema, configured channels, providers, cron job
+//! count and the security policy. `digest` hashes everything except the
+//! timestamps, so fleet scripts can compare instances with one string.
+
+use super::api::require_auth;
+use super::AppState;
+use crate::config::Config;
+use crate::tools::ToolSpec;
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::{IntoResponse, Json},
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Serialize)]
+pub struct CapabilityReport {
+    pub version: String,
+    pub generated_at: String,
+    /// SHA-256 over every field below, hex encoded.
+    pub digest: String,
+    #[serde(flatten)]
+    pub capabilities: Capabilities,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Capabilities {
+    pub tools: Vec<ToolCapability>,
+    /// Names of configured channels, sorted.
+    pub channels: Vec<String>,
+    pub providers: ProviderSummary,
+    pub cron_jobs: usize,
+    pub policy: PolicySummary,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToolCapability {
+    pub name: String,
+    /// SHA-256 of the tool's JSON parameter schema, hex encoded.
+    pub schema_hash: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProviderSummary {
+    pub default_provider: Option<String>,
+    pub default_model: Option<String>,
+    pub fallback_providers: Vec<String>,
+    /// `hint → provider/model` routes, sorted by hint.
+    pub model_routes: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct PolicySummary {
+    pub autonomy_level: crate::security::AutonomyLevel,
+    pub workspace_only: bool,
+    pub allowed_commands: usize,
+    pub forbidden_paths: usize,
+    pub max_actions_per_hour: u32,
+    pub max_cost_per_day_cents: u32,
+    pub require_approval_for_medium_risk: bool,
+    pub block_high_risk_commands: bool,
+    pub non_cli_excluded_tools: Vec<String>,
+    pub otp_enabled: bool,
+    pub estop_enabled: bool,
+    pub pairing_required: bool,
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+pub fn build_report(config: &Config, tools: &[ToolSpec]) -> CapabilityReport {
+    let mut tools: Vec<ToolCapability> = tools
+        .iter()
+        .map(|spec| ToolCapability {
+            name: spec.name.clone(),
+            schema_hash: sha256_hex(spec.parameters.to_string().as_bytes()),
+        })
+        .collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut channels: Vec<String> = config
+        .channels_config
+        .channels()
+        .into_iter()
+        .filter(|(_, present)| *present)
+        .map(|(channel, _)| channel.name().to_string())
+        .collect();
+    channels.sort();
+
+    let mut model_routes: Vec<String> = config
+        .model_routes
+        .iter()
+        .map(|r| format!("{} → {}/{}", r.hint, r.provider, r.model))
+        .collect();
+    model_routes.sort();
+
+    let cron_jobs = match crate::cron::list_jobs(config) {
+        Ok(jobs) => jobs.len(),
+        Err(e) => {
+            tracing::warn!("capability report: failed to list cron jobs: {e}");
+            0
+        }
+    };
+
+    let autonomy = &config.autonomy;
+    let capabilities = Capabilities {
+        tools,
+        channels,
+        providers: ProviderSummary {
+            default_provider: config.default_provider.clone(),
+            default_model: config.default_model.clone(),
+            fallback_providers: config.reliability.fallback_providers.clone(),
+            model_routes,
+        },
+        cron_jobs,
+        policy: PolicySummary {
+            autonomy_level: autonomy.level,
+            workspace_only: autonomy.workspace_only,
+            allowed_commands: autonomy.allowed_commands.len(),
+            forbidden_paths: autonomy.forbidden_paths.len(),
+            max_actions_per_hour: autonomy.max_actions_per_hour,
+            max_cost_per_day_cents: autonomy.max_cost_per_day_cents,
+            require_approval_for_medium_risk: autonomy.require_approval_for_medium_risk,
+            block_high_risk_commands: autonomy.block_high_risk_commands,
+            non_cli_excluded_tools: autonomy.non_cli_excluded_tools.clone(),
+            otp_enabled: config.security.otp.enabled,
+            estop_enabled: config.security.estop.enabled,
+            pairing_required: config.gateway.require_pairing,
+        },
+    };
+    let digest = sha256_hex(
+        serde_json::to_string(&capabilities)
+            .unwrap_or_default()
+            .as_bytes(),
+    );
+
+    CapabilityReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        digest,
+        capabilities,
+    }
+}
+
+/// GET /api/capabilities — capability report
+pub async fn handle_api_capabilities(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    Json(build_report(&config, &state.tools_registry)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(name: &str, parameters: serde_json::Value) -> ToolSpec {
+        ToolSpec {
+            name: name.into(),
+            description: String::new(),
+            parameters,
+        }
+    }
+
+    #[test]
+    fn digest_tracks_schema_changes_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        let tools = vec![
+            spec("shell", json!({"type": "object"})),
+            spec("file_read", json!({"type": "object"})),
+        ];
+
+        let first = build_report(&config, &tools);
+        let second = build_report(&config, &tools);
+        assert_eq!(first.digest, second.digest);
+        assert_eq!(first.capabilities.tools[0].name, "file_read");
+
+        let changed = vec![
+            spec("shell", json!({"type": "object", "required": ["command"]})),
+            spec("file_read", json!({"type": "object"})),
+        ];
+        let third = build_report(&config, &changed);
+        assert_ne!(first.digest, third.digest);
+        assert_ne!(
+            first.capabilities.tools[1].schema_hash,
+            third.capabilities.tools[1].schema_hash
+        );
+        assert_eq!(
+            first.capabilities.tools[0].schema_hash,
+            third.capabilities.tools[0].schema_hash
+        );
+    }
+}
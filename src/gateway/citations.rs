@@ -0,0 +1,154 @@
+//! Tool provenance for `/api/ws` answers.
+//!
+//! Each sentence of the final answer is matched against the turn's
+//! successful tool results: a sentence cites every result whose output
+//! contains one of its facts — numbers, `code spans` and "quoted" strings.
+//! Clients can show where a figure came from, and audits can re-run the call
+//! whose arguments hash to `args_hash` and compare.
+
+use serde::Serialize;
+
+/// A successful tool result recorded during the turn.
+#[derive(Debug, Clone, Serialize)]
+pub struct Evidence {
+    pub tool: String,
+    /// SHA-256 of the call's canonical JSON arguments, hex encoded.
+    pub args_hash: String,
+    pub timestamp: String,
+    #[serde(skip)]
+    pub output: String,
+}
+
+/// An answer segment and the tool results that support it.
+#[derive(Debug, Serialize)]
+pub struct Citation<'a> {
+    pub segment: &'a str,
+    /// Byte range of `segment` in the answer.
+    pub start: usize,
+    pub end: usize,
+    pub sources: Vec<&'a Evidence>,
+}
+
+/// Byte ranges of the sentences and lines of `text`, trimmed.
+fn segments(text: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let boundary = match c {
+            '\n' => true,
+            '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+            _ => false,
+        };
+        if boundary {
+            let end = i + c.len_utf8();
+            ranges.push((start, end));
+            start = end;
+        }
+    }
+    ranges.push((start, text.len()));
+
+    ranges
+        .into_iter()
+        .filter_map(|(start, end)| {
+            let raw = &text[start..end];
+            let trimmed = raw.trim();
+            if trimmed.is_empty() {
+                return None;
+            }
+            let offset = start + (raw.len() - raw.trim_start().len());
+            Some((offset, offset + trimmed.len()))
+        })
+        .collect()
+}
+
+/// Facts worth citing: numbers with at least two digits, and the contents
+/// of `code spans` and "quoted" strings.
+fn facts(segment: &str) -> Vec<String> {
+    let mut facts: Vec<String> = segment
+        .split(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | ',' | ':')))
+        .map(|token| token.trim_matches(|c: char| !c.is_ascii_digit()))
+        .filter(|token| token.chars().filter(char::is_ascii_digit).count() >= 2)
+        .map(str::to_string)
+        .collect();
+    for delimiter in ['`', '"'] {
+        facts.extend(
+            segment
+                .split(delimiter)
+                .skip(1)
+                .step_by(2)
+                .map(str::trim)
+                .filter(|quoted| quoted.len() >= 3)
+                .map(str::to_string),
+        );
+    }
+    facts
+}
+
+fn supports(output: &str, fact: &str) -> bool {
+    output.contains(fact) || (fact.contains(',') && output.contains(&fact.replace(',', "")))
+}
+
+/// Citations for every segment of `answer` backed by `evidence`.
+pub fn cite<'a>(answer: &'a str, evidence: &'a [Evidence]) -> Vec<Citation<'a>> {
+    segments(answer)
+        .into_iter()
+        .filter_map(|(start, end)| {
+            let segment = &answer[start..end];
+            let facts = facts(segment);
+            let sources: Vec<&Evidence> = evidence
+                .iter()
+                .filter(|e| facts.iter().any(|fact| supports(&e.output, fact)))
+                .collect();
+            (!sources.is_empty()).then_some(Citation {
+                segment,
+                start,
+                end,
+                sources,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evidence(tool: &str, output: &str) -> Evidence {
+        Evidence {
+            tool: tool.into(),
+            args_hash: format!("{tool}-hash"),
+            timestamp: "2026-01-01T00:00:00Z".into(),
+            output: output.into(),
+        }
+    }
+
+    #[test]
+    fn cites_segments_whose_facts_appear_in_tool_output() {
+        let results = vec![
+            evidence("shell", "Filesystem  Size  Used\n/dev/sda1  100G  87.5G"),
+            evidence(
+                "http_request",
+                "{\"status\": \"degraded\", \"users\": 1234}",
+            ),
+        ];
+        let answer = "The disk holds 87.5 GB of data. The API reports `degraded` for 1,234 users.\nAll good otherwise!";
+
+        let citations = cite(answer, &results);
+        assert_eq!(citations.len(), 2);
+        assert_eq!(citations[0].segment, "The disk holds 87.5 GB of data.");
+        assert_eq!(
+            &answer[citations[0].start..citations[0].end],
+            citations[0].segment
+        );
+        assert_eq!(citations[0].sources[0].tool, "shell");
+        assert_eq!(citations[1].sources.len(), 1);
+        assert_eq!(citations[1].sources[0].tool, "http_request");
+    }
+
+    #[test]
+    fn single_digits_and_plain_words_are_not_cited() {
+        let results = vec![evidence("shell", "3 files changed")];
+        assert!(cite("I changed 3 files.", &results).is_empty());
+    }
+}
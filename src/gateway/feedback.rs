@@ -0,0 +1,362 @@
+//! Response feedback.
+//!
+//! With `[gateway.feedback] enabled`, every completed `/api/ws` turn is
+//! stored in `{workspace}/gateway/feedback.db` with the user message, the
+//! reply and the tool trace, keyed by the turn's `request_id`. Clients
+//! attach thumbs-up/down ratings and free-text comments to a turn with
+//! `POST /api/feedback`; `GET /api/feedback/export` returns the rated turns
+//! as JSONL for prompt tuning and agent-loop regression tests. Turns nobody
+//! rated are pruned after `retention_days`. Messages, replies and tool
+//! output are passed through `scrub_credentials` before they are stored.
+
+use crate::agent::loop_::scrub_credentials;
+use crate::schema_migrations::{migrate, Migration};
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// The baseline adopts databases created before the store was versioned.
+pub(crate) const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "baseline",
+    up: "CREATE TABLE IF NOT EXISTS turns (
+            request_id TEXT PRIMARY KEY,
+            timestamp  TEXT NOT NULL,
+            model      TEXT NOT NULL,
+            message    TEXT NOT NULL,
+            response   TEXT NOT NULL,
+            tool_trace TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_turns_timestamp ON turns(timestamp);
+        CREATE TABLE IF NOT EXISTS feedback (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            request_id TEXT NOT NULL REFERENCES turns(request_id),
+            timestamp  TEXT NOT NULL,
+            rating     INTEGER,
+            comment    TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_feedback_request ON feedback(request_id);",
+    down: "DROP TABLE feedback;
+        DROP TABLE turns;",
+}];
+
+/// Upper bound for a single list query.
+const MAX_QUERY_LIMIT: usize = 1000;
+/// Longest comment stored.
+const MAX_COMMENT_CHARS: usize = 4000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rating {
+    Up,
+    Down,
+}
+
+impl Rating {
+    fn to_db(self) -> i64 {
+        match self {
+            Self::Up => 1,
+            Self::Down => -1,
+        }
+    }
+
+    fn from_db(value: Option<i64>) -> Option<Self> {
+        match value? {
+            1 => Some(Self::Up),
+            -1 => Some(Self::Down),
+            _ => None,
+        }
+    }
+}
+
+/// One completed agent turn.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Turn {
+    pub request_id: String,
+    pub timestamp: String,
+    pub model: String,
+    pub message: String,
+    pub response: String,
+    /// `{"calls": [...], "evidence": [...], "degradations": [...]}`
+    pub tool_trace: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FeedbackEntry {
+    pub id: i64,
+    pub request_id: String,
+    pub timestamp: String,
+    pub rating: Option<Rating>,
+    pub comment: Option<String>,
+}
+
+pub fn db_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("gateway").join("feedback.db")
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let path = db_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let conn = Connection::open(&path)
+        .with_context(|| format!("Failed to open feedback DB: {}", path.display()))?;
+    migrate(&conn, "feedback", MIGRATIONS)?;
+    f(&conn)
+}
+
+/// `value` with every string passed through `scrub_credentials`.
+fn scrub_value(value: &Value) -> Value {
+    match value {
+        Value::String(text) => Value::String(scrub_credentials(text)),
+        Value::Array(items) => Value::Array(items.iter().map(scrub_value).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, item)| (key.clone(), scrub_value(item)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Prune unrated turns older than `retention_days`, then store a turn so it
+/// can be rated. Credentials in the message, reply and tool trace are
+/// redacted first.
+pub fn record_turn(workspace_dir: &Path, turn: &Turn, retention_days: u32) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(retention_days));
+        conn.execute(
+            "DELETE FROM turns WHERE timestamp < ?1
+             AND request_id NOT IN (SELECT request_id FROM feedback)",
+            params![cutoff.to_rfc3339()],
+        )?;
+        conn.execute(
+            "INSERT OR REPLACE INTO turns
+                (request_id, timestamp, model, message, response, tool_trace)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                turn.request_id,
+                turn.timestamp,
+                turn.model,
+                scrub_credentials(&turn.message),
+                scrub_credentials(&turn.response),
+                scrub_value(&turn.tool_trace).to_string(),
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+/// Attach feedback to a stored turn. Returns the feedback id, or `None`
+/// when the turn is unknown (never stored, or already pruned).
+pub fn add(
+    workspace_dir: &Path,
+    request_id: &str,
+    rating: Option<Rating>,
+    comment: Option<&str>,
+) -> Result<Option<i64>> {
+    let comment = comment.map(str::trim).filter(|c| !c.is_empty());
+    if rating.is_none() && comment.is_none() {
+        bail!("Feedback needs a rating or a comment");
+    }
+    if comment.is_some_and(|c| c.chars().count() > MAX_COMMENT_CHARS) {
+        bail!("Comment is longer than {MAX_COMMENT_CHARS} characters");
+    }
+    with_connection(workspace_dir, |conn| {
+        let known = conn
+            .query_row(
+                "SELECT 1 FROM turns WHERE request_id = ?1",
+                params![request_id],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        if !known {
+            return Ok(None);
+        }
+        conn.execute(
+            "INSERT INTO feedback (request_id, timestamp, rating, comment) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                request_id,
+                chrono::Utc::now().to_rfc3339(),
+                rating.map(Rating::to_db),
+                comment,
+            ],
+        )?;
+        Ok(Some(conn.last_insert_rowid()))
+    })
+}
+
+/// Most recent feedback first.
+pub fn list(
+    workspace_dir: &Path,
+    rating: Option<Rating>,
+    limit: Option<usize>,
+) -> Result<Vec<FeedbackEntry>> {
+    let limit = limit.unwrap_or(100).clamp(1, MAX_QUERY_LIMIT);
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, request_id, timestamp, rating, comment FROM feedback
+             WHERE ?1 IS NULL OR rating = ?1
+             ORDER BY id DESC
+             LIMIT ?2",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                rating.map(Rating::to_db),
+                i64::try_from(limit).unwrap_or(100)
+            ],
+            |row| {
+                Ok(FeedbackEntry {
+                    id: row.get(0)?,
+                    request_id: row.get(1)?,
+                    timestamp: row.get(2)?,
+                    rating: Rating::from_db(row.get(3)?),
+                    comment: row.get(4)?,
+                })
+            },
+        )?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    })
+}
+
+/// Every turn with feedback (optionally only turns at or after `since`,
+/// RFC 3339) as one dataset record each, oldest first.
+pub fn export(workspace_dir: &Path, since: Option<&str>) -> Result<Vec<Value>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT t.request_id, t.timestamp, t.model, t.message, t.response, t.tool_trace,
+                    f.timestamp, f.rating, f.comment
+             FROM turns t JOIN feedback f ON f.request_id = t.request_id
+             WHERE ?1 IS NULL OR t.timestamp >= ?1
+             ORDER BY t.timestamp, t.request_id, f.id",
+        )?;
+        let mut rows = stmt.query(params![since])?;
+        let mut records: Vec<Value> = Vec::new();
+        while let Some(row) = rows.next()? {
+            let request_id: String = row.get(0)?;
+            let feedback = json!({
+                "timestamp": row.get::<_, String>(6)?,
+                "rating": Rating::from_db(row.get(7)?),
+                "comment": row.get::<_, Option<String>>(8)?,
+            });
+            if let Some(last) = records
+                .last_mut()
+                .filter(|r| r["request_id"] == request_id.as_str())
+            {
+                if let Some(items) = last["feedback"].as_array_mut() {
+                    items.push(feedback);
+                }
+                continue;
+            }
+            let tool_trace: String = row.get(5)?;
+            records.push(json!({
+                "request_id": request_id,
+                "timestamp": row.get::<_, String>(1)?,
+                "model": row.get::<_, String>(2)?,
+                "messages": [
+                    {"role": "user", "content": row.get::<_, String>(3)?},
+                    {"role": "assistant", "content": row.get::<_, String>(4)?},
+                ],
+                "tool_trace": serde_json::from_str::<Value>(&tool_trace).unwrap_or(Value::Null),
+                "feedback": [feedback],
+            }));
+        }
+        Ok(records)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn turn(request_id: &str, timestamp: &str) -> Turn {
+        Turn {
+            request_id: request_id.into(),
+            timestamp: timestamp.into(),
+            model: "test-model".into(),
+            message: "How warm is it?".into(),
+            response: "It is 21 °C.".into(),
+            tool_trace: json!({"calls": [{"name": "weather", "success": true, "duration_ms": 12}]}),
+        }
+    }
+
+    #[test]
+    fn rates_turns_and_exports_dataset() {
+        let tmp = TempDir::new().unwrap();
+        let ws = tmp.path();
+        let now = chrono::Utc::now().to_rfc3339();
+        record_turn(ws, &turn("r1", &now), 30).unwrap();
+        record_turn(ws, &turn("r2", &now), 30).unwrap();
+
+        assert!(add(ws, "r1", Some(Rating::Down), Some("Wrong unit"))
+            .unwrap()
+            .is_some());
+        assert!(add(ws, "r1", None, Some("Asked for Fahrenheit"))
+            .unwrap()
+            .is_some());
+        assert_eq!(add(ws, "missing", Some(Rating::Up), None).unwrap(), None);
+        assert!(add(ws, "r2", None, Some("   ")).is_err());
+
+        let down = list(ws, Some(Rating::Down), None).unwrap();
+        assert_eq!(down.len(), 1);
+        assert_eq!(down[0].comment.as_deref(), Some("Wrong unit"));
+        assert_eq!(list(ws, None, None).unwrap().len(), 2);
+
+        let records = export(ws, None).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["request_id"], "r1");
+        assert_eq!(records[0]["messages"][1]["content"], "It is 21 °C.");
+        assert_eq!(records[0]["tool_trace"]["calls"][0]["name"], "weather");
+        assert_eq!(records[0]["feedback"][0]["rating"], "down");
+        assert_eq!(records[0]["feedback"][1]["rating"], Value::Null);
+    }
+
+    #[test]
+    fn prunes_only_unrated_old_turns() {
+        let tmp = TempDir::new().unwrap();
+        let ws = tmp.path();
+        let old = (chrono::Utc::now() - chrono::Duration::days(40)).to_rfc3339();
+        record_turn(ws, &turn("old-rated", &old), 30).unwrap();
+        add(ws, "old-rated", Some(Rating::Up), None).unwrap();
+        record_turn(ws, &turn("old-unrated", &old), 30).unwrap();
+        record_turn(ws, &turn("new", &chrono::Utc::now().to_rfc3339()), 30).unwrap();
+
+        assert_eq!(
+            add(ws, "old-unrated", Some(Rating::Up), None).unwrap(),
+            None
+        );
+        assert!(add(ws, "old-rated", Some(Rating::Up), None)
+            .unwrap()
+            .is_some());
+        assert!(export(ws, Some(&chrono::Utc::now().to_rfc3339()))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn scrubs_credentials_before_storing() {
+        let tmp = TempDir::new().unwrap();
+        let ws = tmp.path();
+        let mut secret = turn("r1", &chrono::Utc::now().to_rfc3339());
+        secret.message = "Use api_key=sk-live-1234567890 for the call".into();
+        secret.response = "Done with token: abcd1234efgh".into();
+        secret.tool_trace = json!({"evidence": [{"output": "password=hunter2hunter2"}]});
+        record_turn(ws, &secret, 30).unwrap();
+        add(ws, "r1", Some(Rating::Up), None).unwrap();
+
+        let stored = export(ws, None).unwrap().remove(0).to_string();
+        assert!(!stored.contains("sk-live-1234567890"));
+        assert!(!stored.contains("abcd1234efgh"));
+        assert!(!stored.contains("hunter2hunter2"));
+        assert!(stored.contains("[REDACTED]"));
+
+        let conn = Connection::open(db_path(ws)).unwrap();
+        let applied = crate::schema_migrations::applied(&conn).unwrap();
+        assert_eq!(applied.len(), MIGRATIONS.len());
+    }
+}
@@ -7,9 +7,14 @@
 //! - Request timeouts (30s) to prevent slow-loris attacks
 //! - Header sanitization (handled by axum/hyper)
 
+pub mod access_log;
 pub mod api;
+pub mod capabilities;
+pub mod citations;
+pub mod feedback;
 pub mod sse;
 pub mod static_files;
+pub mod status_page;
 pub mod ws;
 
 use crate::channels::{Channel, LinqChannel, NextcloudTalkChannel, SendMessage, WhatsAppChannel};
@@ -568,8 +573,26 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     }
     println!("  GET  /api/*     — REST API (bearer token required)");
     println!("  GET  /ws/chat   — WebSocket agent chat");
+    println!("  GET  /api/ws    — WebSocket API (agent with tools, streaming, notifications)");
     println!("  GET  /health    — health check");
+    if config.gateway.public_status_page {
+        println!("  GET  /status    — status page (public)");
+    } else {
+        println!("  GET  /status    — status page (bearer token required)");
+    }
     println!("  GET  /metrics   — Prometheus metrics");
+    let report = capabilities::build_report(&config, &tools_registry);
+    println!(
+        "  🧭 Capabilities: {} tools, {} channels, {} cron jobs (digest {})",
+        report.capabilities.tools.len(),
+        report.capabilities.channels.len(),
+        report.capabilities.cron_jobs,
+        &report.digest[..12]
+    );
+    match serde_json::to_string(&report) {
+        Ok(json) => tracing::info!(target: "capabilities", "{json}"),
+        Err(e) => tracing::warn!("Failed to serialize capability report: {e}"),
+    }
     if let Some(code) = pairing.pairing_code() {
         println!();
         println!("  🔐 PAIRING REQUIRED — use this one-time code:");
@@ -638,29 +661,69 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/whatsapp", post(handle_whatsapp_message))
         .route("/linq", post(handle_linq_webhook))
         .route("/nextcloud-talk", post(handle_nextcloud_talk_webhook))
+        .route("/hooks/location", post(handle_location_hook))
+        .route("/status", get(status_page::handle_status_page))
         // ── Web Dashboard API routes ──
         .route("/api/status", get(api::handle_api_status))
         .route("/api/config", get(api::handle_api_config_get))
         .route("/api/tools", get(api::handle_api_tools))
+        .route(
+            "/api/capabilities",
+            get(capabilities::handle_api_capabilities),
+        )
         .route("/api/cron", get(api::handle_api_cron_list))
         .route("/api/cron", post(api::handle_api_cron_add))
         .route("/api/cron/{id}", delete(api::handle_api_cron_delete))
+        .route("/api/cron/{id}/run", post(api::handle_api_cron_run))
+        .route("/api/cron/{id}/runs", get(api::handle_api_cron_runs))
+        .route(
+            "/api/runs/{id}/artifacts",
+            get(api::handle_api_run_artifacts_list),
+        )
+        .route(
+            "/api/runs/{id}/artifacts/{name}",
+            get(api::handle_api_run_artifact_get),
+        )
         .route("/api/integrations", get(api::handle_api_integrations))
         .route("/api/doctor", post(api::handle_api_doctor))
         .route("/api/memory", get(api::handle_api_memory_list))
         .route("/api/memory", post(api::handle_api_memory_store))
         .route("/api/memory/{key}", delete(api::handle_api_memory_delete))
+        .route("/api/preferences", get(api::handle_api_preferences_list))
+        .route(
+            "/api/preferences/{key}",
+            delete(api::handle_api_preferences_delete),
+        )
         .route("/api/cost", get(api::handle_api_cost))
         .route("/api/cli-tools", get(api::handle_api_cli_tools))
         .route("/api/health", get(api::handle_api_health))
+        .route("/api/access-log", get(api::handle_api_access_log))
+        .route("/api/analytics", get(api::handle_api_analytics))
+        .route("/api/traces/share", get(api::handle_api_trace_share))
+        .route("/api/feedback", get(api::handle_api_feedback_list))
+        .route("/api/feedback", post(api::handle_api_feedback_add))
+        .route("/api/feedback/export", get(api::handle_api_feedback_export))
+        .route("/api/admin/caches", get(api::handle_api_caches_list))
+        .route("/api/admin/caches", delete(api::handle_api_caches_clear))
+        .route("/api/sync/bundle", get(api::handle_api_sync_bundle))
         // ── SSE event stream ──
         .route("/api/events", get(sse::handle_sse_events))
         // ── WebSocket agent chat ──
         .route("/ws/chat", get(ws::handle_ws_chat))
+        .route("/api/ws", get(ws::handle_ws_api))
+        .route(
+            "/api/chat/{request_id}/abort",
+            post(ws::handle_api_chat_abort),
+        )
         // ── Static assets (web dashboard) ──
         .route("/_app/{*path}", get(static_files::handle_static))
         // ── Config PUT with larger body limit ──
         .merge(config_put_router)
+        // ── Access log (route-level so the matched route is known) ──
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            access_log::log_request,
+        ))
         .with_state(state)
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
@@ -804,14 +867,16 @@ async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Res
     // workspace-aware system context before model invocation.
     let system_prompt = {
         let config_guard = state.config.lock();
-        crate::channels::build_system_prompt(
+        let base_prompt = crate::channels::build_system_prompt(
             &config_guard.workspace_dir,
             &state.model,
             &[], // tools - empty for simple chat
             &[], // skills
             Some(&config_guard.identity),
             None, // bootstrap_max_chars - use default
-        )
+        ) + &crate::locale::prompt_section(&config_guard.locale);
+        let base_prompt = crate::locale::with_current_datetime(&config_guard.locale, &base_prompt);
+        crate::preferences::apply_to_prompt(&config_guard.workspace_dir, &base_prompt)
     };
 
     let mut messages = Vec::with_capacity(1 + user_messages.len());
@@ -1395,6 +1460,149 @@ async fn handle_nextcloud_talk_webhook(
     (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
 }
 
+/// Decode `Authorization: Basic` into (user, password).
+fn basic_auth_credentials(headers: &HeaderMap) -> Option<(String, String)> {
+    use base64::Engine;
+
+    let encoded = headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Basic ")?;
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (user, password) = decoded.split_once(':')?;
+    Some((user.to_string(), password.to_string()))
+}
+
+/// POST /hooks/location — OwnTracks (HTTP mode) or plain JSON location reports
+async fn handle_location_hook(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let (location, workspace_dir) = {
+        let config = state.config.lock();
+        (config.location.clone(), config.workspace_dir.clone())
+    };
+    if !location.enabled {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Location ingestion not enabled"})),
+        );
+    }
+
+    let rate_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&rate_key) {
+        tracing::warn!("/hooks/location rate limit exceeded");
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(serde_json::json!({
+                "error": "Too many location reports. Please retry later.",
+                "retry_after": RATE_LIMIT_WINDOW_SECS,
+            })),
+        );
+    }
+
+    // ── Auth: shared location secret, else the pairing bearer token ──
+    let basic = basic_auth_credentials(&headers);
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("");
+    let authorized = if let Some(secret) = location.secret.as_deref() {
+        let presented = headers
+            .get("X-Location-Secret")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+            .or_else(|| basic.as_ref().map(|(_, password)| password.clone()))
+            .unwrap_or_else(|| bearer.to_string());
+        !presented.is_empty() && constant_time_eq(presented.trim(), secret)
+    } else {
+        !state.pairing.require_pairing() || state.pairing.is_authenticated(bearer)
+    };
+    if !authorized {
+        tracing::warn!("Location hook: rejected — invalid or missing credentials");
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Unauthorized"})),
+        );
+    }
+
+    let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid JSON payload"})),
+        );
+    };
+    let person_hint = headers
+        .get("X-Limit-U")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+        .or_else(|| basic.map(|(user, _)| user));
+
+    let parsed = match crate::location::parse_payload(&payload, person_hint.as_deref()) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e})),
+            );
+        }
+    };
+
+    let result = tokio::task::spawn_blocking(move || match parsed {
+        crate::location::LocationPayload::Location(update) => {
+            crate::location::record_update(&workspace_dir, &location.geofences, update)
+        }
+        crate::location::LocationPayload::Transition(event) => {
+            crate::location::record_transition(&workspace_dir, &event).map(|()| vec![event])
+        }
+        crate::location::LocationPayload::Ignored(_) => Ok(Vec::new()),
+    })
+    .await
+    .map_err(anyhow::Error::from)
+    .and_then(|r| r);
+
+    match result {
+        Ok(events) => {
+            for event in events {
+                tracing::info!(
+                    "Geofence: {} {} {}",
+                    event.person,
+                    if event.event == "enter" {
+                        "entered"
+                    } else {
+                        "left"
+                    },
+                    event.region
+                );
+                let _ = state.event_tx.send(serde_json::json!({
+                    "type": "geofence",
+                    "person": event.person,
+                    "region": event.region,
+                    "event": event.event,
+                    "timestamp": event.timestamp.to_rfc3339(),
+                }));
+            }
+            // OwnTracks expects a JSON array (of optional commands) in reply.
+            (StatusCode::OK, Json(serde_json::json!([])))
+        }
+        Err(e) => {
+            tracing::error!("Failed to record location: {e:#}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": "Failed to record location"})),
+            )
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2642,4 +2850,22 @@ mod tests {
         // Should be allowed again
         assert!(limiter.allow("burst-ip"));
     }
+
+    #[test]
+    fn basic_auth_credentials_decodes_owntracks_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Basic YWxpY2U6czNjcjN0Ong="),
+        );
+        let (user, password) = basic_auth_credentials(&headers).unwrap();
+        assert_eq!(user, "alice");
+        assert_eq!(password, "s3cr3t:x");
+
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer abc"),
+        );
+        assert!(basic_auth_credentials(&headers).is_none());
+    }
 }
@@ -98,6 +98,15 @@ impl crate::observability::Observer for BroadcastObserver {
                 "success": success,
                 "timestamp": chrono::Utc::now().to_rfc3339(),
             }),
+            crate::observability::ObserverEvent::ToolDegraded { tool, kind, reason } => {
+                serde_json::json!({
+                    "type": "tool_degraded",
+                    "tool": tool,
+                    "kind": kind,
+                    "reason": reason,
+                    "timestamp": chrono::Utc::now().to_rfc3339(),
+                })
+            }
             crate::observability::ObserverEvent::ToolCallStart { tool } => serde_json::json!({
                 "type": "tool_call_start",
                 "tool": tool,
@@ -0,0 +1,269 @@
+//! Read-only status page (`GET /status`).
+//!
+//! Summarizes agent health and the latest cron results as HTML, or as JSON
+//! for `?format=json` / `Accept: application/json`. Bearer auth applies unless
+//! `[gateway] public_status_page = true`. Only statuses and timestamps are
+//! shown; error messages, commands and job output stay behind the API.
+
+use super::api::require_auth;
+use super::AppState;
+use crate::tools::report::html_escape;
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap},
+    response::{Html, IntoResponse, Json},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+#[derive(Deserialize)]
+pub struct StatusPageQuery {
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    /// `ok` when every component and the last run of every enabled job succeeded.
+    pub overall: String,
+    pub uptime_seconds: u64,
+    pub generated_at: String,
+    pub components: Vec<ComponentStatus>,
+    pub jobs: Vec<JobStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComponentStatus {
+    pub name: String,
+    pub status: String,
+    pub last_ok: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobStatus {
+    pub name: String,
+    pub enabled: bool,
+    pub last_run: Option<String>,
+    pub last_status: Option<String>,
+    pub next_run: String,
+}
+
+fn is_failure(status: &str) -> bool {
+    matches!(status, "error" | "failed")
+}
+
+fn build_report(config: &crate::config::Config) -> StatusReport {
+    let health = crate::health::snapshot();
+    let components: Vec<ComponentStatus> = health
+        .components
+        .into_iter()
+        .map(|(name, c)| ComponentStatus {
+            name,
+            status: c.status,
+            last_ok: c.last_ok,
+        })
+        .collect();
+
+    let jobs: Vec<JobStatus> = match crate::cron::list_jobs(config) {
+        Ok(jobs) => jobs
+            .into_iter()
+            .map(|job| JobStatus {
+                name: job.name.unwrap_or(job.id),
+                enabled: job.enabled,
+                last_run: job.last_run.map(|t| t.to_rfc3339()),
+                last_status: job.last_status,
+                next_run: job.next_run.to_rfc3339(),
+            })
+            .collect(),
+        Err(e) => {
+            tracing::warn!("status page: failed to list cron jobs: {e}");
+            Vec::new()
+        }
+    };
+
+    let degraded = components.iter().any(|c| is_failure(&c.status))
+        || jobs
+            .iter()
+            .any(|j| j.enabled && j.last_status.as_deref().is_some_and(is_failure));
+
+    StatusReport {
+        overall: if degraded { "degraded" } else { "ok" }.into(),
+        uptime_seconds: health.uptime_seconds,
+        generated_at: health.updated_at,
+        components,
+        jobs,
+    }
+}
+
+fn format_uptime(secs: u64) -> String {
+    let (days, hours, mins) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {mins}m")
+    } else {
+        format!("{mins}m")
+    }
+}
+
+fn status_class(status: &str) -> &'static str {
+    if is_failure(status) {
+        "bad"
+    } else {
+        "good"
+    }
+}
+
+fn render_html(report: &StatusReport) -> String {
+    let mut html = String::from(
+        "<!doctype html><html><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <meta http-equiv=\"refresh\" content=\"60\"><title>ZeroClaw status</title>\
+         <style>body{font-family:system-ui,sans-serif;max-width:48rem;margin:2rem auto;padding:0 1rem}\
+         table{border-collapse:collapse;width:100%;margin-bottom:2rem}\
+         td,th{text-align:left;padding:.4rem;border-bottom:1px solid #ddd}\
+         .good{color:#1a7f37}.bad{color:#cf222e}</style></head><body>",
+    );
+    let _ = write!(
+        html,
+        "<h1 class=\"{}\">{}</h1><p>Up {} · updated {}</p>",
+        status_class(&report.overall),
+        if report.overall == "ok" {
+            "All systems normal"
+        } else {
+            "Something needs attention"
+        },
+        format_uptime(report.uptime_seconds),
+        html_escape(&report.generated_at)
+    );
+
+    html.push_str(
+        "<h2>Components</h2><table><tr><th>Component</th><th>Status</th><th>Last OK</th></tr>",
+    );
+    for c in &report.components {
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td class=\"{}\">{}</td><td>{}</td></tr>",
+            html_escape(&c.name),
+            status_class(&c.status),
+            html_escape(&c.status),
+            html_escape(c.last_ok.as_deref().unwrap_or("—"))
+        );
+    }
+    html.push_str("</table>");
+
+    html.push_str(
+        "<h2>Scheduled jobs</h2><table><tr><th>Job</th><th>Last run</th><th>Result</th><th>Next run</th></tr>",
+    );
+    for j in &report.jobs {
+        let result = if j.enabled {
+            j.last_status.as_deref().unwrap_or("—")
+        } else {
+            "paused"
+        };
+        let _ = write!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td class=\"{}\">{}</td><td>{}</td></tr>",
+            html_escape(&j.name),
+            html_escape(j.last_run.as_deref().unwrap_or("never")),
+            status_class(j.last_status.as_deref().unwrap_or("ok")),
+            html_escape(result),
+            html_escape(&j.next_run)
+        );
+    }
+    html.push_str("</table></body></html>");
+    html
+}
+
+fn wants_json(query: &StatusPageQuery, headers: &HeaderMap) -> bool {
+    if let Some(format) = query.format.as_deref() {
+        return format.eq_ignore_ascii_case("json");
+    }
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("application/json") && !accept.contains("text/html"))
+}
+
+/// GET /status — household-friendly health summary
+pub async fn handle_status_page(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<StatusPageQuery>,
+) -> impl IntoResponse {
+    let config = state.config.lock().clone();
+    if !config.gateway.public_status_page {
+        if let Err(e) = require_auth(&state, &headers) {
+            return e.into_response();
+        }
+    }
+
+    let report = build_report(&config);
+    if wants_json(&query, &headers) {
+        Json(report).into_response()
+    } else {
+        Html(render_html(&report)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_report() -> StatusReport {
+        StatusReport {
+            overall: "degraded".into(),
+            uptime_seconds: 93_600,
+            generated_at: "2026-01-01T00:00:00Z".into(),
+            components: vec![ComponentStatus {
+                name: "channel:<telegram>".into(),
+                status: "error".into(),
+                last_ok: None,
+            }],
+            jobs: vec![JobStatus {
+                name: "backup".into(),
+                enabled: true,
+                last_run: Some("2026-01-01T00:00:00Z".into()),
+                last_status: Some("ok".into()),
+                next_run: "2026-01-02T00:00:00Z".into(),
+            }],
+        }
+    }
+
+    #[test]
+    fn html_escapes_names_and_flags_failures() {
+        let html = render_html(&sample_report());
+        assert!(html.contains("Something needs attention"));
+        assert!(html.contains("channel:&lt;telegram&gt;"));
+        assert!(html.contains("<td class=\"bad\">error</td>"));
+        assert!(html.contains("backup"));
+        assert!(html.contains("Up 1d 2h"));
+    }
+
+    #[test]
+    fn json_requested_by_query_or_accept_header() {
+        let mut headers = HeaderMap::new();
+        let json = StatusPageQuery {
+            format: Some("JSON".into()),
+        };
+        let none = StatusPageQuery { format: None };
+        assert!(wants_json(&json, &headers));
+        assert!(!wants_json(&none, &headers));
+
+        headers.insert(header::ACCEPT, "application/json".parse().unwrap());
+        assert!(wants_json(&none, &headers));
+        headers.insert(
+            header::ACCEPT,
+            "text/html,application/json".parse().unwrap(),
+        );
+        assert!(!wants_json(&none, &headers));
+    }
+
+    #[test]
+    fn failure_statuses() {
+        assert!(!is_failure("ok"));
+        assert!(!is_failure("starting"));
+        assert!(!is_failure("deferred"));
+        assert!(is_failure("error"));
+        assert!(is_failure("failed"));
+    }
+}
@@ -1,6 +1,7 @@
-//! WebSocket agent chat handler.
+//! WebSocket handlers: `/ws/chat` (single-turn chat) and `/api/ws`
+//! (full agent with tools, streaming and server-initiated notifications).
 //!
-//! Protocol:
+//! `/ws/chat` protocol:
 //! ```text
 //! Client -> Server: {"type":"message","content":"Hello"}
 //! Server -> Client: {"type":"chunk","content":"Hi! "}
@@ -8,17 +9,137 @@
 //! Server -> Client: {"type":"tool_result","name":"shell","output":"..."}
 //! Server -> Client: {"type":"done","full_response":"..."}
 //! ```
+//!
+//! `/api/ws` protocol (`id` is echoed back when the client sends one):
+//! ```text
+//! Server -> Client: {"type":"ready","model":"..."}
+//! Client -> Server: {"type":"message","id":"1","content":"Hello"}
+//! Server -> Client: {"type":"started","id":"1","request_id":"<uuid>"}
+//! Server -> Client: {"type":"progress","id":"1","content":"🤔 Thinking...\n"}
+//! Server -> Client: {"type":"tool_call","id":"1","name":"shell"}
+//! Server -> Client: {"type":"tool_result","id":"1","name":"shell","success":true,"duration_ms":12}
+//! Server -> Client: {"type":"chunk","id":"1","content":"Hi! "}
+//! Server -> Client: {"type":"done","id":"1","request_id":"<uuid>","full_response":"...","degradations":[],"citations":[]}
+//! Server -> Client: {"type":"notification","kind":"cron_result","data":{...}}
+//! Client -> Server: {"type":"ping"}  ->  {"type":"pong"}
+//! Client -> Server: {"type":"cancel"}
+//! Server -> Client: {"type":"cancelled","id":"1","request_id":"<uuid>","partial_output":"...","completed_tools":[...]}
+//! ```
+//!
+//! `cancel`, closing the socket, or `POST /api/chat/{request_id}/abort`
+//! stops the running turn's provider calls and tool executions. With
+//! `[gateway.feedback]` enabled, the `request_id` of a finished turn can be
+//! rated with `POST /api/feedback`.
 
+use super::api::require_auth;
+use super::citations::{self, Evidence};
+use super::feedback;
 use super::AppState;
 use axum::{
     extract::{
         ws::{Message, WebSocket},
-        Query, State, WebSocketUpgrade,
+        Path, Query, State, WebSocketUpgrade,
     },
-    response::IntoResponse,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json},
 };
 use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{broadcast, mpsc};
+use tokio_util::sync::CancellationToken;
+
+/// Process-wide fan-out for server-initiated `/api/ws` notifications.
+static NOTIFICATIONS: OnceLock<broadcast::Sender<Value>> = OnceLock::new();
+
+fn notifications() -> &'static broadcast::Sender<Value> {
+    NOTIFICATIONS.get_or_init(|| broadcast::channel(64).0)
+}
+
+/// Running `/api/ws` turns by `request_id`, for `POST /api/chat/{id}/abort`.
+static IN_FLIGHT: OnceLock<parking_lot::Mutex<HashMap<String, TurnHandle>>> = OnceLock::new();
+
+fn in_flight() -> &'static parking_lot::Mutex<HashMap<String, TurnHandle>> {
+    IN_FLIGHT.get_or_init(Default::default)
+}
+
+/// Cancellation and progress of one running `/api/ws` turn.
+#[derive(Clone, Default)]
+struct TurnHandle {
+    cancel: CancellationToken,
+    /// Reply chunks streamed so far.
+    partial_output: Arc<parking_lot::Mutex<String>>,
+    /// `{name, success, duration_ms}` of each finished tool call.
+    completed_tools: Arc<parking_lot::Mutex<Vec<Value>>>,
+}
+
+impl TurnHandle {
+    fn report(&self, request_id: &str) -> Value {
+        json!({
+            "request_id": request_id,
+            "partial_output": self.partial_output.lock().clone(),
+            "completed_tools": self.completed_tools.lock().clone(),
+        })
+    }
+}
+
+/// Keeps a turn in [`in_flight`] until dropped.
+struct InFlightEntry(String);
+
+impl InFlightEntry {
+    fn register(request_id: &str, handle: &TurnHandle) -> Self {
+        in_flight()
+            .lock()
+            .insert(request_id.to_string(), handle.clone());
+        Self(request_id.to_string())
+    }
+}
+
+impl Drop for InFlightEntry {
+    fn drop(&mut self) {
+        in_flight().lock().remove(&self.0);
+    }
+}
+
+/// Cancel the running turn `request_id` and report its progress so far.
+fn abort_turn(request_id: &str) -> Option<Value> {
+    let handle = in_flight().lock().get(request_id).cloned()?;
+    handle.cancel.cancel();
+    Some(handle.report(request_id))
+}
+
+/// POST /api/chat/{request_id}/abort — cancel a running `/api/ws` turn
+pub async fn handle_api_chat_abort(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(request_id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    match abort_turn(&request_id) {
+        Some(report) => Json(report).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": format!("No running request '{request_id}'")})),
+        )
+            .into_response(),
+    }
+}
+
+/// Push a notification (e.g. `"cron_result"`, `"alert"`) to every connected
+/// `/api/ws` client. A no-op when nobody is connected.
+pub fn publish_notification(kind: &str, data: Value) {
+    let _ = notifications().send(json!({
+        "type": "notification",
+        "kind": kind,
+        "data": data,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    }));
+}
 
 #[derive(Deserialize)]
 pub struct WsQuery {
@@ -96,14 +217,17 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
         // Simple single-turn chat (no streaming for now — use provider.chat_with_system)
         let system_prompt = {
             let config_guard = state.config.lock();
-            crate::channels::build_system_prompt(
+            let base_prompt = crate::channels::build_system_prompt(
                 &config_guard.workspace_dir,
                 &state.model,
                 &[],
                 &[],
                 Some(&config_guard.identity),
                 None,
-            )
+            ) + &crate::locale::prompt_section(&config_guard.locale);
+            let base_prompt =
+                crate::locale::with_current_datetime(&config_guard.locale, &base_prompt);
+            crate::preferences::apply_to_prompt(&config_guard.workspace_dir, &base_prompt)
         };
 
         let messages = vec![
@@ -165,3 +289,484 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
         }
     }
 }
+
+/// GET /api/ws — WebSocket upgrade for bidirectional API clients.
+/// Accepts `Authorization: Bearer <token>` or `?token=<token>`.
+pub async fn handle_ws_api(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<WsQuery>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    if state.pairing.require_pairing() {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|auth| auth.strip_prefix("Bearer "))
+            .or(params.token.as_deref())
+            .unwrap_or("");
+        if !state.pairing.is_authenticated(token) {
+            return (
+                axum::http::StatusCode::UNAUTHORIZED,
+                "Unauthorized — provide Authorization: Bearer <token> or ?token=<token>",
+            )
+                .into_response();
+        }
+    }
+
+    ws.on_upgrade(move |socket| handle_api_socket(socket, state))
+        .into_response()
+}
+
+async fn handle_api_socket(socket: WebSocket, state: AppState) {
+    let (mut sender, mut receiver) = socket.split();
+    let (out_tx, mut out_rx) = mpsc::channel::<Value>(64);
+
+    // Single writer so chat turns and notifications never interleave mid-frame.
+    let writer = tokio::spawn(async move {
+        while let Some(frame) = out_rx.recv().await {
+            if sender
+                .send(Message::Text(frame.to_string().into()))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    let mut notes = notifications().subscribe();
+    let note_tx = out_tx.clone();
+    let notifier = tokio::spawn(async move {
+        loop {
+            match notes.recv().await {
+                Ok(frame) => {
+                    if note_tx.send(frame).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+
+    let _ = out_tx
+        .send(json!({"type": "ready", "model": state.model}))
+        .await;
+
+    // Tools with per-conversation state (python_repl) key it by socket.
+    let conversation = format!("ws-{}", uuid::Uuid::new_v4().simple());
+
+    while let Some(msg) = receiver.next().await {
+        let text = match msg {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => break,
+            _ => continue,
+        };
+
+        let Ok(frame) = serde_json::from_str::<Value>(&text) else {
+            let _ = out_tx
+                .send(json!({"type": "error", "message": "Invalid JSON"}))
+                .await;
+            continue;
+        };
+        let id = frame.get("id").cloned().unwrap_or(Value::Null);
+
+        match frame["type"].as_str().unwrap_or("") {
+            "ping" => {
+                let _ = out_tx.send(json!({"type": "pong", "id": id})).await;
+            }
+            "message" => {
+                let content = frame["content"].as_str().unwrap_or("").trim();
+                if content.is_empty() {
+                    let _ = out_tx
+                        .send(json!({"type": "error", "id": id, "message": "content is required"}))
+                        .await;
+                    continue;
+                }
+                let handle = TurnHandle::default();
+                let turn = crate::tools::CONVERSATION_ID.scope(
+                    conversation.clone(),
+                    run_api_turn(&state, content, id, &out_tx, handle.clone()),
+                );
+                tokio::pin!(turn);
+                // Keep reading while the turn runs so a cancel frame or a
+                // dropped connection stops it instead of letting it finish
+                // unseen.
+                let mut disconnected = false;
+                loop {
+                    tokio::select! {
+                        () = &mut turn => break,
+                        msg = receiver.next(), if !disconnected => match msg {
+                            Some(Ok(Message::Text(text))) => {
+                                handle_busy_frame(&text, &handle.cancel, &out_tx).await;
+                            }
+                            Some(Ok(Message::Close(_)) | Err(_)) | None => {
+                                handle.cancel.cancel();
+                                disconnected = true;
+                            }
+                            Some(Ok(_)) => {}
+                        },
+                    }
+                }
+                if disconnected {
+                    break;
+                }
+            }
+            "cancel" => {
+                let _ = out_tx
+                    .send(json!({"type": "error", "id": id, "message": "No turn is running"}))
+                    .await;
+            }
+            other => {
+                let _ = out_tx
+                    .send(json!({
+                        "type": "error",
+                        "id": id,
+                        "message": format!("Unknown frame type '{other}'"),
+                    }))
+                    .await;
+            }
+        }
+    }
+
+    notifier.abort();
+    drop(out_tx);
+    let _ = writer.await;
+}
+
+/// Handle a frame that arrives while a turn is running: `cancel` stops the
+/// turn, `ping` is answered, and new messages are refused.
+async fn handle_busy_frame(text: &str, cancel: &CancellationToken, out: &mpsc::Sender<Value>) {
+    let Ok(frame) = serde_json::from_str::<Value>(text) else {
+        let _ = out
+            .send(json!({"type": "error", "message": "Invalid JSON"}))
+            .await;
+        return;
+    };
+    let id = frame.get("id").cloned().unwrap_or(Value::Null);
+    let reply = match frame["type"].as_str().unwrap_or("") {
+        "cancel" => {
+            cancel.cancel();
+            return;
+        }
+        "ping" => json!({"type": "pong", "id": id}),
+        "message" => json!({
+            "type": "error",
+            "id": id,
+            "message": "A turn is already running; send {\"type\":\"cancel\"} to stop it",
+        }),
+        other => json!({
+            "type": "error",
+            "id": id,
+            "message": format!("Unknown frame type '{other}'"),
+        }),
+    };
+    let _ = out.send(reply).await;
+}
+
+/// Run one agent turn, streaming progress, tool events and reply chunks.
+/// Cancelling `handle.cancel` stops pending provider calls and tool executions.
+async fn run_api_turn(
+    state: &AppState,
+    content: &str,
+    id: Value,
+    out: &mpsc::Sender<Value>,
+    handle: TurnHandle,
+) {
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let _entry = InFlightEntry::register(&request_id, &handle);
+    let _ = out
+        .send(json!({"type": "started", "id": id, "request_id": request_id}))
+        .await;
+
+    let config = state.config.lock().clone();
+    let feedback_config = config.gateway.feedback.clone();
+    let workspace_dir = config.workspace_dir.clone();
+    let (delta_tx, mut delta_rx) = mpsc::channel::<String>(64);
+
+    let relay_out = out.clone();
+    let relay_id = id.clone();
+    let partial_output = Arc::clone(&handle.partial_output);
+    let relay = tokio::spawn(async move {
+        // Progress lines come first; the clear sentinel marks the start of the reply.
+        let mut answering = false;
+        while let Some(delta) = delta_rx.recv().await {
+            if delta == crate::agent::loop_::DRAFT_CLEAR_SENTINEL {
+                answering = true;
+                continue;
+            }
+            let kind = if answering {
+                partial_output.lock().push_str(&delta);
+                "chunk"
+            } else {
+                "progress"
+            };
+            let _ = relay_out
+                .send(json!({"type": kind, "id": relay_id, "content": delta}))
+                .await;
+        }
+    });
+
+    let degradations = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let evidence = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let observer = Box::new(ToolEventObserver {
+        id: id.clone(),
+        tx: out.clone(),
+        degradations: Arc::clone(&degradations),
+        completed_tools: Arc::clone(&handle.completed_tools),
+        evidence: Arc::clone(&evidence),
+    });
+    let result = Box::pin(crate::agent::process_message_streaming(
+        config,
+        content,
+        Some(delta_tx),
+        Some(observer),
+        Some(handle.cancel.clone()),
+    ))
+    .await;
+    let _ = relay.await;
+
+    let frame = match result {
+        Err(_) if handle.cancel.is_cancelled() => {
+            let mut frame = handle.report(&request_id);
+            frame["type"] = json!("cancelled");
+            frame["id"] = id;
+            frame
+        }
+        // Tools that failed or were skipped; the answer may rest on partial data.
+        Ok(response) => {
+            let evidence = std::mem::take(&mut *evidence.lock());
+            let degradations = std::mem::take(&mut *degradations.lock());
+            if feedback_config.enabled {
+                let turn = feedback::Turn {
+                    request_id: request_id.clone(),
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    model: state.model.clone(),
+                    message: content.to_string(),
+                    response: response.clone(),
+                    tool_trace: json!({
+                        "calls": handle.completed_tools.lock().clone(),
+                        "evidence": evidence
+                            .iter()
+                            .map(|e| json!({
+                                "tool": e.tool,
+                                "args_hash": e.args_hash,
+                                "timestamp": e.timestamp,
+                                "output": e.output,
+                            }))
+                            .collect::<Vec<_>>(),
+                        "degradations": degradations,
+                    }),
+                };
+                tokio::task::spawn_blocking(move || {
+                    if let Err(e) =
+                        feedback::record_turn(&workspace_dir, &turn, feedback_config.retention_days)
+                    {
+                        tracing::warn!("Failed to store turn for feedback: {e}");
+                    }
+                });
+            }
+            json!({
+                "type": "done",
+                "id": id,
+                "request_id": request_id,
+                "full_response": response,
+                "degradations": degradations,
+                "citations": citations::cite(&response, &evidence),
+            })
+        }
+        Err(e) => {
+            let sanitized = crate::providers::sanitize_api_error(&e.to_string());
+            let _ = state.event_tx.send(json!({
+                "type": "error",
+                "component": "ws_api",
+                "message": sanitized,
+            }));
+            json!({"type": "error", "id": id, "message": sanitized})
+        }
+    };
+    let _ = out.send(frame).await;
+}
+
+/// Forwards tool lifecycle events of one `/api/ws` turn to its socket and
+/// collects degraded tool results and citation evidence for the `done`
+/// frame, and finished tool calls for abort reports.
+struct ToolEventObserver {
+    id: Value,
+    tx: mpsc::Sender<Value>,
+    degradations: Arc<parking_lot::Mutex<Vec<Value>>>,
+    completed_tools: Arc<parking_lot::Mutex<Vec<Value>>>,
+    evidence: Arc<parking_lot::Mutex<Vec<Evidence>>>,
+}
+
+impl crate::observability::Observer for ToolEventObserver {
+    fn record_event(&self, event: &crate::observability::ObserverEvent) {
+        let frame = match event {
+            crate::observability::ObserverEvent::ToolCallStart { tool } => {
+                json!({"type": "tool_call", "id": self.id, "name": tool})
+            }
+            crate::observability::ObserverEvent::ToolCall {
+                tool,
+                duration,
+                success,
+            } => {
+                self.completed_tools.lock().push(json!({
+                    "name": tool,
+                    "success": success,
+                    "duration_ms": duration.as_millis(),
+                }));
+                json!({
+                    "type": "tool_result",
+                    "id": self.id,
+                    "name": tool,
+                    "success": success,
+                    "duration_ms": duration.as_millis(),
+                })
+            }
+            crate::observability::ObserverEvent::ToolDegraded { tool, kind, reason } => {
+                self.degradations
+                    .lock()
+                    .push(json!({"tool": tool, "kind": kind, "reason": reason}));
+                return;
+            }
+            crate::observability::ObserverEvent::ToolEvidence {
+                tool,
+                args_hash,
+                output,
+            } => {
+                self.evidence.lock().push(Evidence {
+                    tool: tool.clone(),
+                    args_hash: args_hash.clone(),
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                    output: output.clone(),
+                });
+                return;
+            }
+            _ => return,
+        };
+        // Observers run on the hot path; drop the frame rather than block.
+        let _ = self.tx.try_send(frame);
+    }
+
+    fn record_metric(&self, _metric: &crate::observability::traits::ObserverMetric) {}
+
+    fn name(&self) -> &str {
+        "ws_api"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::{Observer, ObserverEvent};
+    use std::time::Duration;
+
+    #[test]
+    fn tool_event_observer_emits_call_and_result_frames() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let observer = ToolEventObserver {
+            id: json!("42"),
+            tx,
+            degradations: Arc::default(),
+            completed_tools: Arc::default(),
+            evidence: Arc::default(),
+        };
+        observer.record_event(&ObserverEvent::ToolCallStart {
+            tool: "shell".into(),
+        });
+        observer.record_event(&ObserverEvent::ToolCall {
+            tool: "shell".into(),
+            duration: Duration::from_millis(15),
+            success: true,
+        });
+
+        let start = rx.try_recv().unwrap();
+        assert_eq!(start["type"], "tool_call");
+        assert_eq!(start["id"], "42");
+        let done = rx.try_recv().unwrap();
+        assert_eq!(done["type"], "tool_result");
+        assert_eq!(done["duration_ms"], 15);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn tool_event_observer_collects_degradations() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let degradations = Arc::default();
+        let observer = ToolEventObserver {
+            id: Value::Null,
+            tx,
+            degradations: Arc::clone(&degradations),
+            completed_tools: Arc::default(),
+            evidence: Arc::default(),
+        };
+        observer.record_event(&ObserverEvent::ToolDegraded {
+            tool: "web_search".into(),
+            kind: "rate_limited".into(),
+            reason: "Rate limit exceeded".into(),
+        });
+
+        assert!(rx.try_recv().is_err());
+        let collected = degradations.lock();
+        assert_eq!(collected.len(), 1);
+        assert_eq!(collected[0]["kind"], "rate_limited");
+    }
+
+    #[tokio::test]
+    async fn busy_frames_cancel_or_refuse_during_a_turn() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let cancel = CancellationToken::new();
+
+        handle_busy_frame(
+            r#"{"type":"message","id":"2","content":"hi"}"#,
+            &cancel,
+            &tx,
+        )
+        .await;
+        let refused = rx.try_recv().unwrap();
+        assert_eq!(refused["type"], "error");
+        assert_eq!(refused["id"], "2");
+        handle_busy_frame(r#"{"type":"ping"}"#, &cancel, &tx).await;
+        assert_eq!(rx.try_recv().unwrap()["type"], "pong");
+        assert!(!cancel.is_cancelled());
+
+        handle_busy_frame(r#"{"type":"cancel"}"#, &cancel, &tx).await;
+        assert!(cancel.is_cancelled());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn abort_cancels_registered_turn_and_reports_progress() {
+        let handle = TurnHandle::default();
+        handle.partial_output.lock().push_str("Partial ");
+        handle
+            .completed_tools
+            .lock()
+            .push(json!({"name": "shell", "success": true, "duration_ms": 5}));
+
+        {
+            let _entry = InFlightEntry::register("req-1", &handle);
+            let report = abort_turn("req-1").unwrap();
+            assert!(handle.cancel.is_cancelled());
+            assert_eq!(report["partial_output"], "Partial ");
+            assert_eq!(report["completed_tools"][0]["name"], "shell");
+        }
+        assert!(abort_turn("req-1").is_none());
+    }
+
+    #[test]
+    fn published_notifications_reach_subscribers() {
+        let mut rx = notifications().subscribe();
+        publish_notification("alert", json!({"message": "disk full"}));
+        let frame = rx.try_recv().unwrap();
+        assert_eq!(frame["type"], "notification");
+        assert_eq!(frame["kind"], "alert");
+        assert_eq!(frame["data"]["message"], "disk full");
+    }
+}
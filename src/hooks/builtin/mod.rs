@@ -1,3 +1,34 @@
 pub mod command_logger;
+#[cfg(feature = "scripting")]
+pub mod script;
 
 pub use command_logger::CommandLoggerHook;
+#[cfg(feature = "scripting")]
+pub use script::ScriptHook;
+
+use crate::config::Config;
+use crate::hooks::HookRunner;
+
+/// Register `[[hooks.scripts]]` entries. Scripts that fail to load are
+/// skipped with a warning.
+#[cfg(feature = "scripting")]
+pub fn register_script_hooks(runner: &mut HookRunner, config: &Config) {
+    for script in &config.hooks.scripts {
+        match ScriptHook::load(&config.workspace_dir, script) {
+            Ok(hook) => runner.register(Box::new(hook)),
+            Err(e) => tracing::warn!("Skipping script hook {}: {e:#}", script.path),
+        }
+    }
+}
+
+/// Register `[[hooks.scripts]]` entries. Scripts that fail to load are
+/// skipped with a warning.
+#[cfg(not(feature = "scripting"))]
+pub fn register_script_hooks(_runner: &mut HookRunner, config: &Config) {
+    for script in &config.hooks.scripts {
+        tracing::warn!(
+            "Skipping script hook {}: built without the `scripting` feature",
+            script.path
+        );
+    }
+}
@@ -0,0 +1,368 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use rhai::{Dynamic, Engine, Scope, AST};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::channels::traits::ChannelMessage;
+use crate::config::ScriptHookConfig;
+use crate::hooks::traits::{HookHandler, HookResult};
+use crate::tools::traits::ToolResult;
+
+/// Upper bound on Rhai operations per hook call, so a runaway loop in a
+/// script cannot stall the channel.
+const MAX_OPERATIONS: u64 = 200_000;
+
+/// A Rhai script registered as a lifecycle hook.
+///
+/// The script defines any of `on_message_received(channel, sender, content)`,
+/// `on_message_sending(channel, recipient, content)`,
+/// `before_tool_call(name, args)` and `on_after_tool_call(tool, success, output)`.
+/// Modifying hooks return `()` to pass through, `false` or `#{cancel: "why"}`
+/// to cancel, or a replacement value (content string, or args map).
+///
+/// Scripts run sandboxed: no `import`, no `eval`, bounded operations, and no
+/// file or network access. The only side effects are `log(msg)`,
+/// `kv_get(key)` / `kv_set(key, value)` (a per-script key-value store in
+/// `{workspace}/state/scripts.db`) and `publish(kind, data)` (a `/api/ws`
+/// notification).
+pub struct ScriptHook {
+    name: String,
+    priority: i32,
+    engine: Engine,
+    ast: AST,
+    functions: HashSet<String>,
+}
+
+enum Verdict {
+    Pass,
+    Cancel(String),
+    Replace(Dynamic),
+}
+
+impl ScriptHook {
+    pub fn load(workspace_dir: &Path, config: &ScriptHookConfig) -> Result<Self> {
+        let path = if Path::new(&config.path).is_absolute() {
+            PathBuf::from(&config.path)
+        } else {
+            workspace_dir.join(&config.path)
+        };
+        let source = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read script {}", path.display()))?;
+        let name = format!(
+            "script:{}",
+            path.file_stem()
+                .map_or_else(|| config.path.clone(), |s| s.to_string_lossy().into_owned())
+        );
+
+        let engine = sandboxed_engine(&name, workspace_dir.join("state").join("scripts.db"));
+        let ast = engine
+            .compile(&source)
+            .with_context(|| format!("Failed to compile script {}", path.display()))?;
+        let functions = ast.iter_functions().map(|f| f.name.to_string()).collect();
+
+        Ok(Self {
+            name,
+            priority: config.priority,
+            engine,
+            ast,
+            functions,
+        })
+    }
+
+    /// Call `function` if the script defines it. Script errors are logged and
+    /// treated as a pass-through so a broken script cannot block traffic.
+    fn call(&self, function: &str, args: impl rhai::FuncArgs) -> Option<Dynamic> {
+        if !self.functions.contains(function) {
+            return None;
+        }
+        match self
+            .engine
+            .call_fn::<Dynamic>(&mut Scope::new(), &self.ast, function, args)
+        {
+            Ok(value) => Some(value),
+            Err(e) => {
+                tracing::warn!(hook = %self.name, "{function} failed: {e}");
+                None
+            }
+        }
+    }
+
+    fn verdict(&self, value: Option<Dynamic>) -> Verdict {
+        let Some(value) = value else {
+            return Verdict::Pass;
+        };
+        if value.is_unit() || value.as_bool() == Ok(true) {
+            return Verdict::Pass;
+        }
+        if value.as_bool() == Ok(false) {
+            return Verdict::Cancel(format!("blocked by {}", self.name));
+        }
+        if let Some(map) = value.read_lock::<rhai::Map>() {
+            if let Some(reason) = map.get("cancel") {
+                return Verdict::Cancel(reason.to_string());
+            }
+        }
+        Verdict::Replace(value)
+    }
+}
+
+fn sandboxed_engine(name: &str, db_path: PathBuf) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+    engine.disable_symbol("eval");
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_string_size(1 << 20);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+
+    let hook = name.to_string();
+    engine.on_print(move |msg| tracing::info!(hook = %hook, "{msg}"));
+    let hook = name.to_string();
+    engine.register_fn(
+        "log",
+        move |msg: &str| tracing::info!(hook = %hook, "{msg}"),
+    );
+
+    let (hook, path) = (name.to_string(), db_path.clone());
+    engine.register_fn("kv_get", move |key: &str| -> Dynamic {
+        match kv_get(&path, &hook, key) {
+            Ok(Some(value)) => value.into(),
+            Ok(None) => Dynamic::UNIT,
+            Err(e) => {
+                tracing::warn!(hook = %hook, "kv_get failed: {e}");
+                Dynamic::UNIT
+            }
+        }
+    });
+    let (hook, path) = (name.to_string(), db_path);
+    engine.register_fn("kv_set", move |key: &str, value: Dynamic| {
+        if let Err(e) = kv_set(&path, &hook, key, &value.to_string()) {
+            tracing::warn!(hook = %hook, "kv_set failed: {e}");
+        }
+    });
+
+    let hook = name.to_string();
+    engine.register_fn("publish", move |kind: &str, data: Dynamic| {
+        let data: Value = rhai::serde::from_dynamic(&data).unwrap_or(Value::Null);
+        crate::gateway::ws::publish_notification(
+            "script",
+            serde_json::json!({ "hook": hook, "kind": kind, "data": data }),
+        );
+    });
+
+    engine
+}
+
+fn open_store(path: &Path) -> Result<Connection> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let conn = Connection::open(path)
+        .with_context(|| format!("Failed to open script store: {}", path.display()))?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS script_kv (
+            script TEXT NOT NULL,
+            key    TEXT NOT NULL,
+            value  TEXT NOT NULL,
+            PRIMARY KEY (script, key)
+        );",
+    )?;
+    Ok(conn)
+}
+
+fn kv_get(path: &Path, script: &str, key: &str) -> Result<Option<String>> {
+    let conn = open_store(path)?;
+    Ok(conn
+        .query_row(
+            "SELECT value FROM script_kv WHERE script = ?1 AND key = ?2",
+            params![script, key],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+fn kv_set(path: &Path, script: &str, key: &str, value: &str) -> Result<()> {
+    let conn = open_store(path)?;
+    conn.execute(
+        "INSERT INTO script_kv (script, key, value) VALUES (?1, ?2, ?3)
+         ON CONFLICT(script, key) DO UPDATE SET value = excluded.value",
+        params![script, key, value],
+    )?;
+    Ok(())
+}
+
+#[async_trait]
+impl HookHandler for ScriptHook {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    async fn on_after_tool_call(&self, tool: &str, result: &ToolResult, _duration: Duration) {
+        self.call(
+            "on_after_tool_call",
+            (tool.to_string(), result.success, result.output.clone()),
+        );
+    }
+
+    async fn before_tool_call(&self, name: String, args: Value) -> HookResult<(String, Value)> {
+        let Ok(dyn_args) = rhai::serde::to_dynamic(&args) else {
+            return HookResult::Continue((name, args));
+        };
+        match self.verdict(self.call("before_tool_call", (name.clone(), dyn_args))) {
+            Verdict::Pass => HookResult::Continue((name, args)),
+            Verdict::Cancel(reason) => HookResult::Cancel(reason),
+            Verdict::Replace(value) => match rhai::serde::from_dynamic::<Value>(&value) {
+                Ok(new_args) if new_args.is_object() => HookResult::Continue((name, new_args)),
+                _ => {
+                    tracing::warn!(hook = %self.name, "before_tool_call must return a map");
+                    HookResult::Continue((name, args))
+                }
+            },
+        }
+    }
+
+    async fn on_message_received(&self, mut message: ChannelMessage) -> HookResult<ChannelMessage> {
+        let args = (
+            message.channel.clone(),
+            message.sender.clone(),
+            message.content.clone(),
+        );
+        match self.verdict(self.call("on_message_received", args)) {
+            Verdict::Pass => HookResult::Continue(message),
+            Verdict::Cancel(reason) => HookResult::Cancel(reason),
+            Verdict::Replace(value) => {
+                message.content = value.to_string();
+                HookResult::Continue(message)
+            }
+        }
+    }
+
+    async fn on_message_sending(
+        &self,
+        channel: String,
+        recipient: String,
+        content: String,
+    ) -> HookResult<(String, String, String)> {
+        let args = (channel.clone(), recipient.clone(), content.clone());
+        match self.verdict(self.call("on_message_sending", args)) {
+            Verdict::Pass => HookResult::Continue((channel, recipient, content)),
+            Verdict::Cancel(reason) => HookResult::Cancel(reason),
+            Verdict::Replace(value) => {
+                HookResult::Continue((channel, recipient, value.to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook_from(tmp: &tempfile::TempDir, source: &str) -> ScriptHook {
+        std::fs::write(tmp.path().join("filter.rhai"), source).unwrap();
+        ScriptHook::load(
+            tmp.path(),
+            &ScriptHookConfig {
+                path: "filter.rhai".into(),
+                priority: 0,
+            },
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn transforms_and_cancels_outgoing_messages() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hook = hook_from(
+            &tmp,
+            r#"
+            fn on_message_sending(channel, recipient, content) {
+                if content.contains("[quiet]") { return false; }
+                if channel == "telegram" { return content.to_upper(); }
+            }
+            "#,
+        );
+        assert_eq!(hook.name(), "script:filter");
+
+        match hook
+            .on_message_sending("telegram".into(), "me".into(), "hi".into())
+            .await
+        {
+            HookResult::Continue((_, _, content)) => assert_eq!(content, "HI"),
+            HookResult::Cancel(_) => panic!("should not cancel"),
+        }
+        match hook
+            .on_message_sending("slack".into(), "me".into(), "hi".into())
+            .await
+        {
+            HookResult::Continue((_, _, content)) => assert_eq!(content, "hi"),
+            HookResult::Cancel(_) => panic!("should not cancel"),
+        }
+        assert!(hook
+            .on_message_sending("slack".into(), "me".into(), "[quiet] x".into())
+            .await
+            .is_cancel());
+    }
+
+    #[tokio::test]
+    async fn rewrites_tool_args_and_persists_state() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hook = hook_from(
+            &tmp,
+            r#"
+            fn before_tool_call(name, args) {
+                let n = kv_get("calls");
+                kv_set("calls", if n == () { 1 } else { parse_int(n) + 1 });
+                if name == "shell" { return #{ cancel: "no shell" }; }
+                args.checked = true;
+                args
+            }
+            "#,
+        );
+
+        match hook
+            .before_tool_call("file_read".into(), serde_json::json!({"path": "a"}))
+            .await
+        {
+            HookResult::Continue((_, args)) => assert_eq!(args["checked"], true),
+            HookResult::Cancel(_) => panic!("should not cancel"),
+        }
+        match hook
+            .before_tool_call("shell".into(), serde_json::json!({}))
+            .await
+        {
+            HookResult::Cancel(reason) => assert_eq!(reason, "no shell"),
+            HookResult::Continue(_) => panic!("should cancel"),
+        }
+        let db = tmp.path().join("state").join("scripts.db");
+        assert_eq!(
+            kv_get(&db, "script:filter", "calls").unwrap().as_deref(),
+            Some("2")
+        );
+    }
+
+    #[tokio::test]
+    async fn runaway_scripts_pass_through() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hook = hook_from(
+            &tmp,
+            "fn on_message_sending(channel, recipient, content) { loop {} }",
+        );
+        match hook
+            .on_message_sending("cli".into(), "me".into(), "hi".into())
+            .await
+        {
+            HookResult::Continue((_, _, content)) => assert_eq!(content, "hi"),
+            HookResult::Cancel(_) => panic!("should not cancel"),
+        }
+    }
+}
@@ -0,0 +1,376 @@
+//! Device inventory: what has been seen on the network, and when.
+//!
+//! Observations come from the neighbour (ARP) table and from whatever the
+//! agent or other tools learn about a device (`device_inventory` action
+//! `record`, e.g. manufacturer and firmware read from its status page).
+//! Devices are keyed by MAC address when one is known, so a DHCP address
+//! change updates the same row. New devices, address changes and firmware
+//! changes are kept in `device_events`, so "what is new since Tuesday" has
+//! an answer. The store lives in `workspace/inventory/devices.db`.
+
+use crate::schema_migrations::{migrate, Migration};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+
+pub(crate) const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "baseline",
+    up: "CREATE TABLE devices (
+            key          TEXT PRIMARY KEY,
+            name         TEXT,
+            address      TEXT,
+            mac          TEXT,
+            manufacturer TEXT,
+            model        TEXT,
+            firmware     TEXT,
+            source       TEXT NOT NULL,
+            first_seen   TEXT NOT NULL,
+            last_seen    TEXT NOT NULL
+        );
+        CREATE TABLE device_events (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            device_key TEXT NOT NULL,
+            at         TEXT NOT NULL,
+            kind       TEXT NOT NULL,
+            detail     TEXT
+        );
+        CREATE INDEX idx_device_events_at ON device_events(at);",
+    down: "DROP TABLE device_events;
+        DROP TABLE devices;",
+}];
+
+/// One sighting of a device; unset fields keep their stored value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Observation {
+    pub mac: Option<String>,
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub firmware: Option<String>,
+    pub source: String,
+}
+
+impl Observation {
+    /// The normalized MAC, else `name:<name>`; `None` if neither is set.
+    pub fn key(&self) -> Option<String> {
+        if let Some(mac) = self.mac.as_deref().and_then(normalize_mac) {
+            return Some(mac);
+        }
+        self.name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| format!("name:{}", name.to_lowercase()))
+    }
+}
+
+/// A device as stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Device {
+    pub key: String,
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub mac: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub firmware: Option<String>,
+    pub source: String,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// `new`, `address` or `firmware` change of one device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceEvent {
+    pub device_key: String,
+    pub at: DateTime<Utc>,
+    pub kind: String,
+    pub detail: Option<String>,
+}
+
+/// `aa:bb:cc:dd:ee:ff` from colon, dash or dot separated forms.
+pub fn normalize_mac(raw: &str) -> Option<String> {
+    let hex: String = raw
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-' | '.'))
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if hex.len() != 12 || !hex.chars().all(|c| c.is_ascii_hexdigit()) || hex == "000000000000" {
+        return None;
+    }
+    let pairs: Vec<&str> = (0..12).step_by(2).map(|i| &hex[i..i + 2]).collect();
+    Some(pairs.join(":"))
+}
+
+pub fn db_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("inventory").join("devices.db")
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let path = db_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let conn = Connection::open(&path)
+        .with_context(|| format!("Failed to open inventory DB: {}", path.display()))?;
+    migrate(&conn, "inventory", MIGRATIONS)?;
+    f(&conn)
+}
+
+fn parse_time(raw: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(raw).map_or_else(|_| Utc::now(), |t| t.with_timezone(&Utc))
+}
+
+fn add_event(
+    conn: &Connection,
+    key: &str,
+    at: &str,
+    kind: &str,
+    detail: Option<&str>,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO device_events (device_key, at, kind, detail) VALUES (?1, ?2, ?3, ?4)",
+        params![key, at, kind, detail],
+    )?;
+    Ok(())
+}
+
+/// Upsert `observations`. Returns the keys of devices seen for the first time.
+pub fn record(
+    workspace_dir: &Path,
+    observations: &[Observation],
+    now: DateTime<Utc>,
+) -> Result<Vec<String>> {
+    with_connection(workspace_dir, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        let at = now.to_rfc3339();
+        let mut new_keys = Vec::new();
+        for obs in observations {
+            let Some(key) = obs.key() else { continue };
+            let mac = obs.mac.as_deref().and_then(normalize_mac);
+            let existing: Option<(Option<String>, Option<String>)> = tx
+                .query_row(
+                    "SELECT address, firmware FROM devices WHERE key = ?1",
+                    params![key],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            match existing {
+                None => {
+                    tx.execute(
+                        "INSERT INTO devices (key, name, address, mac, manufacturer, model, firmware,
+                                              source, first_seen, last_seen)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9)",
+                        params![
+                            key,
+                            obs.name,
+                            obs.address,
+                            mac,
+                            obs.manufacturer,
+                            obs.model,
+                            obs.firmware,
+                            obs.source,
+                            at
+                        ],
+                    )?;
+                    let detail = [obs.name.as_deref(), obs.address.as_deref()]
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    add_event(
+                        &tx,
+                        &key,
+                        &at,
+                        "new",
+                        Some(&detail).filter(|d| !d.is_empty()).map(String::as_str),
+                    )?;
+                    new_keys.push(key);
+                }
+                Some((address, firmware)) => {
+                    for (kind, old, new) in [
+                        ("address", address, obs.address.as_ref()),
+                        ("firmware", firmware, obs.firmware.as_ref()),
+                    ] {
+                        if let (Some(old), Some(new)) = (old, new) {
+                            if old != *new {
+                                add_event(&tx, &key, &at, kind, Some(&format!("{old} -> {new}")))?;
+                            }
+                        }
+                    }
+                    tx.execute(
+                        "UPDATE devices SET
+                            name = COALESCE(?2, name),
+                            address = COALESCE(?3, address),
+                            mac = COALESCE(?4, mac),
+                            manufacturer = COALESCE(?5, manufacturer),
+                            model = COALESCE(?6, model),
+                            firmware = COALESCE(?7, firmware),
+                            source = ?8,
+                            last_seen = ?9
+                         WHERE key = ?1",
+                        params![
+                            key,
+                            obs.name,
+                            obs.address,
+                            mac,
+                            obs.manufacturer,
+                            obs.model,
+                            obs.firmware,
+                            obs.source,
+                            at
+                        ],
+                    )?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(new_keys)
+    })
+}
+
+/// Every device, most recently seen first.
+pub fn devices(workspace_dir: &Path) -> Result<Vec<Device>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT key, name, address, mac, manufacturer, model, firmware, source,
+                    first_seen, last_seen
+             FROM devices ORDER BY last_seen DESC, key",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(Device {
+                key: row.get(0)?,
+                name: row.get(1)?,
+                address: row.get(2)?,
+                mac: row.get(3)?,
+                manufacturer: row.get(4)?,
+                model: row.get(5)?,
+                firmware: row.get(6)?,
+                source: row.get(7)?,
+                first_seen: parse_time(&row.get::<_, String>(8)?),
+                last_seen: parse_time(&row.get::<_, String>(9)?),
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    })
+}
+
+/// Events since `since`, newest first, optionally for one device key.
+pub fn events(
+    workspace_dir: &Path,
+    since: DateTime<Utc>,
+    device_key: Option<&str>,
+) -> Result<Vec<DeviceEvent>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT device_key, at, kind, detail FROM device_events
+             WHERE at >= ?1 AND (?2 IS NULL OR device_key = ?2)
+             ORDER BY at DESC, id DESC",
+        )?;
+        let rows = stmt.query_map(params![since.to_rfc3339(), device_key], |row| {
+            Ok(DeviceEvent {
+                device_key: row.get(0)?,
+                at: parse_time(&row.get::<_, String>(1)?),
+                kind: row.get(2)?,
+                detail: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    })
+}
+
+/// Complete entries of a Linux `/proc/net/arp` table.
+fn parse_arp(content: &str) -> Vec<Observation> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // IP address, HW type, Flags, HW address, Mask, Device
+            if fields.len() < 4 || fields[2] == "0x0" {
+                return None;
+            }
+            Some(Observation {
+                mac: Some(normalize_mac(fields[3])?),
+                address: Some(fields[0].to_string()),
+                source: "arp".into(),
+                ..Observation::default()
+            })
+        })
+        .collect()
+}
+
+/// Devices in this host's neighbour table; empty where it is unavailable.
+pub fn neighbors() -> Vec<Observation> {
+    std::fs::read_to_string("/proc/net/arp")
+        .map(|content| parse_arp(&content))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parses_neighbor_table_and_macs() {
+        let arp =
+            "IP address       HW type     Flags       HW address            Mask     Device\n\
+                   192.168.1.1      0x1         0x2         AA:BB:CC:DD:EE:01     *        eth0\n\
+                   192.168.1.9      0x1         0x0         00:00:00:00:00:00     *        eth0\n";
+        let neighbors = parse_arp(arp);
+        assert_eq!(neighbors.len(), 1);
+        assert_eq!(neighbors[0].key().as_deref(), Some("aa:bb:cc:dd:ee:01"));
+        assert_eq!(neighbors[0].address.as_deref(), Some("192.168.1.1"));
+
+        assert_eq!(
+            normalize_mac("aabb.ccdd.ee02").as_deref(),
+            Some("aa:bb:cc:dd:ee:02")
+        );
+        assert_eq!(normalize_mac("AA-BB-CC-DD-EE"), None);
+    }
+
+    #[test]
+    fn records_new_devices_and_changes() {
+        let tmp = TempDir::new().unwrap();
+        let start = Utc::now() - Duration::hours(2);
+        let plug = Observation {
+            mac: Some("aa:bb:cc:dd:ee:01".into()),
+            address: Some("192.168.1.20".into()),
+            source: "arp".into(),
+            ..Observation::default()
+        };
+        assert_eq!(
+            record(tmp.path(), &[plug.clone()], start).unwrap(),
+            vec!["aa:bb:cc:dd:ee:01"]
+        );
+
+        let later = start + Duration::hours(1);
+        let update = Observation {
+            address: Some("192.168.1.21".into()),
+            name: Some("Hallway plug".into()),
+            firmware: Some("1.2.0".into()),
+            source: "agent".into(),
+            ..plug
+        };
+        assert!(record(tmp.path(), &[update], later).unwrap().is_empty());
+
+        let devices = devices(tmp.path()).unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].address.as_deref(), Some("192.168.1.21"));
+        assert_eq!(devices[0].firmware.as_deref(), Some("1.2.0"));
+        assert!(devices[0].first_seen < devices[0].last_seen);
+
+        let kinds: Vec<String> = events(tmp.path(), start - Duration::minutes(1), None)
+            .unwrap()
+            .into_iter()
+            .map(|event| event.kind)
+            .collect();
+        assert_eq!(kinds, vec!["address", "new"]);
+    }
+}
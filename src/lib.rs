@@ -39,14 +39,17 @@ use clap::Subcommand;
 use serde::{Deserialize, Serialize};
 
 pub mod agent;
+pub(crate) mod analytics;
 pub(crate) mod approval;
 pub(crate) mod auth;
+pub(crate) mod backup;
 pub mod channels;
 pub mod config;
 pub(crate) mod cost;
 pub(crate) mod cron;
 pub(crate) mod daemon;
 pub(crate) mod doctor;
+pub(crate) mod facts;
 pub mod gateway;
 pub(crate) mod hardware;
 pub(crate) mod health;
@@ -54,20 +57,31 @@ pub(crate) mod heartbeat;
 pub mod hooks;
 pub(crate) mod identity;
 pub(crate) mod integrations;
+pub(crate) mod inventory;
+pub(crate) mod locale;
+pub(crate) mod location;
 pub mod memory;
+pub(crate) mod metrics;
 pub(crate) mod migration;
 pub(crate) mod multimodal;
+pub(crate) mod notify;
 pub mod observability;
 pub(crate) mod onboard;
+pub(crate) mod people;
 pub mod peripherals;
+pub(crate) mod preferences;
+pub(crate) mod printer;
 pub mod providers;
+pub(crate) mod publish;
 pub mod rag;
 pub mod runtime;
+pub(crate) mod schema_migrations;
 pub(crate) mod security;
 pub(crate) mod service;
 pub(crate) mod skills;
 pub mod tools;
 pub(crate) mod tunnel;
+pub(crate) mod uptime_kuma;
 pub(crate) mod util;
 
 pub use config::Config;
@@ -163,7 +177,7 @@ pub enum SkillCommands {
 /// Migration subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MigrateCommands {
-    /// Import memory from an `OpenClaw` workspace into this `ZeroClaw` workspace
+    /// Import memory, prompt files, skills and cron jobs from an `OpenClaw` workspace
     Openclaw {
         /// Optional path to `OpenClaw` workspace (defaults to ~/.openclaw/workspace)
         #[arg(long)]
@@ -173,6 +187,30 @@ pub enum MigrateCommands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Convert an `OpenAI` Assistants export (JSON) into workspace skill packs
+    Assistant {
+        /// Path to the exported assistant JSON (single object, array, or list response)
+        file: std::path::PathBuf,
+
+        /// Preview the conversion without writing any files
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show applied and pending schema migrations for the cron, memory, inventory, metrics, facts, analytics and feedback databases
+    Status,
+    /// Revert schema migrations of one database down to a version
+    Rollback {
+        /// Database to roll back: `cron`, `memory`, `inventory`, `metrics`, `facts`, `analytics` or `feedback`
+        store: String,
+
+        /// Schema version to keep; later migrations are reverted (0 drops the store's tables and takes a backup first)
+        #[arg(long)]
+        to: u32,
+
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
 }
 
 /// Cron subcommands
@@ -278,6 +316,63 @@ Examples:
         #[arg(long)]
         name: Option<String>,
     },
+    /// Run a scheduled task now, optionally with one-off overrides
+    #[command(long_about = "\
+Run a scheduled task immediately and record it as a manual run.
+
+Overrides apply to this run only. --date replaces {{date}} in the \
+task's command or prompt (default: today, UTC). --channel and --to \
+announce the output to a different destination.
+
+Examples:
+  zeroclaw cron run <task-id>
+  zeroclaw cron run <task-id> --date 2026-03-14
+  zeroclaw cron run <task-id> --channel telegram --to 123456789")]
+    Run {
+        /// Task ID
+        id: String,
+        /// Run date (YYYY-MM-DD) substituted for {{date}}
+        #[arg(long)]
+        date: Option<String>,
+        /// Announce output on this channel (telegram, discord, slack, mattermost)
+        #[arg(long, requires = "to")]
+        channel: Option<String>,
+        /// Delivery target (chat/channel ID) for --channel
+        #[arg(long, requires = "channel")]
+        to: Option<String>,
+        /// Model override for agent tasks
+        #[arg(long)]
+        model: Option<String>,
+    },
+    /// Re-run a date-parameterized task over a past date range
+    #[command(long_about = "\
+Run a task once per day over a historical date range, e.g. to regenerate \
+daily reports missed during an outage.
+
+Each run sees that day's date in place of {{date}} and is recorded in \
+run history as a backfill. Output is not delivered unless --deliver is \
+given. Tasks that stage artifacts should use --concurrency 1, since runs \
+of the same task share a staging directory.
+
+Examples:
+  zeroclaw cron backfill <task-id> --from 2026-03-01 --to 2026-03-07
+  zeroclaw cron backfill <task-id> --from 2026-03-01 --to 2026-03-31 --concurrency 4")]
+    Backfill {
+        /// Task ID
+        id: String,
+        /// First date (YYYY-MM-DD, inclusive)
+        #[arg(long)]
+        from: String,
+        /// Last date (YYYY-MM-DD, inclusive)
+        #[arg(long)]
+        to: String,
+        /// Maximum runs in flight (default: scheduler.max_concurrent)
+        #[arg(long)]
+        concurrency: Option<usize>,
+        /// Deliver each run's output as configured on the task
+        #[arg(long)]
+        deliver: bool,
+    },
     /// Pause a scheduled task
     Pause {
         /// Task ID
@@ -329,6 +424,24 @@ pub enum MemoryCommands {
     },
 }
 
+/// Preference subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PreferenceCommands {
+    /// List recorded user preferences
+    List,
+    /// Delete one preference by key
+    Remove {
+        /// Preference key, e.g. `temperature_unit`
+        key: String,
+    },
+    /// Delete all preferences
+    Clear {
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
 /// Integration subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum IntegrationCommands {
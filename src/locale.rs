@@ -0,0 +1,215 @@
+//! Locale-aware formatting.
+//!
+//! `[locale]` sets the timezone, clock style, temperature unit, measurement
+//! system and currency for a workspace. Tools format timestamps and
+//! quantities through these helpers instead of printing UTC and bare
+//! numbers, and [`prompt_section`] tells the model the same conventions so
+//! replies built from raw tool data (web search, HTTP APIs) follow them too.
+//! The current time lives in its own `## Current Date & Time` section, which
+//! [`with_current_datetime`] re-renders every turn.
+
+use crate::config::{ClockFormat, LocaleConfig, MeasurementSystem, TemperatureUnit};
+use chrono::{DateTime, Local, Utc};
+use std::fmt::Write;
+
+const KM_PER_MILE: f64 = 1.609_344;
+
+fn datetime_pattern(config: &LocaleConfig) -> &'static str {
+    match config.clock {
+        ClockFormat::H24 => "%Y-%m-%d %H:%M",
+        ClockFormat::H12 => "%Y-%m-%d %-I:%M %p",
+    }
+}
+
+/// Configured timezone, or `None` for the host timezone (also used when the
+/// name does not parse; `Config::validate` rejects those up front).
+pub fn timezone(config: &LocaleConfig) -> Option<chrono_tz::Tz> {
+    config.timezone.as_deref()?.parse().ok()
+}
+
+/// `at` in the configured timezone and clock style, e.g. `2026-03-01 14:30`
+/// or `2026-03-01 2:30 PM`.
+pub fn format_datetime(config: &LocaleConfig, at: DateTime<Utc>) -> String {
+    let pattern = datetime_pattern(config);
+    match timezone(config) {
+        Some(tz) => at.with_timezone(&tz).format(pattern).to_string(),
+        None => at.with_timezone(&Local).format(pattern).to_string(),
+    }
+}
+
+/// A Celsius reading in the configured unit, e.g. `21.5 °C` or `70.7 °F`.
+pub fn format_temperature(config: &LocaleConfig, celsius: f64) -> String {
+    match config.temperature {
+        TemperatureUnit::Celsius => format!("{celsius:.1} °C"),
+        TemperatureUnit::Fahrenheit => format!("{:.1} °F", celsius * 9.0 / 5.0 + 32.0),
+    }
+}
+
+/// A distance in kilometres in the configured system, e.g. `5.0 km` or `3.1 mi`.
+pub fn format_distance(config: &LocaleConfig, km: f64) -> String {
+    match config.units {
+        MeasurementSystem::Metric => format!("{km:.1} km"),
+        MeasurementSystem::Imperial => format!("{:.1} mi", km / KM_PER_MILE),
+    }
+}
+
+/// An amount with two decimals and thousands separators, followed by
+/// `currency` or the configured currency code, e.g. `1,234.50 EUR`.
+pub fn format_money(config: &LocaleConfig, amount: f64, currency: Option<&str>) -> String {
+    if !amount.is_finite() {
+        return amount.to_string();
+    }
+    let formatted = format!("{:.2}", amount.abs());
+    let (whole, fraction) = formatted.split_once('.').unwrap_or((&formatted, "00"));
+    let mut grouped = String::new();
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    let sign = if amount < 0.0 { "-" } else { "" };
+    match currency.or(config.currency.as_deref()) {
+        Some(code) => format!("{sign}{grouped}.{fraction} {code}"),
+        None => format!("{sign}{grouped}.{fraction}"),
+    }
+}
+
+const DATETIME_HEADING: &str = "## Current Date & Time\n\n";
+
+/// `## Current Date & Time` section for the system prompt, e.g.
+/// `2026-03-01 2:30 PM (America/New_York)`.
+pub fn datetime_section(config: &LocaleConfig, at: DateTime<Utc>) -> String {
+    let zone = match &config.timezone {
+        Some(name) if timezone(config).is_some() => name.clone(),
+        _ => at.with_timezone(&Local).format("%Z").to_string(),
+    };
+    format!(
+        "{DATETIME_HEADING}{} ({zone})\n\n",
+        format_datetime(config, at)
+    )
+}
+
+/// `prompt` with its `## Current Date & Time` line replaced by the current
+/// time in the configured timezone. System prompts outlive a single turn
+/// (channel daemons build theirs once at startup), so callers apply this
+/// right before each provider call; prompts without the section are
+/// returned unchanged.
+pub fn with_current_datetime(config: &LocaleConfig, prompt: &str) -> String {
+    let Some(start) = prompt.find(DATETIME_HEADING) else {
+        return prompt.to_string();
+    };
+    let line_start = start + DATETIME_HEADING.len();
+    let line_end = prompt[line_start..]
+        .find('\n')
+        .map_or(prompt.len(), |i| line_start + i);
+    let fresh = datetime_section(config, Utc::now());
+    let fresh_line = fresh[DATETIME_HEADING.len()..].trim_end();
+    format!(
+        "{}{fresh_line}{}",
+        &prompt[..line_start],
+        &prompt[line_end..]
+    )
+}
+
+/// `## Locale` section for the system prompt.
+pub fn prompt_section(config: &LocaleConfig) -> String {
+    let zone = config.timezone.as_deref().unwrap_or("host local time");
+    let clock = match config.clock {
+        ClockFormat::H24 => "24-hour",
+        ClockFormat::H12 => "12-hour (AM/PM)",
+    };
+    let temperature = match config.temperature {
+        TemperatureUnit::Celsius => "°C",
+        TemperatureUnit::Fahrenheit => "°F",
+    };
+    let units = match config.units {
+        MeasurementSystem::Metric => "metric (km, m, kg, km/h)",
+        MeasurementSystem::Imperial => "imperial (mi, ft, lb, mph)",
+    };
+    let mut section = String::from("## Locale\n\n");
+    let _ = writeln!(
+        section,
+        "Timezone: {zone} | Clock: {clock} | Temperature: {temperature} | Units: {units}"
+    );
+    if let Some(currency) = &config.currency {
+        let _ = writeln!(section, "Currency: {currency}");
+    }
+    section.push_str(
+        "Present dates, times, temperatures, measurements and amounts in these conventions, converting tool data that uses others.\n\n",
+    );
+    section
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn locale() -> LocaleConfig {
+        LocaleConfig {
+            timezone: Some("America/New_York".into()),
+            clock: ClockFormat::H12,
+            temperature: TemperatureUnit::Fahrenheit,
+            units: MeasurementSystem::Imperial,
+            currency: Some("USD".into()),
+        }
+    }
+
+    #[test]
+    fn formats_in_configured_conventions() {
+        let at = Utc.with_ymd_and_hms(2026, 1, 15, 19, 5, 0).unwrap();
+        assert_eq!(format_datetime(&locale(), at), "2026-01-15 2:05 PM");
+        assert_eq!(format_temperature(&locale(), 21.5), "70.7 °F");
+        assert_eq!(format_distance(&locale(), 5.0), "3.1 mi");
+        assert_eq!(
+            format_money(&locale(), -1_234_567.5, None),
+            "-1,234,567.50 USD"
+        );
+
+        let utc = LocaleConfig {
+            timezone: Some("UTC".into()),
+            ..LocaleConfig::default()
+        };
+        assert_eq!(format_datetime(&utc, at), "2026-01-15 19:05");
+        assert_eq!(format_temperature(&utc, 21.5), "21.5 °C");
+        assert_eq!(format_distance(&utc, 5.0), "5.0 km");
+        assert_eq!(format_money(&utc, 999.0, Some("EUR")), "999.00 EUR");
+    }
+
+    #[test]
+    fn prompt_section_names_conventions() {
+        let section = prompt_section(&locale());
+        assert!(section.starts_with("## Locale"));
+        assert!(section.contains("America/New_York"));
+        assert!(section.contains("°F"));
+        assert!(section.contains("Currency: USD"));
+        assert!(!prompt_section(&LocaleConfig::default()).contains("Currency"));
+        assert!(!section.contains("(now"));
+    }
+
+    #[test]
+    fn datetime_section_uses_configured_timezone() {
+        let at = Utc.with_ymd_and_hms(2026, 1, 15, 19, 5, 0).unwrap();
+        assert_eq!(
+            datetime_section(&locale(), at),
+            "## Current Date & Time\n\n2026-01-15 2:05 PM (America/New_York)\n\n"
+        );
+    }
+
+    #[test]
+    fn with_current_datetime_replaces_only_the_time_line() {
+        let stale = format!(
+            "## Safety\n\nBe careful.\n\n{}## Runtime\n\nHost: box\n",
+            datetime_section(
+                &locale(),
+                Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap()
+            )
+        );
+        let fresh = with_current_datetime(&locale(), &stale);
+        assert!(!fresh.contains("2019-12-31"));
+        assert!(fresh.starts_with("## Safety\n\nBe careful.\n\n## Current Date & Time\n\n"));
+        assert!(fresh.contains("(America/New_York)\n\n## Runtime\n\nHost: box\n"));
+        assert_eq!(with_current_datetime(&locale(), "no section"), "no section");
+    }
+}
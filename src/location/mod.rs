@@ -0,0 +1,383 @@
+//! Last-known locations and geofence presence.
+//!
+//! The gateway's `POST /hooks/location` endpoint feeds OwnTracks (HTTP mode)
+//! or plain JSON payloads into a small per-person store under
+//! `workspace/location/`. Each fix is checked against `[location].geofences`
+//! and enter/leave transitions are returned to the caller, which publishes
+//! them on the gateway event stream. The `where_is` tool reads the same store.
+
+use crate::config::GeofenceConfig;
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// Serializes read-modify-write cycles on the store file.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Most recent position reported for a person.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocationFix {
+    pub lat: f64,
+    pub lon: f64,
+    #[serde(default)]
+    pub accuracy_m: Option<f64>,
+    #[serde(default)]
+    pub battery: Option<i64>,
+    pub timestamp: DateTime<Utc>,
+    pub source: String,
+    /// Geofences the person is currently inside.
+    #[serde(default)]
+    pub regions: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LocationStore {
+    #[serde(default)]
+    people: BTreeMap<String, LocationFix>,
+}
+
+/// A single position report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocationUpdate {
+    pub person: String,
+    pub lat: f64,
+    pub lon: f64,
+    pub accuracy_m: Option<f64>,
+    pub battery: Option<i64>,
+    pub timestamp: DateTime<Utc>,
+    pub source: String,
+}
+
+/// Geofence enter/leave transition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GeofenceEvent {
+    pub person: String,
+    pub region: String,
+    /// `"enter"` or `"leave"`
+    pub event: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// What an incoming webhook body turned out to be.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocationPayload {
+    Location(LocationUpdate),
+    /// Region transition computed on the device (OwnTracks `_type: transition`).
+    Transition(GeofenceEvent),
+    /// Valid message type that carries no position (OwnTracks `lwt`, `card`, …).
+    Ignored(String),
+}
+
+fn store_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("location").join("last_known.json")
+}
+
+fn load_store(workspace_dir: &Path) -> Result<LocationStore> {
+    let path = store_path(workspace_dir);
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .with_context(|| format!("Invalid location store: {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(LocationStore::default()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+fn save_store(workspace_dir: &Path, store: &LocationStore) -> Result<()> {
+    let path = store_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(store)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, &path)
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+/// Last-known fixes keyed by person.
+pub fn last_known(workspace_dir: &Path) -> Result<BTreeMap<String, LocationFix>> {
+    Ok(load_store(workspace_dir)?.people)
+}
+
+/// Great-circle distance in metres.
+pub fn distance_m(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = (lat2 - lat1).to_radians();
+    let d_lambda = (lon2 - lon1).to_radians();
+    let a = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * a.sqrt().asin()
+}
+
+fn regions_containing(geofences: &[GeofenceConfig], lat: f64, lon: f64) -> Vec<String> {
+    geofences
+        .iter()
+        .filter(|fence| distance_m(lat, lon, fence.lat, fence.lon) <= fence.radius_m)
+        .map(|fence| fence.name.clone())
+        .collect()
+}
+
+/// Store a position report and return geofence transitions it caused.
+/// Reports older than the stored fix are ignored.
+pub fn record_update(
+    workspace_dir: &Path,
+    geofences: &[GeofenceConfig],
+    update: LocationUpdate,
+) -> Result<Vec<GeofenceEvent>> {
+    let _guard = STORE_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let mut store = load_store(workspace_dir)?;
+    let previous = store.people.get(&update.person);
+    if previous.is_some_and(|fix| fix.timestamp > update.timestamp) {
+        return Ok(Vec::new());
+    }
+
+    let regions = regions_containing(geofences, update.lat, update.lon);
+    let mut events = Vec::new();
+    if let Some(previous) = previous {
+        // Only fences that still exist in config can produce a leave event.
+        for region in previous
+            .regions
+            .iter()
+            .filter(|r| !regions.contains(r) && geofences.iter().any(|f| &f.name == *r))
+        {
+            events.push(GeofenceEvent {
+                person: update.person.clone(),
+                region: region.clone(),
+                event: "leave".into(),
+                timestamp: update.timestamp,
+            });
+        }
+    }
+    let was_in = |region: &String| previous.is_some_and(|fix| fix.regions.contains(region));
+    for region in regions.iter().filter(|r| !was_in(r)) {
+        events.push(GeofenceEvent {
+            person: update.person.clone(),
+            region: region.clone(),
+            event: "enter".into(),
+            timestamp: update.timestamp,
+        });
+    }
+
+    store.people.insert(
+        update.person,
+        LocationFix {
+            lat: update.lat,
+            lon: update.lon,
+            accuracy_m: update.accuracy_m,
+            battery: update.battery,
+            timestamp: update.timestamp,
+            source: update.source,
+            regions,
+        },
+    );
+    save_store(workspace_dir, &store)?;
+    Ok(events)
+}
+
+/// Apply a device-side transition to the stored region list.
+pub fn record_transition(workspace_dir: &Path, event: &GeofenceEvent) -> Result<()> {
+    let _guard = STORE_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let mut store = load_store(workspace_dir)?;
+    let Some(fix) = store.people.get_mut(&event.person) else {
+        return Ok(());
+    };
+    fix.regions.retain(|r| r != &event.region);
+    if event.event == "enter" {
+        fix.regions.push(event.region.clone());
+    }
+    save_store(workspace_dir, &store)
+}
+
+fn number(body: &Value, keys: &[&str]) -> Option<f64> {
+    keys.iter().find_map(|key| match body.get(*key)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    })
+}
+
+fn text<'a>(body: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|key| body.get(*key)?.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn payload_timestamp(body: &Value) -> DateTime<Utc> {
+    if let Some(tst) = body.get("tst").and_then(Value::as_i64) {
+        if let Some(ts) = Utc.timestamp_opt(tst, 0).single() {
+            return ts;
+        }
+    }
+    text(body, &["timestamp", "time"])
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .map_or_else(Utc::now, |ts| ts.with_timezone(&Utc))
+}
+
+/// Parse an OwnTracks or plain JSON location body.
+///
+/// The person is taken from `person`/`user` in the body, then `person_hint`
+/// (basic auth user or OwnTracks `X-Limit-U`), then the OwnTracks `tid`.
+pub fn parse_payload(body: &Value, person_hint: Option<&str>) -> Result<LocationPayload, String> {
+    let kind = text(body, &["_type"]).unwrap_or("location");
+    if !matches!(kind, "location" | "transition") {
+        return Ok(LocationPayload::Ignored(kind.to_string()));
+    }
+
+    let person = text(body, &["person", "user"])
+        .or(person_hint.map(str::trim).filter(|s| !s.is_empty()))
+        .or_else(|| text(body, &["tid"]))
+        .ok_or("Cannot determine person: send 'person' in the body or use basic auth")?
+        .to_lowercase();
+    let timestamp = payload_timestamp(body);
+
+    if kind == "transition" {
+        let event = text(body, &["event"]).ok_or("transition without 'event'")?;
+        if !matches!(event, "enter" | "leave") {
+            return Err(format!("unknown transition event '{event}'"));
+        }
+        let region = text(body, &["desc"]).ok_or("transition without 'desc'")?;
+        return Ok(LocationPayload::Transition(GeofenceEvent {
+            person,
+            region: region.to_string(),
+            event: event.to_string(),
+            timestamp,
+        }));
+    }
+
+    let lat = number(body, &["lat", "latitude"]).ok_or("missing 'lat'")?;
+    let lon = number(body, &["lon", "lng", "longitude"]).ok_or("missing 'lon'")?;
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+        return Err(format!("coordinates out of range: {lat}, {lon}"));
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let battery = number(body, &["batt", "battery", "battery_level"]).map(|b| b.round() as i64);
+    Ok(LocationPayload::Location(LocationUpdate {
+        person,
+        lat,
+        lon,
+        accuracy_m: number(body, &["acc", "accuracy", "gps_accuracy"]),
+        battery,
+        timestamp,
+        source: if body.get("_type").is_some() {
+            "owntracks".into()
+        } else {
+            "webhook".into()
+        },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn home() -> Vec<GeofenceConfig> {
+        vec![GeofenceConfig {
+            name: "home".into(),
+            lat: 52.5200,
+            lon: 13.4050,
+            radius_m: 150.0,
+        }]
+    }
+
+    fn update(person: &str, lat: f64, lon: f64, tst: i64) -> LocationUpdate {
+        LocationUpdate {
+            person: person.into(),
+            lat,
+            lon,
+            accuracy_m: None,
+            battery: None,
+            timestamp: Utc.timestamp_opt(tst, 0).unwrap(),
+            source: "test".into(),
+        }
+    }
+
+    #[test]
+    fn parses_owntracks_location_and_transition() {
+        let body = json!({"_type": "location", "lat": 52.52, "lon": 13.405, "tst": 1_767_225_600, "acc": 12, "batt": 81, "tid": "AL"});
+        let LocationPayload::Location(update) = parse_payload(&body, Some("Alice")).unwrap() else {
+            panic!("expected location");
+        };
+        assert_eq!(update.person, "alice");
+        assert_eq!(update.battery, Some(81));
+        assert_eq!(update.accuracy_m, Some(12.0));
+        assert_eq!(update.source, "owntracks");
+        assert_eq!(update.timestamp.timestamp(), 1_767_225_600);
+
+        let body =
+            json!({"_type": "transition", "event": "leave", "desc": "work", "tid": "AL", "tst": 1});
+        let LocationPayload::Transition(event) = parse_payload(&body, None).unwrap() else {
+            panic!("expected transition");
+        };
+        assert_eq!(
+            (event.person.as_str(), event.region.as_str()),
+            ("al", "work")
+        );
+
+        assert_eq!(
+            parse_payload(&json!({"_type": "lwt"}), None).unwrap(),
+            LocationPayload::Ignored("lwt".into())
+        );
+    }
+
+    #[test]
+    fn parses_plain_home_assistant_payload() {
+        let body =
+            json!({"person": "Bob", "latitude": "48.1", "longitude": 11.5, "gps_accuracy": 20});
+        let LocationPayload::Location(update) = parse_payload(&body, None).unwrap() else {
+            panic!("expected location");
+        };
+        assert_eq!(update.person, "bob");
+        assert_eq!(update.source, "webhook");
+        assert!(parse_payload(&json!({"lat": 1.0, "lon": 2.0}), None).is_err());
+        assert!(parse_payload(&json!({"person": "x", "lat": 91.0, "lon": 0.0}), None).is_err());
+    }
+
+    #[test]
+    fn geofence_enter_and_leave_are_reported_once() {
+        let tmp = TempDir::new().unwrap();
+        let fences = home();
+
+        let events =
+            record_update(tmp.path(), &fences, update("alice", 52.5205, 13.4052, 10)).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "enter");
+
+        let events =
+            record_update(tmp.path(), &fences, update("alice", 52.5201, 13.4051, 20)).unwrap();
+        assert!(events.is_empty());
+
+        // Stale report is ignored entirely.
+        let events = record_update(tmp.path(), &fences, update("alice", 48.0, 11.0, 15)).unwrap();
+        assert!(events.is_empty());
+
+        let events =
+            record_update(tmp.path(), &fences, update("alice", 52.5300, 13.4050, 30)).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "leave");
+
+        let fixes = last_known(tmp.path()).unwrap();
+        assert!(fixes["alice"].regions.is_empty());
+        assert_eq!(fixes["alice"].timestamp.timestamp(), 30);
+    }
+
+    #[test]
+    fn distance_is_roughly_correct() {
+        // Berlin → Munich is ~504 km.
+        let d = distance_m(52.5200, 13.4050, 48.1351, 11.5820);
+        assert!((d - 504_000.0).abs() < 5_000.0, "{d}");
+    }
+}
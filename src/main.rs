@@ -49,8 +49,10 @@ fn parse_temperature(s: &str) -> std::result::Result<f64, String> {
 }
 
 mod agent;
+mod analytics;
 mod approval;
 mod auth;
+mod backup;
 mod channels;
 mod rag {
     pub use zeroclaw::rag::*;
@@ -60,6 +62,7 @@ mod cost;
 mod cron;
 mod daemon;
 mod doctor;
+mod facts;
 mod gateway;
 mod hardware;
 mod health;
@@ -67,20 +70,31 @@ mod heartbeat;
 mod hooks;
 mod identity;
 mod integrations;
+mod inventory;
+mod locale;
+mod location;
 mod memory;
+mod metrics;
 mod migration;
 mod multimodal;
+mod notify;
 mod observability;
 mod onboard;
+mod people;
 mod peripherals;
+mod preferences;
+mod printer;
 mod providers;
+mod publish;
 mod runtime;
+mod schema_migrations;
 mod security;
 mod service;
 mod skillforge;
 mod skills;
 mod tools;
 mod tunnel;
+mod uptime_kuma;
 mod util;
 
 use config::Config;
@@ -88,7 +102,7 @@ use config::Config;
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
     ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands, MigrateCommands,
-    PeripheralCommands, ServiceCommands, SkillCommands,
+    PeripheralCommands, PreferenceCommands, ServiceCommands, SkillCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -357,12 +371,43 @@ Examples:
         skill_command: SkillCommands,
     },
 
-    /// Migrate data from other agent runtimes
+    /// Migrate data from other agent runtimes and manage state schema versions
     Migrate {
         #[command(subcommand)]
         migrate_command: MigrateCommands,
     },
 
+    /// Back up config and state to a tar.gz archive now
+    #[command(long_about = "\
+Back up config and state to a tar.gz archive now.
+
+Writes the config directory (config.toml, conf.d/, the secret key), \
+snapshots of every SQLite database in the workspace, and the [backup] \
+include paths into [backup] dir, then rotates old archives and uploads \
+the new one when [backup] upload is set.
+
+Examples:
+  zeroclaw backup")]
+    Backup,
+
+    /// Restore config and state from a backup archive
+    #[command(long_about = "\
+Restore config and state from a backup archive.
+
+Files in the archive overwrite their current versions; files not in \
+the archive are left alone. Stop the daemon before restoring.
+
+Examples:
+  zeroclaw restore ~/.zeroclaw/backups/zeroclaw-backup-20260101T030000Z.tar.gz
+  zeroclaw restore backup.tar.gz --yes")]
+    Restore {
+        /// Path to the archive
+        archive: std::path::PathBuf,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+
     /// Manage provider subscription authentication profiles
     Auth {
         #[command(subcommand)]
@@ -424,6 +469,23 @@ Examples:
         memory_command: MemoryCommands,
     },
 
+    /// Review and delete recorded user preferences
+    #[command(long_about = "\
+Review and delete recorded user preferences.
+
+Preferences are standing instructions the agent records with the \
+preference_set tool (\"always use Celsius\"). They are appended to \
+every system prompt until removed.
+
+Examples:
+  zeroclaw preferences list
+  zeroclaw preferences remove temperature_unit
+  zeroclaw preferences clear --yes")]
+    Preferences {
+        #[command(subcommand)]
+        preference_command: PreferenceCommands,
+    },
+
     /// Manage configuration
     #[command(long_about = "\
 Manage ZeroClaw configuration.
@@ -608,6 +670,24 @@ enum DoctorCommands {
         #[arg(long, default_value = "20")]
         limit: usize,
     },
+    /// Export agent turns from the runtime trace as redacted markdown for sharing
+    Share {
+        /// Number of turns to export (max 20)
+        #[arg(long, default_value = "1")]
+        turns: usize,
+        /// Start at this turn id (or id prefix) instead of the most recent turns
+        #[arg(long)]
+        from: Option<String>,
+        /// Only turns from this channel (cli, telegram, gateway, ...)
+        #[arg(long)]
+        channel: Option<String>,
+        /// Tool output beyond this many characters is folded
+        #[arg(long, default_value = "1500")]
+        max_output_chars: usize,
+        /// Write the transcript to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -766,6 +846,7 @@ async fn main() -> Result<()> {
             temperature,
             peripheral,
             true,
+            None,
         )
         .await
         .map(|_| ()),
@@ -893,7 +974,7 @@ async fn main() -> Result<()> {
             tools,
         } => handle_estop_command(&config, estop_command, level, domains, tools),
 
-        Commands::Cron { cron_command } => cron::handle_command(cron_command, &config),
+        Commands::Cron { cron_command } => cron::handle_command(cron_command, &config).await,
 
         Commands::Models { model_command } => match model_command {
             ModelCommands::Refresh { provider, force } => {
@@ -959,6 +1040,22 @@ async fn main() -> Result<()> {
                 contains.as_deref(),
                 limit,
             ),
+            Some(DoctorCommands::Share {
+                turns,
+                from,
+                channel,
+                max_output_chars,
+                output,
+            }) => doctor::run_share(
+                &config,
+                &observability::share::ShareOptions {
+                    from_turn: from,
+                    turns,
+                    channel,
+                    max_output_chars,
+                },
+                output.as_deref(),
+            ),
             None => doctor::run(&config),
         },
 
@@ -978,10 +1075,42 @@ async fn main() -> Result<()> {
             migration::handle_command(migrate_command, &config).await
         }
 
+        Commands::Backup => {
+            let archive = backup::run_once(&config).await?;
+            println!("✅ Backup written to {}", archive.display());
+            Ok(())
+        }
+
+        Commands::Restore { archive, yes } => {
+            let entries = backup::list_archive(&archive)?;
+            println!("Archive holds {} files.", entries.len());
+            if !yes {
+                let confirmed = dialoguer::Confirm::new()
+                    .with_prompt("  Overwrite the current config and workspace files?")
+                    .default(false)
+                    .interact()?;
+                if !confirmed {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+            }
+            let restored = backup::restore(&config, &archive)?;
+            println!(
+                "✅ Restored {} files from {}",
+                restored.len(),
+                archive.display()
+            );
+            Ok(())
+        }
+
         Commands::Memory { memory_command } => {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Preferences { preference_command } => {
+            preferences::handle_command(preference_command, &config)
+        }
+
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
         Commands::Hardware { hardware_command } => {
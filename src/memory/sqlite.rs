@@ -1,11 +1,12 @@
 use super::embeddings::EmbeddingProvider;
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use super::vector;
+use crate::schema_migrations::Migration;
 use anyhow::Context;
 use async_trait::async_trait;
 use chrono::Local;
 use parking_lot::Mutex;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
@@ -17,6 +18,59 @@ use uuid::Uuid;
 /// Maximum allowed open timeout (seconds) to avoid unreasonable waits.
 const SQLITE_OPEN_TIMEOUT_CAP_SECS: u64 = 300;
 
+/// Memory store schema history; append new versions, never edit applied ones.
+pub(crate) const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "baseline",
+    up: "-- Core memories table
+        CREATE TABLE IF NOT EXISTS memories (
+            id          TEXT PRIMARY KEY,
+            key         TEXT NOT NULL UNIQUE,
+            content     TEXT NOT NULL,
+            category    TEXT NOT NULL DEFAULT 'core',
+            embedding   BLOB,
+            created_at  TEXT NOT NULL,
+            updated_at  TEXT NOT NULL,
+            session_id  TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_memories_category ON memories(category);
+        CREATE INDEX IF NOT EXISTS idx_memories_key ON memories(key);
+        CREATE INDEX IF NOT EXISTS idx_memories_session ON memories(session_id);
+
+        -- FTS5 full-text search (BM25 scoring)
+        CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
+            key, content, content=memories, content_rowid=rowid
+        );
+
+        -- FTS5 triggers: keep in sync with memories table
+        CREATE TRIGGER IF NOT EXISTS memories_ai AFTER INSERT ON memories BEGIN
+            INSERT INTO memories_fts(rowid, key, content)
+            VALUES (new.rowid, new.key, new.content);
+        END;
+        CREATE TRIGGER IF NOT EXISTS memories_ad AFTER DELETE ON memories BEGIN
+            INSERT INTO memories_fts(memories_fts, rowid, key, content)
+            VALUES ('delete', old.rowid, old.key, old.content);
+        END;
+        CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE ON memories BEGIN
+            INSERT INTO memories_fts(memories_fts, rowid, key, content)
+            VALUES ('delete', old.rowid, old.key, old.content);
+            INSERT INTO memories_fts(rowid, key, content)
+            VALUES (new.rowid, new.key, new.content);
+        END;
+
+        -- Embedding cache with LRU eviction
+        CREATE TABLE IF NOT EXISTS embedding_cache (
+            content_hash TEXT PRIMARY KEY,
+            embedding    BLOB NOT NULL,
+            created_at   TEXT NOT NULL,
+            accessed_at  TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_cache_accessed ON embedding_cache(accessed_at);",
+    down: "DROP TABLE IF EXISTS embedding_cache;
+           DROP TABLE IF EXISTS memories_fts;
+           DROP TABLE IF EXISTS memories;",
+}];
+
 /// SQLite-backed persistent memory — the brain
 ///
 /// Full-stack search engine:
@@ -126,63 +180,20 @@ impl SqliteMemory {
 
     /// Initialize all tables: memories, FTS5, `embedding_cache`
     fn init_schema(conn: &Connection) -> anyhow::Result<()> {
-        conn.execute_batch(
-            "-- Core memories table
-            CREATE TABLE IF NOT EXISTS memories (
-                id          TEXT PRIMARY KEY,
-                key         TEXT NOT NULL UNIQUE,
-                content     TEXT NOT NULL,
-                category    TEXT NOT NULL DEFAULT 'core',
-                embedding   BLOB,
-                created_at  TEXT NOT NULL,
-                updated_at  TEXT NOT NULL
-            );
-            CREATE INDEX IF NOT EXISTS idx_memories_category ON memories(category);
-            CREATE INDEX IF NOT EXISTS idx_memories_key ON memories(key);
-
-            -- FTS5 full-text search (BM25 scoring)
-            CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
-                key, content, content=memories, content_rowid=rowid
-            );
-
-            -- FTS5 triggers: keep in sync with memories table
-            CREATE TRIGGER IF NOT EXISTS memories_ai AFTER INSERT ON memories BEGIN
-                INSERT INTO memories_fts(rowid, key, content)
-                VALUES (new.rowid, new.key, new.content);
-            END;
-            CREATE TRIGGER IF NOT EXISTS memories_ad AFTER DELETE ON memories BEGIN
-                INSERT INTO memories_fts(memories_fts, rowid, key, content)
-                VALUES ('delete', old.rowid, old.key, old.content);
-            END;
-            CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE ON memories BEGIN
-                INSERT INTO memories_fts(memories_fts, rowid, key, content)
-                VALUES ('delete', old.rowid, old.key, old.content);
-                INSERT INTO memories_fts(rowid, key, content)
-                VALUES (new.rowid, new.key, new.content);
-            END;
-
-            -- Embedding cache with LRU eviction
-            CREATE TABLE IF NOT EXISTS embedding_cache (
-                content_hash TEXT PRIMARY KEY,
-                embedding    BLOB NOT NULL,
-                created_at   TEXT NOT NULL,
-                accessed_at  TEXT NOT NULL
-            );
-            CREATE INDEX IF NOT EXISTS idx_cache_accessed ON embedding_cache(accessed_at);",
-        )?;
-
-        // Migration: add session_id column if not present (safe to run repeatedly)
-        let has_session_id: bool = conn
-            .prepare("SELECT sql FROM sqlite_master WHERE type='table' AND name='memories'")?
-            .query_row([], |row| row.get::<_, String>(0))?
-            .contains("session_id");
-        if !has_session_id {
-            conn.execute_batch(
-                "ALTER TABLE memories ADD COLUMN session_id TEXT;
-                 CREATE INDEX IF NOT EXISTS idx_memories_session ON memories(session_id);",
-            )?;
+        // Databases created before versioned migrations may lack session_id;
+        // patch them up so the baseline below matches.
+        let legacy_sql: Option<String> = conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE type='table' AND name='memories'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if legacy_sql.is_some_and(|sql| !sql.contains("session_id")) {
+            conn.execute_batch("ALTER TABLE memories ADD COLUMN session_id TEXT;")?;
         }
 
+        crate::schema_migrations::migrate(conn, "memory", MIGRATIONS)?;
         Ok(())
     }
 
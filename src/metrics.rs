@@ -0,0 +1,385 @@
+//! Local time series with lightweight anomaly detection.
+//!
+//! Samples the agent records (ping latency, energy use, P&L, uptime ratios,
+//! …) go to `workspace/metrics/metrics.db`. Every metric keeps an
+//! exponentially weighted mean and variance overall and per local hour of
+//! day; a new sample that deviates from its baseline by more than
+//! `[metrics] threshold` standard deviations is stored as an anomaly. The
+//! hourly baseline takes over once it has `min_samples`, so a daily cycle
+//! (solar output, evening load) is not flagged every evening.
+
+use crate::config::MetricsConfig;
+use crate::schema_migrations::{migrate, Migration};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Local, Timelike, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+
+pub(crate) const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "baseline",
+    up: "CREATE TABLE samples (
+            id     INTEGER PRIMARY KEY AUTOINCREMENT,
+            metric TEXT NOT NULL,
+            at     TEXT NOT NULL,
+            value  REAL NOT NULL
+        );
+        CREATE INDEX idx_samples_metric_at ON samples(metric, at);
+        CREATE TABLE baselines (
+            metric TEXT NOT NULL,
+            bucket INTEGER NOT NULL,
+            count  INTEGER NOT NULL,
+            mean   REAL NOT NULL,
+            var    REAL NOT NULL,
+            PRIMARY KEY (metric, bucket)
+        );
+        CREATE TABLE anomalies (
+            id       INTEGER PRIMARY KEY AUTOINCREMENT,
+            metric   TEXT NOT NULL,
+            at       TEXT NOT NULL,
+            value    REAL NOT NULL,
+            expected REAL NOT NULL,
+            score    REAL NOT NULL
+        );
+        CREATE INDEX idx_anomalies_at ON anomalies(at);",
+    down: "DROP TABLE anomalies;
+        DROP TABLE baselines;
+        DROP TABLE samples;",
+}];
+
+/// `baselines.bucket` of the overall baseline; hourly ones use 0–23.
+const OVERALL: i64 = -1;
+
+/// Exponentially weighted mean and variance of one metric (or one hour of it).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Baseline {
+    count: u32,
+    mean: f64,
+    var: f64,
+}
+
+impl Baseline {
+    fn update(&mut self, value: f64, alpha: f64) {
+        if self.count == 0 {
+            self.mean = value;
+            self.var = 0.0;
+        } else {
+            let diff = value - self.mean;
+            let increment = alpha * diff;
+            self.mean += increment;
+            self.var = (1.0 - alpha) * (self.var + diff * increment);
+        }
+        self.count = self.count.saturating_add(1);
+    }
+
+    /// Signed distance from the mean in standard deviations. The deviation
+    /// is floored at 5% of the mean so a perfectly flat series does not turn
+    /// the first tiny wobble into an infinite score.
+    fn score(&self, value: f64) -> f64 {
+        let std = self.var.sqrt().max(self.mean.abs() * 0.05).max(1e-9);
+        (value - self.mean) / std
+    }
+}
+
+/// A sample that deviated from its baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    pub metric: String,
+    pub at: DateTime<Utc>,
+    pub value: f64,
+    pub expected: f64,
+    pub score: f64,
+    /// Another anomaly of this metric was stored within `cooldown_minutes`.
+    pub repeat: bool,
+}
+
+/// Latest state of one metric.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSummary {
+    pub metric: String,
+    pub samples: u64,
+    pub last_at: DateTime<Utc>,
+    pub last_value: f64,
+    pub mean: f64,
+    pub std: f64,
+}
+
+/// Metric names are dotted identifiers such as `ping.gateway_ms`.
+pub fn validate_name(metric: &str) -> Result<()> {
+    if metric.is_empty()
+        || metric.len() > 64
+        || !metric
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+    {
+        bail!("Invalid metric name '{metric}': use up to 64 letters, digits, '.', '_' or '-'");
+    }
+    Ok(())
+}
+
+pub fn db_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("metrics").join("metrics.db")
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let path = db_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let conn = Connection::open(&path)
+        .with_context(|| format!("Failed to open metrics DB: {}", path.display()))?;
+    migrate(&conn, "metrics", MIGRATIONS)?;
+    f(&conn)
+}
+
+fn parse_time(raw: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(raw).map_or_else(|_| Utc::now(), |t| t.with_timezone(&Utc))
+}
+
+fn load_baseline(conn: &Connection, metric: &str, bucket: i64) -> Result<Baseline> {
+    Ok(conn
+        .query_row(
+            "SELECT count, mean, var FROM baselines WHERE metric = ?1 AND bucket = ?2",
+            params![metric, bucket],
+            |row| {
+                Ok(Baseline {
+                    count: row.get(0)?,
+                    mean: row.get(1)?,
+                    var: row.get(2)?,
+                })
+            },
+        )
+        .optional()?
+        .unwrap_or_default())
+}
+
+fn save_baseline(conn: &Connection, metric: &str, bucket: i64, baseline: Baseline) -> Result<()> {
+    conn.execute(
+        "INSERT INTO baselines (metric, bucket, count, mean, var) VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(metric, bucket) DO UPDATE SET
+            count = excluded.count, mean = excluded.mean, var = excluded.var",
+        params![metric, bucket, baseline.count, baseline.mean, baseline.var],
+    )?;
+    Ok(())
+}
+
+/// Store one sample, update its baselines and return the anomaly it
+/// represents, if any. Samples older than `retention_days` are pruned.
+pub fn record(
+    workspace_dir: &Path,
+    config: &MetricsConfig,
+    metric: &str,
+    value: f64,
+    at: DateTime<Utc>,
+) -> Result<Option<Anomaly>> {
+    validate_name(metric)?;
+    if !value.is_finite() {
+        bail!("Metric value must be a finite number");
+    }
+    let alpha = config.alpha.clamp(0.001, 1.0);
+    let hour = i64::from(at.with_timezone(&Local).hour());
+
+    with_connection(workspace_dir, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        let mut overall = load_baseline(&tx, metric, OVERALL)?;
+        let mut hourly = load_baseline(&tx, metric, hour)?;
+        let reference = if config.seasonal && hourly.count >= config.min_samples {
+            hourly
+        } else {
+            overall
+        };
+
+        let at_raw = at.to_rfc3339();
+        let mut anomaly = None;
+        let score = reference.score(value);
+        if reference.count >= config.min_samples.max(1) && score.abs() >= config.threshold {
+            let cooldown_start = at - Duration::minutes(config.cooldown_minutes.into());
+            let repeat: bool = tx.query_row(
+                "SELECT EXISTS(SELECT 1 FROM anomalies WHERE metric = ?1 AND at >= ?2)",
+                params![metric, cooldown_start.to_rfc3339()],
+                |row| row.get(0),
+            )?;
+            tx.execute(
+                "INSERT INTO anomalies (metric, at, value, expected, score)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![metric, at_raw, value, reference.mean, score],
+            )?;
+            anomaly = Some(Anomaly {
+                metric: metric.to_string(),
+                at,
+                value,
+                expected: reference.mean,
+                score,
+                repeat,
+            });
+        }
+
+        overall.update(value, alpha);
+        hourly.update(value, alpha);
+        save_baseline(&tx, metric, OVERALL, overall)?;
+        save_baseline(&tx, metric, hour, hourly)?;
+        tx.execute(
+            "INSERT INTO samples (metric, at, value) VALUES (?1, ?2, ?3)",
+            params![metric, at_raw, value],
+        )?;
+        let cutoff = at - Duration::days(config.retention_days.max(1).into());
+        tx.execute(
+            "DELETE FROM samples WHERE metric = ?1 AND at < ?2",
+            params![metric, cutoff.to_rfc3339()],
+        )?;
+        tx.execute(
+            "DELETE FROM anomalies WHERE metric = ?1 AND at < ?2",
+            params![metric, cutoff.to_rfc3339()],
+        )?;
+        tx.commit()?;
+        Ok(anomaly)
+    })
+}
+
+/// Every metric with its latest sample and overall baseline.
+pub fn summaries(workspace_dir: &Path) -> Result<Vec<MetricSummary>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT s.metric, COUNT(*), MAX(s.at),
+                    (SELECT value FROM samples l WHERE l.metric = s.metric
+                     ORDER BY l.at DESC, l.id DESC LIMIT 1),
+                    COALESCE(b.mean, 0), COALESCE(b.var, 0)
+             FROM samples s
+             LEFT JOIN baselines b ON b.metric = s.metric AND b.bucket = -1
+             GROUP BY s.metric ORDER BY s.metric",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(MetricSummary {
+                metric: row.get(0)?,
+                samples: row.get(1)?,
+                last_at: parse_time(&row.get::<_, String>(2)?),
+                last_value: row.get(3)?,
+                mean: row.get(4)?,
+                std: row.get::<_, f64>(5)?.sqrt(),
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    })
+}
+
+/// Samples of `metric` since `since`, oldest first.
+pub fn history(
+    workspace_dir: &Path,
+    metric: &str,
+    since: DateTime<Utc>,
+) -> Result<Vec<(DateTime<Utc>, f64)>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT at, value FROM samples WHERE metric = ?1 AND at >= ?2 ORDER BY at, id",
+        )?;
+        let rows = stmt.query_map(params![metric, since.to_rfc3339()], |row| {
+            Ok((parse_time(&row.get::<_, String>(0)?), row.get(1)?))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    })
+}
+
+/// Stored anomalies since `since`, newest first.
+pub fn anomalies(
+    workspace_dir: &Path,
+    since: DateTime<Utc>,
+    metric: Option<&str>,
+) -> Result<Vec<Anomaly>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT metric, at, value, expected, score FROM anomalies
+             WHERE at >= ?1 AND (?2 IS NULL OR metric = ?2)
+             ORDER BY at DESC, id DESC",
+        )?;
+        let rows = stmt.query_map(params![since.to_rfc3339(), metric], |row| {
+            Ok(Anomaly {
+                metric: row.get(0)?,
+                at: parse_time(&row.get::<_, String>(1)?),
+                value: row.get(2)?,
+                expected: row.get(3)?,
+                score: row.get(4)?,
+                repeat: false,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn baseline_tracks_mean_and_scores_deviations() {
+        let mut baseline = Baseline::default();
+        for value in [20.0, 22.0, 19.0, 21.0, 20.0, 23.0, 19.0, 20.0] {
+            baseline.update(value, 0.2);
+        }
+        assert_eq!(baseline.count, 8);
+        assert!((baseline.mean - 20.5).abs() < 1.0);
+        assert!(baseline.score(21.0).abs() < 1.0);
+        assert!(baseline.score(60.0) > 5.0);
+
+        // A flat series only flags changes beyond the 5% floor.
+        let mut flat = Baseline::default();
+        for _ in 0..10 {
+            flat.update(100.0, 0.1);
+        }
+        assert!(flat.score(101.0).abs() < 1.0);
+        assert!(flat.score(150.0) > 3.0);
+    }
+
+    #[test]
+    fn records_samples_and_flags_anomalies_with_cooldown() {
+        let tmp = TempDir::new().unwrap();
+        let config = MetricsConfig {
+            min_samples: 5,
+            seasonal: false,
+            ..MetricsConfig::default()
+        };
+        let start = Utc::now() - Duration::hours(1);
+        for i in 0..10 {
+            let value = if i % 2 == 0 { 20.0 } else { 22.0 };
+            let at = start + Duration::minutes(i);
+            assert!(record(tmp.path(), &config, "ping.gw_ms", value, at)
+                .unwrap()
+                .is_none());
+        }
+
+        let spike = record(
+            tmp.path(),
+            &config,
+            "ping.gw_ms",
+            90.0,
+            start + Duration::minutes(20),
+        )
+        .unwrap()
+        .expect("spike should be anomalous");
+        assert!(spike.score > config.threshold);
+        assert!(!spike.repeat);
+        let again = record(
+            tmp.path(),
+            &config,
+            "ping.gw_ms",
+            200.0,
+            start + Duration::minutes(21),
+        )
+        .unwrap()
+        .expect("second spike is still anomalous");
+        assert!(again.repeat);
+
+        let summaries = summaries(tmp.path()).unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].samples, 12);
+        assert!((summaries[0].last_value - 200.0).abs() < f64::EPSILON);
+        assert_eq!(
+            anomalies(tmp.path(), start, Some("ping.gw_ms"))
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(record(tmp.path(), &config, "bad name", 1.0, Utc::now()).is_err());
+    }
+}
@@ -26,11 +26,137 @@ struct MigrationStats {
 pub async fn handle_command(command: crate::MigrateCommands, config: &Config) -> Result<()> {
     match command {
         crate::MigrateCommands::Openclaw { source, dry_run } => {
-            migrate_openclaw_memory(config, source, dry_run).await
+            migrate_openclaw_memory(config, source.clone(), dry_run).await?;
+            migrate_openclaw_config(config, source, dry_run)
+        }
+        crate::MigrateCommands::Assistant { file, dry_run } => {
+            migrate_openai_assistants(config, &file, dry_run)
+        }
+        crate::MigrateCommands::Status => schema_status(config),
+        crate::MigrateCommands::Rollback { store, to, yes } => {
+            schema_rollback(config, &store, to, yes)
+        }
+    }
+}
+
+fn state_store(
+    config: &Config,
+    store: &str,
+) -> Result<(PathBuf, &'static [crate::schema_migrations::Migration])> {
+    match store {
+        "cron" => Ok((
+            config.workspace_dir.join("cron").join("jobs.db"),
+            crate::cron::SCHEMA_MIGRATIONS,
+        )),
+        "memory" => Ok((
+            config.workspace_dir.join("memory").join("brain.db"),
+            memory::sqlite::MIGRATIONS,
+        )),
+        "inventory" => Ok((
+            crate::inventory::db_path(&config.workspace_dir),
+            crate::inventory::MIGRATIONS,
+        )),
+        "metrics" => Ok((
+            crate::metrics::db_path(&config.workspace_dir),
+            crate::metrics::MIGRATIONS,
+        )),
+        "facts" => Ok((
+            crate::facts::db_path(&config.workspace_dir),
+            crate::facts::MIGRATIONS,
+        )),
+        "analytics" => Ok((
+            crate::analytics::db_path(&config.workspace_dir),
+            crate::analytics::MIGRATIONS,
+        )),
+        "feedback" => Ok((
+            crate::gateway::feedback::db_path(&config.workspace_dir),
+            crate::gateway::feedback::MIGRATIONS,
+        )),
+        other => {
+            bail!("Unknown store '{other}'; expected 'cron', 'memory', 'inventory', 'metrics', 'facts', 'analytics' or 'feedback'")
         }
     }
 }
 
+fn schema_status(config: &Config) -> Result<()> {
+    for store in [
+        "cron",
+        "memory",
+        "inventory",
+        "metrics",
+        "facts",
+        "analytics",
+        "feedback",
+    ] {
+        let (db_path, migrations) = state_store(config, store)?;
+        println!("{store} ({})", db_path.display());
+        if !db_path.exists() {
+            println!("  not created yet");
+            continue;
+        }
+        let conn = Connection::open(&db_path)
+            .with_context(|| format!("Failed to open {}", db_path.display()))?;
+        let applied = crate::schema_migrations::applied(&conn)?;
+        for migration in migrations {
+            match applied.iter().find(|a| a.version == migration.version) {
+                Some(record) => println!(
+                    "  ✅ {:>4} {} (applied {})",
+                    migration.version, migration.name, record.applied_at
+                ),
+                None => println!("  ⏳ {:>4} {} (pending)", migration.version, migration.name),
+            }
+        }
+        for record in applied
+            .iter()
+            .filter(|a| a.version as usize > migrations.len())
+        {
+            println!(
+                "  ⚠️ {:>4} {} (unknown to this build)",
+                record.version, record.name
+            );
+        }
+    }
+    Ok(())
+}
+
+fn schema_rollback(config: &Config, store: &str, to: u32, yes: bool) -> Result<()> {
+    let (db_path, migrations) = state_store(config, store)?;
+    if !db_path.exists() {
+        bail!("{store} database not found at {}", db_path.display());
+    }
+    if !yes {
+        let confirmed = dialoguer::Confirm::new()
+            .with_prompt(format!(
+                "  Revert {store} schema migrations above version {to}? Reverted steps may drop data"
+            ))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+    // Reverting the baseline drops every table of the store (all cron jobs,
+    // all memories, ...), so it only runs once a fresh backup is on disk.
+    if to == 0 {
+        let archive = crate::backup::create_backup(config).with_context(|| {
+            format!("Refusing to roll {store} back to version 0 without a backup")
+        })?;
+        println!("📦 Backup written to {}", archive.display());
+    }
+    let conn = Connection::open(&db_path)
+        .with_context(|| format!("Failed to open {}", db_path.display()))?;
+    let reverted = crate::schema_migrations::rollback(&conn, store, migrations, to)?;
+    if reverted.is_empty() {
+        println!("{store} schema is already at or below version {to}.");
+    } else {
+        println!(
+            "✅ Reverted {store} migrations {reverted:?}; the next start re-applies them unless you downgrade."
+        );
+    }
+    Ok(())
+}
+
 async fn migrate_openclaw_memory(
     config: &Config,
     source_workspace: Option<PathBuf>,
@@ -113,6 +239,373 @@ async fn migrate_openclaw_memory(
     Ok(())
 }
 
+/// Workspace prompt files OpenClaw and ZeroClaw both inject into the system prompt.
+const OPENCLAW_PROMPT_FILES: [&str; 6] = [
+    "AGENTS.md",
+    "SOUL.md",
+    "TOOLS.md",
+    "IDENTITY.md",
+    "USER.md",
+    "HEARTBEAT.md",
+];
+
+#[derive(Debug, Default)]
+struct ConfigImportStats {
+    prompts: usize,
+    skills: usize,
+    schedules: usize,
+    skipped: Vec<String>,
+}
+
+impl ConfigImportStats {
+    fn print(&self, dry_run: bool) {
+        let verb = if dry_run { "Would import" } else { "Imported" };
+        println!(
+            "  {verb} {} prompt files, {} skills, {} schedules",
+            self.prompts, self.skills, self.schedules
+        );
+        for reason in &self.skipped {
+            println!("  ⚠️ skipped {reason}");
+        }
+    }
+}
+
+/// Import OpenClaw prompt files, skills and cron jobs. Nothing that already
+/// exists in the ZeroClaw workspace is overwritten.
+fn migrate_openclaw_config(
+    config: &Config,
+    source_workspace: Option<PathBuf>,
+    dry_run: bool,
+) -> Result<()> {
+    let source_workspace = resolve_openclaw_workspace(source_workspace)?;
+    let mut stats = ConfigImportStats::default();
+
+    for name in OPENCLAW_PROMPT_FILES {
+        let src = source_workspace.join(name);
+        if !src.is_file() {
+            continue;
+        }
+        let dst = config.workspace_dir.join(name);
+        if dst.exists() {
+            stats.skipped.push(format!("{name}: already present"));
+            continue;
+        }
+        if !dry_run {
+            fs::create_dir_all(&config.workspace_dir)?;
+            fs::copy(&src, &dst).with_context(|| format!("Failed to copy {}", src.display()))?;
+        }
+        stats.prompts += 1;
+    }
+
+    let skills_src = source_workspace.join("skills");
+    if skills_src.is_dir() {
+        for entry in fs::read_dir(&skills_src)?.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            let dst = crate::skills::skills_dir(&config.workspace_dir).join(&name);
+            if dst.exists() {
+                stats.skipped.push(format!("skill {name}: already present"));
+                continue;
+            }
+            if !dry_run {
+                copy_dir(&path, &dst)?;
+            }
+            stats.skills += 1;
+        }
+    }
+
+    // OpenClaw keeps cron jobs next to the workspace: ~/.openclaw/cron/jobs.json
+    let jobs_path = source_workspace
+        .parent()
+        .map(|root| root.join("cron").join("jobs.json"));
+    if let Some(jobs_path) = jobs_path.filter(|p| p.is_file()) {
+        let raw = fs::read_to_string(&jobs_path)
+            .with_context(|| format!("Failed to read {}", jobs_path.display()))?;
+        let parsed: serde_json::Value = serde_json::from_str(&raw)
+            .with_context(|| format!("Failed to parse {}", jobs_path.display()))?;
+        let existing: HashSet<String> = crate::cron::list_jobs(config)?
+            .into_iter()
+            .filter_map(|job| job.name)
+            .collect();
+        let jobs = parsed
+            .get("jobs")
+            .and_then(serde_json::Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        for job in &jobs {
+            match convert_openclaw_job(job) {
+                Ok(imported) if existing.contains(&imported.name) => stats
+                    .skipped
+                    .push(format!("schedule {}: already present", imported.name)),
+                Ok(imported) => {
+                    if !dry_run {
+                        imported.create(config)?;
+                    }
+                    stats.schedules += 1;
+                }
+                Err(reason) => stats.skipped.push(format!("schedule: {reason}")),
+            }
+        }
+    }
+
+    println!("OpenClaw config from {}:", source_workspace.display());
+    stats.print(dry_run);
+    Ok(())
+}
+
+fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)?.flatten() {
+        let path = entry.path();
+        let target = dst.join(entry.file_name());
+        // Symlinks are neither: the skill audit rejects them anyway.
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_dir(&path, &target)?;
+        } else if file_type.is_file() {
+            fs::copy(&path, &target)
+                .with_context(|| format!("Failed to copy {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+struct ImportedJob {
+    name: String,
+    schedule: crate::cron::Schedule,
+    /// Agent prompt, or shell command when `shell` is set.
+    body: String,
+    shell: bool,
+    session_target: crate::cron::SessionTarget,
+    model: Option<String>,
+    enabled: bool,
+    delete_after_run: bool,
+}
+
+impl ImportedJob {
+    fn create(self, config: &Config) -> Result<()> {
+        let job = if self.shell {
+            crate::cron::add_shell_job(config, Some(self.name), self.schedule, &self.body)?
+        } else {
+            crate::cron::add_agent_job(
+                config,
+                Some(self.name),
+                self.schedule,
+                &self.body,
+                self.session_target,
+                self.model,
+                None,
+                self.delete_after_run,
+                None,
+            )?
+        };
+        if !self.enabled {
+            crate::cron::update_job(
+                config,
+                &job.id,
+                crate::cron::CronJobPatch {
+                    enabled: Some(false),
+                    ..crate::cron::CronJobPatch::default()
+                },
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Map one entry of OpenClaw's `cron/jobs.json` onto a ZeroClaw cron job.
+fn convert_openclaw_job(job: &serde_json::Value) -> std::result::Result<ImportedJob, String> {
+    let str_field = |value: &serde_json::Value, key: &str| {
+        value
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string)
+    };
+    let name = str_field(job, "name")
+        .or_else(|| str_field(job, "id"))
+        .ok_or("job without name or id")?;
+
+    let schedule = job.get("schedule").ok_or(format!("{name}: no schedule"))?;
+    let schedule = match str_field(schedule, "kind").as_deref() {
+        Some("cron") => crate::cron::Schedule::Cron {
+            expr: str_field(schedule, "expr").ok_or(format!("{name}: cron without expr"))?,
+            tz: str_field(schedule, "tz"),
+        },
+        Some("every") => crate::cron::Schedule::Every {
+            every_ms: schedule
+                .get("everyMs")
+                .and_then(serde_json::Value::as_u64)
+                .ok_or(format!("{name}: every without everyMs"))?,
+        },
+        Some("at") => {
+            let at = match schedule.get("atMs").and_then(serde_json::Value::as_i64) {
+                Some(ms) => chrono::DateTime::from_timestamp_millis(ms),
+                None => str_field(schedule, "at")
+                    .and_then(|at| chrono::DateTime::parse_from_rfc3339(&at).ok())
+                    .map(|at| at.with_timezone(&chrono::Utc)),
+            };
+            crate::cron::Schedule::At {
+                at: at.ok_or(format!("{name}: at without a valid time"))?,
+            }
+        }
+        other => return Err(format!("{name}: unsupported schedule kind {other:?}")),
+    };
+
+    let payload = job.get("payload").ok_or(format!("{name}: no payload"))?;
+    let (body, shell) = match str_field(payload, "kind").as_deref() {
+        Some("agentTurn") => (str_field(payload, "message"), false),
+        Some("systemEvent") => (str_field(payload, "text"), false),
+        Some("shell" | "command") => (str_field(payload, "command"), true),
+        other => return Err(format!("{name}: unsupported payload kind {other:?}")),
+    };
+    let body = body.ok_or(format!("{name}: empty payload"))?;
+
+    Ok(ImportedJob {
+        session_target: match str_field(job, "sessionTarget").as_deref() {
+            Some("main") => crate::cron::SessionTarget::Main,
+            _ => crate::cron::SessionTarget::Isolated,
+        },
+        model: str_field(payload, "model"),
+        enabled: job
+            .get("enabled")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true),
+        delete_after_run: job
+            .get("deleteAfterRun")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false),
+        name,
+        schedule,
+        body,
+        shell,
+    })
+}
+
+/// `SKILL.toml` written for an imported assistant.
+#[derive(Debug, serde::Serialize)]
+struct AssistantSkill {
+    prompts: Vec<String>,
+    skill: AssistantSkillMeta,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct AssistantSkillMeta {
+    name: String,
+    description: String,
+    tags: Vec<String>,
+}
+
+/// Convert an OpenAI Assistants export (one assistant, an array, or a list
+/// response with `data`) into workspace skill packs.
+fn migrate_openai_assistants(config: &Config, file: &Path, dry_run: bool) -> Result<()> {
+    let raw =
+        fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let parsed: serde_json::Value = serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse {}", file.display()))?;
+    let assistants = match parsed {
+        serde_json::Value::Array(items) => items,
+        serde_json::Value::Object(ref obj)
+            if obj.get("data").is_some_and(serde_json::Value::is_array) =>
+        {
+            obj["data"].as_array().cloned().unwrap_or_default()
+        }
+        single => vec![single],
+    };
+
+    let mut stats = ConfigImportStats::default();
+    for (idx, assistant) in assistants.iter().enumerate() {
+        let (slug, manifest, notes) = convert_assistant(assistant, idx)?;
+        stats.skipped.extend(notes);
+        let dir = crate::skills::skills_dir(&config.workspace_dir).join(&slug);
+        if dir.exists() {
+            stats.skipped.push(format!("skill {slug}: already present"));
+            continue;
+        }
+        if !dry_run {
+            fs::create_dir_all(&dir)?;
+            fs::write(dir.join("SKILL.toml"), toml::to_string(&manifest)?)?;
+        }
+        stats.skills += 1;
+    }
+
+    println!("OpenAI assistants from {}:", file.display());
+    stats.print(dry_run);
+    Ok(())
+}
+
+fn convert_assistant(
+    assistant: &serde_json::Value,
+    idx: usize,
+) -> Result<(String, AssistantSkill, Vec<String>)> {
+    let field = |key: &str| {
+        assistant
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+    let name = field("name").map_or_else(|| format!("assistant-{}", idx + 1), str::to_string);
+    let slug: String = name
+        .to_ascii_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let slug = if slug.is_empty() {
+        format!("assistant-{}", idx + 1)
+    } else {
+        slug
+    };
+    let instructions = field("instructions")
+        .with_context(|| format!("Assistant {name} has no instructions to import"))?;
+
+    let mut notes = Vec::new();
+    if let Some(model) = field("model") {
+        notes.push(format!(
+            "{name}: model {model} (set default_model in config.toml if wanted)"
+        ));
+    }
+    for tool in assistant
+        .get("tools")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let note = match tool.get("type").and_then(serde_json::Value::as_str) {
+            Some("code_interpreter") => "code_interpreter (use the shell tool)".to_string(),
+            Some("file_search") => "file_search (use memory or file_read)".to_string(),
+            Some("function") => format!(
+                "function {} (no implementation to import; add a [[tools]] entry)",
+                tool.pointer("/function/name")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("?")
+            ),
+            other => format!("tool {other:?}"),
+        };
+        notes.push(format!("{name}: {note}"));
+    }
+
+    let manifest = AssistantSkill {
+        prompts: vec![instructions.to_string()],
+        skill: AssistantSkillMeta {
+            description: field("description").map_or_else(
+                || format!("Imported from OpenAI assistant {name}"),
+                str::to_string,
+            ),
+            name,
+            tags: vec!["openai-assistant".into()],
+        },
+    };
+    Ok((slug, manifest, notes))
+}
+
 fn target_memory_backend(config: &Config) -> Result<Box<dyn Memory>> {
     memory::create_memory_for_migration(&config.memory.backend, &config.workspace_dir)
 }
@@ -440,6 +933,109 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rollback_to_baseline_takes_a_backup_first() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(tmp.path());
+        let db_path = config.workspace_dir.join("cron").join("jobs.db");
+        std::fs::create_dir_all(db_path.parent().unwrap()).unwrap();
+        let conn = Connection::open(&db_path).unwrap();
+        crate::schema_migrations::migrate(&conn, "cron", crate::cron::SCHEMA_MIGRATIONS).unwrap();
+        drop(conn);
+
+        schema_rollback(&config, "cron", 0, true).unwrap();
+
+        let archives: Vec<_> = std::fs::read_dir(crate::backup::backup_dir(&config))
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tar.gz"))
+            .collect();
+        assert_eq!(archives.len(), 1);
+        let conn = Connection::open(&db_path).unwrap();
+        assert!(crate::schema_migrations::applied(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn openclaw_jobs_map_onto_cron_schedules() {
+        let job = serde_json::json!({
+            "id": "j1",
+            "name": "morning brief",
+            "enabled": false,
+            "sessionTarget": "main",
+            "schedule": { "kind": "cron", "expr": "0 8 * * *", "tz": "Europe/Berlin" },
+            "payload": { "kind": "agentTurn", "message": "Summarize my inbox", "model": "gpt-4o" }
+        });
+        let imported = convert_openclaw_job(&job).unwrap();
+        assert_eq!(imported.name, "morning brief");
+        assert_eq!(imported.body, "Summarize my inbox");
+        assert!(!imported.shell && !imported.enabled);
+        assert_eq!(imported.session_target, crate::cron::SessionTarget::Main);
+        assert!(matches!(
+            imported.schedule,
+            crate::cron::Schedule::Cron { ref expr, ref tz }
+                if expr == "0 8 * * *" && tz.as_deref() == Some("Europe/Berlin")
+        ));
+
+        let every = serde_json::json!({
+            "id": "j2",
+            "schedule": { "kind": "every", "everyMs": 60000 },
+            "payload": { "kind": "systemEvent", "text": "tick" }
+        });
+        let imported = convert_openclaw_job(&every).unwrap();
+        assert_eq!(imported.name, "j2");
+        assert!(matches!(
+            imported.schedule,
+            crate::cron::Schedule::Every { every_ms: 60000 }
+        ));
+
+        let unknown = serde_json::json!({
+            "name": "odd",
+            "schedule": { "kind": "lunar" },
+            "payload": { "kind": "agentTurn", "message": "x" }
+        });
+        assert!(convert_openclaw_job(&unknown)
+            .unwrap_err()
+            .contains("unsupported schedule kind"));
+    }
+
+    #[test]
+    fn openai_assistants_become_skill_packs() {
+        let dir = TempDir::new().unwrap();
+        let config = test_config(dir.path());
+        let export = dir.path().join("assistants.json");
+        fs::write(
+            &export,
+            serde_json::json!({
+                "object": "list",
+                "data": [{
+                    "id": "asst_1",
+                    "name": "Release Notes Writer",
+                    "model": "gpt-4o",
+                    "instructions": "Write concise release notes.",
+                    "tools": [{ "type": "function", "function": { "name": "get_commits" } }]
+                }]
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        migrate_openai_assistants(&config, &export, true).unwrap();
+        let skill_dir = dir.path().join("skills").join("release-notes-writer");
+        assert!(!skill_dir.exists());
+
+        migrate_openai_assistants(&config, &export, false).unwrap();
+        let manifest = fs::read_to_string(skill_dir.join("SKILL.toml")).unwrap();
+        let parsed: toml::Value = toml::from_str(&manifest).unwrap();
+        assert_eq!(
+            parsed["skill"]["name"].as_str(),
+            Some("Release Notes Writer")
+        );
+        assert_eq!(
+            parsed["prompts"][0].as_str(),
+            Some("Write concise release notes.")
+        );
+    }
+
     #[test]
     fn parse_structured_markdown_line() {
         let line = "**user_pref**: likes Rust";
@@ -0,0 +1,477 @@
+//! Push notification delivery.
+//!
+//! A [`Notification`] uses ntfy's model (title, message, priority 1–5, tags,
+//! click URL). Each configured target maps it onto its own service, so the
+//! `notify` tool keeps one schema whatever backend a household member uses.
+//! Targets are configured either with per-kind fields or with an
+//! Apprise-style URL (`tgram://`, `discord://`, `mailto://`, …); see
+//! [`apprise`].
+
+use crate::config::{NotifyConfig, NotifyTargetConfig};
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fmt::Write;
+
+mod apprise;
+
+const NTFY_DEFAULT_URL: &str = "https://ntfy.sh";
+const PUSHOVER_API_URL: &str = "https://api.pushover.net/1/messages.json";
+/// ntfy turns longer messages into attachments.
+const NTFY_MAX_CHARS: usize = 4000;
+/// Pushover truncates longer messages.
+const PUSHOVER_MAX_CHARS: usize = 1024;
+
+/// Backend-independent notification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub title: Option<String>,
+    pub message: String,
+    /// 1 (min) … 3 (default) … 5 (max/urgent)
+    pub priority: u8,
+    pub tags: Vec<String>,
+    pub click: Option<String>,
+}
+
+impl Notification {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            title: None,
+            message: message.into(),
+            priority: 3,
+            tags: Vec::new(),
+            click: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Body {
+    Json(Value),
+    Form(Vec<(&'static str, String)>),
+}
+
+/// HTTP request for one target, built without touching the network.
+#[derive(Debug, Clone, PartialEq)]
+struct Prepared {
+    url: String,
+    headers: Vec<(&'static str, String)>,
+    body: Body,
+}
+
+/// A resolved delivery endpoint, from either the per-kind fields or an
+/// Apprise-style `uri`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Endpoint {
+    Ntfy {
+        url: String,
+        topic: String,
+        auth: Option<String>,
+    },
+    Gotify {
+        url: String,
+        token: String,
+    },
+    Pushover {
+        url: String,
+        token: String,
+        user: String,
+    },
+    Telegram {
+        bot_token: String,
+        chat_ids: Vec<String>,
+    },
+    Discord {
+        webhook_id: String,
+        webhook_token: String,
+    },
+    Slack {
+        webhook_url: String,
+    },
+    Json {
+        url: String,
+    },
+    Email(apprise::EmailEndpoint),
+}
+
+fn required<'a>(value: Option<&'a String>, what: &str, target: &str) -> Result<&'a str> {
+    value
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .with_context(|| format!("notify target '{target}' needs `{what}`"))
+}
+
+fn endpoint(target: &NotifyTargetConfig) -> Result<Endpoint> {
+    if let Some(uri) = target.uri.as_deref().filter(|u| !u.trim().is_empty()) {
+        return apprise::parse(uri.trim())
+            .with_context(|| format!("notify target '{}' has an invalid uri", target.name));
+    }
+    match target.kind.as_str() {
+        "ntfy" => Ok(Endpoint::Ntfy {
+            url: target
+                .url
+                .as_deref()
+                .unwrap_or(NTFY_DEFAULT_URL)
+                .trim_end_matches('/')
+                .to_string(),
+            topic: required(target.topic.as_ref(), "topic", &target.name)?.to_string(),
+            auth: target.token.as_ref().map(|token| format!("Bearer {token}")),
+        }),
+        "gotify" => Ok(Endpoint::Gotify {
+            url: required(target.url.as_ref(), "url", &target.name)?
+                .trim_end_matches('/')
+                .to_string(),
+            token: required(target.token.as_ref(), "token", &target.name)?.to_string(),
+        }),
+        "pushover" => Ok(Endpoint::Pushover {
+            url: target
+                .url
+                .clone()
+                .unwrap_or_else(|| PUSHOVER_API_URL.to_string()),
+            token: required(target.token.as_ref(), "token", &target.name)?.to_string(),
+            user: required(target.user_key.as_ref(), "user_key", &target.name)?.to_string(),
+        }),
+        other => anyhow::bail!(
+            "notify target '{}' has unknown kind '{other}' (use ntfy, gotify, or pushover, or set uri)",
+            target.name
+        ),
+    }
+}
+
+/// Plain-text rendering for services without separate title or tag fields.
+fn plain_text(notification: &Notification) -> String {
+    let mut text = String::new();
+    if !notification.tags.is_empty() {
+        let _ = write!(text, "[{}] ", notification.tags.join(", "));
+    }
+    if let Some(title) = &notification.title {
+        let _ = writeln!(text, "{title}");
+    }
+    text.push_str(&notification.message);
+    if let Some(click) = &notification.click {
+        let _ = write!(text, "\n{click}");
+    }
+    text
+}
+
+/// Plain-text message parts for services with no Markdown and a length cap,
+/// each paired with the title to send it under (numbered when split).
+fn plain_parts(notification: &Notification, max_chars: usize) -> Vec<(String, Option<String>)> {
+    let text = crate::channels::format::FormatProfile::PlainText.render(&notification.message);
+    let mut parts = crate::channels::format::split(&text, max_chars);
+    if parts.is_empty() {
+        parts.push(text);
+    }
+    let count = parts.len();
+    parts
+        .into_iter()
+        .enumerate()
+        .map(|(i, part)| {
+            let title = match (&notification.title, count) {
+                (title, 1) => title.clone(),
+                (Some(title), _) => Some(format!("{title} ({}/{count})", i + 1)),
+                (None, _) => Some(format!("({}/{count})", i + 1)),
+            };
+            (part, title)
+        })
+        .collect()
+}
+
+fn prepare(endpoint: &Endpoint, notification: &Notification) -> Vec<Prepared> {
+    let priority = notification.priority.clamp(1, 5);
+    match endpoint {
+        Endpoint::Ntfy { url, topic, auth } => plain_parts(notification, NTFY_MAX_CHARS)
+            .into_iter()
+            .map(|(message, title)| {
+                let mut body = json!({
+                    "topic": topic,
+                    "message": message,
+                    "priority": priority,
+                });
+                if let Some(title) = title {
+                    body["title"] = json!(title);
+                }
+                if !notification.tags.is_empty() {
+                    body["tags"] = json!(notification.tags);
+                }
+                if let Some(click) = &notification.click {
+                    body["click"] = json!(click);
+                }
+                Prepared {
+                    url: url.clone(),
+                    headers: auth
+                        .iter()
+                        .map(|auth| ("Authorization", auth.clone()))
+                        .collect(),
+                    body: Body::Json(body),
+                }
+            })
+            .collect(),
+        Endpoint::Gotify { url, token } => {
+            // Gotify has no tags; prefix them the way ntfy renders emoji tags.
+            let message = if notification.tags.is_empty() {
+                notification.message.clone()
+            } else {
+                format!(
+                    "[{}] {}",
+                    notification.tags.join(", "),
+                    notification.message
+                )
+            };
+            let gotify_priority = [0, 2, 5, 7, 10][usize::from(priority - 1)];
+            let mut body = json!({
+                "title": notification.title.as_deref().unwrap_or("ZeroClaw"),
+                "message": message,
+                "priority": gotify_priority,
+            });
+            if let Some(click) = &notification.click {
+                body["extras"] = json!({"client::notification": {"click": {"url": click}}});
+            }
+            vec![Prepared {
+                url: format!("{url}/message"),
+                headers: vec![("X-Gotify-Key", token.clone())],
+                body: Body::Json(body),
+            }]
+        }
+        Endpoint::Pushover { url, token, user } => {
+            let pushover_priority = i8::try_from(priority).unwrap_or(3) - 3;
+            plain_parts(notification, PUSHOVER_MAX_CHARS)
+                .into_iter()
+                .map(|(message, title)| {
+                    let mut form = vec![
+                        ("token", token.clone()),
+                        ("user", user.clone()),
+                        ("message", message),
+                        ("priority", pushover_priority.to_string()),
+                    ];
+                    if let Some(title) = title {
+                        form.push(("title", title));
+                    }
+                    if let Some(click) = &notification.click {
+                        form.push(("url", click.clone()));
+                    }
+                    if pushover_priority == 2 {
+                        // Emergency priority repeats until acknowledged.
+                        form.push(("retry", "60".into()));
+                        form.push(("expire", "3600".into()));
+                    }
+                    Prepared {
+                        url: url.clone(),
+                        headers: Vec::new(),
+                        body: Body::Form(form),
+                    }
+                })
+                .collect()
+        }
+        Endpoint::Telegram {
+            bot_token,
+            chat_ids,
+        } => chat_ids
+            .iter()
+            .map(|chat_id| Prepared {
+                url: format!("https://api.telegram.org/bot{bot_token}/sendMessage"),
+                headers: Vec::new(),
+                body: Body::Json(json!({
+                    "chat_id": chat_id,
+                    "text": plain_text(notification),
+                    "disable_notification": priority <= 2,
+                })),
+            })
+            .collect(),
+        Endpoint::Discord {
+            webhook_id,
+            webhook_token,
+        } => vec![Prepared {
+            url: format!("https://discord.com/api/webhooks/{webhook_id}/{webhook_token}"),
+            headers: Vec::new(),
+            body: Body::Json(json!({"content": plain_text(notification)})),
+        }],
+        Endpoint::Slack { webhook_url } => vec![Prepared {
+            url: webhook_url.clone(),
+            headers: Vec::new(),
+            body: Body::Json(json!({"text": plain_text(notification)})),
+        }],
+        Endpoint::Json { url } => vec![Prepared {
+            url: url.clone(),
+            headers: Vec::new(),
+            body: Body::Json(json!({
+                "title": notification.title,
+                "message": notification.message,
+                "priority": priority,
+                "tags": notification.tags,
+                "click": notification.click,
+            })),
+        }],
+        // Delivered over SMTP in `send`.
+        Endpoint::Email(_) => Vec::new(),
+    }
+}
+
+/// Look up a target by name, or the first configured target when `name` is `None`.
+pub fn find_target<'a>(
+    config: &'a NotifyConfig,
+    name: Option<&str>,
+) -> Result<&'a NotifyTargetConfig> {
+    match name {
+        Some(name) => config
+            .targets
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+            .with_context(|| format!("Unknown notify target '{name}'")),
+        None => config
+            .targets
+            .first()
+            .context("No [[notify.targets]] configured"),
+    }
+}
+
+/// Deliver `notification` to `target`.
+pub async fn send(
+    config: &NotifyConfig,
+    target: &NotifyTargetConfig,
+    notification: &Notification,
+) -> Result<()> {
+    let endpoint = endpoint(target)?;
+    if let Endpoint::Email(email) = endpoint {
+        let subject = notification
+            .title
+            .clone()
+            .unwrap_or_else(|| "ZeroClaw notification".into());
+        let body = plain_text(&Notification {
+            title: None,
+            ..notification.clone()
+        });
+        return tokio::task::spawn_blocking(move || email.send(&subject, &body)).await?;
+    }
+
+    let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        "tool.notify",
+        config.timeout_secs.max(1),
+        10,
+    );
+    for prepared in prepare(&endpoint, notification) {
+        let mut request = client.post(&prepared.url);
+        for (name, value) in &prepared.headers {
+            request = request.header(*name, value);
+        }
+        request = match &prepared.body {
+            Body::Json(body) => request.json(body),
+            Body::Form(form) => request.form(form),
+        };
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{} returned {status}: {}",
+                target.name,
+                crate::util::truncate_with_ellipsis(body.trim(), 200)
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(kind: &str) -> NotifyTargetConfig {
+        NotifyTargetConfig {
+            name: kind.into(),
+            kind: kind.into(),
+            url: None,
+            topic: None,
+            token: Some("tok".into()),
+            user_key: None,
+            uri: None,
+        }
+    }
+
+    fn prepare(target: &NotifyTargetConfig, notification: &Notification) -> Result<Prepared> {
+        Ok(super::prepare(&endpoint(target)?, notification).remove(0))
+    }
+
+    fn sample() -> Notification {
+        Notification {
+            title: Some("Backup".into()),
+            priority: 5,
+            tags: vec!["warning".into()],
+            click: Some("https://nas.lan".into()),
+            ..Notification::new("Disk almost full")
+        }
+    }
+
+    #[test]
+    fn ntfy_posts_json_to_server_root() {
+        let mut ntfy = target("ntfy");
+        assert!(prepare(&ntfy, &sample()).is_err());
+        ntfy.topic = Some("homelab".into());
+        let prepared = prepare(&ntfy, &sample()).unwrap();
+        assert_eq!(prepared.url, "https://ntfy.sh");
+        assert_eq!(
+            prepared.headers,
+            vec![("Authorization", "Bearer tok".into())]
+        );
+        let Body::Json(body) = prepared.body else {
+            panic!("expected JSON body");
+        };
+        assert_eq!(body["topic"], "homelab");
+        assert_eq!(body["priority"], 5);
+        assert_eq!(body["tags"], json!(["warning"]));
+    }
+
+    #[test]
+    fn gotify_scales_priority_and_sets_click_extra() {
+        let mut gotify = target("gotify");
+        gotify.url = Some("https://gotify.lan/".into());
+        let prepared = prepare(&gotify, &sample()).unwrap();
+        assert_eq!(prepared.url, "https://gotify.lan/message");
+        assert_eq!(prepared.headers, vec![("X-Gotify-Key", "tok".into())]);
+        let Body::Json(body) = prepared.body else {
+            panic!("expected JSON body");
+        };
+        assert_eq!(body["priority"], 10);
+        assert_eq!(body["message"], "[warning] Disk almost full");
+        assert_eq!(
+            body["extras"]["client::notification"]["click"]["url"],
+            "https://nas.lan"
+        );
+    }
+
+    #[test]
+    fn pushover_maps_priority_and_adds_emergency_retry() {
+        let mut pushover = target("pushover");
+        assert!(prepare(&pushover, &sample()).is_err());
+        pushover.user_key = Some("user".into());
+        let Body::Form(form) = prepare(&pushover, &sample()).unwrap().body else {
+            panic!("expected form body");
+        };
+        assert!(form.contains(&("priority", "2".into())));
+        assert!(form.contains(&("retry", "60".into())));
+
+        let Body::Form(form) = prepare(&pushover, &Notification::new("hi")).unwrap().body else {
+            panic!("expected form body");
+        };
+        assert!(form.contains(&("priority", "0".into())));
+        assert!(!form.iter().any(|(key, _)| *key == "retry"));
+    }
+
+    #[test]
+    fn long_markdown_is_flattened_and_split() {
+        let mut pushover = target("pushover");
+        pushover.user_key = Some("user".into());
+        let notification = Notification {
+            title: Some("Report".into()),
+            ..Notification::new(format!("**Summary**\n{}", "word ".repeat(400)))
+        };
+        let parts = super::prepare(&endpoint(&pushover).unwrap(), &notification);
+        assert_eq!(parts.len(), 2);
+        let Body::Form(form) = &parts[0].body else {
+            panic!("expected form body");
+        };
+        assert!(form.contains(&("title", "Report (1/2)".into())));
+        let message = &form.iter().find(|(key, _)| *key == "message").unwrap().1;
+        assert!(message.starts_with("Summary\n"));
+        assert!(message.chars().count() <= PUSHOVER_MAX_CHARS);
+    }
+}
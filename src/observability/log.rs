@@ -38,6 +38,11 @@ impl Observer for LogObserver {
                 let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
                 info!(tool = %tool, duration_ms = ms, success = success, "tool.call");
             }
+            ObserverEvent::ToolDegraded { tool, kind, reason } => {
+                info!(tool = %tool, kind = %kind, reason = %reason, "tool.degraded");
+            }
+            // Outputs can be large; tool.call already logs the outcome.
+            ObserverEvent::ToolEvidence { .. } => {}
             ObserverEvent::TurnComplete => {
                 info!("turn.complete");
             }
@@ -188,6 +188,8 @@ impl Observer for OtelObserver {
             }
             ObserverEvent::LlmRequest { .. }
             | ObserverEvent::ToolCallStart { .. }
+            | ObserverEvent::ToolDegraded { .. }
+            | ObserverEvent::ToolEvidence { .. }
             | ObserverEvent::TurnComplete => {}
             ObserverEvent::LlmResponse {
                 provider,
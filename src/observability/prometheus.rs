@@ -222,6 +222,8 @@ impl Observer for PrometheusObserver {
                 }
             }
             ObserverEvent::ToolCallStart { tool: _ }
+            | ObserverEvent::ToolDegraded { .. }
+            | ObserverEvent::ToolEvidence { .. }
             | ObserverEvent::TurnComplete
             | ObserverEvent::LlmRequest { .. } => {}
             ObserverEvent::ToolCall {
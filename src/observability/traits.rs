@@ -47,6 +47,25 @@ pub enum ObserverEvent {
         duration: Duration,
         success: bool,
     },
+    /// A tool result is missing or partial (failed, skipped, denied, rate
+    /// limited, timed out), so the answer may rest on incomplete data.
+    ToolDegraded {
+        tool: String,
+        /// `failed`, `skipped`, `denied`, `rate_limited`, `timeout`,
+        /// `circuit_open` or `unknown_tool`.
+        kind: String,
+        /// Scrubbed, truncated reason.
+        reason: String,
+    },
+    /// A successful tool result the final answer may draw on, for
+    /// provenance (citations) in gateway responses.
+    ToolEvidence {
+        tool: String,
+        /// SHA-256 of the call's JSON arguments, hex encoded.
+        args_hash: String,
+        /// Scrubbed tool output.
+        output: String,
+    },
     /// The agent produced a final answer for the current user message.
     TurnComplete,
     /// A message was sent or received through a channel.
@@ -163,10 +163,41 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
         agents: std::collections::HashMap::new(),
+        composite_tools: std::collections::HashMap::new(),
         hooks: crate::config::HooksConfig::default(),
         hardware: hardware_config,
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        phone_alert: crate::config::PhoneAlertConfig::default(),
+        sync: crate::config::SyncConfig::default(),
+        safe_mode: crate::config::SafeModeConfig::default(),
+        backup: crate::config::BackupConfig::default(),
+        cert_check: crate::config::CertCheckConfig::default(),
+        dns: crate::config::DnsConfig::default(),
+        domain_info: crate::config::DomainInfoConfig::default(),
+        updates: crate::config::UpdatesConfig::default(),
+        advisories: crate::config::AdvisoriesConfig::default(),
+        inventory: crate::config::InventoryConfig::default(),
+        uptime_kuma: crate::config::UptimeKumaConfig::default(),
+        metrics: crate::config::MetricsConfig::default(),
+        research: crate::config::ResearchConfig::default(),
+        facts: crate::config::FactsConfig::default(),
+        analytics: crate::config::AnalyticsConfig::default(),
+        locale: crate::config::LocaleConfig::default(),
+        notify: crate::config::NotifyConfig::default(),
+        downloads: crate::config::DownloadsConfig::default(),
+        media_server: crate::config::MediaServerConfig::default(),
+        printer: crate::config::PrinterConfig::default(),
+        energy: crate::config::EnergyConfig::default(),
+        location: crate::config::LocationConfig::default(),
+        people: crate::config::PeopleConfig::default(),
+        notes: crate::config::NotesConfig::default(),
+        tasks: crate::config::TasksConfig::default(),
+        webdav: crate::config::WebDavConfig::default(),
+        object_store: crate::config::ObjectStoreConfig::default(),
+        publish: crate::config::PublishConfig::default(),
+        python_repl: crate::config::PythonReplConfig::default(),
+        code_exec: crate::config::CodeExecConfig::default(),
     };
 
     println!(
@@ -511,10 +542,41 @@ async fn run_quick_setup_with_home(
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
         agents: std::collections::HashMap::new(),
+        composite_tools: std::collections::HashMap::new(),
         hooks: crate::config::HooksConfig::default(),
         hardware: crate::config::HardwareConfig::default(),
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        phone_alert: crate::config::PhoneAlertConfig::default(),
+        sync: crate::config::SyncConfig::default(),
+        safe_mode: crate::config::SafeModeConfig::default(),
+        backup: crate::config::BackupConfig::default(),
+        cert_check: crate::config::CertCheckConfig::default(),
+        dns: crate::config::DnsConfig::default(),
+        domain_info: crate::config::DomainInfoConfig::default(),
+        updates: crate::config::UpdatesConfig::default(),
+        advisories: crate::config::AdvisoriesConfig::default(),
+        inventory: crate::config::InventoryConfig::default(),
+        uptime_kuma: crate::config::UptimeKumaConfig::default(),
+        metrics: crate::config::MetricsConfig::default(),
+        research: crate::config::ResearchConfig::default(),
+        facts: crate::config::FactsConfig::default(),
+        analytics: crate::config::AnalyticsConfig::default(),
+        locale: crate::config::LocaleConfig::default(),
+        notify: crate::config::NotifyConfig::default(),
+        downloads: crate::config::DownloadsConfig::default(),
+        media_server: crate::config::MediaServerConfig::default(),
+        printer: crate::config::PrinterConfig::default(),
+        energy: crate::config::EnergyConfig::default(),
+        location: crate::config::LocationConfig::default(),
+        people: crate::config::PeopleConfig::default(),
+        notes: crate::config::NotesConfig::default(),
+        tasks: crate::config::TasksConfig::default(),
+        webdav: crate::config::WebDavConfig::default(),
+        object_store: crate::config::ObjectStoreConfig::default(),
+        publish: crate::config::PublishConfig::default(),
+        python_repl: crate::config::PythonReplConfig::default(),
+        code_exec: crate::config::CodeExecConfig::default(),
     };
 
     config.save().await?;
@@ -4272,6 +4334,9 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         Some(sasl_password.trim().to_string())
                     },
                     verify_tls: Some(verify_tls),
+                    mention_only: false,
+                    flood_burst: 4,
+                    flood_interval_ms: 2000,
                 });
             }
             ChannelMenuChoice::Webhook => {
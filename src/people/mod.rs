@@ -0,0 +1,529 @@
+//! Birthday and anniversary reminders.
+//!
+//! People and their dates come from a workspace TOML file and/or a CardDAV
+//! address book (`BDAY` / `ANNIVERSARY`). A built-in daily rule run by the
+//! daemon sends day-of reminders without involving the model, and the
+//! `people` tool exposes the same upcoming list to the agent for briefings.
+
+use crate::config::{Config, PeopleCardDavConfig};
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use regex::Regex;
+use serde::Deserialize;
+use std::fmt::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+use tokio::time::Duration;
+
+const COMPONENT: &str = "people";
+const CHECK_INTERVAL_SECS: u64 = 60;
+
+const ADDRESSBOOK_QUERY_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<card:addressbook-query xmlns:d="DAV:" xmlns:card="urn:ietf:params:xml:ns:carddav"><d:prop><d:getetag/><card:address-data/></d:prop></card:addressbook-query>"#;
+
+/// On-disk shape of `people.toml`.
+#[derive(Debug, Default, Deserialize)]
+struct PeopleFile {
+    #[serde(default)]
+    person: Vec<PersonEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PersonEntry {
+    name: String,
+    #[serde(default)]
+    birthday: Option<String>,
+    #[serde(default)]
+    anniversary: Option<String>,
+    #[serde(default)]
+    dates: Vec<CustomDateEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CustomDateEntry {
+    label: String,
+    date: String,
+}
+
+/// A recurring yearly date; `year` is known only when the source had one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurringDate {
+    pub kind: String,
+    pub month: u32,
+    pub day: u32,
+    pub year: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Person {
+    pub name: String,
+    pub dates: Vec<RecurringDate>,
+}
+
+/// The next occurrence of a person's date relative to some day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occasion {
+    pub name: String,
+    pub kind: String,
+    pub date: NaiveDate,
+    pub days_away: i64,
+    pub years: Option<i32>,
+}
+
+impl Occasion {
+    /// Human phrasing, e.g. "Alice's birthday (turns 34)".
+    pub fn describe(&self) -> String {
+        match (self.kind.as_str(), self.years) {
+            ("birthday", Some(years)) if years > 0 => {
+                format!("{}'s birthday (turns {years})", self.name)
+            }
+            ("birthday", _) => format!("{}'s birthday", self.name),
+            ("anniversary", Some(years)) if years > 0 => {
+                format!("{}'s anniversary ({years} years)", self.name)
+            }
+            ("anniversary", _) => format!("{}'s anniversary", self.name),
+            (label, Some(years)) if years > 0 => format!("{}: {label} ({years} years)", self.name),
+            (label, _) => format!("{}: {label}", self.name),
+        }
+    }
+}
+
+/// Parse `YYYY-MM-DD`, `YYYYMMDD`, `--MM-DD`, `--MMDD`, or `MM-DD` (vCard
+/// values may also carry a trailing time, which is ignored).
+pub fn parse_recurring_date(kind: &str, raw: &str) -> Option<RecurringDate> {
+    let value = raw.trim();
+    let value = value.split('T').next().unwrap_or(value);
+    let (year, rest) = if let Some(rest) = value.strip_prefix("--") {
+        (None, rest.to_string())
+    } else {
+        let digits: String = value.chars().filter(char::is_ascii_digit).collect();
+        match digits.len() {
+            8 => (
+                Some(digits[..4].parse::<i32>().ok()?),
+                digits[4..].to_string(),
+            ),
+            4 => (None, digits),
+            _ => return None,
+        }
+    };
+    let digits: String = rest.chars().filter(char::is_ascii_digit).collect();
+    if digits.len() != 4 {
+        return None;
+    }
+    let month: u32 = digits[..2].parse().ok()?;
+    let day: u32 = digits[2..].parse().ok()?;
+    // Validate against a leap year so Feb 29 is accepted.
+    NaiveDate::from_ymd_opt(2000, month, day)?;
+    Some(RecurringDate {
+        kind: kind.to_string(),
+        month,
+        day,
+        year,
+    })
+}
+
+/// Next date on or after `today` for a month/day; Feb 29 falls back to
+/// Feb 28 in non-leap years.
+fn next_occurrence(month: u32, day: u32, today: NaiveDate) -> Option<NaiveDate> {
+    (today.year()..=today.year() + 1)
+        .filter_map(|year| {
+            NaiveDate::from_ymd_opt(year, month, day).or_else(|| {
+                if month == 2 && day == 29 {
+                    NaiveDate::from_ymd_opt(year, 2, 28)
+                } else {
+                    None
+                }
+            })
+        })
+        .find(|date| *date >= today)
+}
+
+/// Occasions falling within `days` of `today` (inclusive), soonest first.
+pub fn upcoming(people: &[Person], today: NaiveDate, days: u32) -> Vec<Occasion> {
+    let mut occasions: Vec<Occasion> = people
+        .iter()
+        .flat_map(|person| {
+            person.dates.iter().filter_map(move |date| {
+                let next = next_occurrence(date.month, date.day, today)?;
+                let days_away = (next - today).num_days();
+                (days_away <= i64::from(days)).then(|| Occasion {
+                    name: person.name.clone(),
+                    kind: date.kind.clone(),
+                    date: next,
+                    days_away,
+                    years: date.year.map(|year| next.year() - year),
+                })
+            })
+        })
+        .collect();
+    occasions.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.name.cmp(&b.name)));
+    occasions
+}
+
+/// Render today's occasions followed by the rest of the window. Returns an
+/// empty string when nothing falls in range.
+pub fn format_summary(occasions: &[Occasion]) -> String {
+    let mut output = String::new();
+    let (today, later): (Vec<&Occasion>, Vec<&Occasion>) =
+        occasions.iter().partition(|o| o.days_away == 0);
+    for occasion in &today {
+        let _ = writeln!(output, "🎂 Today: {}", occasion.describe());
+    }
+    if !later.is_empty() {
+        output.push_str("Coming up:\n");
+        for occasion in later {
+            let when = if occasion.days_away == 1 {
+                "tomorrow".to_string()
+            } else {
+                format!("in {} days", occasion.days_away)
+            };
+            let _ = writeln!(
+                output,
+                "- {} ({when}): {}",
+                occasion.date.format("%a %-d %b"),
+                occasion.describe()
+            );
+        }
+    }
+    output.trim_end().to_string()
+}
+
+/// Resolve `[people].file` inside the workspace, rejecting absolute paths and `..`.
+fn dataset_path(config: &Config) -> Result<PathBuf> {
+    let relative = Path::new(config.people.file.trim());
+    if relative.as_os_str().is_empty()
+        || !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        anyhow::bail!(
+            "people.file must be a relative path inside the workspace, got '{}'",
+            config.people.file
+        );
+    }
+    Ok(config.workspace_dir.join(relative))
+}
+
+fn parse_people_toml(content: &str) -> Result<Vec<Person>> {
+    let file: PeopleFile = toml::from_str(content).context("Failed to parse people file")?;
+    Ok(file
+        .person
+        .into_iter()
+        .map(|entry| {
+            let mut dates = Vec::new();
+            let known = [
+                ("birthday", entry.birthday.as_deref()),
+                ("anniversary", entry.anniversary.as_deref()),
+            ];
+            for (kind, raw) in known {
+                let Some(raw) = raw else { continue };
+                match parse_recurring_date(kind, raw) {
+                    Some(date) => dates.push(date),
+                    None => tracing::warn!("Ignoring unparseable {kind} for {}: {raw}", entry.name),
+                }
+            }
+            for custom in &entry.dates {
+                match parse_recurring_date(&custom.label, &custom.date) {
+                    Some(date) => dates.push(date),
+                    None => tracing::warn!(
+                        "Ignoring unparseable date '{}' for {}: {}",
+                        custom.label,
+                        entry.name,
+                        custom.date
+                    ),
+                }
+            }
+            Person {
+                name: entry.name,
+                dates,
+            }
+        })
+        .collect())
+}
+
+/// Join RFC 6350 folded continuation lines.
+fn unfold_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(rest) = line.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+    lines
+}
+
+/// Extract people with `BDAY` / `ANNIVERSARY` from one or more vCards.
+fn parse_vcards(text: &str) -> Vec<Person> {
+    let mut people = Vec::new();
+    let mut current: Option<Person> = None;
+    for line in unfold_lines(text) {
+        let Some((head, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Drop group prefixes (`item1.X-ANNIVERSARY`) and parameters.
+        let name = head.split(';').next().unwrap_or(head);
+        let name = name.rsplit('.').next().unwrap_or(name).to_ascii_uppercase();
+        match name.as_str() {
+            "BEGIN" if value.eq_ignore_ascii_case("VCARD") => {
+                current = Some(Person {
+                    name: String::new(),
+                    dates: Vec::new(),
+                });
+            }
+            "END" if value.eq_ignore_ascii_case("VCARD") => {
+                if let Some(person) = current.take() {
+                    if !person.name.is_empty() && !person.dates.is_empty() {
+                        people.push(person);
+                    }
+                }
+            }
+            "FN" => {
+                if let Some(person) = current.as_mut() {
+                    person.name = value.replace("\\,", ",").replace("\\;", ";");
+                }
+            }
+            "BDAY" | "ANNIVERSARY" | "X-ANNIVERSARY" => {
+                let kind = if name == "BDAY" {
+                    "birthday"
+                } else {
+                    "anniversary"
+                };
+                if let (Some(person), Some(date)) =
+                    (current.as_mut(), parse_recurring_date(kind, value))
+                {
+                    if !person.dates.iter().any(|d| d.kind == kind) {
+                        person.dates.push(date);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    people
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&#xD;", "\r")
+        .replace("&amp;", "&")
+}
+
+async fn fetch_carddav(carddav: &PeopleCardDavConfig) -> Result<Vec<Person>> {
+    static DATA_RE: OnceLock<Regex> = OnceLock::new();
+    let data = DATA_RE.get_or_init(|| {
+        Regex::new(r"(?s)<(?:\w+:)?address-data\b[^>]*>(.*?)</(?:\w+:)?address-data>")
+            .expect("address-data regex must compile")
+    });
+
+    let client = crate::config::build_runtime_proxy_client_with_timeouts("tool.people", 30, 10);
+    let mut request = client
+        .request(reqwest::Method::from_bytes(b"REPORT")?, &carddav.url)
+        .header("Depth", "1")
+        .header("Content-Type", "application/xml; charset=utf-8")
+        .body(ADDRESSBOOK_QUERY_BODY);
+    if let Some(user) = &carddav.username {
+        request = request.basic_auth(user, carddav.password.as_deref());
+    }
+    let response = request.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("CardDAV REPORT {} returned {status}", carddav.url);
+    }
+    let body = response.text().await?;
+    Ok(data
+        .captures_iter(&body)
+        .filter_map(|c| c.get(1))
+        .flat_map(|m| parse_vcards(&xml_unescape(m.as_str())))
+        .collect())
+}
+
+/// Load people from the TOML dataset (missing file is fine) and CardDAV.
+pub async fn load_people(config: &Config) -> Result<Vec<Person>> {
+    let path = dataset_path(config)?;
+    let mut people = match tokio::fs::read_to_string(&path).await {
+        Ok(content) => parse_people_toml(&content)
+            .with_context(|| format!("Invalid people file: {}", path.display()))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    if let Some(carddav) = &config.people.carddav {
+        for person in fetch_carddav(carddav).await? {
+            // Entries in the TOML file win over address book duplicates.
+            if !people
+                .iter()
+                .any(|p| p.name.eq_ignore_ascii_case(&person.name))
+            {
+                people.push(person);
+            }
+        }
+    }
+    Ok(people)
+}
+
+fn state_path(config: &Config) -> PathBuf {
+    config.workspace_dir.join("people").join("last_reminder")
+}
+
+/// Run the daily rule once if `now` is past `remind_at` and today's
+/// reminder has not gone out yet.
+async fn check_and_remind(config: &Config, remind_at: NaiveTime) -> Result<()> {
+    let now = chrono::Local::now().naive_local();
+    let today = now.date();
+    if now.time() < remind_at {
+        return Ok(());
+    }
+    let state = state_path(config);
+    let last = tokio::fs::read_to_string(&state).await.unwrap_or_default();
+    if last.trim() == today.to_string() {
+        return Ok(());
+    }
+
+    let people = load_people(config).await?;
+    let occasions = upcoming(&people, today, config.people.lookahead_days);
+    if occasions.iter().any(|o| o.days_away == 0) {
+        let message = format_summary(&occasions);
+        match (
+            config.people.channel.as_deref(),
+            config.people.to.as_deref(),
+        ) {
+            (Some(channel), Some(to)) => {
+                crate::cron::scheduler::deliver_announcement(config, channel, to, &message).await?;
+            }
+            _ => tracing::info!("People reminder (no [people].channel configured):\n{message}"),
+        }
+    }
+
+    if let Some(parent) = state.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&state, today.to_string()).await?;
+    Ok(())
+}
+
+/// Daemon loop for the built-in daily reminder rule.
+pub async fn run(config: Config) -> Result<()> {
+    let remind_at = NaiveTime::parse_from_str(config.people.remind_at.trim(), "%H:%M")
+        .with_context(|| {
+            format!(
+                "people.remind_at must be HH:MM, got '{}'",
+                config.people.remind_at
+            )
+        })?;
+    let mut interval = tokio::time::interval(Duration::from_secs(CHECK_INTERVAL_SECS));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        interval.tick().await;
+        match check_and_remind(&config, remind_at).await {
+            Ok(()) => crate::health::mark_component_ok(COMPONENT),
+            Err(e) => {
+                crate::health::mark_component_error(COMPONENT, e.to_string());
+                tracing::warn!("People reminder failed: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parses_date_forms() {
+        let full = parse_recurring_date("birthday", "1990-04-15").unwrap();
+        assert_eq!((full.year, full.month, full.day), (Some(1990), 4, 15));
+        let vcard = parse_recurring_date("birthday", "19900415T000000Z").unwrap();
+        assert_eq!(vcard, full);
+        let no_year = parse_recurring_date("birthday", "--0415").unwrap();
+        assert_eq!((no_year.year, no_year.month, no_year.day), (None, 4, 15));
+        assert_eq!(
+            parse_recurring_date("birthday", "04-15").unwrap().year,
+            None
+        );
+        assert!(parse_recurring_date("birthday", "--02-29").is_some());
+        assert!(parse_recurring_date("birthday", "1990-13-01").is_none());
+        assert!(parse_recurring_date("birthday", "soon").is_none());
+    }
+
+    #[test]
+    fn upcoming_wraps_year_and_counts_ages() {
+        let people = parse_people_toml(
+            r#"
+[[person]]
+name = "Alice"
+birthday = "1990-01-02"
+
+[[person]]
+name = "Bob & Carol"
+anniversary = "2016-12-30"
+
+[[person]]
+name = "Dana"
+birthday = "--06-01"
+dates = [{ label = "Name day", date = "12-31" }]
+"#,
+        )
+        .unwrap();
+        let occasions = upcoming(&people, day(2026, 12, 30), 7);
+        let names: Vec<String> = occasions.iter().map(Occasion::describe).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Bob & Carol's anniversary (10 years)",
+                "Dana: Name day",
+                "Alice's birthday (turns 37)",
+            ]
+        );
+        assert_eq!(occasions[0].days_away, 0);
+        assert_eq!(occasions[2].date, day(2027, 1, 2));
+
+        let summary = format_summary(&occasions);
+        assert!(summary.starts_with("🎂 Today: Bob & Carol's anniversary"));
+        assert!(summary.contains("- Thu 31 Dec (tomorrow): Dana: Name day"));
+    }
+
+    #[test]
+    fn leap_day_birthdays_fall_back_to_feb_28() {
+        let people = vec![Person {
+            name: "Leap".into(),
+            dates: vec![parse_recurring_date("birthday", "2000-02-29").unwrap()],
+        }];
+        let occasions = upcoming(&people, day(2027, 2, 28), 0);
+        assert_eq!(occasions.len(), 1);
+        assert_eq!(occasions[0].years, Some(27));
+    }
+
+    #[test]
+    fn parses_vcards_with_groups_and_folding() {
+        let cards = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Erin\r\n  Example\r\nBDAY;VALUE=date:1985-07-04\r\nitem1.X-ANNIVERSARY:2010-09-18\r\nEND:VCARD\r\nBEGIN:VCARD\r\nFN:No Dates\r\nEND:VCARD\r\n";
+        let people = parse_vcards(cards);
+        assert_eq!(people.len(), 1);
+        assert_eq!(people[0].name, "Erin Example");
+        assert_eq!(people[0].dates.len(), 2);
+        assert_eq!(people[0].dates[1].kind, "anniversary");
+    }
+
+    #[test]
+    fn dataset_path_stays_in_workspace() {
+        let mut config = Config::default();
+        config.people.file = "../people.toml".into();
+        assert!(dataset_path(&config).is_err());
+        config.people.file = "data/people.toml".into();
+        assert!(dataset_path(&config).unwrap().ends_with("data/people.toml"));
+    }
+}
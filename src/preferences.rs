@@ -0,0 +1,245 @@
+//! Explicit user preferences.
+//!
+//! Standing instructions the user states ("always use Celsius", "never post
+//! publicly without asking") are recorded by the `preference_set` tool in
+//! `workspace/state/preferences.json` and appended to every system prompt,
+//! so they survive new sessions and memory pruning. They can be reviewed
+//! and removed with `zeroclaw preferences` or `/api/preferences`.
+
+use crate::config::Config;
+use crate::PreferenceCommands;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Preferences kept at most; the prompt section must stay small.
+pub const MAX_PREFERENCES: usize = 50;
+/// Longest preference text, in characters.
+pub const MAX_PREFERENCE_CHARS: usize = 300;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Preference {
+    /// Short identifier such as `temperature_unit`; setting it again replaces the text.
+    pub key: String,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub fn path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join("preferences.json")
+}
+
+/// Keys are lowercase words joined by `_`, e.g. "Temperature unit" → `temperature_unit`.
+pub fn normalize_key(raw: &str) -> Result<String> {
+    let key = raw
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_lowercase();
+    if key.is_empty()
+        || key.len() > 64
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.'))
+    {
+        bail!("Invalid preference key '{raw}': use up to 64 letters, digits, '_' or '.'");
+    }
+    Ok(key)
+}
+
+pub fn load(workspace_dir: &Path) -> Result<Vec<Preference>> {
+    let path = path(workspace_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&raw).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn save(workspace_dir: &Path, preferences: &[Preference]) -> Result<()> {
+    let path = path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_string_pretty(preferences)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, &path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// Record `text` under `key` and return the text it replaced, if any.
+pub fn set(workspace_dir: &Path, key: &str, text: &str) -> Result<Option<String>> {
+    let key = normalize_key(key)?;
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        bail!("Preference text must not be empty");
+    }
+    if text.chars().count() > MAX_PREFERENCE_CHARS {
+        bail!("Preference text is longer than {MAX_PREFERENCE_CHARS} characters");
+    }
+    let now = Utc::now();
+    let mut preferences = load(workspace_dir)?;
+    let previous = match preferences.iter_mut().find(|p| p.key == key) {
+        Some(existing) => {
+            existing.updated_at = now;
+            Some(std::mem::replace(&mut existing.text, text))
+        }
+        None => {
+            if preferences.len() >= MAX_PREFERENCES {
+                bail!(
+                    "Already {MAX_PREFERENCES} preferences stored; remove one with `zeroclaw preferences remove <key>` first"
+                );
+            }
+            preferences.push(Preference {
+                key,
+                text,
+                created_at: now,
+                updated_at: now,
+            });
+            None
+        }
+    };
+    save(workspace_dir, &preferences)?;
+    Ok(previous)
+}
+
+/// Delete one preference; returns whether it existed.
+pub fn remove(workspace_dir: &Path, key: &str) -> Result<bool> {
+    let key = normalize_key(key)?;
+    let mut preferences = load(workspace_dir)?;
+    let before = preferences.len();
+    preferences.retain(|p| p.key != key);
+    if preferences.len() == before {
+        return Ok(false);
+    }
+    save(workspace_dir, &preferences)?;
+    Ok(true)
+}
+
+/// `## User Preferences` section for the system prompt, or an empty string.
+pub fn prompt_section(preferences: &[Preference]) -> String {
+    if preferences.is_empty() {
+        return String::new();
+    }
+    let mut section = String::from(
+        "## User Preferences\n\nStanding instructions from the user. Follow them unless the user overrides one in the current request.\n\n",
+    );
+    for preference in preferences {
+        let _ = writeln!(section, "- {}: {}", preference.key, preference.text);
+    }
+    section
+}
+
+/// `base_prompt` followed by the current preferences. Read on every call so
+/// preferences set mid-conversation apply from the next turn.
+pub fn apply_to_prompt(workspace_dir: &Path, base_prompt: &str) -> String {
+    let preferences = match load(workspace_dir) {
+        Ok(preferences) => preferences,
+        Err(e) => {
+            tracing::warn!("Failed to load preferences: {e}");
+            return base_prompt.to_string();
+        }
+    };
+    let section = prompt_section(&preferences);
+    if section.is_empty() {
+        base_prompt.to_string()
+    } else {
+        format!("{base_prompt}\n\n{}", section.trim_end())
+    }
+}
+
+pub fn handle_command(command: PreferenceCommands, config: &Config) -> Result<()> {
+    let workspace_dir = &config.workspace_dir;
+    match command {
+        PreferenceCommands::List => {
+            let preferences = load(workspace_dir)?;
+            if preferences.is_empty() {
+                println!("No preferences recorded.");
+            }
+            for preference in preferences {
+                println!(
+                    "{}: {} (updated {})",
+                    preference.key,
+                    preference.text,
+                    preference.updated_at.format("%Y-%m-%d %H:%M")
+                );
+            }
+        }
+        PreferenceCommands::Remove { key } => {
+            if remove(workspace_dir, &key)? {
+                println!("✅ Removed preference '{key}'");
+            } else {
+                bail!("No preference '{key}'");
+            }
+        }
+        PreferenceCommands::Clear { yes } => {
+            let count = load(workspace_dir)?.len();
+            if count == 0 {
+                println!("No preferences recorded.");
+                return Ok(());
+            }
+            if !yes {
+                let confirmed = dialoguer::Confirm::new()
+                    .with_prompt(format!("Delete all {count} preferences?"))
+                    .default(false)
+                    .interact()?;
+                if !confirmed {
+                    println!("Cancelled.");
+                    return Ok(());
+                }
+            }
+            save(workspace_dir, &[])?;
+            println!("✅ Removed {count} preferences");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn sets_replaces_and_removes_preferences() {
+        let tmp = TempDir::new().unwrap();
+        let ws = tmp.path();
+        assert_eq!(
+            set(ws, "Temperature unit", "Always use Celsius").unwrap(),
+            None
+        );
+        assert_eq!(
+            set(ws, "temperature_unit", "Use  Celsius,\nand km/h for wind").unwrap(),
+            Some("Always use Celsius".into())
+        );
+        set(ws, "public-posts", "Never post publicly without asking").unwrap();
+
+        let preferences = load(ws).unwrap();
+        assert_eq!(preferences.len(), 2);
+        assert_eq!(preferences[0].key, "temperature_unit");
+        assert_eq!(preferences[0].text, "Use Celsius, and km/h for wind");
+        assert_eq!(preferences[1].key, "public_posts");
+
+        assert!(set(ws, "bad/key", "x").is_err());
+        assert!(set(ws, "empty", "   ").is_err());
+        assert!(remove(ws, "PUBLIC POSTS").unwrap());
+        assert!(!remove(ws, "public_posts").unwrap());
+        assert_eq!(load(ws).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn appends_preferences_to_the_prompt() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(apply_to_prompt(tmp.path(), "base"), "base");
+        set(tmp.path(), "units", "Always use Celsius").unwrap();
+        let prompt = apply_to_prompt(tmp.path(), "base");
+        assert!(prompt.starts_with("base\n\n## User Preferences\n\n"));
+        assert!(prompt.ends_with("- units: Always use Celsius"));
+    }
+}
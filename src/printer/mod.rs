@@ -0,0 +1,465 @@
+//! OctoPrint / Moonraker integration.
+//!
+//! Both backends are normalized to a single [`PrinterStatus`]. The `printer`
+//! tool reads it on demand; the daemon watcher polls it and announces
+//! finished or failed jobs on the configured channel.
+
+use crate::config::{Config, PrinterConfig};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use tokio::time::Duration;
+
+const COMPONENT: &str = "printer";
+
+/// Job control commands accepted by both backends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrinterCommand {
+    Pause,
+    Resume,
+    Cancel,
+}
+
+impl PrinterCommand {
+    pub fn parse(action: &str) -> Option<Self> {
+        match action {
+            "pause" => Some(Self::Pause),
+            "resume" => Some(Self::Resume),
+            "cancel" => Some(Self::Cancel),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Temperature {
+    pub name: String,
+    pub actual: f64,
+    pub target: f64,
+}
+
+/// Backend-independent printer state.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PrinterStatus {
+    /// One of `printing`, `paused`, `complete`, `cancelled`, `error`, `idle`, `offline`
+    pub state: String,
+    pub file: Option<String>,
+    pub progress_pct: Option<f64>,
+    pub elapsed_secs: Option<u64>,
+    pub remaining_secs: Option<u64>,
+    pub temperatures: Vec<Temperature>,
+    pub message: Option<String>,
+}
+
+impl PrinterStatus {
+    fn is_active(&self) -> bool {
+        matches!(self.state.as_str(), "printing" | "paused")
+    }
+}
+
+fn client(config: &PrinterConfig) -> reqwest::Client {
+    crate::config::build_runtime_proxy_client_with_timeouts(
+        "tool.printer",
+        config.timeout_secs.max(1),
+        10,
+    )
+}
+
+fn base_url(config: &PrinterConfig) -> Result<&str> {
+    let url = config.url.trim().trim_end_matches('/');
+    if url.is_empty() {
+        anyhow::bail!("printer.url is not configured");
+    }
+    Ok(url)
+}
+
+fn request(
+    config: &PrinterConfig,
+    method: reqwest::Method,
+    path: &str,
+) -> Result<reqwest::RequestBuilder> {
+    let request = client(config).request(method, format!("{}{path}", base_url(config)?));
+    Ok(match &config.api_key {
+        Some(key) => request.header("X-Api-Key", key),
+        None => request,
+    })
+}
+
+async fn get_json(config: &PrinterConfig, path: &str) -> Result<Option<Value>> {
+    let response = request(config, reqwest::Method::GET, path)?.send().await?;
+    let status = response.status();
+    // OctoPrint answers 409 on /api/printer while the printer is disconnected.
+    if status == reqwest::StatusCode::CONFLICT {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        anyhow::bail!("GET {path} returned {status}");
+    }
+    Ok(Some(response.json().await?))
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn secs(value: &Value) -> Option<u64> {
+    value
+        .as_f64()
+        .filter(|v| *v >= 0.0)
+        .map(|v| v.round() as u64)
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+fn octoprint_state(text: &str) -> String {
+    let text = text.to_ascii_lowercase();
+    if text.starts_with("printing") || text.starts_with("starting") {
+        "printing"
+    } else if text.starts_with("paus") || text.starts_with("resuming") {
+        "paused"
+    } else if text.starts_with("cancelling") {
+        "cancelled"
+    } else if text.starts_with("offline") || text.contains("closed") {
+        "offline"
+    } else if text.contains("error") {
+        "error"
+    } else {
+        "idle"
+    }
+    .into()
+}
+
+/// Build a status from OctoPrint `GET /api/job` and (optional) `GET /api/printer`.
+fn parse_octoprint(job: &Value, printer: Option<&Value>) -> PrinterStatus {
+    let state = printer
+        .and_then(|p| p["state"]["text"].as_str())
+        .or_else(|| job["state"].as_str())
+        .unwrap_or("Offline");
+    let mut temperatures = Vec::new();
+    if let Some(temps) = printer.and_then(|p| p["temperature"].as_object()) {
+        for (name, reading) in temps {
+            if let (Some(actual), target) = (reading["actual"].as_f64(), reading["target"].as_f64())
+            {
+                temperatures.push(Temperature {
+                    name: name.clone(),
+                    actual: round1(actual),
+                    target: round1(target.unwrap_or(0.0)),
+                });
+            }
+        }
+    }
+    PrinterStatus {
+        state: octoprint_state(state),
+        file: job["job"]["file"]["name"].as_str().map(str::to_string),
+        progress_pct: job["progress"]["completion"].as_f64().map(round1),
+        elapsed_secs: secs(&job["progress"]["printTime"]),
+        remaining_secs: secs(&job["progress"]["printTimeLeft"]),
+        temperatures,
+        message: job["error"].as_str().map(str::to_string),
+    }
+}
+
+/// Build a status from Moonraker `printer/objects/query` results.
+fn parse_moonraker(result: &Value) -> PrinterStatus {
+    let status = &result["result"]["status"];
+    let stats = &status["print_stats"];
+    let state = match stats["state"].as_str().unwrap_or("standby") {
+        "printing" => "printing",
+        "paused" => "paused",
+        "complete" => "complete",
+        "cancelled" => "cancelled",
+        "error" => "error",
+        _ => "idle",
+    };
+    let progress = status["virtual_sdcard"]["progress"]
+        .as_f64()
+        .or_else(|| status["display_status"]["progress"].as_f64());
+    let elapsed = stats["print_duration"].as_f64();
+    let remaining = match (progress, elapsed) {
+        (Some(p), Some(e)) if p > 0.0 && p < 1.0 => secs(&json!(e / p - e)),
+        _ => None,
+    };
+    let temperatures = ["extruder", "heater_bed"]
+        .iter()
+        .filter_map(|name| {
+            let heater = &status[*name];
+            Some(Temperature {
+                name: (*name).to_string(),
+                actual: round1(heater["temperature"].as_f64()?),
+                target: round1(heater["target"].as_f64().unwrap_or(0.0)),
+            })
+        })
+        .collect();
+    PrinterStatus {
+        state: state.into(),
+        file: stats["filename"]
+            .as_str()
+            .filter(|f| !f.is_empty())
+            .map(str::to_string),
+        progress_pct: progress.map(|p| round1(p * 100.0)),
+        elapsed_secs: secs(&stats["print_duration"]),
+        remaining_secs: remaining,
+        temperatures,
+        message: stats["message"]
+            .as_str()
+            .filter(|m| !m.is_empty())
+            .map(str::to_string),
+    }
+}
+
+pub async fn fetch_status(config: &PrinterConfig) -> Result<PrinterStatus> {
+    match config.backend.as_str() {
+        "octoprint" => {
+            let job = get_json(config, "/api/job")
+                .await?
+                .context("OctoPrint /api/job unavailable")?;
+            let printer = get_json(config, "/api/printer").await?;
+            Ok(parse_octoprint(&job, printer.as_ref()))
+        }
+        "moonraker" => {
+            let result = get_json(
+                config,
+                "/printer/objects/query?print_stats&virtual_sdcard&display_status&extruder&heater_bed",
+            )
+            .await?
+            .context("Moonraker is not ready")?;
+            Ok(parse_moonraker(&result))
+        }
+        other => {
+            anyhow::bail!("Unknown printer backend '{other}'. Use 'octoprint' or 'moonraker'.")
+        }
+    }
+}
+
+pub async fn send_command(config: &PrinterConfig, command: PrinterCommand) -> Result<()> {
+    let builder = match config.backend.as_str() {
+        "octoprint" => {
+            let body = match command {
+                PrinterCommand::Pause => json!({"command": "pause", "action": "pause"}),
+                PrinterCommand::Resume => json!({"command": "pause", "action": "resume"}),
+                PrinterCommand::Cancel => json!({"command": "cancel"}),
+            };
+            request(config, reqwest::Method::POST, "/api/job")?.json(&body)
+        }
+        "moonraker" => {
+            let path = match command {
+                PrinterCommand::Pause => "/printer/print/pause",
+                PrinterCommand::Resume => "/printer/print/resume",
+                PrinterCommand::Cancel => "/printer/print/cancel",
+            };
+            request(config, reqwest::Method::POST, path)?
+        }
+        other => anyhow::bail!("Unknown printer backend '{other}'"),
+    };
+    let response = builder.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        anyhow::bail!(
+            "Printer rejected command ({status}): {}",
+            crate::util::truncate_with_ellipsis(body.trim(), 200)
+        );
+    }
+    Ok(())
+}
+
+/// Fetch a single webcam frame. Returns the bytes and content type.
+pub async fn fetch_snapshot(config: &PrinterConfig) -> Result<(Vec<u8>, String)> {
+    let url = match &config.snapshot_url {
+        Some(url) => url.clone(),
+        None => format!("{}/webcam/?action=snapshot", base_url(config)?),
+    };
+    let response = client(config).get(&url).send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("Snapshot request returned {status}");
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("image/jpeg")
+        .to_string();
+    Ok((response.bytes().await?.to_vec(), content_type))
+}
+
+fn format_duration(secs: u64) -> String {
+    let (h, m) = (secs / 3600, (secs % 3600) / 60);
+    if h > 0 {
+        format!("{h}h {m:02}m")
+    } else {
+        format!("{m}m")
+    }
+}
+
+pub fn format_status(status: &PrinterStatus) -> String {
+    let mut out = format!("State: {}", status.state);
+    if let Some(file) = &status.file {
+        let _ = write!(out, "\nFile: {file}");
+    }
+    if let Some(progress) = status.progress_pct {
+        let _ = write!(out, "\nProgress: {progress:.1}%");
+    }
+    if let Some(elapsed) = status.elapsed_secs {
+        let _ = write!(out, "\nElapsed: {}", format_duration(elapsed));
+    }
+    if let Some(remaining) = status.remaining_secs {
+        let _ = write!(out, "\nRemaining: ~{}", format_duration(remaining));
+    }
+    for temp in &status.temperatures {
+        let _ = write!(
+            out,
+            "\n{}: {:.1}°C / {:.0}°C",
+            temp.name, temp.actual, temp.target
+        );
+    }
+    if let Some(message) = &status.message {
+        let _ = write!(out, "\nMessage: {message}");
+    }
+    out
+}
+
+/// Notification text for a job that left the printing/paused state, if any.
+fn transition_message(previous: &PrinterStatus, current: &PrinterStatus) -> Option<String> {
+    if !previous.is_active() || current.is_active() {
+        return None;
+    }
+    let file = previous.file.as_deref().unwrap_or("print job");
+    let mut message = match current.state.as_str() {
+        // OctoPrint reports "Operational" again once a job is done.
+        "complete" | "idle" => format!("🖨️ Print finished: {file}"),
+        "cancelled" => format!("🖨️ Print cancelled: {file}"),
+        "error" | "offline" => format!("⚠️ Print failed: {file} (printer {})", current.state),
+        _ => return None,
+    };
+    if let Some(elapsed) = current.elapsed_secs.or(previous.elapsed_secs) {
+        let _ = write!(message, " after {}", format_duration(elapsed));
+    }
+    if let Some(detail) = &current.message {
+        let _ = write!(message, "\n{detail}");
+    }
+    Some(message)
+}
+
+/// Daemon loop announcing finished and failed prints on `[printer].channel`.
+pub async fn run(config: Config) -> Result<()> {
+    let channel = config
+        .printer
+        .channel
+        .clone()
+        .context("printer.channel is required for notifications")?;
+    let to = config
+        .printer
+        .to
+        .clone()
+        .context("printer.to is required for notifications")?;
+    let mut interval = tokio::time::interval(Duration::from_secs(config.printer.poll_secs.max(10)));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    let mut previous: Option<PrinterStatus> = None;
+    loop {
+        interval.tick().await;
+        let current = match fetch_status(&config.printer).await {
+            Ok(status) => {
+                crate::health::mark_component_ok(COMPONENT);
+                status
+            }
+            Err(e) => {
+                crate::health::mark_component_error(COMPONENT, e.to_string());
+                tracing::debug!("Printer poll failed: {e}");
+                PrinterStatus {
+                    state: "offline".into(),
+                    ..PrinterStatus::default()
+                }
+            }
+        };
+        if let Some(message) = previous
+            .as_ref()
+            .and_then(|prev| transition_message(prev, &current))
+        {
+            if let Err(e) =
+                crate::cron::scheduler::deliver_announcement(&config, &channel, &to, &message).await
+            {
+                tracing::warn!("Printer notification failed: {e}");
+            }
+        }
+        previous = Some(current);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_octoprint_job_and_temperatures() {
+        let job = json!({
+            "job": {"file": {"name": "benchy.gcode"}},
+            "progress": {"completion": 42.345, "printTime": 1800, "printTimeLeft": 2460},
+            "state": "Printing"
+        });
+        let printer = json!({
+            "state": {"text": "Printing", "flags": {"printing": true}},
+            "temperature": {"bed": {"actual": 59.96, "target": 60.0}, "tool0": {"actual": 214.9, "target": 215.0}}
+        });
+        let status = parse_octoprint(&job, Some(&printer));
+        assert_eq!(status.state, "printing");
+        assert_eq!(status.file.as_deref(), Some("benchy.gcode"));
+        assert_eq!(status.progress_pct, Some(42.3));
+        assert_eq!(status.remaining_secs, Some(2460));
+        assert_eq!(status.temperatures.len(), 2);
+
+        let text = format_status(&status);
+        assert!(text.contains("Progress: 42.3%"));
+        assert!(text.contains("Remaining: ~41m"));
+        assert!(text.contains("tool0: 214.9°C / 215°C"));
+    }
+
+    #[test]
+    fn parses_moonraker_objects() {
+        let result = json!({"result": {"status": {
+            "print_stats": {"state": "paused", "filename": "vase.gcode", "print_duration": 3600.0, "message": ""},
+            "virtual_sdcard": {"progress": 0.25},
+            "extruder": {"temperature": 205.04, "target": 210.0},
+            "heater_bed": {"temperature": 65.0, "target": 65.0}
+        }}});
+        let status = parse_moonraker(&result);
+        assert_eq!(status.state, "paused");
+        assert_eq!(status.progress_pct, Some(25.0));
+        assert_eq!(status.remaining_secs, Some(10_800));
+        assert_eq!(status.message, None);
+        assert_eq!(status.temperatures[0].actual, 205.0);
+    }
+
+    #[test]
+    fn announces_only_when_a_job_ends() {
+        let printing = PrinterStatus {
+            state: "printing".into(),
+            file: Some("benchy.gcode".into()),
+            elapsed_secs: Some(5400),
+            ..PrinterStatus::default()
+        };
+        let idle = PrinterStatus {
+            state: "idle".into(),
+            ..PrinterStatus::default()
+        };
+        let error = PrinterStatus {
+            state: "error".into(),
+            message: Some("Thermal runaway".into()),
+            ..PrinterStatus::default()
+        };
+        assert_eq!(
+            transition_message(&printing, &idle).as_deref(),
+            Some("🖨️ Print finished: benchy.gcode after 1h 30m")
+        );
+        assert!(transition_message(&printing, &error)
+            .unwrap()
+            .contains("Print failed: benchy.gcode (printer error) after 1h 30m\nThermal runaway"));
+        assert_eq!(transition_message(&idle, &printing), None);
+        assert_eq!(transition_message(&printing, &printing), None);
+        assert_eq!(
+            PrinterCommand::parse("resume"),
+            Some(PrinterCommand::Resume)
+        );
+    }
+}
@@ -0,0 +1,640 @@
+//! Static dashboard publisher.
+//!
+//! Renders workspace reports and a status summary (component health, cron
+//! jobs) into plain HTML under `[publish].output_dir`, optionally committing
+//! and pushing the result when that directory is a git checkout and/or
+//! uploading it to an S3-compatible bucket. The daemon regenerates the site
+//! every `interval_minutes`.
+
+use crate::config::Config;
+use crate::tools::object_store::{is_location_allowed, S3Client};
+use crate::tools::report::{html_escape, render_template, REPORT_TEMPLATES_DIR};
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::fmt::Write as _;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+use tokio::process::Command;
+use tokio::time::Duration;
+
+/// Optional workspace template overriding the built-in dashboard index.
+const DASHBOARD_TEMPLATE: &str = "dashboard.html";
+/// Reports larger than this are skipped rather than rendered (1MB).
+const MAX_REPORT_BYTES: u64 = 1_048_576;
+
+const PAGE_TEMPLATE: &str = r#"<!doctype html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{ title }}</title>
+<style>
+body { font-family: system-ui, sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; color: #1f2328; }
+table { border-collapse: collapse; width: 100%; margin: 1rem 0; }
+th, td { border: 1px solid #d0d7de; padding: .35rem .6rem; text-align: left; }
+pre { background: #f6f8fa; padding: .75rem; overflow-x: auto; }
+.ok { color: #1a7f37; } .error, .failed { color: #cf222e; } .muted { color: #656d76; }
+</style>
+</head>
+<body>
+<p class="muted"><a href="{{ root | safe }}index.html">Dashboard</a> · generated {{ generated_at }}</p>
+{{ body | safe }}
+</body>
+</html>
+"#;
+
+const INDEX_BODY_TEMPLATE: &str = r#"<h1>ZeroClaw dashboard</h1>
+<h2>Components</h2>
+{% if components %}
+<table>
+<tr><th>Component</th><th>Status</th><th>Restarts</th><th>Last error</th></tr>
+{% for c in components %}
+<tr><td>{{ c.name }}</td><td class="{{ c.status }}">{{ c.status }}</td><td>{{ c.restart_count }}</td><td>{{ c.last_error | default("") }}</td></tr>
+{% endfor %}
+</table>
+{% else %}
+<p class="muted">No component health recorded (daemon not running in this process).</p>
+{% endif %}
+<h2>Scheduled jobs</h2>
+{% if jobs %}
+<table>
+<tr><th>Job</th><th>Schedule</th><th>Last status</th><th>Last run</th><th>Next run</th></tr>
+{% for j in jobs %}
+<tr><td>{{ j.name }}</td><td><code>{{ j.expression }}</code></td><td class="{{ j.last_status }}">{{ j.last_status | default("never run") }}</td><td>{{ j.last_run | default("-") }}</td><td>{% if j.enabled %}{{ j.next_run }}{% else %}paused{% endif %}</td></tr>
+{% endfor %}
+</table>
+{% else %}
+<p class="muted">No cron jobs.</p>
+{% endif %}
+<h2>Reports</h2>
+<ul>
+{% for r in reports %}
+<li><a href="{{ r.href | safe }}">{{ r.title }}</a> <span class="muted">{{ r.source }}</span></li>
+{% else %}
+<li class="muted">No reports matched the configured sources.</li>
+{% endfor %}
+</ul>
+"#;
+
+/// Result of one publish pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishSummary {
+    pub output_dir: PathBuf,
+    pub reports: usize,
+    pub pushed: bool,
+}
+
+/// Long-running daemon worker: regenerate the site every `interval_minutes`.
+pub async fn run(config: Config) -> Result<()> {
+    let interval_mins = config.publish.interval_minutes.max(1);
+    let mut interval = tokio::time::interval(Duration::from_secs(u64::from(interval_mins) * 60));
+
+    loop {
+        interval.tick().await;
+        match publish_site(&config).await {
+            Ok(summary) => {
+                crate::health::mark_component_ok("publisher");
+                tracing::debug!(
+                    "Published dashboard with {} report(s) to {}",
+                    summary.reports,
+                    summary.output_dir.display()
+                );
+            }
+            Err(e) => {
+                crate::health::mark_component_error("publisher", e.to_string());
+                tracing::warn!("Dashboard publish failed: {e}");
+            }
+        }
+    }
+}
+
+/// Resolve `[publish].output_dir` inside the workspace, rejecting absolute
+/// paths and `..` so the publisher can never clear directories elsewhere.
+pub fn output_dir(config: &Config) -> Result<PathBuf> {
+    let relative = Path::new(config.publish.output_dir.trim());
+    if relative.as_os_str().is_empty()
+        || !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        anyhow::bail!(
+            "publish.output_dir must be a relative path inside the workspace, got '{}'",
+            config.publish.output_dir
+        );
+    }
+    Ok(config.workspace_dir.join(relative))
+}
+
+/// Render all configured reports plus the status index, then push if enabled.
+pub async fn publish_site(config: &Config) -> Result<PublishSummary> {
+    let out_dir = output_dir(config)?;
+    let reports_dir = out_dir.join("reports");
+    if reports_dir.exists() {
+        tokio::fs::remove_dir_all(&reports_dir)
+            .await
+            .with_context(|| format!("Failed to clear {}", reports_dir.display()))?;
+    }
+    tokio::fs::create_dir_all(&reports_dir).await?;
+
+    let generated_at = chrono::Utc::now().to_rfc3339();
+    let mut reports = Vec::new();
+    for source in collect_sources(config) {
+        let Some(relative) = source
+            .strip_prefix(&config.workspace_dir)
+            .ok()
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+        else {
+            continue;
+        };
+        let content = match tokio::fs::read_to_string(&source).await {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("Skipping report {relative}: {e}");
+                continue;
+            }
+        };
+
+        let is_html = has_extension(&source, &["html", "htm"]);
+        let title = report_title(&content, is_html).unwrap_or_else(|| relative.clone());
+        let body = if is_html {
+            content
+        } else {
+            markdown_to_html(&content)
+        };
+
+        let file_name = format!("{}.html", page_slug(&relative));
+        let page = render_page(&title, &body, "../", &generated_at)?;
+        tokio::fs::write(reports_dir.join(&file_name), page).await?;
+        // `href` is built from the slug alone, so templates may emit it unescaped.
+        reports.push(json!({
+            "title": title,
+            "href": format!("reports/{file_name}"),
+            "source": relative,
+        }));
+    }
+
+    let report_count = reports.len();
+    let context = json!({
+        "generated_at": generated_at,
+        "components": component_rows(),
+        "jobs": job_rows(config),
+        "reports": reports,
+    });
+    let index = match load_dashboard_override(config).await {
+        Some(template) => render_template(&template, &context, true)?,
+        None => render_page(
+            "ZeroClaw dashboard",
+            &render_template(INDEX_BODY_TEMPLATE, &context, true)?,
+            "",
+            &generated_at,
+        )?,
+    };
+    tokio::fs::write(out_dir.join("index.html"), index).await?;
+
+    let pushed = if config.publish.git_push {
+        git_push(&out_dir, &config.publish.git_remote).await?
+    } else {
+        false
+    };
+
+    if let Some(target) = config.publish.s3_target.as_deref() {
+        upload_site(config, &out_dir, target).await?;
+    }
+
+    Ok(PublishSummary {
+        output_dir: out_dir,
+        reports: report_count,
+        pushed,
+    })
+}
+
+fn collect_sources(config: &Config) -> Vec<PathBuf> {
+    let mut sources = Vec::new();
+    for pattern in &config.publish.sources {
+        let pattern = pattern.trim();
+        if pattern.is_empty() || Path::new(pattern).is_absolute() || pattern.contains("..") {
+            tracing::warn!("Ignoring publish source outside the workspace: {pattern}");
+            continue;
+        }
+        let full = config.workspace_dir.join(pattern);
+        let Ok(paths) = glob::glob(&full.to_string_lossy()) else {
+            tracing::warn!("Invalid publish source pattern: {pattern}");
+            continue;
+        };
+        for path in paths.flatten() {
+            let Ok(meta) = std::fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if meta.is_file()
+                && meta.len() <= MAX_REPORT_BYTES
+                && has_extension(&path, &["md", "markdown", "txt", "html", "htm"])
+                && !sources.contains(&path)
+            {
+                sources.push(path);
+            }
+        }
+    }
+    sources.sort();
+    sources
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+}
+
+fn page_slug(relative: &str) -> String {
+    let stem = relative.rsplit_once('.').map_or(relative, |(stem, _)| stem);
+    stem.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+fn report_title(content: &str, is_html: bool) -> Option<String> {
+    if is_html {
+        let start = content.find("<title>")? + "<title>".len();
+        let end = content[start..].find("</title>")?;
+        let title = content[start..start + end].trim();
+        return (!title.is_empty()).then(|| title.to_string());
+    }
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+}
+
+fn render_page(title: &str, body: &str, root: &str, generated_at: &str) -> Result<String> {
+    render_template(
+        PAGE_TEMPLATE,
+        &json!({
+            "title": title,
+            "body": body,
+            "root": root,
+            "generated_at": generated_at,
+        }),
+        true,
+    )
+}
+
+async fn load_dashboard_override(config: &Config) -> Option<String> {
+    let path = config
+        .workspace_dir
+        .join(REPORT_TEMPLATES_DIR)
+        .join(DASHBOARD_TEMPLATE);
+    tokio::fs::read_to_string(path).await.ok()
+}
+
+fn component_rows() -> Vec<Value> {
+    crate::health::snapshot()
+        .components
+        .into_iter()
+        .map(|(name, health)| {
+            json!({
+                "name": name,
+                "status": health.status,
+                "restart_count": health.restart_count,
+                "last_error": health.last_error,
+            })
+        })
+        .collect()
+}
+
+fn job_rows(config: &Config) -> Vec<Value> {
+    let jobs = match crate::cron::list_jobs(config) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            tracing::warn!("Dashboard could not list cron jobs: {e}");
+            return Vec::new();
+        }
+    };
+    jobs.into_iter()
+        .map(|job| {
+            json!({
+                "name": job.name.clone().unwrap_or_else(|| job.id.clone()),
+                "expression": job.expression,
+                "enabled": job.enabled,
+                "last_status": job.last_status,
+                "last_run": job.last_run.map(|t| t.to_rfc3339()),
+                "next_run": job.next_run.to_rfc3339(),
+            })
+        })
+        .collect()
+}
+
+/// Commit and push the output directory. Returns `false` when it is not a
+/// git checkout or nothing changed.
+async fn git_push(dir: &Path, remote: &str) -> Result<bool> {
+    if !dir.join(".git").exists() {
+        tracing::warn!(
+            "publish.git_push is enabled but {} is not a git checkout",
+            dir.display()
+        );
+        return Ok(false);
+    }
+
+    let status = git(dir, &["status", "--porcelain"]).await?;
+    if status.trim().is_empty() {
+        return Ok(false);
+    }
+
+    git(dir, &["add", "-A"]).await?;
+    let message = format!("Update dashboard {}", chrono::Utc::now().to_rfc3339());
+    git(dir, &["commit", "-m", &message]).await?;
+    git(dir, &["push", remote, "HEAD"]).await?;
+    Ok(true)
+}
+
+/// Upload `index.html` and every report page to `bucket/prefix`.
+async fn upload_site(config: &Config, out_dir: &Path, target: &str) -> Result<()> {
+    let target = target.trim().trim_matches('/');
+    let (bucket, prefix) = target.split_once('/').unwrap_or((target, ""));
+    let prefix = if prefix.is_empty() {
+        String::new()
+    } else {
+        format!("{prefix}/")
+    };
+
+    let mut pages = vec!["index.html".to_string()];
+    let mut entries = tokio::fs::read_dir(out_dir.join("reports")).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if let Some(name) = entry.file_name().to_str() {
+            pages.push(format!("reports/{name}"));
+        }
+    }
+
+    for page in &pages {
+        let key = format!("{prefix}{page}");
+        if !is_location_allowed(&config.object_store.allowed, bucket, &key) {
+            anyhow::bail!("publish.s3_target s3://{bucket}/{key} is not in object_store.allowed");
+        }
+    }
+
+    let client = S3Client::from_config(&config.object_store)?;
+    for page in pages {
+        let data = tokio::fs::read(out_dir.join(&page)).await?;
+        client
+            .put(
+                bucket,
+                &format!("{prefix}{page}"),
+                data,
+                "text/html; charset=utf-8",
+            )
+            .await
+            .with_context(|| format!("Failed to upload {page}"))?;
+    }
+    Ok(())
+}
+
+async fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .await
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// ── Markdown ────────────────────────────────────────────────────
+//
+// Just enough markdown for generated reports: headings, paragraphs, lists,
+// fenced code, pipe tables, rules, and inline code/bold/links.
+
+fn markdown_to_html(markdown: &str) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    let flush = |paragraph: &mut Vec<&str>, html: &mut String| {
+        if !paragraph.is_empty() {
+            let _ = writeln!(html, "<p>{}</p>", inline_markdown(&paragraph.join(" ")));
+            paragraph.clear();
+        }
+    };
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            flush(&mut paragraph, &mut html);
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim().starts_with("```") {
+                code.push(lines[i]);
+                i += 1;
+            }
+            let _ = writeln!(
+                html,
+                "<pre><code>{}</code></pre>",
+                html_escape(&code.join("\n"))
+            );
+            i += 1;
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush(&mut paragraph, &mut html);
+            i += 1;
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            flush(&mut paragraph, &mut html);
+            let _ = writeln!(
+                html,
+                "<h{level}>{}</h{level}>",
+                inline_markdown(trimmed[level..].trim())
+            );
+            i += 1;
+            continue;
+        }
+
+        if matches!(trimmed, "---" | "***" | "___") {
+            flush(&mut paragraph, &mut html);
+            html.push_str("<hr>\n");
+            i += 1;
+            continue;
+        }
+
+        if trimmed.starts_with('|') && lines.get(i + 1).is_some_and(|next| is_table_rule(next)) {
+            flush(&mut paragraph, &mut html);
+            html.push_str("<table>\n");
+            html.push_str(&table_row(trimmed, "th"));
+            i += 2;
+            while i < lines.len() && lines[i].trim().starts_with('|') {
+                html.push_str(&table_row(lines[i].trim(), "td"));
+                i += 1;
+            }
+            html.push_str("</table>\n");
+            continue;
+        }
+
+        if let Some(list_tag) = list_item(trimmed).map(|(tag, _)| tag) {
+            flush(&mut paragraph, &mut html);
+            let _ = writeln!(html, "<{list_tag}>");
+            while let Some((tag, item)) = lines.get(i).and_then(|l| list_item(l.trim())) {
+                if tag != list_tag {
+                    break;
+                }
+                let _ = writeln!(html, "<li>{}</li>", inline_markdown(item));
+                i += 1;
+            }
+            let _ = writeln!(html, "</{list_tag}>");
+            continue;
+        }
+
+        paragraph.push(trimmed);
+        i += 1;
+    }
+    flush(&mut paragraph, &mut html);
+    html
+}
+
+fn list_item(line: &str) -> Option<(&'static str, &str)> {
+    if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some(("ul", item));
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(item) = line[digits..].strip_prefix(". ") {
+            return Some(("ol", item));
+        }
+    }
+    None
+}
+
+fn is_table_rule(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with('|')
+        && trimmed.contains('-')
+        && trimmed.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+fn table_row(line: &str, cell_tag: &str) -> String {
+    let inner = line.trim().trim_start_matches('|').trim_end_matches('|');
+    let cells = inner.split('|').fold(String::new(), |mut out, cell| {
+        let _ = write!(
+            out,
+            "<{cell_tag}>{}</{cell_tag}>",
+            inline_markdown(cell.trim())
+        );
+        out
+    });
+    format!("<tr>{cells}</tr>\n")
+}
+
+fn inline_markdown(text: &str) -> String {
+    static CODE_RE: OnceLock<Regex> = OnceLock::new();
+    static BOLD_RE: OnceLock<Regex> = OnceLock::new();
+    static LINK_RE: OnceLock<Regex> = OnceLock::new();
+    let code = CODE_RE.get_or_init(|| Regex::new(r"`([^`]+)`").expect("code regex must compile"));
+    let bold =
+        BOLD_RE.get_or_init(|| Regex::new(r"\*\*([^*]+)\*\*").expect("bold regex must compile"));
+    let link = LINK_RE
+        .get_or_init(|| Regex::new(r"\[([^\]]+)\]\(([^)\s]+)\)").expect("link regex must compile"));
+
+    let escaped = html_escape(text);
+    let with_code = code.replace_all(&escaped, "<code>$1</code>");
+    let with_bold = bold.replace_all(&with_code, "<strong>$1</strong>");
+    link.replace_all(&with_bold, |caps: &regex::Captures| {
+        let target = &caps[2];
+        let safe = target.starts_with("http://")
+            || target.starts_with("https://")
+            || target.starts_with('#')
+            || !target.contains(':');
+        if safe {
+            format!("<a href=\"{target}\">{}</a>", &caps[1])
+        } else {
+            caps[1].to_string()
+        }
+    })
+    .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.publish.enabled = true;
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        config
+    }
+
+    #[test]
+    fn markdown_renders_common_blocks() {
+        let html = markdown_to_html(
+            "# Weekly\n\nDisk is **fine**.\n\n| host | use |\n|---|---|\n| nas | 40% |\n\n- one\n- `two`\n\n```\n<raw>\n```\n",
+        );
+        assert!(html.contains("<h1>Weekly</h1>"));
+        assert!(html.contains("<p>Disk is <strong>fine</strong>.</p>"));
+        assert!(html.contains("<tr><th>host</th><th>use</th></tr>"));
+        assert!(html.contains("<tr><td>nas</td><td>40%</td></tr>"));
+        assert!(html.contains("<li><code>two</code></li>"));
+        assert!(html.contains("<pre><code>&lt;raw&gt;</code></pre>"));
+    }
+
+    #[test]
+    fn markdown_drops_unsafe_link_targets() {
+        let html = inline_markdown("[ok](https://example.com) [bad](javascript:alert(1))");
+        assert!(html.contains("<a href=\"https://example.com\">ok</a>"));
+        assert!(!html.contains("javascript"));
+    }
+
+    #[test]
+    fn output_dir_must_stay_inside_workspace() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.publish.output_dir = "../escape".into();
+        assert!(output_dir(&config).is_err());
+        config.publish.output_dir = "/tmp/site".into();
+        assert!(output_dir(&config).is_err());
+        config.publish.output_dir = "site".into();
+        assert_eq!(
+            output_dir(&config).unwrap(),
+            config.workspace_dir.join("site")
+        );
+    }
+
+    #[tokio::test]
+    async fn publish_site_renders_reports_and_index() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let reports = config.workspace_dir.join("reports/out");
+        std::fs::create_dir_all(&reports).unwrap();
+        std::fs::write(reports.join("weekly.md"), "# Weekly infra\n\nAll good.\n").unwrap();
+
+        let summary = publish_site(&config).await.unwrap();
+        assert_eq!(summary.reports, 1);
+        assert!(!summary.pushed);
+
+        let site = config.workspace_dir.join("site");
+        let page = std::fs::read_to_string(site.join("reports/reports-out-weekly.html")).unwrap();
+        assert!(page.contains("<title>Weekly infra</title>"));
+        assert!(page.contains("<p>All good.</p>"));
+
+        let index = std::fs::read_to_string(site.join("index.html")).unwrap();
+        assert!(index.contains("href=\"reports/reports-out-weekly.html\""));
+        assert!(index.contains("Weekly infra"));
+    }
+}
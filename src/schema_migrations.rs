@@ -0,0 +1,303 @@
+//! Versioned schema migrations for the SQLite state stores.
+//!
+//! Each store (cron, memory) embeds an ordered list of [`Migration`]s. On
+//! open, [`migrate`] applies the pending ones, each inside its own
+//! `BEGIN IMMEDIATE` transaction together with its bookkeeping row in
+//! `schema_migrations`, so concurrent processes never apply a step twice and
+//! a failed step leaves the database untouched.
+//!
+//! Integrity checks:
+//! - the SHA-256 of every applied migration's `up` SQL is recorded and
+//!   compared on each open, so an edited migration is caught instead of
+//!   silently diverging;
+//! - a database migrated by a newer release is refused rather than written
+//!   with an older schema;
+//! - `PRAGMA quick_check` must pass before a step commits.
+//!
+//! Migrations are append-only: ship schema changes as a new version with a
+//! matching `down`, never by editing an applied one.
+
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Connection, Transaction, TransactionBehavior};
+use sha2::{Digest, Sha256};
+
+/// One schema step. `down` must undo exactly what `up` does.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+}
+
+impl Migration {
+    fn checksum(&self) -> String {
+        hex::encode(Sha256::digest(self.up.as_bytes()))
+    }
+}
+
+/// A migration recorded in `schema_migrations`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub checksum: String,
+    pub applied_at: String,
+}
+
+fn ensure_table(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version    INTEGER PRIMARY KEY,
+            name       TEXT NOT NULL,
+            checksum   TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        );",
+    )
+    .context("Failed to create schema_migrations table")
+}
+
+fn validate(store: &str, migrations: &[Migration]) -> Result<()> {
+    for (i, migration) in migrations.iter().enumerate() {
+        let expected = u32::try_from(i + 1).unwrap_or(u32::MAX);
+        if migration.version != expected {
+            bail!(
+                "{store} migrations must be numbered 1..N without gaps; found version {} at position {expected}",
+                migration.version
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Migrations recorded as applied, oldest first.
+pub fn applied(conn: &Connection) -> Result<Vec<AppliedMigration>> {
+    ensure_table(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT version, name, checksum, applied_at FROM schema_migrations ORDER BY version",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(AppliedMigration {
+            version: row.get(0)?,
+            name: row.get(1)?,
+            checksum: row.get(2)?,
+            applied_at: row.get(3)?,
+        })
+    })?;
+    let applied = rows
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to read schema_migrations")?;
+    Ok(applied)
+}
+
+/// Compare what the database has applied against what this build ships.
+fn verify(store: &str, migrations: &[Migration], applied: &[AppliedMigration]) -> Result<()> {
+    for record in applied {
+        let Some(migration) = usize::try_from(record.version)
+            .ok()
+            .and_then(|v| v.checked_sub(1))
+            .and_then(|i| migrations.get(i))
+        else {
+            bail!(
+                "{store} database is at schema version {} ({}), newer than this build supports (v{}); upgrade zeroclaw or restore a backup",
+                record.version,
+                record.name,
+                migrations.len()
+            );
+        };
+        if migration.checksum() != record.checksum {
+            bail!(
+                "{store} migration {} ({}) differs from the one applied on {}; refusing to continue",
+                record.version,
+                migration.name,
+                record.applied_at
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check_integrity(conn: &Connection) -> Result<()> {
+    let status: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+    if status != "ok" {
+        bail!("integrity check failed: {status}");
+    }
+    Ok(())
+}
+
+fn is_applied(conn: &Connection, version: u32) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM schema_migrations WHERE version = ?1)",
+        params![version],
+        |row| row.get(0),
+    )?)
+}
+
+/// Apply every pending migration in order. Returns the versions applied.
+pub fn migrate(conn: &Connection, store: &str, migrations: &[Migration]) -> Result<Vec<u32>> {
+    validate(store, migrations)?;
+    let recorded = applied(conn)?;
+    verify(store, migrations, &recorded)?;
+
+    let mut newly_applied = Vec::new();
+    for migration in &migrations[recorded.len().min(migrations.len())..] {
+        let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+        // Another process may have applied it while we waited for the lock.
+        if is_applied(&tx, migration.version)? {
+            continue;
+        }
+        tx.execute_batch(migration.up).with_context(|| {
+            format!(
+                "{store} migration {} ({}) failed",
+                migration.version, migration.name
+            )
+        })?;
+        tx.execute(
+            "INSERT INTO schema_migrations (version, name, checksum, applied_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                migration.version,
+                migration.name,
+                migration.checksum(),
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+        check_integrity(&tx).with_context(|| {
+            format!(
+                "{store} migration {} ({}) rolled back",
+                migration.version, migration.name
+            )
+        })?;
+        tx.commit()?;
+        tracing::info!(
+            "Applied {store} schema migration {} ({})",
+            migration.version,
+            migration.name
+        );
+        newly_applied.push(migration.version);
+    }
+    Ok(newly_applied)
+}
+
+/// Run `down` for every applied migration above `target`, newest first.
+/// Returns the versions reverted.
+pub fn rollback(
+    conn: &Connection,
+    store: &str,
+    migrations: &[Migration],
+    target: u32,
+) -> Result<Vec<u32>> {
+    validate(store, migrations)?;
+    let recorded = applied(conn)?;
+    verify(store, migrations, &recorded)?;
+
+    let mut reverted = Vec::new();
+    for record in recorded.iter().rev().filter(|r| r.version > target) {
+        let migration = migrations[record.version as usize - 1];
+        let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+        tx.execute_batch(migration.down).with_context(|| {
+            format!(
+                "{store} rollback of migration {} ({}) failed",
+                migration.version, migration.name
+            )
+        })?;
+        tx.execute(
+            "DELETE FROM schema_migrations WHERE version = ?1",
+            params![migration.version],
+        )?;
+        check_integrity(&tx)?;
+        tx.commit()?;
+        tracing::info!(
+            "Reverted {store} schema migration {} ({})",
+            migration.version,
+            migration.name
+        );
+        reverted.push(migration.version);
+    }
+    Ok(reverted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIGRATIONS: &[Migration] = &[
+        Migration {
+            version: 1,
+            name: "notes",
+            up: "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT NOT NULL);",
+            down: "DROP TABLE notes;",
+        },
+        Migration {
+            version: 2,
+            name: "notes_tag",
+            up: "ALTER TABLE notes ADD COLUMN tag TEXT;",
+            down: "ALTER TABLE notes DROP COLUMN tag;",
+        },
+    ];
+
+    fn columns(conn: &Connection) -> Vec<String> {
+        conn.prepare("PRAGMA table_info(notes)")
+            .unwrap()
+            .query_map([], |row| row.get(1))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn migrate_applies_pending_once_and_rollback_reverts() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert_eq!(migrate(&conn, "test", &MIGRATIONS[..1]).unwrap(), vec![1]);
+        conn.execute("INSERT INTO notes (body) VALUES ('keep me')", [])
+            .unwrap();
+
+        assert_eq!(migrate(&conn, "test", MIGRATIONS).unwrap(), vec![2]);
+        assert!(migrate(&conn, "test", MIGRATIONS).unwrap().is_empty());
+        assert_eq!(columns(&conn), vec!["id", "body", "tag"]);
+
+        assert_eq!(rollback(&conn, "test", MIGRATIONS, 1).unwrap(), vec![2]);
+        assert_eq!(columns(&conn), vec!["id", "body"]);
+        let body: String = conn
+            .query_row("SELECT body FROM notes", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(body, "keep me");
+        assert_eq!(applied(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn edited_or_unknown_migrations_are_refused() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn, "test", MIGRATIONS).unwrap();
+
+        let edited = [
+            MIGRATIONS[0],
+            Migration {
+                up: "ALTER TABLE notes ADD COLUMN label TEXT;",
+                ..MIGRATIONS[1]
+            },
+        ];
+        let err = migrate(&conn, "test", &edited).unwrap_err();
+        assert!(err.to_string().contains("differs"));
+
+        let err = migrate(&conn, "test", &MIGRATIONS[..1]).unwrap_err();
+        assert!(err.to_string().contains("newer than this build"));
+    }
+
+    #[test]
+    fn failed_migration_leaves_database_untouched() {
+        let conn = Connection::open_in_memory().unwrap();
+        let broken = [
+            MIGRATIONS[0],
+            Migration {
+                version: 2,
+                name: "broken",
+                up: "ALTER TABLE notes ADD COLUMN tag TEXT; ALTER TABLE missing ADD COLUMN x;",
+                down: "",
+            },
+        ];
+        assert!(migrate(&conn, "test", &broken).is_err());
+        assert_eq!(applied(&conn).unwrap().len(), 1);
+        assert_eq!(columns(&conn), vec!["id", "body"]);
+    }
+}
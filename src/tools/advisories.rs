@@ -0,0 +1,512 @@
+use super::traits::{Tool, ToolResult};
+use super::updates::UpdatesTool;
+use crate::config::{AdvisoriesConfig, NotifyConfig, UpdatesConfig};
+use crate::runtime::RuntimeAdapter;
+use crate::security::SecurityPolicy;
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// OSV aggregates GitHub, distribution (Debian, Ubuntu, Rocky, Alma) and
+/// language-ecosystem advisories behind one API.
+const OSV_API: &str = "https://api.osv.dev/v1";
+/// OSV's per-request limit for `querybatch`.
+const QUERY_BATCH: usize = 1000;
+/// Advisory details fetched per run; the rest are fetched on the next one.
+const MAX_NEW_DETAILS: usize = 100;
+/// Advisories listed in the output, most severe first.
+const MAX_LISTED: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Unknown,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    fn parse(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "critical" => Some(Self::Critical),
+            "high" | "important" => Some(Self::High),
+            "medium" | "moderate" => Some(Self::Medium),
+            "low" | "negligible" | "unimportant" => Some(Self::Low),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Unknown => "UNKNOWN",
+            Self::Low => "LOW",
+            Self::Medium => "MEDIUM",
+            Self::High => "HIGH",
+            Self::Critical => "CRITICAL",
+        }
+    }
+
+    /// Rough bucket from a CVSS vector: network reachability and high impact.
+    fn from_cvss(vector: &str) -> Self {
+        let network = vector.contains("AV:N");
+        let high_impact = ["/C:H", "/I:H", "/A:H", "/VC:H", "/VI:H", "/VA:H"]
+            .iter()
+            .any(|metric| vector.contains(metric));
+        match (network, high_impact) {
+            (true, true) => Self::High,
+            (true, false) | (false, true) => Self::Medium,
+            (false, false) => Self::Low,
+        }
+    }
+}
+
+/// Severity of an OSV record: the database's own rating (GHSA, Ubuntu,
+/// Debian urgency) when present, else a CVSS-vector estimate.
+fn severity_of(vuln: &Value) -> Severity {
+    if let Some(severity) = vuln["database_specific"]["severity"]
+        .as_str()
+        .and_then(Severity::parse)
+    {
+        return severity;
+    }
+    let entries = vuln["severity"].as_array().into_iter().flatten();
+    let mut cvss = None;
+    for entry in entries.chain(
+        vuln["affected"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|affected| affected["severity"].as_array())
+            .flatten(),
+    ) {
+        let score = entry["score"].as_str().unwrap_or_default();
+        if entry["type"]
+            .as_str()
+            .is_some_and(|t| t.starts_with("CVSS"))
+        {
+            if cvss.is_none() {
+                cvss = Some(Severity::from_cvss(score));
+            }
+        } else if let Some(severity) = Severity::parse(score) {
+            return severity;
+        }
+    }
+    if let Some(urgency) = vuln["affected"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find_map(|affected| affected["ecosystem_specific"]["urgency"].as_str())
+        .and_then(Severity::parse)
+    {
+        return urgency;
+    }
+    cvss.unwrap_or(Severity::Unknown)
+}
+
+/// `ecosystem/name@version`; names may contain `/` and a leading `@`.
+fn parse_package_entry(entry: &str) -> Option<(String, String, String)> {
+    let (ecosystem, rest) = entry.trim().split_once('/')?;
+    let (name, version) = rest.rsplit_once('@')?;
+    (!ecosystem.is_empty() && !name.is_empty() && !version.is_empty())
+        .then(|| (ecosystem.to_string(), name.to_string(), version.to_string()))
+}
+
+/// One known advisory, kept between runs so only new ones alert.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Advisory {
+    summary: String,
+    severity: Severity,
+    first_seen: String,
+}
+
+/// Security advisories (OSV) affecting installed OS packages and configured software.
+pub struct AdvisoriesTool {
+    security: Arc<SecurityPolicy>,
+    updates: UpdatesTool,
+    config: AdvisoriesConfig,
+    notify: NotifyConfig,
+    workspace_dir: PathBuf,
+}
+
+impl AdvisoriesTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        runtime: Arc<dyn RuntimeAdapter>,
+        updates: UpdatesConfig,
+        config: AdvisoriesConfig,
+        notify: NotifyConfig,
+        workspace_dir: PathBuf,
+    ) -> Self {
+        Self {
+            updates: UpdatesTool::new(security.clone(), runtime, updates),
+            security,
+            config,
+            notify,
+            workspace_dir,
+        }
+    }
+
+    fn state_path(&self) -> PathBuf {
+        self.workspace_dir.join("advisories").join("state.json")
+    }
+
+    fn client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.advisories",
+            self.config.timeout_secs.max(1),
+            10,
+        )
+    }
+
+    /// Advisory ids per query, in query order.
+    async fn query(
+        &self,
+        queries: &[(String, String, String)],
+    ) -> anyhow::Result<Vec<Vec<String>>> {
+        let mut ids = Vec::with_capacity(queries.len());
+        for chunk in queries.chunks(QUERY_BATCH) {
+            let body = json!({
+                "queries": chunk
+                    .iter()
+                    .map(|(ecosystem, name, version)| json!({
+                        "package": {"ecosystem": ecosystem, "name": name},
+                        "version": version,
+                    }))
+                    .collect::<Vec<_>>()
+            });
+            let response: Value = self
+                .client()
+                .post(format!("{OSV_API}/querybatch"))
+                .json(&body)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            let results = response["results"]
+                .as_array()
+                .context("OSV response has no results")?;
+            for result in results {
+                ids.push(
+                    result["vulns"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|vuln| vuln["id"].as_str())
+                        .map(str::to_string)
+                        .collect(),
+                );
+            }
+        }
+        Ok(ids)
+    }
+
+    async fn details(&self, id: &str) -> anyhow::Result<(String, Severity)> {
+        let vuln: Value = self
+            .client()
+            .get(format!("{OSV_API}/vulns/{id}"))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let summary = vuln["summary"]
+            .as_str()
+            .or_else(|| vuln["details"].as_str())
+            .unwrap_or_default();
+        Ok((
+            crate::util::truncate_with_ellipsis(summary.lines().next().unwrap_or_default(), 160),
+            severity_of(&vuln),
+        ))
+    }
+
+    async fn alert(&self, lines: &[String], top: Severity) -> anyhow::Result<()> {
+        let mut notification = crate::notify::Notification::new(lines.join("\n"));
+        notification.title = Some(format!(
+            "{} new security advisor{}",
+            lines.len(),
+            if lines.len() == 1 { "y" } else { "ies" }
+        ));
+        notification.priority = if top == Severity::Critical { 5 } else { 4 };
+        notification.tags = vec!["warning".into()];
+        crate::gateway::ws::publish_notification(
+            "alert",
+            json!({
+                "source": "advisories",
+                "priority": notification.priority,
+                "title": notification.title,
+                "message": notification.message,
+            }),
+        );
+        if let Some(target) = &self.config.notify_target {
+            let target = crate::notify::find_target(&self.notify, Some(target))?;
+            crate::notify::send(&self.notify, target, &notification).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Tool for AdvisoriesTool {
+    fn name(&self) -> &str {
+        "advisories"
+    }
+
+    fn description(&self) -> &str {
+        "Match installed OS packages (this host and [updates] SSH hosts) and configured software \
+         against OSV security advisories (GitHub, Debian, Ubuntu, Rocky, Alma, PyPI, npm, …). \
+         Lists affected packages most severe first and alerts on newly seen advisories."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "alert": {
+                    "type": "boolean",
+                    "description": "Send alerts for newly seen advisories at or above [advisories] alert_severity (default true)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let failure = |error: String| -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            })
+        };
+        let alert = args
+            .get("alert")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true);
+        let Some(threshold) = Severity::parse(&self.config.alert_severity) else {
+            return failure(format!(
+                "Invalid [advisories] alert_severity '{}'",
+                self.config.alert_severity
+            ));
+        };
+
+        if self.security.is_rate_limited() {
+            return failure("Rate limit exceeded: too many actions in the last hour".into());
+        }
+
+        if !self.security.record_action() {
+            return failure("Rate limit exceeded: action budget exhausted".into());
+        }
+
+        // (ecosystem, name, version) -> hosts that have it
+        let mut installed: BTreeMap<(String, String, String), Vec<String>> = BTreeMap::new();
+        let mut notes = Vec::new();
+        let mut hosts = 0usize;
+        if self.config.os_packages {
+            for (host, result) in self.updates.inventories().await {
+                match result {
+                    Ok(inventory) => match inventory.ecosystem {
+                        Some(ecosystem) => {
+                            hosts += 1;
+                            for (name, version) in inventory.packages {
+                                installed
+                                    .entry((ecosystem.clone(), name, version))
+                                    .or_default()
+                                    .push(host.clone());
+                            }
+                        }
+                        None => {
+                            notes.push(format!("{host}: distribution has no OSV feed, skipped"));
+                        }
+                    },
+                    Err(e) => notes.push(format!("{host}: inventory failed — {e}")),
+                }
+            }
+        }
+        for entry in &self.config.packages {
+            match parse_package_entry(entry) {
+                Some(key) => installed.entry(key).or_default().push("configured".into()),
+                None => notes.push(format!(
+                    "Invalid [advisories] packages entry '{entry}', expected ecosystem/name@version"
+                )),
+            }
+        }
+        if installed.is_empty() {
+            let mut error =
+                "Nothing to match: no supported OS inventory or [advisories] packages".to_string();
+            for note in &notes {
+                let _ = write!(error, "; {note}");
+            }
+            return failure(error);
+        }
+
+        let queries: Vec<(String, String, String)> = installed.keys().cloned().collect();
+        let results = match self.query(&queries).await {
+            Ok(results) => results,
+            Err(e) => return failure(format!("OSV query failed: {e}")),
+        };
+        // advisory id -> "host: name version" lines
+        let mut affected: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (key, ids) in queries.iter().zip(results) {
+            for id in ids {
+                for host in &installed[key] {
+                    affected
+                        .entry(id.clone())
+                        .or_default()
+                        .push(format!("{host}: {} {}", key.1, key.2));
+                }
+            }
+        }
+
+        let path = self.state_path();
+        let mut known: HashMap<String, Advisory> = tokio::fs::read_to_string(&path)
+            .await
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let mut new_ids = Vec::new();
+        for id in affected.keys() {
+            if known.contains_key(id) || new_ids.len() >= MAX_NEW_DETAILS {
+                continue;
+            }
+            match self.details(id).await {
+                Ok((summary, severity)) => {
+                    known.insert(
+                        id.clone(),
+                        Advisory {
+                            summary,
+                            severity,
+                            first_seen: chrono::Utc::now().to_rfc3339(),
+                        },
+                    );
+                    new_ids.push(id.clone());
+                }
+                Err(e) => notes.push(format!("{id}: details failed — {e}")),
+            }
+        }
+        // Forget fixed advisories so a regression alerts again.
+        known.retain(|id, _| affected.contains_key(id));
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, serde_json::to_string_pretty(&known)?)
+            .await
+            .context("Failed to save advisory state")?;
+
+        let mut ranked: Vec<(&String, Severity)> = affected
+            .keys()
+            .map(|id| (id, known.get(id).map_or(Severity::Unknown, |a| a.severity)))
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then_with(|| affected[b.0].len().cmp(&affected[a.0].len()))
+        });
+
+        let mut output = format!(
+            "Matched {} packages ({hosts} hosts) against OSV: {} advisories ({} new)\n",
+            installed.len(),
+            affected.len(),
+            new_ids.len()
+        );
+        let mut alert_lines = Vec::new();
+        let mut top = Severity::Unknown;
+        for (i, (id, severity)) in ranked.iter().enumerate() {
+            let is_new = new_ids.contains(*id);
+            let summary = known.get(*id).map_or("", |a| a.summary.as_str());
+            let packages = affected[*id].join("; ");
+            if is_new && *severity >= threshold {
+                top = top.max(*severity);
+                alert_lines.push(format!(
+                    "[{}] {id} — {summary} ({packages})",
+                    severity.label()
+                ));
+            }
+            if i < MAX_LISTED {
+                let _ = writeln!(
+                    output,
+                    "[{}] {id}{} — {summary}\n    {packages}",
+                    severity.label(),
+                    if is_new { " (new)" } else { "" }
+                );
+            }
+        }
+        if ranked.len() > MAX_LISTED {
+            let _ = writeln!(output, "… and {} more", ranked.len() - MAX_LISTED);
+        }
+
+        if alert && !alert_lines.is_empty() {
+            match self.alert(&alert_lines, top).await {
+                Ok(()) => {
+                    let _ = writeln!(
+                        output,
+                        "Alerted on {} new advisories at {} or above",
+                        alert_lines.len(),
+                        threshold.label().to_ascii_lowercase()
+                    );
+                }
+                Err(e) => notes.push(format!("alert delivery failed — {e}")),
+            }
+        }
+        for note in notes {
+            let _ = writeln!(output, "Note: {note}");
+        }
+        Ok(ToolResult {
+            success: true,
+            output: output.trim_end().to_string(),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn severity_prefers_database_rating_then_cvss() {
+        let ghsa = json!({"database_specific": {"severity": "MODERATE"}});
+        assert_eq!(severity_of(&ghsa), Severity::Medium);
+
+        let ubuntu = json!({"severity": [{"type": "Ubuntu", "score": "high"}]});
+        assert_eq!(severity_of(&ubuntu), Severity::High);
+
+        let debian = json!({"affected": [{"ecosystem_specific": {"urgency": "unimportant"}}]});
+        assert_eq!(severity_of(&debian), Severity::Low);
+
+        let cvss = json!({"severity": [{
+            "type": "CVSS_V3",
+            "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"
+        }]});
+        assert_eq!(severity_of(&cvss), Severity::High);
+        assert_eq!(severity_of(&json!({})), Severity::Unknown);
+        assert!(Severity::Critical > Severity::High && Severity::Low > Severity::Unknown);
+    }
+
+    #[test]
+    fn parses_configured_packages() {
+        assert_eq!(
+            parse_package_entry("PyPI/django@4.2.1"),
+            Some(("PyPI".into(), "django".into(), "4.2.1".into()))
+        );
+        assert_eq!(
+            parse_package_entry("npm/@babel/core@7.0.0"),
+            Some(("npm".into(), "@babel/core".into(), "7.0.0".into()))
+        );
+        assert_eq!(
+            parse_package_entry("Go/github.com/gin-gonic/gin@v1.9.0"),
+            Some((
+                "Go".into(),
+                "github.com/gin-gonic/gin".into(),
+                "v1.9.0".into()
+            ))
+        );
+        assert_eq!(parse_package_entry("django@4.2.1"), None);
+        assert_eq!(parse_package_entry("PyPI/django"), None);
+    }
+}
@@ -996,6 +996,10 @@ impl Tool for BrowserTool {
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
         // Security checks
         if !self.security.can_act() {
@@ -2006,7 +2010,7 @@ fn extract_host(url_str: &str) -> anyhow::Result<String> {
     Ok(host.to_lowercase())
 }
 
-fn is_private_host(host: &str) -> bool {
+pub(super) fn is_private_host(host: &str) -> bool {
     // Strip brackets from IPv6 addresses like [::1]
     let bare = host
         .strip_prefix('[')
@@ -76,6 +76,10 @@ impl Tool for BrowserOpenTool {
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let url = args
             .get("url")
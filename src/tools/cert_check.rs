@@ -0,0 +1,444 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::CertCheckConfig;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use parking_lot::Mutex;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use serde_json::json;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// OIDs (DER contents) for subject/issuer name attributes.
+const OID_COMMON_NAME: [u8; 3] = [0x55, 0x04, 0x03];
+const OID_ORGANIZATION: [u8; 3] = [0x55, 0x04, 0x0a];
+
+/// Subject, issuer and validity of one certificate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CertInfo {
+    subject: String,
+    issuer: String,
+    not_before: DateTime<Utc>,
+    not_after: DateTime<Utc>,
+}
+
+/// Split one DER TLV off the front of `input`: `(tag, contents, rest)`.
+fn der_tlv(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (len, rest) = if first & 0x80 == 0 {
+        (usize::from(first), rest)
+    } else {
+        let n = usize::from(first & 0x7f);
+        if n == 0 || n > 4 || rest.len() < n {
+            return None;
+        }
+        let len = rest[..n]
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | usize::from(*b));
+        (len, &rest[n..])
+    };
+    (rest.len() >= len).then(|| (tag, &rest[..len], &rest[len..]))
+}
+
+fn der_time(tag: u8, contents: &[u8]) -> Option<DateTime<Utc>> {
+    let text = std::str::from_utf8(contents).ok()?;
+    let full = match tag {
+        // UTCTime: YYMMDDHHMMSSZ, years 50-99 are 19xx (RFC 5280)
+        0x17 => {
+            let century = if text.get(..2)?.parse::<u8>().ok()? >= 50 {
+                "19"
+            } else {
+                "20"
+            };
+            format!("{century}{text}")
+        }
+        // GeneralizedTime: YYYYMMDDHHMMSSZ
+        0x18 => text.to_string(),
+        _ => return None,
+    };
+    NaiveDateTime::parse_from_str(&full, "%Y%m%d%H%M%SZ")
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+/// Common name of an X.501 `Name`, falling back to the organization.
+fn der_name(mut name: &[u8]) -> String {
+    let mut organization = None;
+    while let Some((_, set, rest)) = der_tlv(name) {
+        name = rest;
+        let Some((_, attribute, _)) = der_tlv(set) else {
+            continue;
+        };
+        let Some((0x06, oid, value)) = der_tlv(attribute) else {
+            continue;
+        };
+        let Some((_, value, _)) = der_tlv(value) else {
+            continue;
+        };
+        let value = String::from_utf8_lossy(value).to_string();
+        if oid == OID_COMMON_NAME {
+            return value;
+        }
+        if oid == OID_ORGANIZATION {
+            organization = Some(value);
+        }
+    }
+    organization.unwrap_or_else(|| "(unnamed)".into())
+}
+
+/// Read subject, issuer and validity from a DER certificate.
+fn parse_certificate(der: &[u8]) -> Option<CertInfo> {
+    let (0x30, certificate, _) = der_tlv(der)? else {
+        return None;
+    };
+    let (0x30, mut tbs, _) = der_tlv(certificate)? else {
+        return None;
+    };
+    // Optional explicit version [0]
+    if tbs.first() == Some(&0xa0) {
+        tbs = der_tlv(tbs)?.2;
+    }
+    let (_, _serial, rest) = der_tlv(tbs)?;
+    let (_, _signature, rest) = der_tlv(rest)?;
+    let (_, issuer, rest) = der_tlv(rest)?;
+    let (_, validity, rest) = der_tlv(rest)?;
+    let (_, subject, _) = der_tlv(rest)?;
+    let (before_tag, not_before, rest) = der_tlv(validity)?;
+    let (after_tag, not_after, _) = der_tlv(rest)?;
+    Some(CertInfo {
+        subject: der_name(subject),
+        issuer: der_name(issuer),
+        not_before: der_time(before_tag, not_before)?,
+        not_after: der_time(after_tag, not_after)?,
+    })
+}
+
+/// Accepts every chain so expired or untrusted certificates can still be
+/// reported, and keeps the real verification outcome for the report.
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<rustls::client::WebPkiServerVerifier>,
+    outcome: Mutex<Option<Result<(), String>>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let outcome = self
+            .inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+        *self.outcome.lock() = Some(outcome);
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Split `host`, `host:port` or `[v6]:port`; the port defaults to 443.
+fn split_target(target: &str) -> Option<(String, u16)> {
+    let target = target.trim();
+    if let Some(rest) = target.strip_prefix('[') {
+        let (host, port) = rest.split_once(']')?;
+        let port = match port.strip_prefix(':') {
+            Some(port) => port.parse().ok()?,
+            None if port.is_empty() => 443,
+            None => return None,
+        };
+        return Some((host.to_string(), port));
+    }
+    match target.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') => Some((host.to_string(), port.parse().ok()?)),
+        None if target.is_empty() => None,
+        // Bare IPv6 addresses and plain hostnames default to 443.
+        Some(_) | None => Some((target.to_string(), 443)),
+    }
+}
+
+/// `expired`, `critical`, `warning` or `ok` for the days left until an expiry
+/// (the soonest in a chain here; registrations in `domain_info`).
+pub(super) fn status(days_left: i64, warn_days: i64, critical_days: i64) -> &'static str {
+    if days_left < 0 {
+        "expired"
+    } else if days_left <= critical_days {
+        "critical"
+    } else if days_left <= warn_days {
+        "warning"
+    } else {
+        "ok"
+    }
+}
+
+struct CheckResult {
+    chain: Vec<CertInfo>,
+    verification: Result<(), String>,
+}
+
+/// Report TLS certificate chains and days to expiry for configured endpoints.
+pub struct CertCheckTool {
+    config: CertCheckConfig,
+}
+
+impl CertCheckTool {
+    pub fn new(config: CertCheckConfig) -> Self {
+        Self { config }
+    }
+
+    async fn check(&self, host: &str, port: u16) -> anyhow::Result<CheckResult> {
+        let roots: rustls::RootCertStore = webpki_roots::TLS_SERVER_ROOTS.iter().cloned().collect();
+        let verifier = Arc::new(RecordingVerifier {
+            inner: rustls::client::WebPkiServerVerifier::builder(Arc::new(roots)).build()?,
+            outcome: Mutex::new(None),
+        });
+        let tls_config = rustls::ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(verifier.clone())
+            .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(tls_config));
+        let server_name = ServerName::try_from(host.to_string())?;
+
+        let timeout = Duration::from_secs(self.config.timeout_secs.max(1));
+        let tls = tokio::time::timeout(timeout, async {
+            let tcp = tokio::net::TcpStream::connect((host, port)).await?;
+            connector.connect(server_name, tcp).await
+        })
+        .await
+        .map_err(|_| anyhow::anyhow!("timed out after {}s", timeout.as_secs()))??;
+
+        let chain = tls
+            .get_ref()
+            .1
+            .peer_certificates()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|cert| parse_certificate(cert.as_ref()))
+            .collect::<Vec<_>>();
+        if chain.is_empty() {
+            anyhow::bail!("server presented no readable certificates");
+        }
+        let verification = verifier
+            .outcome
+            .lock()
+            .take()
+            .unwrap_or_else(|| Err("chain was not verified".into()));
+        Ok(CheckResult {
+            chain,
+            verification,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for CertCheckTool {
+    fn name(&self) -> &str {
+        "cert_check"
+    }
+
+    fn description(&self) -> &str {
+        "Check TLS certificates of configured endpoints: chain, trust, and days until \
+         expiry, with a status of ok, warning, critical, or expired per endpoint. Omit \
+         'target' to check every configured endpoint."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "target": {
+                    "type": "string",
+                    "description": format!(
+                        "Endpoint to check (configured: {})",
+                        self.config.targets.join(", ")
+                    )
+                },
+                "warn_days": {
+                    "type": "integer",
+                    "description": format!("Override the warning threshold (default {})", self.config.warn_days)
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let targets: Vec<&String> = match args.get("target").and_then(|v| v.as_str()) {
+            Some(wanted) => match self.config.targets.iter().find(|t| t.as_str() == wanted) {
+                Some(target) => vec![target],
+                None => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Target '{wanted}' is not in [cert_check] targets")),
+                    })
+                }
+            },
+            None => self.config.targets.iter().collect(),
+        };
+        if targets.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("No targets configured in [cert_check] targets".into()),
+            });
+        }
+        let warn_days = args
+            .get("warn_days")
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(self.config.warn_days);
+
+        let now = Utc::now();
+        let mut output = String::new();
+        let mut counts = std::collections::BTreeMap::<&str, usize>::new();
+        for target in targets {
+            let Some((host, port)) = split_target(target) else {
+                *counts.entry("error").or_default() += 1;
+                let _ = writeln!(output, "{target}: error — invalid target");
+                continue;
+            };
+            match self.check(&host, port).await {
+                Ok(result) => {
+                    let soonest = result
+                        .chain
+                        .iter()
+                        .map(|cert| cert.not_after)
+                        .min()
+                        .unwrap_or(now);
+                    let days_left = (soonest - now).num_days();
+                    let mut state = status(days_left, warn_days, self.config.critical_days);
+                    if result.verification.is_err() && state == "ok" {
+                        state = "untrusted";
+                    }
+                    *counts.entry(state).or_default() += 1;
+                    let _ = writeln!(
+                        output,
+                        "{target}: {state} — expires {} ({days_left} days), {}",
+                        soonest.format("%Y-%m-%d"),
+                        match &result.verification {
+                            Ok(()) => "chain trusted".to_string(),
+                            Err(e) => format!("chain NOT trusted: {e}"),
+                        }
+                    );
+                    for (depth, cert) in result.chain.iter().enumerate() {
+                        let _ = writeln!(
+                            output,
+                            "  {depth}. {} (issuer {}) valid {} → {}",
+                            cert.subject,
+                            cert.issuer,
+                            cert.not_before.format("%Y-%m-%d"),
+                            cert.not_after.format("%Y-%m-%d")
+                        );
+                    }
+                }
+                Err(e) => {
+                    *counts.entry("error").or_default() += 1;
+                    let _ = writeln!(output, "{target}: error — {e}");
+                }
+            }
+        }
+
+        let summary: Vec<String> = counts
+            .iter()
+            .map(|(state, n)| format!("{n} {state}"))
+            .collect();
+        let _ = write!(output, "Summary: {}", summary.join(", "));
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// DER for a minimal certificate skeleton: version, serial, algorithm,
+    /// issuer "Test CA", validity, subject "example.com".
+    fn fake_certificate(not_before: &[u8], not_after: &[u8]) -> Vec<u8> {
+        fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+            let mut out = vec![tag];
+            if contents.len() < 0x80 {
+                out.push(u8::try_from(contents.len()).unwrap());
+            } else {
+                out.push(0x82);
+                out.extend_from_slice(&u16::try_from(contents.len()).unwrap().to_be_bytes());
+            }
+            out.extend_from_slice(contents);
+            out
+        }
+        let name = |cn: &str| {
+            let attribute = [tlv(0x06, &OID_COMMON_NAME), tlv(0x0c, cn.as_bytes())].concat();
+            tlv(0x30, &tlv(0x31, &tlv(0x30, &attribute)))
+        };
+        let tbs = [
+            tlv(0xa0, &tlv(0x02, &[2])),
+            tlv(0x02, &[1]),
+            tlv(0x30, &tlv(0x06, &[0x2a, 0x86, 0x48])),
+            name("Test CA"),
+            tlv(
+                0x30,
+                &[tlv(0x17, not_before), tlv(0x18, not_after)].concat(),
+            ),
+            name("example.com"),
+        ]
+        .concat();
+        tlv(0x30, &tlv(0x30, &tbs))
+    }
+
+    #[test]
+    fn parses_names_and_both_time_formats() {
+        let der = fake_certificate(b"990101000000Z", b"20301231235959Z");
+        let info = parse_certificate(&der).unwrap();
+        assert_eq!(info.subject, "example.com");
+        assert_eq!(info.issuer, "Test CA");
+        assert_eq!(info.not_before.to_rfc3339(), "1999-01-01T00:00:00+00:00");
+        assert_eq!(info.not_after.to_rfc3339(), "2030-12-31T23:59:59+00:00");
+        assert!(parse_certificate(&der[..der.len() - 3]).is_none());
+    }
+
+    #[test]
+    fn targets_and_thresholds() {
+        assert_eq!(
+            split_target("example.com"),
+            Some(("example.com".into(), 443))
+        );
+        assert_eq!(split_target("mail.lan:993"), Some(("mail.lan".into(), 993)));
+        assert_eq!(split_target("[::1]:8443"), Some(("::1".into(), 8443)));
+        assert_eq!(split_target("bad:port"), None);
+
+        assert_eq!(status(-1, 21, 7), "expired");
+        assert_eq!(status(5, 21, 7), "critical");
+        assert_eq!(status(20, 21, 7), "warning");
+        assert_eq!(status(90, 21, 7), "ok");
+    }
+}
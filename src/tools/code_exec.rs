@@ -0,0 +1,415 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::CodeExecConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Maximum snippet size accepted from the model.
+const MAX_CODE_BYTES: usize = 64 * 1024;
+/// Maximum output size returned to the model (256KB).
+const MAX_OUTPUT_BYTES: usize = 262_144;
+/// Process limit inside the sandbox to stop fork bombs.
+const SANDBOX_PIDS_LIMIT: &str = "64";
+/// Interpreters read the snippet from stdin so it never touches the host filesystem.
+const PYTHON_INTERPRETER: &[&str] = &["python3", "-"];
+const NODE_INTERPRETER: &[&str] = &["node", "-"];
+
+/// Run short Python or JavaScript snippets in a network-less container sandbox.
+pub struct CodeExecTool {
+    security: Arc<SecurityPolicy>,
+    config: CodeExecConfig,
+}
+
+impl CodeExecTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: CodeExecConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn image_and_interpreter(&self, language: &str) -> Option<(&str, &'static [&'static str])> {
+        match language {
+            "python" | "py" => Some((self.config.python_image.as_str(), PYTHON_INTERPRETER)),
+            "javascript" | "js" | "node" => {
+                Some((self.config.node_image.as_str(), NODE_INTERPRETER))
+            }
+            _ => None,
+        }
+    }
+
+    /// Build the container invocation. The snippet itself is fed over stdin.
+    fn build_run_args(&self, container_name: &str, language: &str) -> Option<Vec<String>> {
+        let (image, interpreter) = self.image_and_interpreter(language)?;
+        let mut args = sandbox_run_args(
+            &self.config,
+            &self.security.workspace_dir,
+            container_name,
+            image,
+        );
+        args.extend(interpreter.iter().map(|s| (*s).to_string()));
+        Some(args)
+    }
+
+    async fn force_remove(&self, container_name: &str) {
+        force_remove_container(&self.config.container_runtime, container_name).await;
+    }
+}
+
+/// `run` arguments up to and including `image` for a sandbox container with
+/// no network, a read-only root, dropped capabilities and resource limits.
+/// The command to run inside is appended by the caller.
+pub(super) fn sandbox_run_args(
+    config: &CodeExecConfig,
+    workspace_dir: &Path,
+    container_name: &str,
+    image: &str,
+) -> Vec<String> {
+    let mount_mode = if config.read_only_workspace {
+        "ro"
+    } else {
+        "rw"
+    };
+    vec![
+        "run".into(),
+        "--rm".into(),
+        "-i".into(),
+        "--name".into(),
+        container_name.into(),
+        "--network".into(),
+        "none".into(),
+        "--memory".into(),
+        format!("{}m", config.memory_limit_mb.max(16)),
+        "--cpus".into(),
+        format!("{:.2}", config.cpu_limit.max(0.1)),
+        "--pids-limit".into(),
+        SANDBOX_PIDS_LIMIT.into(),
+        "--read-only".into(),
+        "--tmpfs".into(),
+        "/tmp:rw,size=64m".into(),
+        "--cap-drop".into(),
+        "ALL".into(),
+        "--security-opt".into(),
+        "no-new-privileges".into(),
+        "-v".into(),
+        format!("{}:/workspace:{mount_mode}", workspace_dir.display()),
+        "-w".into(),
+        "/workspace".into(),
+        image.into(),
+    ]
+}
+
+/// Remove a sandbox container that outlived its client process.
+pub(super) async fn force_remove_container(runtime: &str, container_name: &str) {
+    let _ = Command::new(runtime)
+        .args(["rm", "-f", container_name])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await;
+}
+
+fn truncate_output(mut text: String, label: &str) -> String {
+    if text.len() > MAX_OUTPUT_BYTES {
+        let mut end = MAX_OUTPUT_BYTES;
+        while end > 0 && !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+        let _ = write!(text, "\n... [{label} truncated at 256KB]");
+    }
+    text
+}
+
+#[async_trait]
+impl Tool for CodeExecTool {
+    fn name(&self) -> &str {
+        "code_exec"
+    }
+
+    fn description(&self) -> &str {
+        "Run a short Python or JavaScript snippet in an isolated container with no network access. \
+         The workspace is mounted at /workspace. Print results to stdout."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "language": {
+                    "type": "string",
+                    "enum": ["python", "javascript"],
+                    "description": "Snippet language"
+                },
+                "code": {
+                    "type": "string",
+                    "description": "Source code to execute; write results to stdout"
+                }
+            },
+            "required": ["language", "code"]
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let language = args
+            .get("language")
+            .and_then(|v| v.as_str())
+            .map(|v| v.trim().to_ascii_lowercase())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'language' parameter"))?;
+        let code = args
+            .get("code")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'code' parameter"))?;
+
+        if code.len() > MAX_CODE_BYTES {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Code too large: {} bytes (limit: {MAX_CODE_BYTES} bytes)",
+                    code.len()
+                )),
+            });
+        }
+
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        let container_name = format!("zeroclaw-exec-{}", uuid::Uuid::new_v4().simple());
+        let Some(run_args) = self.build_run_args(&container_name, &language) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Unsupported language '{language}'. Use 'python' or 'javascript'."
+                )),
+            });
+        };
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let mut child = match Command::new(&self.config.container_runtime)
+            .args(&run_args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Failed to start '{}' sandbox: {e}",
+                        self.config.container_runtime
+                    )),
+                });
+            }
+        };
+
+        // Feed the snippet while draining output, all under the timeout, so a
+        // sandbox that never reads stdin cannot stall the call. Dropping the
+        // future kills the runtime client (`kill_on_drop`).
+        let stdin = child.stdin.take();
+        let feed_stdin = async {
+            if let Some(mut stdin) = stdin {
+                stdin.write_all(code.as_bytes()).await?;
+                stdin.shutdown().await?;
+            }
+            Ok::<(), std::io::Error>(())
+        };
+        let run = async {
+            tokio::try_join!(feed_stdin, child.wait_with_output()).map(|((), output)| output)
+        };
+
+        let timeout = Duration::from_secs(self.config.timeout_secs.max(1));
+        match tokio::time::timeout(timeout, run).await {
+            Ok(Ok(output)) => {
+                let stdout = truncate_output(
+                    String::from_utf8_lossy(&output.stdout).to_string(),
+                    "output",
+                );
+                let stderr = truncate_output(
+                    String::from_utf8_lossy(&output.stderr).to_string(),
+                    "stderr",
+                );
+                Ok(ToolResult {
+                    success: output.status.success(),
+                    output: stdout,
+                    error: if stderr.is_empty() {
+                        None
+                    } else {
+                        Some(stderr)
+                    },
+                })
+            }
+            Ok(Err(e)) => {
+                self.force_remove(&container_name).await;
+                Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to run sandbox: {e}")),
+                })
+            }
+            Err(_) => {
+                // Dropping the client does not stop a detached container; remove it explicitly.
+                self.force_remove(&container_name).await;
+                Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Code execution timed out after {}s and was killed",
+                        timeout.as_secs()
+                    )),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_security(level: AutonomyLevel, max_actions_per_hour: u32) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: level,
+            max_actions_per_hour,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn tool(level: AutonomyLevel) -> CodeExecTool {
+        CodeExecTool::new(test_security(level, 100), CodeExecConfig::default())
+    }
+
+    #[test]
+    fn code_exec_tool_name_and_schema() {
+        let tool = tool(AutonomyLevel::Full);
+        assert_eq!(tool.name(), "code_exec");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["code"].is_object());
+        assert!(schema["properties"]["language"].is_object());
+    }
+
+    #[test]
+    fn run_args_disable_network_and_limit_resources() {
+        let tool = tool(AutonomyLevel::Full);
+        let args = tool.build_run_args("sandbox-1", "python").unwrap();
+        let joined = args.join(" ");
+        assert!(joined.contains("--network none"));
+        assert!(joined.contains("--memory 256m"));
+        assert!(joined.contains("--read-only"));
+        assert!(joined.contains("--cap-drop ALL"));
+        assert!(joined.contains(":/workspace:rw"));
+        assert!(joined.ends_with("python:3.12-alpine python3 -"));
+    }
+
+    #[test]
+    fn run_args_reject_unknown_language() {
+        let tool = tool(AutonomyLevel::Full);
+        assert!(tool.build_run_args("sandbox-1", "ruby").is_none());
+        assert!(tool
+            .build_run_args("sandbox-1", "javascript")
+            .unwrap()
+            .ends_with(&["node".to_string(), "-".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn code_exec_blocks_readonly_mode() {
+        let tool = tool(AutonomyLevel::ReadOnly);
+        let result = tool
+            .execute(json!({"language": "python", "code": "print(1)"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn code_exec_blocks_when_rate_limited() {
+        let tool = CodeExecTool::new(
+            test_security(AutonomyLevel::Full, 0),
+            CodeExecConfig::default(),
+        );
+        let result = tool
+            .execute(json!({"language": "python", "code": "print(1)"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Rate limit"));
+    }
+
+    #[tokio::test]
+    async fn code_exec_rejects_oversized_code() {
+        let tool = tool(AutonomyLevel::Full);
+        let code = "x".repeat(MAX_CODE_BYTES + 1);
+        let result = tool
+            .execute(json!({"language": "python", "code": code}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Code too large"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn code_exec_times_out_when_sandbox_never_reads_stdin() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let runtime = tmp.path().join("fake-runtime");
+        std::fs::write(
+            &runtime,
+            "#!/bin/sh\ncase \"$1\" in run) exec sleep 30 ;; esac\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&runtime, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let config = CodeExecConfig {
+            container_runtime: runtime.display().to_string(),
+            timeout_secs: 1,
+            ..CodeExecConfig::default()
+        };
+        let tool = CodeExecTool::new(test_security(AutonomyLevel::Full, 100), config);
+        let started = std::time::Instant::now();
+        let result = tool
+            .execute(json!({"language": "python", "code": "x".repeat(MAX_CODE_BYTES)}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("timed out"));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -609,6 +609,10 @@ impl Tool for ComposioTool {
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let action = args
             .get("action")
@@ -0,0 +1,472 @@
+use super::concurrency;
+use super::template::{self, Escape};
+use super::traits::{Tool, ToolResult};
+use crate::config::confd::{ConfD, ConfDWatcher};
+use crate::config::CompositeToolConfig;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Context names the framework fills in; parameters may not reuse them.
+const RESERVED_NAMES: [&str; 5] = ["now", "prev", "steps", "last_result", "trigger"];
+
+/// A config-defined tool that runs a fixed sequence of existing tool calls,
+/// so a common multi-step operation costs the LLM a single call.
+pub struct CompositeTool {
+    name: String,
+    config: CompositeToolConfig,
+    /// Resolved step tools, one per `config.steps` entry.
+    steps: Vec<Arc<dyn Tool>>,
+}
+
+impl CompositeTool {
+    /// Build a composite tool, resolving every step against `registry` (the
+    /// caller's allowlisted tools, when scoped) and checking that each
+    /// placeholder refers to a parameter, a context variable, or an earlier
+    /// step.
+    pub fn new(
+        name: &str,
+        config: CompositeToolConfig,
+        registry: &[Arc<dyn Tool>],
+    ) -> Result<Self> {
+        if config.steps.is_empty() {
+            bail!("composite tool '{name}' has no steps");
+        }
+        if registry.iter().any(|tool| tool.name() == name) {
+            bail!("composite tool '{name}' shadows a built-in tool");
+        }
+        if let Some(param) = config
+            .parameters
+            .keys()
+            .find(|param| RESERVED_NAMES.contains(&param.as_str()))
+        {
+            bail!("composite tool '{name}' parameter '{param}' is a reserved name");
+        }
+
+        let mut steps = Vec::with_capacity(config.steps.len());
+        for (index, step) in config.steps.iter().enumerate() {
+            let Some(tool) = registry.iter().find(|tool| tool.name() == step.tool) else {
+                bail!(
+                    "composite tool '{name}' step {index} uses unknown tool '{}'",
+                    step.tool
+                );
+            };
+            for value in step.args.values() {
+                for key in template::placeholders_in(value) {
+                    if !placeholder_is_valid(&key, &config, index) {
+                        bail!("composite tool '{name}' step {index} has unknown placeholder {{{{{key}}}}}");
+                    }
+                }
+            }
+            steps.push(tool.clone());
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            config,
+            steps,
+        })
+    }
+}
+
+/// Composites declared in `conf.d/tools`, rebuilt whenever the shared
+/// [`ConfDWatcher`] reloads the directory, so added or edited files apply
+/// without a restart.
+pub struct ConfDComposites {
+    watcher: Arc<ConfDWatcher>,
+    /// Tools the composites may call, already scoped and limited.
+    base: Vec<Arc<dyn Tool>>,
+    /// Names defined in `config.toml`, which win over `conf.d` files.
+    taken: HashSet<String>,
+    allowed: Option<Vec<String>>,
+    limits: HashMap<String, usize>,
+    built: Mutex<Option<BuiltComposites>>,
+}
+
+/// The snapshot the composites were built from, and the tools built.
+type BuiltComposites = (Arc<ConfD>, Vec<Arc<dyn Tool>>);
+
+impl ConfDComposites {
+    pub fn new(
+        watcher: Arc<ConfDWatcher>,
+        base: Vec<Arc<dyn Tool>>,
+        taken: HashSet<String>,
+        allowed: Option<&[String]>,
+        limits: HashMap<String, usize>,
+    ) -> Self {
+        Self {
+            watcher,
+            base,
+            taken,
+            allowed: allowed.map(<[String]>::to_vec),
+            limits,
+            built: Mutex::new(None),
+        }
+    }
+
+    /// Current composites, rebuilt if `conf.d` changed since the last call.
+    pub fn current(&self) -> Vec<Arc<dyn Tool>> {
+        let conf_d = self.watcher.current();
+        let mut built = self.built.lock();
+        if let Some((seen, tools)) = built.as_ref() {
+            if Arc::ptr_eq(seen, &conf_d) {
+                return tools.clone();
+            }
+        }
+
+        let mut names: Vec<&String> = conf_d
+            .composite_tools
+            .keys()
+            .filter(|name| !self.taken.contains(*name))
+            .filter(|name| {
+                self.allowed
+                    .as_ref()
+                    .is_none_or(|allowed| allowed.contains(name))
+            })
+            .collect();
+        names.sort();
+        let tools: Vec<Arc<dyn Tool>> = names
+            .into_iter()
+            .filter_map(|name| {
+                match CompositeTool::new(name, conf_d.composite_tools[name].clone(), &self.base) {
+                    Ok(tool) => Some(concurrency::limit(Arc::new(tool), &self.limits)),
+                    Err(e) => {
+                        tracing::warn!("Skipping conf.d composite tool: {e}");
+                        None
+                    }
+                }
+            })
+            .collect();
+        *built = Some((conf_d, tools.clone()));
+        tools
+    }
+}
+
+fn placeholder_is_valid(key: &str, config: &CompositeToolConfig, step_index: usize) -> bool {
+    let (root, rest) = key.split_once('.').unwrap_or((key, ""));
+    match root {
+        "now" => rest.is_empty(),
+        "prev" => rest.is_empty() && step_index > 0,
+        "last_result" => matches!(rest, "output" | "success") && step_index > 0,
+        "steps" => rest.parse::<usize>().is_ok_and(|n| n < step_index),
+        "trigger" => rest == "payload" || rest.starts_with("payload."),
+        param => rest.is_empty() && config.parameters.contains_key(param),
+    }
+}
+
+/// `shell` commands get their substituted values shell-quoted.
+fn escape_for(tool: &str, arg: &str) -> Escape {
+    if tool == "shell" && arg == "command" {
+        Escape::Shell
+    } else {
+        Escape::None
+    }
+}
+
+#[async_trait]
+impl Tool for CompositeTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.config.description
+    }
+
+    fn parameters_schema(&self) -> Value {
+        let mut names: Vec<&String> = self.config.parameters.keys().collect();
+        names.sort();
+        let properties: serde_json::Map<String, Value> = names
+            .iter()
+            .map(|name| {
+                (
+                    (*name).clone(),
+                    json!({ "type": "string", "description": self.config.parameters[*name] }),
+                )
+            })
+            .collect();
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": names
+        })
+    }
+
+    fn nested_tools(&self, _args: &Value) -> Vec<String> {
+        self.config
+            .steps
+            .iter()
+            .map(|step| step.tool.clone())
+            .collect()
+    }
+
+    fn writes_state(&self) -> bool {
+        self.steps.iter().any(|step| step.writes_state())
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let mut ctx = serde_json::Map::new();
+        for name in self.config.parameters.keys() {
+            let value = match args.get(name) {
+                Some(Value::String(s)) => s.clone(),
+                Some(Value::Null) | None => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Missing '{name}' parameter")),
+                    })
+                }
+                Some(other) => other.to_string(),
+            };
+            ctx.insert(name.clone(), Value::String(value));
+        }
+        ctx.insert("now".into(), Value::String(template::now()));
+        ctx.insert("trigger".into(), json!({ "payload": args }));
+        ctx.insert("steps".into(), json!({}));
+
+        let mut output = String::new();
+        for (index, (step, tool)) in self.config.steps.iter().zip(&self.steps).enumerate() {
+            let ctx_value = Value::Object(ctx.clone());
+            let rendered: Result<serde_json::Map<String, Value>> = step
+                .args
+                .iter()
+                .map(|(key, value)| {
+                    let escape = escape_for(&step.tool, key);
+                    Ok((
+                        key.clone(),
+                        template::render_value(value, &ctx_value, escape)?,
+                    ))
+                })
+                .collect();
+            let result = match rendered {
+                Ok(step_args) => match tool.execute(Value::Object(step_args)).await {
+                    Ok(result) => result,
+                    Err(e) => ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(e.to_string()),
+                    },
+                },
+                Err(e) => ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e.to_string()),
+                },
+            };
+            if !result.success {
+                let reason = result.error.unwrap_or(result.output);
+                return Ok(ToolResult {
+                    success: false,
+                    output: output.clone(),
+                    error: Some(format!("Step {index} ({}) failed: {reason}", step.tool)),
+                });
+            }
+            output = result.output.trim().to_string();
+            if let Some(Value::Object(steps)) = ctx.get_mut("steps") {
+                steps.insert(index.to_string(), Value::String(output.clone()));
+            }
+            ctx.insert("prev".into(), Value::String(output.clone()));
+            ctx.insert(
+                "last_result".into(),
+                json!({ "output": output, "success": true }),
+            );
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CompositeStepConfig;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Echoes `text` and fails when it is "fail".
+    struct EchoTool {
+        seen: Arc<Mutex<Vec<Value>>>,
+    }
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "echo"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({ "type": "object" })
+        }
+
+        async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+            self.seen.lock().unwrap().push(args.clone());
+            let text = args["text"].as_str().unwrap_or_default();
+            Ok(ToolResult {
+                success: text != "fail",
+                output: format!("{text}\n"),
+                error: (text == "fail").then(|| "echo failed".into()),
+            })
+        }
+    }
+
+    fn step(text: &str) -> CompositeStepConfig {
+        CompositeStepConfig {
+            tool: "echo".into(),
+            args: json!({ "text": text }).as_object().unwrap().clone(),
+        }
+    }
+
+    fn composite(steps: Vec<CompositeStepConfig>) -> CompositeToolConfig {
+        CompositeToolConfig {
+            description: "test".into(),
+            parameters: HashMap::from([("room".into(), "Room name".into())]),
+            steps,
+        }
+    }
+
+    fn registry() -> (Vec<Arc<dyn Tool>>, Arc<Mutex<Vec<Value>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let tools: Vec<Arc<dyn Tool>> = vec![Arc::new(EchoTool { seen: seen.clone() })];
+        (tools, seen)
+    }
+
+    #[tokio::test]
+    async fn templates_params_and_pipes_outputs() {
+        let (tools, seen) = registry();
+        let tool = CompositeTool::new(
+            "movie_mode",
+            composite(vec![
+                step("dim {{room}}"),
+                step("then {{ prev }}"),
+                step("{{steps.0}} / {{last_result.output}}"),
+            ]),
+            &tools,
+        )
+        .unwrap();
+
+        let result = tool.execute(json!({ "room": "lounge" })).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "dim lounge / then dim lounge");
+        assert_eq!(seen.lock().unwrap()[1]["text"], "then dim lounge");
+        assert_eq!(tool.parameters_schema()["required"], json!(["room"]));
+    }
+
+    #[tokio::test]
+    async fn stops_at_failing_step() {
+        let (tools, seen) = registry();
+        let tool = CompositeTool::new(
+            "broken",
+            composite(vec![step("fail"), step("never")]),
+            &tools,
+        )
+        .unwrap();
+
+        let result = tool.execute(json!({ "room": "x" })).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Step 0 (echo) failed"));
+        assert_eq!(seen.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn rejects_unknown_tools_and_placeholders() {
+        let (tools, _) = registry();
+        let unknown_tool = CompositeStepConfig {
+            tool: "nope".into(),
+            args: serde_json::Map::new(),
+        };
+        assert!(CompositeTool::new("a", composite(vec![unknown_tool]), &tools).is_err());
+        assert!(CompositeTool::new("b", composite(vec![step("{{prev}}")]), &tools).is_err());
+        assert!(CompositeTool::new("c", composite(vec![step("{{steps.0}}")]), &tools).is_err());
+        assert!(CompositeTool::new("d", composite(vec![step("{{missing}}")]), &tools).is_err());
+        assert!(CompositeTool::new("echo", composite(vec![step("hi")]), &tools).is_err());
+        assert!(CompositeTool::new("e", composite(vec![step("{{steps}}")]), &tools).is_err());
+    }
+
+    #[tokio::test]
+    async fn trigger_payload_and_now_resolve() {
+        let (tools, seen) = registry();
+        let tool = CompositeTool::new(
+            "scene",
+            composite(vec![step("{{trigger.payload.scene.level}} at {{now}}")]),
+            &tools,
+        )
+        .unwrap();
+
+        let result = tool
+            .execute(json!({ "room": "x", "scene": { "level": 30 } }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.starts_with("30 at 20"));
+
+        let missing = tool.execute(json!({ "room": "x" })).await.unwrap();
+        assert!(!missing.success);
+        assert!(missing
+            .error
+            .unwrap()
+            .contains("unknown template placeholder"));
+        assert_eq!(seen.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn nested_tools_lists_every_step() {
+        let (tools, _) = registry();
+        let tool =
+            CompositeTool::new("twice", composite(vec![step("a"), step("b")]), &tools).unwrap();
+        assert_eq!(tool.nested_tools(&json!({})), ["echo", "echo"]);
+    }
+
+    #[test]
+    fn conf_d_composites_follow_directory_edits() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tools_dir = tmp.path().join("tools");
+        std::fs::create_dir_all(&tools_dir).unwrap();
+        let define = |name: &str| {
+            std::fs::write(
+                tools_dir.join(format!("{name}.toml")),
+                "description = \"test\"\nsteps = [{ tool = \"echo\", args = { text = \"hi\" } }]\n",
+            )
+            .unwrap();
+        };
+        define("first");
+        define("taken");
+
+        let (tools, _) = registry();
+        let composites = ConfDComposites::new(
+            Arc::new(ConfDWatcher::new(tmp.path().to_path_buf())),
+            tools,
+            HashSet::from(["taken".to_string()]),
+            None,
+            HashMap::new(),
+        );
+        let names = |composites: &ConfDComposites| -> Vec<String> {
+            composites
+                .current()
+                .iter()
+                .map(|tool| tool.name().to_string())
+                .collect()
+        };
+        assert_eq!(names(&composites), ["first"]);
+
+        define("second");
+        assert_eq!(names(&composites), ["first", "second"]);
+    }
+
+    #[test]
+    fn shell_commands_quote_substituted_values() {
+        assert_eq!(escape_for("shell", "command"), Escape::Shell);
+        assert_eq!(escape_for("shell", "cwd"), Escape::None);
+        assert_eq!(escape_for("echo", "command"), Escape::None);
+    }
+}
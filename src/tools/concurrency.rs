@@ -0,0 +1,144 @@
+//! Per-tool concurrency limits (`[agent] tool_concurrency`).
+//!
+//! Every agent turn, channel worker and cron job builds its own registry, so
+//! the semaphores live in a process-wide table keyed by tool name: two cron
+//! jobs calling a limited tool at the same moment queue behind the same
+//! permits instead of stampeding the target.
+
+use super::traits::{Compensation, Tool, ToolResult};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::Semaphore;
+
+/// Semaphores by (tool name, limit); a changed limit gets a fresh one.
+type SemaphoreTable = Mutex<HashMap<(String, usize), Arc<Semaphore>>>;
+
+static SEMAPHORES: OnceLock<SemaphoreTable> = OnceLock::new();
+
+fn semaphore(name: &str, limit: usize) -> Arc<Semaphore> {
+    SEMAPHORES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .entry((name.to_string(), limit))
+        .or_insert_with(|| Arc::new(Semaphore::new(limit)))
+        .clone()
+}
+
+struct ConcurrencyLimitedTool {
+    inner: Arc<dyn Tool>,
+    semaphore: Arc<Semaphore>,
+}
+
+#[async_trait]
+impl Tool for ConcurrencyLimitedTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.inner.parameters_schema()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if self.semaphore.available_permits() == 0 {
+            tracing::debug!(tool = self.inner.name(), "waiting for a concurrency slot");
+        }
+        let _permit = self.semaphore.acquire().await?;
+        self.inner.execute(args).await
+    }
+
+    fn compensation(&self, args: &serde_json::Value, result: &ToolResult) -> Option<Compensation> {
+        self.inner.compensation(args, result)
+    }
+
+    fn nested_tools(&self, args: &serde_json::Value) -> Vec<String> {
+        self.inner.nested_tools(args)
+    }
+
+    fn writes_state(&self) -> bool {
+        self.inner.writes_state()
+    }
+}
+
+/// Wrap `tool` if `limits` caps its concurrency; `0` means unlimited.
+#[allow(clippy::implicit_hasher)]
+pub fn limit(tool: Arc<dyn Tool>, limits: &HashMap<String, usize>) -> Arc<dyn Tool> {
+    match limits.get(tool.name()) {
+        Some(&max) if max > 0 => {
+            let semaphore = semaphore(tool.name(), max);
+            Arc::new(ConcurrencyLimitedTool {
+                inner: tool,
+                semaphore,
+            })
+        }
+        _ => tool,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    struct SlowTool {
+        running: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Tool for SlowTool {
+        fn name(&self) -> &str {
+            "concurrency_test_slow"
+        }
+
+        fn description(&self) -> &str {
+            "sleeps briefly"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({"type": "object"})
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            let now = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok(ToolResult {
+                success: true,
+                output: String::new(),
+                error: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn limit_is_shared_across_registries() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let limits = HashMap::from([("concurrency_test_slow".to_string(), 2)]);
+
+        // Separate wrappers stand in for separate registries (turns, cron jobs).
+        let tools: Vec<Arc<dyn Tool>> = (0..6)
+            .map(|_| {
+                let tool: Arc<dyn Tool> = Arc::new(SlowTool {
+                    running: running.clone(),
+                    peak: peak.clone(),
+                });
+                limit(tool, &limits)
+            })
+            .collect();
+        let calls = tools.iter().map(|tool| tool.execute(serde_json::json!({})));
+        for result in futures_util::future::join_all(calls).await {
+            assert!(result.unwrap().success);
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,6 +1,8 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{Compensation, Tool, ToolResult};
 use crate::config::Config;
-use crate::cron::{self, DeliveryConfig, JobType, Schedule, SessionTarget};
+use crate::cron::{
+    self, ChainCondition, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
+};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -73,6 +75,20 @@ impl Tool for CronAddTool {
                 "model": { "type": "string" },
                 "delivery": { "type": "object" },
                 "delete_after_run": { "type": "boolean" },
+                "allowed_tools": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Agent jobs only: restrict the run to these tool names (omit for all tools)"
+                },
+                "after": {
+                    "type": "string",
+                    "description": "Name or id of a job this one is chained after; when both are due together it runs once that job finishes"
+                },
+                "after_condition": {
+                    "type": "string",
+                    "enum": ["success", "failure", "always"],
+                    "description": "Upstream outcome required to run (default: success)"
+                },
                 "approved": {
                     "type": "boolean",
                     "description": "Set true to explicitly approve medium/high-risk shell commands in supervised mode",
@@ -83,6 +99,10 @@ impl Tool for CronAddTool {
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.config.cron.enabled {
             return Ok(ToolResult {
@@ -145,6 +165,25 @@ impl Tool for CronAddTool {
             .get("approved")
             .and_then(serde_json::Value::as_bool)
             .unwrap_or(false);
+        let after = args
+            .get("after")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|after| !after.is_empty())
+            .map(str::to_string);
+        let after_condition = match args.get("after_condition") {
+            Some(v) => match serde_json::from_value::<ChainCondition>(v.clone()) {
+                Ok(condition) => Some(condition),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid after_condition: {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
 
         let result = match job_type {
             JobType::Shell => {
@@ -218,6 +257,21 @@ impl Tool for CronAddTool {
                     None => None,
                 };
 
+                let allowed_tools = match args.get("allowed_tools") {
+                    Some(v) => match serde_json::from_value::<Vec<String>>(v.clone()) {
+                        Ok(tools) if !tools.is_empty() => Some(tools),
+                        Ok(_) => None,
+                        Err(e) => {
+                            return Ok(ToolResult {
+                                success: false,
+                                output: String::new(),
+                                error: Some(format!("Invalid allowed_tools: {e}")),
+                            });
+                        }
+                    },
+                    None => None,
+                };
+
                 if let Some(blocked) = self.enforce_mutation_allowed("cron_add") {
                     return Ok(blocked);
                 }
@@ -231,10 +285,25 @@ impl Tool for CronAddTool {
                     model,
                     delivery,
                     delete_after_run,
+                    allowed_tools,
                 )
             }
         };
 
+        let result = match (result, after) {
+            (Ok(job), Some(after)) => {
+                let patch = CronJobPatch {
+                    after: Some(after),
+                    after_condition,
+                    ..CronJobPatch::default()
+                };
+                cron::update_job(&self.config, &job.id, patch).inspect_err(|_| {
+                    let _ = cron::remove_job(&self.config, &job.id);
+                })
+            }
+            (result, _) => result,
+        };
+
         match result {
             Ok(job) => Ok(ToolResult {
                 success: true,
@@ -244,7 +313,10 @@ impl Tool for CronAddTool {
                     "job_type": job.job_type,
                     "schedule": job.schedule,
                     "next_run": job.next_run,
-                    "enabled": job.enabled
+                    "enabled": job.enabled,
+                    "allowed_tools": job.allowed_tools,
+                    "after": job.after,
+                    "after_condition": job.after_condition
                 }))?,
                 error: None,
             }),
@@ -255,6 +327,15 @@ impl Tool for CronAddTool {
             }),
         }
     }
+
+    fn compensation(&self, _args: &serde_json::Value, result: &ToolResult) -> Option<Compensation> {
+        let created: serde_json::Value = serde_json::from_str(&result.output).ok()?;
+        let id = created.get("id")?.as_str()?;
+        Some(Compensation {
+            tool: "cron_remove".into(),
+            args: json!({ "job_id": id }),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -450,6 +531,71 @@ mod tests {
             .contains("every_ms must be > 0"));
     }
 
+    #[tokio::test]
+    async fn agent_job_stores_allowed_tools() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "0 8 * * *" },
+                "job_type": "agent",
+                "prompt": "Send the morning digest",
+                "allowed_tools": ["http_request", "notify"]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let jobs = cron::list_jobs(&cfg).unwrap();
+        assert_eq!(
+            jobs[0].allowed_tools,
+            Some(vec!["http_request".to_string(), "notify".to_string()])
+        );
+    }
+
+    #[tokio::test]
+    async fn chains_job_after_existing_job() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+
+        let upstream = tool
+            .execute(json!({
+                "name": "fetch",
+                "schedule": { "kind": "cron", "expr": "0 6 * * *" },
+                "command": "echo fetch"
+            }))
+            .await
+            .unwrap();
+        assert!(upstream.success, "{:?}", upstream.error);
+
+        let chained = tool
+            .execute(json!({
+                "name": "report",
+                "schedule": { "kind": "cron", "expr": "0 6 * * *" },
+                "command": "echo report",
+                "after": "fetch",
+                "after_condition": "failure"
+            }))
+            .await
+            .unwrap();
+        assert!(chained.success, "{:?}", chained.error);
+        assert!(chained.output.contains("\"after_condition\": \"failure\""));
+
+        let dangling = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "0 6 * * *" },
+                "command": "echo orphan",
+                "after": "missing"
+            }))
+            .await
+            .unwrap();
+        assert!(!dangling.success);
+        assert_eq!(cron::list_jobs(&cfg).unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn agent_job_requires_prompt() {
         let tmp = TempDir::new().unwrap();
@@ -469,4 +615,23 @@ mod tests {
             .unwrap_or_default()
             .contains("Missing 'prompt'"));
     }
+
+    #[tokio::test]
+    async fn compensation_removes_created_job() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+        let args = json!({
+            "schedule": { "kind": "cron", "expr": "*/5 * * * *" },
+            "job_type": "shell",
+            "command": "echo ok"
+        });
+        let result = tool.execute(args.clone()).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let undo = tool.compensation(&args, &result).unwrap();
+        assert_eq!(undo.tool, "cron_remove");
+        let jobs = cron::list_jobs(&cfg).unwrap();
+        assert_eq!(undo.args["job_id"], jobs[0].id);
+    }
 }
@@ -67,6 +67,10 @@ impl Tool for CronRemoveTool {
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.config.cron.enabled {
             return Ok(ToolResult {
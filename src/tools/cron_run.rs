@@ -1,9 +1,8 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
-use crate::cron::{self, JobType};
+use crate::cron::{self, JobType, RunOverrides};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
-use chrono::Utc;
 use serde_json::json;
 use std::sync::Arc;
 
@@ -25,7 +24,7 @@ impl Tool for CronRunTool {
     }
 
     fn description(&self) -> &str {
-        "Force-run a cron job immediately and record run history"
+        "Force-run a cron job immediately (optionally with a different run date, delivery target or model) and record it as a manual run"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -33,6 +32,15 @@ impl Tool for CronRunTool {
             "type": "object",
             "properties": {
                 "job_id": { "type": "string" },
+                "date": {
+                    "type": "string",
+                    "description": "Run date (YYYY-MM-DD) substituted for {{date}} in the command/prompt; defaults to today"
+                },
+                "delivery": {
+                    "type": "object",
+                    "description": "Delivery override for this run only, e.g. {mode:'announce',channel:'telegram',to:'123'}"
+                },
+                "model": { "type": "string", "description": "Model override for agent jobs" },
                 "approved": {
                     "type": "boolean",
                     "description": "Set true to explicitly approve medium/high-risk shell commands in supervised mode",
@@ -43,6 +51,10 @@ impl Tool for CronRunTool {
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.config.cron.enabled {
             return Ok(ToolResult {
@@ -66,6 +78,20 @@ impl Tool for CronRunTool {
             .get("approved")
             .and_then(serde_json::Value::as_bool)
             .unwrap_or(false);
+        let overrides = match serde_json::from_value::<RunOverrides>(json!({
+            "date": args.get("date"),
+            "delivery": args.get("delivery"),
+            "model": args.get("model"),
+        })) {
+            Ok(overrides) => overrides,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Invalid override: {e}")),
+                });
+            }
+        };
 
         if !self.security.can_act() {
             return Ok(ToolResult {
@@ -115,30 +141,24 @@ impl Tool for CronRunTool {
             });
         }
 
-        let started_at = Utc::now();
-        let (success, output) = cron::scheduler::execute_job_now(&self.config, &job).await;
-        let finished_at = Utc::now();
-        let duration_ms = (finished_at - started_at).num_milliseconds();
-        let status = if success { "ok" } else { "error" };
-
-        let _ = cron::record_run(
+        let run = Box::pin(cron::scheduler::run_job_manually(
             &self.config,
-            &job.id,
-            started_at,
-            finished_at,
-            status,
-            Some(&output),
-            duration_ms,
-        );
-        let _ = cron::record_last_run(&self.config, &job.id, finished_at, success, &output);
+            &job,
+            &overrides,
+        ))
+        .await;
+        let success = run.success;
+        let status = if success { "ok" } else { "error" };
 
         Ok(ToolResult {
             success,
             output: serde_json::to_string_pretty(&json!({
                 "job_id": job.id,
+                "run_id": run.run_id,
+                "invocation": "manual",
                 "status": status,
-                "duration_ms": duration_ms,
-                "output": output
+                "duration_ms": run.duration_ms,
+                "output": run.output
             }))?,
             error: if success {
                 None
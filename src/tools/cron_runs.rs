@@ -27,6 +27,7 @@ struct RunView {
     status: String,
     output: Option<String>,
     duration_ms: Option<i64>,
+    invocation: String,
 }
 
 #[async_trait]
@@ -87,6 +88,7 @@ impl Tool for CronRunsTool {
                         status: run.status,
                         output: run.output.map(|out| truncate(&out, MAX_RUN_OUTPUT_CHARS)),
                         duration_ms: run.duration_ms,
+                        invocation: run.invocation,
                     })
                     .collect();
 
@@ -159,6 +159,10 @@ impl Tool for DelegateTool {
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let agent_name = args
             .get("agent")
@@ -476,6 +480,22 @@ impl Tool for ToolArcRef {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         self.inner.execute(args).await
     }
+
+    fn compensation(
+        &self,
+        args: &serde_json::Value,
+        result: &ToolResult,
+    ) -> Option<super::traits::Compensation> {
+        self.inner.compensation(args, result)
+    }
+
+    fn nested_tools(&self, args: &serde_json::Value) -> Vec<String> {
+        self.inner.nested_tools(args)
+    }
+
+    fn writes_state(&self) -> bool {
+        self.inner.writes_state()
+    }
 }
 
 struct NoopObserver;
@@ -0,0 +1,383 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{InventoryConfig, LocaleConfig};
+use crate::inventory::{self, Device, Observation};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+/// Rows shown by `list` before the output is cut off.
+const MAX_LISTED: usize = 200;
+
+/// Query and update the device inventory (`workspace/inventory/devices.db`).
+pub struct DeviceInventoryTool {
+    config: InventoryConfig,
+    locale: LocaleConfig,
+    security: Arc<SecurityPolicy>,
+}
+
+impl DeviceInventoryTool {
+    pub fn new(config: InventoryConfig, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            config,
+            locale: LocaleConfig::default(),
+            security,
+        }
+    }
+
+    /// Show sighting and event times in the `[locale]` conventions.
+    pub fn with_locale(mut self, locale: LocaleConfig) -> Self {
+        self.locale = locale;
+        self
+    }
+}
+
+fn describe(device: &Device, locale: &LocaleConfig) -> String {
+    let mut line = device.name.clone().unwrap_or_else(|| device.key.clone());
+    for value in [&device.address, &device.mac].into_iter().flatten() {
+        if !line.contains(value.as_str()) {
+            let _ = write!(line, " {value}");
+        }
+    }
+    let hardware = [&device.manufacturer, &device.model]
+        .into_iter()
+        .flatten()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !hardware.is_empty() {
+        let _ = write!(line, " — {hardware}");
+    }
+    if let Some(firmware) = &device.firmware {
+        let _ = write!(line, ", firmware {firmware}");
+    }
+    let _ = write!(
+        line,
+        " (first seen {}, last seen {}, via {})",
+        crate::locale::format_datetime(locale, device.first_seen),
+        crate::locale::format_datetime(locale, device.last_seen),
+        device.source
+    );
+    line
+}
+
+fn matches(device: &Device, query: &str) -> bool {
+    [
+        Some(&device.key),
+        device.name.as_ref(),
+        device.address.as_ref(),
+        device.manufacturer.as_ref(),
+        device.model.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|value| value.to_lowercase().contains(query))
+}
+
+fn string_arg(args: &serde_json::Value, key: &str) -> Option<String> {
+    args.get(key)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+}
+
+#[async_trait]
+impl Tool for DeviceInventoryTool {
+    fn name(&self) -> &str {
+        "device_inventory"
+    }
+
+    fn description(&self) -> &str {
+        "Inventory of devices seen on the network, with manufacturer, firmware and first/last \
+         seen times. action 'scan' records this host's neighbour table and reports new devices; \
+         'list' shows devices (or only those first seen within 'hours'); 'history' shows new \
+         devices, address and firmware changes; 'record' stores what you learned about a device."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["scan", "list", "history", "record"],
+                    "description": "list (default), scan, history or record"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "list: only devices whose name, address, MAC, manufacturer or model contains this"
+                },
+                "hours": {
+                    "type": "integer",
+                    "description": "list: only devices first seen within this many hours"
+                },
+                "days": {
+                    "type": "integer",
+                    "description": "history: how far back to look (default 7)"
+                },
+                "mac": { "type": "string", "description": "history/record: device MAC address" },
+                "name": { "type": "string", "description": "record: device name (identifies it when there is no MAC)" },
+                "address": { "type": "string", "description": "record: IP address" },
+                "manufacturer": { "type": "string", "description": "record: manufacturer" },
+                "model": { "type": "string", "description": "record: model" },
+                "firmware": { "type": "string", "description": "record: firmware version" }
+            }
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let failure = |error: String| -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            })
+        };
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list");
+        if matches!(action, "scan" | "record") {
+            if let Err(error) = self
+                .security
+                .enforce_tool_operation(ToolOperation::Act, "device_inventory")
+            {
+                return failure(error);
+            }
+        }
+        let workspace_dir = self.security.workspace_dir.clone();
+        let mut output = String::new();
+
+        match action {
+            "scan" => {
+                let neighbors = inventory::neighbors();
+                if neighbors.is_empty() {
+                    return failure(
+                        "No neighbour table entries (needs Linux /proc/net/arp and recent traffic)"
+                            .into(),
+                    );
+                }
+                let count = neighbors.len();
+                let (new_keys, devices) = tokio::task::spawn_blocking(move || {
+                    let new_keys = inventory::record(&workspace_dir, &neighbors, Utc::now())?;
+                    anyhow::Ok((new_keys, inventory::devices(&workspace_dir)?))
+                })
+                .await??;
+                let _ = writeln!(output, "Scanned {count} neighbours, {} new", new_keys.len());
+                for device in devices.iter().filter(|d| new_keys.contains(&d.key)) {
+                    let _ = writeln!(output, "NEW {}", describe(device, &self.locale));
+                }
+            }
+            "list" => {
+                let query = string_arg(&args, "query").map(|q| q.to_lowercase());
+                let since = args
+                    .get("hours")
+                    .and_then(serde_json::Value::as_i64)
+                    .map(|hours| Utc::now() - Duration::hours(hours.max(1)));
+                let devices =
+                    tokio::task::spawn_blocking(move || inventory::devices(&workspace_dir))
+                        .await??;
+                let selected: Vec<&Device> = devices
+                    .iter()
+                    .filter(|d| query.as_deref().is_none_or(|q| matches(d, q)))
+                    .filter(|d| since.is_none_or(|since| d.first_seen >= since))
+                    .collect();
+                let recent = Utc::now() - Duration::hours(self.config.new_window_hours.into());
+                let _ = writeln!(output, "{} device(s)", selected.len());
+                for device in selected.iter().take(MAX_LISTED) {
+                    let marker = if device.first_seen >= recent {
+                        "NEW "
+                    } else {
+                        ""
+                    };
+                    let _ = writeln!(output, "{marker}{}", describe(device, &self.locale));
+                }
+                if selected.len() > MAX_LISTED {
+                    let _ = writeln!(output, "… {} more", selected.len() - MAX_LISTED);
+                }
+            }
+            "history" => {
+                let days = args
+                    .get("days")
+                    .and_then(serde_json::Value::as_i64)
+                    .unwrap_or(7)
+                    .max(1);
+                let device_key = match args.get("mac").and_then(|v| v.as_str()) {
+                    Some(raw) => match inventory::normalize_mac(raw) {
+                        Some(mac) => Some(mac),
+                        None => return failure(format!("Invalid MAC address '{raw}'")),
+                    },
+                    None => None,
+                };
+                let since = Utc::now() - Duration::days(days);
+                let events = tokio::task::spawn_blocking(move || {
+                    inventory::events(&workspace_dir, since, device_key.as_deref())
+                })
+                .await??;
+                let _ = writeln!(
+                    output,
+                    "{} event(s) in the last {days} day(s)",
+                    events.len()
+                );
+                for event in events {
+                    let _ = writeln!(
+                        output,
+                        "{} {} {}{}",
+                        crate::locale::format_datetime(&self.locale, event.at),
+                        event.kind,
+                        event.device_key,
+                        event
+                            .detail
+                            .map(|detail| format!(": {detail}"))
+                            .unwrap_or_default()
+                    );
+                }
+            }
+            "record" => {
+                let observation = Observation {
+                    mac: string_arg(&args, "mac"),
+                    name: string_arg(&args, "name"),
+                    address: string_arg(&args, "address"),
+                    manufacturer: string_arg(&args, "manufacturer"),
+                    model: string_arg(&args, "model"),
+                    firmware: string_arg(&args, "firmware"),
+                    source: "agent".into(),
+                };
+                if let Some(mac) = &observation.mac {
+                    if inventory::normalize_mac(mac).is_none() {
+                        return failure(format!("Invalid MAC address '{mac}'"));
+                    }
+                }
+                let Some(key) = observation.key() else {
+                    return failure("Pass 'mac' or 'name' to identify the device".into());
+                };
+                let new_keys = tokio::task::spawn_blocking(move || {
+                    inventory::record(&workspace_dir, &[observation], Utc::now())
+                })
+                .await??;
+                let verb = if new_keys.is_empty() {
+                    "Updated"
+                } else {
+                    "Added"
+                };
+                let _ = write!(output, "{verb} {key}");
+            }
+            other => return failure(format!("Unknown action '{other}'")),
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_security(tmp: &TempDir, autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn record_then_list_and_history() {
+        let tmp = TempDir::new().unwrap();
+        let tool = DeviceInventoryTool::new(
+            InventoryConfig::default(),
+            test_security(&tmp, AutonomyLevel::Supervised),
+        );
+
+        let result = tool
+            .execute(json!({
+                "action": "record",
+                "mac": "AA-BB-CC-DD-EE-01",
+                "name": "Kitchen bulb",
+                "manufacturer": "LIFX",
+                "firmware": "3.70"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "Added aa:bb:cc:dd:ee:01");
+
+        let result = tool
+            .execute(json!({"action": "record", "mac": "aa:bb:cc:dd:ee:01", "firmware": "3.90"}))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "Updated aa:bb:cc:dd:ee:01");
+
+        let result = tool
+            .execute(json!({"action": "list", "query": "lifx"}))
+            .await
+            .unwrap();
+        assert!(result.output.starts_with("1 device(s)"));
+        assert!(result
+            .output
+            .contains("NEW Kitchen bulb aa:bb:cc:dd:ee:01 — LIFX, firmware 3.90"));
+
+        let result = tool
+            .execute(json!({"action": "history", "mac": "aa:bb:cc:dd:ee:01"}))
+            .await
+            .unwrap();
+        assert!(result
+            .output
+            .contains("firmware aa:bb:cc:dd:ee:01: 3.70 -> 3.90"));
+    }
+
+    #[tokio::test]
+    async fn rejects_unidentified_devices() {
+        let tmp = TempDir::new().unwrap();
+        let tool = DeviceInventoryTool::new(
+            InventoryConfig::default(),
+            test_security(&tmp, AutonomyLevel::Supervised),
+        );
+
+        let result = tool
+            .execute(json!({"action": "record", "firmware": "1.0"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+
+        let result = tool
+            .execute(json!({"action": "record", "mac": "not-a-mac"}))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.error.as_deref(),
+            Some("Invalid MAC address 'not-a-mac'")
+        );
+    }
+
+    #[tokio::test]
+    async fn writes_blocked_in_readonly_mode() {
+        let tmp = TempDir::new().unwrap();
+        let tool = DeviceInventoryTool::new(
+            InventoryConfig::default(),
+            test_security(&tmp, AutonomyLevel::ReadOnly),
+        );
+        let result = tool
+            .execute(json!({"action": "record", "mac": "aa:bb:cc:dd:ee:01"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+        let result = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert!(result.success);
+    }
+}
@@ -0,0 +1,559 @@
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const MAX_INPUT_BYTES: u64 = 2 * 1024 * 1024;
+/// Upper bound on the LCS table size (old lines × new lines after trimming).
+const MAX_DIFF_CELLS: usize = 4_000_000;
+const DEFAULT_CONTEXT_LINES: usize = 3;
+
+/// Compute unified diffs and apply patches to workspace files.
+pub struct DiffPatchTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl DiffPatchTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+
+    /// Resolve an existing workspace file, enforcing path policy and symlink escapes.
+    async fn resolve_file(&self, path: &str) -> Result<PathBuf, String> {
+        if !self.security.is_path_allowed(path) {
+            return Err(format!("Path not allowed by security policy: {path}"));
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+        let resolved = tokio::fs::canonicalize(&full_path)
+            .await
+            .map_err(|e| format!("Failed to resolve file path: {e}"))?;
+
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            return Err(self.security.resolved_path_violation_message(&resolved));
+        }
+
+        let meta = tokio::fs::metadata(&resolved)
+            .await
+            .map_err(|e| format!("Failed to read file metadata: {e}"))?;
+        if !meta.is_file() {
+            return Err(format!("Not a regular file: {path}"));
+        }
+        if meta.len() > MAX_INPUT_BYTES {
+            return Err(format!(
+                "File too large: {} bytes (limit: {MAX_INPUT_BYTES} bytes)",
+                meta.len()
+            ));
+        }
+
+        Ok(resolved)
+    }
+
+    async fn load_side(
+        &self,
+        args: &serde_json::Value,
+        path_key: &str,
+        text_key: &str,
+    ) -> Result<(String, String), String> {
+        if let Some(path) = args.get(path_key).and_then(|v| v.as_str()) {
+            let resolved = self.resolve_file(path).await?;
+            let content = tokio::fs::read_to_string(&resolved)
+                .await
+                .map_err(|e| format!("Failed to read {path}: {e}"))?;
+            return Ok((path.to_string(), content));
+        }
+        if let Some(text) = args.get(text_key).and_then(|v| v.as_str()) {
+            return Ok((text_key.to_string(), text.to_string()));
+        }
+        Err(format!("Provide either '{path_key}' or '{text_key}'"))
+    }
+
+    async fn run_diff(&self, args: &serde_json::Value) -> ToolResult {
+        let context = args
+            .get("context")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_CONTEXT_LINES, |c| {
+                usize::try_from(c.min(20)).unwrap_or(DEFAULT_CONTEXT_LINES)
+            });
+
+        if !self.security.record_action() {
+            return failure("Rate limit exceeded: action budget exhausted");
+        }
+
+        let old = self.load_side(args, "old_path", "old_text").await;
+        let new = self.load_side(args, "new_path", "new_text").await;
+        let ((old_label, old_text), (new_label, new_text)) = match (old, new) {
+            (Ok(old), Ok(new)) => (old, new),
+            (Err(e), _) | (_, Err(e)) => return failure(e),
+        };
+
+        match unified_diff(&old_label, &new_label, &old_text, &new_text, context) {
+            Ok(diff) if diff.is_empty() => ToolResult {
+                success: true,
+                output: "No differences".into(),
+                error: None,
+            },
+            Ok(diff) => ToolResult {
+                success: true,
+                output: diff,
+                error: None,
+            },
+            Err(e) => failure(e),
+        }
+    }
+
+    async fn run_apply(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+        let patch = args
+            .get("patch")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'patch' parameter"))?;
+
+        if !self.security.can_act() {
+            return Ok(failure("Action blocked: autonomy is read-only"));
+        }
+
+        if !self.security.record_action() {
+            return Ok(failure("Rate limit exceeded: action budget exhausted"));
+        }
+
+        let resolved = match self.resolve_file(path).await {
+            Ok(p) => p,
+            Err(e) => return Ok(failure(e)),
+        };
+
+        if let Ok(meta) = tokio::fs::symlink_metadata(self.security.workspace_dir.join(path)).await
+        {
+            if meta.file_type().is_symlink() {
+                return Ok(failure(format!(
+                    "Refusing to write through symlink: {}",
+                    resolved.display()
+                )));
+            }
+        }
+
+        let original = tokio::fs::read_to_string(&resolved).await?;
+        let patched = match apply_unified_patch(&original, patch) {
+            Ok(patched) => patched,
+            Err(e) => return Ok(failure(e)),
+        };
+
+        tokio::fs::write(&resolved, &patched.content).await?;
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Applied {} hunk(s) to {path} ({} bytes)",
+                patched.hunks,
+                patched.content.len()
+            ),
+            error: None,
+        })
+    }
+}
+
+fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Line-level LCS diff. Returns `(op, old_index, new_index)` triples where the
+/// index belonging to the side that does not contain the line is unused.
+fn diff_lines(old: &[&str], new: &[&str]) -> Result<Vec<(Op, usize, usize)>, String> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    let (n, m) = (old_mid.len(), new_mid.len());
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        return Err(format!(
+            "Inputs too different to diff ({n} x {m} changed lines)"
+        ));
+    }
+
+    // lcs[i][j] = LCS length of old_mid[i..] and new_mid[j..]
+    let width = m + 1;
+    let mut lcs = vec![0u32; (n + 1) * width];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i * width + j] = if old_mid[i] == new_mid[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(old.len() + new.len());
+    ops.extend((0..prefix).map(|k| (Op::Equal, k, k)));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_mid[i] == new_mid[j] {
+            ops.push((Op::Equal, prefix + i, prefix + j));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+            ops.push((Op::Delete, prefix + i, prefix + j));
+            i += 1;
+        } else {
+            ops.push((Op::Insert, prefix + i, prefix + j));
+            j += 1;
+        }
+    }
+    ops.extend((0..suffix).map(|k| (Op::Equal, prefix + n + k, prefix + m + k)));
+
+    Ok(ops)
+}
+
+/// Render a unified diff; returns an empty string when the inputs are identical.
+fn unified_diff(
+    old_label: &str,
+    new_label: &str,
+    old_text: &str,
+    new_text: &str,
+    context: usize,
+) -> Result<String, String> {
+    let old: Vec<&str> = old_text.lines().collect();
+    let new: Vec<&str> = new_text.lines().collect();
+    let ops = diff_lines(&old, &new)?;
+
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _, _))| *op != Op::Equal)
+        .map(|(idx, _)| idx)
+        .collect();
+    if changes.is_empty() {
+        return Ok(String::new());
+    }
+
+    // Group change positions into hunks whose context windows overlap.
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &idx in &changes {
+        let start = idx.saturating_sub(context);
+        let end = (idx + context + 1).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(out, "--- {old_label}");
+    let _ = writeln!(out, "+++ {new_label}");
+    for (start, end) in ranges {
+        let slice = &ops[start..end];
+        let old_len = slice.iter().filter(|(op, _, _)| *op != Op::Insert).count();
+        let new_len = slice.iter().filter(|(op, _, _)| *op != Op::Delete).count();
+        let (_, old_pos, new_pos) = slice[0];
+        let old_start = if old_len == 0 { old_pos } else { old_pos + 1 };
+        let new_start = if new_len == 0 { new_pos } else { new_pos + 1 };
+        let _ = writeln!(out, "@@ -{old_start},{old_len} +{new_start},{new_len} @@");
+        for &(op, oi, ni) in slice {
+            let _ = match op {
+                Op::Equal => writeln!(out, " {}", old[oi]),
+                Op::Delete => writeln!(out, "-{}", old[oi]),
+                Op::Insert => writeln!(out, "+{}", new[ni]),
+            };
+        }
+    }
+    Ok(out)
+}
+
+struct PatchOutcome {
+    content: String,
+    hunks: usize,
+}
+
+struct Hunk {
+    old_start: usize,
+    old_lines: Vec<String>,
+    new_lines: Vec<String>,
+}
+
+fn parse_hunk_header(line: &str) -> Option<usize> {
+    let rest = line.strip_prefix("@@ -")?;
+    let old_range = rest.split_whitespace().next()?;
+    old_range.split(',').next()?.parse().ok()
+}
+
+fn parse_patch(patch: &str) -> Result<Vec<Hunk>, String> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    for line in patch.lines() {
+        if line.starts_with("@@") {
+            let old_start =
+                parse_hunk_header(line).ok_or_else(|| format!("Malformed hunk header: {line}"))?;
+            hunks.push(Hunk {
+                old_start,
+                old_lines: Vec::new(),
+                new_lines: Vec::new(),
+            });
+            continue;
+        }
+        let Some(hunk) = hunks.last_mut() else {
+            // Skip preamble (---/+++ headers, commit text) before the first hunk.
+            continue;
+        };
+        if let Some(text) = line.strip_prefix(' ') {
+            hunk.old_lines.push(text.to_string());
+            hunk.new_lines.push(text.to_string());
+        } else if let Some(text) = line.strip_prefix('-') {
+            hunk.old_lines.push(text.to_string());
+        } else if let Some(text) = line.strip_prefix('+') {
+            hunk.new_lines.push(text.to_string());
+        } else if line.is_empty() {
+            // Some editors strip the single space from blank context lines.
+            hunk.old_lines.push(String::new());
+            hunk.new_lines.push(String::new());
+        }
+        // "\ No newline at end of file" and other markers are ignored.
+    }
+    if hunks.is_empty() {
+        return Err("Patch contains no hunks".into());
+    }
+    Ok(hunks)
+}
+
+/// Apply a unified diff, locating each hunk by its context (nearest match to
+/// the recorded line number wins) so small upstream drift is tolerated.
+fn apply_unified_patch(original: &str, patch: &str) -> Result<PatchOutcome, String> {
+    let hunks = parse_patch(patch)?;
+    let mut lines: Vec<String> = original.lines().map(str::to_string).collect();
+    let mut offset: isize = 0;
+
+    for (n, hunk) in hunks.iter().enumerate() {
+        let expected = isize::try_from(hunk.old_start.saturating_sub(1)).unwrap_or(0) + offset;
+        let position = find_block(&lines, &hunk.old_lines, expected)
+            .ok_or_else(|| format!("Hunk {} does not apply (context not found)", n + 1))?;
+
+        lines.splice(
+            position..position + hunk.old_lines.len(),
+            hunk.new_lines.iter().cloned(),
+        );
+        let old_len = isize::try_from(hunk.old_lines.len()).unwrap_or(0);
+        let new_len = isize::try_from(hunk.new_lines.len()).unwrap_or(0);
+        offset += new_len - old_len;
+    }
+
+    let mut content = lines.join("\n");
+    if original.ends_with('\n') || (original.is_empty() && !content.is_empty()) {
+        content.push('\n');
+    }
+    Ok(PatchOutcome {
+        content,
+        hunks: hunks.len(),
+    })
+}
+
+fn find_block(haystack: &[String], block: &[String], expected: isize) -> Option<usize> {
+    if block.is_empty() {
+        let pos = usize::try_from(expected.max(0)).unwrap_or(0);
+        return Some(pos.min(haystack.len()));
+    }
+    if block.len() > haystack.len() {
+        return None;
+    }
+    let last_start = haystack.len() - block.len();
+    let matches_at = |start: usize| haystack[start..start + block.len()] == *block;
+    let expected = usize::try_from(expected.max(0))
+        .unwrap_or(0)
+        .min(last_start);
+
+    (0..=last_start).find_map(|distance| {
+        let after = expected + distance;
+        if after <= last_start && matches_at(after) {
+            return Some(after);
+        }
+        let before = expected.checked_sub(distance)?;
+        matches_at(before).then_some(before)
+    })
+}
+
+#[async_trait]
+impl Tool for DiffPatchTool {
+    fn name(&self) -> &str {
+        "diff_patch"
+    }
+
+    fn description(&self) -> &str {
+        "Compute a unified diff between workspace files or strings (action 'diff'), or apply a \
+         unified diff patch to a workspace file (action 'apply')."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["diff", "apply"],
+                    "description": "'diff' to compare, 'apply' to patch a file"
+                },
+                "old_path": { "type": "string", "description": "diff: original file path" },
+                "old_text": { "type": "string", "description": "diff: original text (instead of old_path)" },
+                "new_path": { "type": "string", "description": "diff: updated file path" },
+                "new_text": { "type": "string", "description": "diff: updated text (instead of new_path)" },
+                "context": { "type": "integer", "description": "diff: context lines (default: 3)" },
+                "path": { "type": "string", "description": "apply: file to patch" },
+                "patch": { "type": "string", "description": "apply: unified diff to apply" }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+
+        if self.security.is_rate_limited() {
+            return Ok(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        match action {
+            "diff" => Ok(self.run_diff(&args).await),
+            "apply" => self.run_apply(&args).await,
+            other => Ok(failure(format!(
+                "Unknown action '{other}'. Use 'diff' or 'apply'."
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_security(level: AutonomyLevel, workspace: PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: level,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[test]
+    fn unified_diff_marks_changed_lines() {
+        let diff = unified_diff("a", "b", "one\ntwo\nthree\n", "one\n2\nthree\n", 3).unwrap();
+        assert!(diff.starts_with("--- a\n+++ b\n@@ -1,3 +1,3 @@\n"));
+        assert!(diff.contains("-two\n+2\n"));
+    }
+
+    #[test]
+    fn unified_diff_identical_inputs_is_empty() {
+        assert!(unified_diff("a", "b", "x\ny\n", "x\ny\n", 3)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn diff_then_apply_round_trips() {
+        let old = (1..=40).map(|i| format!("line {i}\n")).collect::<String>();
+        let new = old
+            .replace("line 5\n", "line five\n")
+            .replace("line 30\n", "line 30\ninserted\n");
+        let diff = unified_diff("old", "new", &old, &new, 3).unwrap();
+        assert_eq!(diff.matches("@@ -").count(), 2);
+
+        let patched = apply_unified_patch(&old, &diff).unwrap();
+        assert_eq!(patched.content, new);
+        assert_eq!(patched.hunks, 2);
+    }
+
+    #[test]
+    fn apply_tolerates_line_drift_and_rejects_missing_context() {
+        let patch = "@@ -1,2 +1,2 @@\n alpha\n-beta\n+BETA\n";
+        let drifted = "header\nextra\nalpha\nbeta\n";
+        assert_eq!(
+            apply_unified_patch(drifted, patch).unwrap().content,
+            "header\nextra\nalpha\nBETA\n"
+        );
+        assert!(apply_unified_patch("gamma\n", patch).is_err());
+    }
+
+    #[tokio::test]
+    async fn apply_writes_file_and_respects_readonly() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("notes.txt"), "a\nb\n")
+            .await
+            .unwrap();
+        let args = json!({
+            "action": "apply",
+            "path": "notes.txt",
+            "patch": "@@ -1,2 +1,2 @@\n a\n-b\n+c\n"
+        });
+
+        let readonly = DiffPatchTool::new(test_security(
+            AutonomyLevel::ReadOnly,
+            tmp.path().to_path_buf(),
+        ));
+        let blocked = readonly.execute(args.clone()).await.unwrap();
+        assert!(!blocked.success);
+        assert!(blocked.error.unwrap().contains("read-only"));
+
+        let tool = DiffPatchTool::new(test_security(
+            AutonomyLevel::Supervised,
+            tmp.path().to_path_buf(),
+        ));
+        let result = tool.execute(args).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            tokio::fs::read_to_string(tmp.path().join("notes.txt"))
+                .await
+                .unwrap(),
+            "a\nc\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn diff_reads_workspace_files() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("a.txt"), "x\n")
+            .await
+            .unwrap();
+        let tool = DiffPatchTool::new(test_security(
+            AutonomyLevel::ReadOnly,
+            tmp.path().to_path_buf(),
+        ));
+        let result = tool
+            .execute(json!({"action": "diff", "old_path": "a.txt", "new_text": "y\n"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("-x\n+y\n"));
+    }
+}
@@ -0,0 +1,653 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::DnsConfig;
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+
+/// Used when neither the call, `[dns] resolver` nor `/etc/resolv.conf` names one.
+const FALLBACK_RESOLVER: SocketAddr =
+    SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(1, 1, 1, 1), 53));
+const TYPE_CNAME: u16 = 5;
+const RCODE_NXDOMAIN: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RecordType {
+    A,
+    Aaaa,
+    Mx,
+    Txt,
+}
+
+impl RecordType {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.to_ascii_uppercase().as_str() {
+            "A" => Some(Self::A),
+            "AAAA" => Some(Self::Aaaa),
+            "MX" => Some(Self::Mx),
+            "TXT" => Some(Self::Txt),
+            _ => None,
+        }
+    }
+
+    fn code(self) -> u16 {
+        match self {
+            Self::A => 1,
+            Self::Aaaa => 28,
+            Self::Mx => 15,
+            Self::Txt => 16,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::A => "A",
+            Self::Aaaa => "AAAA",
+            Self::Mx => "MX",
+            Self::Txt => "TXT",
+        }
+    }
+}
+
+/// One answer record with its RDATA rendered as text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Record {
+    kind: u16,
+    ttl: u32,
+    value: String,
+}
+
+#[derive(Debug)]
+struct Answer {
+    rcode: u8,
+    truncated: bool,
+    records: Vec<Record>,
+}
+
+impl Answer {
+    /// Sorted, de-duplicated values of the queried type (CNAMEs dropped).
+    fn values(&self, rtype: RecordType) -> Vec<String> {
+        let mut values: Vec<String> = self
+            .records
+            .iter()
+            .filter(|record| record.kind == rtype.code())
+            .map(|record| record.value.clone())
+            .collect();
+        values.sort();
+        values.dedup();
+        values
+    }
+}
+
+fn rcode_name(rcode: u8) -> String {
+    match rcode {
+        1 => "FORMERR".into(),
+        2 => "SERVFAIL".into(),
+        RCODE_NXDOMAIN => "NXDOMAIN".into(),
+        4 => "NOTIMP".into(),
+        5 => "REFUSED".into(),
+        other => format!("RCODE {other}"),
+    }
+}
+
+fn type_name(code: u16) -> &'static str {
+    match code {
+        1 => "A",
+        28 => "AAAA",
+        15 => "MX",
+        16 => "TXT",
+        TYPE_CNAME => "CNAME",
+        _ => "?",
+    }
+}
+
+fn encode_query(id: u16, name: &str, rtype: RecordType) -> anyhow::Result<Vec<u8>> {
+    let name = name.trim_end_matches('.');
+    if name.is_empty() || name.len() > 253 {
+        bail!("Invalid domain name '{name}'");
+    }
+    let mut msg = Vec::with_capacity(18 + name.len());
+    msg.extend_from_slice(&id.to_be_bytes());
+    // Recursion desired, one question.
+    msg.extend_from_slice(&[0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0]);
+    for label in name.split('.') {
+        let len = u8::try_from(label.len())
+            .ok()
+            .filter(|len| (1..=63).contains(len))
+            .with_context(|| format!("Invalid domain name '{name}'"))?;
+        msg.push(len);
+        msg.extend_from_slice(label.as_bytes());
+    }
+    msg.push(0);
+    msg.extend_from_slice(&rtype.code().to_be_bytes());
+    msg.extend_from_slice(&1u16.to_be_bytes());
+    Ok(msg)
+}
+
+fn be16(msg: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([*msg.get(pos)?, *msg.get(pos + 1)?]))
+}
+
+/// Decode a (possibly compressed) name at `pos`: `(name, offset after it)`.
+fn read_name(msg: &[u8], mut pos: usize) -> Option<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut end = None;
+    // Bounded so a pointer loop cannot spin forever.
+    for _ in 0..128 {
+        let len = *msg.get(pos)?;
+        match len {
+            0 => return Some((labels.join("."), end.unwrap_or(pos + 1))),
+            ptr if ptr & 0xc0 == 0xc0 => {
+                if end.is_none() {
+                    end = Some(pos + 2);
+                }
+                pos = usize::from(u16::from_be_bytes([ptr & 0x3f, *msg.get(pos + 1)?]));
+            }
+            len if len & 0xc0 == 0 => {
+                let label = msg.get(pos + 1..pos + 1 + usize::from(len))?;
+                labels.push(String::from_utf8_lossy(label).into_owned());
+                pos += 1 + usize::from(len);
+            }
+            _ => return None,
+        }
+    }
+    None
+}
+
+fn render_rdata(msg: &[u8], kind: u16, start: usize, rdata: &[u8]) -> Option<String> {
+    match kind {
+        1 => Some(Ipv4Addr::from(<[u8; 4]>::try_from(rdata).ok()?).to_string()),
+        28 => Some(Ipv6Addr::from(<[u8; 16]>::try_from(rdata).ok()?).to_string()),
+        15 => Some(format!(
+            "{} {}",
+            be16(rdata, 0)?,
+            read_name(msg, start + 2)?.0
+        )),
+        16 => {
+            // Character-strings are joined, as SPF/DKIM consumers do.
+            let mut text = String::new();
+            let mut rest = rdata;
+            while let Some((&len, tail)) = rest.split_first() {
+                let len = usize::from(len);
+                text.push_str(&String::from_utf8_lossy(tail.get(..len)?));
+                rest = &tail[len..];
+            }
+            Some(format!("\"{text}\""))
+        }
+        TYPE_CNAME => Some(read_name(msg, start)?.0),
+        _ => None,
+    }
+}
+
+/// Parse a response to query `id`; `None` if it is malformed or not ours.
+fn parse_response(msg: &[u8], id: u16) -> Option<Answer> {
+    let flags = be16(msg, 2)?;
+    if be16(msg, 0)? != id || flags & 0x8000 == 0 {
+        return None;
+    }
+    let truncated = flags & 0x0200 != 0;
+    let rcode = u8::try_from(flags & 0x000f).ok()?;
+    let questions = be16(msg, 4)?;
+    let answers = be16(msg, 6)?;
+
+    let mut pos = 12;
+    for _ in 0..questions {
+        pos = read_name(msg, pos)?.1 + 4;
+    }
+    let mut records = Vec::new();
+    for _ in 0..answers {
+        let (_, after) = read_name(msg, pos)?;
+        let kind = be16(msg, after)?;
+        let ttl = u32::from_be_bytes(msg.get(after + 4..after + 8)?.try_into().ok()?);
+        let len = usize::from(be16(msg, after + 8)?);
+        let start = after + 10;
+        let rdata = msg.get(start..start + len)?;
+        if let Some(value) = render_rdata(msg, kind, start, rdata) {
+            records.push(Record { kind, ttl, value });
+        }
+        pos = start + len;
+    }
+    Some(Answer {
+        rcode,
+        truncated,
+        records,
+    })
+}
+
+async fn exchange(
+    resolver: SocketAddr,
+    query: &[u8],
+    id: u16,
+    timeout: Duration,
+) -> anyhow::Result<Answer> {
+    let bind: SocketAddr = if resolver.is_ipv4() {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(bind).await?;
+    socket.connect(resolver).await?;
+    socket.send(query).await?;
+    let mut buf = vec![0u8; 4096];
+    let answer = tokio::time::timeout(timeout, async {
+        loop {
+            let n = socket.recv(&mut buf).await?;
+            // Stray or spoofed datagrams with another id are ignored.
+            if let Some(answer) = parse_response(&buf[..n], id) {
+                return anyhow::Ok(answer);
+            }
+        }
+    })
+    .await
+    .with_context(|| format!("No answer from {resolver} within {}s", timeout.as_secs()))??;
+    if !answer.truncated {
+        return Ok(answer);
+    }
+
+    // Too large for UDP: retry over TCP with a two-byte length prefix.
+    tokio::time::timeout(timeout, async {
+        let mut stream = TcpStream::connect(resolver).await?;
+        let len = u16::try_from(query.len())?.to_be_bytes();
+        stream.write_all(&[&len[..], query].concat()).await?;
+        let mut len = [0u8; 2];
+        stream.read_exact(&mut len).await?;
+        let mut msg = vec![0u8; usize::from(u16::from_be_bytes(len))];
+        stream.read_exact(&mut msg).await?;
+        parse_response(&msg, id).context("Malformed DNS response")
+    })
+    .await
+    .with_context(|| {
+        format!(
+            "No TCP answer from {resolver} within {}s",
+            timeout.as_secs()
+        )
+    })?
+}
+
+fn parse_resolver(raw: &str) -> Option<SocketAddr> {
+    raw.parse::<SocketAddr>()
+        .ok()
+        .or_else(|| raw.parse::<IpAddr>().ok().map(|ip| SocketAddr::new(ip, 53)))
+}
+
+fn system_resolver() -> Option<SocketAddr> {
+    let conf = std::fs::read_to_string("/etc/resolv.conf").ok()?;
+    conf.lines()
+        .filter_map(|line| line.trim().strip_prefix("nameserver"))
+        .find_map(|rest| parse_resolver(rest.trim()))
+}
+
+/// Split a `[dns] watch` entry (`"example.com MX"`); the type defaults to A.
+fn parse_watch_entry(entry: &str) -> Option<(String, RecordType)> {
+    let mut parts = entry.split_whitespace();
+    let name = parts.next()?.trim_end_matches('.').to_ascii_lowercase();
+    let rtype = match parts.next() {
+        Some(raw) => RecordType::parse(raw)?,
+        None => RecordType::A,
+    };
+    parts.next().is_none().then_some((name, rtype))
+}
+
+/// Last values seen for one watched record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WatchState {
+    values: Vec<String>,
+    checked_at: String,
+}
+
+/// `(added, removed)` between two sorted value lists.
+fn diff<'a>(previous: &'a [String], current: &'a [String]) -> (Vec<&'a str>, Vec<&'a str>) {
+    let added = current
+        .iter()
+        .filter(|v| !previous.contains(v))
+        .map(String::as_str)
+        .collect();
+    let removed = previous
+        .iter()
+        .filter(|v| !current.contains(v))
+        .map(String::as_str)
+        .collect();
+    (added, removed)
+}
+
+/// Record lookups and change detection for the watched records.
+pub struct DnsTool {
+    config: DnsConfig,
+    workspace_dir: PathBuf,
+}
+
+impl DnsTool {
+    pub fn new(config: DnsConfig, workspace_dir: PathBuf) -> Self {
+        Self {
+            config,
+            workspace_dir,
+        }
+    }
+
+    fn state_path(&self) -> PathBuf {
+        self.workspace_dir.join("dns").join("watch.json")
+    }
+
+    fn resolver(&self, requested: Option<&str>) -> anyhow::Result<SocketAddr> {
+        match requested.or(self.config.resolver.as_deref()) {
+            Some(raw) => parse_resolver(raw)
+                .with_context(|| format!("Invalid resolver '{raw}'; expected ip or ip:port")),
+            None => Ok(system_resolver().unwrap_or(FALLBACK_RESOLVER)),
+        }
+    }
+
+    async fn query(
+        &self,
+        resolver: SocketAddr,
+        name: &str,
+        rtype: RecordType,
+    ) -> anyhow::Result<Answer> {
+        let id = rand::random::<u16>();
+        let query = encode_query(id, name, rtype)?;
+        exchange(
+            resolver,
+            &query,
+            id,
+            Duration::from_secs(self.config.timeout_secs.max(1)),
+        )
+        .await
+    }
+
+    async fn lookup(
+        &self,
+        resolver: SocketAddr,
+        name: &str,
+        rtype: RecordType,
+    ) -> anyhow::Result<ToolResult> {
+        let answer = self.query(resolver, name, rtype).await?;
+        let mut output = format!("{name} {} via {resolver}", rtype.as_str());
+        if answer.rcode != 0 {
+            let _ = write!(output, ": {}", rcode_name(answer.rcode));
+            return Ok(ToolResult {
+                success: answer.rcode == RCODE_NXDOMAIN,
+                output,
+                error: (answer.rcode != RCODE_NXDOMAIN)
+                    .then(|| format!("Resolver answered {}", rcode_name(answer.rcode))),
+            });
+        }
+        if answer.records.is_empty() {
+            output.push_str(": no records");
+        }
+        for record in &answer.records {
+            let _ = write!(output, "\n  ");
+            if record.kind != rtype.code() {
+                let _ = write!(output, "{} ", type_name(record.kind));
+            }
+            let _ = write!(output, "{} (ttl {})", record.value, record.ttl);
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+
+    async fn watch(
+        &self,
+        resolver: SocketAddr,
+        entries: Vec<(String, RecordType)>,
+    ) -> anyhow::Result<ToolResult> {
+        let path = self.state_path();
+        let mut state: BTreeMap<String, WatchState> = tokio::fs::read_to_string(&path)
+            .await
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        let mut output = String::new();
+        let mut changed = 0usize;
+        for (name, rtype) in entries {
+            let key = format!("{name} {}", rtype.as_str());
+            let answer = match self.query(resolver, &name, rtype).await {
+                Ok(answer) if answer.rcode == 0 || answer.rcode == RCODE_NXDOMAIN => answer,
+                Ok(answer) => {
+                    let _ = writeln!(output, "{key}: error — {}", rcode_name(answer.rcode));
+                    continue;
+                }
+                Err(e) => {
+                    let _ = writeln!(output, "{key}: error — {e}");
+                    continue;
+                }
+            };
+            // NXDOMAIN is kept as an empty set so a vanished domain counts as a change.
+            let current = answer.values(rtype);
+            let shown = if current.is_empty() {
+                "(none)".to_string()
+            } else {
+                current.join(", ")
+            };
+            match state.get(&key) {
+                None => {
+                    let _ = writeln!(output, "{key}: baseline recorded — {shown}");
+                }
+                Some(previous) if previous.values == current => {
+                    let _ = writeln!(output, "{key}: unchanged — {shown}");
+                }
+                Some(previous) => {
+                    changed += 1;
+                    let (added, removed) = diff(&previous.values, &current);
+                    let _ = writeln!(output, "{key}: CHANGED — now {shown}");
+                    for value in &added {
+                        let _ = writeln!(output, "  + {value}");
+                    }
+                    for value in &removed {
+                        let _ = writeln!(output, "  - {value}");
+                    }
+                    crate::gateway::ws::publish_notification(
+                        "dns_change",
+                        json!({
+                            "name": name,
+                            "type": rtype.as_str(),
+                            "resolver": resolver.to_string(),
+                            "previous": previous.values,
+                            "current": current,
+                            "added": added,
+                            "removed": removed,
+                        }),
+                    );
+                }
+            }
+            state.insert(
+                key,
+                WatchState {
+                    values: current,
+                    checked_at: chrono::Utc::now().to_rfc3339(),
+                },
+            );
+        }
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, serde_json::to_string_pretty(&state)?)
+            .await
+            .context("Failed to save DNS watch state")?;
+        let _ = write!(output, "{changed} changed");
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for DnsTool {
+    fn name(&self) -> &str {
+        "dns"
+    }
+
+    fn description(&self) -> &str {
+        "Look up DNS records (A, AAAA, MX, TXT), optionally against a specific resolver. \
+         action 'watch' re-checks the configured watch list (or the given name), stores the \
+         results and reports any record that changed since the last check."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["lookup", "watch"],
+                    "description": "lookup (default) or watch"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Domain name; required for lookup, optional for watch"
+                },
+                "type": {
+                    "type": "string",
+                    "enum": ["A", "AAAA", "MX", "TXT"],
+                    "description": "Record type (default A)"
+                },
+                "resolver": {
+                    "type": "string",
+                    "description": "Resolver as ip or ip:port (default: [dns] resolver, then the system resolver)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let failure = |error: String| -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            })
+        };
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("lookup");
+        let name = args
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(|name| name.trim().trim_end_matches('.').to_ascii_lowercase())
+            .filter(|name| !name.is_empty());
+        let rtype = match args.get("type").and_then(|v| v.as_str()) {
+            Some(raw) => match RecordType::parse(raw) {
+                Some(rtype) => rtype,
+                None => return failure(format!("Unsupported record type '{raw}'")),
+            },
+            None => RecordType::A,
+        };
+        let resolver = match self.resolver(args.get("resolver").and_then(|v| v.as_str())) {
+            Ok(resolver) => resolver,
+            Err(e) => return failure(e.to_string()),
+        };
+
+        match action {
+            "lookup" => {
+                let Some(name) = name else {
+                    return failure("Missing 'name' parameter".into());
+                };
+                self.lookup(resolver, &name, rtype).await
+            }
+            "watch" => {
+                let entries = match name {
+                    Some(name) => vec![(name, rtype)],
+                    None => {
+                        let mut entries = Vec::new();
+                        for entry in &self.config.watch {
+                            match parse_watch_entry(entry) {
+                                Some(parsed) => entries.push(parsed),
+                                None => {
+                                    return failure(format!("Invalid [dns] watch entry '{entry}'"))
+                                }
+                            }
+                        }
+                        entries
+                    }
+                };
+                if entries.is_empty() {
+                    return failure("Nothing to watch: pass 'name' or set [dns] watch".into());
+                }
+                self.watch(resolver, entries).await
+            }
+            other => failure(format!("Unknown action '{other}'")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_query_and_parses_compressed_answers() {
+        let query = encode_query(0x1234, "example.com.", RecordType::Mx).unwrap();
+        assert_eq!(&query[12..25], b"\x07example\x03com\x00");
+        assert!(encode_query(1, "bad..name", RecordType::A).is_err());
+
+        // Response: same question, answers MX 10 mail.example.com and a split TXT.
+        let mut msg = query.clone();
+        msg[2..4].copy_from_slice(&0x8180u16.to_be_bytes());
+        msg[6..8].copy_from_slice(&2u16.to_be_bytes());
+        msg.extend_from_slice(&[0xc0, 0x0c, 0, 15, 0, 1, 0, 0, 1, 44, 0, 9, 0, 10]);
+        msg.extend_from_slice(b"\x04mail\xc0\x0c");
+        msg.extend_from_slice(&[0xc0, 0x0c, 0, 16, 0, 1, 0, 0, 0, 60, 0, 8]);
+        msg.extend_from_slice(b"\x03v=s\x03pf1");
+
+        let answer = parse_response(&msg, 0x1234).unwrap();
+        assert_eq!(answer.rcode, 0);
+        assert_eq!(
+            answer.records,
+            vec![
+                Record {
+                    kind: 15,
+                    ttl: 300,
+                    value: "10 mail.example.com".into()
+                },
+                Record {
+                    kind: 16,
+                    ttl: 60,
+                    value: "\"v=spf1\"".into()
+                },
+            ]
+        );
+        assert_eq!(answer.values(RecordType::Mx), vec!["10 mail.example.com"]);
+        assert!(parse_response(&msg, 0x4321).is_none());
+        assert!(parse_response(&msg[..msg.len() - 2], 0x1234).is_none());
+
+        // A pointer to itself must not loop.
+        assert!(read_name(&[0xc0, 0x00], 0).is_none());
+    }
+
+    #[test]
+    fn watch_entries_resolvers_and_diff() {
+        assert_eq!(
+            parse_watch_entry("Example.com. MX"),
+            Some(("example.com".into(), RecordType::Mx))
+        );
+        assert_eq!(
+            parse_watch_entry("example.com"),
+            Some(("example.com".into(), RecordType::A))
+        );
+        assert_eq!(parse_watch_entry("example.com SRV"), None);
+
+        assert_eq!(parse_resolver("9.9.9.9"), "9.9.9.9:53".parse().ok());
+        assert_eq!(parse_resolver("[::1]:5353"), "[::1]:5353".parse().ok());
+        assert_eq!(parse_resolver("dns.google"), None);
+
+        let previous = vec!["1.1.1.1".to_string(), "2.2.2.2".to_string()];
+        let current = vec!["2.2.2.2".to_string(), "3.3.3.3".to_string()];
+        assert_eq!(
+            diff(&previous, &current),
+            (vec!["3.3.3.3"], vec!["1.1.1.1"])
+        );
+    }
+}
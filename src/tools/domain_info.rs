@@ -0,0 +1,391 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::DomainInfoConfig;
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::fmt::Write as _;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+/// IANA's RDAP bootstrap registry: which server answers for which TLD.
+const BOOTSTRAP_URL: &str = "https://data.iana.org/rdap/dns.json";
+const BOOTSTRAP_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+type Bootstrap = Arc<Vec<(String, String)>>;
+
+/// Fetched bootstrap, shared by every registry built in this process.
+static BOOTSTRAP: OnceLock<Mutex<Option<(Instant, Bootstrap)>>> = OnceLock::new();
+
+/// `(suffix, base URL)` pairs from the bootstrap file, preferring HTTPS.
+fn parse_bootstrap(body: &Value) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    for service in body["services"].as_array().into_iter().flatten() {
+        let urls: Vec<&str> = service[1]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let Some(url) = urls
+            .iter()
+            .find(|url| url.starts_with("https://"))
+            .or_else(|| urls.first())
+        else {
+            continue;
+        };
+        for suffix in service[0].as_array().into_iter().flatten() {
+            if let Some(suffix) = suffix.as_str() {
+                entries.push((suffix.to_ascii_lowercase(), (*url).to_string()));
+            }
+        }
+    }
+    entries
+}
+
+/// The RDAP base URL for the longest bootstrap suffix matching `domain`.
+fn rdap_base<'a>(bootstrap: &'a [(String, String)], domain: &str) -> Option<&'a str> {
+    let labels: Vec<&str> = domain.split('.').collect();
+    (1..labels.len()).find_map(|start| {
+        let suffix = labels[start..].join(".");
+        bootstrap
+            .iter()
+            .find(|(candidate, _)| *candidate == suffix)
+            .map(|(_, url)| url.as_str())
+    })
+}
+
+fn normalize_domain(raw: &str) -> Option<String> {
+    let domain = raw.trim().trim_end_matches('.').to_ascii_lowercase();
+    let valid = domain.contains('.')
+        && domain.split('.').all(|label| {
+            !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    valid.then_some(domain)
+}
+
+/// Registration data from one RDAP domain response.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct DomainInfo {
+    registrar: Option<String>,
+    registered: Option<DateTime<Utc>>,
+    updated: Option<DateTime<Utc>>,
+    expires: Option<DateTime<Utc>>,
+    statuses: Vec<String>,
+    nameservers: Vec<String>,
+    dnssec: Option<bool>,
+}
+
+fn parse_rdap(body: &Value) -> DomainInfo {
+    let mut info = DomainInfo::default();
+    for event in body["events"].as_array().into_iter().flatten() {
+        let Some(date) = event["eventDate"]
+            .as_str()
+            .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+            .map(|date| date.with_timezone(&Utc))
+        else {
+            continue;
+        };
+        match event["eventAction"].as_str() {
+            Some("registration") => info.registered = Some(date),
+            Some("last changed") => info.updated = Some(date),
+            Some("expiration") => info.expires = Some(date),
+            _ => {}
+        }
+    }
+    info.registrar = body["entities"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|entity| {
+            entity["roles"]
+                .as_array()
+                .is_some_and(|roles| roles.iter().any(|role| role == "registrar"))
+        })
+        .and_then(|entity| {
+            // vcardArray: ["vcard", [["fn", {}, "text", "Name"], ...]]
+            entity["vcardArray"][1]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find(|field| field[0] == "fn")
+                .and_then(|field| field[3].as_str())
+                .or_else(|| entity["handle"].as_str())
+                .map(str::to_string)
+        });
+    info.statuses = body["status"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect();
+    info.nameservers = body["nameservers"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|ns| ns["ldhName"].as_str())
+        .map(str::to_ascii_lowercase)
+        .collect();
+    info.dnssec = body["secureDNS"]["delegationSigned"].as_bool();
+    info
+}
+
+/// Registrar, registration dates and expiry status for domains via RDAP.
+pub struct DomainInfoTool {
+    config: DomainInfoConfig,
+}
+
+impl DomainInfoTool {
+    pub fn new(config: DomainInfoConfig) -> Self {
+        Self { config }
+    }
+
+    fn client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.domain_info",
+            self.config.timeout_secs.max(1),
+            10,
+        )
+    }
+
+    async fn bootstrap(&self) -> anyhow::Result<Bootstrap> {
+        let cache = BOOTSTRAP.get_or_init(|| Mutex::new(None));
+        if let Some((fetched, entries)) = cache.lock().as_ref() {
+            if fetched.elapsed() < BOOTSTRAP_TTL {
+                return Ok(entries.clone());
+            }
+        }
+        let response = self.client().get(BOOTSTRAP_URL).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("RDAP bootstrap returned {status}");
+        }
+        let entries = Arc::new(parse_bootstrap(&response.json().await?));
+        *cache.lock() = Some((Instant::now(), entries.clone()));
+        Ok(entries)
+    }
+
+    /// `None` when the registry has no record of the domain.
+    async fn lookup(&self, domain: &str) -> anyhow::Result<Option<DomainInfo>> {
+        let bootstrap = self.bootstrap().await?;
+        let base = rdap_base(&bootstrap, domain)
+            .with_context(|| format!("no RDAP service for {domain}'s TLD"))?;
+        let response = self
+            .client()
+            .get(format!("{}/domain/{domain}", base.trim_end_matches('/')))
+            .header("Accept", "application/rdap+json")
+            .send()
+            .await?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            anyhow::bail!("RDAP server returned {status}");
+        }
+        Ok(Some(parse_rdap(&response.json().await?)))
+    }
+}
+
+#[async_trait]
+impl Tool for DomainInfoTool {
+    fn name(&self) -> &str {
+        "domain_info"
+    }
+
+    fn description(&self) -> &str {
+        "Look up domain registration data via RDAP: registrar, registration and expiry \
+         dates, status codes, nameservers and DNSSEC, with an expiry status of ok, warning, \
+         critical, or expired. Omit 'domain' to check every configured domain."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "domain": {
+                    "type": "string",
+                    "description": format!(
+                        "Registered domain, e.g. example.com (configured: {})",
+                        self.config.domains.join(", ")
+                    )
+                },
+                "warn_days": {
+                    "type": "integer",
+                    "description": format!("Override the warning threshold (default {})", self.config.warn_days)
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let requested: Vec<&str> = match args.get("domain").and_then(|v| v.as_str()) {
+            Some(domain) => vec![domain],
+            None => self.config.domains.iter().map(String::as_str).collect(),
+        };
+        if requested.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("No domain given and none configured in [domain_info] domains".into()),
+            });
+        }
+        let warn_days = args
+            .get("warn_days")
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(self.config.warn_days);
+
+        let now = Utc::now();
+        let mut output = String::new();
+        let mut counts = std::collections::BTreeMap::<&str, usize>::new();
+        for raw in requested {
+            let Some(domain) = normalize_domain(raw) else {
+                *counts.entry("error").or_default() += 1;
+                let _ = writeln!(output, "{raw}: error — invalid domain");
+                continue;
+            };
+            let info = match self.lookup(&domain).await {
+                Ok(Some(info)) => info,
+                Ok(None) => {
+                    *counts.entry("not_found").or_default() += 1;
+                    let _ = writeln!(output, "{domain}: not_found — no registration record");
+                    continue;
+                }
+                Err(e) => {
+                    *counts.entry("error").or_default() += 1;
+                    let _ = writeln!(output, "{domain}: error — {e}");
+                    continue;
+                }
+            };
+
+            let registrar = info.registrar.as_deref().unwrap_or("unknown registrar");
+            match info.expires {
+                Some(expires) => {
+                    let days_left = (expires - now).num_days();
+                    let state =
+                        super::cert_check::status(days_left, warn_days, self.config.critical_days);
+                    *counts.entry(state).or_default() += 1;
+                    let _ = writeln!(
+                        output,
+                        "{domain}: {state} — expires {} ({days_left} days), {registrar}",
+                        expires.format("%Y-%m-%d")
+                    );
+                }
+                None => {
+                    *counts.entry("unknown").or_default() += 1;
+                    let _ = writeln!(
+                        output,
+                        "{domain}: unknown — registry publishes no expiry date, {registrar}"
+                    );
+                }
+            }
+            let mut details = Vec::new();
+            if let Some(registered) = info.registered {
+                details.push(format!("registered {}", registered.format("%Y-%m-%d")));
+            }
+            if let Some(updated) = info.updated {
+                details.push(format!("updated {}", updated.format("%Y-%m-%d")));
+            }
+            match info.dnssec {
+                Some(true) => details.push("DNSSEC signed".into()),
+                Some(false) => details.push("DNSSEC unsigned".into()),
+                None => {}
+            }
+            if !details.is_empty() {
+                let _ = writeln!(output, "  {}", details.join(", "));
+            }
+            if !info.statuses.is_empty() {
+                let _ = writeln!(output, "  status: {}", info.statuses.join(", "));
+            }
+            if !info.nameservers.is_empty() {
+                let _ = writeln!(output, "  nameservers: {}", info.nameservers.join(", "));
+            }
+        }
+
+        let summary: Vec<String> = counts
+            .iter()
+            .map(|(state, n)| format!("{n} {state}"))
+            .collect();
+        let _ = write!(output, "Summary: {}", summary.join(", "));
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bootstrap_picks_https_and_longest_suffix() {
+        let body = json!({
+            "services": [
+                [["com", "net"], ["http://rdap.verisign.com/com/v1/", "https://rdap.verisign.com/com/v1/"]],
+                [["uk"], ["https://rdap.nominet.uk/uk/"]],
+                [["co.uk"], ["https://rdap.example/co.uk/"]]
+            ]
+        });
+        let bootstrap = parse_bootstrap(&body);
+        assert_eq!(
+            rdap_base(&bootstrap, "example.com"),
+            Some("https://rdap.verisign.com/com/v1/")
+        );
+        assert_eq!(
+            rdap_base(&bootstrap, "shop.example.co.uk"),
+            Some("https://rdap.example/co.uk/")
+        );
+        assert_eq!(rdap_base(&bootstrap, "example.invalid"), None);
+
+        assert_eq!(
+            normalize_domain(" Example.COM. "),
+            Some("example.com".into())
+        );
+        assert_eq!(normalize_domain("localhost"), None);
+        assert_eq!(normalize_domain("bad domain.com"), None);
+    }
+
+    #[test]
+    fn parses_rdap_domain_response() {
+        let body = json!({
+            "ldhName": "EXAMPLE.COM",
+            "status": ["client delete prohibited", "client transfer prohibited"],
+            "events": [
+                {"eventAction": "registration", "eventDate": "1995-08-14T04:00:00Z"},
+                {"eventAction": "expiration", "eventDate": "2027-08-13T04:00:00Z"},
+                {"eventAction": "last changed", "eventDate": "2024-08-14T07:01:34Z"},
+                {"eventAction": "last update of RDAP database", "eventDate": "not a date"}
+            ],
+            "entities": [
+                {"roles": ["technical"], "vcardArray": ["vcard", [["fn", {}, "text", "Tech"]]]},
+                {
+                    "roles": ["registrar"],
+                    "handle": "376",
+                    "vcardArray": ["vcard", [["version", {}, "text", "4.0"], ["fn", {}, "text", "Example Registrar"]]]
+                }
+            ],
+            "nameservers": [{"ldhName": "A.IANA-SERVERS.NET"}, {"ldhName": "B.IANA-SERVERS.NET"}],
+            "secureDNS": {"delegationSigned": true}
+        });
+        let info = parse_rdap(&body);
+        assert_eq!(info.registrar.as_deref(), Some("Example Registrar"));
+        assert_eq!(
+            info.expires.unwrap().to_rfc3339(),
+            "2027-08-13T04:00:00+00:00"
+        );
+        assert_eq!(info.registered.unwrap().format("%Y").to_string(), "1995");
+        assert!(info.updated.is_some());
+        assert_eq!(info.statuses.len(), 2);
+        assert_eq!(
+            info.nameservers,
+            vec!["a.iana-servers.net", "b.iana-servers.net"]
+        );
+        assert_eq!(info.dnssec, Some(true));
+
+        assert_eq!(parse_rdap(&json!({})), DomainInfo::default());
+    }
+}
@@ -0,0 +1,723 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{DownloadClientConfig, DownloadsConfig};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// qBittorrent reports this ETA for "unknown / infinite".
+const QBT_ETA_INFINITE: i64 = 8_640_000;
+
+fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+/// A queue entry normalized across clients.
+#[derive(Debug, Clone, PartialEq)]
+struct Download {
+    id: String,
+    name: String,
+    state: String,
+    progress_pct: f64,
+    eta_secs: Option<u64>,
+    speed_bps: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    Pause,
+    Resume,
+}
+
+/// List, pause/resume, and add downloads on qBittorrent, Transmission, and SABnzbd.
+pub struct DownloadsTool {
+    security: Arc<SecurityPolicy>,
+    config: DownloadsConfig,
+}
+
+impl DownloadsTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: DownloadsConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn http(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.downloads",
+            self.config.timeout_secs.max(1),
+            10,
+        )
+    }
+
+    fn find_client(&self, name: Option<&str>) -> Result<&DownloadClientConfig, String> {
+        match name {
+            Some(name) => self
+                .config
+                .clients
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(name))
+                .ok_or_else(|| format!("Unknown download client '{name}'")),
+            None => match self.config.clients.as_slice() {
+                [only] => Ok(only),
+                [] => Err("No [[downloads.clients]] configured".into()),
+                _ => Err(format!(
+                    "Several clients configured; pass 'client' (one of: {})",
+                    self.config
+                        .clients
+                        .iter()
+                        .map(|c| c.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            },
+        }
+    }
+
+    // ── qBittorrent ─────────────────────────────────────────────
+
+    /// Log in and return the `SID` cookie, or `None` when no credentials are set
+    /// (e.g. "bypass authentication for clients on localhost").
+    async fn qbt_login(&self, client: &DownloadClientConfig) -> anyhow::Result<Option<String>> {
+        let Some(username) = &client.username else {
+            return Ok(None);
+        };
+        let response = self
+            .http()
+            .post(format!("{}/api/v2/auth/login", base(client)))
+            .header("Referer", base(client))
+            .form(&[
+                ("username", username.as_str()),
+                ("password", client.password.as_deref().unwrap_or_default()),
+            ])
+            .send()
+            .await?;
+        let cookie = response
+            .headers()
+            .get_all(reqwest::header::SET_COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .find_map(|v| v.split(';').next().filter(|c| c.starts_with("SID=")))
+            .map(str::to_string);
+        let body = response.text().await.unwrap_or_default();
+        match cookie {
+            Some(cookie) => Ok(Some(cookie)),
+            None => anyhow::bail!("qBittorrent login failed: {}", body.trim()),
+        }
+    }
+
+    async fn qbt_post(
+        &self,
+        client: &DownloadClientConfig,
+        path: &str,
+        form: &[(&str, &str)],
+    ) -> anyhow::Result<reqwest::Response> {
+        let mut request = self
+            .http()
+            .post(format!("{}/api/v2/{path}", base(client)))
+            .form(form);
+        if let Some(cookie) = self.qbt_login(client).await? {
+            request = request.header(reqwest::header::COOKIE, cookie);
+        }
+        Ok(request.send().await?)
+    }
+
+    async fn qbt_list(&self, client: &DownloadClientConfig) -> anyhow::Result<Vec<Download>> {
+        let response = self.qbt_post(client, "torrents/info", &[]).await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("torrents/info returned {status}");
+        }
+        Ok(parse_qbittorrent(&response.json().await?))
+    }
+
+    async fn qbt_control(
+        &self,
+        client: &DownloadClientConfig,
+        id: &str,
+        control: Control,
+    ) -> anyhow::Result<()> {
+        // qBittorrent 5 renamed pause/resume to stop/start.
+        let (legacy, current) = match control {
+            Control::Pause => ("torrents/pause", "torrents/stop"),
+            Control::Resume => ("torrents/resume", "torrents/start"),
+        };
+        let mut response = self.qbt_post(client, legacy, &[("hashes", id)]).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            response = self.qbt_post(client, current, &[("hashes", id)]).await?;
+        }
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("qBittorrent returned {status}");
+        }
+        Ok(())
+    }
+
+    async fn qbt_add(&self, client: &DownloadClientConfig, uri: &str) -> anyhow::Result<()> {
+        let response = self
+            .qbt_post(client, "torrents/add", &[("urls", uri)])
+            .await?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if !status.is_success() || body.trim().eq_ignore_ascii_case("fails.") {
+            anyhow::bail!(
+                "qBittorrent rejected the torrent ({status}): {}",
+                body.trim()
+            );
+        }
+        Ok(())
+    }
+
+    // ── Transmission ────────────────────────────────────────────
+
+    async fn transmission_rpc(
+        &self,
+        client: &DownloadClientConfig,
+        method: &str,
+        arguments: Value,
+    ) -> anyhow::Result<Value> {
+        let url = format!("{}/transmission/rpc", base(client));
+        let body = json!({"method": method, "arguments": arguments});
+        let mut session_id = String::new();
+        // The first call is answered with 409 and the session id to use.
+        for _ in 0..2 {
+            let mut request = self
+                .http()
+                .post(&url)
+                .header("X-Transmission-Session-Id", &session_id)
+                .json(&body);
+            if let Some(username) = &client.username {
+                request = request.basic_auth(username, client.password.as_deref());
+            }
+            let response = request.send().await?;
+            if response.status() == reqwest::StatusCode::CONFLICT {
+                session_id = response
+                    .headers()
+                    .get("X-Transmission-Session-Id")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string();
+                continue;
+            }
+            let status = response.status();
+            if !status.is_success() {
+                anyhow::bail!("Transmission RPC returned {status}");
+            }
+            let reply: Value = response.json().await?;
+            return match reply["result"].as_str() {
+                Some("success") => Ok(reply["arguments"].clone()),
+                other => anyhow::bail!("Transmission {method} failed: {}", other.unwrap_or("?")),
+            };
+        }
+        anyhow::bail!("Transmission did not accept the session id")
+    }
+
+    async fn transmission_list(
+        &self,
+        client: &DownloadClientConfig,
+    ) -> anyhow::Result<Vec<Download>> {
+        let arguments = self
+            .transmission_rpc(
+                client,
+                "torrent-get",
+                json!({"fields": ["hashString", "name", "status", "percentDone", "eta", "rateDownload", "errorString"]}),
+            )
+            .await?;
+        Ok(parse_transmission(&arguments))
+    }
+
+    // ── SABnzbd ─────────────────────────────────────────────────
+
+    async fn sab_api(
+        &self,
+        client: &DownloadClientConfig,
+        params: &[(&str, &str)],
+    ) -> anyhow::Result<Value> {
+        let mut query = vec![
+            ("output", "json"),
+            ("apikey", client.api_key.as_deref().unwrap_or_default()),
+        ];
+        query.extend_from_slice(params);
+        let response = self
+            .http()
+            .get(format!("{}/api", base(client)))
+            .query(&query)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("SABnzbd returned {status}");
+        }
+        let reply: Value = response.json().await?;
+        if reply["status"] == false {
+            anyhow::bail!(
+                "SABnzbd error: {}",
+                reply["error"].as_str().unwrap_or("request failed")
+            );
+        }
+        Ok(reply)
+    }
+
+    // ── Dispatch ────────────────────────────────────────────────
+
+    async fn list(&self, client: &DownloadClientConfig) -> anyhow::Result<Vec<Download>> {
+        match client.kind.as_str() {
+            "qbittorrent" => self.qbt_list(client).await,
+            "transmission" => self.transmission_list(client).await,
+            "sabnzbd" => Ok(parse_sabnzbd(
+                &self.sab_api(client, &[("mode", "queue")]).await?,
+            )),
+            other => anyhow::bail!("Unknown client kind '{other}'"),
+        }
+    }
+
+    async fn control(
+        &self,
+        client: &DownloadClientConfig,
+        id: &str,
+        control: Control,
+    ) -> anyhow::Result<()> {
+        match client.kind.as_str() {
+            "qbittorrent" => self.qbt_control(client, id, control).await,
+            "transmission" => {
+                let method = match control {
+                    Control::Pause => "torrent-stop",
+                    Control::Resume => "torrent-start",
+                };
+                let ids = match id.parse::<u64>() {
+                    Ok(numeric) => json!([numeric]),
+                    Err(_) => json!([id]),
+                };
+                self.transmission_rpc(client, method, json!({"ids": ids}))
+                    .await
+                    .map(|_| ())
+            }
+            "sabnzbd" => {
+                let name = match control {
+                    Control::Pause => "pause",
+                    Control::Resume => "resume",
+                };
+                self.sab_api(client, &[("mode", "queue"), ("name", name), ("value", id)])
+                    .await
+                    .map(|_| ())
+            }
+            other => anyhow::bail!("Unknown client kind '{other}'"),
+        }
+    }
+
+    async fn add(&self, client: &DownloadClientConfig, uri: &str) -> anyhow::Result<()> {
+        match client.kind.as_str() {
+            "qbittorrent" => self.qbt_add(client, uri).await,
+            "transmission" => self
+                .transmission_rpc(client, "torrent-add", json!({"filename": uri}))
+                .await
+                .map(|_| ()),
+            "sabnzbd" => self
+                .sab_api(client, &[("mode", "addurl"), ("name", uri)])
+                .await
+                .map(|_| ()),
+            other => anyhow::bail!("Unknown client kind '{other}'"),
+        }
+    }
+}
+
+fn base(client: &DownloadClientConfig) -> &str {
+    client.url.trim().trim_end_matches('/')
+}
+
+fn host_allowed(host: &str, allowed: &[String]) -> bool {
+    let host = host.to_ascii_lowercase();
+    allowed.iter().any(|entry| {
+        let entry = entry.trim().trim_start_matches("*.").to_ascii_lowercase();
+        !entry.is_empty() && (host == entry || host.ends_with(&format!(".{entry}")))
+    })
+}
+
+/// Check a magnet link or NZB/torrent URL against `allowed_sources`.
+fn check_source(uri: &str, kind: &str, allowed: &[String]) -> Result<(), String> {
+    if allowed.is_empty() {
+        return Err("Adding downloads is disabled: [downloads].allowed_sources is empty".into());
+    }
+    if let Some(query) = uri.strip_prefix("magnet:?") {
+        if kind == "sabnzbd" {
+            return Err("SABnzbd cannot take magnet links".into());
+        }
+        let trackers: Vec<String> = query
+            .split('&')
+            .filter_map(|pair| pair.strip_prefix("tr="))
+            .filter_map(|tr| urlencoding::decode(tr).ok())
+            .filter_map(|tr| {
+                reqwest::Url::parse(&tr)
+                    .ok()?
+                    .host_str()
+                    .map(str::to_string)
+            })
+            .collect();
+        if trackers.iter().any(|host| host_allowed(host, allowed)) {
+            return Ok(());
+        }
+        return Err(if trackers.is_empty() {
+            "Magnet link has no tracker; only allowlisted trackers are accepted".into()
+        } else {
+            format!(
+                "None of the magnet's trackers ({}) are in allowed_sources",
+                trackers.join(", ")
+            )
+        });
+    }
+
+    let url = reqwest::Url::parse(uri).map_err(|_| format!("Not a magnet link or URL: {uri}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Unsupported URL scheme '{}'", url.scheme()));
+    }
+    let host = url.host_str().unwrap_or_default();
+    if host_allowed(host, allowed) {
+        Ok(())
+    } else {
+        Err(format!("Host '{host}' is not in allowed_sources"))
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn non_negative(value: Option<f64>) -> Option<u64> {
+    value.filter(|v| *v >= 0.0).map(|v| v.round() as u64)
+}
+
+fn parse_qbittorrent(body: &Value) -> Vec<Download> {
+    body.as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|t| Download {
+            id: t["hash"].as_str().unwrap_or_default().to_string(),
+            name: t["name"].as_str().unwrap_or("?").to_string(),
+            state: t["state"].as_str().unwrap_or("unknown").to_string(),
+            progress_pct: t["progress"].as_f64().unwrap_or(0.0) * 100.0,
+            eta_secs: t["eta"]
+                .as_i64()
+                .filter(|eta| (0..QBT_ETA_INFINITE).contains(eta))
+                .and_then(|eta| u64::try_from(eta).ok()),
+            speed_bps: t["dlspeed"].as_u64(),
+        })
+        .collect()
+}
+
+fn transmission_state(status: i64, error: &str) -> String {
+    if !error.is_empty() {
+        return format!("error: {error}");
+    }
+    match status {
+        0 => "paused",
+        1 | 2 => "checking",
+        3 => "queued",
+        4 => "downloading",
+        5 | 6 => "seeding",
+        _ => "unknown",
+    }
+    .into()
+}
+
+fn parse_transmission(arguments: &Value) -> Vec<Download> {
+    arguments["torrents"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|t| Download {
+            id: t["hashString"].as_str().unwrap_or_default().to_string(),
+            name: t["name"].as_str().unwrap_or("?").to_string(),
+            state: transmission_state(
+                t["status"].as_i64().unwrap_or(-1),
+                t["errorString"].as_str().unwrap_or_default(),
+            ),
+            progress_pct: t["percentDone"].as_f64().unwrap_or(0.0) * 100.0,
+            eta_secs: non_negative(t["eta"].as_f64()),
+            speed_bps: t["rateDownload"].as_u64(),
+        })
+        .collect()
+}
+
+/// Parse SABnzbd's `H:MM:SS` time-left field.
+fn parse_timeleft(value: &str) -> Option<u64> {
+    let mut total = 0u64;
+    for part in value.split(':') {
+        total = total * 60 + part.trim().parse::<u64>().ok()?;
+    }
+    Some(total)
+}
+
+fn parse_sabnzbd(reply: &Value) -> Vec<Download> {
+    let queue = &reply["queue"];
+    let paused_all = queue["paused"].as_bool().unwrap_or(false);
+    queue["slots"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|slot| {
+            let state = slot["status"].as_str().unwrap_or("Queued").to_lowercase();
+            Download {
+                id: slot["nzo_id"].as_str().unwrap_or_default().to_string(),
+                name: slot["filename"].as_str().unwrap_or("?").to_string(),
+                state: if paused_all && state != "paused" {
+                    format!("{state} (queue paused)")
+                } else {
+                    state
+                },
+                progress_pct: slot["percentage"]
+                    .as_str()
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(0.0),
+                eta_secs: slot["timeleft"].as_str().and_then(parse_timeleft),
+                speed_bps: None,
+            }
+        })
+        .collect()
+}
+
+fn format_eta(secs: u64) -> String {
+    match secs {
+        s if s < 60 => format!("{s}s"),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h {:02}m", s / 3600, (s % 3600) / 60),
+        s => format!("{}d {}h", s / 86_400, (s % 86_400) / 3600),
+    }
+}
+
+fn format_downloads(client: &str, downloads: &[Download]) -> String {
+    let mut out = format!("{client}:\n");
+    if downloads.is_empty() {
+        out.push_str("  (nothing active)\n");
+    }
+    for d in downloads {
+        let _ = write!(out, "  - {} — {:.1}%, {}", d.name, d.progress_pct, d.state);
+        if let Some(eta) = d.eta_secs {
+            let _ = write!(out, ", ETA {}", format_eta(eta));
+        }
+        if let Some(speed) = d.speed_bps.filter(|s| *s > 0) {
+            let mib = speed as f64 / 1_048_576.0;
+            let _ = write!(out, ", {mib:.1} MiB/s");
+        }
+        let _ = writeln!(out, " [id: {}]", d.id);
+    }
+    out
+}
+
+#[async_trait]
+impl Tool for DownloadsTool {
+    fn name(&self) -> &str {
+        "downloads"
+    }
+
+    fn description(&self) -> &str {
+        "Manage the download clients (qBittorrent, Transmission, SABnzbd): list active \
+         downloads with progress and ETA, pause or resume one by id, or add a magnet link / \
+         NZB URL from an allowlisted tracker or indexer."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "pause", "resume", "add"]
+                },
+                "client": {
+                    "type": "string",
+                    "description": "Configured client name; optional when only one is configured (list covers all)"
+                },
+                "id": {
+                    "type": "string",
+                    "description": "Torrent hash / NZB id from list (pause, resume)"
+                },
+                "uri": {
+                    "type": "string",
+                    "description": "Magnet link or .torrent/.nzb URL (add)"
+                },
+                "all": {
+                    "type": "boolean",
+                    "description": "Include completed / seeding items in list (default: false)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        let client_name = args.get("client").and_then(|v| v.as_str());
+
+        if action != "list" && !self.security.can_act() {
+            return Ok(failure("Action blocked: autonomy is read-only"));
+        }
+        if self.security.is_rate_limited() {
+            return Ok(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        match action {
+            "list" => {
+                let include_done = args
+                    .get("all")
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or(false);
+                let clients: Vec<&DownloadClientConfig> = match client_name {
+                    Some(name) => match self.find_client(Some(name)) {
+                        Ok(client) => vec![client],
+                        Err(e) => return Ok(failure(e)),
+                    },
+                    None => self.config.clients.iter().collect(),
+                };
+                if clients.is_empty() {
+                    return Ok(failure("No [[downloads.clients]] configured"));
+                }
+                let mut output = String::new();
+                for client in clients {
+                    match self.list(client).await {
+                        Ok(mut downloads) => {
+                            if !include_done {
+                                downloads.retain(|d| {
+                                    d.progress_pct < 100.0 && !d.state.contains("seed")
+                                });
+                            }
+                            output.push_str(&format_downloads(&client.name, &downloads));
+                        }
+                        Err(e) => {
+                            let _ = writeln!(output, "{}: error: {e}", client.name);
+                        }
+                    }
+                }
+                Ok(ToolResult {
+                    success: true,
+                    output: output.trim_end().to_string(),
+                    error: None,
+                })
+            }
+            "pause" | "resume" => {
+                let client = match self.find_client(client_name) {
+                    Ok(client) => client,
+                    Err(e) => return Ok(failure(e)),
+                };
+                let Some(id) = args
+                    .get("id")
+                    .and_then(|v| v.as_str())
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                else {
+                    return Ok(failure(format!("{action} requires an 'id'")));
+                };
+                if !self.security.record_action() {
+                    return Ok(failure("Rate limit exceeded: action budget exhausted"));
+                }
+                let control = if action == "pause" {
+                    Control::Pause
+                } else {
+                    Control::Resume
+                };
+                match self.control(client, id, control).await {
+                    Ok(()) => Ok(ToolResult {
+                        success: true,
+                        output: format!("{action}d {id} on {}", client.name),
+                        error: None,
+                    }),
+                    Err(e) => Ok(failure(e.to_string())),
+                }
+            }
+            "add" => {
+                let client = match self.find_client(client_name) {
+                    Ok(client) => client,
+                    Err(e) => return Ok(failure(e)),
+                };
+                let Some(uri) = args
+                    .get("uri")
+                    .and_then(|v| v.as_str())
+                    .map(str::trim)
+                    .filter(|uri| !uri.is_empty())
+                else {
+                    return Ok(failure("add requires a 'uri'"));
+                };
+                if let Err(e) = check_source(uri, &client.kind, &self.config.allowed_sources) {
+                    return Ok(failure(e));
+                }
+                if !self.security.record_action() {
+                    return Ok(failure("Rate limit exceeded: action budget exhausted"));
+                }
+                match self.add(client, uri).await {
+                    Ok(()) => Ok(ToolResult {
+                        success: true,
+                        output: format!("Added to {}", client.name),
+                        error: None,
+                    }),
+                    Err(e) => Ok(failure(e.to_string())),
+                }
+            }
+            other => Ok(failure(format!(
+                "Unknown action '{other}'. Use list, pause, resume, or add."
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_must_be_allowlisted() {
+        let allowed = vec!["tracker.example.org".to_string(), "nzb.example".to_string()];
+        let magnet =
+            "magnet:?xt=urn:btih:abc&dn=iso&tr=udp%3A%2F%2Ftracker.example.org%3A1337%2Fannounce";
+        assert!(check_source(magnet, "qbittorrent", &allowed).is_ok());
+        assert!(check_source(magnet, "sabnzbd", &allowed).is_err());
+        assert!(check_source(
+            "magnet:?xt=urn:btih:abc&tr=udp%3A%2F%2Fevil.example%3A80",
+            "transmission",
+            &allowed
+        )
+        .unwrap_err()
+        .contains("evil.example"));
+        assert!(check_source("magnet:?xt=urn:btih:abc", "transmission", &allowed).is_err());
+        assert!(check_source("https://api.nzb.example/get/1.nzb", "sabnzbd", &allowed).is_ok());
+        assert!(check_source("https://nzb.example.evil/x.nzb", "sabnzbd", &allowed).is_err());
+        assert!(check_source("file:///etc/passwd", "sabnzbd", &allowed).is_err());
+        assert!(check_source(magnet, "qbittorrent", &[]).is_err());
+    }
+
+    #[test]
+    fn parses_client_queues() {
+        let qbt = json!([{"hash": "abc", "name": "debian.iso", "state": "downloading",
+                          "progress": 0.5, "eta": 90, "dlspeed": 2_097_152},
+                         {"hash": "def", "name": "stalled", "state": "stalledDL",
+                          "progress": 0.1, "eta": 8_640_000, "dlspeed": 0}]);
+        let parsed = parse_qbittorrent(&qbt);
+        assert_eq!(parsed[0].eta_secs, Some(90));
+        assert_eq!(parsed[1].eta_secs, None);
+        let text = format_downloads("qbt", &parsed);
+        assert!(text.contains("- debian.iso — 50.0%, downloading, ETA 1m, 2.0 MiB/s [id: abc]"));
+
+        let transmission = json!({"torrents": [{"hashString": "h1", "name": "arch.iso", "status": 0,
+            "percentDone": 0.25, "eta": -1, "rateDownload": 0, "errorString": ""}]});
+        let parsed = parse_transmission(&transmission);
+        assert_eq!(parsed[0].state, "paused");
+        assert_eq!(parsed[0].eta_secs, None);
+
+        let sab = json!({"queue": {"paused": false, "slots": [
+            {"nzo_id": "SABnzbd_nzo_1", "filename": "Show.S01E01", "percentage": "42",
+             "timeleft": "0:12:05", "status": "Downloading"}]}});
+        let parsed = parse_sabnzbd(&sab);
+        assert_eq!(parsed[0].progress_pct, 42.0);
+        assert_eq!(parsed[0].eta_secs, Some(725));
+    }
+}
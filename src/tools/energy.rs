@@ -0,0 +1,819 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{EnergyConfig, EnergyInverterConfig, EnergyMeterConfig};
+use crate::security::SecurityPolicy;
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::Duration;
+
+const SOLAREDGE_API: &str = "https://monitoringapi.solaredge.com";
+/// Candidate SunSpec base addresses, in the order the spec recommends probing.
+const SUNSPEC_BASES: [u16; 3] = [40000, 0, 50000];
+const SUNSPEC_MARKER: [u16; 2] = [0x5375, 0x6e53]; // "SunS"
+const MAX_SUNSPEC_MODELS: usize = 32;
+const MAX_MQTT_PACKET: usize = 64 * 1024;
+
+/// Current power flow plus daily totals. Power is in watts; grid power is
+/// positive when importing and negative when exporting.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+struct EnergyReading {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    production_w: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    consumption_w: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grid_w: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    export_w: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    production_today_kwh: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grid_import_today_kwh: Option<f64>,
+    /// Lifetime counters used to derive daily totals when the backend has none.
+    #[serde(skip)]
+    production_lifetime_kwh: Option<f64>,
+    #[serde(skip)]
+    grid_import_lifetime_kwh: Option<f64>,
+    sources: Vec<String>,
+}
+
+/// First counter values seen today, for backends that only report lifetime totals.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DailyBaseline {
+    date: String,
+    #[serde(default)]
+    counters: BTreeMap<String, f64>,
+}
+
+/// Report solar production, household consumption, and grid import/export.
+pub struct EnergyTool {
+    security: Arc<SecurityPolicy>,
+    config: EnergyConfig,
+    workspace_dir: PathBuf,
+}
+
+impl EnergyTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        config: EnergyConfig,
+        workspace_dir: PathBuf,
+    ) -> Self {
+        Self {
+            security,
+            config,
+            workspace_dir,
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.config.timeout_secs.max(1))
+    }
+
+    fn client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.energy",
+            self.config.timeout_secs.max(1),
+            10,
+        )
+    }
+
+    async fn get_json(&self, url: &str) -> anyhow::Result<Value> {
+        let response = self.client().get(url).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("GET {} returned {status}", redact_query(url));
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn read_inverter(
+        &self,
+        inverter: &EnergyInverterConfig,
+    ) -> anyhow::Result<EnergyReading> {
+        match inverter.backend.trim().to_ascii_lowercase().as_str() {
+            "fronius" => {
+                let base = inverter
+                    .url
+                    .as_deref()
+                    .context("energy.inverter.url is required for fronius")?;
+                let url = format!(
+                    "{}/solar_api/v1/GetPowerFlowRealtimeData.fcgi",
+                    base.trim_end_matches('/')
+                );
+                Ok(parse_fronius_power_flow(&self.get_json(&url).await?))
+            }
+            "solaredge" => {
+                let site = inverter
+                    .site_id
+                    .as_deref()
+                    .context("energy.inverter.site_id is required for solaredge")?;
+                let key = inverter
+                    .api_key
+                    .as_deref()
+                    .context("energy.inverter.api_key is required for solaredge")?;
+                let site = urlencoding::encode(site);
+                let key = urlencoding::encode(key);
+                let flow = self
+                    .get_json(&format!(
+                        "{SOLAREDGE_API}/site/{site}/currentPowerFlow?api_key={key}"
+                    ))
+                    .await?;
+                let overview = self
+                    .get_json(&format!(
+                        "{SOLAREDGE_API}/site/{site}/overview?api_key={key}"
+                    ))
+                    .await?;
+                Ok(parse_solaredge(&flow, &overview))
+            }
+            "sunspec" => {
+                let address = inverter
+                    .url
+                    .as_deref()
+                    .context("energy.inverter.url (host:port) is required for sunspec")?;
+                let address = with_default_port(address, 502);
+                let registers = tokio::time::timeout(
+                    self.timeout(),
+                    read_sunspec_inverter(&address, inverter.unit_id),
+                )
+                .await
+                .context("SunSpec read timed out")??;
+                parse_sunspec_inverter(&registers)
+            }
+            other => anyhow::bail!(
+                "Unknown inverter backend '{other}'. Use 'fronius', 'solaredge', or 'sunspec'."
+            ),
+        }
+    }
+
+    async fn read_meter(&self, meter: &EnergyMeterConfig) -> anyhow::Result<EnergyReading> {
+        let mut topics = vec![meter.power_topic.clone()];
+        if let Some(topic) = &meter.import_energy_topic {
+            topics.push(topic.clone());
+        }
+        let broker = with_default_port(&meter.broker, 1883);
+        let payloads = tokio::time::timeout(
+            self.timeout(),
+            mqtt_read_topics(
+                &broker,
+                meter.username.as_deref(),
+                meter.password.as_deref(),
+                &topics,
+            ),
+        )
+        .await
+        .with_context(|| format!("No MQTT message on {} before timeout", topics.join(", ")))??;
+
+        let grid_w = payloads
+            .get(&meter.power_topic)
+            .and_then(|payload| payload_number(payload, meter.power_field.as_deref()))
+            .context("MQTT power payload is not a number")?;
+        let import_kwh = meter.import_energy_topic.as_ref().and_then(|topic| {
+            payload_number(payloads.get(topic)?, meter.import_energy_field.as_deref())
+        });
+        Ok(EnergyReading {
+            grid_w: Some(grid_w),
+            grid_import_lifetime_kwh: import_kwh,
+            sources: vec!["mqtt".into()],
+            ..EnergyReading::default()
+        })
+    }
+
+    /// Fill daily totals from lifetime counters using the first value seen today.
+    async fn apply_daily_baseline(&self, reading: &mut EnergyReading) {
+        let path = self
+            .workspace_dir
+            .join("energy")
+            .join("daily_baseline.json");
+        let today = chrono::Local::now().date_naive().to_string();
+        let mut baseline = tokio::fs::read_to_string(&path)
+            .await
+            .ok()
+            .and_then(|content| serde_json::from_str::<DailyBaseline>(&content).ok())
+            .filter(|baseline| baseline.date == today)
+            .unwrap_or_else(|| DailyBaseline {
+                date: today,
+                counters: BTreeMap::new(),
+            });
+
+        let mut changed = false;
+        let counters = [
+            ("production_kwh", reading.production_lifetime_kwh),
+            ("grid_import_kwh", reading.grid_import_lifetime_kwh),
+        ];
+        let mut daily = [None, None];
+        for (slot, (name, value)) in counters.into_iter().enumerate() {
+            let Some(value) = value else { continue };
+            let start = *baseline
+                .counters
+                .entry(name.to_string())
+                .or_insert_with(|| {
+                    changed = true;
+                    value
+                });
+            daily[slot] = Some(round3((value - start).max(0.0)));
+        }
+        if reading.production_today_kwh.is_none() {
+            reading.production_today_kwh = daily[0];
+        }
+        if reading.grid_import_today_kwh.is_none() {
+            reading.grid_import_today_kwh = daily[1];
+        }
+
+        if changed {
+            if let Some(parent) = path.parent() {
+                let _ = tokio::fs::create_dir_all(parent).await;
+            }
+            if let Ok(content) = serde_json::to_string_pretty(&baseline) {
+                if let Err(e) = tokio::fs::write(&path, content).await {
+                    tracing::warn!("Failed to persist energy baseline: {e}");
+                }
+            }
+        }
+    }
+}
+
+fn redact_query(url: &str) -> &str {
+    url.split('?').next().unwrap_or(url)
+}
+
+fn with_default_port(address: &str, port: u16) -> String {
+    let address = address
+        .trim()
+        .trim_start_matches("tcp://")
+        .trim_start_matches("mqtt://")
+        .trim_end_matches('/');
+    if address
+        .rsplit_once(':')
+        .is_some_and(|(_, p)| p.parse::<u16>().is_ok())
+    {
+        address.to_string()
+    } else {
+        format!("{address}:{port}")
+    }
+}
+
+fn round3(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+/// Merge inverter and meter readings; the meter is authoritative for grid flow.
+fn combine(inverter: Option<EnergyReading>, meter: Option<EnergyReading>) -> EnergyReading {
+    let mut reading = inverter.unwrap_or_default();
+    if let Some(meter) = meter {
+        reading.grid_w = meter.grid_w.or(reading.grid_w);
+        reading.grid_import_lifetime_kwh = meter.grid_import_lifetime_kwh;
+        reading.sources.extend(meter.sources);
+    }
+    if let (Some(production), Some(grid)) = (reading.production_w, reading.grid_w) {
+        if reading.consumption_w.is_none() || reading.sources.iter().any(|s| s == "mqtt") {
+            reading.consumption_w = Some((production + grid).max(0.0));
+        }
+    }
+    reading.export_w = reading.grid_w.map(|grid| (-grid).max(0.0));
+    reading
+}
+
+/// Fronius Solar API v1 `GetPowerFlowRealtimeData`.
+fn parse_fronius_power_flow(body: &Value) -> EnergyReading {
+    let site = &body["Body"]["Data"]["Site"];
+    let production = site["P_PV"].as_f64().unwrap_or(0.0);
+    EnergyReading {
+        production_w: Some(production),
+        // P_Load is negative when the house draws power.
+        consumption_w: site["P_Load"].as_f64().map(f64::abs),
+        grid_w: site["P_Grid"].as_f64(),
+        production_today_kwh: site["E_Day"].as_f64().map(|wh| round3(wh / 1000.0)),
+        production_lifetime_kwh: site["E_Total"].as_f64().map(|wh| wh / 1000.0),
+        sources: vec!["fronius".into()],
+        ..EnergyReading::default()
+    }
+}
+
+/// SolarEdge monitoring API `currentPowerFlow` + `overview`.
+fn parse_solaredge(flow: &Value, overview: &Value) -> EnergyReading {
+    let flow = &flow["siteCurrentPowerFlow"];
+    let scale = if flow["unit"]
+        .as_str()
+        .unwrap_or("kW")
+        .eq_ignore_ascii_case("W")
+    {
+        1.0
+    } else {
+        1000.0
+    };
+    let power = |key: &str| flow[key]["currentPower"].as_f64().map(|p| p * scale);
+    let exporting = flow["connections"].as_array().is_some_and(|connections| {
+        connections.iter().any(|c| {
+            c["to"]
+                .as_str()
+                .is_some_and(|to| to.eq_ignore_ascii_case("grid"))
+        })
+    });
+    let grid = power("GRID").map(|p| if exporting { -p } else { p });
+    EnergyReading {
+        production_w: power("PV").or(Some(0.0)),
+        consumption_w: power("LOAD"),
+        grid_w: grid,
+        production_today_kwh: overview["overview"]["lastDayData"]["energy"]
+            .as_f64()
+            .map(|wh| round3(wh / 1000.0)),
+        sources: vec!["solaredge".into()],
+        ..EnergyReading::default()
+    }
+}
+
+/// Extract a number from a raw or JSON MQTT payload, optionally following a
+/// dot path such as `SML.Power_curr`.
+fn payload_number(payload: &str, field: Option<&str>) -> Option<f64> {
+    let payload = payload.trim();
+    let Some(field) = field.filter(|f| !f.is_empty()) else {
+        return payload.parse().ok().or_else(|| {
+            serde_json::from_str::<Value>(payload)
+                .ok()
+                .and_then(|v| v.as_f64())
+        });
+    };
+    let mut value: Value = serde_json::from_str(payload).ok()?;
+    for key in field.split('.') {
+        value = value.get_mut(key)?.take();
+    }
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+}
+
+// ── Modbus TCP / SunSpec ────────────────────────────────────────
+
+async fn modbus_read_holding(
+    stream: &mut TcpStream,
+    transaction: u16,
+    unit_id: u8,
+    address: u16,
+    count: u16,
+) -> anyhow::Result<Vec<u16>> {
+    let mut request = Vec::with_capacity(12);
+    request.extend_from_slice(&transaction.to_be_bytes());
+    request.extend_from_slice(&[0, 0, 0, 6, unit_id, 0x03]);
+    request.extend_from_slice(&address.to_be_bytes());
+    request.extend_from_slice(&count.to_be_bytes());
+    stream.write_all(&request).await?;
+
+    let mut header = [0u8; 7];
+    stream.read_exact(&mut header).await?;
+    let length = usize::from(u16::from_be_bytes([header[4], header[5]]));
+    if !(2..=260).contains(&length) {
+        anyhow::bail!("Malformed Modbus response length {length}");
+    }
+    let mut pdu = vec![0u8; length - 1];
+    stream.read_exact(&mut pdu).await?;
+    if u16::from_be_bytes([header[0], header[1]]) != transaction {
+        anyhow::bail!("Modbus transaction id mismatch");
+    }
+    match pdu.first() {
+        Some(0x03) => {}
+        Some(0x83) => anyhow::bail!(
+            "Modbus exception {} reading register {address}",
+            pdu.get(1).copied().unwrap_or_default()
+        ),
+        _ => anyhow::bail!("Unexpected Modbus function in response"),
+    }
+    let data = pdu.get(2..).unwrap_or_default();
+    if data.len() != usize::from(count) * 2 {
+        anyhow::bail!("Short Modbus response");
+    }
+    Ok(data
+        .chunks_exact(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+        .collect())
+}
+
+/// Locate the first SunSpec inverter model (101–103) and return its registers,
+/// starting at the model ID.
+async fn read_sunspec_inverter(address: &str, unit_id: u8) -> anyhow::Result<Vec<u16>> {
+    let mut stream = TcpStream::connect(address)
+        .await
+        .with_context(|| format!("Failed to connect to {address}"))?;
+    let mut transaction: u16 = 0;
+    let mut next_transaction = || {
+        transaction = transaction.wrapping_add(1);
+        transaction
+    };
+
+    for base in SUNSPEC_BASES {
+        let Ok(marker) =
+            modbus_read_holding(&mut stream, next_transaction(), unit_id, base, 2).await
+        else {
+            continue;
+        };
+        if marker != SUNSPEC_MARKER {
+            continue;
+        }
+        let mut cursor = base + 2;
+        for _ in 0..MAX_SUNSPEC_MODELS {
+            let header =
+                modbus_read_holding(&mut stream, next_transaction(), unit_id, cursor, 2).await?;
+            let (model, length) = (header[0], header[1]);
+            if model == 0xFFFF {
+                break;
+            }
+            if (101..=103).contains(&model) {
+                return modbus_read_holding(
+                    &mut stream,
+                    next_transaction(),
+                    unit_id,
+                    cursor,
+                    length.min(123) + 2,
+                )
+                .await;
+            }
+            cursor = cursor.saturating_add(length).saturating_add(2);
+        }
+        anyhow::bail!("SunSpec device has no inverter model (101-103)");
+    }
+    anyhow::bail!("No SunSpec marker found at {address}")
+}
+
+fn scaled(value: f64, scale_factor: u16) -> Option<f64> {
+    let sf = scale_factor as i16;
+    if sf == i16::MIN {
+        return None;
+    }
+    // Divide for negative factors: 0.1 has no exact binary representation.
+    let factor = 10f64.powi(i32::from(sf.unsigned_abs()));
+    Some(if sf < 0 {
+        value / factor
+    } else {
+        value * factor
+    })
+}
+
+/// Decode AC power and lifetime energy from SunSpec inverter model 101–103
+/// registers (`W` at offset 14, `W_SF` 15, `WH` 24–25, `WH_SF` 26).
+fn parse_sunspec_inverter(registers: &[u16]) -> anyhow::Result<EnergyReading> {
+    if registers.len() < 27 {
+        anyhow::bail!("SunSpec inverter model too short");
+    }
+    let raw_w = registers[14] as i16;
+    let production_w = if raw_w == i16::MIN {
+        None
+    } else {
+        scaled(f64::from(raw_w), registers[15])
+    };
+    let raw_wh = (u32::from(registers[24]) << 16) | u32::from(registers[25]);
+    let lifetime_wh = if raw_wh == 0 {
+        None
+    } else {
+        scaled(f64::from(raw_wh), registers[26])
+    };
+    Ok(EnergyReading {
+        production_w: Some(production_w.unwrap_or(0.0)),
+        production_lifetime_kwh: lifetime_wh.map(|wh| wh / 1000.0),
+        sources: vec!["sunspec".into()],
+        ..EnergyReading::default()
+    })
+}
+
+// ── MQTT 3.1.1 (subscribe-only) ─────────────────────────────────
+
+fn mqtt_remaining_length(mut length: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(4);
+    loop {
+        #[allow(clippy::cast_possible_truncation)]
+        let mut byte = (length % 128) as u8;
+        length /= 128;
+        if length > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if length == 0 {
+            return out;
+        }
+    }
+}
+
+fn mqtt_string(out: &mut Vec<u8>, value: &str) {
+    #[allow(clippy::cast_possible_truncation)]
+    let length = value.len().min(usize::from(u16::MAX)) as u16;
+    out.extend_from_slice(&length.to_be_bytes());
+    out.extend_from_slice(&value.as_bytes()[..usize::from(length)]);
+}
+
+fn mqtt_packet(first_byte: u8, body: &[u8]) -> Vec<u8> {
+    let mut packet = vec![first_byte];
+    packet.extend(mqtt_remaining_length(body.len()));
+    packet.extend_from_slice(body);
+    packet
+}
+
+fn mqtt_connect_packet(client_id: &str, username: Option<&str>, password: Option<&str>) -> Vec<u8> {
+    let mut body = Vec::new();
+    mqtt_string(&mut body, "MQTT");
+    body.push(4); // protocol level 3.1.1
+    let mut flags = 0x02; // clean session
+    if username.is_some() {
+        flags |= 0x80;
+        if password.is_some() {
+            flags |= 0x40;
+        }
+    }
+    body.push(flags);
+    body.extend_from_slice(&30u16.to_be_bytes()); // keep-alive seconds
+    mqtt_string(&mut body, client_id);
+    if let Some(username) = username {
+        mqtt_string(&mut body, username);
+        if let Some(password) = password {
+            mqtt_string(&mut body, password);
+        }
+    }
+    mqtt_packet(0x10, &body)
+}
+
+fn mqtt_subscribe_packet(packet_id: u16, topics: &[String]) -> Vec<u8> {
+    let mut body = packet_id.to_be_bytes().to_vec();
+    for topic in topics {
+        mqtt_string(&mut body, topic);
+        body.push(0); // QoS 0
+    }
+    mqtt_packet(0x82, &body)
+}
+
+async fn mqtt_read_packet(stream: &mut TcpStream) -> anyhow::Result<(u8, Vec<u8>)> {
+    let first = stream.read_u8().await?;
+    let mut length = 0usize;
+    let mut multiplier = 1usize;
+    for _ in 0..4 {
+        let byte = stream.read_u8().await?;
+        length += usize::from(byte & 0x7f) * multiplier;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        multiplier *= 128;
+    }
+    if length > MAX_MQTT_PACKET {
+        anyhow::bail!("MQTT packet too large ({length} bytes)");
+    }
+    let mut body = vec![0u8; length];
+    stream.read_exact(&mut body).await?;
+    Ok((first, body))
+}
+
+/// Split a PUBLISH body into (topic, payload).
+fn mqtt_parse_publish(first_byte: u8, body: &[u8]) -> Option<(String, String)> {
+    let topic_len = usize::from(u16::from_be_bytes([*body.first()?, *body.get(1)?]));
+    let topic = std::str::from_utf8(body.get(2..2 + topic_len)?).ok()?;
+    let qos = (first_byte >> 1) & 0x03;
+    let payload_start = 2 + topic_len + if qos > 0 { 2 } else { 0 };
+    let payload = body.get(payload_start..)?;
+    Some((
+        topic.to_string(),
+        String::from_utf8_lossy(payload).into_owned(),
+    ))
+}
+
+/// Subscribe to `topics` and return the first payload seen on each
+/// (retained messages arrive immediately after SUBACK).
+async fn mqtt_read_topics(
+    broker: &str,
+    username: Option<&str>,
+    password: Option<&str>,
+    topics: &[String],
+) -> anyhow::Result<HashMap<String, String>> {
+    let mut stream = TcpStream::connect(broker)
+        .await
+        .with_context(|| format!("Failed to connect to MQTT broker {broker}"))?;
+    let client_id = format!(
+        "zeroclaw-energy-{}",
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    stream
+        .write_all(&mqtt_connect_packet(&client_id, username, password))
+        .await?;
+    let (kind, body) = mqtt_read_packet(&mut stream).await?;
+    if kind != 0x20 || body.len() < 2 {
+        anyhow::bail!("Unexpected MQTT response to CONNECT");
+    }
+    if body[1] != 0 {
+        anyhow::bail!("MQTT broker refused connection (code {})", body[1]);
+    }
+    stream.write_all(&mqtt_subscribe_packet(1, topics)).await?;
+
+    let mut payloads = HashMap::new();
+    while payloads.len() < topics.len() {
+        let (first, body) = mqtt_read_packet(&mut stream).await?;
+        if first >> 4 != 3 {
+            continue;
+        }
+        if let Some((topic, payload)) = mqtt_parse_publish(first, &body) {
+            if topics.contains(&topic) {
+                payloads.entry(topic).or_insert(payload);
+            }
+        }
+    }
+    let _ = stream.write_all(&[0xE0, 0x00]).await;
+    Ok(payloads)
+}
+
+#[async_trait]
+impl Tool for EnergyTool {
+    fn name(&self) -> &str {
+        "energy"
+    }
+
+    fn description(&self) -> &str {
+        "Read current solar production, household consumption, and grid import/export \
+         (watts; export_w > 0 means surplus going to the grid) plus today's totals in kWh \
+         from the configured inverter and smart meter. Returns JSON."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {}
+        })
+    }
+
+    async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if self.config.inverter.is_none() && self.config.meter.is_none() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("No [energy.inverter] or [energy.meter] configured".into()),
+            });
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let mut errors = Vec::new();
+        let inverter = match &self.config.inverter {
+            Some(inverter) => match self.read_inverter(inverter).await {
+                Ok(reading) => Some(reading),
+                Err(e) => {
+                    errors.push(format!("inverter: {e}"));
+                    None
+                }
+            },
+            None => None,
+        };
+        let meter = match &self.config.meter {
+            Some(meter) => match self.read_meter(meter).await {
+                Ok(reading) => Some(reading),
+                Err(e) => {
+                    errors.push(format!("meter: {e}"));
+                    None
+                }
+            },
+            None => None,
+        };
+        if inverter.is_none() && meter.is_none() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(errors.join("; ")),
+            });
+        }
+
+        let mut reading = combine(inverter, meter);
+        self.apply_daily_baseline(&mut reading).await;
+        let mut output = serde_json::to_value(&reading)?;
+        if !errors.is_empty() {
+            output["errors"] = json!(errors);
+        }
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&output)?,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fronius_power_flow() {
+        let body = json!({"Body": {"Data": {"Site": {
+            "P_PV": 3120.5, "P_Load": -870.2, "P_Grid": -2250.3, "E_Day": 12345.0, "E_Total": 9_876_543.0
+        }}}});
+        let reading = combine(Some(parse_fronius_power_flow(&body)), None);
+        assert_eq!(reading.production_w, Some(3120.5));
+        assert_eq!(reading.consumption_w, Some(870.2));
+        assert_eq!(reading.export_w, Some(2250.3));
+        assert_eq!(reading.production_today_kwh, Some(12.345));
+
+        // At night P_PV is null.
+        let night =
+            json!({"Body": {"Data": {"Site": {"P_PV": null, "P_Load": -400.0, "P_Grid": 400.0}}}});
+        let reading = combine(Some(parse_fronius_power_flow(&night)), None);
+        assert_eq!(reading.production_w, Some(0.0));
+        assert_eq!(reading.export_w, Some(0.0));
+    }
+
+    #[test]
+    fn parses_solaredge_export_direction() {
+        let flow = json!({"siteCurrentPowerFlow": {
+            "unit": "kW",
+            "connections": [{"from": "PV", "to": "Load"}, {"from": "LOAD", "to": "Grid"}],
+            "GRID": {"status": "Active", "currentPower": 1.5},
+            "LOAD": {"status": "Active", "currentPower": 0.8},
+            "PV": {"status": "Active", "currentPower": 2.3}
+        }});
+        let overview = json!({"overview": {"lastDayData": {"energy": 9870.0}}});
+        let reading = combine(Some(parse_solaredge(&flow, &overview)), None);
+        assert_eq!(reading.grid_w, Some(-1500.0));
+        assert_eq!(reading.export_w, Some(1500.0));
+        assert_eq!(reading.production_today_kwh, Some(9.87));
+    }
+
+    #[test]
+    fn meter_overrides_grid_and_derives_consumption() {
+        let inverter = EnergyReading {
+            production_w: Some(2000.0),
+            sources: vec!["sunspec".into()],
+            ..EnergyReading::default()
+        };
+        let meter = EnergyReading {
+            grid_w: Some(-1200.0),
+            sources: vec!["mqtt".into()],
+            ..EnergyReading::default()
+        };
+        let reading = combine(Some(inverter), Some(meter));
+        assert_eq!(reading.consumption_w, Some(800.0));
+        assert_eq!(reading.export_w, Some(1200.0));
+        assert_eq!(reading.sources, vec!["sunspec", "mqtt"]);
+    }
+
+    #[test]
+    fn decodes_sunspec_inverter_registers() {
+        let mut registers = vec![0u16; 52];
+        registers[0] = 103;
+        registers[1] = 50;
+        registers[14] = 4567;
+        registers[15] = 0xFFFF; // W_SF = -1
+        registers[24] = 0x0001;
+        registers[25] = 0x86A0; // 100000 Wh
+        registers[26] = 0;
+        let reading = parse_sunspec_inverter(&registers).unwrap();
+        assert_eq!(reading.production_w, Some(456.7));
+        assert_eq!(reading.production_lifetime_kwh, Some(100.0));
+        assert!(parse_sunspec_inverter(&registers[..10]).is_err());
+    }
+
+    #[test]
+    fn mqtt_packets_are_encoded() {
+        assert_eq!(mqtt_remaining_length(0), vec![0]);
+        assert_eq!(mqtt_remaining_length(321), vec![0xC1, 0x02]);
+
+        let connect = mqtt_connect_packet("c1", Some("u"), Some("p"));
+        assert_eq!(connect[0], 0x10);
+        assert_eq!(&connect[2..8], &[0, 4, b'M', b'Q', b'T', b'T']);
+        assert_eq!(connect[9], 0xC2);
+
+        let subscribe = mqtt_subscribe_packet(1, &["a/b".to_string()]);
+        assert_eq!(subscribe, vec![0x82, 8, 0, 1, 0, 3, b'a', b'/', b'b', 0]);
+
+        let publish_body = [0, 3, b'a', b'/', b'b', b'4', b'2'];
+        assert_eq!(
+            mqtt_parse_publish(0x31, &publish_body),
+            Some(("a/b".to_string(), "42".to_string()))
+        );
+    }
+
+    #[test]
+    fn payload_numbers_follow_json_paths() {
+        assert_eq!(payload_number(" -350 ", None), Some(-350.0));
+        assert_eq!(
+            payload_number(
+                r#"{"SML":{"Power_curr":512,"Total_in":"1234.5"}}"#,
+                Some("SML.Total_in")
+            ),
+            Some(1234.5)
+        );
+        assert_eq!(payload_number("{}", Some("missing")), None);
+        assert_eq!(
+            with_default_port("mqtt://broker.lan", 1883),
+            "broker.lan:1883"
+        );
+        assert_eq!(with_default_port("10.0.0.5:1502", 502), "10.0.0.5:1502");
+    }
+}
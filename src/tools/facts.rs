@@ -0,0 +1,302 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{FactsConfig, LocaleConfig};
+use crate::facts::{self, Fact};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::json;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+/// Confidence of facts recorded without one.
+const DEFAULT_CONFIDENCE: f64 = 0.8;
+
+/// Read and write the structured fact store (`workspace/facts/facts.db`).
+pub struct FactsTool {
+    config: FactsConfig,
+    locale: LocaleConfig,
+    security: Arc<SecurityPolicy>,
+}
+
+impl FactsTool {
+    pub fn new(config: FactsConfig, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            config,
+            locale: LocaleConfig::default(),
+            security,
+        }
+    }
+
+    /// Show observation and expiry times in the `[locale]` conventions.
+    pub fn with_locale(mut self, locale: LocaleConfig) -> Self {
+        self.locale = locale;
+        self
+    }
+}
+
+#[async_trait]
+impl Tool for FactsTool {
+    fn name(&self) -> &str {
+        "facts"
+    }
+
+    fn description(&self) -> &str {
+        "Structured facts that go stale: IP addresses, prices, versions, statuses. action 'set' \
+         stores subject/predicate/value with the tool that produced it, a confidence and a \
+         lifetime (prefer this over memory for such data, e.g. from briefings); 'get' shows the \
+         facts of a subject including expired ones; 'list' shows all facts; 'forget' deletes. \
+         Current facts about subjects the user mentions are added to the prompt automatically."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["set", "get", "list", "forget"],
+                    "description": "set, get, list (default) or forget"
+                },
+                "subject": {
+                    "type": "string",
+                    "description": "What the fact is about, e.g. 'nas' or 'bitcoin'; required for set, get and forget"
+                },
+                "predicate": {
+                    "type": "string",
+                    "description": "Property, e.g. 'ip address' or 'price usd'; required for set"
+                },
+                "value": {
+                    "type": "string",
+                    "description": "set: the current value"
+                },
+                "source": {
+                    "type": "string",
+                    "description": "set: tool or flow the value came from, e.g. 'web_search' or 'morning_briefing'"
+                },
+                "source_ref": {
+                    "type": "string",
+                    "description": "set: URL, file or record the value was read from"
+                },
+                "confidence": {
+                    "type": "number",
+                    "description": "set: 0-1, default 0.8"
+                },
+                "ttl_hours": {
+                    "type": "integer",
+                    "description": "set: hours until the value is stale ([facts] default_ttl_hours when omitted; 0 = never)"
+                }
+            }
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let failure = |error: String| -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            })
+        };
+        let text = |key: &str| {
+            args.get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(String::from)
+        };
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list");
+        if matches!(action, "set" | "forget") {
+            if let Err(error) = self
+                .security
+                .enforce_tool_operation(ToolOperation::Act, "facts")
+            {
+                return failure(error);
+            }
+        }
+        let workspace_dir = self.security.workspace_dir.clone();
+        let subject = text("subject");
+        let predicate = text("predicate");
+        let now = Utc::now();
+        let mut output = String::new();
+
+        match action {
+            "set" => {
+                let (Some(subject), Some(predicate), Some(value)) =
+                    (subject, predicate, text("value"))
+                else {
+                    return failure("'set' needs 'subject', 'predicate' and 'value'".into());
+                };
+                let ttl_hours = args.get("ttl_hours").and_then(serde_json::Value::as_u64);
+                let fact = Fact {
+                    subject: facts::normalize(&subject),
+                    predicate: facts::normalize(&predicate),
+                    value,
+                    source: text("source").unwrap_or_else(|| "agent".into()),
+                    source_ref: text("source_ref"),
+                    confidence: args
+                        .get("confidence")
+                        .and_then(serde_json::Value::as_f64)
+                        .unwrap_or(DEFAULT_CONFIDENCE),
+                    observed_at: now,
+                    expires_at: facts::expiry(&self.config, ttl_hours, now),
+                };
+                let stored = fact.clone();
+                let recorded =
+                    tokio::task::spawn_blocking(move || facts::record(&workspace_dir, &stored))
+                        .await?;
+                match recorded {
+                    Ok(previous) => {
+                        let _ = write!(output, "Stored {}", fact.describe(now, &self.locale));
+                        if let Some(previous) = previous {
+                            let _ = write!(output, "\nReplaced previous value: {previous}");
+                        }
+                    }
+                    Err(e) => return failure(e.to_string()),
+                }
+            }
+            "get" | "list" => {
+                if action == "get" && subject.is_none() {
+                    return failure("Missing 'subject' parameter".into());
+                }
+                let found = tokio::task::spawn_blocking(move || {
+                    facts::query(&workspace_dir, subject.as_deref(), predicate.as_deref())
+                })
+                .await??;
+                if found.is_empty() {
+                    output.push_str("No matching facts");
+                }
+                for fact in &found {
+                    let _ = writeln!(output, "{}", fact.describe(now, &self.locale));
+                }
+            }
+            "forget" => {
+                let Some(subject) = subject else {
+                    return failure("Missing 'subject' parameter".into());
+                };
+                let removed = tokio::task::spawn_blocking(move || {
+                    facts::forget(&workspace_dir, &subject, predicate.as_deref())
+                })
+                .await??;
+                let _ = write!(
+                    output,
+                    "Removed {removed} fact{}",
+                    if removed == 1 { "" } else { "s" }
+                );
+            }
+            other => return failure(format!("Unknown action '{other}'")),
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_security(tmp: &TempDir, autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn sets_and_gets_facts_with_provenance() {
+        let tmp = TempDir::new().unwrap();
+        let tool = FactsTool::new(
+            FactsConfig::default(),
+            test_security(&tmp, AutonomyLevel::Supervised),
+        );
+        let result = tool
+            .execute(json!({
+                "action": "set",
+                "subject": "Home Assistant",
+                "predicate": "latest version",
+                "value": "2026.10.1",
+                "source": "web_search",
+                "source_ref": "https://www.home-assistant.io/blog/",
+                "ttl_hours": 24
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains(
+            "home assistant latest version: 2026.10.1 (source web_search https://www.home-assistant.io/blog/, confidence 0.80"
+        ));
+        assert!(result.output.contains("valid until"));
+
+        let result = tool
+            .execute(json!({"action": "set", "subject": "home assistant", "predicate": "latest version", "value": "2026.10.2", "ttl_hours": 0}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("Replaced previous value: 2026.10.1"));
+        assert!(!result.output.contains("valid until"));
+
+        let result = tool
+            .execute(json!({"action": "get", "subject": "home assistant"}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("2026.10.2 (source agent"));
+    }
+
+    #[tokio::test]
+    async fn rejects_incomplete_requests() {
+        let tmp = TempDir::new().unwrap();
+        let tool = FactsTool::new(
+            FactsConfig::default(),
+            test_security(&tmp, AutonomyLevel::Supervised),
+        );
+        let result = tool
+            .execute(json!({"action": "set", "subject": "nas", "value": "x"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let result = tool.execute(json!({"action": "get"})).await.unwrap();
+        assert_eq!(result.error.as_deref(), Some("Missing 'subject' parameter"));
+        let result = tool
+            .execute(json!({"action": "forget", "subject": "nas"}))
+            .await
+            .unwrap();
+        assert_eq!(result.output, "Removed 0 facts");
+    }
+
+    #[tokio::test]
+    async fn writes_blocked_in_readonly_mode() {
+        let tmp = TempDir::new().unwrap();
+        let tool = FactsTool::new(
+            FactsConfig::default(),
+            test_security(&tmp, AutonomyLevel::ReadOnly),
+        );
+        let result = tool
+            .execute(
+                json!({"action": "set", "subject": "nas", "predicate": "ip", "value": "10.0.0.2"}),
+            )
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+        let result = tool
+            .execute(json!({"action": "forget", "subject": "nas"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let result = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert!(result.success);
+    }
+}
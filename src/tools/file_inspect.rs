@@ -0,0 +1,325 @@
+use super::image_info::ImageInfoTool;
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+
+/// Leading bytes used for MIME, encoding, and image header sniffing.
+const SNIFF_BYTES: usize = 64 * 1024;
+const HASH_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Report checksum, size, MIME type, image dimensions, and text encoding for a workspace file.
+pub struct FileInspectTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl FileInspectTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+/// Identify common formats by magic bytes; `None` means "no signature matched".
+fn sniff_mime(bytes: &[u8]) -> Option<&'static str> {
+    match ImageInfoTool::detect_format(bytes) {
+        "png" => return Some("image/png"),
+        "jpeg" => return Some("image/jpeg"),
+        "gif" => return Some("image/gif"),
+        "webp" => return Some("image/webp"),
+        "bmp" => return Some("image/bmp"),
+        _ => {}
+    }
+
+    let signatures: &[(&[u8], &str)] = &[
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1F\x8B", "application/gzip"),
+        (b"7z\xBC\xAF\x27\x1C", "application/x-7z-compressed"),
+        (b"\xFD7zXZ\x00", "application/x-xz"),
+        (b"BZh", "application/x-bzip2"),
+        (b"\x7FELF", "application/x-executable"),
+        (b"SQLite format 3\x00", "application/vnd.sqlite3"),
+        (b"ID3", "audio/mpeg"),
+        (b"OggS", "audio/ogg"),
+        (b"fLaC", "audio/flac"),
+        (b"\x1A\x45\xDF\xA3", "video/webm"),
+    ];
+    if let Some((_, mime)) = signatures.iter().find(|(sig, _)| bytes.starts_with(sig)) {
+        return Some(mime);
+    }
+
+    if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WAVE" {
+        return Some("audio/wav");
+    }
+    if bytes.len() >= 12 && &bytes[4..8] == b"ftyp" {
+        return Some("video/mp4");
+    }
+    if bytes.len() >= 262 && &bytes[257..262] == b"ustar" {
+        return Some("application/x-tar");
+    }
+    None
+}
+
+/// Classify the text encoding of a leading sample.
+fn detect_encoding(sample: &[u8], truncated: bool) -> &'static str {
+    if sample.starts_with(b"\xEF\xBB\xBF") {
+        return "utf-8 (bom)";
+    }
+    if sample.starts_with(b"\xFF\xFE\x00\x00") {
+        return "utf-32le (bom)";
+    }
+    if sample.starts_with(b"\x00\x00\xFE\xFF") {
+        return "utf-32be (bom)";
+    }
+    if sample.starts_with(b"\xFF\xFE") {
+        return "utf-16le (bom)";
+    }
+    if sample.starts_with(b"\xFE\xFF") {
+        return "utf-16be (bom)";
+    }
+    if sample.contains(&0) {
+        return "binary";
+    }
+    if sample.is_ascii() {
+        return "ascii";
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => "utf-8",
+        // A multi-byte sequence cut off by the sample boundary is still UTF-8.
+        Err(e) if truncated && e.error_len().is_none() => "utf-8",
+        Err(_) => "8-bit (not utf-8; likely latin-1/windows-1252)",
+    }
+}
+
+#[async_trait]
+impl Tool for FileInspectTool {
+    fn name(&self) -> &str {
+        "file_inspect"
+    }
+
+    fn description(&self) -> &str {
+        "Inspect a workspace file: SHA-256 checksum, size, MIME type (content sniffing), image \
+         dimensions, and text encoding. Pass expected_sha256 to verify a download."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the file. Relative paths resolve from workspace; outside paths require policy allowlist."
+                },
+                "expected_sha256": {
+                    "type": "string",
+                    "description": "Optional hex SHA-256 to verify against; mismatch fails the call"
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+        let expected = args
+            .get("expected_sha256")
+            .and_then(|v| v.as_str())
+            .map(|v| v.trim().to_ascii_lowercase())
+            .filter(|v| !v.is_empty());
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Path not allowed by security policy: {path}")),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+        let resolved = match tokio::fs::canonicalize(&full_path).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve file path: {e}")),
+                });
+            }
+        };
+
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(self.security.resolved_path_violation_message(&resolved)),
+            });
+        }
+
+        let metadata = tokio::fs::metadata(&resolved).await?;
+        if !metadata.is_file() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Not a regular file: {path}")),
+            });
+        }
+
+        // Single pass: hash everything, keep the leading bytes for sniffing.
+        let mut file = tokio::fs::File::open(&resolved).await?;
+        let mut hasher = Sha256::new();
+        let mut sample: Vec<u8> = Vec::with_capacity(SNIFF_BYTES);
+        let mut buf = vec![0u8; HASH_CHUNK_BYTES];
+        let mut size: u64 = 0;
+        loop {
+            let n = file.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            size += n as u64;
+            if sample.len() < SNIFF_BYTES {
+                let take = n.min(SNIFF_BYTES - sample.len());
+                sample.extend_from_slice(&buf[..take]);
+            }
+        }
+        let sha256 = hex::encode(hasher.finalize());
+        let truncated = size > sample.len() as u64;
+
+        let encoding = detect_encoding(&sample, truncated);
+        let mime = sniff_mime(&sample).map_or_else(
+            || {
+                if encoding == "binary" {
+                    "application/octet-stream".to_string()
+                } else {
+                    mime_guess::from_path(path)
+                        .first_raw()
+                        .unwrap_or("text/plain")
+                        .to_string()
+                }
+            },
+            str::to_string,
+        );
+
+        let mut output = format!(
+            "File: {path}\nSize: {size} bytes\nSHA-256: {sha256}\nMIME: {mime}\nEncoding: {encoding}"
+        );
+        let image_format = ImageInfoTool::detect_format(&sample);
+        if let Some((w, h)) = ImageInfoTool::extract_dimensions(&sample, image_format) {
+            let _ = write!(output, "\nDimensions: {w}x{h}");
+        }
+
+        match expected {
+            Some(expected) if expected != sha256 => Ok(ToolResult {
+                success: false,
+                output,
+                error: Some(format!(
+                    "SHA-256 mismatch: expected {expected}, got {sha256}"
+                )),
+            }),
+            Some(_) => {
+                output.push_str("\nChecksum: verified");
+                Ok(ToolResult {
+                    success: true,
+                    output,
+                    error: None,
+                })
+            }
+            None => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_security(workspace: std::path::PathBuf) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[test]
+    fn sniffs_common_signatures() {
+        assert_eq!(sniff_mime(b"%PDF-1.7 ..."), Some("application/pdf"));
+        assert_eq!(sniff_mime(b"PK\x03\x04rest"), Some("application/zip"));
+        assert_eq!(sniff_mime(b"\x89PNG\r\n\x1a\n"), Some("image/png"));
+        assert_eq!(sniff_mime(b"plain words"), None);
+    }
+
+    #[test]
+    fn detects_text_encodings() {
+        assert_eq!(detect_encoding(b"hello", false), "ascii");
+        assert_eq!(detect_encoding("héllo".as_bytes(), false), "utf-8");
+        assert_eq!(detect_encoding(b"\xEF\xBB\xBFhi", false), "utf-8 (bom)");
+        assert_eq!(detect_encoding(b"\xFF\xFEh\x00", false), "utf-16le (bom)");
+        assert!(detect_encoding(b"caf\xE9 au lait", false).starts_with("8-bit"));
+        assert_eq!(detect_encoding(b"a\x00b", false), "binary");
+        // "é" split by the sample boundary
+        assert_eq!(detect_encoding(b"abc\xC3", true), "utf-8");
+    }
+
+    #[tokio::test]
+    async fn inspects_file_and_verifies_checksum() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::write(tmp.path().join("notes.txt"), "hello\n")
+            .await
+            .unwrap();
+        let tool = FileInspectTool::new(test_security(tmp.path().to_path_buf()));
+
+        let result = tool.execute(json!({"path": "notes.txt"})).await.unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("Size: 6 bytes"));
+        assert!(result
+            .output
+            .contains("5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03"));
+        assert!(result.output.contains("MIME: text/plain"));
+        assert!(result.output.contains("Encoding: ascii"));
+
+        let mismatch = tool
+            .execute(json!({"path": "notes.txt", "expected_sha256": "00"}))
+            .await
+            .unwrap();
+        assert!(!mismatch.success);
+        assert!(mismatch.error.unwrap().contains("SHA-256 mismatch"));
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_workspace() {
+        let tmp = TempDir::new().unwrap();
+        let tool = FileInspectTool::new(test_security(tmp.path().to_path_buf()));
+        let result = tool.execute(json!({"path": "/etc/passwd"})).await.unwrap();
+        assert!(!result.success);
+    }
+}
@@ -0,0 +1,310 @@
+//! Idempotency keys for write tools.
+//!
+//! Tools that post to the outside world (notifications, SMS) accept an
+//! optional `idempotency_key` argument. The first call with a key claims it in
+//! `{workspace}/state/idempotency.db`; later calls with the same key inside
+//! [`DEDUPE_WINDOW_SECS`] return the recorded result instead of sending again.
+//! A failed or cancelled send releases the key so a retry can go through, and
+//! an in-flight claim older than [`CLAIM_LEASE_SECS`] (the process died
+//! mid-send) is treated as free.
+
+use super::traits::ToolResult;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use serde_json::{json, Value};
+use std::future::Future;
+use std::path::Path;
+
+/// How long a key suppresses repeats.
+pub const DEDUPE_WINDOW_SECS: i64 = 24 * 60 * 60;
+/// How long an unfinished claim blocks repeats; every send path gives up
+/// well before this.
+pub const CLAIM_LEASE_SECS: i64 = 10 * 60;
+const MAX_KEY_CHARS: usize = 200;
+
+/// JSON schema fragment for the `idempotency_key` argument.
+pub fn schema_property() -> Value {
+    json!({
+        "type": "string",
+        "description": "Optional unique key for this send; repeating a call with the same key within 24h returns the first result instead of sending twice"
+    })
+}
+
+enum Claim {
+    Fresh,
+    /// Already claimed; carries the recorded output, or `None` while the
+    /// first call is still in flight.
+    Duplicate(Option<String>),
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let dir = workspace_dir.join("state");
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create state directory: {}", dir.display()))?;
+    let db_path = dir.join("idempotency.db");
+    let conn = Connection::open(&db_path)
+        .with_context(|| format!("Failed to open idempotency DB: {}", db_path.display()))?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS idempotency_keys (
+            tool       TEXT NOT NULL,
+            key        TEXT NOT NULL,
+            claimed_at INTEGER NOT NULL,
+            output     TEXT,
+            PRIMARY KEY (tool, key)
+        );",
+    )
+    .context("Failed to initialize idempotency schema")?;
+    f(&conn)
+}
+
+fn claim(workspace_dir: &Path, tool: &str, key: &str) -> Result<Claim> {
+    let now = Utc::now().timestamp();
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "DELETE FROM idempotency_keys
+             WHERE claimed_at < ?1 OR (output IS NULL AND claimed_at < ?2)",
+            params![now - DEDUPE_WINDOW_SECS, now - CLAIM_LEASE_SECS],
+        )?;
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO idempotency_keys (tool, key, claimed_at) VALUES (?1, ?2, ?3)",
+            params![tool, key, now],
+        )?;
+        if inserted == 1 {
+            return Ok(Claim::Fresh);
+        }
+        let output: Option<String> = conn.query_row(
+            "SELECT output FROM idempotency_keys WHERE tool = ?1 AND key = ?2",
+            params![tool, key],
+            |row| row.get(0),
+        )?;
+        Ok(Claim::Duplicate(output))
+    })
+}
+
+fn complete(workspace_dir: &Path, tool: &str, key: &str, output: &str) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "UPDATE idempotency_keys SET output = ?3 WHERE tool = ?1 AND key = ?2",
+            params![tool, key, output],
+        )?;
+        Ok(())
+    })
+}
+
+fn release(workspace_dir: &Path, tool: &str, key: &str) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "DELETE FROM idempotency_keys WHERE tool = ?1 AND key = ?2",
+            params![tool, key],
+        )?;
+        Ok(())
+    })
+}
+
+/// Releases a claim when the send future is dropped before it finishes
+/// (tool-loop timeout or cancellation).
+struct ClaimGuard<'a> {
+    workspace_dir: &'a Path,
+    tool: &'a str,
+    key: &'a str,
+    armed: bool,
+}
+
+impl Drop for ClaimGuard<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            if let Err(e) = release(self.workspace_dir, self.tool, self.key) {
+                tracing::warn!("Failed to release idempotency key for {}: {e}", self.tool);
+            }
+        }
+    }
+}
+
+/// Run `send` unless `args.idempotency_key` was already used by `tool` inside
+/// the dedupe window. Calls without a key always run.
+pub async fn run_once<F, Fut>(
+    workspace_dir: &Path,
+    tool: &str,
+    args: &Value,
+    send: F,
+) -> Result<ToolResult>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<ToolResult>>,
+{
+    let Some(key) = args
+        .get("idempotency_key")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+    else {
+        return send().await;
+    };
+    if key.chars().count() > MAX_KEY_CHARS {
+        return Ok(ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(format!(
+                "idempotency_key is too long (max {MAX_KEY_CHARS} chars)"
+            )),
+        });
+    }
+
+    match claim(workspace_dir, tool, key)? {
+        Claim::Duplicate(previous) => {
+            let previous = previous.unwrap_or_else(|| "still in progress".into());
+            return Ok(ToolResult {
+                success: true,
+                output: format!("Skipped: idempotency_key '{key}' was already used ({previous})"),
+                error: None,
+            });
+        }
+        Claim::Fresh => {}
+    }
+
+    let mut guard = ClaimGuard {
+        workspace_dir,
+        tool,
+        key,
+        armed: true,
+    };
+    let result = send().await;
+    guard.armed = false;
+    let outcome = match &result {
+        Ok(result) if result.success => complete(workspace_dir, tool, key, &result.output),
+        _ => release(workspace_dir, tool, key),
+    };
+    if let Err(e) = outcome {
+        tracing::warn!("Failed to update idempotency key for {tool}: {e}");
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn ok(output: &str) -> Result<ToolResult> {
+        Ok(ToolResult {
+            success: true,
+            output: output.into(),
+            error: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn repeated_key_sends_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let sends = AtomicUsize::new(0);
+        let args = json!({ "message": "hi", "idempotency_key": "alert-42" });
+
+        for _ in 0..2 {
+            let result = run_once(dir.path(), "notify", &args, || async {
+                sends.fetch_add(1, Ordering::SeqCst);
+                ok("Notification sent to 'phone'")
+            })
+            .await
+            .unwrap();
+            assert!(result.success);
+        }
+        assert_eq!(sends.load(Ordering::SeqCst), 1);
+
+        let other_tool = run_once(dir.path(), "pushover", &args, || async {
+            sends.fetch_add(1, Ordering::SeqCst);
+            ok("sent")
+        })
+        .await
+        .unwrap();
+        assert!(other_tool.success);
+        assert_eq!(sends.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn duplicate_reports_first_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = json!({ "idempotency_key": "k" });
+        run_once(dir.path(), "notify", &args, || async { ok("first") })
+            .await
+            .unwrap();
+        let repeat = run_once(dir.path(), "notify", &args, || async { ok("second") })
+            .await
+            .unwrap();
+        assert!(repeat.output.contains("already used (first)"));
+    }
+
+    #[tokio::test]
+    async fn failed_send_releases_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = json!({ "idempotency_key": "retry-me" });
+        let failed = run_once(dir.path(), "notify", &args, || async {
+            Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("boom".into()),
+            })
+        })
+        .await
+        .unwrap();
+        assert!(!failed.success);
+
+        let retried = run_once(dir.path(), "notify", &args, || async { ok("sent") })
+            .await
+            .unwrap();
+        assert_eq!(retried.output, "sent");
+    }
+
+    #[tokio::test]
+    async fn cancelled_send_releases_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = json!({ "idempotency_key": "cancel-me" });
+        let cancelled = tokio::time::timeout(
+            std::time::Duration::from_millis(20),
+            run_once(dir.path(), "notify", &args, || {
+                std::future::pending::<Result<ToolResult>>()
+            }),
+        )
+        .await;
+        assert!(cancelled.is_err());
+
+        let retried = run_once(dir.path(), "notify", &args, || async { ok("sent") })
+            .await
+            .unwrap();
+        assert_eq!(retried.output, "sent");
+    }
+
+    #[tokio::test]
+    async fn stale_in_flight_claim_is_free() {
+        let dir = tempfile::tempdir().unwrap();
+        with_connection(dir.path(), |conn| {
+            conn.execute(
+                "INSERT INTO idempotency_keys (tool, key, claimed_at) VALUES ('notify', 'k', ?1)",
+                params![Utc::now().timestamp() - CLAIM_LEASE_SECS - 1],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        let args = json!({ "idempotency_key": "k" });
+        let result = run_once(dir.path(), "notify", &args, || async { ok("sent") })
+            .await
+            .unwrap();
+        assert_eq!(result.output, "sent");
+    }
+
+    #[tokio::test]
+    async fn calls_without_key_always_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let sends = AtomicUsize::new(0);
+        for _ in 0..2 {
+            run_once(dir.path(), "notify", &json!({}), || async {
+                sends.fetch_add(1, Ordering::SeqCst);
+                ok("sent")
+            })
+            .await
+            .unwrap();
+        }
+        assert_eq!(sends.load(Ordering::SeqCst), 2);
+    }
+}
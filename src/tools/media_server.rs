@@ -0,0 +1,480 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::MediaServerConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::sync::Arc;
+
+const DEFAULT_LIMIT: u64 = 20;
+const MAX_LIMIT: u64 = 100;
+
+fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+/// A library item normalized across Jellyfin and Plex.
+#[derive(Debug, Clone, PartialEq)]
+struct MediaItem {
+    title: String,
+    kind: String,
+    year: Option<i64>,
+    added: Option<DateTime<Utc>>,
+}
+
+/// A playback session normalized across Jellyfin and Plex.
+#[derive(Debug, Clone, PartialEq)]
+struct Session {
+    user: String,
+    title: String,
+    device: String,
+    paused: bool,
+    progress_pct: Option<f64>,
+}
+
+/// Recently added items, now-playing sessions, and library search on a
+/// Jellyfin or Plex server.
+pub struct MediaServerTool {
+    security: Arc<SecurityPolicy>,
+    config: MediaServerConfig,
+}
+
+impl MediaServerTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: MediaServerConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn is_plex(&self) -> bool {
+        self.config.backend.eq_ignore_ascii_case("plex")
+    }
+
+    async fn get(&self, path: &str, query: &[(&str, String)]) -> anyhow::Result<Value> {
+        let base = self.config.url.trim().trim_end_matches('/');
+        if base.is_empty() {
+            anyhow::bail!("media_server.url is not configured");
+        }
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.media_server",
+            self.config.timeout_secs.max(1),
+            10,
+        );
+        let mut request = client
+            .get(format!("{base}{path}"))
+            .query(query)
+            .header("Accept", "application/json");
+        if let Some(key) = &self.config.api_key {
+            request = if self.is_plex() {
+                request.header("X-Plex-Token", key)
+            } else {
+                request.header("X-Emby-Token", key)
+            };
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("GET {path} returned {status}");
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn recent(&self, limit: u64) -> anyhow::Result<Vec<MediaItem>> {
+        if self.is_plex() {
+            let body = self
+                .get(
+                    "/library/recentlyAdded",
+                    &[
+                        ("X-Plex-Container-Start", "0".into()),
+                        ("X-Plex-Container-Size", limit.to_string()),
+                    ],
+                )
+                .await?;
+            Ok(parse_plex_items(&body))
+        } else {
+            let body = self
+                .get(
+                    "/Items",
+                    &[
+                        ("Recursive", "true".into()),
+                        ("SortBy", "DateCreated".into()),
+                        ("SortOrder", "Descending".into()),
+                        ("IncludeItemTypes", "Movie,Episode,Series,MusicAlbum".into()),
+                        ("Fields", "DateCreated,ProductionYear".into()),
+                        ("Limit", limit.to_string()),
+                    ],
+                )
+                .await?;
+            Ok(parse_jellyfin_items(&body))
+        }
+    }
+
+    async fn search(&self, query: &str, limit: u64) -> anyhow::Result<Vec<MediaItem>> {
+        if self.is_plex() {
+            let body = self
+                .get(
+                    "/search",
+                    &[("query", query.to_string()), ("limit", limit.to_string())],
+                )
+                .await?;
+            Ok(parse_plex_items(&body))
+        } else {
+            let body = self
+                .get(
+                    "/Items",
+                    &[
+                        ("Recursive", "true".into()),
+                        ("searchTerm", query.to_string()),
+                        ("IncludeItemTypes", "Movie,Series,Episode,MusicAlbum".into()),
+                        ("Fields", "DateCreated,ProductionYear".into()),
+                        ("Limit", limit.to_string()),
+                    ],
+                )
+                .await?;
+            Ok(parse_jellyfin_items(&body))
+        }
+    }
+
+    async fn sessions(&self) -> anyhow::Result<Vec<Session>> {
+        if self.is_plex() {
+            Ok(parse_plex_sessions(
+                &self.get("/status/sessions", &[]).await?,
+            ))
+        } else {
+            Ok(parse_jellyfin_sessions(&self.get("/Sessions", &[]).await?))
+        }
+    }
+}
+
+fn parse_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Number(n) => DateTime::from_timestamp(n.as_i64()?, 0),
+        Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|d| d.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+fn percent(position: Option<f64>, total: Option<f64>) -> Option<f64> {
+    match (position, total) {
+        (Some(p), Some(t)) if t > 0.0 => Some((p / t * 1000.0).round() / 10.0),
+        _ => None,
+    }
+}
+
+fn jellyfin_title(item: &Value) -> String {
+    let name = item["Name"].as_str().unwrap_or("(untitled)");
+    match (item["SeriesName"].as_str(), item["Type"].as_str()) {
+        (Some(series), Some("Episode")) => match (
+            item["ParentIndexNumber"].as_i64(),
+            item["IndexNumber"].as_i64(),
+        ) {
+            (Some(s), Some(e)) => format!("{series} S{s:02}E{e:02} – {name}"),
+            _ => format!("{series} – {name}"),
+        },
+        _ => match item["AlbumArtist"].as_str() {
+            Some(artist) if item["Type"] == "MusicAlbum" => format!("{artist} – {name}"),
+            _ => name.to_string(),
+        },
+    }
+}
+
+fn parse_jellyfin_items(body: &Value) -> Vec<MediaItem> {
+    body["Items"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|item| MediaItem {
+            title: jellyfin_title(item),
+            kind: item["Type"].as_str().unwrap_or("Item").to_lowercase(),
+            year: item["ProductionYear"].as_i64(),
+            added: parse_timestamp(&item["DateCreated"]),
+        })
+        .collect()
+}
+
+fn parse_jellyfin_sessions(body: &Value) -> Vec<Session> {
+    body.as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter(|s| s["NowPlayingItem"].is_object())
+        .map(|s| {
+            let item = &s["NowPlayingItem"];
+            // Jellyfin reports positions in 100ns ticks.
+            Session {
+                user: s["UserName"].as_str().unwrap_or("?").to_string(),
+                title: jellyfin_title(item),
+                device: s["DeviceName"]
+                    .as_str()
+                    .or_else(|| s["Client"].as_str())
+                    .unwrap_or("?")
+                    .to_string(),
+                paused: s["PlayState"]["IsPaused"].as_bool().unwrap_or(false),
+                progress_pct: percent(
+                    s["PlayState"]["PositionTicks"].as_f64(),
+                    item["RunTimeTicks"].as_f64(),
+                ),
+            }
+        })
+        .collect()
+}
+
+fn plex_title(item: &Value) -> String {
+    let title = item["title"].as_str().unwrap_or("(untitled)");
+    match item["type"].as_str() {
+        Some("episode") => match item["grandparentTitle"].as_str() {
+            Some(show) => match (item["parentIndex"].as_i64(), item["index"].as_i64()) {
+                (Some(s), Some(e)) => format!("{show} S{s:02}E{e:02} – {title}"),
+                _ => format!("{show} – {title}"),
+            },
+            None => title.to_string(),
+        },
+        Some("album" | "season") => match item["parentTitle"].as_str() {
+            Some(parent) => format!("{parent} – {title}"),
+            None => title.to_string(),
+        },
+        _ => title.to_string(),
+    }
+}
+
+fn plex_metadata(body: &Value) -> &[Value] {
+    body["MediaContainer"]["Metadata"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn parse_plex_items(body: &Value) -> Vec<MediaItem> {
+    plex_metadata(body)
+        .iter()
+        .map(|item| MediaItem {
+            title: plex_title(item),
+            kind: item["type"].as_str().unwrap_or("item").to_string(),
+            year: item["year"].as_i64(),
+            added: parse_timestamp(&item["addedAt"]),
+        })
+        .collect()
+}
+
+fn parse_plex_sessions(body: &Value) -> Vec<Session> {
+    plex_metadata(body)
+        .iter()
+        .map(|item| Session {
+            user: item["User"]["title"].as_str().unwrap_or("?").to_string(),
+            title: plex_title(item),
+            device: item["Player"]["title"]
+                .as_str()
+                .or_else(|| item["Player"]["product"].as_str())
+                .unwrap_or("?")
+                .to_string(),
+            paused: item["Player"]["state"] == "paused",
+            progress_pct: percent(item["viewOffset"].as_f64(), item["duration"].as_f64()),
+        })
+        .collect()
+}
+
+fn format_items(items: &[MediaItem]) -> String {
+    let mut out = String::new();
+    for item in items {
+        let _ = write!(out, "- {} ({}", item.title, item.kind);
+        if let Some(year) = item.year {
+            let _ = write!(out, ", {year}");
+        }
+        out.push(')');
+        if let Some(added) = item.added {
+            let _ = write!(out, " — added {}", added.format("%Y-%m-%d"));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn format_sessions(sessions: &[Session]) -> String {
+    let mut out = String::new();
+    for session in sessions {
+        let _ = write!(
+            out,
+            "- {} is {} {} on {}",
+            session.user,
+            if session.paused {
+                "paused on"
+            } else {
+                "watching"
+            },
+            session.title,
+            session.device
+        );
+        if let Some(progress) = session.progress_pct {
+            let _ = write!(out, " ({progress:.0}%)");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[async_trait]
+impl Tool for MediaServerTool {
+    fn name(&self) -> &str {
+        "media_server"
+    }
+
+    fn description(&self) -> &str {
+        "Query the household Jellyfin/Plex media server: list recently added movies and \
+         episodes (optionally within the last N days), show who is watching what right now, \
+         or search the library by title."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["recent", "now_playing", "search"],
+                    "description": "recent = recently added, now_playing = active sessions, search = find by title"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Title to search for (search only)"
+                },
+                "days": {
+                    "type": "integer",
+                    "description": "Only include items added in the last N days (recent only)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum items to return (default: 20, max: 100)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(DEFAULT_LIMIT)
+            .clamp(1, MAX_LIMIT);
+
+        if self.security.is_rate_limited() {
+            return Ok(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        let output = match action {
+            "recent" => {
+                let days = args.get("days").and_then(serde_json::Value::as_i64);
+                let mut items = match self.recent(limit).await {
+                    Ok(items) => items,
+                    Err(e) => return Ok(failure(format!("Media server request failed: {e}"))),
+                };
+                if let Some(days) = days {
+                    let cutoff = Utc::now() - chrono::Duration::days(days.max(0));
+                    items.retain(|item| item.added.is_none_or(|added| added >= cutoff));
+                }
+                if items.is_empty() {
+                    "Nothing new in the library".to_string()
+                } else {
+                    format_items(&items)
+                }
+            }
+            "now_playing" => match self.sessions().await {
+                Ok(sessions) if sessions.is_empty() => "Nothing is playing".to_string(),
+                Ok(sessions) => format_sessions(&sessions),
+                Err(e) => return Ok(failure(format!("Media server request failed: {e}"))),
+            },
+            "search" => {
+                let Some(query) = args
+                    .get("query")
+                    .and_then(|v| v.as_str())
+                    .map(str::trim)
+                    .filter(|q| !q.is_empty())
+                else {
+                    return Ok(failure("search requires a 'query'"));
+                };
+                match self.search(query, limit).await {
+                    Ok(items) if items.is_empty() => format!("No matches for '{query}'"),
+                    Ok(items) => format_items(&items),
+                    Err(e) => return Ok(failure(format!("Media server request failed: {e}"))),
+                }
+            }
+            other => {
+                return Ok(failure(format!(
+                    "Unknown action '{other}'. Use recent, now_playing, or search."
+                )))
+            }
+        };
+
+        Ok(ToolResult {
+            success: true,
+            output: output.trim_end().to_string(),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_jellyfin_items_and_sessions() {
+        let items = json!({"Items": [
+            {"Name": "The Long Night", "Type": "Episode", "SeriesName": "Got", "ParentIndexNumber": 8, "IndexNumber": 3,
+             "DateCreated": "2026-10-12T18:04:11.0000000Z"},
+            {"Name": "Arrival", "Type": "Movie", "ProductionYear": 2016, "DateCreated": "2026-10-10T08:00:00Z"}
+        ]});
+        let parsed = parse_jellyfin_items(&items);
+        assert_eq!(parsed[0].title, "Got S08E03 – The Long Night");
+        assert_eq!(parsed[1].year, Some(2016));
+        assert!(format_items(&parsed).contains("- Arrival (movie, 2016) — added 2026-10-10"));
+
+        let sessions = json!([
+            {"UserName": "alice", "DeviceName": "Living Room TV",
+             "PlayState": {"IsPaused": true, "PositionTicks": 36_000_000_000u64},
+             "NowPlayingItem": {"Name": "Arrival", "Type": "Movie", "RunTimeTicks": 69_600_000_000u64}},
+            {"UserName": "idle", "DeviceName": "Phone"}
+        ]);
+        let parsed = parse_jellyfin_sessions(&sessions);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(
+            format_sessions(&parsed).trim(),
+            "- alice is paused on Arrival on Living Room TV (52%)"
+        );
+    }
+
+    #[test]
+    fn parses_plex_items_and_sessions() {
+        let recent = json!({"MediaContainer": {"Metadata": [
+            {"type": "episode", "title": "Pilot", "grandparentTitle": "Severance", "parentIndex": 1, "index": 1, "addedAt": 1_760_000_000},
+            {"type": "album", "title": "OK Computer", "parentTitle": "Radiohead", "year": 1997},
+            {"type": "movie", "title": "Dune", "year": 2021}
+        ]}});
+        let parsed = parse_plex_items(&recent);
+        assert_eq!(parsed[0].title, "Severance S01E01 – Pilot");
+        assert!(parsed[0].added.is_some());
+        assert_eq!(parsed[1].title, "Radiohead – OK Computer");
+        assert_eq!(parsed[2].title, "Dune");
+
+        let sessions = json!({"MediaContainer": {"Metadata": [
+            {"type": "movie", "title": "Dune", "viewOffset": 1_800_000, "duration": 9_300_000,
+             "User": {"title": "bob"}, "Player": {"title": "Shield", "state": "playing"}}
+        ]}});
+        assert_eq!(
+            format_sessions(&parse_plex_sessions(&sessions)).trim(),
+            "- bob is watching Dune on Shield (19%)"
+        );
+    }
+}
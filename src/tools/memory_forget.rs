@@ -41,6 +41,10 @@ impl Tool for MemoryForgetTool {
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let key = args
             .get("key")
@@ -49,6 +49,10 @@ impl Tool for MemoryStoreTool {
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let key = args
             .get("key")
@@ -0,0 +1,323 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{Config, LocaleConfig};
+use crate::metrics::{self, Anomaly};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+/// Samples shown by `history` before older ones are summarized away.
+const MAX_HISTORY_ROWS: usize = 100;
+
+/// Record metrics and query their history and anomalies.
+pub struct MetricsTool {
+    config: Arc<Config>,
+}
+
+impl MetricsTool {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self { config }
+    }
+
+    /// Push a first (non-repeat) anomaly to `/api/ws`, the notify target and
+    /// the `on_anomaly` automation. The automation runs in the background so
+    /// the recording call is not held up by a full agent turn.
+    async fn alert(&self, anomaly: &Anomaly) -> anyhow::Result<()> {
+        let mut notification =
+            crate::notify::Notification::new(describe(anomaly, &self.config.locale));
+        notification.title = Some(format!("Anomaly in {}", anomaly.metric));
+        notification.priority = 4;
+        notification.tags = vec!["chart_with_upwards_trend".into()];
+        crate::gateway::ws::publish_notification(
+            "anomaly",
+            json!({
+                "metric": anomaly.metric,
+                "at": anomaly.at.to_rfc3339(),
+                "value": anomaly.value,
+                "expected": anomaly.expected,
+                "score": anomaly.score,
+            }),
+        );
+        if let Some(automation) = self.config.metrics.on_anomaly.clone() {
+            let config = Arc::clone(&self.config);
+            tokio::spawn(async move {
+                match Box::pin(crate::cron::scheduler::run_automation_for_event(
+                    &config,
+                    &automation,
+                ))
+                .await
+                {
+                    Ok(run) if run.success => {}
+                    Ok(run) => {
+                        tracing::warn!("Anomaly automation '{automation}' failed: {}", run.output);
+                    }
+                    Err(e) => tracing::warn!("Anomaly automation '{automation}' not run: {e}"),
+                }
+            });
+        }
+        if let Some(target) = &self.config.metrics.notify_target {
+            let target = crate::notify::find_target(&self.config.notify, Some(target))?;
+            crate::notify::send(&self.config.notify, target, &notification).await?;
+        }
+        Ok(())
+    }
+}
+
+fn describe(anomaly: &Anomaly, locale: &LocaleConfig) -> String {
+    format!(
+        "{} = {} at {} is {:.1}σ {} the expected {:.3}",
+        anomaly.metric,
+        anomaly.value,
+        crate::locale::format_datetime(locale, anomaly.at),
+        anomaly.score.abs(),
+        if anomaly.score > 0.0 {
+            "above"
+        } else {
+            "below"
+        },
+        anomaly.expected
+    )
+}
+
+#[async_trait]
+impl Tool for MetricsTool {
+    fn name(&self) -> &str {
+        "metrics"
+    }
+
+    fn description(&self) -> &str {
+        "Local time series with anomaly detection. action 'record' stores a value (e.g. ping \
+         latency, energy use, P&L, uptime ratio) and reports whether it deviates from the \
+         metric's usual level for that hour; 'list' shows all metrics; 'history' shows recent \
+         samples of one metric; 'anomalies' lists detected anomalies."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["record", "list", "history", "anomalies"],
+                    "description": "record, list (default), history or anomalies"
+                },
+                "metric": {
+                    "type": "string",
+                    "description": "Metric name such as 'ping.gateway_ms'; required for record and history"
+                },
+                "value": {
+                    "type": "number",
+                    "description": "record: the sample value"
+                },
+                "hours": {
+                    "type": "integer",
+                    "description": "history/anomalies: how far back to look (default 24 / 168)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let failure = |error: String| -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            })
+        };
+        let workspace_dir = self.config.workspace_dir.clone();
+        let metric = args
+            .get("metric")
+            .and_then(|v| v.as_str())
+            .map(|metric| metric.trim().to_string());
+        let hours = args.get("hours").and_then(serde_json::Value::as_i64);
+        let mut output = String::new();
+
+        match args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list")
+        {
+            "record" => {
+                let Some(metric) = metric else {
+                    return failure("Missing 'metric' parameter".into());
+                };
+                let Some(value) = args.get("value").and_then(serde_json::Value::as_f64) else {
+                    return failure("Missing or non-numeric 'value' parameter".into());
+                };
+                let config = self.config.metrics.clone();
+                let name = metric.clone();
+                let recorded = tokio::task::spawn_blocking(move || {
+                    metrics::record(&workspace_dir, &config, &name, value, Utc::now())
+                })
+                .await?;
+                let anomaly = match recorded {
+                    Ok(anomaly) => anomaly,
+                    Err(e) => return failure(e.to_string()),
+                };
+                let _ = write!(output, "Recorded {metric} = {value}");
+                if let Some(anomaly) = anomaly {
+                    let _ = write!(
+                        output,
+                        "\nANOMALY: {}",
+                        describe(&anomaly, &self.config.locale)
+                    );
+                    if anomaly.repeat {
+                        output.push_str(" (ongoing; already alerted)");
+                    } else if let Err(e) = self.alert(&anomaly).await {
+                        let _ = write!(output, "\nAlert failed: {e}");
+                    }
+                }
+            }
+            "list" => {
+                let summaries =
+                    tokio::task::spawn_blocking(move || metrics::summaries(&workspace_dir))
+                        .await??;
+                if summaries.is_empty() {
+                    output.push_str("No metrics recorded yet");
+                }
+                for summary in summaries {
+                    let _ = writeln!(
+                        output,
+                        "{}: {} (at {}), usual {:.3} ± {:.3}, {} samples",
+                        summary.metric,
+                        summary.last_value,
+                        crate::locale::format_datetime(&self.config.locale, summary.last_at),
+                        summary.mean,
+                        summary.std,
+                        summary.samples
+                    );
+                }
+            }
+            "history" => {
+                let Some(metric) = metric else {
+                    return failure("Missing 'metric' parameter".into());
+                };
+                let since = Utc::now() - Duration::hours(hours.unwrap_or(24).max(1));
+                let name = metric.clone();
+                let samples = tokio::task::spawn_blocking(move || {
+                    metrics::history(&workspace_dir, &name, since)
+                })
+                .await??;
+                if samples.is_empty() {
+                    return failure(format!("No samples of '{metric}' in that window"));
+                }
+                let values: Vec<f64> = samples.iter().map(|(_, value)| *value).collect();
+                let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                #[allow(clippy::cast_precision_loss)]
+                let mean = values.iter().sum::<f64>() / values.len() as f64;
+                let _ = writeln!(
+                    output,
+                    "{metric}: {} samples, min {min}, max {max}, mean {mean:.3}",
+                    samples.len()
+                );
+                let skipped = samples.len().saturating_sub(MAX_HISTORY_ROWS);
+                if skipped > 0 {
+                    let _ = writeln!(output, "… {skipped} older samples");
+                }
+                for (at, value) in &samples[skipped..] {
+                    let _ = writeln!(
+                        output,
+                        "{} {value}",
+                        crate::locale::format_datetime(&self.config.locale, *at)
+                    );
+                }
+            }
+            "anomalies" => {
+                let since = Utc::now() - Duration::hours(hours.unwrap_or(168).max(1));
+                let found = tokio::task::spawn_blocking(move || {
+                    metrics::anomalies(&workspace_dir, since, metric.as_deref())
+                })
+                .await??;
+                let _ = writeln!(
+                    output,
+                    "{} anomal{}",
+                    found.len(),
+                    if found.len() == 1 { "y" } else { "ies" }
+                );
+                for anomaly in &found {
+                    let _ = writeln!(output, "{}", describe(anomaly, &self.config.locale));
+                }
+            }
+            other => return failure(format!("Unknown action '{other}'")),
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tool(tmp: &TempDir) -> MetricsTool {
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.metrics.min_samples = 3;
+        config.metrics.seasonal = false;
+        MetricsTool::new(Arc::new(config))
+    }
+
+    #[tokio::test]
+    async fn records_and_reports_anomalies() {
+        let tmp = TempDir::new().unwrap();
+        let tool = tool(&tmp);
+        for value in [10.0, 11.0, 10.0, 11.0] {
+            let result = tool
+                .execute(json!({"action": "record", "metric": "energy.load_kw", "value": value}))
+                .await
+                .unwrap();
+            assert!(result.success);
+            assert!(!result.output.contains("ANOMALY"));
+        }
+
+        let result = tool
+            .execute(json!({"action": "record", "metric": "energy.load_kw", "value": 40}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("ANOMALY: energy.load_kw = 40"));
+        assert!(result.output.contains("above the expected"));
+
+        let result = tool
+            .execute(json!({"action": "anomalies", "metric": "energy.load_kw"}))
+            .await
+            .unwrap();
+        assert!(result.output.starts_with("1 anomaly"));
+
+        let result = tool
+            .execute(json!({"action": "history", "metric": "energy.load_kw"}))
+            .await
+            .unwrap();
+        assert!(result
+            .output
+            .starts_with("energy.load_kw: 5 samples, min 10, max 40"));
+    }
+
+    #[tokio::test]
+    async fn rejects_bad_input() {
+        let tmp = TempDir::new().unwrap();
+        let tool = tool(&tmp);
+        let result = tool
+            .execute(json!({"action": "record", "metric": "ping ms", "value": 1}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let result = tool
+            .execute(json!({"action": "record", "metric": "ping.ms", "value": "fast"}))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.error.as_deref(),
+            Some("Missing or non-numeric 'value' parameter")
+        );
+    }
+}
@@ -15,10 +15,15 @@
 //! To add a new tool, implement [`Tool`] in a new submodule and register it in
 //! [`all_tools_with_runtime`]. See `AGENTS.md` §7.3 for the full change playbook.
 
+pub mod advisories;
 pub mod browser;
 pub mod browser_open;
+pub mod cert_check;
 pub mod cli_discovery;
+pub mod code_exec;
 pub mod composio;
+pub mod composite;
+pub mod concurrency;
 pub mod content_search;
 pub mod cron_add;
 pub mod cron_list;
@@ -27,7 +32,15 @@ pub mod cron_run;
 pub mod cron_runs;
 pub mod cron_update;
 pub mod delegate;
+pub mod device_inventory;
+pub mod diff_patch;
+pub mod dns;
+pub mod domain_info;
+pub mod downloads;
+pub mod energy;
+pub mod facts;
 pub mod file_edit;
+pub mod file_inspect;
 pub mod file_read;
 pub mod file_write;
 pub mod git_operations;
@@ -36,24 +49,48 @@ pub mod hardware_board_info;
 pub mod hardware_memory_map;
 pub mod hardware_memory_read;
 pub mod http_request;
+pub mod idempotency;
 pub mod image_info;
+pub mod media_server;
 pub mod memory_forget;
 pub mod memory_recall;
 pub mod memory_store;
+pub mod metrics;
 pub mod model_routing_config;
+pub mod note;
+pub mod notify;
+pub mod object_store;
 pub mod pdf_read;
+pub mod people;
+pub mod phone_alert;
+pub mod plan;
+pub mod preference_set;
+pub mod printer;
 pub mod proxy_config;
 pub mod pushover;
+pub mod python_repl;
+pub mod report;
+pub mod research;
 pub mod schedule;
 pub mod schema;
 pub mod screenshot;
+pub mod self_status;
 pub mod shell;
+pub mod tasks;
+pub mod template;
 pub mod traits;
+pub mod updates;
 pub mod web_search_tool;
+pub mod webdav;
+pub mod where_is;
 
+pub use advisories::AdvisoriesTool;
 pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
+pub use cert_check::CertCheckTool;
+pub use code_exec::CodeExecTool;
 pub use composio::ComposioTool;
+pub use composite::{CompositeTool, ConfDComposites};
 pub use content_search::ContentSearchTool;
 pub use cron_add::CronAddTool;
 pub use cron_list::CronListTool;
@@ -62,7 +99,15 @@ pub use cron_run::CronRunTool;
 pub use cron_runs::CronRunsTool;
 pub use cron_update::CronUpdateTool;
 pub use delegate::DelegateTool;
+pub use device_inventory::DeviceInventoryTool;
+pub use diff_patch::DiffPatchTool;
+pub use dns::DnsTool;
+pub use domain_info::DomainInfoTool;
+pub use downloads::DownloadsTool;
+pub use energy::EnergyTool;
+pub use facts::FactsTool;
 pub use file_edit::FileEditTool;
+pub use file_inspect::FileInspectTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
 pub use git_operations::GitOperationsTool;
@@ -72,22 +117,40 @@ pub use hardware_memory_map::HardwareMemoryMapTool;
 pub use hardware_memory_read::HardwareMemoryReadTool;
 pub use http_request::HttpRequestTool;
 pub use image_info::ImageInfoTool;
+pub use media_server::MediaServerTool;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
+pub use metrics::MetricsTool;
 pub use model_routing_config::ModelRoutingConfigTool;
+pub use note::NoteTool;
+pub use notify::NotifyTool;
+pub use object_store::ObjectStoreTool;
 pub use pdf_read::PdfReadTool;
+pub use people::PeopleTool;
+pub use phone_alert::PhoneAlertTool;
+pub use plan::PlanTool;
+pub use preference_set::PreferenceSetTool;
+pub use printer::PrinterTool;
 pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
+pub use python_repl::PythonReplTool;
+pub use report::ReportTool;
+pub use research::ResearchTool;
 pub use schedule::ScheduleTool;
 #[allow(unused_imports)]
 pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
+pub use self_status::SelfStatusTool;
 pub use shell::ShellTool;
+pub use tasks::TasksTool;
 pub use traits::Tool;
 #[allow(unused_imports)]
-pub use traits::{ToolResult, ToolSpec};
+pub use traits::{Compensation, ToolResult, ToolSpec};
+pub use updates::UpdatesTool;
 pub use web_search_tool::WebSearchTool;
+pub use webdav::WebDavTool;
+pub use where_is::WhereIsTool;
 
 use crate::config::{Config, DelegateAgentConfig};
 use crate::memory::Memory;
@@ -97,14 +160,33 @@ use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+tokio::task_local! {
+    /// Conversation the running tool calls belong to (a channel thread or
+    /// gateway session). Tools that keep per-conversation state read it with
+    /// [`current_conversation`]; turns outside any scope share one slot.
+    pub static CONVERSATION_ID: String;
+}
+
+/// The conversation id set by the caller of the tool loop, if any.
+pub fn current_conversation() -> Option<String> {
+    CONVERSATION_ID.try_with(Clone::clone).ok()
+}
+
+/// Appended to the description of write-capable tools in read-only mode.
+const READ_ONLY_SUFFIX: &str = " (write actions blocked in read-only mode)";
+
 #[derive(Clone)]
 struct ArcDelegatingTool {
     inner: Arc<dyn Tool>,
+    /// Replacement description, set for write-capable tools in read-only mode.
+    description: Option<String>,
 }
 
 impl ArcDelegatingTool {
-    fn boxed(inner: Arc<dyn Tool>) -> Box<dyn Tool> {
-        Box::new(Self { inner })
+    fn boxed(inner: Arc<dyn Tool>, read_only: bool) -> Box<dyn Tool> {
+        let description = (read_only && inner.writes_state())
+            .then(|| format!("{}{READ_ONLY_SUFFIX}", inner.description()));
+        Box::new(Self { inner, description })
     }
 }
 
@@ -115,7 +197,9 @@ impl Tool for ArcDelegatingTool {
     }
 
     fn description(&self) -> &str {
-        self.inner.description()
+        self.description
+            .as_deref()
+            .unwrap_or_else(|| self.inner.description())
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -125,10 +209,92 @@ impl Tool for ArcDelegatingTool {
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         self.inner.execute(args).await
     }
+
+    fn compensation(&self, args: &serde_json::Value, result: &ToolResult) -> Option<Compensation> {
+        self.inner.compensation(args, result)
+    }
+
+    fn nested_tools(&self, args: &serde_json::Value) -> Vec<String> {
+        self.inner.nested_tools(args)
+    }
+
+    fn writes_state(&self) -> bool {
+        self.inner.writes_state()
+    }
+}
+
+fn boxed_registry_from_arcs(tools: Vec<Arc<dyn Tool>>, read_only: bool) -> Vec<Box<dyn Tool>> {
+    tools
+        .into_iter()
+        .map(|tool| ArcDelegatingTool::boxed(tool, read_only))
+        .collect()
 }
 
-fn boxed_registry_from_arcs(tools: Vec<Arc<dyn Tool>>) -> Vec<Box<dyn Tool>> {
-    tools.into_iter().map(ArcDelegatingTool::boxed).collect()
+/// Capability notice for read-only autonomy. Without it the model keeps
+/// attempting writes that the policy refuses; with it, it proposes them.
+pub fn read_only_notice(
+    autonomy: crate::security::AutonomyLevel,
+    tools: &[Box<dyn Tool>],
+) -> Option<String> {
+    use std::fmt::Write;
+
+    if autonomy != crate::security::AutonomyLevel::ReadOnly {
+        return None;
+    }
+    let mut notice = String::from(
+        "## Read-Only Mode\n\n\
+         This deployment is read-only: write actions will be blocked. Reading files, searching, and other observation still work.\n\
+         - Do not attempt actions that change state (writing files, side-effecting commands, sending messages, scheduling); they will fail.\n\
+         - When a change is needed, propose it instead: give the exact command, file edit, or tool call for the user to apply.\n",
+    );
+    let blocked: Vec<&str> = tools
+        .iter()
+        .filter(|tool| tool.writes_state())
+        .map(|tool| tool.name())
+        .collect();
+    if !blocked.is_empty() {
+        let _ = writeln!(
+            notice,
+            "- Write actions of these tools will be refused: {}.",
+            blocked.join(", ")
+        );
+    }
+    notice.push('\n');
+    Some(notice)
+}
+
+/// A built tool registry. Long-lived callers such as channel workers take
+/// [`Self::tools`] once per turn, so `conf.d/tools` edits apply without a
+/// restart.
+pub struct ToolRegistry {
+    fixed: Vec<Arc<dyn Tool>>,
+    conf_d: Option<Arc<ConfDComposites>>,
+    /// Mark write-capable tools as blocked in their descriptions.
+    read_only: bool,
+}
+
+impl ToolRegistry {
+    /// Registry of `tools` alone, without `conf.d` composites.
+    pub fn fixed(tools: Vec<Box<dyn Tool>>) -> Self {
+        Self {
+            fixed: tools.into_iter().map(Arc::from).collect(),
+            conf_d: None,
+            read_only: false,
+        }
+    }
+
+    /// The fixed tools followed by the current `conf.d` composites.
+    pub fn tools(&self) -> Vec<Box<dyn Tool>> {
+        let conf_d = self
+            .conf_d
+            .as_ref()
+            .map(|composites| composites.current())
+            .unwrap_or_default();
+        boxed_registry_from_arcs(
+            self.fixed.iter().cloned().chain(conf_d).collect(),
+            self.read_only,
+        )
+    }
 }
 
 /// Create the default tool registry
@@ -198,22 +364,68 @@ pub fn all_tools_with_runtime(
     fallback_api_key: Option<&str>,
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
+    tool_registry_with_runtime(
+        config,
+        security,
+        runtime,
+        memory,
+        composio_key,
+        composio_entity_id,
+        browser_config,
+        http_config,
+        workspace_dir,
+        agents,
+        fallback_api_key,
+        root_config,
+        None,
+    )
+    .tools()
+}
+
+/// Like [`all_tools_with_runtime`], limited to `allowed_tools` when given and
+/// returned as a [`ToolRegistry`]. Tools that call other tools (plan,
+/// composites, delegate) only see the allowlisted ones, so allowing `plan`
+/// does not grant everything else.
+#[allow(clippy::implicit_hasher, clippy::too_many_arguments)]
+pub fn tool_registry_with_runtime(
+    config: Arc<Config>,
+    security: &Arc<SecurityPolicy>,
+    runtime: Arc<dyn RuntimeAdapter>,
+    memory: Arc<dyn Memory>,
+    composio_key: Option<&str>,
+    composio_entity_id: Option<&str>,
+    browser_config: &crate::config::BrowserConfig,
+    http_config: &crate::config::HttpRequestConfig,
+    workspace_dir: &std::path::Path,
+    agents: &HashMap<String, DelegateAgentConfig>,
+    fallback_api_key: Option<&str>,
+    root_config: &crate::config::Config,
+    allowed_tools: Option<&[String]>,
+) -> ToolRegistry {
+    let in_scope = |tool: &Arc<dyn Tool>| {
+        allowed_tools.is_none_or(|allowed| allowed.iter().any(|name| name == tool.name()))
+    };
+
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
-        Arc::new(ShellTool::new(security.clone(), runtime)),
+        Arc::new(ShellTool::new(security.clone(), runtime.clone())),
         Arc::new(FileReadTool::new(security.clone())),
         Arc::new(FileWriteTool::new(security.clone())),
         Arc::new(FileEditTool::new(security.clone())),
         Arc::new(GlobSearchTool::new(security.clone())),
         Arc::new(ContentSearchTool::new(security.clone())),
+        Arc::new(DiffPatchTool::new(security.clone())),
+        Arc::new(ReportTool::new(security.clone()).with_locale(root_config.locale.clone())),
         Arc::new(CronAddTool::new(config.clone(), security.clone())),
         Arc::new(CronListTool::new(config.clone())),
         Arc::new(CronRemoveTool::new(config.clone(), security.clone())),
         Arc::new(CronUpdateTool::new(config.clone(), security.clone())),
         Arc::new(CronRunTool::new(config.clone(), security.clone())),
         Arc::new(CronRunsTool::new(config.clone())),
+        Arc::new(SelfStatusTool::new(config.clone())),
         Arc::new(MemoryStoreTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryRecallTool::new(memory.clone())),
         Arc::new(MemoryForgetTool::new(memory, security.clone())),
+        Arc::new(PreferenceSetTool::new(security.clone())),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
         Arc::new(ModelRoutingConfigTool::new(
             config.clone(),
@@ -276,12 +488,211 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    if root_config.research.enabled && root_config.web_search.enabled {
+        match crate::providers::create_resilient_provider_with_options(
+            root_config
+                .default_provider
+                .as_deref()
+                .unwrap_or("openrouter"),
+            root_config.api_key.as_deref(),
+            root_config.api_url.as_deref(),
+            &root_config.reliability,
+            &crate::providers::ProviderRuntimeOptions {
+                auth_profile_override: None,
+                zeroclaw_dir: root_config
+                    .config_path
+                    .parent()
+                    .map(std::path::PathBuf::from),
+                secrets_encrypt: root_config.secrets.encrypt,
+                reasoning_enabled: root_config.runtime.reasoning_enabled,
+            },
+        ) {
+            Ok(provider) => {
+                let search = Arc::new(WebSearchTool::new(
+                    root_config.web_search.provider.clone(),
+                    root_config.web_search.brave_api_key.clone(),
+                    root_config.web_search.max_results,
+                    root_config.web_search.timeout_secs,
+                ));
+                let mut research = ResearchTool::new(
+                    security.clone(),
+                    root_config.research.clone(),
+                    search,
+                    Arc::from(provider),
+                    root_config
+                        .research
+                        .model
+                        .clone()
+                        .or_else(|| root_config.default_model.clone())
+                        .unwrap_or_else(|| "anthropic/claude-sonnet-4".into()),
+                );
+                if root_config.facts.enabled {
+                    research = research.with_facts(root_config.facts.clone());
+                }
+                tool_arcs.push(Arc::new(research));
+            }
+            Err(e) => tracing::warn!("research tool disabled: provider unavailable: {e}"),
+        }
+    }
+
+    if root_config.code_exec.enabled {
+        tool_arcs.push(Arc::new(CodeExecTool::new(
+            security.clone(),
+            root_config.code_exec.clone(),
+        )));
+    }
+
+    if root_config.python_repl.enabled {
+        tool_arcs.push(Arc::new(PythonReplTool::new(
+            security.clone(),
+            root_config.python_repl.clone(),
+            root_config.code_exec.clone(),
+        )));
+    }
+
+    if root_config.object_store.enabled {
+        tool_arcs.push(Arc::new(ObjectStoreTool::new(
+            security.clone(),
+            root_config.object_store.clone(),
+        )));
+    }
+
+    if root_config.webdav.enabled {
+        tool_arcs.push(Arc::new(WebDavTool::new(
+            security.clone(),
+            root_config.webdav.clone(),
+        )));
+    }
+
+    if root_config.notes.enabled {
+        tool_arcs.push(Arc::new(NoteTool::new(
+            security.clone(),
+            root_config.notes.clone(),
+            workspace_dir.to_path_buf(),
+        )));
+    }
+
+    if root_config.location.enabled {
+        tool_arcs.push(Arc::new(WhereIsTool::new(workspace_dir.to_path_buf())));
+    }
+
+    if root_config.people.enabled {
+        tool_arcs.push(Arc::new(PeopleTool::new(root_config.clone())));
+    }
+
+    if root_config.tasks.enabled {
+        tool_arcs.push(Arc::new(TasksTool::new(
+            security.clone(),
+            root_config.tasks.clone(),
+            workspace_dir.to_path_buf(),
+        )));
+    }
+
+    if root_config.energy.enabled {
+        tool_arcs.push(Arc::new(EnergyTool::new(
+            security.clone(),
+            root_config.energy.clone(),
+            workspace_dir.to_path_buf(),
+        )));
+    }
+
+    if root_config.cert_check.enabled {
+        tool_arcs.push(Arc::new(CertCheckTool::new(root_config.cert_check.clone())));
+    }
+
+    if root_config.dns.enabled {
+        tool_arcs.push(Arc::new(DnsTool::new(
+            root_config.dns.clone(),
+            workspace_dir.to_path_buf(),
+        )));
+    }
+
+    if root_config.domain_info.enabled {
+        tool_arcs.push(Arc::new(DomainInfoTool::new(
+            root_config.domain_info.clone(),
+        )));
+    }
+
+    if root_config.updates.enabled {
+        tool_arcs.push(Arc::new(UpdatesTool::new(
+            security.clone(),
+            runtime.clone(),
+            root_config.updates.clone(),
+        )));
+    }
+
+    if root_config.advisories.enabled {
+        tool_arcs.push(Arc::new(AdvisoriesTool::new(
+            security.clone(),
+            runtime,
+            root_config.updates.clone(),
+            root_config.advisories.clone(),
+            root_config.notify.clone(),
+            workspace_dir.to_path_buf(),
+        )));
+    }
+
+    if root_config.facts.enabled {
+        tool_arcs.push(Arc::new(
+            FactsTool::new(root_config.facts.clone(), security.clone())
+                .with_locale(root_config.locale.clone()),
+        ));
+    }
+
+    if root_config.metrics.enabled {
+        tool_arcs.push(Arc::new(MetricsTool::new(config.clone())));
+    }
+
+    if root_config.inventory.enabled {
+        tool_arcs.push(Arc::new(
+            DeviceInventoryTool::new(root_config.inventory.clone(), security.clone())
+                .with_locale(root_config.locale.clone()),
+        ));
+    }
+
+    if root_config.printer.enabled {
+        tool_arcs.push(Arc::new(PrinterTool::new(
+            security.clone(),
+            root_config.printer.clone(),
+            workspace_dir.to_path_buf(),
+        )));
+    }
+
+    if root_config.media_server.enabled {
+        tool_arcs.push(Arc::new(MediaServerTool::new(
+            security.clone(),
+            root_config.media_server.clone(),
+        )));
+    }
+
+    if root_config.downloads.enabled {
+        tool_arcs.push(Arc::new(DownloadsTool::new(
+            security.clone(),
+            root_config.downloads.clone(),
+        )));
+    }
+
+    if root_config.notify.enabled {
+        tool_arcs.push(Arc::new(NotifyTool::new(
+            security.clone(),
+            root_config.notify.clone(),
+        )));
+    }
+
+    if root_config.phone_alert.enabled {
+        tool_arcs.push(Arc::new(PhoneAlertTool::new(
+            security.clone(),
+            root_config.phone_alert.clone(),
+        )));
+    }
+
     // PDF extraction (feature-gated at compile time via rag-pdf)
     tool_arcs.push(Arc::new(PdfReadTool::new(security.clone())));
 
     // Vision tools are always available
     tool_arcs.push(Arc::new(ScreenshotTool::new(security.clone())));
     tool_arcs.push(Arc::new(ImageInfoTool::new(security.clone())));
+    tool_arcs.push(Arc::new(FileInspectTool::new(security.clone())));
 
     if let Some(key) = composio_key {
         if !key.is_empty() {
@@ -293,6 +704,38 @@ pub fn all_tools_with_runtime(
         }
     }
 
+    // Limit before anything captures the tools (composites, delegate, plan),
+    // so nested calls share the same permits
+    let limits = &root_config.agent.tool_concurrency;
+    let mut tool_arcs: Vec<Arc<dyn Tool>> = tool_arcs
+        .into_iter()
+        .filter(&in_scope)
+        .map(|tool| concurrency::limit(tool, limits))
+        .collect();
+
+    // Config-defined composites wrap the (allowlisted) tools registered so
+    // far; conf.d/tools adds more, rebuilt whenever the directory changes,
+    // with config.toml winning on name clashes. A composite whose steps
+    // reach outside the allowlist is skipped.
+    let base_tools = tool_arcs.clone();
+    let conf_d = Arc::new(ConfDComposites::new(
+        crate::config::confd::ConfDWatcher::shared(root_config),
+        base_tools.clone(),
+        root_config.composite_tools.keys().cloned().collect(),
+        allowed_tools,
+        limits.clone(),
+    ));
+    let mut composites: Vec<(&String, &crate::config::CompositeToolConfig)> =
+        root_config.composite_tools.iter().collect();
+    composites.sort_by_key(|(name, _)| *name);
+    for (name, composite) in composites {
+        match CompositeTool::new(name, composite.clone(), &base_tools) {
+            Ok(tool) => tool_arcs.push(concurrency::limit(Arc::new(tool), limits)),
+            Err(e) => tracing::warn!("Skipping composite tool: {e}"),
+        }
+    }
+    tool_arcs.retain(&in_scope);
+
     // Add delegation tool when agents are configured
     if !agents.is_empty() {
         let delegate_agents: HashMap<String, DelegateAgentConfig> = agents
@@ -320,10 +763,23 @@ pub fn all_tools_with_runtime(
         )
         .with_parent_tools(parent_tools)
         .with_multimodal_config(root_config.multimodal.clone());
-        tool_arcs.push(Arc::new(delegate_tool));
+        tool_arcs.push(concurrency::limit(Arc::new(delegate_tool), limits));
+    }
+
+    tool_arcs.retain(&in_scope);
+    let plan: Arc<dyn Tool> = Arc::new(
+        PlanTool::new(security.clone(), Arc::new(tool_arcs.clone()))
+            .with_conf_d_composites(conf_d.clone()),
+    );
+    if in_scope(&plan) {
+        tool_arcs.push(concurrency::limit(plan, limits));
     }
 
-    boxed_registry_from_arcs(tool_arcs)
+    ToolRegistry {
+        fixed: tool_arcs,
+        conf_d: Some(conf_d),
+        read_only: security.autonomy == crate::security::AutonomyLevel::ReadOnly,
+    }
 }
 
 #[cfg(test)]
@@ -347,6 +803,204 @@ mod tests {
         assert_eq!(tools.len(), 6);
     }
 
+    #[test]
+    fn all_tools_registers_valid_composite_tools() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let mut cfg = test_config(&tmp);
+        let step = |tool: &str| crate::config::CompositeStepConfig {
+            tool: tool.into(),
+            args: serde_json::Map::new(),
+        };
+        for (name, tool) in [("check_disk", "shell"), ("broken", "no_such_tool")] {
+            cfg.composite_tools.insert(
+                name.into(),
+                crate::config::CompositeToolConfig {
+                    description: "test".into(),
+                    parameters: HashMap::new(),
+                    steps: vec![step(tool)],
+                },
+            );
+        }
+
+        let tools = all_tools(
+            Arc::new(Config::default()),
+            &security,
+            mem,
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+        );
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert!(names.contains(&"check_disk"));
+        assert!(!names.contains(&"broken"));
+    }
+
+    #[test]
+    fn read_only_registry_marks_write_capable_tools() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy {
+            autonomy: crate::security::AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let mut cfg = test_config(&tmp);
+        cfg.facts.enabled = true;
+        cfg.inventory.enabled = true;
+        cfg.composite_tools.insert(
+            "check_disk".into(),
+            crate::config::CompositeToolConfig {
+                description: "Check disk".into(),
+                parameters: HashMap::new(),
+                steps: vec![crate::config::CompositeStepConfig {
+                    tool: "shell".into(),
+                    args: serde_json::Map::new(),
+                }],
+            },
+        );
+
+        let tools = all_tools(
+            Arc::new(Config::default()),
+            &security,
+            mem,
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+        );
+        let tool = |name: &str| tools.iter().find(|t| t.name() == name).unwrap();
+        for name in ["shell", "facts", "device_inventory", "check_disk"] {
+            assert!(tool(name).writes_state(), "{name}");
+            assert!(
+                tool(name).description().ends_with(READ_ONLY_SUFFIX),
+                "{name}"
+            );
+        }
+        assert!(!tool("file_read").writes_state());
+        assert!(!tool("file_read").description().ends_with(READ_ONLY_SUFFIX));
+
+        assert!(read_only_notice(crate::security::AutonomyLevel::Supervised, &tools).is_none());
+        let notice = read_only_notice(crate::security::AutonomyLevel::ReadOnly, &tools).unwrap();
+        assert!(notice.contains("write actions will be blocked"));
+        assert!(notice.contains("shell, "));
+        assert!(!notice.contains("file_read"));
+    }
+
+    #[test]
+    fn scoped_composites_skip_steps_outside_allowlist() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let mut cfg = test_config(&tmp);
+        let step = |tool: &str| crate::config::CompositeStepConfig {
+            tool: tool.into(),
+            args: serde_json::Map::new(),
+        };
+        for (name, tool) in [("check_disk", "shell"), ("peek", "file_read")] {
+            cfg.composite_tools.insert(
+                name.into(),
+                crate::config::CompositeToolConfig {
+                    description: "test".into(),
+                    parameters: HashMap::new(),
+                    steps: vec![step(tool)],
+                },
+            );
+        }
+        let allowed: Vec<String> = ["check_disk", "peek", "file_read"]
+            .map(String::from)
+            .to_vec();
+
+        let tools = tool_registry_with_runtime(
+            Arc::new(Config::default()),
+            &security,
+            Arc::new(NativeRuntime::new()),
+            mem,
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+            Some(&allowed),
+        )
+        .tools();
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert_eq!(names, ["file_read", "peek"]);
+    }
+
+    #[tokio::test]
+    async fn scoped_plan_cannot_reach_tools_outside_allowlist() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let cfg = test_config(&tmp);
+        let allowed = vec!["plan".to_string(), "file_read".to_string()];
+
+        let tools = tool_registry_with_runtime(
+            Arc::new(Config::default()),
+            &security,
+            Arc::new(NativeRuntime::new()),
+            mem,
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+            Some(&allowed),
+        )
+        .tools();
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert_eq!(names, ["file_read", "plan"]);
+
+        let plan = tools.iter().find(|t| t.name() == "plan").unwrap();
+        let result = plan
+            .execute(serde_json::json!({ "steps": [
+                { "tool": "shell", "args": { "command": "touch pwned" } }
+            ]}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("Unknown tools in plan: shell"));
+    }
+
     #[test]
     fn all_tools_excludes_browser_when_disabled() {
         let tmp = TempDir::new().unwrap();
@@ -382,9 +1036,14 @@ mod tests {
         );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"browser_open"));
+        assert!(names.contains(&"diff_patch"));
+        assert!(names.contains(&"file_inspect"));
+        assert!(names.contains(&"report"));
         assert!(names.contains(&"schedule"));
         assert!(names.contains(&"model_routing_config"));
         assert!(names.contains(&"pushover"));
+        assert!(names.contains(&"plan"));
+        assert!(names.contains(&"self_status"));
         assert!(names.contains(&"proxy_config"));
     }
 
@@ -0,0 +1,540 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::NotesConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+
+const ENTRY_HEADING: &str = "### ";
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_FILES: usize = 5000;
+
+/// One `### HH:MM` entry inside a daily notes file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NoteEntry {
+    heading: String,
+    body: String,
+}
+
+/// Append timestamped notes to daily markdown files and search them back.
+pub struct NoteTool {
+    security: Arc<SecurityPolicy>,
+    config: NotesConfig,
+    workspace_dir: PathBuf,
+}
+
+impl NoteTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: NotesConfig, workspace_dir: PathBuf) -> Self {
+        Self {
+            security,
+            config,
+            workspace_dir,
+        }
+    }
+
+    /// Create (if needed) and canonicalize the notes directory, then check it
+    /// against the workspace / allowed_roots policy.
+    async fn notes_dir(&self, create: bool) -> Result<PathBuf, String> {
+        let configured = Path::new(self.config.dir.trim());
+        if configured.as_os_str().is_empty() {
+            return Err("notes.dir is empty".into());
+        }
+        if !configured.is_absolute() && !self.security.is_path_allowed(&self.config.dir) {
+            return Err(format!(
+                "Path not allowed by security policy: {}",
+                self.config.dir
+            ));
+        }
+        let dir = self.workspace_dir.join(configured);
+        if create {
+            tokio::fs::create_dir_all(&dir)
+                .await
+                .map_err(|e| format!("Failed to create notes directory: {e}"))?;
+        }
+        let resolved = tokio::fs::canonicalize(&dir)
+            .await
+            .map_err(|e| format!("Failed to resolve notes directory: {e}"))?;
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            return Err(self.security.resolved_path_violation_message(&resolved));
+        }
+        Ok(resolved)
+    }
+
+    async fn add(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let text = args
+            .get("text")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .unwrap_or_default();
+        if text.is_empty() {
+            return Ok(failure("'text' is required for add"));
+        }
+        let title = args
+            .get("title")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty());
+        let tags: Vec<String> = args
+            .get("tags")
+            .and_then(|v| v.as_array())
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .filter_map(normalize_tag)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if !self.security.can_act() {
+            return Ok(failure("Action blocked: autonomy is read-only"));
+        }
+        if self.security.is_rate_limited() {
+            return Ok(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+        if !self.security.record_action() {
+            return Ok(failure("Rate limit exceeded: action budget exhausted"));
+        }
+
+        let dir = match self.notes_dir(true).await {
+            Ok(dir) => dir,
+            Err(e) => return Ok(failure(e)),
+        };
+        let now = chrono::Local::now();
+        let file_stem = now.format(&self.config.daily_format).to_string();
+        if file_stem.is_empty() || file_stem.contains(['/', '\\']) || file_stem.contains("..") {
+            return Ok(failure(format!(
+                "notes.daily_format produced an invalid file name: '{file_stem}'"
+            )));
+        }
+        let path = dir.join(format!("{file_stem}.md"));
+
+        let existing = match tokio::fs::symlink_metadata(&path).await {
+            Ok(meta) if meta.file_type().is_symlink() => {
+                return Ok(failure(format!(
+                    "Refusing to write through symlink: {}",
+                    path.display()
+                )));
+            }
+            Ok(meta) => meta.len() > 0,
+            Err(_) => false,
+        };
+
+        let mut entry = String::new();
+        if !existing {
+            let _ = writeln!(entry, "# {}\n", now.format("%Y-%m-%d"));
+        }
+        entry.push_str(&format_entry(
+            &now.format("%H:%M").to_string(),
+            title,
+            text,
+            &tags,
+        ));
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await?;
+        file.write_all(entry.as_bytes()).await?;
+        file.flush().await?;
+
+        Ok(ToolResult {
+            success: true,
+            output: format!("Noted in {}", path.display()),
+            error: None,
+        })
+    }
+
+    async fn search(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let query = args
+            .get("query")
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty());
+        let tag = args
+            .get("tag")
+            .and_then(|v| v.as_str())
+            .and_then(normalize_tag);
+        if query.is_none() && tag.is_none() {
+            return Ok(failure("search needs a 'query' and/or 'tag'"));
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_SEARCH_LIMIT, |n| n.clamp(1, 200) as usize);
+
+        if !self.workspace_dir.join(self.config.dir.trim()).exists() {
+            return Ok(ToolResult {
+                success: true,
+                output: "No notes yet".into(),
+                error: None,
+            });
+        }
+        let dir = match self.notes_dir(false).await {
+            Ok(dir) => dir,
+            Err(e) => return Ok(failure(e)),
+        };
+
+        let files = tokio::task::spawn_blocking(move || {
+            let mut files = Vec::new();
+            collect_markdown_files(&dir, &dir, &mut files);
+            // Daily files sort by name; newest first.
+            files.sort_by(|a, b| b.1.cmp(&a.1));
+            files
+        })
+        .await?;
+
+        let mut output = String::new();
+        let mut matches = 0usize;
+        'files: for (path, relative) in files {
+            let Ok(content) = tokio::fs::read_to_string(&path).await else {
+                continue;
+            };
+            let mut entries = parse_entries(&content);
+            entries.reverse();
+            for entry in entries {
+                if !entry_matches(&entry, query.as_deref(), tag.as_deref()) {
+                    continue;
+                }
+                let _ = writeln!(
+                    output,
+                    "{relative} — {}\n{}\n",
+                    entry.heading,
+                    crate::util::truncate_with_ellipsis(entry.body.trim(), 500)
+                );
+                matches += 1;
+                if matches >= limit {
+                    break 'files;
+                }
+            }
+        }
+
+        if matches == 0 {
+            return Ok(ToolResult {
+                success: true,
+                output: "No matching notes".into(),
+                error: None,
+            });
+        }
+        Ok(ToolResult {
+            success: true,
+            output: output.trim_end().to_string(),
+            error: None,
+        })
+    }
+}
+
+fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+/// Normalize a tag to Obsidian-compatible form: no leading `#`, spaces to
+/// dashes, only letters, digits, `-`, `_`, and `/`.
+fn normalize_tag(raw: &str) -> Option<String> {
+    let tag: String = raw
+        .trim()
+        .trim_start_matches('#')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'))
+        .collect();
+    (!tag.is_empty() && !tag.chars().all(|c| c.is_ascii_digit())).then_some(tag)
+}
+
+fn format_entry(time: &str, title: Option<&str>, text: &str, tags: &[String]) -> String {
+    let mut entry = match title {
+        Some(title) => format!("{ENTRY_HEADING}{time} {title}\n\n"),
+        None => format!("{ENTRY_HEADING}{time}\n\n"),
+    };
+    entry.push_str(text);
+    entry.push('\n');
+    if !tags.is_empty() {
+        let line: Vec<String> = tags.iter().map(|tag| format!("#{tag}")).collect();
+        let _ = write!(entry, "\n{}\n", line.join(" "));
+    }
+    entry.push('\n');
+    entry
+}
+
+/// Split a notes file into `### ` entries. Files written by hand without
+/// entry headings are treated as one entry named after the file heading.
+fn parse_entries(content: &str) -> Vec<NoteEntry> {
+    let mut entries: Vec<NoteEntry> = Vec::new();
+    let mut preamble = String::new();
+    for line in content.lines() {
+        if let Some(heading) = line.strip_prefix(ENTRY_HEADING) {
+            entries.push(NoteEntry {
+                heading: heading.trim().to_string(),
+                body: String::new(),
+            });
+        } else if let Some(entry) = entries.last_mut() {
+            entry.body.push_str(line);
+            entry.body.push('\n');
+        } else {
+            preamble.push_str(line);
+            preamble.push('\n');
+        }
+    }
+    let preamble_body = preamble
+        .lines()
+        .filter(|line| !line.starts_with("# "))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !preamble_body.trim().is_empty() {
+        let heading = preamble
+            .lines()
+            .find_map(|line| line.strip_prefix("# "))
+            .unwrap_or("(untitled)")
+            .trim()
+            .to_string();
+        entries.insert(
+            0,
+            NoteEntry {
+                heading,
+                body: preamble_body,
+            },
+        );
+    }
+    entries
+}
+
+fn entry_matches(entry: &NoteEntry, query: Option<&str>, tag: Option<&str>) -> bool {
+    if let Some(tag) = tag {
+        let wanted = format!("#{}", tag.to_lowercase());
+        let has_tag = entry
+            .body
+            .split_whitespace()
+            .any(|word| word.to_lowercase() == wanted);
+        if !has_tag {
+            return false;
+        }
+    }
+    query.is_none_or(|query| {
+        entry.heading.to_lowercase().contains(query) || entry.body.to_lowercase().contains(query)
+    })
+}
+
+/// Collect `*.md` files below `dir`, skipping hidden directories such as
+/// `.obsidian` and `.trash`. Symlinks are not followed.
+fn collect_markdown_files(root: &Path, dir: &Path, out: &mut Vec<(PathBuf, String)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if out.len() >= MAX_SEARCH_FILES {
+            return;
+        }
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_markdown_files(root, &path, out);
+        } else if file_type.is_file() && path.extension().is_some_and(|ext| ext == "md") {
+            let relative = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .into_owned();
+            out.push((path, relative));
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for NoteTool {
+    fn name(&self) -> &str {
+        "note"
+    }
+
+    fn description(&self) -> &str {
+        "Quick-capture notes: 'add' appends a timestamped note (optional title and tags) to \
+         today's markdown file in the notes folder; 'search' finds past notes by text and/or \
+         tag, newest first."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["add", "search"],
+                    "description": "add a note or search existing notes"
+                },
+                "text": {
+                    "type": "string",
+                    "description": "Note body (markdown) for add"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Optional short title shown next to the timestamp"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Tags for add, e.g. ['recipe', 'dinner']"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Case-insensitive text to search for"
+                },
+                "tag": {
+                    "type": "string",
+                    "description": "Only return notes carrying this tag"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum results for search (default: 20)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        match action {
+            "add" => self.add(&args).await,
+            "search" => self.search(&args).await,
+            other => Ok(failure(format!(
+                "Unknown action '{other}'. Use 'add' or 'search'."
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_tool(level: AutonomyLevel, workspace: &Path) -> NoteTool {
+        NoteTool::new(
+            Arc::new(SecurityPolicy {
+                autonomy: level,
+                workspace_dir: workspace.to_path_buf(),
+                ..SecurityPolicy::default()
+            }),
+            NotesConfig::default(),
+            workspace.to_path_buf(),
+        )
+    }
+
+    #[test]
+    fn tags_are_normalized() {
+        assert_eq!(normalize_tag("#Recipe").as_deref(), Some("Recipe"));
+        assert_eq!(
+            normalize_tag(" weeknight dinner ").as_deref(),
+            Some("weeknight-dinner")
+        );
+        assert_eq!(normalize_tag("food/pasta!").as_deref(), Some("food/pasta"));
+        assert!(normalize_tag("#").is_none());
+        assert!(normalize_tag("2026").is_none());
+    }
+
+    #[test]
+    fn parses_entries_and_hand_written_preamble() {
+        let content = "# 2026-03-01\n\nloose thought\n\n### 09:15 Pancakes\n\n200g flour\n\n#recipe\n\n### 12:00\n\ncall plumber\n";
+        let entries = parse_entries(content);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].heading, "2026-03-01");
+        assert!(entries[0].body.contains("loose thought"));
+        assert_eq!(entries[1].heading, "09:15 Pancakes");
+        assert!(entry_matches(&entries[1], None, Some("recipe")));
+        assert!(!entry_matches(&entries[2], None, Some("recipe")));
+        assert!(entry_matches(&entries[2], Some("plumber"), None));
+    }
+
+    #[tokio::test]
+    async fn add_then_search_by_tag_and_text() {
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(AutonomyLevel::Full, tmp.path());
+
+        let result = tool
+            .execute(json!({
+                "action": "add",
+                "title": "Pancakes",
+                "text": "200g flour, 2 eggs, 300ml milk",
+                "tags": ["recipe", "#breakfast"]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        tool.execute(json!({"action": "add", "text": "Ask landlord about boiler"}))
+            .await
+            .unwrap();
+
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let content =
+            std::fs::read_to_string(tmp.path().join("notes").join(format!("{today}.md"))).unwrap();
+        assert!(content.starts_with(&format!("# {today}\n")));
+        assert_eq!(content.matches("# ").count(), 3);
+        assert!(content.contains("#recipe #breakfast"));
+
+        let result = tool
+            .execute(json!({"action": "search", "tag": "recipe"}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("Pancakes"));
+        assert!(!result.output.contains("boiler"));
+
+        let result = tool
+            .execute(json!({"action": "search", "query": "BOILER"}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("landlord"));
+    }
+
+    #[tokio::test]
+    async fn add_blocked_in_readonly_mode() {
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(AutonomyLevel::ReadOnly, tmp.path());
+        let result = tool
+            .execute(json!({"action": "add", "text": "hello"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+        assert!(!tmp.path().join("notes").exists());
+    }
+
+    #[tokio::test]
+    async fn absolute_dir_outside_allowed_roots_is_rejected() {
+        let workspace = TempDir::new().unwrap();
+        let vault = TempDir::new().unwrap();
+        let mut tool = test_tool(AutonomyLevel::Full, workspace.path());
+        tool.config.dir = vault.path().to_string_lossy().into_owned();
+        let result = tool
+            .execute(json!({"action": "add", "text": "hello"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("allowed_roots"));
+    }
+}
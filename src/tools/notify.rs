@@ -0,0 +1,292 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::NotifyConfig;
+use crate::notify::Notification;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Largest batch accepted in one call.
+const MAX_BATCH: usize = 100;
+/// Pause between batch deliveries unless `interval_ms` says otherwise.
+const DEFAULT_BATCH_INTERVAL_MS: u64 = 250;
+const MAX_BATCH_INTERVAL_MS: u64 = 10_000;
+
+fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+/// Send a notification to a configured `[[notify.targets]]` entry.
+pub struct NotifyTool {
+    security: Arc<SecurityPolicy>,
+    config: NotifyConfig,
+}
+
+impl NotifyTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: NotifyConfig) -> Self {
+        Self { security, config }
+    }
+}
+
+#[async_trait]
+impl Tool for NotifyTool {
+    fn name(&self) -> &str {
+        "notify"
+    }
+
+    fn description(&self) -> &str {
+        "Send a notification to a person via their configured target (ntfy, Gotify, \
+         Pushover, Telegram, Discord, Slack, email, or a JSON webhook). Omit 'target' to \
+         use the default one. Pass 'messages' instead of 'message' to send a batch in one \
+         call; each item reports its own result."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let targets: Vec<&str> = self
+            .config
+            .targets
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        json!({
+            "type": "object",
+            "properties": {
+                "target": {
+                    "type": "string",
+                    "description": format!("Target name (configured: {})", targets.join(", "))
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Notification body"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Optional title"
+                },
+                "priority": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": 5,
+                    "description": "1 = min, 3 = default, 5 = urgent"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Optional tags (ntfy renders known tags as emoji)"
+                },
+                "click": {
+                    "type": "string",
+                    "description": "URL to open when the notification is tapped"
+                },
+                "messages": {
+                    "type": "array",
+                    "maxItems": MAX_BATCH,
+                    "description": "Batch mode: messages sent in order. Each item is a string or an object with message/title/priority/tags/click/target; missing fields fall back to the top-level ones",
+                    "items": {
+                        "oneOf": [
+                            {"type": "string"},
+                            {"type": "object", "required": ["message"]}
+                        ]
+                    }
+                },
+                "interval_ms": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "maximum": MAX_BATCH_INTERVAL_MS,
+                    "description": format!("Batch mode: pause between deliveries (default {DEFAULT_BATCH_INTERVAL_MS})")
+                },
+                "idempotency_key": super::idempotency::schema_property()
+            }
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        super::idempotency::run_once(&self.security.workspace_dir, self.name(), &args, || async {
+            match args.get("messages").and_then(serde_json::Value::as_array) {
+                Some(items) => self.send_batch(&args, items).await,
+                None => self.send_once(&args).await,
+            }
+        })
+        .await
+    }
+}
+
+/// Batch item merged over the top-level arguments.
+fn batch_item(args: &serde_json::Value, item: &serde_json::Value) -> serde_json::Value {
+    let mut merged = serde_json::Map::new();
+    if let Some(top) = args.as_object() {
+        for key in ["target", "title", "priority", "tags", "click"] {
+            if let Some(value) = top.get(key) {
+                merged.insert(key.into(), value.clone());
+            }
+        }
+    }
+    match item {
+        serde_json::Value::Object(fields) => {
+            merged.extend(fields.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        other => {
+            merged.insert("message".into(), other.clone());
+        }
+    }
+    serde_json::Value::Object(merged)
+}
+
+impl NotifyTool {
+    async fn send_batch(
+        &self,
+        args: &serde_json::Value,
+        items: &[serde_json::Value],
+    ) -> anyhow::Result<ToolResult> {
+        if items.is_empty() {
+            return Ok(failure("'messages' is empty"));
+        }
+        if items.len() > MAX_BATCH {
+            return Ok(failure(format!(
+                "Batch too large: {} messages (max {MAX_BATCH})",
+                items.len()
+            )));
+        }
+        let interval = Duration::from_millis(
+            args.get("interval_ms")
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or(DEFAULT_BATCH_INTERVAL_MS)
+                .min(MAX_BATCH_INTERVAL_MS),
+        );
+
+        let mut report = String::new();
+        let mut failed = 0;
+        for (idx, item) in items.iter().enumerate() {
+            if idx > 0 {
+                tokio::time::sleep(interval).await;
+            }
+            let outcome = match self.send_once(&batch_item(args, item)).await {
+                Ok(result) if result.success => result.output,
+                Ok(result) => {
+                    failed += 1;
+                    format!("failed: {}", result.error.unwrap_or_default())
+                }
+                Err(e) => {
+                    failed += 1;
+                    format!("failed: {e}")
+                }
+            };
+            let _ = writeln!(report, "{}. {outcome}", idx + 1);
+        }
+
+        let sent = items.len() - failed;
+        let _ = write!(report, "{sent}/{} sent", items.len());
+        Ok(ToolResult {
+            success: failed == 0,
+            output: report,
+            error: (failed > 0).then(|| format!("{failed} of {} messages failed", items.len())),
+        })
+    }
+
+    async fn send_once(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let message = args
+            .get("message")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'message' parameter"))?;
+
+        let target = match crate::notify::find_target(
+            &self.config,
+            args.get("target").and_then(|v| v.as_str()),
+        ) {
+            Ok(target) => target,
+            Err(e) => return Ok(failure(e.to_string())),
+        };
+
+        if !self.security.can_act() {
+            return Ok(failure("Action blocked: autonomy is read-only"));
+        }
+        if self.security.is_rate_limited() {
+            return Ok(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+        if !self.security.record_action() {
+            return Ok(failure("Rate limit exceeded: action budget exhausted"));
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let priority = args
+            .get("priority")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(3, |p| p.clamp(1, 5) as u8);
+        let notification = Notification {
+            title: args
+                .get("title")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            priority,
+            tags: args
+                .get("tags")
+                .and_then(|v| v.as_array())
+                .map(|tags| {
+                    tags.iter()
+                        .filter_map(|t| t.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            click: args
+                .get("click")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            ..Notification::new(message)
+        };
+
+        match crate::notify::send(&self.config, target, &notification).await {
+            Ok(()) => {
+                crate::gateway::ws::publish_notification(
+                    "alert",
+                    serde_json::json!({
+                        "source": "notify",
+                        "priority": notification.priority,
+                        "title": notification.title,
+                        "message": notification.message,
+                    }),
+                );
+                Ok(ToolResult {
+                    success: true,
+                    output: format!("Notification sent to '{}'", target.name),
+                    error: None,
+                })
+            }
+            Err(e) => Ok(failure(format!("Notification failed: {e}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_items_inherit_top_level_fields() {
+        let args = json!({"target": "ops", "priority": 4, "messages": ["a", {"message": "b", "priority": 1}]});
+        let items = args["messages"].as_array().unwrap();
+
+        let first = batch_item(&args, &items[0]);
+        assert_eq!(
+            first,
+            json!({"target": "ops", "priority": 4, "message": "a"})
+        );
+        let second = batch_item(&args, &items[1]);
+        assert_eq!(second["priority"], 1);
+        assert_eq!(second["target"], "ops");
+        assert!(second.get("messages").is_none());
+    }
+}
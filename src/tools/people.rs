@@ -0,0 +1,118 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Upcoming birthdays, anniversaries, and other yearly dates from `[people]`.
+pub struct PeopleTool {
+    config: Config,
+}
+
+impl PeopleTool {
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Tool for PeopleTool {
+    fn name(&self) -> &str {
+        "people"
+    }
+
+    fn description(&self) -> &str {
+        "List upcoming birthdays, anniversaries, and other yearly dates for the people in the \
+         configured dataset, soonest first. Use this when preparing briefings or when asked \
+         what is coming up."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "days": {
+                    "type": "integer",
+                    "description": format!(
+                        "How many days ahead to look (default: {})",
+                        self.config.people.lookahead_days
+                    )
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        #[allow(clippy::cast_possible_truncation)]
+        let days = args
+            .get("days")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(self.config.people.lookahead_days, |d| d.min(366) as u32);
+
+        let people = match crate::people::load_people(&self.config).await {
+            Ok(people) => people,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to load people: {e}")),
+                });
+            }
+        };
+
+        let today = chrono::Local::now().date_naive();
+        let summary = crate::people::format_summary(&crate::people::upcoming(&people, today, days));
+        Ok(ToolResult {
+            success: true,
+            output: if summary.is_empty() {
+                format!("Nothing in the next {days} days")
+            } else {
+                summary
+            },
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn lists_todays_birthday_from_workspace_file() {
+        let tmp = TempDir::new().unwrap();
+        let today = chrono::Local::now().date_naive();
+        std::fs::write(
+            tmp.path().join("people.toml"),
+            format!(
+                "[[person]]\nname = \"Alice\"\nbirthday = \"--{}\"\n",
+                today.format("%m-%d")
+            ),
+        )
+        .unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        config.people.enabled = true;
+
+        let result = PeopleTool::new(config).execute(json!({})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("Today: Alice's birthday"));
+    }
+
+    #[tokio::test]
+    async fn missing_dataset_reports_nothing_upcoming() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        let result = PeopleTool::new(config)
+            .execute(json!({"days": 3}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "Nothing in the next 3 days");
+    }
+}
@@ -0,0 +1,554 @@
+use super::traits::{Tool, ToolResult};
+use crate::channels::format::FormatProfile;
+use crate::config::{PhoneAlertConfig, PhoneAlertVonageConfig};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use base64::Engine;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Only the top tier of the `notify` priority scale may reach a phone.
+const REQUIRED_PRIORITY: u64 = 5;
+const MAX_SMS_CHARS: usize = 480;
+
+fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Sms,
+    Call,
+}
+
+/// Reduce a phone number to `+` and digits so config and tool input compare equal.
+fn normalize_number(number: &str) -> String {
+    let trimmed = number.trim();
+    let digits: String = trimmed.chars().filter(char::is_ascii_digit).collect();
+    if let Some(rest) = digits.strip_prefix("00") {
+        format!("+{rest}")
+    } else if trimmed.starts_with('+') {
+        format!("+{digits}")
+    } else {
+        digits
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// TwiML that reads the alert twice so it survives a late pickup.
+fn twiml_say(message: &str) -> String {
+    format!(
+        "<Response><Say loop=\"2\">{}</Say></Response>",
+        xml_escape(message)
+    )
+}
+
+fn pem_to_der(pem: &str) -> anyhow::Result<Vec<u8>> {
+    let body: String = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect::<Vec<_>>()
+        .concat();
+    Ok(base64::engine::general_purpose::STANDARD.decode(body.trim())?)
+}
+
+/// RS256 JWT for the Vonage Voice API.
+fn vonage_jwt(application_id: &str, private_key_pem: &str, now: i64) -> anyhow::Result<String> {
+    let url_safe = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    let header = url_safe.encode(br#"{"alg":"RS256","typ":"JWT"}"#);
+    let claims = url_safe.encode(
+        json!({
+            "application_id": application_id,
+            "iat": now,
+            "exp": now + 300,
+            "jti": uuid::Uuid::new_v4().to_string(),
+        })
+        .to_string(),
+    );
+    let signing_input = format!("{header}.{claims}");
+
+    let key = ring::signature::RsaKeyPair::from_pkcs8(&pem_to_der(private_key_pem)?)
+        .map_err(|e| anyhow::anyhow!("Invalid Vonage private key (expected PKCS#8 PEM): {e}"))?;
+    let mut signature = vec![0u8; key.public().modulus_len()];
+    key.sign(
+        &ring::signature::RSA_PKCS1_SHA256,
+        &ring::rand::SystemRandom::new(),
+        signing_input.as_bytes(),
+        &mut signature,
+    )
+    .map_err(|_| anyhow::anyhow!("Failed to sign Vonage JWT"))?;
+    Ok(format!("{signing_input}.{}", url_safe.encode(signature)))
+}
+
+/// SMS and text-to-speech calls for critical escalations, restricted to
+/// `[phone_alert].recipients`.
+pub struct PhoneAlertTool {
+    security: Arc<SecurityPolicy>,
+    config: PhoneAlertConfig,
+}
+
+impl PhoneAlertTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: PhoneAlertConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.phone_alert",
+            self.config.timeout_secs.max(1),
+            10,
+        )
+    }
+
+    fn sender(&self) -> anyhow::Result<&str> {
+        self.config
+            .from
+            .as_deref()
+            .filter(|f| !f.trim().is_empty())
+            .ok_or_else(|| anyhow::anyhow!("phone_alert.from is required for this backend"))
+    }
+
+    async fn check_response(response: reqwest::Response, what: &str) -> anyhow::Result<()> {
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "{what} returned {status}: {}",
+                crate::util::truncate_with_ellipsis(body.trim(), 200)
+            );
+        }
+        Ok(())
+    }
+
+    async fn send_twilio(&self, kind: Kind, to: &str, message: &str) -> anyhow::Result<()> {
+        let twilio = self
+            .config
+            .twilio
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("[phone_alert.twilio] is not configured"))?;
+        let from = self.sender()?;
+        let base = format!(
+            "https://api.twilio.com/2010-04-01/Accounts/{}",
+            twilio.account_sid
+        );
+        let (url, form) = match kind {
+            Kind::Sms => (
+                format!("{base}/Messages.json"),
+                vec![
+                    ("To", to.to_string()),
+                    ("From", from.to_string()),
+                    ("Body", message.to_string()),
+                ],
+            ),
+            Kind::Call => (
+                format!("{base}/Calls.json"),
+                vec![
+                    ("To", to.to_string()),
+                    ("From", from.to_string()),
+                    ("Twiml", twiml_say(message)),
+                ],
+            ),
+        };
+        let response = self
+            .client()
+            .post(url)
+            .basic_auth(&twilio.account_sid, Some(&twilio.auth_token))
+            .form(&form)
+            .send()
+            .await?;
+        Self::check_response(response, "Twilio").await
+    }
+
+    async fn send_vonage(
+        &self,
+        vonage: &PhoneAlertVonageConfig,
+        kind: Kind,
+        to: &str,
+        message: &str,
+    ) -> anyhow::Result<()> {
+        let from = self.sender()?;
+        // Vonage expects numbers without the leading '+'.
+        let (to, from) = (to.trim_start_matches('+'), from.trim_start_matches('+'));
+        match kind {
+            Kind::Sms => {
+                let response = self
+                    .client()
+                    .post("https://rest.nexmo.com/sms/json")
+                    .form(&[
+                        ("api_key", vonage.api_key.as_str()),
+                        ("api_secret", vonage.api_secret.as_str()),
+                        ("from", from),
+                        ("to", to),
+                        ("text", message),
+                    ])
+                    .send()
+                    .await?;
+                let status = response.status();
+                let body: serde_json::Value = response.json().await.unwrap_or_default();
+                // The SMS API answers 200 with a per-message status.
+                let message_status = body["messages"][0]["status"].as_str().unwrap_or("?");
+                if !status.is_success() || message_status != "0" {
+                    anyhow::bail!(
+                        "Vonage SMS failed ({status}): {}",
+                        body["messages"][0]["error-text"]
+                            .as_str()
+                            .unwrap_or(message_status)
+                    );
+                }
+                Ok(())
+            }
+            Kind::Call => {
+                let (Some(application_id), Some(key_path)) =
+                    (&vonage.application_id, &vonage.private_key_path)
+                else {
+                    anyhow::bail!(
+                        "Vonage calls need phone_alert.vonage.application_id and private_key_path"
+                    );
+                };
+                let pem = tokio::fs::read_to_string(key_path).await.map_err(|e| {
+                    anyhow::anyhow!("Failed to read Vonage private key {key_path}: {e}")
+                })?;
+                let jwt = vonage_jwt(application_id, &pem, chrono::Utc::now().timestamp())?;
+                let response = self
+                    .client()
+                    .post("https://api.nexmo.com/v1/calls")
+                    .bearer_auth(jwt)
+                    .json(&json!({
+                        "to": [{"type": "phone", "number": to}],
+                        "from": {"type": "phone", "number": from},
+                        "ncco": [{"action": "talk", "text": message, "loop": 2}],
+                    }))
+                    .send()
+                    .await?;
+                Self::check_response(response, "Vonage").await
+            }
+        }
+    }
+
+    async fn send(&self, kind: Kind, to: &str, message: &str) -> anyhow::Result<()> {
+        // SMS and text-to-speech both want the agent's Markdown stripped.
+        let message = &FormatProfile::PlainText.render(message);
+        match self.config.backend.as_str() {
+            "twilio" => self.send_twilio(kind, to, message).await,
+            "vonage" => {
+                let vonage = self
+                    .config
+                    .vonage
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("[phone_alert.vonage] is not configured"))?;
+                self.send_vonage(vonage, kind, to, message).await
+            }
+            "modem" => {
+                let modem = self
+                    .config
+                    .modem
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("[phone_alert.modem] is not configured"))?;
+                modem::send(modem, kind, to, message).await
+            }
+            other => anyhow::bail!(
+                "Unknown phone_alert backend '{other}'. Use 'twilio', 'vonage', or 'modem'."
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "hardware")]
+mod modem {
+    use super::Kind;
+    use crate::config::PhoneAlertModemConfig;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::time::{timeout, Duration};
+    use tokio_serial::{SerialPortBuilderExt, SerialStream};
+
+    const CTRL_Z: u8 = 0x1A;
+    /// Single-part GSM-7 SMS length; longer alerts go out as several texts.
+    const SMS_MAX_CHARS: usize = 160;
+
+    /// Read until one of `markers` shows up in the modem's reply.
+    async fn expect(
+        port: &mut SerialStream,
+        markers: &[&str],
+        secs: u64,
+    ) -> anyhow::Result<String> {
+        let mut reply = String::new();
+        let mut buf = [0u8; 256];
+        timeout(Duration::from_secs(secs), async {
+            loop {
+                let n = port.read(&mut buf).await?;
+                if n == 0 {
+                    anyhow::bail!("Modem closed the connection");
+                }
+                reply.push_str(&String::from_utf8_lossy(&buf[..n]));
+                if reply.contains("ERROR") {
+                    anyhow::bail!("Modem error: {}", reply.trim());
+                }
+                if markers.iter().any(|m| reply.contains(m)) {
+                    return Ok(());
+                }
+            }
+        })
+        .await
+        .map_err(|_| anyhow::anyhow!("Modem did not answer in {secs}s: {}", reply.trim()))??;
+        Ok(reply)
+    }
+
+    async fn command(port: &mut SerialStream, cmd: &str) -> anyhow::Result<String> {
+        port.write_all(format!("{cmd}\r").as_bytes()).await?;
+        expect(port, &["OK"], 10).await
+    }
+
+    pub(super) async fn send(
+        config: &PhoneAlertModemConfig,
+        kind: Kind,
+        to: &str,
+        message: &str,
+    ) -> anyhow::Result<()> {
+        let mut port = tokio_serial::new(&config.device, config.baud)
+            .open_native_async()
+            .map_err(|e| anyhow::anyhow!("Failed to open {}: {e}", config.device))?;
+        command(&mut port, "AT").await?;
+        match kind {
+            Kind::Sms => {
+                command(&mut port, "AT+CMGF=1").await?;
+                let message = message.replace(char::from(CTRL_Z), "");
+                for part in crate::channels::format::split(&message, SMS_MAX_CHARS) {
+                    port.write_all(format!("AT+CMGS=\"{to}\"\r").as_bytes())
+                        .await?;
+                    expect(&mut port, &[">"], 10).await?;
+                    let mut body = part.into_bytes();
+                    body.push(CTRL_Z);
+                    port.write_all(&body).await?;
+                    expect(&mut port, &["+CMGS", "OK"], 60).await?;
+                }
+            }
+            Kind::Call => {
+                // Plain modems cannot speak; the call rings the phone and hangs up.
+                command(&mut port, &format!("ATD{to};")).await?;
+                tokio::time::sleep(Duration::from_secs(config.ring_secs.max(5))).await;
+                command(&mut port, "ATH").await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "hardware"))]
+mod modem {
+    use super::Kind;
+    use crate::config::PhoneAlertModemConfig;
+
+    #[allow(clippy::unused_async)]
+    pub(super) async fn send(
+        _config: &PhoneAlertModemConfig,
+        _kind: Kind,
+        _to: &str,
+        _message: &str,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("The modem backend requires building with the 'hardware' feature")
+    }
+}
+
+#[async_trait]
+impl Tool for PhoneAlertTool {
+    fn name(&self) -> &str {
+        "phone_alert"
+    }
+
+    fn description(&self) -> &str {
+        "Escalate a CRITICAL alert by SMS or a text-to-speech phone call to pre-approved \
+         numbers. Only for priority 5 emergencies (e.g. fire/leak sensors, total outage) \
+         after other notifications were not enough; use `notify` for everything else."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["sms", "call"],
+                    "description": "sms sends a text; call places a voice call that reads the message"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Short alert text"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "One configured recipient number; omit to alert all recipients"
+                },
+                "priority": {
+                    "type": "integer",
+                    "description": "Must be 5 (critical)"
+                },
+                "idempotency_key": super::idempotency::schema_property()
+            },
+            "required": ["action", "message", "priority"]
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        super::idempotency::run_once(&self.security.workspace_dir, self.name(), &args, || {
+            self.send_once(&args)
+        })
+        .await
+    }
+}
+
+impl PhoneAlertTool {
+    async fn send_once(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let kind = match args.get("action").and_then(|v| v.as_str()) {
+            Some("sms") => Kind::Sms,
+            Some("call") => Kind::Call,
+            _ => return Ok(failure("action must be 'sms' or 'call'")),
+        };
+        let message = args
+            .get("message")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'message' parameter"))?;
+        if args.get("priority").and_then(serde_json::Value::as_u64) != Some(REQUIRED_PRIORITY) {
+            return Ok(failure(
+                "phone_alert is reserved for priority 5 (critical) alerts; use notify instead",
+            ));
+        }
+        if message.chars().count() > MAX_SMS_CHARS {
+            return Ok(failure(format!(
+                "Message too long ({} chars, limit {MAX_SMS_CHARS})",
+                message.chars().count()
+            )));
+        }
+
+        let allowed: Vec<String> = self
+            .config
+            .recipients
+            .iter()
+            .map(|r| normalize_number(r))
+            .filter(|r| !r.is_empty())
+            .collect();
+        if allowed.is_empty() {
+            return Ok(failure("No [phone_alert].recipients configured"));
+        }
+        let recipients = match args.get("to").and_then(|v| v.as_str()) {
+            Some(to) => {
+                let to = normalize_number(to);
+                if !allowed.contains(&to) {
+                    return Ok(failure(format!("{to} is not in [phone_alert].recipients")));
+                }
+                vec![to]
+            }
+            None => allowed,
+        };
+
+        if !self.security.can_act() {
+            return Ok(failure("Action blocked: autonomy is read-only"));
+        }
+        if self.security.is_rate_limited() {
+            return Ok(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+        if !self.security.record_action() {
+            return Ok(failure("Rate limit exceeded: action budget exhausted"));
+        }
+
+        let mut sent = Vec::new();
+        let mut errors = Vec::new();
+        for to in &recipients {
+            match self.send(kind, to, message).await {
+                Ok(()) => sent.push(to.clone()),
+                Err(e) => errors.push(format!("{to}: {e}")),
+            }
+        }
+        let what = if kind == Kind::Sms {
+            "SMS sent"
+        } else {
+            "Call placed"
+        };
+        if sent.is_empty() {
+            return Ok(failure(errors.join("; ")));
+        }
+        crate::gateway::ws::publish_notification(
+            "alert",
+            serde_json::json!({
+                "source": "phone_alert",
+                "priority": REQUIRED_PRIORITY,
+                "message": message,
+            }),
+        );
+        Ok(ToolResult {
+            success: errors.is_empty(),
+            output: format!("{what} to {}", sent.join(", ")),
+            error: (!errors.is_empty()).then(|| errors.join("; ")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool() -> PhoneAlertTool {
+        PhoneAlertTool::new(
+            Arc::new(SecurityPolicy::default()),
+            PhoneAlertConfig {
+                enabled: true,
+                recipients: vec!["+49 171 555-0100".into()],
+                ..PhoneAlertConfig::default()
+            },
+        )
+    }
+
+    #[test]
+    fn numbers_normalize_for_allowlist_checks() {
+        assert_eq!(normalize_number("+49 171 555-0100"), "+491715550100");
+        assert_eq!(normalize_number("0049 (171) 5550100"), "+491715550100");
+        assert_eq!(normalize_number("  "), "");
+    }
+
+    #[test]
+    fn twiml_escapes_message() {
+        assert_eq!(
+            twiml_say("Leak in <garage> & basement"),
+            "<Response><Say loop=\"2\">Leak in &lt;garage&gt; &amp; basement</Say></Response>"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_low_priority_and_unlisted_numbers() {
+        let tool = tool();
+        let result = tool
+            .execute(json!({"action": "sms", "message": "disk 80%", "priority": 3}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("priority 5"));
+
+        let result = tool
+            .execute(
+                json!({"action": "call", "message": "fire", "priority": 5, "to": "+1 555 0100"}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            result.error.as_deref(),
+            Some("+15550100 is not in [phone_alert].recipients")
+        );
+    }
+}
@@ -0,0 +1,489 @@
+use super::composite::ConfDComposites;
+use super::traits::{Compensation, Tool, ToolResult};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Upper bound on steps in a single plan.
+const MAX_PLAN_STEPS: usize = 20;
+
+/// Run an ordered list of tool calls as one unit. Steps execute sequentially;
+/// when one fails, the steps that already succeeded are compensated in reverse
+/// order, using the step's explicit `compensate` call or the tool's own
+/// [`Tool::compensation`].
+pub struct PlanTool {
+    security: Arc<SecurityPolicy>,
+    /// Registry the plan steps are resolved against; already narrowed to the
+    /// caller's tool allowlist, so a plan cannot reach tools the caller lacks.
+    parent_tools: Arc<Vec<Arc<dyn Tool>>>,
+    /// `conf.d/tools` composites, looked up after `parent_tools`.
+    conf_d: Option<Arc<ConfDComposites>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlanStep {
+    tool: String,
+    #[serde(default)]
+    args: serde_json::Value,
+    #[serde(default)]
+    compensate: Option<Compensation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum StepStatus {
+    Succeeded,
+    Failed,
+    NotRun,
+    Compensated,
+    CompensationFailed,
+    NotCompensable,
+}
+
+#[derive(Debug, Serialize)]
+struct StepReport {
+    index: usize,
+    tool: String,
+    status: StepStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compensation: Option<CompensationReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct CompensationReport {
+    tool: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct PlanOutcome {
+    /// `completed`, `rolled_back`, or `rollback_incomplete`.
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failed_step: Option<usize>,
+    steps: Vec<StepReport>,
+}
+
+impl PlanTool {
+    pub fn new(security: Arc<SecurityPolicy>, parent_tools: Arc<Vec<Arc<dyn Tool>>>) -> Self {
+        Self {
+            security,
+            parent_tools,
+            conf_d: None,
+        }
+    }
+
+    pub fn with_conf_d_composites(mut self, conf_d: Arc<ConfDComposites>) -> Self {
+        self.conf_d = Some(conf_d);
+        self
+    }
+
+    fn find(&self, name: &str) -> Option<Arc<dyn Tool>> {
+        if name == "plan" {
+            return None;
+        }
+        self.parent_tools
+            .iter()
+            .find(|tool| tool.name() == name)
+            .cloned()
+            .or_else(|| {
+                self.conf_d
+                    .as_ref()?
+                    .current()
+                    .into_iter()
+                    .find(|tool| tool.name() == name)
+            })
+    }
+
+    /// Execute one call, folding `Err` into a failed [`ToolResult`].
+    async fn call(&self, name: &str, args: serde_json::Value) -> ToolResult {
+        let Some(tool) = self.find(name) else {
+            return ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Unknown tool: {name}")),
+            };
+        };
+        match tool.execute(args).await {
+            Ok(result) => result,
+            Err(e) => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Error executing {name}: {e}")),
+            },
+        }
+    }
+
+    async fn run(&self, steps: Vec<PlanStep>) -> PlanOutcome {
+        let mut reports: Vec<StepReport> = Vec::with_capacity(steps.len());
+        let mut undo: Vec<Option<Compensation>> = Vec::with_capacity(steps.len());
+        let mut failed_step = None;
+
+        for (index, step) in steps.iter().enumerate() {
+            if failed_step.is_some() {
+                reports.push(StepReport {
+                    index,
+                    tool: step.tool.clone(),
+                    status: StepStatus::NotRun,
+                    output: None,
+                    error: None,
+                    compensation: None,
+                });
+                continue;
+            }
+
+            let result = self.call(&step.tool, step.args.clone()).await;
+            if result.success {
+                undo.push(step.compensate.clone().or_else(|| {
+                    self.find(&step.tool)
+                        .and_then(|tool| tool.compensation(&step.args, &result))
+                }));
+                reports.push(StepReport {
+                    index,
+                    tool: step.tool.clone(),
+                    status: StepStatus::Succeeded,
+                    output: Some(result.output),
+                    error: None,
+                    compensation: None,
+                });
+            } else {
+                failed_step = Some(index);
+                reports.push(StepReport {
+                    index,
+                    tool: step.tool.clone(),
+                    status: StepStatus::Failed,
+                    output: (!result.output.is_empty()).then_some(result.output),
+                    error: result.error,
+                    compensation: None,
+                });
+            }
+        }
+
+        let Some(failed) = failed_step else {
+            return PlanOutcome {
+                status: "completed",
+                failed_step: None,
+                steps: reports,
+            };
+        };
+
+        let mut clean = true;
+        for index in (0..failed).rev() {
+            let report = &mut reports[index];
+            let Some(compensation) = undo[index].take() else {
+                report.status = StepStatus::NotCompensable;
+                clean = false;
+                continue;
+            };
+            let result = self
+                .call(&compensation.tool, compensation.args.clone())
+                .await;
+            report.status = if result.success {
+                StepStatus::Compensated
+            } else {
+                clean = false;
+                StepStatus::CompensationFailed
+            };
+            report.compensation = Some(CompensationReport {
+                tool: compensation.tool,
+                output: (!result.output.is_empty()).then_some(result.output),
+                error: result.error,
+            });
+        }
+
+        PlanOutcome {
+            status: if clean {
+                "rolled_back"
+            } else {
+                "rollback_incomplete"
+            },
+            failed_step: Some(failed),
+            steps: reports,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for PlanTool {
+    fn name(&self) -> &str {
+        "plan"
+    }
+
+    fn description(&self) -> &str {
+        "Run an ordered list of tool calls as one unit. Steps run one after another; if a step fails, the steps that already succeeded are undone in reverse order (via each step's 'compensate' call, or the tool's built-in undo) and a per-step outcome is returned."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "steps": {
+                    "type": "array",
+                    "description": format!("Ordered tool calls (max {MAX_PLAN_STEPS})"),
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "tool": { "type": "string", "description": "Tool name" },
+                            "args": { "type": "object", "description": "Arguments for the tool" },
+                            "compensate": {
+                                "type": "object",
+                                "description": "Optional call that undoes this step if a later step fails",
+                                "properties": {
+                                    "tool": { "type": "string" },
+                                    "args": { "type": "object" }
+                                },
+                                "required": ["tool"]
+                            }
+                        },
+                        "required": ["tool"]
+                    }
+                }
+            },
+            "required": ["steps"]
+        })
+    }
+
+    fn nested_tools(&self, args: &serde_json::Value) -> Vec<String> {
+        let Some(steps) = args.get("steps").and_then(serde_json::Value::as_array) else {
+            return Vec::new();
+        };
+        steps
+            .iter()
+            .flat_map(|step| [step.get("tool"), step.pointer("/compensate/tool")])
+            .flatten()
+            .filter_map(serde_json::Value::as_str)
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "plan")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        let steps: Vec<PlanStep> = match args.get("steps").cloned().map(serde_json::from_value) {
+            Some(Ok(steps)) => steps,
+            Some(Err(e)) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Invalid steps: {e}")),
+                })
+            }
+            None => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("Missing 'steps' parameter".into()),
+                })
+            }
+        };
+        if steps.is_empty() || steps.len() > MAX_PLAN_STEPS {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("A plan needs 1 to {MAX_PLAN_STEPS} steps")),
+            });
+        }
+
+        // Refuse the whole plan up front rather than failing halfway on a typo.
+        let unknown: Vec<&str> = steps
+            .iter()
+            .flat_map(|step| {
+                std::iter::once(step.tool.as_str())
+                    .chain(step.compensate.as_ref().map(|c| c.tool.as_str()))
+            })
+            .filter(|name| self.find(name).is_none())
+            .collect();
+        if !unknown.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Unknown tools in plan: {}", unknown.join(", "))),
+            });
+        }
+
+        let outcome = self.run(steps).await;
+        let success = outcome.status == "completed";
+        let output = serde_json::to_string_pretty(&outcome)?;
+        Ok(ToolResult {
+            success,
+            output: output.clone(),
+            error: (!success).then_some(output),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Records every call; fails when `args.fail` is true and offers an
+    /// `undo` compensation for successful calls.
+    struct RecordingTool {
+        name: &'static str,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Tool for RecordingTool {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "records calls"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            json!({ "type": "object" })
+        }
+
+        async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            let label = args["label"].as_str().unwrap_or_default().to_string();
+            self.calls
+                .lock()
+                .unwrap()
+                .push(format!("{}:{label}", self.name));
+            let fail = args["fail"].as_bool().unwrap_or(false);
+            Ok(ToolResult {
+                success: !fail,
+                output: label,
+                error: fail.then(|| "boom".into()),
+            })
+        }
+
+        fn compensation(
+            &self,
+            args: &serde_json::Value,
+            _result: &ToolResult,
+        ) -> Option<Compensation> {
+            (self.name == "write").then(|| Compensation {
+                tool: "undo".into(),
+                args: json!({ "label": args["label"] }),
+            })
+        }
+    }
+
+    fn plan_tool() -> (PlanTool, Arc<Mutex<Vec<String>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let tools: Vec<Arc<dyn Tool>> = ["write", "undo", "read"]
+            .into_iter()
+            .map(|name| {
+                Arc::new(RecordingTool {
+                    name,
+                    calls: calls.clone(),
+                }) as Arc<dyn Tool>
+            })
+            .collect();
+        (
+            PlanTool::new(Arc::new(SecurityPolicy::default()), Arc::new(tools)),
+            calls,
+        )
+    }
+
+    #[tokio::test]
+    async fn runs_all_steps_in_order() {
+        let (tool, calls) = plan_tool();
+        let result = tool
+            .execute(json!({ "steps": [
+                { "tool": "write", "args": { "label": "a" } },
+                { "tool": "read", "args": { "label": "b" } }
+            ]}))
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(*calls.lock().unwrap(), ["write:a", "read:b"]);
+        let outcome: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(outcome["status"], "completed");
+    }
+
+    #[tokio::test]
+    async fn failure_compensates_completed_steps_in_reverse() {
+        let (tool, calls) = plan_tool();
+        let result = tool
+            .execute(json!({ "steps": [
+                { "tool": "write", "args": { "label": "a" } },
+                { "tool": "read", "args": { "label": "b" },
+                  "compensate": { "tool": "undo", "args": { "label": "explicit" } } },
+                { "tool": "write", "args": { "label": "c", "fail": true } },
+                { "tool": "write", "args": { "label": "d" } }
+            ]}))
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            ["write:a", "read:b", "write:c", "undo:explicit", "undo:a"]
+        );
+        let outcome: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(outcome["status"], "rolled_back");
+        assert_eq!(outcome["failed_step"], 2);
+        let statuses: Vec<&str> = outcome["steps"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|step| step["status"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            statuses,
+            ["compensated", "compensated", "failed", "not_run"]
+        );
+    }
+
+    #[tokio::test]
+    async fn steps_without_undo_mark_rollback_incomplete() {
+        let (tool, _calls) = plan_tool();
+        let result = tool
+            .execute(json!({ "steps": [
+                { "tool": "read", "args": { "label": "a" } },
+                { "tool": "write", "args": { "label": "b", "fail": true } }
+            ]}))
+            .await
+            .unwrap();
+
+        let outcome: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(outcome["status"], "rollback_incomplete");
+        assert_eq!(outcome["steps"][0]["status"], "not_compensable");
+    }
+
+    #[tokio::test]
+    async fn unknown_tool_rejects_plan_before_running() {
+        let (tool, calls) = plan_tool();
+        let result = tool
+            .execute(json!({ "steps": [
+                { "tool": "write", "args": { "label": "a" } },
+                { "tool": "plan", "args": {} }
+            ]}))
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("plan"));
+        assert!(calls.lock().unwrap().is_empty());
+    }
+}
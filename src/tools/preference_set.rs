@@ -0,0 +1,147 @@
+use super::traits::{Tool, ToolResult};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Record a standing user preference that is added to every system prompt.
+pub struct PreferenceSetTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl PreferenceSetTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for PreferenceSetTool {
+    fn name(&self) -> &str {
+        "preference_set"
+    }
+
+    fn description(&self) -> &str {
+        "Record an explicit, standing user preference such as 'always use Celsius' or 'never post publicly without asking'. Only use when the user states how you should behave from now on, not for facts about the world. Preferences are added to every future system prompt; setting an existing key replaces it."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "key": {
+                    "type": "string",
+                    "description": "Short identifier for the preference (e.g. 'temperature_unit', 'public_posts')"
+                },
+                "preference": {
+                    "type": "string",
+                    "description": "The instruction in the user's terms, as an imperative (e.g. 'Always use Celsius')"
+                }
+            },
+            "required": ["key", "preference"]
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let key = args
+            .get("key")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'key' parameter"))?;
+
+        let preference = args
+            .get("preference")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'preference' parameter"))?;
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "preference_set")
+        {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            });
+        }
+
+        let workspace_dir = self.security.workspace_dir.clone();
+        let (key, preference) = (key.to_string(), preference.to_string());
+        let stored = key.clone();
+        match tokio::task::spawn_blocking(move || {
+            crate::preferences::set(&workspace_dir, &stored, &preference)
+        })
+        .await?
+        {
+            Ok(None) => Ok(ToolResult {
+                success: true,
+                output: format!("Stored preference: {key}"),
+                error: None,
+            }),
+            Ok(Some(previous)) => Ok(ToolResult {
+                success: true,
+                output: format!("Updated preference: {key} (was: {previous})"),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to store preference: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn security(tmp: &TempDir, autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn stores_and_updates_preferences() {
+        let tmp = TempDir::new().unwrap();
+        let tool = PreferenceSetTool::new(security(&tmp, AutonomyLevel::Supervised));
+        let result = tool
+            .execute(json!({"key": "temperature_unit", "preference": "Always use Celsius"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "Stored preference: temperature_unit");
+
+        let result = tool
+            .execute(json!({"key": "temperature_unit", "preference": "Use Kelvin"}))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.output,
+            "Updated preference: temperature_unit (was: Always use Celsius)"
+        );
+        let stored = crate::preferences::load(tmp.path()).unwrap();
+        assert_eq!(stored[0].text, "Use Kelvin");
+    }
+
+    #[tokio::test]
+    async fn blocked_in_read_only_mode() {
+        let tmp = TempDir::new().unwrap();
+        let tool = PreferenceSetTool::new(security(&tmp, AutonomyLevel::ReadOnly));
+        let result = tool
+            .execute(json!({"key": "units", "preference": "Celsius"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(crate::preferences::load(tmp.path()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::PrinterConfig;
+use crate::printer::PrinterCommand;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const MAX_SNAPSHOT_BYTES: usize = 5 * 1024 * 1024;
+
+fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+/// OctoPrint / Moonraker job status, webcam snapshot, and job control.
+pub struct PrinterTool {
+    security: Arc<SecurityPolicy>,
+    config: PrinterConfig,
+    workspace_dir: PathBuf,
+}
+
+impl PrinterTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        config: PrinterConfig,
+        workspace_dir: PathBuf,
+    ) -> Self {
+        Self {
+            security,
+            config,
+            workspace_dir,
+        }
+    }
+
+    async fn snapshot(&self) -> anyhow::Result<ToolResult> {
+        let (bytes, content_type) = crate::printer::fetch_snapshot(&self.config).await?;
+        if bytes.is_empty() {
+            return Ok(failure("Webcam returned an empty snapshot"));
+        }
+        if bytes.len() > MAX_SNAPSHOT_BYTES {
+            return Ok(failure(format!(
+                "Snapshot too large ({} bytes, limit {MAX_SNAPSHOT_BYTES})",
+                bytes.len()
+            )));
+        }
+        let extension = if content_type.contains("png") {
+            "png"
+        } else {
+            "jpg"
+        };
+        let dir = self.workspace_dir.join("printer");
+        tokio::fs::create_dir_all(&dir).await?;
+        let path = dir.join(format!(
+            "snapshot_{}.{extension}",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        ));
+        tokio::fs::write(&path, &bytes).await?;
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Saved webcam snapshot ({} bytes) to {}\n[IMAGE:{}]",
+                bytes.len(),
+                path.display(),
+                path.display()
+            ),
+            error: None,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for PrinterTool {
+    fn name(&self) -> &str {
+        "printer"
+    }
+
+    fn description(&self) -> &str {
+        "Check the 3D printer (OctoPrint / Moonraker): job progress, time remaining, and \
+         nozzle/bed temperatures; grab a webcam snapshot; or pause, resume, or cancel the \
+         current print."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["status", "snapshot", "pause", "resume", "cancel"],
+                    "description": "What to do (default: status). pause/resume/cancel change the running job."
+                }
+            }
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("status");
+
+        if let Some(command) = PrinterCommand::parse(action) {
+            if !self.security.can_act() {
+                return Ok(failure("Action blocked: autonomy is read-only"));
+            }
+            if self.security.is_rate_limited() {
+                return Ok(failure(
+                    "Rate limit exceeded: too many actions in the last hour",
+                ));
+            }
+            if !self.security.record_action() {
+                return Ok(failure("Rate limit exceeded: action budget exhausted"));
+            }
+            return match crate::printer::send_command(&self.config, command).await {
+                Ok(()) => Ok(ToolResult {
+                    success: true,
+                    output: format!("Sent {action} to the printer"),
+                    error: None,
+                }),
+                Err(e) => Ok(failure(e.to_string())),
+            };
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        match action {
+            "status" => match crate::printer::fetch_status(&self.config).await {
+                Ok(status) => Ok(ToolResult {
+                    success: true,
+                    output: crate::printer::format_status(&status),
+                    error: None,
+                }),
+                Err(e) => Ok(failure(format!("Failed to read printer status: {e}"))),
+            },
+            "snapshot" => match self.snapshot().await {
+                Ok(result) => Ok(result),
+                Err(e) => Ok(failure(format!("Failed to capture snapshot: {e}"))),
+            },
+            other => Ok(failure(format!(
+                "Unknown action '{other}'. Use status, snapshot, pause, resume, or cancel."
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    #[tokio::test]
+    async fn job_control_is_blocked_in_read_only_mode() {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            ..SecurityPolicy::default()
+        });
+        let config = PrinterConfig {
+            enabled: true,
+            url: "http://127.0.0.1:9".into(),
+            ..PrinterConfig::default()
+        };
+        let tool = PrinterTool::new(security, config, std::env::temp_dir());
+        let result = tool.execute(json!({"action": "cancel"})).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(
+            result.error.as_deref(),
+            Some("Action blocked: autonomy is read-only")
+        );
+
+        let result = tool.execute(json!({"action": "explode"})).await.unwrap();
+        assert!(result.error.unwrap().starts_with("Unknown action"));
+    }
+}
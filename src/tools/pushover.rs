@@ -105,13 +105,27 @@ impl Tool for PushoverTool {
                 "sound": {
                     "type": "string",
                     "description": "Notification sound override (e.g., 'pushover', 'bike', 'bugle', 'cashregister', etc.)"
-                }
+                },
+                "idempotency_key": super::idempotency::schema_property()
             },
             "required": ["message"]
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        super::idempotency::run_once(&self.security.workspace_dir, self.name(), &args, || {
+            self.send_once(&args)
+        })
+        .await
+    }
+}
+
+impl PushoverTool {
+    async fn send_once(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
         if !self.security.can_act() {
             return Ok(ToolResult {
                 success: false,
@@ -0,0 +1,508 @@
+use super::code_exec::{force_remove_container, sandbox_run_args};
+use super::traits::{Tool, ToolResult};
+use crate::config::{CodeExecConfig, PythonReplConfig};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
+
+/// Maximum characters of stdout/stderr returned per call.
+const MAX_OUTPUT_CHARS: usize = 100_000;
+/// Session slot for turns that run outside a conversation scope.
+const DEFAULT_CONVERSATION: &str = "default";
+
+/// Driver executed by the interpreter: reads one JSON request per line, runs
+/// it against a persistent namespace, and echoes the value of a trailing
+/// expression like a notebook cell.
+const PYTHON_DRIVER: &str = r#"
+import ast, contextlib, io, json, sys, traceback
+ns = {"__name__": "__zeroclaw_repl__"}
+limit = int(sys.argv[1])
+for line in sys.stdin:
+    code = json.loads(line)["code"]
+    out, err, ok = io.StringIO(), io.StringIO(), True
+    with contextlib.redirect_stdout(out), contextlib.redirect_stderr(err):
+        try:
+            tree = ast.parse(code, "<repl>", "exec")
+            last = tree.body.pop() if tree.body and isinstance(tree.body[-1], ast.Expr) else None
+            exec(compile(tree, "<repl>", "exec"), ns)
+            if last is not None:
+                value = eval(compile(ast.Expression(last.value), "<repl>", "eval"), ns)
+                if value is not None:
+                    print(repr(value))
+        except BaseException:
+            ok = False
+            traceback.print_exc()
+    sys.__stdout__.write(json.dumps({"ok": ok, "stdout": out.getvalue()[:limit], "stderr": err.getvalue()[:limit]}) + "\n")
+    sys.__stdout__.flush()
+"#;
+
+struct PythonReplSession {
+    child: Child,
+    container_name: String,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl PythonReplSession {
+    async fn shut_down(&mut self, runtime: &str) {
+        let _ = self.child.start_kill();
+        // Killing the client does not stop a detached container.
+        force_remove_container(runtime, &self.container_name).await;
+    }
+}
+
+struct SessionSlot {
+    session: Arc<Mutex<PythonReplSession>>,
+    last_used: Instant,
+}
+
+/// (workspace, conversation) → session. Process-wide because turns build
+/// fresh tool registries, and a conversation must find its interpreter again.
+type SessionTable = parking_lot::Mutex<HashMap<(PathBuf, String), SessionSlot>>;
+
+static SESSIONS: OnceLock<SessionTable> = OnceLock::new();
+
+fn sessions() -> &'static SessionTable {
+    SESSIONS.get_or_init(|| parking_lot::Mutex::new(HashMap::new()))
+}
+
+/// Persistent Python interpreters, one per conversation.
+///
+/// At most `max_sessions` run per workspace; opening another evicts the least
+/// recently used one. Each session is a long-lived `python3` process inside the `[code_exec]`
+/// sandbox container (no network, read-only root, dropped capabilities,
+/// resource limits, workspace at `/workspace`), driven by `PYTHON_DRIVER`
+/// over line-delimited JSON on stdin/stdout. It is not a Jupyter kernel:
+/// there is no rich display output and no interrupt, so a call that exceeds
+/// the timeout discards the whole session.
+pub struct PythonReplTool {
+    security: Arc<SecurityPolicy>,
+    config: PythonReplConfig,
+    sandbox: CodeExecConfig,
+}
+
+impl PythonReplTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        config: PythonReplConfig,
+        sandbox: CodeExecConfig,
+    ) -> Self {
+        Self {
+            security,
+            config,
+            sandbox,
+        }
+    }
+
+    fn build_run_args(&self, container_name: &str) -> Vec<String> {
+        let mut args = sandbox_run_args(
+            &self.sandbox,
+            &self.security.workspace_dir,
+            container_name,
+            &self.sandbox.python_image,
+        );
+        args.extend([
+            "python3".into(),
+            "-u".into(),
+            "-c".into(),
+            PYTHON_DRIVER.into(),
+            MAX_OUTPUT_CHARS.to_string(),
+        ]);
+        args
+    }
+
+    fn spawn_session(&self) -> anyhow::Result<PythonReplSession> {
+        let container_name = format!("zeroclaw-repl-{}", uuid::Uuid::new_v4().simple());
+        let mut child = Command::new(&self.sandbox.container_runtime)
+            .args(self.build_run_args(&container_name))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Failed to start '{}' sandbox: {e}",
+                    self.sandbox.container_runtime
+                )
+            })?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("REPL stdin unavailable"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("REPL stdout unavailable"))?;
+
+        Ok(PythonReplSession {
+            child,
+            container_name,
+            stdin,
+            stdout: BufReader::new(stdout),
+        })
+    }
+
+    fn key(&self, conversation: &str) -> (PathBuf, String) {
+        (
+            self.security.workspace_dir.clone(),
+            conversation.to_string(),
+        )
+    }
+
+    fn session(&self, conversation: &str) -> anyhow::Result<Arc<Mutex<PythonReplSession>>> {
+        let key = self.key(conversation);
+        let (session, evicted) = {
+            let mut table = sessions().lock();
+            if let Some(slot) = table.get_mut(&key) {
+                slot.last_used = Instant::now();
+                return Ok(Arc::clone(&slot.session));
+            }
+            let open = table.keys().filter(|(dir, _)| *dir == key.0).count();
+            let evicted = if open >= self.config.max_sessions.max(1) {
+                let oldest = table
+                    .iter()
+                    .filter(|((dir, _), _)| *dir == key.0)
+                    .min_by_key(|(_, slot)| slot.last_used)
+                    .map(|(key, _)| key.clone());
+                oldest.and_then(|oldest| table.remove(&oldest))
+            } else {
+                None
+            };
+            let session = Arc::new(Mutex::new(self.spawn_session()?));
+            table.insert(
+                key,
+                SessionSlot {
+                    session: Arc::clone(&session),
+                    last_used: Instant::now(),
+                },
+            );
+            (session, evicted)
+        };
+        if let Some(slot) = evicted {
+            let runtime = self.sandbox.container_runtime.clone();
+            // The evicted session may still be finishing a call.
+            tokio::spawn(async move { slot.session.lock().await.shut_down(&runtime).await });
+        }
+        Ok(session)
+    }
+
+    async fn discard(&self, conversation: &str) -> bool {
+        let removed = sessions().lock().remove(&self.key(conversation));
+        match removed {
+            Some(slot) => {
+                slot.session
+                    .lock()
+                    .await
+                    .shut_down(&self.sandbox.container_runtime)
+                    .await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn run_code(&self, conversation: &str, code: &str) -> anyhow::Result<ToolResult> {
+        let session = self.session(conversation)?;
+        let mut session = session.lock().await;
+
+        let mut request = serde_json::to_string(&json!({ "code": code }))?;
+        request.push('\n');
+
+        let timeout = Duration::from_secs(self.config.timeout_secs.max(1));
+        let exchange = async {
+            session.stdin.write_all(request.as_bytes()).await?;
+            session.stdin.flush().await?;
+            let mut line = String::new();
+            let read = session.stdout.read_line(&mut line).await?;
+            if read == 0 {
+                anyhow::bail!("REPL session exited unexpectedly");
+            }
+            Ok::<String, anyhow::Error>(line)
+        };
+
+        let line = match tokio::time::timeout(timeout, exchange).await {
+            Ok(Ok(line)) => line,
+            Ok(Err(e)) => {
+                drop(session);
+                self.discard(conversation).await;
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("{e}; the session was reset")),
+                });
+            }
+            Err(_) => {
+                drop(session);
+                self.discard(conversation).await;
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Execution timed out after {}s; the session was reset",
+                        timeout.as_secs()
+                    )),
+                });
+            }
+        };
+
+        let reply: serde_json::Value = serde_json::from_str(line.trim())?;
+        let stdout = reply["stdout"].as_str().unwrap_or_default().to_string();
+        let stderr = reply["stderr"].as_str().unwrap_or_default().to_string();
+        Ok(ToolResult {
+            success: reply["ok"].as_bool().unwrap_or(false),
+            output: stdout,
+            error: if stderr.is_empty() {
+                None
+            } else {
+                Some(stderr)
+            },
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for PythonReplTool {
+    fn name(&self) -> &str {
+        "python_repl"
+    }
+
+    fn description(&self) -> &str {
+        "Run Python in a persistent interpreter inside an isolated container with no network \
+         access; the workspace is mounted at /workspace. Variables, imports, and functions \
+         survive between calls in this conversation, so multi-step analysis can build on \
+         earlier results. The value of a trailing expression is printed."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["execute", "reset"],
+                    "description": "execute code (default) or reset this conversation's session"
+                },
+                "code": {
+                    "type": "string",
+                    "description": "Python code to run (required for execute)"
+                }
+            }
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("execute");
+        let conversation =
+            super::current_conversation().unwrap_or_else(|| DEFAULT_CONVERSATION.into());
+
+        match action {
+            "reset" => {
+                let existed = self.discard(&conversation).await;
+                Ok(ToolResult {
+                    success: true,
+                    output: if existed {
+                        "Session reset".into()
+                    } else {
+                        "No session was open".into()
+                    },
+                    error: None,
+                })
+            }
+            "execute" => {
+                let code = args
+                    .get("code")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow::anyhow!("Missing 'code' parameter"))?;
+
+                if !self.security.can_act() {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some("Action blocked: autonomy is read-only".into()),
+                    });
+                }
+
+                if self.security.is_rate_limited() {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(
+                            "Rate limit exceeded: too many actions in the last hour".into(),
+                        ),
+                    });
+                }
+
+                if !self.security.record_action() {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some("Rate limit exceeded: action budget exhausted".into()),
+                    });
+                }
+
+                match self.run_code(&conversation, code).await {
+                    Ok(result) => Ok(result),
+                    Err(e) => Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(e.to_string()),
+                    }),
+                }
+            }
+            other => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Unknown action '{other}'. Use 'execute' or 'reset'."
+                )),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_security(level: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: level,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn tool(level: AutonomyLevel, config: PythonReplConfig) -> PythonReplTool {
+        PythonReplTool::new(test_security(level), config, CodeExecConfig::default())
+    }
+
+    /// A container runtime stand-in that runs the in-container command on the
+    /// host, so session handling can be tested without podman or docker.
+    #[cfg(unix)]
+    fn host_runtime_tool(dir: &std::path::Path, config: PythonReplConfig) -> PythonReplTool {
+        use std::os::unix::fs::PermissionsExt;
+
+        let runtime = dir.join("fake-runtime");
+        std::fs::write(
+            &runtime,
+            "#!/bin/sh\ncase \"$1\" in run) while [ \"$1\" != python3 ]; do shift; done; exec \"$@\" ;; esac\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&runtime, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let sandbox = CodeExecConfig {
+            container_runtime: runtime.display().to_string(),
+            ..CodeExecConfig::default()
+        };
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: dir.to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        PythonReplTool::new(security, config, sandbox)
+    }
+
+    fn python_available() -> bool {
+        which::which("python3").is_ok()
+    }
+
+    #[test]
+    fn python_repl_tool_name_and_schema() {
+        let tool = tool(AutonomyLevel::Full, PythonReplConfig::default());
+        assert_eq!(tool.name(), "python_repl");
+        assert!(tool.parameters_schema()["properties"]["code"].is_object());
+    }
+
+    #[test]
+    fn python_repl_runs_in_the_code_exec_sandbox() {
+        let tool = tool(AutonomyLevel::Full, PythonReplConfig::default());
+        let joined = tool.build_run_args("repl-1").join(" ");
+        assert!(joined.contains("--network none"));
+        assert!(joined.contains("--read-only"));
+        assert!(joined.contains("--cap-drop ALL"));
+        assert!(joined.contains("python:3.12-alpine python3 -u -c"));
+    }
+
+    #[tokio::test]
+    async fn python_repl_blocks_readonly_mode() {
+        let tool = tool(AutonomyLevel::ReadOnly, PythonReplConfig::default());
+        let result = tool.execute(json!({"code": "1 + 1"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn python_repl_keeps_state_per_conversation() {
+        if !python_available() {
+            return;
+        }
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = host_runtime_tool(tmp.path(), PythonReplConfig::default());
+        let in_conversation = |id: &str, args: serde_json::Value| {
+            super::super::CONVERSATION_ID.scope(id.to_string(), tool.execute(args))
+        };
+
+        let first = in_conversation("chat-a", json!({"code": "x = 20"}))
+            .await
+            .unwrap();
+        assert!(first.success, "{:?}", first.error);
+
+        let second = in_conversation("chat-a", json!({"code": "x + 22"}))
+            .await
+            .unwrap();
+        assert!(second.success);
+        assert_eq!(second.output.trim(), "42");
+
+        let other = in_conversation("chat-b", json!({"code": "x"}))
+            .await
+            .unwrap();
+        assert!(!other.success);
+        assert!(other.error.unwrap().contains("NameError"));
+
+        let reset = in_conversation("chat-a", json!({"action": "reset"}))
+            .await
+            .unwrap();
+        assert_eq!(reset.output, "Session reset");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn python_repl_evicts_least_recently_used_session() {
+        if !python_available() {
+            return;
+        }
+        let tmp = tempfile::TempDir::new().unwrap();
+        let config = PythonReplConfig {
+            max_sessions: 1,
+            ..PythonReplConfig::default()
+        };
+        let tool = host_runtime_tool(tmp.path(), config);
+        let in_conversation = |id: &str, code: &str| {
+            super::super::CONVERSATION_ID.scope(id.to_string(), tool.execute(json!({"code": code})))
+        };
+
+        assert!(in_conversation("a", "x = 1").await.unwrap().success);
+        assert!(in_conversation("b", "y = 2").await.unwrap().success);
+        let evicted = in_conversation("a", "x").await.unwrap();
+        assert!(!evicted.success);
+        assert!(evicted.error.unwrap().contains("NameError"));
+    }
+}
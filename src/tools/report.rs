@@ -0,0 +1,782 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::LocaleConfig;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use minijinja::value::Value as TemplateValue;
+use minijinja::{AutoEscape, Environment, State};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Workspace-relative directory searched for named templates.
+pub const REPORT_TEMPLATES_DIR: &str = "reports/templates";
+/// Maximum template size accepted (256KB).
+const MAX_TEMPLATE_BYTES: usize = 262_144;
+/// Instruction budget so a bad template cannot spin forever on huge inputs.
+const TEMPLATE_FUEL: u64 = 10_000_000;
+/// Workspace-relative directory holding `history_key` snapshots.
+pub const REPORT_HISTORY_DIR: &str = "reports/history";
+/// Snapshots older than this are dropped when a new one is stored.
+const HISTORY_RETENTION_DAYS: i64 = 35;
+
+/// Render structured data through user-defined templates into workspace files.
+pub struct ReportTool {
+    security: Arc<SecurityPolicy>,
+    locale: LocaleConfig,
+}
+
+impl ReportTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            security,
+            locale: LocaleConfig::default(),
+        }
+    }
+
+    /// Conventions used by the `datetime`, `temperature`, `distance` and
+    /// `money` filters, exposed to templates as `locale`.
+    pub fn with_locale(mut self, locale: LocaleConfig) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    fn failure(message: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    /// Resolve a template name to its source. Bare names are looked up in
+    /// `reports/templates/`, with `.j2` / `.jinja` suffixes tried in order.
+    async fn load_template(&self, name: &str) -> Result<String, String> {
+        let candidates: Vec<String> = if name.contains('/') {
+            vec![name.to_string()]
+        } else {
+            ["", ".j2", ".jinja"]
+                .iter()
+                .map(|ext| format!("{REPORT_TEMPLATES_DIR}/{name}{ext}"))
+                .collect()
+        };
+
+        for candidate in candidates {
+            if !self.security.is_path_allowed(&candidate) {
+                return Err(format!("Path not allowed by security policy: {candidate}"));
+            }
+            let full_path = self.security.workspace_dir.join(&candidate);
+            let Ok(resolved) = tokio::fs::canonicalize(&full_path).await else {
+                continue;
+            };
+            if !self.security.is_resolved_path_allowed(&resolved) {
+                return Err(self.security.resolved_path_violation_message(&resolved));
+            }
+            let source = tokio::fs::read_to_string(&resolved)
+                .await
+                .map_err(|e| format!("Failed to read template {candidate}: {e}"))?;
+            if source.len() > MAX_TEMPLATE_BYTES {
+                return Err(format!(
+                    "Template too large: {} bytes (limit: {MAX_TEMPLATE_BYTES} bytes)",
+                    source.len()
+                ));
+            }
+            return Ok(source);
+        }
+
+        Err(format!(
+            "Template '{name}' not found (looked in {REPORT_TEMPLATES_DIR}/)"
+        ))
+    }
+
+    /// Write the rendered report, applying the same sandboxing as `file_write`.
+    async fn write_output(&self, path: &str, content: &str) -> Result<(), String> {
+        if !self.security.is_path_allowed(path) {
+            return Err(format!("Path not allowed by security policy: {path}"));
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+        let (Some(parent), Some(file_name)) = (full_path.parent(), full_path.file_name()) else {
+            return Err("Invalid output path".into());
+        };
+
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create output directory: {e}"))?;
+        let resolved_parent = tokio::fs::canonicalize(parent)
+            .await
+            .map_err(|e| format!("Failed to resolve output path: {e}"))?;
+        if !self.security.is_resolved_path_allowed(&resolved_parent) {
+            return Err(self
+                .security
+                .resolved_path_violation_message(&resolved_parent));
+        }
+
+        let resolved_target = resolved_parent.join(file_name);
+        if let Ok(meta) = tokio::fs::symlink_metadata(&resolved_target).await {
+            if meta.file_type().is_symlink() {
+                return Err(format!(
+                    "Refusing to write through symlink: {}",
+                    resolved_target.display()
+                ));
+            }
+        }
+
+        tokio::fs::write(&resolved_target, content)
+            .await
+            .map_err(|e| format!("Failed to write report: {e}"))
+    }
+
+    fn history_path(&self, key: &str) -> Result<PathBuf, String> {
+        if key.is_empty()
+            || key.len() > 64
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+        {
+            return Err(format!(
+                "Invalid history_key '{key}': use letters, digits, '_' or '-'"
+            ));
+        }
+        let relative = format!("{REPORT_HISTORY_DIR}/{key}.jsonl");
+        if !self.security.is_path_allowed(&relative) {
+            return Err(format!("Path not allowed by security policy: {relative}"));
+        }
+        Ok(self.security.workspace_dir.join(relative))
+    }
+
+    /// Stored snapshots for `key`, oldest first; unreadable lines are skipped.
+    async fn load_history(&self, key: &str) -> Result<Vec<Snapshot>, String> {
+        let path = self.history_path(key)?;
+        let Ok(raw) = tokio::fs::read_to_string(&path).await else {
+            return Ok(Vec::new());
+        };
+        Ok(raw
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Append `data` to the history of `key`, dropping expired snapshots.
+    async fn store_history(
+        &self,
+        key: &str,
+        mut snapshots: Vec<Snapshot>,
+        data: Value,
+        now: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let path = self.history_path(key)?;
+        snapshots.retain(|s| now - s.at <= Duration::days(HISTORY_RETENTION_DAYS));
+        snapshots.push(Snapshot { at: now, data });
+        let mut content = String::new();
+        for snapshot in &snapshots {
+            content.push_str(&serde_json::to_string(snapshot).map_err(|e| e.to_string())?);
+            content.push('\n');
+        }
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create history directory: {e}"))?;
+        }
+        tokio::fs::write(&path, content)
+            .await
+            .map_err(|e| format!("Failed to write report history: {e}"))
+    }
+}
+
+// ── Trends ──────────────────────────────────────────────────────
+//
+// With `history_key`, each call's `data` is kept in
+// `reports/history/<key>.jsonl`. Numeric values are compared with the newest
+// snapshot that is about a day old and the newest about a week old, so a
+// recurring briefing can say "latency up 30% vs yesterday" instead of
+// repeating absolute numbers.
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    at: DateTime<Utc>,
+    data: Value,
+}
+
+/// Newest snapshot whose age is within `min_hours..=max_hours`.
+fn snapshot_aged(
+    snapshots: &[Snapshot],
+    now: DateTime<Utc>,
+    min_hours: i64,
+    max_hours: i64,
+) -> Option<&Value> {
+    snapshots
+        .iter()
+        .filter(|s| {
+            let age = now - s.at;
+            age >= Duration::hours(min_hours) && age <= Duration::hours(max_hours)
+        })
+        .max_by_key(|s| s.at)
+        .map(|s| &s.data)
+}
+
+/// Numeric leaves of `value` keyed by dotted path (`hosts[0].latency_ms`).
+fn numeric_leaves(value: &Value, path: &str, out: &mut BTreeMap<String, f64>) {
+    match value {
+        Value::Number(n) => {
+            if let Some(n) = n.as_f64() {
+                out.insert(path.to_string(), n);
+            }
+        }
+        Value::Object(map) => {
+            for (key, child) in map {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                numeric_leaves(child, &child_path, out);
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                numeric_leaves(child, &format!("{path}[{i}]"), out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Percent change (`None` from zero) and its wording.
+fn change(current: f64, previous: f64) -> (Option<f64>, String) {
+    let direction = if current > previous { "up" } else { "down" };
+    if (current - previous).abs() < f64::EPSILON {
+        (Some(0.0), "unchanged".into())
+    } else if previous.abs() < f64::EPSILON {
+        (None, format!("{direction} from 0"))
+    } else {
+        let pct = (current - previous) / previous.abs() * 100.0;
+        (
+            Some((pct * 10.0).round() / 10.0),
+            format!("{direction} {:.0}%", pct.abs()),
+        )
+    }
+}
+
+/// One entry per numeric value that has a day- or week-old counterpart.
+fn trends(current: &Value, day: Option<&Value>, week: Option<&Value>) -> Vec<Value> {
+    let leaves = |value: &Value| {
+        let mut out = BTreeMap::new();
+        numeric_leaves(value, "", &mut out);
+        out
+    };
+    let day = day.map(leaves);
+    let week = week.map(leaves);
+
+    let mut entries = Vec::new();
+    for (path, value) in leaves(current) {
+        let mut entry = json!({"path": path, "current": value});
+        let mut parts = Vec::new();
+        for (field, label, previous) in [("day", "yesterday", &day), ("week", "last week", &week)] {
+            let Some(previous) = previous.as_ref().and_then(|m| m.get(&path)) else {
+                continue;
+            };
+            let (pct, words) = change(value, *previous);
+            entry[field] = json!({"previous": previous, "change_pct": pct});
+            parts.push(format!("{words} vs {label}"));
+        }
+        if parts.is_empty() {
+            continue;
+        }
+        entry["summary"] = json!(format!("{path} {value} ({})", parts.join(", ")));
+        entries.push(entry);
+    }
+    entries
+}
+
+fn is_html_path(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"))
+}
+
+#[async_trait]
+impl Tool for ReportTool {
+    fn name(&self) -> &str {
+        "report"
+    }
+
+    fn description(&self) -> &str {
+        "Render JSON data through a Jinja template (minijinja) into a markdown or HTML file in \
+         the workspace. Templates live in reports/templates/ (or pass template_text inline) and \
+         support the usual {{ value | filter }}, {% if %}, {% for %} and built-in filters. \
+         Values are HTML-escaped automatically when the output ends in .html. Filters datetime \
+         (RFC 3339 or unix seconds), temperature (Celsius, or temperature('f')), distance (km) \
+         and money (optional currency code) format values in the user's locale."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "template": {
+                    "type": "string",
+                    "description": "Template name in reports/templates/ (e.g. 'weekly_infra.md') or a workspace path"
+                },
+                "template_text": {
+                    "type": "string",
+                    "description": "Inline template source, used instead of 'template'"
+                },
+                "data": {
+                    "type": "object",
+                    "description": "Values exposed to the template as top-level variables"
+                },
+                "output": {
+                    "type": "string",
+                    "description": "Workspace path for the rendered report (.md, .html, ...)"
+                },
+                "history_key": {
+                    "type": "string",
+                    "description": "Name of a recurring report (e.g. 'morning_briefing'); keeps 'data' and exposes day-over-day and week-over-week changes as 'trend' / 'trend_summary'"
+                }
+            },
+            "required": ["output"]
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let output_path = args
+            .get("output")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'output' parameter"))?;
+        let template_name = args.get("template").and_then(|v| v.as_str());
+        let template_text = args.get("template_text").and_then(|v| v.as_str());
+        let history_key = args.get("history_key").and_then(|v| v.as_str());
+        let data = match args.get("data") {
+            None | Some(Value::Null) => Map::new(),
+            Some(Value::Object(map)) => map.clone(),
+            Some(_) => return Ok(Self::failure("'data' must be a JSON object")),
+        };
+
+        if !self.security.can_act() {
+            return Ok(Self::failure("Action blocked: autonomy is read-only"));
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(Self::failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        let source = match (template_text, template_name) {
+            (Some(text), _) => {
+                if text.len() > MAX_TEMPLATE_BYTES {
+                    return Ok(Self::failure(format!(
+                        "Template too large: {} bytes (limit: {MAX_TEMPLATE_BYTES} bytes)",
+                        text.len()
+                    )));
+                }
+                text.to_string()
+            }
+            (None, Some(name)) => match self.load_template(name).await {
+                Ok(source) => source,
+                Err(e) => return Ok(Self::failure(e)),
+            },
+            (None, None) => {
+                return Ok(Self::failure(
+                    "Provide either 'template' or 'template_text'",
+                ));
+            }
+        };
+
+        let now = Utc::now();
+        let history = match history_key {
+            Some(key) => match self.load_history(key).await {
+                Ok(snapshots) => Some((key, snapshots)),
+                Err(e) => return Ok(Self::failure(e)),
+            },
+            None => None,
+        };
+        let snapshot = Value::Object(data.clone());
+        let trend = history.as_ref().map_or_else(Vec::new, |(_, snapshots)| {
+            trends(
+                &snapshot,
+                snapshot_aged(snapshots, now, 20, 48),
+                snapshot_aged(snapshots, now, 156, 240),
+            )
+        });
+        let trend_summary = trend
+            .iter()
+            .filter_map(|entry| entry["summary"].as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut context = data;
+        context
+            .entry("generated_at")
+            .or_insert_with(|| Value::String(now.to_rfc3339()));
+        context
+            .entry("locale")
+            .or_insert_with(|| serde_json::to_value(&self.locale).unwrap_or(Value::Null));
+        if history.is_some() {
+            context
+                .entry("trend")
+                .or_insert_with(|| Value::Array(trend.clone()));
+            context
+                .entry("trend_summary")
+                .or_insert_with(|| Value::String(trend_summary.clone()));
+        }
+
+        let rendered =
+            match render_template(&source, &Value::Object(context), is_html_path(output_path)) {
+                Ok(rendered) => rendered,
+                Err(e) => return Ok(Self::failure(e.to_string())),
+            };
+
+        if !self.security.record_action() {
+            return Ok(Self::failure(
+                "Rate limit exceeded: action budget exhausted",
+            ));
+        }
+
+        if let Err(e) = self.write_output(output_path, &rendered).await {
+            return Ok(Self::failure(e));
+        }
+        let mut output = format!("Rendered {} bytes to {output_path}", rendered.len());
+        if let Some((key, snapshots)) = history {
+            if let Err(e) = self.store_history(key, snapshots, snapshot, now).await {
+                return Ok(Self::failure(e));
+            }
+            if trend_summary.is_empty() {
+                let _ = write!(
+                    output,
+                    "\nNo day- or week-old snapshot of '{key}' to compare with yet"
+                );
+            } else {
+                let _ = write!(output, "\nChanges:\n{trend_summary}");
+            }
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+// ── Template engine ─────────────────────────────────────────────
+//
+// Templates are rendered with minijinja, so the full Jinja syntax and its
+// built-in filters are available. Blocks trim the newline that follows them
+// (`trim_blocks`) so loops over markdown table rows render cleanly, and the
+// locale-aware filters below are registered on top.
+
+/// Render `template` against `context` (a JSON object). With `autoescape`,
+/// output expressions are HTML-escaped unless marked `safe`.
+pub(crate) fn render_template(
+    template: &str,
+    context: &Value,
+    autoescape: bool,
+) -> anyhow::Result<String> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env.set_fuel(Some(TEMPLATE_FUEL));
+    env.set_auto_escape_callback(move |_| {
+        if autoescape {
+            AutoEscape::Html
+        } else {
+            AutoEscape::None
+        }
+    });
+    env.add_filter("datetime", datetime_filter);
+    env.add_filter("temperature", temperature_filter);
+    env.add_filter("distance", distance_filter);
+    env.add_filter("money", money_filter);
+
+    env.render_str(template, context)
+        .map_err(|e| anyhow::anyhow!("Template error: {e}"))
+}
+
+pub(crate) fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// `locale` from the template context, defaulting when absent or malformed.
+fn template_locale(state: &State) -> LocaleConfig {
+    state
+        .lookup("locale")
+        .and_then(|value| LocaleConfig::deserialize(value).ok())
+        .unwrap_or_default()
+}
+
+/// RFC 3339 strings or unix seconds, in the locale's timezone and clock.
+fn datetime_filter(state: &State, value: TemplateValue) -> TemplateValue {
+    let at = match value.as_str() {
+        Some(s) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.with_timezone(&Utc)),
+        None => value
+            .as_i64()
+            .and_then(|secs| DateTime::from_timestamp(secs, 0)),
+    };
+    match at {
+        Some(at) => crate::locale::format_datetime(&template_locale(state), at).into(),
+        None => value,
+    }
+}
+
+/// Celsius input, or Fahrenheit with `temperature('f')`.
+fn temperature_filter(state: &State, value: TemplateValue, unit: Option<String>) -> TemplateValue {
+    let Ok(n) = f64::try_from(value.clone()) else {
+        return value;
+    };
+    let celsius = if unit.is_some_and(|unit| unit.eq_ignore_ascii_case("f")) {
+        (n - 32.0) * 5.0 / 9.0
+    } else {
+        n
+    };
+    crate::locale::format_temperature(&template_locale(state), celsius).into()
+}
+
+/// Kilometre input.
+fn distance_filter(state: &State, value: TemplateValue) -> TemplateValue {
+    match f64::try_from(value.clone()) {
+        Ok(km) => crate::locale::format_distance(&template_locale(state), km).into(),
+        Err(_) => value,
+    }
+}
+
+/// Amount in the locale's currency, or the code given as `money('EUR')`.
+fn money_filter(state: &State, value: TemplateValue, currency: Option<String>) -> TemplateValue {
+    match f64::try_from(value.clone()) {
+        Ok(amount) => {
+            crate::locale::format_money(&template_locale(state), amount, currency.as_deref()).into()
+        }
+        Err(_) => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn test_security(workspace: std::path::PathBuf, level: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: level,
+            workspace_dir: workspace,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn render(template: &str, data: Value) -> String {
+        render_template(template, &data, false).unwrap()
+    }
+
+    #[test]
+    fn renders_variables_and_filters() {
+        let data = json!({"host": {"name": "nas"}, "load": 0.4567, "tags": ["a", "b"]});
+        assert_eq!(
+            render(
+                "{{ host.name | upper }} {{ load | round(2) }} {{ tags | join(', ') }} {{ missing | default('n/a') }}",
+                data
+            ),
+            "NAS 0.46 a, b n/a"
+        );
+    }
+
+    #[test]
+    fn locale_filters_follow_context_locale() {
+        let template =
+            "{{ at | datetime }} {{ temp | temperature }} {{ run | distance }} {{ cost | money }}";
+        let data = json!({
+            "at": "2026-01-15T19:05:00Z",
+            "temp": 20,
+            "run": 10,
+            "cost": 1234.5,
+            "locale": {
+                "timezone": "America/Chicago",
+                "clock": "12h",
+                "temperature": "fahrenheit",
+                "units": "imperial",
+                "currency": "USD"
+            }
+        });
+        assert_eq!(
+            render(template, data),
+            "2026-01-15 1:05 PM 68.0 °F 6.2 mi 1,234.50 USD"
+        );
+        assert_eq!(
+            render(
+                "{{ t | temperature('f') }} {{ x | money('EUR') }}",
+                json!({"t": 212, "x": 3, "locale": {"timezone": "UTC"}})
+            ),
+            "100.0 °C 3.00 EUR"
+        );
+    }
+
+    #[test]
+    fn renders_loops_and_conditionals() {
+        let template = "| host | state |\n{% for h in hosts %}\n| {{ h.name }} | {% if h.up %}up{% else %}down{% endif %} |\n{% else %}\nno hosts\n{% endfor %}\n";
+        let data = json!({"hosts": [{"name": "a", "up": true}, {"name": "b", "up": false}]});
+        assert_eq!(
+            render(template, data),
+            "| host | state |\n| a | up |\n| b | down |\n"
+        );
+        assert_eq!(
+            render(template, json!({"hosts": []})),
+            "| host | state |\nno hosts\n"
+        );
+    }
+
+    #[test]
+    fn supports_comparisons_elif_and_loop_vars() {
+        let template = "{% for n in nums %}{% if n > 10 %}big{% elif n == 10 %}ten{% else %}small{% endif %}{% if not loop.last %},{% endif %}{% endfor %}";
+        assert_eq!(
+            render(template, json!({"nums": [3, 10, 42]})),
+            "small,ten,big"
+        );
+        assert_eq!(
+            render(
+                "{% for k, v in m | items %}{{ k }}={{ v }};{% endfor %}",
+                json!({"m": {"a": 1, "b": 2}})
+            ),
+            "a=1;b=2;"
+        );
+    }
+
+    #[test]
+    fn autoescapes_html_unless_safe() {
+        let data = json!({"v": "<b>&</b>"});
+        assert_eq!(
+            render_template("{{ v }}|{{ v | safe }}", &data, true).unwrap(),
+            "&lt;b&gt;&amp;&lt;&#x2f;b&gt;|<b>&</b>"
+        );
+    }
+
+    #[test]
+    fn reports_template_errors() {
+        assert!(render_template("{% if x %}open", &json!({}), false).is_err());
+        assert!(render_template("{{ x | nope }}", &json!({}), false).is_err());
+        assert!(render_template("{{ x", &json!({}), false).is_err());
+    }
+
+    #[tokio::test]
+    async fn report_renders_named_template_to_workspace() {
+        let tmp = TempDir::new().unwrap();
+        let templates = tmp.path().join(REPORT_TEMPLATES_DIR);
+        tokio::fs::create_dir_all(&templates).await.unwrap();
+        tokio::fs::write(templates.join("weekly.md.j2"), "# {{ title }}\n")
+            .await
+            .unwrap();
+
+        let tool = ReportTool::new(test_security(tmp.path().to_path_buf(), AutonomyLevel::Full));
+        let result = tool
+            .execute(json!({
+                "template": "weekly.md",
+                "data": {"title": "Infra"},
+                "output": "reports/out/weekly.md"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let written = tokio::fs::read_to_string(tmp.path().join("reports/out/weekly.md"))
+            .await
+            .unwrap();
+        assert_eq!(written, "# Infra\n");
+    }
+
+    #[test]
+    fn trends_compare_numeric_leaves() {
+        assert_eq!(change(130.0, 100.0), (Some(30.0), "up 30%".into()));
+        assert_eq!(change(5.0, 0.0), (None, "up from 0".into()));
+        assert_eq!(change(2.0, 2.0), (Some(0.0), "unchanged".into()));
+
+        let current = json!({"ping": {"latency_ms": 130}, "hosts": [{"up": 4}], "note": "x"});
+        let day = json!({"ping": {"latency_ms": 100}, "hosts": [{"up": 5}]});
+        let summaries: Vec<String> = trends(&current, Some(&day), None)
+            .iter()
+            .map(|entry| entry["summary"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            summaries,
+            vec![
+                "hosts[0].up 4 (down 20% vs yesterday)",
+                "ping.latency_ms 130 (up 30% vs yesterday)"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn report_history_key_feeds_trends() {
+        let tmp = TempDir::new().unwrap();
+        let history_dir = tmp.path().join(REPORT_HISTORY_DIR);
+        tokio::fs::create_dir_all(&history_dir).await.unwrap();
+        let now = Utc::now();
+        let lines = [
+            json!({"at": now - Duration::days(7), "data": {"latency_ms": 130}}),
+            json!({"at": now - Duration::hours(24), "data": {"latency_ms": 100}}),
+            json!({"at": now - Duration::days(60), "data": {"latency_ms": 1}}),
+        ]
+        .iter()
+        .map(Value::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+        tokio::fs::write(history_dir.join("briefing.jsonl"), lines)
+            .await
+            .unwrap();
+
+        let tool = ReportTool::new(test_security(tmp.path().to_path_buf(), AutonomyLevel::Full));
+        let result = tool
+            .execute(json!({
+                "template_text": "{{ trend_summary }}",
+                "data": {"latency_ms": 130},
+                "output": "reports/out/briefing.md",
+                "history_key": "briefing"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let expected = "latency_ms 130 (up 30% vs yesterday, unchanged vs last week)";
+        assert!(result.output.ends_with(expected));
+        let written = tokio::fs::read_to_string(tmp.path().join("reports/out/briefing.md"))
+            .await
+            .unwrap();
+        assert_eq!(written, expected);
+        let stored = tokio::fs::read_to_string(history_dir.join("briefing.jsonl"))
+            .await
+            .unwrap();
+        assert_eq!(stored.lines().count(), 3);
+    }
+
+    #[tokio::test]
+    async fn report_blocks_readonly_mode() {
+        let tmp = TempDir::new().unwrap();
+        let tool = ReportTool::new(test_security(
+            tmp.path().to_path_buf(),
+            AutonomyLevel::ReadOnly,
+        ));
+        let result = tool
+            .execute(json!({"template_text": "x", "output": "r.md"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
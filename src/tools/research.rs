@@ -0,0 +1,678 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{FactsConfig, ResearchConfig};
+use crate::facts::{self, Fact};
+use crate::providers::Provider;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::json;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::sync::{Arc, OnceLock};
+
+/// Pages larger than this are cut off before text extraction.
+const MAX_PAGE_BYTES: usize = 2 * 1024 * 1024;
+/// Notes answer saying a page has nothing on the question.
+const IRRELEVANT: &str = "IRRELEVANT";
+
+const NOTES_SYSTEM_PROMPT: &str = "You extract research notes. Given a research question and \
+    the text of one web page, list the facts from the page that help answer the question as \
+    short bullet points, keeping numbers, dates and names exact. Do not add anything the page \
+    does not say. If the page has nothing relevant, answer exactly IRRELEVANT.";
+
+const FOLLOW_UP_SYSTEM_PROMPT: &str = "You plan web research. Given a question and the notes \
+    gathered so far, reply with up to {n} new web search queries, one per line, that would fill \
+    the most important gaps. Reply with nothing if the notes already answer the question.";
+
+const REPORT_SYSTEM_PROMPT: &str = "You write research reports in markdown. Answer the \
+    question using only the numbered source notes. Cite every factual statement with the \
+    source number in brackets, e.g. [2]; cite several as [1][3]. Point out where sources \
+    disagree or where the notes leave the question open. Do not add a source list; it is \
+    appended for you.";
+
+const FACTS_SYSTEM_PROMPT: &str = "You extract facts that go stale. From the numbered source \
+    notes, list up to 10 facts likely to change over time (versions, prices, addresses, dates, \
+    statuses) as lines `subject | predicate | value | n`, where n is the number of the source \
+    stating it. Reply with nothing if there are none.";
+
+/// Confidence of facts the research flow extracts from web pages.
+const FACT_CONFIDENCE: f64 = 0.7;
+
+/// One search hit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Hit {
+    title: String,
+    url: String,
+    snippet: String,
+}
+
+/// A source that made it into the report.
+#[derive(Debug, Clone)]
+struct Source {
+    title: String,
+    url: String,
+    notes: String,
+}
+
+/// Multi-step web research: search, read, take notes, report with citations.
+pub struct ResearchTool {
+    security: Arc<SecurityPolicy>,
+    config: ResearchConfig,
+    search: Arc<dyn Tool>,
+    provider: Arc<dyn Provider>,
+    model: String,
+    facts: Option<FactsConfig>,
+}
+
+impl ResearchTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        config: ResearchConfig,
+        search: Arc<dyn Tool>,
+        provider: Arc<dyn Provider>,
+        model: String,
+    ) -> Self {
+        Self {
+            security,
+            config,
+            search,
+            provider,
+            model,
+            facts: None,
+        }
+    }
+
+    /// Also record time-sensitive findings in the fact store.
+    pub fn with_facts(mut self, facts: FactsConfig) -> Self {
+        self.facts = Some(facts);
+        self
+    }
+
+    async fn ask(&self, system: &str, message: &str) -> anyhow::Result<String> {
+        self.provider
+            .chat_with_system(Some(system), message, &self.model, 0.2)
+            .await
+    }
+
+    async fn search(&self, query: &str) -> Vec<Hit> {
+        match self.search.execute(json!({ "query": query })).await {
+            Ok(result) if result.success => parse_search_results(&result.output),
+            Ok(result) => {
+                tracing::warn!("Research search '{query}' failed: {:?}", result.error);
+                Vec::new()
+            }
+            Err(e) => {
+                tracing::warn!("Research search '{query}' failed: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Readable text of `url`, or `None` if it cannot or may not be fetched.
+    async fn read(&self, url: &str) -> Option<String> {
+        let parsed = reqwest::Url::parse(url).ok()?;
+        if !matches!(parsed.scheme(), "http" | "https")
+            || parsed
+                .host_str()
+                .is_none_or(super::browser::is_private_host)
+        {
+            return None;
+        }
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.research",
+            self.config.timeout_secs,
+            10,
+        );
+        let response = client.get(parsed).send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("text/html")
+            .to_ascii_lowercase();
+        if !content_type.starts_with("text/") && !content_type.contains("xhtml") {
+            return None;
+        }
+        let bytes = response.bytes().await.ok()?;
+        let raw = String::from_utf8_lossy(&bytes[..bytes.len().min(MAX_PAGE_BYTES)]);
+        let text = if content_type.starts_with("text/plain") {
+            raw.into_owned()
+        } else {
+            html_to_text(&raw)
+        };
+        Some(truncate_chars(&text, self.config.max_chars_per_source))
+    }
+
+    /// Extract stale-prone facts from the notes into the fact store; returns
+    /// how many were recorded.
+    async fn record_facts(
+        &self,
+        config: &FactsConfig,
+        question: &str,
+        sources: &[Source],
+    ) -> usize {
+        let message = format!(
+            "Question: {question}\n\nSource notes:\n{}",
+            numbered_notes(sources)
+        );
+        let reply = match self.ask(FACTS_SYSTEM_PROMPT, &message).await {
+            Ok(reply) => reply,
+            Err(e) => {
+                tracing::warn!("Research fact extraction failed: {e}");
+                return 0;
+            }
+        };
+        let now = chrono::Utc::now();
+        let found: Vec<Fact> = parse_fact_lines(&reply, sources)
+            .into_iter()
+            .map(|(subject, predicate, value, url)| Fact {
+                subject,
+                predicate,
+                value,
+                source: "research".into(),
+                source_ref: Some(url),
+                confidence: FACT_CONFIDENCE,
+                observed_at: now,
+                expires_at: facts::expiry(config, None, now),
+            })
+            .collect();
+        let workspace_dir = self.security.workspace_dir.clone();
+        let recorded = tokio::task::spawn_blocking(move || {
+            found
+                .iter()
+                .filter(|fact| match facts::record(&workspace_dir, fact) {
+                    Ok(_) => true,
+                    Err(e) => {
+                        tracing::warn!("Research fact not recorded: {e}");
+                        false
+                    }
+                })
+                .count()
+        })
+        .await;
+        recorded.unwrap_or_default()
+    }
+
+    async fn write_report(&self, question: &str, document: &str) -> Result<String, String> {
+        let date = chrono::Local::now().format("%Y-%m-%d");
+        let relative = format!(
+            "{}/{}-{date}.md",
+            self.config.output_dir.trim_end_matches('/'),
+            slug(question)
+        );
+        if !self.security.is_path_allowed(&relative) {
+            return Err(format!("Path not allowed by security policy: {relative}"));
+        }
+        let path = self.security.workspace_dir.join(&relative);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        tokio::fs::write(&path, document)
+            .await
+            .map_err(|e| format!("Failed to write report: {e}"))?;
+        Ok(relative)
+    }
+}
+
+/// Hits from `web_search_tool` output (`N. title` / `   url` / `   snippet`).
+fn parse_search_results(output: &str) -> Vec<Hit> {
+    let mut hits: Vec<Hit> = Vec::new();
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if !line.starts_with(' ') {
+            if let Some((number, title)) = trimmed.split_once(". ") {
+                if number.chars().all(|c| c.is_ascii_digit()) && !number.is_empty() {
+                    hits.push(Hit {
+                        title: title.trim().to_string(),
+                        url: String::new(),
+                        snippet: String::new(),
+                    });
+                }
+            }
+            continue;
+        }
+        let Some(hit) = hits.last_mut() else { continue };
+        if hit.url.is_empty() {
+            hit.url = trimmed.to_string();
+        } else if hit.snippet.is_empty() {
+            hit.snippet = trimmed.to_string();
+        }
+    }
+    hits.retain(|hit| hit.url.starts_with("http"));
+    hits
+}
+
+/// `subject | predicate | value | n` lines with the URL of source `n`;
+/// lines citing an unknown source are dropped.
+fn parse_fact_lines(reply: &str, sources: &[Source]) -> Vec<(String, String, String, String)> {
+    reply
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line
+                .trim()
+                .trim_matches('`')
+                .split('|')
+                .map(str::trim)
+                .collect();
+            let [subject, predicate, value, number] = fields[..] else {
+                return None;
+            };
+            let index: usize = number.trim_matches(['[', ']']).parse().ok()?;
+            let source = sources.get(index.checked_sub(1)?)?;
+            (!subject.is_empty() && !predicate.is_empty() && !value.is_empty()).then(|| {
+                (
+                    subject.to_string(),
+                    predicate.to_string(),
+                    value.to_string(),
+                    source.url.clone(),
+                )
+            })
+        })
+        .collect()
+}
+
+fn html_to_text(html: &str) -> String {
+    static BLOCKS: OnceLock<Regex> = OnceLock::new();
+    static BREAKS: OnceLock<Regex> = OnceLock::new();
+    static TAGS: OnceLock<Regex> = OnceLock::new();
+    static SPACES: OnceLock<Regex> = OnceLock::new();
+    let blocks = BLOCKS.get_or_init(|| {
+        Regex::new(r"(?is)<(script|style|noscript|svg|nav|footer|header)\b.*?</(script|style|noscript|svg|nav|footer|header)>|<!--.*?-->").unwrap()
+    });
+    let breaks = BREAKS.get_or_init(|| {
+        Regex::new(r"(?i)<(br|/p|/div|/li|/h[1-6]|/tr|/section|/article)\b[^>]*>").unwrap()
+    });
+    let tags = TAGS.get_or_init(|| Regex::new(r"<[^>]+>").unwrap());
+    let spaces = SPACES.get_or_init(|| Regex::new(r"[ \t\r\f\v]+").unwrap());
+
+    let text = blocks.replace_all(html, " ");
+    let text = breaks.replace_all(&text, "\n");
+    let text = tags.replace_all(&text, " ");
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    spaces
+        .replace_all(&text, " ")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// File-name friendly form of the question, at most 60 characters.
+fn slug(question: &str) -> String {
+    let mut slug = String::new();
+    for c in question.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+        if slug.len() >= 60 {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "research".into()
+    } else {
+        slug.to_string()
+    }
+}
+
+fn numbered_notes(sources: &[Source]) -> String {
+    let mut out = String::new();
+    for (i, source) in sources.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "[{}] {} ({})\n{}\n",
+            i + 1,
+            source.title,
+            source.url,
+            source.notes.trim()
+        );
+    }
+    out
+}
+
+#[async_trait]
+impl Tool for ResearchTool {
+    fn name(&self) -> &str {
+        "research"
+    }
+
+    fn description(&self) -> &str {
+        "Research a question on the web: searches, reads the result pages, takes notes per \
+         source, optionally runs follow-up searches, and writes a markdown report with numbered \
+         citations to the workspace. Use for 'go research X'; returns the report and its path."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "question": {
+                    "type": "string",
+                    "description": "What to research, as a full question"
+                },
+                "depth": {
+                    "type": "integer",
+                    "description": "Search rounds including follow-ups (capped by [research] max_depth)"
+                },
+                "breadth": {
+                    "type": "integer",
+                    "description": "Queries per round (capped by [research] max_queries)"
+                }
+            },
+            "required": ["question"]
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let failure = |error: String| -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            })
+        };
+        let question = args
+            .get("question")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .unwrap_or_default();
+        if question.is_empty() {
+            return failure("Missing 'question' parameter".into());
+        }
+        let cap = |key: &str, max: usize| {
+            args.get(key)
+                .and_then(serde_json::Value::as_u64)
+                .map_or(max, |v| usize::try_from(v).unwrap_or(max))
+                .clamp(1, max.max(1))
+        };
+        let depth = cap("depth", self.config.max_depth);
+        let breadth = cap("breadth", self.config.max_queries);
+
+        if !self.security.can_act() {
+            return failure("Action blocked: autonomy is read-only".into());
+        }
+        if self.security.is_rate_limited() {
+            return failure("Rate limit exceeded: too many actions in the last hour".into());
+        }
+        if !self.security.record_action() {
+            return failure("Rate limit exceeded: action budget exhausted".into());
+        }
+
+        let mut queries = vec![question.to_string()];
+        let mut searched = 0;
+        let mut seen = HashSet::new();
+        let mut sources: Vec<Source> = Vec::new();
+        for round in 0..depth {
+            for query in queries.iter().take(breadth) {
+                searched += 1;
+                for hit in self.search(query).await {
+                    if sources.len() >= self.config.max_sources {
+                        break;
+                    }
+                    if !seen.insert(hit.url.clone()) {
+                        continue;
+                    }
+                    let text = match self.read(&hit.url).await {
+                        Some(text) if !text.trim().is_empty() => text,
+                        _ if !hit.snippet.is_empty() => hit.snippet.clone(),
+                        _ => continue,
+                    };
+                    let message = format!(
+                        "Question: {question}\n\nPage: {} ({})\n\n{text}",
+                        hit.title, hit.url
+                    );
+                    let notes = match self.ask(NOTES_SYSTEM_PROMPT, &message).await {
+                        Ok(notes) => notes,
+                        Err(e) => {
+                            tracing::warn!("Research notes for {} failed: {e}", hit.url);
+                            continue;
+                        }
+                    };
+                    if notes.trim().is_empty() || notes.trim() == IRRELEVANT {
+                        continue;
+                    }
+                    sources.push(Source {
+                        title: hit.title,
+                        url: hit.url,
+                        notes,
+                    });
+                }
+            }
+            if round + 1 == depth || sources.len() >= self.config.max_sources {
+                break;
+            }
+            let message = format!(
+                "Question: {question}\n\nNotes so far:\n{}",
+                numbered_notes(&sources)
+            );
+            let system = FOLLOW_UP_SYSTEM_PROMPT.replace("{n}", &breadth.to_string());
+            queries = match self.ask(&system, &message).await {
+                Ok(reply) => reply
+                    .lines()
+                    .map(|line| line.trim().trim_start_matches(['-', '*', ' ']).trim())
+                    .filter(|line| !line.is_empty())
+                    .map(String::from)
+                    .collect(),
+                Err(e) => {
+                    tracing::warn!("Research follow-up planning failed: {e}");
+                    Vec::new()
+                }
+            };
+            if queries.is_empty() {
+                break;
+            }
+        }
+
+        if sources.is_empty() {
+            return failure(format!(
+                "No usable sources found for '{question}' after {searched} search(es)"
+            ));
+        }
+
+        let message = format!(
+            "Question: {question}\n\nSource notes:\n{}",
+            numbered_notes(&sources)
+        );
+        let body = match self.ask(REPORT_SYSTEM_PROMPT, &message).await {
+            Ok(body) => body,
+            Err(e) => return failure(format!("Report writing failed: {e}")),
+        };
+        let accessed = chrono::Local::now().format("%Y-%m-%d");
+        let mut document = format!("# {question}\n\n{}\n\n## Sources\n\n", body.trim());
+        for (i, source) in sources.iter().enumerate() {
+            let _ = writeln!(
+                document,
+                "{}. [{}]({}) — accessed {accessed}",
+                i + 1,
+                source.title,
+                source.url
+            );
+        }
+
+        let path = match self.write_report(question, &document).await {
+            Ok(path) => path,
+            Err(e) => return failure(e),
+        };
+        let mut summary = format!(
+            "Saved {path} ({} sources, {searched} searches",
+            sources.len()
+        );
+        if let Some(config) = &self.facts {
+            let recorded = self.record_facts(config, question, &sources).await;
+            let _ = write!(summary, ", {recorded} facts recorded");
+        }
+        Ok(ToolResult {
+            success: true,
+            output: format!("{summary})\n\n{document}"),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    struct FakeSearch;
+
+    #[async_trait]
+    impl Tool for FakeSearch {
+        fn name(&self) -> &str {
+            "web_search_tool"
+        }
+
+        fn description(&self) -> &str {
+            "fake"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            json!({})
+        }
+
+        async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: "Search results for: x (via DuckDuckGo)\n\
+                         1. Release notes\n   http://127.0.0.1:9/notes\n   Version 2.0 shipped in May.\n\
+                         2. Forum\n   http://localhost/forum\n   Unrelated chatter."
+                    .into(),
+                error: None,
+            })
+        }
+    }
+
+    struct FakeProvider {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Provider for FakeProvider {
+        async fn chat_with_system(
+            &self,
+            system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let system = system_prompt.unwrap_or_default();
+            Ok(if system == NOTES_SYSTEM_PROMPT {
+                if message.contains("Version 2.0") {
+                    "- Version 2.0 shipped in May".into()
+                } else {
+                    IRRELEVANT.into()
+                }
+            } else if system == REPORT_SYSTEM_PROMPT {
+                "Version 2.0 shipped in May [1].".into()
+            } else if system == FACTS_SYSTEM_PROMPT {
+                "widget | latest version | 2.0 | 1\nwidget | price | 9 | 7".into()
+            } else {
+                String::new()
+            })
+        }
+    }
+
+    #[test]
+    fn parses_results_and_extracts_text() {
+        let hits = parse_search_results(
+            "Search results for: q (via Brave)\n1. A. B\n   https://a.example/x\n   Snippet\n2. No url\n",
+        );
+        assert_eq!(
+            hits,
+            vec![Hit {
+                title: "A. B".into(),
+                url: "https://a.example/x".into(),
+                snippet: "Snippet".into(),
+            }]
+        );
+
+        let text = html_to_text(
+            "<html><head><style>p{}</style><script>x()</script></head><body><h1>Title</h1>\
+             <p>Fish &amp; chips</p><!-- hidden --><p>costs  <b>5</b></p></body></html>",
+        );
+        assert_eq!(text, "Title\nFish & chips\ncosts 5");
+        assert_eq!(slug("What's new in Rust 1.87?"), "what-s-new-in-rust-1-87");
+        assert_eq!(truncate_chars("héllo", 2), "hé…");
+    }
+
+    #[tokio::test]
+    async fn writes_cited_report_from_relevant_sources() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: tmp.path().to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let provider = Arc::new(FakeProvider {
+            calls: AtomicUsize::new(0),
+        });
+        let tool = ResearchTool::new(
+            security,
+            ResearchConfig::default(),
+            Arc::new(FakeSearch),
+            provider.clone(),
+            "test-model".into(),
+        )
+        .with_facts(FactsConfig::default());
+
+        let result = tool
+            .execute(json!({"question": "When did version 2.0 ship?", "depth": 1}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        // Private hosts are never fetched; the snippets stand in for them.
+        assert!(result.output.contains("Version 2.0 shipped in May [1]."));
+        assert!(result
+            .output
+            .contains("1. [Release notes](http://127.0.0.1:9/notes) — accessed"));
+        assert!(!result.output.contains("Forum"));
+        // Two notes calls, the report and fact extraction; no follow-up
+        // planning at depth 1.
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 4);
+        assert!(result.output.contains("1 searches, 1 facts recorded)"));
+        let recorded = facts::query(tmp.path(), Some("widget"), None).unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].source, "research");
+        assert_eq!(
+            recorded[0].source_ref.as_deref(),
+            Some("http://127.0.0.1:9/notes")
+        );
+
+        let path = result.output.lines().next().unwrap();
+        let path = path
+            .strip_prefix("Saved ")
+            .and_then(|rest| rest.split(" (").next())
+            .unwrap();
+        assert!(path.starts_with("research/when-did-version-2-0-ship-"));
+        assert!(tmp.path().join(path).exists());
+    }
+}
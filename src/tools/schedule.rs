@@ -69,6 +69,10 @@ impl Tool for ScheduleTool {
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
         let action = args
             .get("action")
@@ -0,0 +1,329 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use async_trait::async_trait;
+use chrono::{Duration as ChronoDuration, Utc};
+use serde::Serialize;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Window for `recent_errors.cron_runs`.
+const ERROR_WINDOW_HOURS: i64 = 24;
+/// Runs inspected per job when counting recent failures.
+const RUNS_PER_JOB: usize = 50;
+/// CPU usage is sampled over this interval.
+const CPU_SAMPLE: Duration = Duration::from_millis(250);
+/// Open descriptors above this share of the soft limit count as an issue.
+const FD_WARN_RATIO: f64 = 0.8;
+
+/// Reports the zeroclaw process's own resource usage and error state.
+pub struct SelfStatusTool {
+    config: Arc<Config>,
+}
+
+impl SelfStatusTool {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self { config }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ProcessStats {
+    pid: u32,
+    uptime_seconds: u64,
+    cpu_percent: Option<f64>,
+    cpu_seconds: Option<f64>,
+    rss_bytes: Option<u64>,
+    threads: Option<u64>,
+    open_fds: Option<u64>,
+    fd_limit: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct RuntimeStats {
+    workers: usize,
+    alive_tasks: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct StorageStats {
+    workspace_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorStats {
+    /// Health components currently in the `error` state.
+    components: Vec<String>,
+    /// Failed cron runs in the last `ERROR_WINDOW_HOURS` hours.
+    cron_runs: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct SelfStatus {
+    healthy: bool,
+    issues: Vec<String>,
+    process: ProcessStats,
+    runtime: Option<RuntimeStats>,
+    storage: StorageStats,
+    recent_errors: ErrorStats,
+}
+
+/// `(utime + stime)` in clock ticks, from `/proc/self/stat`.
+#[cfg(target_os = "linux")]
+fn cpu_ticks() -> Option<u64> {
+    let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+    // Fields after the parenthesised command name; utime and stime are the
+    // 14th and 15th fields overall.
+    let rest = &stat[stat.rfind(')')? + 2..];
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(utime + stime)
+}
+
+#[cfg(target_os = "linux")]
+#[allow(clippy::cast_precision_loss)]
+fn clock_ticks_per_second() -> f64 {
+    // SAFETY: sysconf has no preconditions.
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 {
+        ticks as f64
+    } else {
+        100.0
+    }
+}
+
+/// A `/proc/self/status` value such as `VmRSS:  1234 kB`.
+#[cfg(target_os = "linux")]
+fn proc_status_value(status: &str, key: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+#[cfg(unix)]
+#[allow(clippy::useless_conversion)]
+fn fd_soft_limit() -> Option<u64> {
+    let mut limit = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: `limit` is a valid, writable rlimit.
+    let rc = unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &raw mut limit) };
+    if rc != 0 || limit.rlim_cur == libc::RLIM_INFINITY {
+        return None;
+    }
+    u64::try_from(limit.rlim_cur).ok()
+}
+
+#[cfg(not(unix))]
+fn fd_soft_limit() -> Option<u64> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+#[allow(clippy::cast_precision_loss)]
+async fn process_stats() -> ProcessStats {
+    let ticks_per_second = clock_ticks_per_second();
+    let before = cpu_ticks();
+    tokio::time::sleep(CPU_SAMPLE).await;
+    let after = cpu_ticks();
+    let cpu_percent = before.zip(after).map(|(before, after)| {
+        let used = after.saturating_sub(before) as f64 / ticks_per_second;
+        (used / CPU_SAMPLE.as_secs_f64() * 1000.0).round() / 10.0
+    });
+    let cpu_seconds = after.map(|ticks| ticks as f64 / ticks_per_second);
+
+    let status = std::fs::read_to_string("/proc/self/status").unwrap_or_default();
+    let open_fds = std::fs::read_dir("/proc/self/fd")
+        .ok()
+        .and_then(|entries| u64::try_from(entries.count()).ok());
+
+    ProcessStats {
+        pid: std::process::id(),
+        uptime_seconds: crate::health::snapshot().uptime_seconds,
+        cpu_percent,
+        cpu_seconds,
+        rss_bytes: proc_status_value(&status, "VmRSS").map(|kb| kb * 1024),
+        threads: proc_status_value(&status, "Threads"),
+        open_fds,
+        fd_limit: fd_soft_limit(),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn process_stats() -> ProcessStats {
+    ProcessStats {
+        pid: std::process::id(),
+        uptime_seconds: crate::health::snapshot().uptime_seconds,
+        fd_limit: fd_soft_limit(),
+        ..ProcessStats::default()
+    }
+}
+
+fn runtime_stats() -> Option<RuntimeStats> {
+    let metrics = tokio::runtime::Handle::try_current().ok()?.metrics();
+    Some(RuntimeStats {
+        workers: metrics.num_workers(),
+        alive_tasks: metrics.num_alive_tasks(),
+    })
+}
+
+/// Total size of regular files under `dir`; symlinks are not followed.
+fn dir_size(dir: &Path) -> u64 {
+    let mut total = 0;
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let Ok(meta) = entry.path().symlink_metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                pending.push(entry.path());
+            } else if meta.is_file() {
+                total += meta.len();
+            }
+        }
+    }
+    total
+}
+
+fn recent_cron_failures(config: &Config) -> usize {
+    let Ok(jobs) = crate::cron::list_jobs(config) else {
+        return 0;
+    };
+    let since = Utc::now() - ChronoDuration::hours(ERROR_WINDOW_HOURS);
+    jobs.iter()
+        .filter_map(|job| crate::cron::list_runs(config, &job.id, RUNS_PER_JOB).ok())
+        .flatten()
+        .filter(|run| run.started_at >= since && run.status == "error")
+        .count()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn issues(status: &SelfStatus) -> Vec<String> {
+    let mut issues: Vec<String> = status
+        .recent_errors
+        .components
+        .iter()
+        .map(|name| format!("component {name} is in error"))
+        .collect();
+    if let (Some(open), Some(limit)) = (status.process.open_fds, status.process.fd_limit) {
+        if limit > 0 && open as f64 >= limit as f64 * FD_WARN_RATIO {
+            issues.push(format!("{open} of {limit} file descriptors in use"));
+        }
+    }
+    if status.recent_errors.cron_runs > 0 {
+        issues.push(format!(
+            "{} cron run(s) failed in the last {ERROR_WINDOW_HOURS}h",
+            status.recent_errors.cron_runs
+        ));
+    }
+    issues
+}
+
+#[async_trait]
+impl Tool for SelfStatusTool {
+    fn name(&self) -> &str {
+        "self_status"
+    }
+
+    fn description(&self) -> &str {
+        "Report this agent's own health: CPU, memory (RSS), open file descriptors, async task \
+         count, workspace storage size, and recent errors (failing components, failed cron runs). \
+         Use when asked whether you are healthy or to check resource usage."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        })
+    }
+
+    async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let process = process_stats().await;
+        let config = self.config.clone();
+        let (workspace_bytes, cron_runs) = tokio::task::spawn_blocking(move || {
+            (
+                dir_size(&config.workspace_dir),
+                recent_cron_failures(&config),
+            )
+        })
+        .await?;
+        let components = crate::health::snapshot()
+            .components
+            .into_iter()
+            .filter(|(_, c)| c.status == "error")
+            .map(|(name, _)| name)
+            .collect();
+
+        let mut status = SelfStatus {
+            healthy: true,
+            issues: Vec::new(),
+            process,
+            runtime: runtime_stats(),
+            storage: StorageStats { workspace_bytes },
+            recent_errors: ErrorStats {
+                components,
+                cron_runs,
+            },
+        };
+        status.issues = issues(&status);
+        status.healthy = status.issues.is_empty();
+
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&status)?,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn reports_process_and_storage_stats() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(config.workspace_dir.join("notes")).unwrap();
+        std::fs::write(config.workspace_dir.join("notes/a.md"), "12345").unwrap();
+
+        let tool = SelfStatusTool::new(Arc::new(config));
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(result.success);
+        let status: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(status["process"]["pid"], std::process::id());
+        assert_eq!(status["storage"]["workspace_bytes"], 5);
+        assert_eq!(status["recent_errors"]["cron_runs"], 0);
+        if cfg!(target_os = "linux") {
+            assert!(status["process"]["rss_bytes"].as_u64().unwrap() > 0);
+            assert!(status["process"]["open_fds"].as_u64().unwrap() > 0);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parses_proc_status_values() {
+        let status = "Name:\tzeroclaw\nVmRSS:\t  2048 kB\nThreads:\t7\n";
+        assert_eq!(proc_status_value(status, "VmRSS"), Some(2048));
+        assert_eq!(proc_status_value(status, "Threads"), Some(7));
+        assert_eq!(proc_status_value(status, "VmSwap"), None);
+    }
+}
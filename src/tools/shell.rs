@@ -85,6 +85,10 @@ impl Tool for ShellTool {
         })
     }
 
+    fn writes_state(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let command = args
             .get("command")
@@ -154,6 +158,10 @@ impl Tool for ShellTool {
             }
         }
 
+        // A timed-out or cancelled call drops the output future; take the
+        // child process down with it instead of leaving it running.
+        cmd.kill_on_drop(true);
+
         let result =
             tokio::time::timeout(Duration::from_secs(SHELL_TIMEOUT_SECS), cmd.output()).await;
 
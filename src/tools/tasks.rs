@@ -0,0 +1,950 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{TasksCalDavConfig, TasksConfig, TasksVikunjaConfig};
+use crate::security::SecurityPolicy;
+use anyhow::Context;
+use async_trait::async_trait;
+use regex::Regex;
+use rusqlite::{params, Connection};
+use serde_json::json;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+const CALENDAR_QUERY_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav"><d:prop><d:getetag/><c:calendar-data/></d:prop><c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VTODO"/></c:comp-filter></c:filter></c:calendar-query>"#;
+
+/// How a backend finds an item again when completing it.
+#[derive(Debug, Clone)]
+enum ItemHandle {
+    Sqlite(i64),
+    CalDav {
+        href: String,
+        etag: Option<String>,
+        ics: String,
+    },
+    Vikunja(serde_json::Value),
+}
+
+/// One entry of a named list, normalized across backends.
+#[derive(Debug, Clone)]
+struct TaskItem {
+    id: String,
+    title: String,
+    done: bool,
+    handle: ItemHandle,
+}
+
+#[async_trait]
+trait TaskBackend: Send + Sync {
+    async fn add(&self, list: &str, title: &str) -> anyhow::Result<TaskItem>;
+    async fn items(&self, list: &str, include_done: bool) -> anyhow::Result<Vec<TaskItem>>;
+    async fn mark_done(&self, list: &str, item: &TaskItem) -> anyhow::Result<()>;
+}
+
+/// Add, complete, and list items on named todo / shopping lists backed by
+/// a workspace sqlite file, a CalDAV server (VTODO), or Vikunja.
+pub struct TasksTool {
+    security: Arc<SecurityPolicy>,
+    config: TasksConfig,
+    workspace_dir: PathBuf,
+}
+
+impl TasksTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: TasksConfig, workspace_dir: PathBuf) -> Self {
+        Self {
+            security,
+            config,
+            workspace_dir,
+        }
+    }
+
+    fn backend(&self) -> anyhow::Result<Box<dyn TaskBackend>> {
+        match self.config.backend.trim().to_ascii_lowercase().as_str() {
+            "sqlite" | "" => Ok(Box::new(SqliteBackend {
+                db_path: self.workspace_dir.join("tasks").join("tasks.db"),
+            })),
+            "caldav" => {
+                let config = self
+                    .config
+                    .caldav
+                    .clone()
+                    .context("tasks.backend = \"caldav\" requires a [tasks.caldav] section")?;
+                Ok(Box::new(CalDavBackend { config }))
+            }
+            "vikunja" => {
+                let config =
+                    self.config.vikunja.clone().context(
+                        "tasks.backend = \"vikunja\" requires a [tasks.vikunja] section",
+                    )?;
+                Ok(Box::new(VikunjaBackend { config }))
+            }
+            other => anyhow::bail!(
+                "Unknown tasks backend '{other}'. Use 'sqlite', 'caldav', or 'vikunja'."
+            ),
+        }
+    }
+
+    async fn run(
+        &self,
+        action: &str,
+        list: &str,
+        args: &serde_json::Value,
+    ) -> anyhow::Result<ToolResult> {
+        let backend = self.backend()?;
+        match action {
+            "add" => {
+                let titles = item_titles(args);
+                if titles.is_empty() {
+                    return Ok(failure("'item' is required for add"));
+                }
+                let mut added = Vec::with_capacity(titles.len());
+                for title in &titles {
+                    added.push(backend.add(list, title).await?.title);
+                }
+                Ok(ToolResult {
+                    success: true,
+                    output: format!("Added to {list}: {}", added.join(", ")),
+                    error: None,
+                })
+            }
+            "complete" => {
+                let Some(query) = args.get("item").and_then(|v| v.as_str()) else {
+                    return Ok(failure("'item' is required for complete"));
+                };
+                let items = backend.items(list, false).await?;
+                let item = match find_item(&items, query) {
+                    Ok(item) => item,
+                    Err(e) => return Ok(failure(e)),
+                };
+                backend.mark_done(list, item).await?;
+                Ok(ToolResult {
+                    success: true,
+                    output: format!("Completed '{}' on {list}", item.title),
+                    error: None,
+                })
+            }
+            _ => {
+                let include_done = args
+                    .get("include_done")
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or(false);
+                let items = backend.items(list, include_done).await?;
+                Ok(ToolResult {
+                    success: true,
+                    output: format_items(list, &items),
+                    error: None,
+                })
+            }
+        }
+    }
+}
+
+fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+/// `item` may be a single string or an array of strings for add.
+fn item_titles(args: &serde_json::Value) -> Vec<String> {
+    let raw: Vec<&str> = match args.get("item") {
+        Some(serde_json::Value::String(s)) => vec![s.as_str()],
+        Some(serde_json::Value::Array(values)) => {
+            values.iter().filter_map(|v| v.as_str()).collect()
+        }
+        _ => Vec::new(),
+    };
+    raw.into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Resolve an item by id, then exact title, then a unique substring.
+fn find_item<'a>(items: &'a [TaskItem], query: &str) -> Result<&'a TaskItem, String> {
+    let query = query.trim();
+    if let Some(item) = items.iter().find(|item| item.id == query) {
+        return Ok(item);
+    }
+    let needle = query.to_lowercase();
+    if let Some(item) = items
+        .iter()
+        .find(|item| item.title.to_lowercase() == needle)
+    {
+        return Ok(item);
+    }
+    let partial: Vec<&TaskItem> = items
+        .iter()
+        .filter(|item| item.title.to_lowercase().contains(&needle))
+        .collect();
+    match partial.as_slice() {
+        [item] => Ok(*item),
+        [] => Err(format!("No open item matching '{query}'")),
+        many => Err(format!(
+            "'{query}' matches several items: {}",
+            many.iter()
+                .map(|item| item.title.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+fn format_items(list: &str, items: &[TaskItem]) -> String {
+    if items.is_empty() {
+        return format!("{list} is empty");
+    }
+    let open = items.iter().filter(|item| !item.done).count();
+    let mut output = format!("{list} ({open} open):\n");
+    for item in items {
+        let mark = if item.done { 'x' } else { ' ' };
+        let _ = writeln!(output, "- [{mark}] {} (id {})", item.title, item.id);
+    }
+    output
+}
+
+// ── sqlite ──────────────────────────────────────────────────────
+
+struct SqliteBackend {
+    db_path: PathBuf,
+}
+
+fn with_tasks_db<T>(
+    db_path: &Path,
+    f: impl FnOnce(&Connection) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create tasks directory: {}", parent.display()))?;
+    }
+    let conn = Connection::open(db_path)
+        .with_context(|| format!("Failed to open tasks DB: {}", db_path.display()))?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS task_items (
+            id           INTEGER PRIMARY KEY AUTOINCREMENT,
+            list         TEXT NOT NULL,
+            title        TEXT NOT NULL,
+            done         INTEGER NOT NULL DEFAULT 0,
+            created_at   TEXT NOT NULL,
+            completed_at TEXT
+         );
+         CREATE INDEX IF NOT EXISTS idx_task_items_list ON task_items(list, done);",
+    )
+    .context("Failed to initialize tasks schema")?;
+    f(&conn)
+}
+
+fn list_key(list: &str) -> String {
+    list.trim().to_lowercase()
+}
+
+#[async_trait]
+impl TaskBackend for SqliteBackend {
+    async fn add(&self, list: &str, title: &str) -> anyhow::Result<TaskItem> {
+        let db_path = self.db_path.clone();
+        let list = list_key(list);
+        let title = title.to_string();
+        tokio::task::spawn_blocking(move || {
+            with_tasks_db(&db_path, |conn| {
+                conn.execute(
+                    "INSERT INTO task_items (list, title, done, created_at) VALUES (?1, ?2, 0, ?3)",
+                    params![list, title, chrono::Utc::now().to_rfc3339()],
+                )?;
+                let id = conn.last_insert_rowid();
+                Ok(TaskItem {
+                    id: id.to_string(),
+                    title,
+                    done: false,
+                    handle: ItemHandle::Sqlite(id),
+                })
+            })
+        })
+        .await?
+    }
+
+    async fn items(&self, list: &str, include_done: bool) -> anyhow::Result<Vec<TaskItem>> {
+        let db_path = self.db_path.clone();
+        let list = list_key(list);
+        tokio::task::spawn_blocking(move || {
+            with_tasks_db(&db_path, |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, title, done FROM task_items
+                     WHERE list = ?1 AND (?2 OR done = 0)
+                     ORDER BY done, id",
+                )?;
+                let rows = stmt.query_map(params![list, include_done], |row| {
+                    let id: i64 = row.get(0)?;
+                    Ok(TaskItem {
+                        id: id.to_string(),
+                        title: row.get(1)?,
+                        done: row.get::<_, i64>(2)? != 0,
+                        handle: ItemHandle::Sqlite(id),
+                    })
+                })?;
+                Ok(rows.collect::<Result<Vec<_>, _>>()?)
+            })
+        })
+        .await?
+    }
+
+    async fn mark_done(&self, _list: &str, item: &TaskItem) -> anyhow::Result<()> {
+        let ItemHandle::Sqlite(id) = item.handle else {
+            anyhow::bail!("item does not belong to the sqlite backend");
+        };
+        let db_path = self.db_path.clone();
+        tokio::task::spawn_blocking(move || {
+            with_tasks_db(&db_path, |conn| {
+                conn.execute(
+                    "UPDATE task_items SET done = 1, completed_at = ?2 WHERE id = ?1",
+                    params![id, chrono::Utc::now().to_rfc3339()],
+                )?;
+                Ok(())
+            })
+        })
+        .await?
+    }
+}
+
+// ── CalDAV ──────────────────────────────────────────────────────
+
+struct CalDavBackend {
+    config: TasksCalDavConfig,
+}
+
+impl CalDavBackend {
+    fn client() -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts("tool.tasks", 30, 10)
+    }
+
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        let request = Self::client().request(method, url);
+        match &self.config.username {
+            Some(user) => request.basic_auth(user, self.config.password.as_deref()),
+            None => request,
+        }
+    }
+
+    fn collection_url(&self, list: &str) -> String {
+        let name = self
+            .config
+            .calendars
+            .get(list)
+            .cloned()
+            .unwrap_or_else(|| calendar_slug(list));
+        format!(
+            "{}/{}/",
+            self.config.url.trim_end_matches('/'),
+            urlencoding::encode(&name)
+        )
+    }
+
+    fn object_url(&self, href: &str) -> anyhow::Result<String> {
+        let base = reqwest::Url::parse(&self.config.url)?;
+        Ok(base.join(href)?.to_string())
+    }
+}
+
+fn calendar_slug(list: &str) -> String {
+    list.trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[async_trait]
+impl TaskBackend for CalDavBackend {
+    async fn add(&self, list: &str, title: &str) -> anyhow::Result<TaskItem> {
+        let uid = uuid::Uuid::new_v4().to_string();
+        let ics = new_vtodo(&uid, title, chrono::Utc::now());
+        let url = format!("{}{uid}.ics", self.collection_url(list));
+        let response = self
+            .request(reqwest::Method::PUT, &url)
+            .header("Content-Type", "text/calendar; charset=utf-8")
+            .header("If-None-Match", "*")
+            .body(ics.clone())
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("PUT {url} returned {status}");
+        }
+        Ok(TaskItem {
+            id: uid,
+            title: title.to_string(),
+            done: false,
+            handle: ItemHandle::CalDav {
+                href: url,
+                etag: None,
+                ics,
+            },
+        })
+    }
+
+    async fn items(&self, list: &str, include_done: bool) -> anyhow::Result<Vec<TaskItem>> {
+        let url = self.collection_url(list);
+        let method = reqwest::Method::from_bytes(b"REPORT")?;
+        let response = self
+            .request(method, &url)
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(CALENDAR_QUERY_BODY)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("REPORT {url} returned {status}");
+        }
+        let body = response.text().await?;
+        Ok(parse_calendar_query(&body)
+            .into_iter()
+            .filter(|item| include_done || !item.done)
+            .collect())
+    }
+
+    async fn mark_done(&self, _list: &str, item: &TaskItem) -> anyhow::Result<()> {
+        let ItemHandle::CalDav { href, etag, ics } = &item.handle else {
+            anyhow::bail!("item does not belong to the CalDAV backend");
+        };
+        let url = self.object_url(href)?;
+        let mut request = self
+            .request(reqwest::Method::PUT, &url)
+            .header("Content-Type", "text/calendar; charset=utf-8")
+            .body(complete_vtodo(ics, chrono::Utc::now()));
+        if let Some(etag) = etag {
+            request = request.header("If-Match", etag);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("PUT {url} returned {status}");
+        }
+        Ok(())
+    }
+}
+
+fn ics_timestamp(at: chrono::DateTime<chrono::Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn unescape_ics_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn new_vtodo(uid: &str, title: &str, now: chrono::DateTime<chrono::Utc>) -> String {
+    let stamp = ics_timestamp(now);
+    [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "PRODID:-//ZeroClaw//tasks//EN",
+        "BEGIN:VTODO",
+        &format!("UID:{uid}"),
+        &format!("DTSTAMP:{stamp}"),
+        &format!("CREATED:{stamp}"),
+        &format!("SUMMARY:{}", escape_ics_text(title)),
+        "STATUS:NEEDS-ACTION",
+        "END:VTODO",
+        "END:VCALENDAR",
+        "",
+    ]
+    .join("\r\n")
+}
+
+/// Join RFC 5545 folded continuation lines.
+fn unfold_ics(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in ics.split('\n') {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(rest) = line.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+    lines
+}
+
+/// Split `NAME;PARAM=x:value` into (`NAME`, `value`).
+fn ics_property(line: &str) -> Option<(String, &str)> {
+    let (head, value) = line.split_once(':')?;
+    let name = head.split(';').next().unwrap_or(head);
+    Some((name.to_ascii_uppercase(), value))
+}
+
+/// Extract UID, SUMMARY and completion state from the first VTODO.
+fn parse_vtodo(ics: &str) -> Option<(String, String, bool)> {
+    let mut in_todo = false;
+    let (mut uid, mut summary, mut done) = (None, String::new(), false);
+    for line in unfold_ics(ics) {
+        let Some((name, value)) = ics_property(&line) else {
+            continue;
+        };
+        match (name.as_str(), in_todo) {
+            ("BEGIN", _) if value.eq_ignore_ascii_case("VTODO") => in_todo = true,
+            ("END", true) if value.eq_ignore_ascii_case("VTODO") => break,
+            ("UID", true) => uid = Some(value.to_string()),
+            ("SUMMARY", true) => summary = unescape_ics_text(value),
+            ("STATUS", true) => {
+                done = matches!(
+                    value.to_ascii_uppercase().as_str(),
+                    "COMPLETED" | "CANCELLED"
+                );
+            }
+            ("COMPLETED", true) => done = true,
+            _ => {}
+        }
+    }
+    uid.map(|uid| (uid, summary, done))
+}
+
+/// Rewrite the VTODO as completed, replacing any existing state fields.
+fn complete_vtodo(ics: &str, now: chrono::DateTime<chrono::Utc>) -> String {
+    let stamp = ics_timestamp(now);
+    let mut out = Vec::new();
+    let mut in_todo = false;
+    for line in unfold_ics(ics) {
+        let property = ics_property(&line).map(|(name, value)| (name, value.to_ascii_uppercase()));
+        match property.as_ref().map(|(n, v)| (n.as_str(), v.as_str())) {
+            Some(("BEGIN", "VTODO")) => in_todo = true,
+            Some(("END", "VTODO")) if in_todo => {
+                out.push("STATUS:COMPLETED".to_string());
+                out.push(format!("COMPLETED:{stamp}"));
+                out.push("PERCENT-COMPLETE:100".to_string());
+                out.push(format!("LAST-MODIFIED:{stamp}"));
+                in_todo = false;
+            }
+            Some(("STATUS" | "COMPLETED" | "PERCENT-COMPLETE" | "LAST-MODIFIED", _)) if in_todo => {
+                continue;
+            }
+            _ => {}
+        }
+        out.push(line);
+    }
+    out.push(String::new());
+    out.join("\r\n")
+}
+
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&#xD;", "\r")
+        .replace("&amp;", "&")
+}
+
+fn parse_calendar_query(xml: &str) -> Vec<TaskItem> {
+    static RESPONSE_RE: OnceLock<Regex> = OnceLock::new();
+    static HREF_RE: OnceLock<Regex> = OnceLock::new();
+    static ETAG_RE: OnceLock<Regex> = OnceLock::new();
+    static DATA_RE: OnceLock<Regex> = OnceLock::new();
+    let response = RESPONSE_RE.get_or_init(|| {
+        Regex::new(r"(?s)<(?:\w+:)?response\b[^>]*>(.*?)</(?:\w+:)?response>")
+            .expect("response regex must compile")
+    });
+    let href = HREF_RE.get_or_init(|| {
+        Regex::new(r"(?s)<(?:\w+:)?href\b[^>]*>(.*?)</(?:\w+:)?href>")
+            .expect("href regex must compile")
+    });
+    let etag = ETAG_RE.get_or_init(|| {
+        Regex::new(r"(?s)<(?:\w+:)?getetag\b[^>]*>(.*?)</(?:\w+:)?getetag>")
+            .expect("etag regex must compile")
+    });
+    let data = DATA_RE.get_or_init(|| {
+        Regex::new(r"(?s)<(?:\w+:)?calendar-data\b[^>]*>(.*?)</(?:\w+:)?calendar-data>")
+            .expect("calendar-data regex must compile")
+    });
+
+    response
+        .captures_iter(xml)
+        .filter_map(|block| {
+            let block = block.get(1)?.as_str();
+            let href = xml_unescape(href.captures(block)?.get(1)?.as_str().trim());
+            let ics = xml_unescape(data.captures(block)?.get(1)?.as_str());
+            let etag = etag
+                .captures(block)
+                .and_then(|c| c.get(1))
+                .map(|m| xml_unescape(m.as_str().trim()));
+            let (uid, title, done) = parse_vtodo(&ics)?;
+            Some(TaskItem {
+                id: uid,
+                title,
+                done,
+                handle: ItemHandle::CalDav { href, etag, ics },
+            })
+        })
+        .collect()
+}
+
+// ── Vikunja ─────────────────────────────────────────────────────
+
+struct VikunjaBackend {
+    config: TasksVikunjaConfig,
+}
+
+impl VikunjaBackend {
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}/api/v1{path}", self.config.url.trim_end_matches('/'));
+        let request = crate::config::build_runtime_proxy_client_with_timeouts("tool.tasks", 30, 10)
+            .request(method, url);
+        match &self.config.api_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> anyhow::Result<serde_json::Value> {
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            anyhow::bail!(
+                "Vikunja returned {status}: {}",
+                crate::util::truncate_with_ellipsis(&body, 200)
+            );
+        }
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    async fn project_id(&self, list: &str) -> anyhow::Result<i64> {
+        let projects = self
+            .send(self.request(reqwest::Method::GET, "/projects?per_page=250"))
+            .await?;
+        projects
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|project| {
+                project
+                    .get("title")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|title| title.trim().eq_ignore_ascii_case(list.trim()))
+            })
+            .and_then(|project| project.get("id").and_then(serde_json::Value::as_i64))
+            .with_context(|| format!("No Vikunja project named '{list}'"))
+    }
+}
+
+fn vikunja_item(task: serde_json::Value) -> Option<TaskItem> {
+    Some(TaskItem {
+        id: task.get("id")?.as_i64()?.to_string(),
+        title: task.get("title")?.as_str()?.to_string(),
+        done: task
+            .get("done")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false),
+        handle: ItemHandle::Vikunja(task),
+    })
+}
+
+#[async_trait]
+impl TaskBackend for VikunjaBackend {
+    async fn add(&self, list: &str, title: &str) -> anyhow::Result<TaskItem> {
+        let project = self.project_id(list).await?;
+        let task = self
+            .send(
+                self.request(reqwest::Method::PUT, &format!("/projects/{project}/tasks"))
+                    .json(&json!({ "title": title })),
+            )
+            .await?;
+        vikunja_item(task).context("Vikunja returned an unexpected task payload")
+    }
+
+    async fn items(&self, list: &str, include_done: bool) -> anyhow::Result<Vec<TaskItem>> {
+        let project = self.project_id(list).await?;
+        let mut filter = format!("project = {project}");
+        if !include_done {
+            filter.push_str(" && done = false");
+        }
+        let path = format!(
+            "/tasks/all?per_page=250&filter={}",
+            urlencoding::encode(&filter)
+        );
+        let tasks = self.send(self.request(reqwest::Method::GET, &path)).await?;
+        // Older Vikunja releases ignore `filter`; re-apply it locally.
+        Ok(tasks
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|task| {
+                task.get("project_id").and_then(serde_json::Value::as_i64) == Some(project)
+            })
+            .filter_map(vikunja_item)
+            .filter(|item| include_done || !item.done)
+            .collect())
+    }
+
+    async fn mark_done(&self, _list: &str, item: &TaskItem) -> anyhow::Result<()> {
+        let ItemHandle::Vikunja(task) = &item.handle else {
+            anyhow::bail!("item does not belong to the Vikunja backend");
+        };
+        // Vikunja's update endpoint replaces the whole task, so send it back intact.
+        let mut task = task.clone();
+        task["done"] = json!(true);
+        self.send(
+            self.request(reqwest::Method::POST, &format!("/tasks/{}", item.id))
+                .json(&task),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Tool for TasksTool {
+    fn name(&self) -> &str {
+        "tasks"
+    }
+
+    fn description(&self) -> &str {
+        "Manage named todo and shopping lists: add items, complete items, or list what is \
+         still open. Lists persist in the configured backend (local sqlite, CalDAV, or \
+         Vikunja) so they sync to phones and other clients."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["add", "complete", "list"],
+                    "description": "add items, mark an item done, or list a list's items"
+                },
+                "list": {
+                    "type": "string",
+                    "description": format!("List name (default: '{}')", self.config.default_list)
+                },
+                "item": {
+                    "description": "Item title for add (string or array of strings); item id or title for complete",
+                    "oneOf": [
+                        {"type": "string"},
+                        {"type": "array", "items": {"type": "string"}}
+                    ]
+                },
+                "include_done": {
+                    "type": "boolean",
+                    "description": "Include completed items when listing (default: false)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        if !matches!(action, "add" | "complete" | "list") {
+            return Ok(failure(format!(
+                "Unknown action '{action}'. Use 'add', 'complete', or 'list'."
+            )));
+        }
+
+        let list = args
+            .get("list")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or(self.config.default_list.as_str())
+            .to_string();
+
+        if action != "list" && !self.security.can_act() {
+            return Ok(failure("Action blocked: autonomy is read-only"));
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        if !self.security.record_action() {
+            return Ok(failure("Rate limit exceeded: action budget exhausted"));
+        }
+
+        Ok(self
+            .run(action, &list, &args)
+            .await
+            .unwrap_or_else(|e| failure(format!("tasks {action} failed: {e}"))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn test_security(level: AutonomyLevel, workspace: &Path) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: level,
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn test_tool(level: AutonomyLevel, workspace: &Path) -> TasksTool {
+        TasksTool::new(
+            test_security(level, workspace),
+            TasksConfig {
+                enabled: true,
+                ..TasksConfig::default()
+            },
+            workspace.to_path_buf(),
+        )
+    }
+
+    #[tokio::test]
+    async fn sqlite_add_complete_list_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(AutonomyLevel::Full, tmp.path());
+
+        let result = tool
+            .execute(json!({"action": "add", "item": ["Milk", "Oat milk", "Eggs"]}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let result = tool
+            .execute(json!({"action": "complete", "item": "milk"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("'Milk'"));
+
+        let result = tool
+            .execute(json!({"action": "list", "list": "Shopping"}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("(2 open)"));
+        assert!(!result.output.contains("] Milk"));
+
+        let result = tool
+            .execute(json!({"action": "list", "include_done": true}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("- [x] Milk"));
+    }
+
+    #[tokio::test]
+    async fn add_blocked_in_readonly_mode() {
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(AutonomyLevel::ReadOnly, tmp.path());
+        let result = tool
+            .execute(json!({"action": "add", "item": "bread"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+
+        let result = tool.execute(json!({"action": "list"})).await.unwrap();
+        assert!(result.success);
+    }
+
+    #[test]
+    fn find_item_rejects_ambiguous_substrings() {
+        let item = |id: i64, title: &str| TaskItem {
+            id: id.to_string(),
+            title: title.into(),
+            done: false,
+            handle: ItemHandle::Sqlite(id),
+        };
+        let items = vec![item(1, "Milk"), item(2, "Oat milk"), item(3, "Eggs")];
+        assert_eq!(find_item(&items, "3").unwrap().title, "Eggs");
+        assert_eq!(find_item(&items, "MILK").unwrap().id, "1");
+        assert_eq!(find_item(&items, "oat").unwrap().id, "2");
+        assert!(find_item(&items, "il").unwrap_err().contains("several"));
+        assert!(find_item(&items, "butter").is_err());
+    }
+
+    #[test]
+    fn parses_calendar_query_and_completes_vtodo() {
+        let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+ <d:response><d:href>/dav/calendars/alice/shopping/a.ics</d:href>
+  <d:propstat><d:prop><d:getetag>"e1"</d:getetag>
+  <cal:calendar-data>BEGIN:VCALENDAR&#13;
+BEGIN:VTODO&#13;
+UID:a&#13;
+SUMMARY:Milk\, 2 litres&#13;
+STATUS:NEEDS-ACTION&#13;
+END:VTODO&#13;
+END:VCALENDAR&#13;
+</cal:calendar-data></d:prop></d:propstat></d:response>
+ <d:response><d:href>/dav/calendars/alice/shopping/b.ics</d:href>
+  <d:propstat><d:prop><d:getetag>"e2"</d:getetag>
+  <cal:calendar-data>BEGIN:VCALENDAR
+BEGIN:VTODO
+UID:b
+SUMMARY:Very long
+  title
+COMPLETED:20260101T000000Z
+END:VTODO
+END:VCALENDAR
+</cal:calendar-data></d:prop></d:propstat></d:response>
+</d:multistatus>"#;
+        let items = parse_calendar_query(xml);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].title, "Milk, 2 litres");
+        assert!(!items[0].done);
+        assert_eq!(items[1].title, "Very long title");
+        assert!(items[1].done);
+
+        let ItemHandle::CalDav { etag, ics, .. } = &items[0].handle else {
+            panic!("expected CalDAV handle");
+        };
+        assert_eq!(etag.as_deref(), Some("\"e1\""));
+        let now = chrono::Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+        let completed = complete_vtodo(ics, now);
+        assert!(!completed.contains("NEEDS-ACTION"));
+        assert!(completed.contains("STATUS:COMPLETED\r\nCOMPLETED:20260301T090000Z"));
+        assert!(parse_vtodo(&completed).unwrap().2);
+    }
+
+    #[test]
+    fn new_vtodo_escapes_summary() {
+        let now = chrono::Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+        let ics = new_vtodo("u1", "Apples; pears, plums", now);
+        assert!(ics.contains("SUMMARY:Apples\\; pears\\, plums\r\n"));
+        let (uid, title, done) = parse_vtodo(&ics).unwrap();
+        assert_eq!(uid, "u1");
+        assert_eq!(title, "Apples; pears, plums");
+        assert!(!done);
+    }
+}
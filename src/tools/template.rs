@@ -0,0 +1,209 @@
+//! `{{…}}` argument templates shared by composite tools and cron jobs.
+//!
+//! A placeholder is a dot path into a JSON context, e.g. `{{now}}`,
+//! `{{last_result.output}}` or `{{trigger.payload.room}}`; array elements are
+//! addressed by index (`{{trigger.payload.items.0}}`). Strings are inserted
+//! as-is, other values as JSON. `\{{` produces a literal `{{`. Substituted
+//! values are never expanded again, and [`Escape::Shell`] single-quotes them
+//! so they reach a shell command as one literal word.
+
+use anyhow::{bail, Result};
+use chrono::{SecondsFormat, Utc};
+use serde_json::Value;
+
+/// How substituted values are escaped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escape {
+    None,
+    /// POSIX single quotes, for `sh -c` command strings.
+    Shell,
+}
+
+/// What to do with a placeholder the context cannot resolve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unknown {
+    Error,
+    /// Leave the placeholder text untouched (for strings that predate
+    /// templating and may contain unrelated `{{…}}`).
+    Keep,
+}
+
+enum Piece<'a> {
+    Text(&'a str),
+    Placeholder { key: &'a str, raw: &'a str },
+}
+
+fn parse(template: &str) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        if rest[..start].ends_with('\\') {
+            pieces.push(Piece::Text(&rest[..start - 1]));
+            pieces.push(Piece::Text("{{"));
+            rest = &rest[start + 2..];
+            continue;
+        }
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + len + 2;
+        pieces.push(Piece::Text(&rest[..start]));
+        pieces.push(Piece::Placeholder {
+            key: rest[start + 2..end - 2].trim(),
+            raw: &rest[start..end],
+        });
+        rest = &rest[end..];
+    }
+    pieces.push(Piece::Text(rest));
+    pieces
+}
+
+/// Placeholder paths used in `template`, in order of appearance.
+pub fn placeholders(template: &str) -> Vec<&str> {
+    parse(template)
+        .into_iter()
+        .filter_map(|piece| match piece {
+            Piece::Placeholder { key, .. } => Some(key),
+            Piece::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Placeholder paths in every string inside `value`.
+pub fn placeholders_in(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => placeholders(s).into_iter().map(str::to_string).collect(),
+        Value::Array(items) => items.iter().flat_map(placeholders_in).collect(),
+        Value::Object(map) => map.values().flat_map(placeholders_in).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Resolve a dot path against `ctx`.
+pub fn lookup<'a>(ctx: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(ctx, |node, segment| match node {
+        Value::Object(map) => map.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+/// Current time as an RFC 3339 UTC timestamp, the value of `{{now}}`.
+pub fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Shell-quote `value` as a single word.
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Expand placeholders in `template` from `ctx`.
+pub fn render(template: &str, ctx: &Value, escape: Escape, unknown: Unknown) -> Result<String> {
+    render_with(template, ctx, |_| escape, unknown)
+}
+
+/// Like [`render`], choosing the escaping per placeholder path.
+pub fn render_with(
+    template: &str,
+    ctx: &Value,
+    escape_for: impl Fn(&str) -> Escape,
+    unknown: Unknown,
+) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    for piece in parse(template) {
+        match piece {
+            Piece::Text(text) => out.push_str(text),
+            Piece::Placeholder { key, raw } => {
+                let Some(value) = lookup(ctx, key) else {
+                    match unknown {
+                        Unknown::Error => bail!("unknown template placeholder {raw}"),
+                        Unknown::Keep => {
+                            out.push_str(raw);
+                            continue;
+                        }
+                    }
+                };
+                let text = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                match escape_for(key) {
+                    Escape::None => out.push_str(&text),
+                    Escape::Shell => out.push_str(&shell_quote(&text)),
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Expand placeholders in every string inside `value`.
+pub fn render_value(value: &Value, ctx: &Value, escape: Escape) -> Result<Value> {
+    Ok(match value {
+        Value::String(s) => Value::String(render(s, ctx, escape, Unknown::Error)?),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|v| render_value(v, ctx, escape))
+                .collect::<Result<_>>()?,
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| Ok((k.clone(), render_value(v, ctx, escape)?)))
+                .collect::<Result<_>>()?,
+        ),
+        other => other.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn resolves_nested_paths_and_indexes() {
+        let ctx = json!({
+            "trigger": { "payload": { "room": "lounge", "items": ["a", "b"], "level": 40 } },
+            "last_result": { "output": "ok" }
+        });
+        let out = render(
+            "{{trigger.payload.room}}/{{ trigger.payload.items.1 }}/{{trigger.payload.level}}/{{last_result.output}}",
+            &ctx,
+            Escape::None,
+            Unknown::Error,
+        )
+        .unwrap();
+        assert_eq!(out, "lounge/b/40/ok");
+    }
+
+    #[test]
+    fn unknown_placeholders_error_or_stay() {
+        let ctx = json!({ "date": "2026-01-02" });
+        assert!(render("{{nope}}", &ctx, Escape::None, Unknown::Error).is_err());
+        let kept = render(
+            "docker ps --format '{{.Names}}' > {{date}}.txt",
+            &ctx,
+            Escape::None,
+            Unknown::Keep,
+        )
+        .unwrap();
+        assert_eq!(kept, "docker ps --format '{{.Names}}' > 2026-01-02.txt");
+    }
+
+    #[test]
+    fn escapes_braces_and_never_reexpands_values() {
+        let ctx = json!({ "x": "{{y}}", "y": "secret" });
+        let out = render(r"\{{x}} = {{x}}", &ctx, Escape::None, Unknown::Error).unwrap();
+        assert_eq!(out, "{{x}} = {{y}}");
+        assert_eq!(placeholders(r"\{{x}} {{ y }}"), ["y"]);
+    }
+
+    #[test]
+    fn shell_escape_quotes_values() {
+        let ctx = json!({ "name": "a'; rm -rf / #" });
+        let out = render("echo {{name}}", &ctx, Escape::Shell, Unknown::Error).unwrap();
+        assert_eq!(out, r"echo 'a'\''; rm -rf / #'");
+    }
+}
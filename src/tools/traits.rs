@@ -17,6 +17,15 @@ pub struct ToolSpec {
     pub parameters: serde_json::Value,
 }
 
+/// Tool call that undoes an earlier successful call, used to roll back
+/// partially applied plans.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Compensation {
+    pub tool: String,
+    #[serde(default)]
+    pub args: serde_json::Value,
+}
+
 /// Core tool trait — implement for any capability
 #[async_trait]
 pub trait Tool: Send + Sync {
@@ -32,6 +41,29 @@ pub trait Tool: Send + Sync {
     /// Execute the tool with given arguments
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult>;
 
+    /// Call that reverses a successful `execute(args)` which returned `result`.
+    /// `None` (the default) means the tool cannot be undone automatically.
+    fn compensation(
+        &self,
+        _args: &serde_json::Value,
+        _result: &ToolResult,
+    ) -> Option<Compensation> {
+        None
+    }
+
+    /// Tools that `execute(args)` would call on the caller's behalf, such as
+    /// plan or composite steps. The agent loop runs its approval check over
+    /// these as well as over the outer call.
+    fn nested_tools(&self, _args: &serde_json::Value) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Whether some action of this tool changes state, so the security
+    /// policy refuses it in read-only mode.
+    fn writes_state(&self) -> bool {
+        false
+    }
+
     /// Get the full spec for LLM registration
     fn spec(&self) -> ToolSpec {
         ToolSpec {
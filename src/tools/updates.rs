@@ -0,0 +1,728 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::UpdatesConfig;
+use crate::runtime::RuntimeAdapter;
+use crate::security::SecurityPolicy;
+use anyhow::Context;
+use async_trait::async_trait;
+use reqwest::header::{ACCEPT, USER_AGENT, WWW_AUTHENTICATE};
+use serde_json::{json, Value};
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Package lines shown per host before the rest are summarized.
+const MAX_PACKAGES_LISTED: usize = 25;
+
+/// Manifest types a registry may answer with; the index types come first so
+/// the digest matches what `docker pull <tag>` records in `RepoDigests`.
+const MANIFEST_ACCEPT: &str = "application/vnd.oci.image.index.v1+json, \
+     application/vnd.docker.distribution.manifest.list.v2+json, \
+     application/vnd.docker.distribution.manifest.v2+json, \
+     application/vnd.oci.image.manifest.v1+json";
+
+/// List commands per package manager. None of them needs root or refreshes
+/// the package index; that stays with the host's own update timer.
+fn list_command(manager: &str) -> Option<&'static str> {
+    match manager {
+        "apt" => Some("apt list --upgradable 2>/dev/null"),
+        // check-update exits 100 when updates are pending.
+        "dnf" => Some("dnf -q check-update; rc=$?; [ $rc -eq 100 ] && exit 0; exit $rc"),
+        // checkupdates (pacman-contrib) exits 2 when there is nothing to do.
+        "pacman" => Some(
+            "if command -v checkupdates >/dev/null 2>&1; then checkupdates; rc=$?; \
+             [ $rc -eq 2 ] && exit 0; exit $rc; else pacman -Qu; exit 0; fi",
+        ),
+        _ => None,
+    }
+}
+
+/// A script that prints `#manager <name>` and then that manager's pending updates.
+fn package_script(package_manager: &str) -> Option<String> {
+    let managers = match package_manager {
+        "auto" => vec!["apt", "dnf", "pacman"],
+        other => vec![other],
+    };
+    let mut script = String::new();
+    for (i, manager) in managers.iter().enumerate() {
+        let command = list_command(manager)?;
+        let keyword = if i == 0 { "if" } else { "elif" };
+        let _ = write!(
+            script,
+            "{keyword} command -v {manager} >/dev/null 2>&1; then echo \"#manager {manager}\"; {command}; "
+        );
+    }
+    script.push_str("else echo \"#manager none\"; fi");
+    Some(script)
+}
+
+/// `(manager, pending updates)` from a [`package_script`] run.
+fn parse_packages(output: &str) -> (String, Vec<String>) {
+    let mut lines = output.lines();
+    let manager = lines
+        .by_ref()
+        .find_map(|line| line.strip_prefix("#manager "))
+        .unwrap_or("unknown")
+        .trim()
+        .to_string();
+    let mut packages = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        match manager.as_str() {
+            // openssl/jammy-updates 3.0.2-0ubuntu1.16 amd64 [upgradable from: 3.0.2-0ubuntu1.15]
+            "apt" => {
+                let Some((head, from)) = line.split_once("[upgradable from: ") else {
+                    continue;
+                };
+                let mut fields = head.split_whitespace();
+                let (Some(name), Some(version)) = (fields.next(), fields.next()) else {
+                    continue;
+                };
+                let name = name.split('/').next().unwrap_or(name);
+                packages.push(format!(
+                    "{name} {} -> {version}",
+                    from.trim_end_matches(']')
+                ));
+            }
+            // openssl.x86_64  1:3.0.7-27.el9  baseos
+            "dnf" => {
+                if line.starts_with("Obsoleting") {
+                    break;
+                }
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if fields.len() == 3 {
+                    packages.push(format!("{} {}", fields[0], fields[1]));
+                }
+            }
+            // openssl 3.3.1-1 -> 3.3.2-1
+            "pacman" if line.contains(" -> ") => packages.push(line.to_string()),
+            _ => {}
+        }
+    }
+    (manager, packages)
+}
+
+/// Prints `#os <ID> <VERSION_ID>`, then `name version` per installed package,
+/// using source package names and versions as Debian/Ubuntu advisories do.
+const INVENTORY_SCRIPT: &str = "( . /etc/os-release 2>/dev/null; echo \"#os ${ID:-unknown} ${VERSION_ID:-}\" ); \
+     if command -v dpkg-query >/dev/null 2>&1; then dpkg-query -W -f='${db:Status-Status} ${source:Package} ${source:Version}\\n' \
+     | awk '$1 == \"installed\" { print $2, $3 }'; \
+     elif command -v rpm >/dev/null 2>&1; then rpm -qa --qf '%{NAME} %|EPOCH?{%{EPOCH}:}:{}|%{VERSION}-%{RELEASE}\\n'; fi";
+
+/// Installed OS packages of one host, in OSV terms.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct Inventory {
+    /// OSV ecosystem (`Debian:12`, `Ubuntu:22.04:LTS`, `Rocky Linux:9`);
+    /// `None` when the distribution has no OSV feed.
+    pub ecosystem: Option<String>,
+    /// `(name, version)`, de-duplicated.
+    pub packages: Vec<(String, String)>,
+}
+
+/// The OSV ecosystem for an `/etc/os-release` `ID` and `VERSION_ID`.
+fn osv_ecosystem(id: &str, version_id: &str) -> Option<String> {
+    let major = version_id.split('.').next().filter(|v| !v.is_empty());
+    match id {
+        "debian" => Some(format!("Debian:{}", major?)),
+        "ubuntu" => {
+            let (year, month) = version_id.split_once('.')?;
+            let lts = month == "04" && year.parse::<u32>().is_ok_and(|y| y % 2 == 0);
+            Some(if lts {
+                format!("Ubuntu:{version_id}:LTS")
+            } else {
+                format!("Ubuntu:{version_id}")
+            })
+        }
+        "rocky" => Some(format!("Rocky Linux:{}", major?)),
+        "almalinux" => Some(format!("AlmaLinux:{}", major?)),
+        _ => None,
+    }
+}
+
+fn parse_inventory(output: &str) -> Inventory {
+    let mut inventory = Inventory::default();
+    for line in output.lines() {
+        if let Some(os) = line.strip_prefix("#os ") {
+            let mut fields = os.split_whitespace();
+            inventory.ecosystem = osv_ecosystem(
+                fields.next().unwrap_or_default(),
+                fields.next().unwrap_or_default(),
+            );
+        } else if let Some((name, version)) = line
+            .trim()
+            .split_once(' ')
+            .filter(|(_, version)| !version.trim().is_empty())
+        {
+            inventory
+                .packages
+                .push((name.to_string(), version.trim().to_string()));
+        }
+    }
+    inventory.packages.sort();
+    inventory.packages.dedup();
+    inventory
+}
+
+/// Single-quote `value` for `sh`.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// SSH destinations, image and repo names end up in shell commands or URLs.
+fn is_safe_ref(value: &str) -> bool {
+    !value.is_empty()
+        && !value.starts_with('-')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "@._-:/".contains(c))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ImageRef {
+    /// Registry API host (`registry-1.docker.io` for Docker Hub).
+    registry: String,
+    repository: String,
+    tag: String,
+}
+
+/// Parse `nginx`, `nginx:1.27`, `ghcr.io/org/app:v2`; `None` for digest-pinned refs.
+fn parse_image(image: &str) -> Option<ImageRef> {
+    if image.contains('@') || !is_safe_ref(image) {
+        return None;
+    }
+    let (registry, path) = match image.split_once('/') {
+        Some((first, rest))
+            if first.contains('.') || first.contains(':') || first == "localhost" =>
+        {
+            (first, rest)
+        }
+        _ => ("docker.io", image),
+    };
+    let (path, tag) = match path.rsplit_once(':') {
+        Some((path, tag)) => (path, tag),
+        None => (path, "latest"),
+    };
+    let docker_hub = registry == "docker.io";
+    Some(ImageRef {
+        registry: if docker_hub {
+            "registry-1.docker.io".into()
+        } else {
+            registry.into()
+        },
+        repository: if docker_hub && !path.contains('/') {
+            format!("library/{path}")
+        } else {
+            path.into()
+        },
+        tag: tag.into(),
+    })
+}
+
+/// `(realm, params)` from `Bearer realm="…",service="…",scope="…"`.
+fn parse_bearer_challenge(header: &str) -> Option<(String, Vec<(String, String)>)> {
+    let rest = header.trim().strip_prefix("Bearer ")?;
+    let mut realm = None;
+    let mut params = Vec::new();
+    for part in rest.split(',') {
+        let (key, value) = part.trim().split_once('=')?;
+        let value = value.trim_matches('"').to_string();
+        if key == "realm" {
+            realm = Some(value);
+        } else {
+            params.push((key.to_string(), value));
+        }
+    }
+    Some((realm?, params))
+}
+
+fn normalize_version(version: &str) -> &str {
+    version.trim().trim_start_matches(['v', 'V'])
+}
+
+/// Pending OS packages, container image and GitHub release updates.
+pub struct UpdatesTool {
+    security: Arc<SecurityPolicy>,
+    runtime: Arc<dyn RuntimeAdapter>,
+    config: UpdatesConfig,
+}
+
+impl UpdatesTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        runtime: Arc<dyn RuntimeAdapter>,
+        config: UpdatesConfig,
+    ) -> Self {
+        Self {
+            security,
+            runtime,
+            config,
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.config.timeout_secs.max(1))
+    }
+
+    fn client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.updates",
+            self.config.timeout_secs.max(1),
+            10,
+        )
+    }
+
+    /// Run a fixed command through the runtime adapter with a minimal environment.
+    async fn run(&self, command: &str) -> anyhow::Result<String> {
+        let mut cmd = self
+            .runtime
+            .build_shell_command(command, &self.security.workspace_dir)?;
+        cmd.env_clear();
+        for var in ["PATH", "HOME", "SSH_AUTH_SOCK"] {
+            if let Ok(value) = std::env::var(var) {
+                cmd.env(var, value);
+            }
+        }
+        cmd.env("LC_ALL", "C");
+        cmd.kill_on_drop(true);
+        let output = tokio::time::timeout(self.timeout(), cmd.output())
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out after {}s", self.timeout().as_secs()))??;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(
+                "{}",
+                crate::util::truncate_with_ellipsis(
+                    stderr.trim().lines().last().unwrap_or("command failed"),
+                    200
+                )
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Run `script` on this host (`local`) and on every `ssh_hosts` entry.
+    async fn run_on_hosts(&self, script: &str) -> Vec<(String, anyhow::Result<String>)> {
+        let mut results = vec![("local".to_string(), self.run(script).await)];
+        for host in &self.config.ssh_hosts {
+            let result = if is_safe_ref(host) {
+                self.run(&format!(
+                    "ssh -o BatchMode=yes -o ConnectTimeout=10 {host} {}",
+                    shell_quote(script)
+                ))
+                .await
+            } else {
+                Err(anyhow::anyhow!("invalid SSH destination"))
+            };
+            results.push((host.clone(), result));
+        }
+        results
+    }
+
+    /// Installed OS packages per host, for advisory matching.
+    pub(super) async fn inventories(&self) -> Vec<(String, anyhow::Result<Inventory>)> {
+        self.run_on_hosts(INVENTORY_SCRIPT)
+            .await
+            .into_iter()
+            .map(|(host, result)| (host, result.map(|output| parse_inventory(&output))))
+            .collect()
+    }
+
+    async fn packages(
+        &self,
+        script: &str,
+        out: &mut String,
+        pending: &mut usize,
+        errors: &mut usize,
+    ) {
+        for (host, result) in self.run_on_hosts(script).await {
+            match result {
+                Ok(output) => {
+                    let (manager, packages) = parse_packages(&output);
+                    if manager == "none" {
+                        let _ = writeln!(out, "  {host}: no supported package manager");
+                    } else if packages.is_empty() {
+                        let _ = writeln!(out, "  {host} ({manager}): up to date");
+                    } else {
+                        *pending += packages.len();
+                        let _ = writeln!(out, "  {host} ({manager}): {} pending", packages.len());
+                        for package in packages.iter().take(MAX_PACKAGES_LISTED) {
+                            let _ = writeln!(out, "    {package}");
+                        }
+                        if packages.len() > MAX_PACKAGES_LISTED {
+                            let _ = writeln!(
+                                out,
+                                "    … and {} more",
+                                packages.len() - MAX_PACKAGES_LISTED
+                            );
+                        }
+                    }
+                }
+                Err(e) => {
+                    *errors += 1;
+                    let _ = writeln!(out, "  {host}: error — {e}");
+                }
+            }
+        }
+    }
+
+    /// Digests the local image store has recorded for `image`.
+    async fn local_digests(&self, image: &str) -> anyhow::Result<Vec<String>> {
+        let output = self
+            .run(&format!(
+                "{} image inspect --format '{{{{json .RepoDigests}}}}' {image}",
+                shell_quote(&self.config.container_runtime)
+            ))
+            .await?;
+        let digests: Vec<String> =
+            serde_json::from_str(output.trim()).context("unexpected image inspect output")?;
+        Ok(digests
+            .iter()
+            .filter_map(|reference| reference.split_once('@'))
+            .map(|(_, digest)| digest.to_string())
+            .collect())
+    }
+
+    /// Current manifest digest for the image's tag, with anonymous token auth.
+    async fn remote_digest(&self, image: &ImageRef) -> anyhow::Result<String> {
+        let client = self.client();
+        let url = format!(
+            "https://{}/v2/{}/manifests/{}",
+            image.registry, image.repository, image.tag
+        );
+        let mut response = client
+            .head(&url)
+            .header(ACCEPT, MANIFEST_ACCEPT)
+            .send()
+            .await?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+            let (realm, params) = response
+                .headers()
+                .get(WWW_AUTHENTICATE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_bearer_challenge)
+                .context("registry requires unsupported authentication")?;
+            let token: Value = client
+                .get(&realm)
+                .query(&params)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            let token = token["token"]
+                .as_str()
+                .or_else(|| token["access_token"].as_str())
+                .context("registry token response has no token")?;
+            response = client
+                .head(&url)
+                .header(ACCEPT, MANIFEST_ACCEPT)
+                .bearer_auth(token)
+                .send()
+                .await?;
+        }
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("registry returned {status}");
+        }
+        response
+            .headers()
+            .get("docker-content-digest")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+            .context("registry response has no digest")
+    }
+
+    async fn images(&self, out: &mut String, pending: &mut usize, errors: &mut usize) {
+        for image in &self.config.images {
+            let Some(reference) = parse_image(image) else {
+                *errors += 1;
+                let _ = writeln!(out, "  {image}: error — not a tag reference");
+                continue;
+            };
+            let remote = match self.remote_digest(&reference).await {
+                Ok(digest) => digest,
+                Err(e) => {
+                    *errors += 1;
+                    let _ = writeln!(out, "  {image}: error — {e}");
+                    continue;
+                }
+            };
+            let short = remote.get(..19).unwrap_or(&remote);
+            match self.local_digests(image).await {
+                Ok(local) if local.contains(&remote) => {
+                    let _ = writeln!(out, "  {image}: up to date ({short})");
+                }
+                Ok(local) if local.is_empty() => {
+                    let _ = writeln!(out, "  {image}: no local digest (built locally?)");
+                }
+                Ok(_) => {
+                    *pending += 1;
+                    let _ = writeln!(out, "  {image}: update available (registry {short})");
+                }
+                Err(e) => {
+                    *errors += 1;
+                    let _ = writeln!(out, "  {image}: error — local image: {e}");
+                }
+            }
+        }
+    }
+
+    async fn latest_release(&self, repo: &str) -> anyhow::Result<Value> {
+        let response = self
+            .client()
+            .get(format!(
+                "https://api.github.com/repos/{repo}/releases/latest"
+            ))
+            .header(USER_AGENT, "zeroclaw")
+            .header(ACCEPT, "application/vnd.github+json")
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("GitHub returned {status}");
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn releases(&self, out: &mut String, pending: &mut usize, errors: &mut usize) {
+        for entry in &self.config.github_releases {
+            let (repo, installed) = match entry.split_once('@') {
+                Some((repo, installed)) => (repo, Some(installed)),
+                None => (entry.as_str(), None),
+            };
+            if repo.split('/').count() != 2 || !is_safe_ref(repo) {
+                *errors += 1;
+                let _ = writeln!(out, "  {entry}: error — expected owner/repo");
+                continue;
+            }
+            let release = match self.latest_release(repo).await {
+                Ok(release) => release,
+                Err(e) => {
+                    *errors += 1;
+                    let _ = writeln!(out, "  {repo}: error — {e}");
+                    continue;
+                }
+            };
+            let tag = release["tag_name"].as_str().unwrap_or("?");
+            let published = release["published_at"]
+                .as_str()
+                .and_then(|date| date.get(..10))
+                .unwrap_or("unknown date");
+            match installed {
+                Some(installed) if normalize_version(installed) == normalize_version(tag) => {
+                    let _ = writeln!(out, "  {repo}: {tag} ({published}), up to date");
+                }
+                Some(installed) => {
+                    *pending += 1;
+                    let _ = writeln!(
+                        out,
+                        "  {repo}: {tag} ({published}), installed {installed} — update available"
+                    );
+                }
+                None => {
+                    let _ = writeln!(out, "  {repo}: latest {tag} ({published})");
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for UpdatesTool {
+    fn name(&self) -> &str {
+        "updates"
+    }
+
+    fn description(&self) -> &str {
+        "Report pending updates: OS packages (apt/dnf/pacman) on this host and configured SSH \
+         hosts, newer registry digests for configured container images, and new GitHub \
+         releases. Read-only; nothing is installed."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "source": {
+                    "type": "string",
+                    "enum": ["all", "packages", "images", "releases"],
+                    "description": "Which checks to run (default all)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let source = args.get("source").and_then(|v| v.as_str()).unwrap_or("all");
+        if !["all", "packages", "images", "releases"].contains(&source) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Unknown source '{source}'")),
+            });
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let wants = |name: &str| source == "all" || source == name;
+        let mut output = String::new();
+        let mut pending = 0usize;
+        let mut errors = 0usize;
+
+        let manager = self.config.package_manager.trim().to_ascii_lowercase();
+        if wants("packages") && manager != "none" {
+            output.push_str("Packages:\n");
+            match package_script(&manager) {
+                Some(script) => {
+                    self.packages(&script, &mut output, &mut pending, &mut errors)
+                        .await;
+                }
+                None => {
+                    errors += 1;
+                    let _ = writeln!(output, "  error — unknown package_manager '{manager}'");
+                }
+            }
+        }
+        if wants("images") && !self.config.images.is_empty() {
+            output.push_str("Images:\n");
+            self.images(&mut output, &mut pending, &mut errors).await;
+        }
+        if wants("releases") && !self.config.github_releases.is_empty() {
+            output.push_str("Releases:\n");
+            self.releases(&mut output, &mut pending, &mut errors).await;
+        }
+
+        if output.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Nothing to check for '{source}' in [updates]")),
+            });
+        }
+        let _ = write!(output, "Summary: {pending} pending, {errors} errors");
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_package_manager_output() {
+        let apt = "#manager apt\nListing...\n\
+                   openssl/jammy-updates 3.0.2-0ubuntu1.16 amd64 [upgradable from: 3.0.2-0ubuntu1.15]\n";
+        assert_eq!(
+            parse_packages(apt),
+            (
+                "apt".to_string(),
+                vec!["openssl 3.0.2-0ubuntu1.15 -> 3.0.2-0ubuntu1.16".to_string()]
+            )
+        );
+
+        let dnf = "#manager dnf\n\nopenssl.x86_64  1:3.0.7-27.el9  baseos\n\
+                   Obsoleting Packages\ngrub2-tools.x86_64  1:2.06-80.el9  baseos\n";
+        assert_eq!(
+            parse_packages(dnf).1,
+            vec!["openssl.x86_64 1:3.0.7-27.el9".to_string()]
+        );
+
+        let pacman = "#manager pacman\nopenssl 3.3.1-1 -> 3.3.2-1\n";
+        assert_eq!(parse_packages(pacman).1, vec!["openssl 3.3.1-1 -> 3.3.2-1"]);
+        assert_eq!(parse_packages("#manager none\n").0, "none");
+
+        let script = package_script("auto").unwrap();
+        assert!(script.starts_with("if command -v apt"));
+        assert!(script.contains("elif command -v pacman"));
+        assert!(package_script("zypper").is_none());
+        assert_eq!(shell_quote("echo 'hi'"), r"'echo '\''hi'\'''");
+    }
+
+    #[test]
+    fn parses_inventory_and_osv_ecosystems() {
+        let output =
+            "#os ubuntu 22.04\nopenssl 3.0.2-0ubuntu1.15\nopenssl 3.0.2-0ubuntu1.15\nzlib 1:1.2.13\n";
+        assert_eq!(
+            parse_inventory(output),
+            Inventory {
+                ecosystem: Some("Ubuntu:22.04:LTS".into()),
+                packages: vec![
+                    ("openssl".into(), "3.0.2-0ubuntu1.15".into()),
+                    ("zlib".into(), "1:1.2.13".into()),
+                ],
+            }
+        );
+        assert_eq!(
+            osv_ecosystem("ubuntu", "23.10").as_deref(),
+            Some("Ubuntu:23.10")
+        );
+        assert_eq!(osv_ecosystem("debian", "12").as_deref(), Some("Debian:12"));
+        assert_eq!(
+            osv_ecosystem("rocky", "9.4").as_deref(),
+            Some("Rocky Linux:9")
+        );
+        assert_eq!(osv_ecosystem("debian", ""), None);
+        assert_eq!(osv_ecosystem("arch", ""), None);
+    }
+
+    #[test]
+    fn parses_image_refs_and_registry_challenges() {
+        assert_eq!(
+            parse_image("nginx"),
+            Some(ImageRef {
+                registry: "registry-1.docker.io".into(),
+                repository: "library/nginx".into(),
+                tag: "latest".into(),
+            })
+        );
+        assert_eq!(
+            parse_image("ghcr.io/org/app:v2"),
+            Some(ImageRef {
+                registry: "ghcr.io".into(),
+                repository: "org/app".into(),
+                tag: "v2".into(),
+            })
+        );
+        assert_eq!(
+            parse_image("localhost:5000/tools/app").map(|r| (r.registry, r.tag)),
+            Some(("localhost:5000".into(), "latest".into()))
+        );
+        assert!(parse_image("nginx@sha256:abc").is_none());
+        assert!(parse_image("nginx; rm -rf /").is_none());
+
+        let (realm, params) = parse_bearer_challenge(
+            r#"Bearer realm="https://auth.docker.io/token",service="registry.docker.io",scope="repository:library/nginx:pull""#,
+        )
+        .unwrap();
+        assert_eq!(realm, "https://auth.docker.io/token");
+        assert_eq!(
+            params,
+            vec![
+                ("service".to_string(), "registry.docker.io".to_string()),
+                (
+                    "scope".to_string(),
+                    "repository:library/nginx:pull".to_string()
+                ),
+            ]
+        );
+        assert!(parse_bearer_challenge("Basic realm=\"x\"").is_none());
+        assert_eq!(normalize_version("v1.2.3"), normalize_version("1.2.3"));
+    }
+}
@@ -0,0 +1,566 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::{WebDavConfig, WebDavTarget};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::{Arc, OnceLock};
+
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:resourcetype/><d:getcontentlength/><d:getlastmodified/></d:prop></d:propfind>"#;
+
+/// Entry returned by a depth-1 PROPFIND.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DavEntry {
+    path: String,
+    is_dir: bool,
+    size: u64,
+    modified: String,
+}
+
+/// List, download, and upload files on configured WebDAV / Nextcloud bases.
+pub struct WebDavTool {
+    security: Arc<SecurityPolicy>,
+    config: WebDavConfig,
+}
+
+impl WebDavTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: WebDavConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn max_bytes(&self) -> u64 {
+        self.config.max_file_mb.max(1).saturating_mul(1024 * 1024)
+    }
+
+    fn client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.webdav",
+            self.config.timeout_secs.max(1),
+            10,
+        )
+    }
+
+    fn request(
+        &self,
+        target: &WebDavTarget,
+        method: reqwest::Method,
+        remote: &str,
+    ) -> reqwest::RequestBuilder {
+        let request = self
+            .client()
+            .request(method, remote_url(&target.url, remote));
+        match &target.username {
+            Some(user) => request.basic_auth(user, target.password.as_deref()),
+            None => request,
+        }
+    }
+
+    async fn list(&self, target: &WebDavTarget, remote: &str) -> anyhow::Result<ToolResult> {
+        let method = reqwest::Method::from_bytes(b"PROPFIND")?;
+        let response = self
+            .request(target, method, remote)
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(PROPFIND_BODY)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Ok(failure(format!("PROPFIND {remote} returned {status}")));
+        }
+
+        let body = response.text().await?;
+        let base_path = reqwest::Url::parse(&target.url)
+            .map(|u| u.path().to_string())
+            .unwrap_or_default();
+        let self_path = normalize_remote(remote).unwrap_or_default();
+        let entries: Vec<DavEntry> = parse_propfind(&body, &base_path)
+            .into_iter()
+            .filter(|entry| entry.path.trim_end_matches('/') != self_path.trim_end_matches('/'))
+            .collect();
+
+        if entries.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: format!("{remote} is empty"),
+                error: None,
+            });
+        }
+
+        let mut output = String::new();
+        for entry in &entries {
+            if entry.is_dir {
+                let _ = writeln!(
+                    output,
+                    "{}/\t<dir>\t{}",
+                    entry.path.trim_end_matches('/'),
+                    entry.modified
+                );
+            } else {
+                let _ = writeln!(
+                    output,
+                    "{}\t{} bytes\t{}",
+                    entry.path, entry.size, entry.modified
+                );
+            }
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+
+    async fn download(
+        &self,
+        target: &WebDavTarget,
+        remote: &str,
+        local: &str,
+    ) -> anyhow::Result<ToolResult> {
+        let destination = match self.resolve_write_target(local).await {
+            Ok(path) => path,
+            Err(e) => return Ok(failure(e)),
+        };
+
+        let mut response = self
+            .request(target, reqwest::Method::GET, remote)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Ok(failure(format!("GET {remote} returned {status}")));
+        }
+
+        let limit = self.max_bytes();
+        if response.content_length().is_some_and(|len| len > limit) {
+            return Ok(failure(format!(
+                "{remote} exceeds the {limit}-byte transfer limit"
+            )));
+        }
+        let mut data = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            data.extend_from_slice(&chunk);
+            if data.len() as u64 > limit {
+                return Ok(failure(format!(
+                    "{remote} exceeds the {limit}-byte transfer limit"
+                )));
+            }
+        }
+
+        tokio::fs::write(&destination, &data).await?;
+        Ok(ToolResult {
+            success: true,
+            output: format!("Downloaded {remote} ({} bytes) to {local}", data.len()),
+            error: None,
+        })
+    }
+
+    async fn upload(
+        &self,
+        target: &WebDavTarget,
+        remote: &str,
+        local: &str,
+    ) -> anyhow::Result<ToolResult> {
+        let full_path = self.security.workspace_dir.join(local);
+        let resolved = match tokio::fs::canonicalize(&full_path).await {
+            Ok(p) => p,
+            Err(e) => return Ok(failure(format!("Failed to resolve file path: {e}"))),
+        };
+        if !self.security.is_resolved_path_allowed(&resolved) {
+            return Ok(failure(
+                self.security.resolved_path_violation_message(&resolved),
+            ));
+        }
+        let size = tokio::fs::metadata(&resolved).await?.len();
+        if size > self.max_bytes() {
+            return Ok(failure(format!(
+                "File too large: {size} bytes (limit: {} bytes)",
+                self.max_bytes()
+            )));
+        }
+
+        let data = tokio::fs::read(&resolved).await?;
+        let content_type = mime_guess::from_path(&resolved)
+            .first_raw()
+            .unwrap_or("application/octet-stream");
+        let response = self
+            .request(target, reqwest::Method::PUT, remote)
+            .header("Content-Type", content_type)
+            .body(data)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Ok(failure(format!("PUT {remote} returned {status}")));
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: format!("Uploaded {local} ({size} bytes) to {remote}"),
+            error: None,
+        })
+    }
+
+    /// Resolve a download destination with the same sandboxing as `file_write`.
+    async fn resolve_write_target(&self, path: &str) -> Result<std::path::PathBuf, String> {
+        let full_path = self.security.workspace_dir.join(path);
+        let (Some(parent), Some(file_name)) = (full_path.parent(), full_path.file_name()) else {
+            return Err("Invalid path: missing parent directory or file name".into());
+        };
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create directory: {e}"))?;
+        let resolved_parent = tokio::fs::canonicalize(parent)
+            .await
+            .map_err(|e| format!("Failed to resolve file path: {e}"))?;
+        if !self.security.is_resolved_path_allowed(&resolved_parent) {
+            return Err(self
+                .security
+                .resolved_path_violation_message(&resolved_parent));
+        }
+        let destination = resolved_parent.join(file_name);
+        if let Ok(meta) = tokio::fs::symlink_metadata(&destination).await {
+            if meta.file_type().is_symlink() {
+                return Err(format!(
+                    "Refusing to write through symlink: {}",
+                    destination.display()
+                ));
+            }
+        }
+        Ok(destination)
+    }
+}
+
+fn failure(message: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(message.into()),
+    }
+}
+
+/// Normalize a remote path to `/a/b`, rejecting `..` and empty segments
+/// that could climb out of an allowed prefix.
+fn normalize_remote(path: &str) -> Option<String> {
+    let mut segments = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => return None,
+            other => segments.push(other),
+        }
+    }
+    Some(format!("/{}", segments.join("/")))
+}
+
+/// Whether `remote` (already normalized) sits under one of `allowed`.
+fn is_remote_allowed(allowed: &[String], remote: &str) -> bool {
+    allowed.iter().any(|prefix| {
+        let Some(prefix) = normalize_remote(prefix) else {
+            return false;
+        };
+        prefix == "/"
+            || remote == prefix
+            || remote
+                .strip_prefix(prefix.as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+fn remote_url(base: &str, remote: &str) -> String {
+    let encoded: Vec<String> = remote
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect();
+    format!("{}/{}", base.trim_end_matches('/'), encoded.join("/"))
+}
+
+fn parse_propfind(xml: &str, base_path: &str) -> Vec<DavEntry> {
+    static RESPONSE_RE: OnceLock<Regex> = OnceLock::new();
+    static HREF_RE: OnceLock<Regex> = OnceLock::new();
+    static LENGTH_RE: OnceLock<Regex> = OnceLock::new();
+    static MODIFIED_RE: OnceLock<Regex> = OnceLock::new();
+    static COLLECTION_RE: OnceLock<Regex> = OnceLock::new();
+    let response = RESPONSE_RE.get_or_init(|| {
+        Regex::new(r"(?s)<(?:\w+:)?response\b[^>]*>(.*?)</(?:\w+:)?response>")
+            .expect("response regex must compile")
+    });
+    let href = HREF_RE.get_or_init(|| {
+        Regex::new(r"(?s)<(?:\w+:)?href\b[^>]*>(.*?)</(?:\w+:)?href>")
+            .expect("href regex must compile")
+    });
+    let length = LENGTH_RE.get_or_init(|| {
+        Regex::new(r"<(?:\w+:)?getcontentlength\b[^>]*>\s*(\d+)\s*<")
+            .expect("length regex must compile")
+    });
+    let modified = MODIFIED_RE.get_or_init(|| {
+        Regex::new(r"<(?:\w+:)?getlastmodified\b[^>]*>([^<]*)<")
+            .expect("modified regex must compile")
+    });
+    let collection = COLLECTION_RE.get_or_init(|| {
+        Regex::new(r"<(?:\w+:)?collection\s*/?>").expect("collection regex must compile")
+    });
+
+    let base_path = base_path.trim_end_matches('/');
+    response
+        .captures_iter(xml)
+        .filter_map(|caps| {
+            let block = &caps[1];
+            let raw_href = href
+                .captures(block)?
+                .get(1)?
+                .as_str()
+                .trim()
+                .replace("&amp;", "&");
+            // Some servers return absolute URLs rather than paths.
+            let href_path = reqwest::Url::parse(&raw_href)
+                .map(|u| u.path().to_string())
+                .unwrap_or(raw_href);
+            let decoded = urlencoding::decode(&href_path)
+                .map(std::borrow::Cow::into_owned)
+                .unwrap_or(href_path);
+            let relative = decoded.strip_prefix(base_path).unwrap_or(&decoded);
+            Some(DavEntry {
+                path: if relative.starts_with('/') {
+                    relative.to_string()
+                } else {
+                    format!("/{relative}")
+                },
+                is_dir: collection.is_match(block),
+                size: length
+                    .captures(block)
+                    .and_then(|c| c[1].parse().ok())
+                    .unwrap_or(0),
+                modified: modified
+                    .captures(block)
+                    .map(|c| c[1].trim().to_string())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Tool for WebDavTool {
+    fn name(&self) -> &str {
+        "webdav"
+    }
+
+    fn description(&self) -> &str {
+        "List, download, or upload files on a configured WebDAV / Nextcloud target. Remote \
+         paths are relative to the target's base URL and limited to its allowed_paths; local \
+         paths are workspace files."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let mut targets: Vec<&String> = self.config.targets.keys().collect();
+        targets.sort();
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "download", "upload"],
+                    "description": "list a remote directory, download a remote file, or upload a workspace file"
+                },
+                "target": {
+                    "type": "string",
+                    "enum": targets,
+                    "description": "Configured WebDAV target name"
+                },
+                "remote_path": {
+                    "type": "string",
+                    "description": "Path relative to the target base, e.g. 'Documents/notes.md'"
+                },
+                "local_path": {
+                    "type": "string",
+                    "description": "Workspace path to write (download) or read (upload)"
+                }
+            },
+            "required": ["action", "target"]
+        })
+    }
+
+    fn writes_state(&self) -> bool {
+        true
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        let target_name = args
+            .get("target")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'target' parameter"))?;
+        let remote_raw = args
+            .get("remote_path")
+            .and_then(|v| v.as_str())
+            .unwrap_or("/");
+        let local = args.get("local_path").and_then(|v| v.as_str());
+
+        if !matches!(action, "list" | "download" | "upload") {
+            return Ok(failure(format!(
+                "Unknown action '{action}'. Use 'list', 'download', or 'upload'."
+            )));
+        }
+
+        let Some(target) = self.config.targets.get(target_name) else {
+            return Ok(failure(format!("Unknown WebDAV target '{target_name}'")));
+        };
+
+        let Some(remote) = normalize_remote(remote_raw) else {
+            return Ok(failure(format!("Invalid remote path: {remote_raw}")));
+        };
+        if !is_remote_allowed(&target.allowed_paths, &remote) {
+            return Ok(failure(format!(
+                "Remote path {remote} is not in the allowed_paths of '{target_name}'"
+            )));
+        }
+
+        if action != "list" {
+            if remote == "/" {
+                return Ok(failure(format!(
+                    "'remote_path' must name a file for {action}"
+                )));
+            }
+            let Some(local) = local else {
+                return Ok(failure(format!("'local_path' is required for {action}")));
+            };
+            if !self.security.can_act() {
+                return Ok(failure("Action blocked: autonomy is read-only"));
+            }
+            if !self.security.is_path_allowed(local) {
+                return Ok(failure(format!(
+                    "Path not allowed by security policy: {local}"
+                )));
+            }
+        }
+
+        if self.security.is_rate_limited() {
+            return Ok(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+
+        if !self.security.record_action() {
+            return Ok(failure("Rate limit exceeded: action budget exhausted"));
+        }
+
+        let local = local.unwrap_or_default();
+        let result = match action {
+            "list" => self.list(target, &remote).await,
+            "download" => self.download(target, &remote, local).await,
+            _ => self.upload(target, &remote, local).await,
+        };
+        Ok(result.unwrap_or_else(|e| failure(format!("WebDAV {action} failed: {e}"))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use std::collections::HashMap;
+
+    fn test_security(level: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy: level,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn test_config() -> WebDavConfig {
+        let mut targets = HashMap::new();
+        targets.insert(
+            "cloud".to_string(),
+            WebDavTarget {
+                url: "https://cloud.example.com/remote.php/dav/files/alice/".into(),
+                username: Some("alice".into()),
+                password: Some("app-password".into()),
+                allowed_paths: vec!["/Documents".into()],
+            },
+        );
+        WebDavConfig {
+            enabled: true,
+            targets,
+            ..WebDavConfig::default()
+        }
+    }
+
+    #[test]
+    fn remote_paths_are_normalized_and_scoped() {
+        assert_eq!(
+            normalize_remote("Documents//a/./b.md").unwrap(),
+            "/Documents/a/b.md"
+        );
+        assert!(normalize_remote("Documents/../secrets").is_none());
+
+        let allowed = vec!["/Documents".to_string()];
+        assert!(is_remote_allowed(&allowed, "/Documents"));
+        assert!(is_remote_allowed(&allowed, "/Documents/tax.pdf"));
+        assert!(!is_remote_allowed(&allowed, "/DocumentsPrivate/x"));
+        assert!(!is_remote_allowed(&allowed, "/Photos/x.jpg"));
+        assert!(is_remote_allowed(&["/".to_string()], "/anything"));
+    }
+
+    #[test]
+    fn remote_url_encodes_segments() {
+        assert_eq!(
+            remote_url("https://h/dav/files/alice/", "/My Docs/a#b.md"),
+            "https://h/dav/files/alice/My%20Docs/a%23b.md"
+        );
+    }
+
+    #[test]
+    fn parses_nextcloud_propfind_response() {
+        let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:">
+ <d:response><d:href>/remote.php/dav/files/alice/Documents/</d:href>
+  <d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype>
+  <d:getlastmodified>Mon, 05 Jan 2026 10:00:00 GMT</d:getlastmodified></d:prop></d:propstat></d:response>
+ <d:response><d:href>/remote.php/dav/files/alice/Documents/Tax%202025.pdf</d:href>
+  <d:propstat><d:prop><d:resourcetype/><d:getcontentlength>2048</d:getcontentlength>
+  <d:getlastmodified>Tue, 06 Jan 2026 10:00:00 GMT</d:getlastmodified></d:prop></d:propstat></d:response>
+</d:multistatus>"#;
+        let entries = parse_propfind(xml, "/remote.php/dav/files/alice/");
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].is_dir);
+        assert_eq!(entries[0].path, "/Documents/");
+        assert_eq!(entries[1].path, "/Documents/Tax 2025.pdf");
+        assert_eq!(entries[1].size, 2048);
+        assert!(!entries[1].is_dir);
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_allowlist() {
+        let tool = WebDavTool::new(test_security(AutonomyLevel::Full), test_config());
+        let result = tool
+            .execute(json!({"action": "list", "target": "cloud", "remote_path": "Photos"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("allowed_paths"));
+    }
+
+    #[tokio::test]
+    async fn upload_blocked_in_readonly_mode() {
+        let tool = WebDavTool::new(test_security(AutonomyLevel::ReadOnly), test_config());
+        let result = tool
+            .execute(json!({
+                "action": "upload",
+                "target": "cloud",
+                "remote_path": "Documents/a.md",
+                "local_path": "a.md"
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+}
@@ -0,0 +1,149 @@
+use super::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Read-only lookup of last-known locations reported via `/hooks/location`.
+pub struct WhereIsTool {
+    workspace_dir: PathBuf,
+}
+
+impl WhereIsTool {
+    pub fn new(workspace_dir: PathBuf) -> Self {
+        Self { workspace_dir }
+    }
+}
+
+fn format_age(seconds: i64) -> String {
+    match seconds {
+        s if s < 90 => "just now".into(),
+        s if s < 90 * 60 => format!("{} min ago", s / 60),
+        s if s < 36 * 3600 => format!("{} h ago", s / 3600),
+        s => format!("{} days ago", s / 86_400),
+    }
+}
+
+#[async_trait]
+impl Tool for WhereIsTool {
+    fn name(&self) -> &str {
+        "where_is"
+    }
+
+    fn description(&self) -> &str {
+        "Show the last-known location of a person (or everyone) as reported by OwnTracks / \
+         Home Assistant, including age of the fix and which geofences they are in."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "person": {
+                    "type": "string",
+                    "description": "Person to look up; omit to list everyone"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let person = args
+            .get("person")
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty());
+
+        let workspace_dir = self.workspace_dir.clone();
+        let fixes =
+            tokio::task::spawn_blocking(move || crate::location::last_known(&workspace_dir))
+                .await??;
+
+        let now = chrono::Utc::now();
+        let mut output = String::new();
+        for (name, fix) in &fixes {
+            if person.as_ref().is_some_and(|p| p != name) {
+                continue;
+            }
+            let _ = write!(output, "{name}: {:.5}, {:.5}", fix.lat, fix.lon);
+            if let Some(acc) = fix.accuracy_m {
+                let _ = write!(output, " (±{acc:.0} m)");
+            }
+            let _ = write!(
+                output,
+                " — {}",
+                format_age((now - fix.timestamp).num_seconds().max(0))
+            );
+            if !fix.regions.is_empty() {
+                let _ = write!(output, ", in {}", fix.regions.join(", "));
+            }
+            if let Some(battery) = fix.battery {
+                let _ = write!(output, ", battery {battery}%");
+            }
+            let _ = writeln!(
+                output,
+                "\n  https://www.openstreetmap.org/?mlat={:.5}&mlon={:.5}#map=16/{:.5}/{:.5}",
+                fix.lat, fix.lon, fix.lat, fix.lon
+            );
+        }
+
+        if output.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(match person {
+                    Some(person) => format!("No location reported for '{person}'"),
+                    None => "No locations reported yet".into(),
+                }),
+            });
+        }
+        Ok(ToolResult {
+            success: true,
+            output: output.trim_end().to_string(),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GeofenceConfig;
+    use crate::location::{record_update, LocationUpdate};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn reports_last_fix_and_region() {
+        let tmp = TempDir::new().unwrap();
+        let fences = vec![GeofenceConfig {
+            name: "home".into(),
+            lat: 52.52,
+            lon: 13.405,
+            radius_m: 100.0,
+        }];
+        record_update(
+            tmp.path(),
+            &fences,
+            LocationUpdate {
+                person: "alice".into(),
+                lat: 52.52,
+                lon: 13.405,
+                accuracy_m: Some(8.0),
+                battery: Some(64),
+                timestamp: chrono::Utc::now(),
+                source: "owntracks".into(),
+            },
+        )
+        .unwrap();
+
+        let tool = WhereIsTool::new(tmp.path().to_path_buf());
+        let result = tool.execute(json!({"person": "Alice"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result
+            .output
+            .contains("alice: 52.52000, 13.40500 (±8 m) — just now, in home, battery 64%"));
+
+        let result = tool.execute(json!({"person": "bob"})).await.unwrap();
+        assert!(!result.success);
+    }
+}
@@ -0,0 +1,118 @@
+//! Uptime Kuma push heartbeats.
+//!
+//! Each `[[uptime_kuma.push_jobs]]` entry is an Uptime Kuma "Push" monitor:
+//! the daemon calls its push URL every `interval_secs`, so the monitor goes
+//! down when the daemon does. Pushes are plain HTTP and never involve the
+//! model, so they prove the daemon is alive rather than that it is busy.
+
+use crate::config::{Config, UptimeKumaPushJobConfig};
+use anyhow::{anyhow, bail, Result};
+use tokio::time::{Duration, MissedTickBehavior};
+
+const COMPONENT: &str = "uptime_kuma";
+const PUSH_TIMEOUT_SECS: u64 = 10;
+const MIN_INTERVAL_SECS: u64 = 20;
+
+fn push_endpoint(job: &UptimeKumaPushJobConfig) -> String {
+    format!(
+        "{}/api/push/{}",
+        job.url.trim().trim_end_matches('/'),
+        job.token.trim()
+    )
+}
+
+/// Send one `status=up` heartbeat. Errors never include the token.
+async fn push(client: &reqwest::Client, job: &UptimeKumaPushJobConfig) -> Result<()> {
+    let response = client
+        .get(push_endpoint(job))
+        .query(&[("status", "up"), ("msg", job.msg.as_str()), ("ping", "")])
+        .send()
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "Uptime Kuma push to {} failed: {}",
+                job.url,
+                e.without_url()
+            )
+        })?;
+    let status = response.status();
+    // Kuma answers `{"ok": true}`, or `{"ok": false, "msg": "..."}` for an
+    // unknown token or a paused monitor.
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() || body.get("ok") == Some(&serde_json::Value::Bool(false)) {
+        let msg = body.get("msg").and_then(|v| v.as_str()).unwrap_or("");
+        bail!("Uptime Kuma push to {} rejected ({status}): {msg}", job.url);
+    }
+    Ok(())
+}
+
+/// Daemon loop heartbeating every configured push monitor.
+pub async fn run(config: Config) -> Result<()> {
+    let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        "uptime_kuma.push",
+        PUSH_TIMEOUT_SECS,
+        10,
+    );
+    let mut jobs = tokio::task::JoinSet::new();
+    for job in config.uptime_kuma.push_jobs {
+        let client = client.clone();
+        jobs.spawn(async move {
+            let period = Duration::from_secs(job.interval_secs.max(MIN_INTERVAL_SECS));
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                match push(&client, &job).await {
+                    Ok(()) => crate::health::mark_component_ok(COMPONENT),
+                    Err(e) => {
+                        crate::health::mark_component_error(COMPONENT, e.to_string());
+                        tracing::warn!("{e}");
+                    }
+                }
+            }
+        });
+    }
+    while let Some(result) = jobs.join_next().await {
+        result?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::UptimeKumaConfig;
+
+    #[test]
+    fn parses_push_jobs_with_defaults() {
+        let config: UptimeKumaConfig = toml::from_str(
+            r#"
+            [[push_jobs]]
+            url = "https://status.example.com/"
+            token = "abc123"
+            "#,
+        )
+        .unwrap();
+        let job = &config.push_jobs[0];
+        assert_eq!(job.interval_secs, 60);
+        assert_eq!(job.msg, "OK");
+        assert_eq!(
+            push_endpoint(job),
+            "https://status.example.com/api/push/abc123"
+        );
+    }
+
+    #[tokio::test]
+    async fn push_errors_do_not_leak_the_token() {
+        let job = UptimeKumaPushJobConfig {
+            url: "http://127.0.0.1:9".into(),
+            token: "secret-token".into(),
+            interval_secs: 60,
+            msg: "OK".into(),
+        };
+        let client = reqwest::Client::new();
+        let error = push(&client, &job).await.unwrap_err().to_string();
+        assert!(error.starts_with("Uptime Kuma push to http://127.0.0.1:9 failed"));
+        assert!(!error.contains("secret-token"));
+    }
+}
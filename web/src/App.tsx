@@ -10,6 +10,7 @@ import Memory from './pages/Memory';
 import Config from './pages/Config';
 import Cost from './pages/Cost';
 import Logs from './pages/Logs';
+import AccessLog from './pages/AccessLog';
 import Doctor from './pages/Doctor';
 import { AuthProvider, useAuth } from './hooks/useAuth';
 import { setLocale, type Locale } from './lib/i18n';
@@ -114,6 +115,7 @@ function AppContent() {
           <Route path="/config" element={<Config />} />
           <Route path="/cost" element={<Cost />} />
           <Route path="/logs" element={<Logs />} />
+          <Route path="/access-log" element={<AccessLog />} />
           <Route path="/doctor" element={<Doctor />} />
           <Route path="*" element={<Navigate to="/" replace />} />
         </Route>
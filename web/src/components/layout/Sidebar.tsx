@@ -9,6 +9,7 @@ import {
   Settings,
   DollarSign,
   Activity,
+  ScrollText,
   Stethoscope,
 } from 'lucide-react';
 import { t } from '@/lib/i18n';
@@ -23,6 +24,7 @@ const navItems = [
   { to: '/config', icon: Settings, labelKey: 'nav.config' },
   { to: '/cost', icon: DollarSign, labelKey: 'nav.cost' },
   { to: '/logs', icon: Activity, labelKey: 'nav.logs' },
+  { to: '/access-log', icon: ScrollText, labelKey: 'nav.access_log' },
   { to: '/doctor', icon: Stethoscope, labelKey: 'nav.doctor' },
 ];
 
@@ -2,12 +2,14 @@ import type {
   StatusResponse,
   ToolSpec,
   CronJob,
+  CronRunResult,
   Integration,
   DiagResult,
   MemoryEntry,
   CostSummary,
   CliTool,
   HealthSnapshot,
+  AccessLogEntry,
 } from '../types/api';
 import { clearToken, getToken, setToken } from './auth';
 
@@ -162,6 +164,20 @@ export function deleteCronJob(id: string): Promise<void> {
   });
 }
 
+export function runCronJob(
+  id: string,
+  overrides: {
+    date?: string;
+    delivery?: { mode: string; channel?: string; to?: string };
+    model?: string;
+  } = {},
+): Promise<CronRunResult> {
+  return apiFetch<CronRunResult>(`/api/cron/${encodeURIComponent(id)}/run`, {
+    method: 'POST',
+    body: JSON.stringify(overrides),
+  });
+}
+
 // ---------------------------------------------------------------------------
 // Integrations
 // ---------------------------------------------------------------------------
@@ -235,3 +251,22 @@ export function getCliTools(): Promise<CliTool[]> {
     unwrapField(data, 'cli_tools'),
   );
 }
+
+// ---------------------------------------------------------------------------
+// Access log
+// ---------------------------------------------------------------------------
+
+export function getAccessLog(params: {
+  limit?: number;
+  route?: string;
+  status?: number;
+} = {}): Promise<AccessLogEntry[]> {
+  const query = new URLSearchParams();
+  if (params.limit) query.set('limit', String(params.limit));
+  if (params.route) query.set('route', params.route);
+  if (params.status) query.set('status', String(params.status));
+  const qs = query.toString();
+  return apiFetch<AccessLogEntry[] | { entries: AccessLogEntry[] }>(
+    `/api/access-log${qs ? `?${qs}` : ''}`,
+  ).then((data) => unwrapField(data, 'entries'));
+}
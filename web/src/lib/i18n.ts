@@ -19,6 +19,7 @@ const translations: Record<Locale, Record<string, string>> = {
     'nav.config': 'Configuration',
     'nav.cost': 'Cost Tracker',
     'nav.logs': 'Logs',
+    'nav.access_log': 'Access Log',
     'nav.doctor': 'Doctor',
 
     // Dashboard
@@ -202,6 +203,7 @@ const translations: Record<Locale, Record<string, string>> = {
     'nav.config': 'Yapilandirma',
     'nav.cost': 'Maliyet Takibi',
     'nav.logs': 'Kayitlar',
+    'nav.access_log': 'Erisim Kaydi',
     'nav.doctor': 'Doktor',
 
     // Dashboard
@@ -0,0 +1,142 @@
+import { useState, useEffect } from 'react';
+import { ScrollText, RefreshCw } from 'lucide-react';
+import type { AccessLogEntry } from '@/types/api';
+import { getAccessLog } from '@/lib/api';
+
+function formatDate(iso: string): string {
+  return new Date(iso).toLocaleString();
+}
+
+function statusClass(status: number): string {
+  if (status >= 500) return 'text-red-400';
+  if (status >= 400) return 'text-yellow-400';
+  return 'text-green-400';
+}
+
+export default function AccessLog() {
+  const [entries, setEntries] = useState<AccessLogEntry[]>([]);
+  const [loading, setLoading] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+  const [route, setRoute] = useState('');
+  const [status, setStatus] = useState('');
+
+  const fetchEntries = () => {
+    setLoading(true);
+    getAccessLog({
+      limit: 200,
+      route: route.trim() || undefined,
+      status: status.trim() ? Number(status) : undefined,
+    })
+      .then(setEntries)
+      .catch((err) => setError(err.message))
+      .finally(() => setLoading(false));
+  };
+
+  useEffect(() => {
+    fetchEntries();
+  }, []);
+
+  if (error) {
+    return (
+      <div className="p-6">
+        <div className="rounded-lg bg-red-900/30 border border-red-700 p-4 text-red-300">
+          Failed to load access log: {error}
+        </div>
+      </div>
+    );
+  }
+
+  return (
+    <div className="p-6 space-y-6">
+      {/* Header */}
+      <div className="flex items-center justify-between gap-4">
+        <div className="flex items-center gap-2">
+          <ScrollText className="h-5 w-5 text-blue-400" />
+          <h2 className="text-base font-semibold text-white">
+            Access Log ({entries.length})
+          </h2>
+        </div>
+        <div className="flex items-center gap-2">
+          <input
+            type="text"
+            value={route}
+            onChange={(e) => setRoute(e.target.value)}
+            placeholder="Route, e.g. /webhook"
+            className="bg-gray-800 border border-gray-700 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:border-blue-500"
+          />
+          <input
+            type="text"
+            value={status}
+            onChange={(e) => setStatus(e.target.value.replace(/\D/g, ''))}
+            placeholder="Status"
+            className="w-24 bg-gray-800 border border-gray-700 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:border-blue-500"
+          />
+          <button
+            onClick={fetchEntries}
+            className="flex items-center gap-2 bg-blue-600 hover:bg-blue-700 text-white text-sm font-medium px-4 py-2 rounded-lg transition-colors"
+          >
+            <RefreshCw className="h-4 w-4" />
+            Refresh
+          </button>
+        </div>
+      </div>
+
+      {loading ? (
+        <div className="flex items-center justify-center h-64">
+          <div className="animate-spin rounded-full h-8 w-8 border-2 border-blue-500 border-t-transparent" />
+        </div>
+      ) : entries.length === 0 ? (
+        <div className="bg-gray-900 rounded-xl border border-gray-800 p-8 text-center">
+          <ScrollText className="h-10 w-10 text-gray-600 mx-auto mb-3" />
+          <p className="text-gray-400">No requests recorded.</p>
+        </div>
+      ) : (
+        <div className="bg-gray-900 rounded-xl border border-gray-800 overflow-x-auto">
+          <table className="w-full text-sm">
+            <thead>
+              <tr className="border-b border-gray-800">
+                <th className="text-left px-4 py-3 text-gray-400 font-medium">Time</th>
+                <th className="text-left px-4 py-3 text-gray-400 font-medium">Method</th>
+                <th className="text-left px-4 py-3 text-gray-400 font-medium">Route</th>
+                <th className="text-left px-4 py-3 text-gray-400 font-medium">Status</th>
+                <th className="text-left px-4 py-3 text-gray-400 font-medium">Latency</th>
+                <th className="text-left px-4 py-3 text-gray-400 font-medium">Token</th>
+                <th className="text-left px-4 py-3 text-gray-400 font-medium">Body</th>
+              </tr>
+            </thead>
+            <tbody>
+              {entries.map((entry) => (
+                <tr
+                  key={entry.id}
+                  className="border-b border-gray-800/50 hover:bg-gray-800/30 transition-colors"
+                >
+                  <td className="px-4 py-3 text-gray-400 text-xs">
+                    {formatDate(entry.timestamp)}
+                  </td>
+                  <td className="px-4 py-3 text-gray-300 font-mono text-xs">
+                    {entry.method}
+                  </td>
+                  <td className="px-4 py-3 text-white font-mono text-xs">
+                    {entry.route}
+                  </td>
+                  <td className={`px-4 py-3 font-mono text-xs ${statusClass(entry.status)}`}>
+                    {entry.status}
+                  </td>
+                  <td className="px-4 py-3 text-gray-400 text-xs">
+                    {entry.latency_ms} ms
+                  </td>
+                  <td className="px-4 py-3 text-gray-300 text-xs">
+                    {entry.token_scope}
+                  </td>
+                  <td className="px-4 py-3 text-gray-400 font-mono text-xs max-w-[280px] truncate">
+                    {entry.body ?? (entry.body_bytes > 0 ? `${entry.body_bytes} bytes` : '-')}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,6 +1,7 @@
 import { useState, useEffect } from 'react';
 import {
   Clock,
+  Play,
   Plus,
   Trash2,
   X,
@@ -9,7 +10,7 @@ import {
   AlertCircle,
 } from 'lucide-react';
 import type { CronJob } from '@/types/api';
-import { getCronJobs, addCronJob, deleteCronJob } from '@/lib/api';
+import { getCronJobs, addCronJob, deleteCronJob, runCronJob } from '@/lib/api';
 
 function formatDate(iso: string | null): string {
   if (!iso) return '-';
@@ -23,6 +24,7 @@ export default function Cron() {
   const [error, setError] = useState<string | null>(null);
   const [showForm, setShowForm] = useState(false);
   const [confirmDelete, setConfirmDelete] = useState<string | null>(null);
+  const [running, setRunning] = useState<string | null>(null);
 
   // Form state
   const [formName, setFormName] = useState('');
@@ -79,6 +81,18 @@ export default function Cron() {
     }
   };
 
+  const handleRun = async (id: string) => {
+    setRunning(id);
+    try {
+      await runCronJob(id);
+      fetchJobs();
+    } catch (err: unknown) {
+      setError(err instanceof Error ? err.message : 'Failed to run job');
+    } finally {
+      setRunning(null);
+    }
+  };
+
   const statusIcon = (status: string | null) => {
     if (!status) return null;
     switch (status.toLowerCase()) {
@@ -303,12 +317,22 @@ export default function Cron() {
                         </button>
                       </div>
                     ) : (
-                      <button
-                        onClick={() => setConfirmDelete(job.id)}
-                        className="text-gray-400 hover:text-red-400 transition-colors"
-                      >
-                        <Trash2 className="h-4 w-4" />
-                      </button>
+                      <div className="flex items-center justify-end gap-3">
+                        <button
+                          onClick={() => handleRun(job.id)}
+                          disabled={running === job.id}
+                          title="Run now"
+                          className="text-gray-400 hover:text-green-400 transition-colors disabled:opacity-50"
+                        >
+                          <Play className="h-4 w-4" />
+                        </button>
+                        <button
+                          onClick={() => setConfirmDelete(job.id)}
+                          className="text-gray-400 hover:text-red-400 transition-colors"
+                        >
+                          <Trash2 className="h-4 w-4" />
+                        </button>
+                      </div>
                     )}
                   </td>
                 </tr>
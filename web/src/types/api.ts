@@ -42,6 +42,14 @@ export interface CronJob {
   enabled: boolean;
 }
 
+export interface CronRunResult {
+  status: 'ok' | 'error';
+  run_id: number | null;
+  invocation: 'manual';
+  duration_ms: number;
+  output: string;
+}
+
 export interface Integration {
   name: string;
   description: string;
@@ -88,6 +96,18 @@ export interface CliTool {
   category: string;
 }
 
+export interface AccessLogEntry {
+  id: number;
+  timestamp: string;
+  method: string;
+  route: string;
+  status: number;
+  latency_ms: number;
+  token_scope: string;
+  body_bytes: number;
+  body: string | null;
+}
+
 export interface SSEEvent {
   type: string;
   timestamp?: string;